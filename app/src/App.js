@@ -1,4 +1,4 @@
-import React, { useEffect, useState } from "react";
+import React, { useEffect, useRef, useState } from "react";
 import "./App.css";
 import "./styles/toast.css";
 import toast, { Toaster } from "react-hot-toast";
@@ -10,6 +10,7 @@ import {
   getPlayer,
   getAllGames,
   getAvailableGames,
+  getLiveGames,
   joinGame,
   joinAsSpectator,
   createGame,
@@ -20,50 +21,113 @@ import {
   startGame,
   leaveCurrentGame,
   getGameState,
+  getChainClockOffset,
+  getCluster,
+  findCluster,
 } from "./solana-bridge";
-import { initGameBridge, onGameMessage } from "./game-bridge";
+import {
+  initGameBridge,
+  onGameMessage,
+  DEFAULT_AUDIO_VOLUMES,
+  DEFAULT_COMFORT_SETTINGS,
+  DEFAULT_VIEWMODEL_SETTINGS,
+  DEFAULT_VIEW_DISTANCE,
+} from "./game-bridge";
 import EphemeralWalletPanel from "./components/EphemeralWalletPanel";
 import LobbyBrowser from "./components/LobbyBrowser";
 import LobbyRoom from "./components/LobbyRoom";
 import Minimap from "./components/Minimap";
 import MatchStatus from "./components/MatchStatus";
+import KillFeed from "./components/KillFeed";
+import HudSlot from "./components/HudSlot";
+import HudLayoutEditor from "./components/HudLayoutEditor";
 import RespawnOverlay from "./components/RespawnOverlay";
 import VirtualJoystick from "./components/VirtualJoystick";
 import VictoryDialog from "./components/VictoryDialog";
 import PauseMenu from "./components/PauseMenu";
 import SettingsPanel from "./components/SettingsPanel";
 import DebugConsole from "./components/DebugConsole";
+import ActivityPanel from "./components/ActivityPanel";
+import LoadoutsPanel from "./components/LoadoutsPanel";
 import LatencyDisplay from "./components/LatencyDisplay";
-
-// NOTE: This app is configured to connect to Solana LOCALNET only
-// RPC URL is hardcoded to http://127.0.0.1:8899 in solana-bridge.js
+import PartyWidget from "./components/PartyWidget";
+import PlayerName from "./components/PlayerName";
+import StartCountdown from "./components/StartCountdown";
+import Scoreboard from "./components/Scoreboard";
+import PracticeSetup from "./components/PracticeSetup";
+import { normalizeClanTag, validateTaggedName } from "./utils/clan-tag";
+import { loadMutedPlayers, toggleMute } from "./utils/chat-filter";
+import { playStartTime, toLocalMs } from "./utils/match-start";
+import { BridgeErrorKind, bridgeErrorMessage, toBridgeError } from "./utils/bridge-error";
+import { loadLoadouts, resolveLoadout } from "./utils/loadouts";
+import { loadHudLayout, saveHudLayout } from "./utils/hud-layout";
+import { promptCrashReport } from "./utils/crash-report";
+import {
+  MAX_PARTY_SIZE,
+  PARTY_DISBAND,
+  PARTY_HELLO,
+  PARTY_JOIN,
+  PARTY_LEAVE,
+  PARTY_ROSTER,
+  createPartyId,
+  getPartyKey,
+  normalizePartyId,
+  openPartyChannel,
+  partyFitsTogether,
+  pickQuickPlayLobby,
+} from "./utils/party";
+
+// NOTE: The cluster (localnet by default, REACT_APP_SOLANA_CLUSTER to change it)
+// and its endpoints are configured in solana-bridge.js and switchable in Settings
 
 import * as solanaBridge from "./solana-bridge";
+import * as mapLibrary from "./map-library";
 
 // Polyfill Buffer for browser environment (required by Solana/Anchor)
 import { Buffer } from "buffer";
 window.Buffer = Buffer;
 
+// Classifies caught errors into tagged bridge errors for the Rust menu (see utils/bridge-error.js)
+window.toBridgeError = toBridgeError;
+
 // Expose Solana bridge globally for Rust/Emscripten to access
 window.solanaMapBridge = {
   createMap: solanaBridge.createMap,
   getUserMaps: solanaBridge.getUserMaps,
   getMapData: solanaBridge.getMapData,
   getMapMetadata: solanaBridge.getMapMetadata,
+  getMapPlayCounts: solanaBridge.getMapPlayCounts,
+  updateMapData: solanaBridge.updateMapData,
+  updateMapMetadata: solanaBridge.updateMapMetadata,
+  getMapFingerprint: solanaBridge.getMapFingerprint,
+  getLobbiesUsingMap: solanaBridge.getLobbiesUsingMap,
+  deleteMap: solanaBridge.deleteMap,
 };
 
+// Local map library (IndexedDB) for the editor's Save/Load dialog
+window.mapLibrary = mapLibrary;
+
 function App() {
   const [solanaReady, setSolanaReady] = useState(false);
   const [gameReady, setGameReady] = useState(false);
   const [walletConnected, setWalletConnected] = useState(false);
   const [walletAddress, setWalletAddress] = useState("");
   const [balance, setBalance] = useState(0);
+  const [cluster, setCluster] = useState(getCluster);
   const [gameMessages, setGameMessages] = useState([]);
   const [playerInitialized, setPlayerInitialized] = useState(false);
   const [playerData, setPlayerData] = useState(null);
   const [playerUsername, setPlayerUsername] = useState("");
+  const [playerClanTag, setPlayerClanTag] = useState("");
   const [games, setGames] = useState([]);
   const [gamesLoading, setGamesLoading] = useState(false);
+  const [liveGames, setLiveGames] = useState([]);
+  const [spectatingGame, setSpectatingGame] = useState(null); // Live match being watched (read-only)
+  const [offlineMode, setOfflineMode] = useState(null); // "tutorial" | "range" | "practice" while an offline session runs
+  const [practiceSetupOpen, setPracticeSetupOpen] = useState(false);
+  const [tutorialCompleted, setTutorialCompleted] = useState(
+    () => localStorage.getItem("tutorialCompleted") === "true"
+  );
   const [showGameBrowser, setShowGameBrowser] = useState(false); // Don't show lobby by default
 
   // Lobby state
@@ -71,12 +135,26 @@ function App() {
   const [currentLobbyData, setCurrentLobbyData] = useState(null);
   const [playerReady, setPlayerReady] = useState(false);
   const [isLobbyLeader, setIsLobbyLeader] = useState(false);
+  const [botBackfill, setBotBackfill] = useState(false); // Leader fills empty slots with bots
+  const botBackfillRef = useRef(false);
+  botBackfillRef.current = isLobbyLeader && botBackfill;
+
+  // Party state ({ id, leader, members }; leader is null while waiting to be let in)
+  const [party, setParty] = useState(null);
+  const partyRef = useRef(null);
+  const partyChannelRef = useRef(null);
+  const joinRoomRef = useRef(null);
+  partyRef.current = party;
+  const partyName = playerData?.username || walletAddress.slice(0, 8);
+  const partyNameRef = useRef(partyName);
+  partyNameRef.current = partyName;
 
   // Tab navigation state
-  const [activeTab, setActiveTab] = useState("lobby"); // 'lobby', 'store', 'mapeditor'
+  const [activeTab, setActiveTab] = useState("lobby"); // 'lobby', 'weapons', 'store', 'mapeditor'
 
   // Game state tracking
   const [currentGameState, setCurrentGameState] = useState(null); // 0=waiting, 1=active, 2=ended, 3=paused
+  const [playStartAtMs, setPlayStartAtMs] = useState(null); // Local time every client enters the match
   const [isFullscreen, setIsFullscreen] = useState(false);
 
   // Victory dialog state
@@ -91,6 +169,7 @@ function App() {
   const [isReloading, setIsReloading] = useState(false);
 
   const [settingsOpen, setSettingsOpen] = useState(false);
+  const [pausePlayers, setPausePlayers] = useState([]); // Kick vote candidates shown in the pause menu
   const [sensitivity, setSensitivity] = useState(() => {
     const stored = localStorage.getItem("sensitivity");
     return stored ? parseFloat(stored) : 1.0;
@@ -99,6 +178,67 @@ function App() {
     const stored = localStorage.getItem("musicEnabled");
     return stored !== null ? stored === "true" : true;
   });
+  const [audioVolumes, setAudioVolumes] = useState(() => {
+    try {
+      const stored = JSON.parse(localStorage.getItem("audioVolumes") || "{}");
+      return { ...DEFAULT_AUDIO_VOLUMES, ...stored };
+    } catch (_) {
+      return { ...DEFAULT_AUDIO_VOLUMES };
+    }
+  });
+  const [uiSoundsEnabled, setUiSoundsEnabled] = useState(() => {
+    const stored = localStorage.getItem("uiSoundsEnabled");
+    return stored !== null ? stored === "true" : true;
+  });
+  const [comfortSettings, setComfortSettings] = useState(() => {
+    try {
+      const stored = JSON.parse(localStorage.getItem("comfortSettings") || "{}");
+      return { ...DEFAULT_COMFORT_SETTINGS, ...stored };
+    } catch (_) {
+      return { ...DEFAULT_COMFORT_SETTINGS };
+    }
+  });
+  const [viewmodelSettings, setViewmodelSettings] = useState(() => {
+    try {
+      const stored = JSON.parse(localStorage.getItem("viewmodelSettings") || "{}");
+      return { ...DEFAULT_VIEWMODEL_SETTINGS, ...stored };
+    } catch (_) {
+      return { ...DEFAULT_VIEWMODEL_SETTINGS };
+    }
+  });
+  const [colorblindMode, setColorblindMode] = useState(() => {
+    return localStorage.getItem("colorblindMode") || "off";
+  });
+  const [language, setLanguage] = useState(() => {
+    return localStorage.getItem("language") || "en";
+  });
+  const [renderMode, setRenderMode] = useState(() => {
+    return localStorage.getItem("renderMode") || "full";
+  });
+  const [viewDistance, setViewDistance] = useState(() => {
+    try {
+      const stored = JSON.parse(localStorage.getItem("viewDistance") || "{}");
+      return { ...DEFAULT_VIEW_DISTANCE, ...stored };
+    } catch (_) {
+      return { ...DEFAULT_VIEW_DISTANCE };
+    }
+  });
+  const [filmEffects, setFilmEffects] = useState(() => {
+    return localStorage.getItem("filmEffects") === "true";
+  });
+  const [streamerSafe, setStreamerSafe] = useState(() => {
+    return localStorage.getItem("streamerSafe") === "true";
+  });
+  const [exportMatchEvents, setExportMatchEvents] = useState(() => {
+    return localStorage.getItem("exportMatchEvents") === "true";
+  });
+  const [profanityFilter, setProfanityFilter] = useState(() => {
+    const stored = localStorage.getItem("profanityFilter");
+    return stored !== null ? stored === "true" : true;
+  });
+  const [mutedPlayers, setMutedPlayers] = useState(loadMutedPlayers);
+  const [hudLayout, setHudLayout] = useState(loadHudLayout);
+  const [hudEditorOpen, setHudEditorOpen] = useState(false);
 
   // Save settings to localStorage/global on change
   useEffect(() => {
@@ -112,6 +252,175 @@ function App() {
     if (typeof window.toggleMusic === "function")
       window.toggleMusic(musicEnabled);
   }, [musicEnabled]);
+  useEffect(() => {
+    // Persisted and pushed into the Rust mixer by the game bridge
+    Object.entries(audioVolumes).forEach(([channel, volume]) =>
+      window.gameBridge?.setAudioVolume?.(channel, volume)
+    );
+  }, [audioVolumes, gameReady]);
+  useEffect(() => {
+    window.gameBridge?.setUiSoundsEnabled?.(uiSoundsEnabled);
+  }, [uiSoundsEnabled, gameReady]);
+  useEffect(() => {
+    localStorage.setItem("colorblindMode", colorblindMode);
+    // Remaps team colors, hit effects, minimap markers and HUD accents in the game
+    window.gameBridge?.setColorblindMode?.(colorblindMode);
+  }, [colorblindMode, gameReady]);
+  useEffect(() => {
+    localStorage.setItem("comfortSettings", JSON.stringify(comfortSettings));
+    // Scales screen shake, flash overlays and viewmodel bob in the game
+    Object.entries(comfortSettings).forEach(([effect, setting]) =>
+      window.gameBridge?.setComfortSetting?.(effect, setting)
+    );
+  }, [comfortSettings, gameReady]);
+  useEffect(() => {
+    localStorage.setItem("viewmodelSettings", JSON.stringify(viewmodelSettings));
+    // Mirrors / moves / rescales the first-person gun in the game
+    window.gameBridge?.setViewmodel?.(viewmodelSettings);
+  }, [viewmodelSettings, gameReady]);
+  useEffect(() => {
+    localStorage.setItem("language", language);
+    // HUD prompts, announcements and map editor text
+    window.gameBridge?.setLanguage?.(language);
+  }, [language, gameReady]);
+  useEffect(() => {
+    localStorage.setItem("renderMode", renderMode);
+    // Retro raycaster instead of the 3D world on devices that can't hold 30 fps
+    window.gameBridge?.setRenderMode?.(renderMode);
+  }, [renderMode, gameReady]);
+  useEffect(() => {
+    // The lobby shows the game canvas behind its panels; other tabs ignore the id
+    window.gameBridge?.setMenuTab?.(activeTab);
+  }, [activeTab, gameReady]);
+  useEffect(() => {
+    localStorage.setItem("viewDistance", JSON.stringify(viewDistance));
+    // Shorter draw distance skips far map geometry; fog fades it into the sky instead of popping
+    window.gameBridge?.setViewDistance?.(viewDistance);
+  }, [viewDistance, gameReady]);
+  useEffect(() => {
+    localStorage.setItem("filmEffects", filmEffects.toString());
+    // Grain and color fringing over the 3D world
+    window.gameBridge?.setFilmEffects?.(filmEffects);
+  }, [filmEffects, gameReady]);
+  useEffect(() => {
+    saveHudLayout(hudLayout);
+    // The game draws the health bar; the web HUD below places the rest
+    window.gameBridge?.setHudLayout?.(hudLayout);
+  }, [hudLayout, gameReady]);
+
+  // Menu click/hover sounds for every button (routed through the game's UI channel)
+  useEffect(() => {
+    let hoveredButton = null;
+    const onClick = (evt) => {
+      if (evt.target.closest?.("button")) {
+        window.gameBridge?.playUiSound?.("click");
+      }
+    };
+    const onMouseOver = (evt) => {
+      const button = evt.target.closest?.("button");
+      if (button && button !== hoveredButton && !button.disabled) {
+        window.gameBridge?.playUiSound?.("hover");
+      }
+      hoveredButton = button;
+    };
+    document.addEventListener("click", onClick, true);
+    document.addEventListener("mouseover", onMouseOver, true);
+    return () => {
+      document.removeEventListener("click", onClick, true);
+      document.removeEventListener("mouseover", onMouseOver, true);
+    };
+  }, []);
+  useEffect(() => {
+    localStorage.setItem("streamerSafe", streamerSafe.toString());
+    // Polled by the game loop: swaps blood hit effects for neutral sparks
+    window.__streamer_safe = streamerSafe;
+  }, [streamerSafe]);
+  useEffect(() => {
+    localStorage.setItem("exportMatchEvents", exportMatchEvents.toString());
+    // Polled by the game loop: downloads the match event log (JSON + CSV) at match end
+    window.__export_match_events = exportMatchEvents;
+  }, [exportMatchEvents]);
+  useEffect(() => {
+    localStorage.setItem("profanityFilter", profanityFilter.toString());
+  }, [profanityFilter]);
+  useEffect(() => {
+    localStorage.setItem("mutedPlayers", JSON.stringify(mutedPlayers));
+    // Checked by the game bridge: pings/voice-lines from muted players are dropped
+    window.__muted_players = mutedPlayers;
+  }, [mutedPlayers]);
+
+  // Party relay: the leader owns the roster and tells members which lobby to join.
+  // `from` is the sender's relay key: the leader binds each member name to the key
+  // that asked for it, and members only take orders from the leader's key.
+  useEffect(() => {
+    if (!party?.id) return;
+    const me = partyNameRef.current;
+    const channel = openPartyChannel(party.id, (message, from) => {
+      const current = partyRef.current;
+      if (!current) return;
+      const isLeader = current.leader === partyNameRef.current;
+      // A joiner learns the leader's key from the first roster it gets
+      const fromLeader = current.leaderKey ? from === current.leaderKey : message.type === PARTY_ROSTER;
+
+      if (isLeader && message.type === PARTY_HELLO) {
+        const claimed = current.keys[message.member];
+        const named = Object.values(current.keys).includes(from);
+        if (typeof message.member !== "string" || (claimed ? claimed !== from : named)) return;
+        const full = current.members.length >= MAX_PARTY_SIZE;
+        const joins = !full && !current.members.includes(message.member);
+        const members = joins ? [...current.members, message.member] : current.members;
+        const keys = joins ? { ...current.keys, [message.member]: from } : current.keys;
+        setParty({ ...current, members, keys });
+        channel.post({ type: PARTY_ROSTER, leader: current.leader, members });
+      } else if (isLeader && message.type === PARTY_LEAVE) {
+        const member = Object.keys(current.keys).find((name) => current.keys[name] === from);
+        if (!member || member === current.leader) return;
+        const members = current.members.filter((m) => m !== member);
+        const { [member]: _, ...keys } = current.keys;
+        setParty({ ...current, members, keys });
+        channel.post({ type: PARTY_ROSTER, leader: current.leader, members });
+      } else if (!fromLeader) {
+        return;
+      } else if (!isLeader && message.type === PARTY_ROSTER) {
+        if (message.members.includes(partyNameRef.current)) {
+          setParty({ ...current, leader: message.leader, leaderKey: from, members: message.members });
+        } else if (!current.leader) {
+          toast.error("That party is full");
+          setParty(null);
+        }
+      } else if (!isLeader && message.type === PARTY_DISBAND) {
+        toast("The party leader disbanded the party");
+        setParty(null);
+      } else if (!isLeader && message.type === PARTY_JOIN) {
+        console.log(`👥 Following party leader into lobby ${message.gamePubkey}`);
+        joinRoomRef.current?.(message.gamePubkey, { followingParty: true });
+      }
+    });
+    if (!channel) {
+      toast.error("Parties are not supported in this browser");
+      setParty(null);
+      return;
+    }
+    partyChannelRef.current = channel;
+
+    // Joining by code: ask the leader to let us in, give up if nobody answers
+    let notFoundTimer = null;
+    if (!partyRef.current?.leader) {
+      channel.post({ type: PARTY_HELLO, member: me });
+      notFoundTimer = setTimeout(() => {
+        if (partyRef.current && !partyRef.current.leader) {
+          toast.error("Party not found");
+          setParty(null);
+        }
+      }, 5000);
+    }
+
+    return () => {
+      clearTimeout(notFoundTimer);
+      channel.close();
+      partyChannelRef.current = null;
+    };
+  }, [party?.id]);
 
   // Settings panel toggle by 'M' key
   useEffect(() => {
@@ -231,6 +540,20 @@ function App() {
     });
   }, []);
 
+  // Cluster switched from the settings panel: balances and lobbies are per cluster
+  useEffect(() => {
+    const handleClusterChanged = (event) => {
+      setCluster(event.detail.cluster);
+      setGames([]);
+      if (walletConnected) {
+        getBalance().then(setBalance);
+        loadGames();
+      }
+    };
+    window.addEventListener("clusterChanged", handleClusterChanged);
+    return () => window.removeEventListener("clusterChanged", handleClusterChanged);
+  }, [walletConnected]);
+
   // Auto-refresh games when game browser is open
   useEffect(() => {
     if (!showGameBrowser || !walletConnected) return;
@@ -273,6 +596,7 @@ function App() {
               setPlayerReady(false);
               setIsLobbyLeader(false);
               setCurrentGameState(null);
+              setPlayStartAtMs(null);
 
               // Disconnect WebSocket if connected
               try {
@@ -370,6 +694,15 @@ function App() {
             // Switch to map editor tab (where game canvas is)
             setActiveTab("mapeditor");
 
+            // Everyone enters Playing at the on-chain match start + start countdown,
+            // not whenever their poll happened to notice the state change
+            const clockOffset = await getChainClockOffset();
+            const startedGame = await getGame(currentLobbyData.gamePublicKey);
+            const matchStart = Number(startedGame?.matchStartTimestamp?.toString() || 0);
+            const playAtMs = matchStart ? toLocalMs(playStartTime(matchStart), clockOffset) : Date.now();
+            setPlayStartAtMs(playAtMs);
+            console.log(`⏱️ Entering match in ${((playAtMs - Date.now()) / 1000).toFixed(1)}s`);
+
             // Set the current game in Raylib for multiplayer sync
             if (
               window.gameBridge &&
@@ -378,6 +711,27 @@ function App() {
             ) {
               window.gameBridge.setCurrentGame(currentLobbyData.gamePublicKey);
               console.log("✅ Set current game pubkey in Raylib");
+
+              // Spawn with the loadout preset picked for this map / mode
+              const loadout = resolveLoadout(loadLoadouts(), {
+                mapId: currentLobbyData.mapName,
+                // Lobbies are always team deathmatch for now
+                mode: "team_deathmatch",
+              });
+              window.gameBridge.setLoadout?.(loadout.weapons, loadout.primary);
+              console.log("🔫 Loadout:", loadout.name);
+
+              // Line the freeze countdown up with the synchronized entry time
+              window.gameBridge.syncRoundStart?.(currentLobbyData.gamePublicKey, clockOffset);
+
+              // Backfill bots run on the leader's client, in the slots nobody took
+              if (botBackfillRef.current) {
+                const slotsPerTeam = Math.floor((currentLobbyData.maxPlayers || 10) / 2);
+                const openA = Math.max(0, slotsPerTeam - (currentLobbyData.teamA?.length || 0));
+                const openB = Math.max(0, slotsPerTeam - (currentLobbyData.teamB?.length || 0));
+                window.gameBridge.setBotBackfill?.(openA, openB);
+                console.log(`🤖 Bot backfill: ${openA} + ${openB} bots`);
+              }
             }
 
             // Initialize WebSocket connection and subscribe to game players
@@ -497,6 +851,12 @@ function App() {
                   console.warn("⚠️ [STEP 3] gameBridge or getMapDataById not available for map loading");
                 }
 
+                // Hold until the synchronized start (late clients go straight in)
+                const untilPlay = playAtMs - Date.now();
+                if (untilPlay > 0) {
+                  await new Promise((resolve) => setTimeout(resolve, untilPlay));
+                }
+
                 // Now that everything is set up, tell Raylib game to switch to playing mode
                 if (window.gameBridge && window.gameBridge.startGameMode) {
                   window.gameBridge.startGameMode();
@@ -888,6 +1248,9 @@ function App() {
                 if (window.gameBridge && window.gameBridge.setCurrentGame) {
                   window.gameBridge.setCurrentGame(currentGamePubkey);
                   console.log("✅ Set current game pubkey in Raylib");
+
+                  // Rejoining mid-match skips whatever is left of the freeze
+                  window.gameBridge.syncRoundStart?.(currentGamePubkey);
                 }
 
                 // Connect WebSocket
@@ -973,10 +1336,16 @@ function App() {
       alert("Please enter a username");
       return;
     }
+    const clanTag = normalizeClanTag(playerClanTag);
+    const tagError = validateTaggedName(clanTag, playerUsername.trim());
+    if (tagError) {
+      alert(tagError);
+      return;
+    }
 
     try {
-      console.log("🎮 Initializing player:", playerUsername);
-      const result = await initPlayer(playerUsername);
+      console.log("🎮 Initializing player:", playerUsername, clanTag ? `[${clanTag}]` : "");
+      const result = await initPlayer(playerUsername.trim(), clanTag);
       if (result) {
         setPlayerInitialized(true);
         console.log("✅ Player initialized successfully:", result);
@@ -1015,8 +1384,12 @@ function App() {
     setGamesLoading(true);
     try {
       console.log("🎮 Loading available games...");
-      const availableGames = await getAvailableGames();
+      const [availableGames, activeGames] = await Promise.all([
+        getAvailableGames(),
+        getLiveGames(),
+      ]);
       setGames(availableGames);
+      setLiveGames(activeGames);
       console.log(`✅ Loaded ${availableGames.length} games`);
     } catch (error) {
       console.error("❌ Error loading games:", error);
@@ -1169,19 +1542,36 @@ function App() {
     }
   };
 
-  const handleJoinRoom = async (gamePublicKey) => {
+  const handleJoinRoom = async (
+    gamePublicKey,
+    { followingParty = false, game = null } = {}
+  ) => {
     if (!playerInitialized) {
       alert("Please initialize your player first");
       return;
     }
 
+    // Party members follow the leader; the leader only joins where the whole party fits on one team
+    const leadingParty = !followingParty && party?.members.length > 1;
+    if (party && !followingParty && party.leader !== partyName) {
+      toast.error("Only the party leader can join lobbies");
+      return;
+    }
+    if (leadingParty) {
+      const target = game || games.find((g) => g.publicKey === gamePublicKey);
+      if (target && !partyFitsTogether(target, party.members.length)) {
+        toast.error("Your party can't fit on one team in this lobby");
+        return;
+      }
+    }
+
     try {
       console.log(`🎮 Joining room: ${gamePublicKey}`);
       const result = await joinGame(gamePublicKey);
 
       console.log("🔍 Join game result:", result);
 
-      if (result?.error === "PlayerAlreadyInGame") {
+      if (result?.error?.kind === BridgeErrorKind.PlayerAlreadyInGame) {
         console.warn("⚠️ Player already in game:", result.currentGame);
         alert(
           `You are already in a game (${result.currentGame}). Please leave that game first.`
@@ -1195,6 +1585,10 @@ function App() {
           result.transaction
         );
 
+        if (leadingParty) {
+          partyChannelRef.current?.post({ type: PARTY_JOIN, gamePubkey: gamePublicKey });
+        }
+
         // Wait a moment for transaction to be confirmed
         await new Promise((resolve) => setTimeout(resolve, 2000));
 
@@ -1257,6 +1651,111 @@ function App() {
     }
   };
 
+  joinRoomRef.current = handleJoinRoom;
+
+  // Join the fullest open lobby the party (or solo player) fits into together
+  const handleQuickPlay = async () => {
+    const size = party?.members.length || 1;
+    const availableGames = await getAvailableGames();
+    setGames(availableGames);
+    const lobby = pickQuickPlayLobby(availableGames, size);
+    if (!lobby) {
+      toast.error(
+        size > 1
+          ? `No open lobby has room for your party of ${size} on one team`
+          : "No open lobbies - create a room instead"
+      );
+      return;
+    }
+    console.log(`⚡ Quick Play: joining ${lobby.publicKey} (party of ${size})`);
+    await handleJoinRoom(lobby.publicKey, { game: lobby });
+  };
+
+  const handleCreateParty = () => {
+    const key = getPartyKey();
+    setParty({ id: createPartyId(), leader: partyName, leaderKey: key, members: [partyName], keys: { [partyName]: key } });
+  };
+
+  const handleJoinParty = (code) => {
+    const id = normalizePartyId(code);
+    if (id) setParty({ id, leader: null, leaderKey: null, members: [], keys: {} });
+  };
+
+  const handleLeaveParty = () => {
+    const isLeader = party?.leader === partyName;
+    partyChannelRef.current?.post(
+      isLeader ? { type: PARTY_DISBAND } : { type: PARTY_LEAVE }
+    );
+    setParty(null);
+  };
+
+  // Watch a match already in progress without touching the player account
+  const handleSpectateLive = async (game) => {
+    const gamePublicKey = game.publicKey || game.id;
+    console.log(`📺 Spectating live match: ${gamePublicKey}`);
+
+    setActiveTab("mapeditor"); // Show the game canvas
+    const result = await window.gameBridge?.startSpectating?.(
+      gamePublicKey,
+      game.mapId || game.map_id
+    );
+    if (!result?.success) {
+      console.error("❌ Failed to start spectating:", result?.error);
+      toast.error("Could not spectate this match");
+      setActiveTab("lobby");
+      return;
+    }
+
+    setSpectatingGame(gamePublicKey);
+    enterFullscreen();
+  };
+
+  // Offline sessions: onboarding mission, shooting range or practice match (no wallet or match needed)
+  const handleStartOfflineMode = (mode, practiceSetup) => {
+    setActiveTab("mapeditor"); // Show the game canvas
+    const result =
+      mode === "tutorial"
+        ? window.gameBridge?.startTutorial?.()
+        : mode === "practice"
+          ? window.gameBridge?.startPracticeMatch?.(practiceSetup)
+          : window.gameBridge?.startShootingRange?.();
+    if (!result?.success) {
+      toast.error("The game is still loading, try again in a moment");
+      setActiveTab("lobby");
+      return;
+    }
+    setOfflineMode(mode);
+    enterFullscreen();
+  };
+
+  const handleStopOfflineMode = () => {
+    window.gameBridge?.stopOfflineMode?.();
+    setOfflineMode(null);
+    setActiveTab("lobby");
+  };
+
+  // The game reports completion once the player confirms the last step
+  useEffect(() => {
+    const onComplete = () => {
+      localStorage.setItem("tutorialCompleted", "true");
+      setTutorialCompleted(true);
+      window.gameBridge?.stopOfflineMode?.();
+      setOfflineMode(null);
+      setActiveTab("lobby");
+      toast.success("Training complete - you're ready for a real match!");
+    };
+    window.addEventListener("tutorialComplete", onComplete);
+    return () => window.removeEventListener("tutorialComplete", onComplete);
+  }, []);
+
+  const handleStopSpectating = async () => {
+    console.log(`📺 Stopped spectating: ${spectatingGame}`);
+    await window.gameBridge?.stopSpectating?.(spectatingGame);
+    setSpectatingGame(null);
+    setActiveTab("lobby");
+    loadGames();
+  };
+
   const handleJoinAsSpectator = async (gamePublicKey) => {
     if (!playerInitialized) {
       alert("Please initialize your player first");
@@ -1269,7 +1768,7 @@ function App() {
 
       console.log("🔍 Join as spectator result:", result);
 
-      if (result?.error === "PlayerAlreadyInGame") {
+      if (result?.error?.kind === BridgeErrorKind.PlayerAlreadyInGame) {
         console.warn("⚠️ Player already in game:", result.currentGame);
         alert(
           `You are already in a game (${result.currentGame}). Please leave that game first.`
@@ -1425,7 +1924,7 @@ function App() {
         setIsLobbyLeader(false);
         // Refresh games list
         await loadGames();
-      } else if (result?.error === "NotInGame") {
+      } else if (result?.error?.kind === BridgeErrorKind.NotInGame) {
         // Player is not in a game, just clear local state
         console.log("ℹ️ Not in a game, clearing local state");
         setInLobby(false);
@@ -1438,7 +1937,7 @@ function App() {
       }
     } catch (error) {
       console.error("❌ Error leaving lobby:", error);
-      alert("Error leaving lobby: " + error.message);
+      alert("Error leaving lobby: " + bridgeErrorMessage(error));
     }
   };
 
@@ -1463,13 +1962,14 @@ function App() {
       // Leave the game on blockchain
       const result = await leaveCurrentGame();
 
-      if (result || result?.error === "NotInGame") {
+      if (result || result?.error?.kind === BridgeErrorKind.NotInGame) {
         console.log("✅ Left game successfully");
       }
 
       // Exit fullscreen and return to lobby browser
       exitFullscreen();
       setCurrentGameState(null);
+      setPlayStartAtMs(null);
       setInLobby(false);
       setCurrentLobbyData(null);
       setPlayerReady(false);
@@ -1488,6 +1988,38 @@ function App() {
     }
   };
 
+  // Kick candidates for the pause menu's vote section (everyone but us)
+  useEffect(() => {
+    if (!isPaused || !currentLobbyData?.gamePublicKey || !window.gameBridge?.getGamePlayers) return;
+    let cancelled = false;
+    window.gameBridge.getGamePlayers(currentLobbyData.gamePublicKey).then((players) => {
+      if (cancelled || !Array.isArray(players)) return;
+      const localKey = window.gameBridge.getCurrentPlayerEphemeralKey?.();
+      setPausePlayers(players.filter((p) => p.authority !== localKey));
+    });
+    return () => {
+      cancelled = true;
+    };
+  }, [isPaused, currentLobbyData?.gamePublicKey]);
+
+  // A passed kick vote against us: leave the match like quitting does
+  useEffect(() => {
+    if (currentGameState !== 1) return;
+    const handleKicked = () => {
+      toast.error("You were vote-kicked from the match");
+      handleQuitGame();
+    };
+    window.addEventListener("kickedFromMatch", handleKicked);
+    return () => window.removeEventListener("kickedFromMatch", handleKicked);
+  }, [currentGameState]);
+
+  // Game panics and fatal errors: the player decides whether the report goes anywhere
+  useEffect(() => {
+    const handleCrash = (event) => promptCrashReport(event.detail);
+    window.addEventListener("gameCrashReport", handleCrash);
+    return () => window.removeEventListener("gameCrashReport", handleCrash);
+  }, []);
+
   return (
     <div id="container">
       {/* React Hot Toast Notifications */}
@@ -1566,28 +2098,75 @@ function App() {
         isOpen={settingsOpen}
         sensitivity={sensitivity}
         musicEnabled={musicEnabled}
+        audioVolumes={audioVolumes}
+        uiSoundsEnabled={uiSoundsEnabled}
+        colorblindMode={colorblindMode}
+        comfortSettings={comfortSettings}
+        viewmodelSettings={viewmodelSettings}
+        language={language}
+        renderMode={renderMode}
+        viewDistance={viewDistance}
+        filmEffects={filmEffects}
+        streamerSafe={streamerSafe}
+        exportMatchEvents={exportMatchEvents}
+        profanityFilter={profanityFilter}
+        mutedPlayers={mutedPlayers}
+        onEditHudLayout={() => {
+          // The game stays in its settings state (mouse released) until the editor closes
+          setSettingsOpen(false);
+          setHudEditorOpen(true);
+        }}
         onClose={() => {
           setSettingsOpen(false);
           window.gameBridge?.closeSettings?.();
         }}
-        onSave={({ sensitivity, musicEnabled }) => {
+        onSave={({ sensitivity, musicEnabled, audioVolumes, uiSoundsEnabled, colorblindMode, comfortSettings, viewmodelSettings, language, renderMode, viewDistance, filmEffects, streamerSafe, exportMatchEvents, profanityFilter, mutedPlayers, connection }) => {
           setSensitivity(sensitivity);
           setMusicEnabled(musicEnabled);
+          setAudioVolumes(audioVolumes);
+          setUiSoundsEnabled(uiSoundsEnabled);
+          setColorblindMode(colorblindMode);
+          setComfortSettings(comfortSettings);
+          setViewmodelSettings(viewmodelSettings);
+          setLanguage(language);
+          setRenderMode(renderMode);
+          setViewDistance(viewDistance);
+          setFilmEffects(filmEffects);
+          setStreamerSafe(streamerSafe);
+          setExportMatchEvents(exportMatchEvents);
+          setProfanityFilter(profanityFilter);
+          setMutedPlayers(mutedPlayers);
           // apply immediately in Rust
           try {
             window.gameBridge?.setMouseSensitivity?.(sensitivity);
+            if (connection) window.gameBridge?.setConnectionSettings?.(connection);
           } catch (e) {}
           setSettingsOpen(false);
           window.gameBridge?.closeSettings?.();
         }}
       />
+      <HudLayoutEditor
+        isOpen={hudEditorOpen}
+        layout={hudLayout}
+        onClose={() => {
+          setHudEditorOpen(false);
+          window.gameBridge?.closeSettings?.();
+        }}
+        onSave={(layout) => {
+          setHudLayout(layout);
+          setHudEditorOpen(false);
+          window.gameBridge?.closeSettings?.();
+        }}
+      />
       {/* Game canvas - full screen background */}
       <canvas
         id="canvas"
         onContextMenu={(e) => e.preventDefault()}
         style={{
           display:
-            activeTab === "mapeditor" || currentGameState === 1
+            activeTab === "mapeditor" ||
+            activeTab === "lobby" ||
+            currentGameState === 1
               ? "block"
               : "none",
         }}
@@ -1616,7 +2195,10 @@ function App() {
           className="game-nav"
           style={{
             pointerEvents: "auto",
-            display: currentGameState === 1 ? "none" : "flex",
+            display:
+              currentGameState === 1 || spectatingGame || offlineMode
+                ? "none"
+                : "flex",
           }}
         >
           {/* Left: Logo and Tabs */}
@@ -1637,6 +2219,12 @@ function App() {
               >
                 🎮 Lobby
               </button>
+              <button
+                className={`nav-tab ${activeTab === "weapons" ? "active" : ""}`}
+                onClick={() => setActiveTab("weapons")}
+              >
+                🔫 Weapons
+              </button>
               <button
                 className={`nav-tab ${activeTab === "store" ? "active" : ""}`}
                 onClick={() => setActiveTab("store")}
@@ -1651,6 +2239,29 @@ function App() {
               >
                 🗺️ Map Editor
               </button>
+              <button
+                className={`nav-tab ${
+                  walletConnected && !tutorialCompleted ? "nav-tab-highlight" : ""
+                }`}
+                onClick={() => handleStartOfflineMode("tutorial")}
+                title="Offline training: movement, shooting, reloading and objectives"
+              >
+                🎓 How to Play
+              </button>
+              <button
+                className="nav-tab"
+                onClick={() => handleStartOfflineMode("range")}
+                title="Offline practice against target dummies"
+              >
+                🎯 Range
+              </button>
+              <button
+                className="nav-tab"
+                onClick={() => setPracticeSetupOpen(true)}
+                title="Offline match against bots at the difficulty of your choice"
+              >
+                🤖 Practice
+              </button>
               <button
                 className="nav-tab"
                 onClick={() => setSettingsOpen(true)}
@@ -1665,6 +2276,13 @@ function App() {
           {/* Right: Status and Wallet */}
           <div className="nav-right">
             <div className="nav-status">
+              <span
+                className="nav-cluster"
+                style={{ color: findCluster(cluster)?.color, borderColor: findCluster(cluster)?.color }}
+                title="Cluster this session is on (change it in Settings)"
+              >
+                {findCluster(cluster)?.label || cluster}
+              </span>
               <span>{solanaReady ? "✅" : "⏳"} Solana</span>
               <span>{gameReady ? "✅" : "⏳"} Game</span>
             </div>
@@ -1711,7 +2329,9 @@ function App() {
               <div className="player-header">
                 <div className="player-avatar-icon">👤</div>
                 <div className="player-info">
-                  <h3 className="player-username">{playerData.username}</h3>
+                  <h3 className="player-username">
+                    <PlayerName username={playerData.username} />
+                  </h3>
                   <div className="player-level">Level {playerData.level}</div>
                 </div>
               </div>
@@ -1751,6 +2371,15 @@ function App() {
                 className="hud-input"
                 style={{ width: "100%" }}
               />
+              <input
+                type="text"
+                placeholder="Clan tag (optional, 2-5 letters/digits)"
+                value={playerClanTag}
+                maxLength={5}
+                onChange={(e) => setPlayerClanTag(e.target.value.toUpperCase())}
+                className="hud-input"
+                style={{ width: "100%" }}
+              />
               <button
                 className="hud-button-success hud-button"
                 onClick={handleInitPlayer}
@@ -1763,6 +2392,18 @@ function App() {
           ) : null}
         </div>
 
+        {/* Party - Hidden when in active game */}
+        {playerInitialized && currentGameState !== 1 && (
+          <PartyWidget
+            party={party}
+            currentPlayer={partyName}
+            onCreateParty={handleCreateParty}
+            onJoinParty={handleJoinParty}
+            onLeaveParty={handleLeaveParty}
+            onQuickPlay={handleQuickPlay}
+          />
+        )}
+
         {/* Bottom Right - Ephemeral Wallet - Hidden when in active game */}
         <div
           className="hud-bottom-right"
@@ -1782,8 +2423,11 @@ function App() {
             loading={gamesLoading}
             onRefresh={loadGames}
             onCreateRoom={handleCreateRoom}
-            onJoinRoom={handleJoinRoom}
+            onJoinRoom={(gamePublicKey) => handleJoinRoom(gamePublicKey)}
+            onQuickPlay={handleQuickPlay}
             onJoinAsSpectator={handleJoinAsSpectator}
+            liveGames={liveGames}
+            onSpectateLive={handleSpectateLive}
             onClose={() => setActiveTab("lobby")}
           />
         )}
@@ -1797,9 +2441,16 @@ function App() {
             onToggleReady={handleToggleReady}
             onStartGame={handleStartGame}
             onLeaveLobby={handleLeaveLobby}
+            mutedPlayers={mutedPlayers}
+            onToggleMute={(player) => setMutedPlayers((muted) => toggleMute(muted, player))}
+            profanityFilter={profanityFilter}
+            botBackfill={botBackfill}
+            onToggleBotBackfill={() => setBotBackfill((enabled) => !enabled)}
           />
         )}
 
+        {activeTab === "weapons" && <LoadoutsPanel />}
+
         {activeTab === "store" && (
           <div
             style={{
@@ -1867,51 +2518,8 @@ function App() {
               ></div>
             </div>
 
-            {/* Health and Ammo Display */}
-            <div
-              style={{
-                position: "fixed",
-                bottom: "20px",
-                right: "20px",
-                display: "flex",
-                gap: "20px",
-                pointerEvents: "none",
-                zIndex: 1000,
-              }}
-            >
-              {/* Health */}
-              <div
-                style={{
-                  background:
-                    "linear-gradient(135deg, rgba(156, 81, 255, 0.2), rgba(156, 81, 255, 0.1))",
-                  backdropFilter: "blur(10px)",
-                  border: "2px solid rgba(156, 81, 255, 0.5)",
-                  borderRadius: "8px",
-                  padding: "15px 25px",
-                  boxShadow: "0 4px 20px rgba(156, 81, 255, 0.3)",
-                }}
-              >
-                <div
-                  style={{
-                    color: "#9c51ff",
-                    fontSize: "12px",
-                    marginBottom: "5px",
-                  }}
-                >
-                  HEALTH
-                </div>
-                <div
-                  style={{
-                    color: "#fff",
-                    fontSize: "32px",
-                    fontWeight: "bold",
-                  }}
-                >
-                  100
-                </div>
-              </div>
-
-              {/* Ammo */}
+            {/* Ammo - the health bar is drawn by the game at its own HUD layout spot */}
+            <HudSlot placement={hudLayout.ammo}>
               <div
                 style={{
                   background:
@@ -1943,28 +2551,47 @@ function App() {
                   {isReloading && " (Reloading...)"}
                 </div>
               </div>
-            </div>
+            </HudSlot>
 
             {/* Minimap - Modern web-based implementation */}
-            <Minimap gamePublicKey={currentLobbyData?.gamePublicKey} />
+            <HudSlot placement={hudLayout.minimap}>
+              <Minimap gamePublicKey={currentLobbyData?.gamePublicKey} />
+            </HudSlot>
 
-            {/* Latency Display - Shows ping/connection status on left side */}
-            <LatencyDisplay 
+            {/* Kill Feed - Recent kills, assists and hazard deaths */}
+            <HudSlot placement={hudLayout.killFeed}>
+              <KillFeed />
+            </HudSlot>
+
+            {/* Scoreboard - Hold TAB for K/D/A and per-player ping */}
+            <Scoreboard
               gamePublicKey={currentLobbyData?.gamePublicKey}
               isPlaying={currentGameState === 1}
             />
 
-            {/* Match Status - Shows team scores during gameplay */}
-            <MatchStatus
+            {/* Latency Display - Shows ping/connection status on left side */}
+            <LatencyDisplay 
               gamePublicKey={currentLobbyData?.gamePublicKey}
-              currentGameState={currentGameState}
-              onGameEnd={(data) => {
-                console.log("🏆 Game ended, showing victory dialog:", data);
-                setVictoryData(data);
-                setShowVictoryDialog(true);
-              }}
+              isPlaying={currentGameState === 1}
+              isIdle={isPaused}
             />
 
+            {/* Match Status - Shows team scores and the round timer */}
+            <HudSlot placement={hudLayout.timer}>
+              <MatchStatus
+                gamePublicKey={currentLobbyData?.gamePublicKey}
+                currentGameState={currentGameState}
+                onGameEnd={async (data) => {
+                  console.log("🏆 Game ended, playing round-end presentation:", data);
+                  if (window.gameBridge && window.gameBridge.endRound) {
+                    await window.gameBridge.endRound(data.winningTeam);
+                  }
+                  setVictoryData(data);
+                  setShowVictoryDialog(true);
+                }}
+              />
+            </HudSlot>
+
             {/* Fullscreen toggle button - only show when in game but not fullscreen */}
             {currentGameState === 1 && !isFullscreen && (
               <button
@@ -2024,6 +2651,9 @@ function App() {
 
             {/* Respawn Overlay - Shows death screen and countdown */}
             <RespawnOverlay />
+
+            {/* Synchronized match start countdown */}
+            {playStartAtMs && <StartCountdown playAtMs={playStartAtMs} />}
           </>
         )}
 
@@ -2034,6 +2664,7 @@ function App() {
             teamAScore={victoryData.teamAScore}
             teamBScore={victoryData.teamBScore}
             mvpPlayer={victoryData.mvpPlayer}
+            overtimePeriods={victoryData.overtimePeriods}
             onClose={async () => {
               setShowVictoryDialog(false);
               setVictoryData(null);
@@ -2064,6 +2695,7 @@ function App() {
               // Exit fullscreen and return to lobby
               exitFullscreen();
               setCurrentGameState(null);
+              setPlayStartAtMs(null);
               setInLobby(false);
               setCurrentLobbyData(null);
 
@@ -2076,6 +2708,50 @@ function App() {
         )}
       </div>
 
+      {/* Spectator exit button - the match keeps running without us */}
+      {spectatingGame && (
+        <button
+          className="btn btn-secondary"
+          onClick={handleStopSpectating}
+          style={{
+            position: "fixed",
+            top: "16px",
+            right: "16px",
+            zIndex: 1000,
+            pointerEvents: "auto",
+          }}
+        >
+          ✕ Stop Spectating
+        </button>
+      )}
+
+      {/* Practice match setup (difficulty, bot roles, bot count) */}
+      <PracticeSetup
+        isOpen={practiceSetupOpen}
+        onClose={() => setPracticeSetupOpen(false)}
+        onStart={(setup) => {
+          setPracticeSetupOpen(false);
+          handleStartOfflineMode("practice", setup);
+        }}
+      />
+
+      {/* Tutorial / shooting range / practice match exit button */}
+      {offlineMode && (
+        <button
+          className="btn btn-secondary"
+          onClick={handleStopOfflineMode}
+          style={{
+            position: "fixed",
+            top: "16px",
+            right: "16px",
+            zIndex: 1000,
+            pointerEvents: "auto",
+          }}
+        >
+          ✕ {offlineMode === "tutorial" ? "Exit Tutorial" : offlineMode === "practice" ? "Leave Practice" : "Leave Range"}
+        </button>
+      )}
+
       {/* Pause Menu - Rendered outside web-ui-overlay for proper pointer events */}
       {/* Only show during active gameplay, NOT in lobby */}
       {isPaused && currentGameState === 1 && !inLobby && !showVictoryDialog && (
@@ -2089,9 +2765,21 @@ function App() {
             }
           }}
           onQuit={handleQuitGame}
+          players={pausePlayers}
+          onCallVote={(kind, target) => {
+            window.gameBridge?.requestVote?.(kind, target);
+            // Back into the game so everyone (us included) can vote with F1 / F2
+            setIsPaused(false);
+            if (window.gameBridge && window.gameBridge.startGameMode) {
+              window.gameBridge.startGameMode();
+            }
+          }}
         />
       )}
 
+      {/* Activity - Recent join/shoot/kill/respawn/reload transactions (not ours to show while casting) */}
+      {!spectatingGame && <ActivityPanel />}
+
       {/* Debug Console - Press '/' to toggle */}
       <DebugConsole />
     </div>
@@ -0,0 +1,79 @@
+import React, { useState, useEffect } from 'react';
+import './ActivityPanel.css';
+import { getActivityLog, subscribeActivityLog, clearActivityLog, explorerUrl } from '../utils/activity-log';
+
+/**
+ * ActivityPanel Component
+ * Collapsible list of recent gameplay transactions (join, shoot, kill,
+ * respawn, reload) with their status. Click a signature to copy it, or open
+ * it in the explorer.
+ */
+function ActivityPanel() {
+  const [expanded, setExpanded] = useState(false);
+  const [entries, setEntries] = useState(getActivityLog());
+  const [copiedId, setCopiedId] = useState(null);
+
+  useEffect(() => subscribeActivityLog(setEntries), []);
+
+  const copySignature = (entry) => {
+    navigator.clipboard.writeText(entry.signature);
+    setCopiedId(entry.id);
+    setTimeout(() => setCopiedId((id) => (id === entry.id ? null : id)), 1500);
+  };
+
+  const pendingCount = entries.filter((entry) => entry.status === 'pending').length;
+  const failedCount = entries.filter((entry) => entry.status === 'failed').length;
+
+  return (
+    <div className={`activity-panel ${expanded ? 'expanded' : ''}`}>
+      <button className="activity-header" onClick={() => setExpanded((value) => !value)}>
+        <span>{expanded ? '▾' : '▸'} ACTIVITY</span>
+        <span className="activity-counts">
+          {pendingCount > 0 && <span className="activity-status-pending">{pendingCount} pending</span>}
+          {failedCount > 0 && <span className="activity-status-failed">{failedCount} failed</span>}
+        </span>
+      </button>
+
+      {expanded && (
+        <div className="activity-body">
+          {entries.length === 0 ? (
+            <div className="activity-empty">No transactions yet</div>
+          ) : (
+            entries.map((entry) => (
+              <div key={entry.id} className="activity-row" title={entry.error || undefined}>
+                <span className="activity-time">{new Date(entry.timestamp).toLocaleTimeString()}</span>
+                <span className="activity-label">{entry.label}</span>
+                <span className={`activity-status activity-status-${entry.status}`}>{entry.status}</span>
+                {entry.signature ? (
+                  <>
+                    <code className="activity-signature" onClick={() => copySignature(entry)} title="Click to copy">
+                      {copiedId === entry.id ? 'copied' : `${entry.signature.slice(0, 6)}...${entry.signature.slice(-6)}`}
+                    </code>
+                    <a
+                      className="activity-link"
+                      href={explorerUrl(entry.signature, entry.endpoint)}
+                      target="_blank"
+                      rel="noopener noreferrer"
+                      title="View on Solscan"
+                    >
+                      ↗
+                    </a>
+                  </>
+                ) : (
+                  <span className="activity-signature">---</span>
+                )}
+              </div>
+            ))
+          )}
+          {entries.length > 0 && (
+            <button className="activity-clear" onClick={clearActivityLog}>
+              Clear
+            </button>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
+
+export default ActivityPanel;
@@ -0,0 +1,127 @@
+import React, { useEffect, useState } from "react";
+
+/**
+ * ConnectionSettings - cluster and RPC / ephemeral rollup endpoint picker
+ * "Auto" follows the game's latency probe; manual mode keeps the chosen endpoints.
+ * Switching cluster asks for confirmation (wallets and lobbies don't carry over).
+ */
+const ConnectionSettings = ({ value, onChange }) => {
+  const [info, setInfo] = useState(() => window.gameBridge?.getConnectionSettings?.() || null);
+  const [probing, setProbing] = useState(false);
+
+  // Refresh latencies whenever a probe finishes or the endpoints change
+  useEffect(() => {
+    if (!window.gameBridge) return;
+    const previous = window.gameBridge.onConnectionSettingsChanged;
+    window.gameBridge.onConnectionSettingsChanged = (next) => {
+      setInfo(next);
+      setProbing(false);
+      previous?.(next);
+    };
+    return () => {
+      if (window.gameBridge) window.gameBridge.onConnectionSettingsChanged = previous;
+    };
+  }, []);
+
+  if (!info || !value) return null;
+
+  const latencyLabel = (id) => {
+    const latencies = info.probe?.latencies || {};
+    if (!(id in latencies)) return "—";
+    return latencies[id] == null ? "offline" : `${Math.round(latencies[id])} ms`;
+  };
+
+  const changeCluster = (clusterId) => {
+    const cluster = info.clusters.find((c) => c.id === clusterId);
+    if (!cluster || clusterId === value.cluster) return;
+
+    const warning = cluster.realFunds
+      ? `Switch to ${cluster.label}? Every transaction on ${cluster.label} costs real SOL.`
+      : `Switch to ${cluster.label}?`;
+    const confirmed = window.confirm(
+      `${warning}\n\nYour session wallet balance, lobbies and maps are separate on each cluster. The switch applies when you save.`
+    );
+    if (!confirmed) return;
+
+    const first = (kind) => info.endpoints.find((e) => e.cluster === clusterId && e.kind === kind)?.id;
+    onChange({ ...value, cluster: clusterId, rpcId: first("rpc"), rollupId: first("rollup") });
+  };
+
+  const renderSelect = (kind, key) => (
+    <select
+      value={value[key]}
+      disabled={value.mode === "auto"}
+      onChange={(e) => onChange({ ...value, [key]: e.target.value })}
+      style={{ flex: 1, background: "#232336", color: "#fff", border: "1px solid #33334e", borderRadius: 6, padding: 4 }}
+    >
+      {info.endpoints
+        .filter((endpoint) => endpoint.kind === kind && endpoint.cluster === value.cluster)
+        .map((endpoint) => (
+          <option key={endpoint.id} value={endpoint.id}>
+            {endpoint.region} ({latencyLabel(endpoint.id)})
+          </option>
+        ))}
+    </select>
+  );
+
+  return (
+    <div style={{ margin: "24px 0" }}>
+      <label style={{ display: "block", fontWeight: 700, marginBottom: 8 }}>
+        Connection
+      </label>
+      <div style={{ display: "flex", alignItems: "center", gap: 10, marginBottom: 8 }}>
+        <span style={{ width: 64, fontSize: 13 }}>Cluster</span>
+        <select
+          value={value.cluster}
+          onChange={(e) => changeCluster(e.target.value)}
+          style={{ flex: 1, background: "#232336", color: "#fff", border: "1px solid #33334e", borderRadius: 6, padding: 4 }}
+        >
+          {info.clusters.map((cluster) => (
+            <option key={cluster.id} value={cluster.id}>
+              {cluster.label}
+              {cluster.realFunds ? " (real SOL)" : ""}
+            </option>
+          ))}
+        </select>
+      </div>
+      <label style={{ fontSize: 13 }}>
+        <input
+          type="checkbox"
+          checked={value.mode === "auto"}
+          onChange={(e) => onChange({ ...value, mode: e.target.checked ? "auto" : "manual" })}
+          style={{ marginRight: 8 }}
+        />
+        Auto-select lowest latency
+      </label>
+      <div style={{ display: "flex", alignItems: "center", gap: 10, marginTop: 8 }}>
+        <span style={{ width: 64, fontSize: 13 }}>RPC</span>
+        {renderSelect("rpc", "rpcId")}
+      </div>
+      <div style={{ display: "flex", alignItems: "center", gap: 10, marginTop: 6 }}>
+        <span style={{ width: 64, fontSize: 13 }}>Rollup</span>
+        {renderSelect("rollup", "rollupId")}
+      </div>
+      <button
+        onClick={() => {
+          setProbing(true);
+          window.gameBridge?.reprobeEndpoints?.();
+        }}
+        disabled={probing}
+        style={{
+          marginTop: 8,
+          padding: "4px 12px",
+          borderRadius: 6,
+          border: "none",
+          background: "#33334e",
+          color: "#fff",
+          cursor: "pointer",
+          fontSize: 12,
+        }}
+      >
+        {probing ? "Testing..." : "Test latency"}
+      </button>
+    </div>
+  );
+};
+
+export default ConnectionSettings;
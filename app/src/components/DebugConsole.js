@@ -5,6 +5,7 @@ const DebugConsole = () => {
   const [isOpen, setIsOpen] = useState(false);
   const [logs, setLogs] = useState([]);
   const [isPaused, setIsPaused] = useState(false);
+  const [gameLogPanel, setGameLogPanel] = useState(false);
   const consoleRef = useRef(null);
   const autoScrollRef = useRef(true); // Track if auto-scroll is enabled
 
@@ -83,6 +84,13 @@ const DebugConsole = () => {
     setIsPaused(prev => !prev);
   };
 
+  // Mirror the game's own log lines into a panel drawn in the canvas
+  const toggleGameLogPanel = () => {
+    const next = !gameLogPanel;
+    window.gameBridge?.setLogPanel?.(next);
+    setGameLogPanel(next);
+  };
+
   const getStatusIcon = (status) => {
     switch (status) {
       case 'pending':
@@ -156,6 +164,13 @@ const DebugConsole = () => {
           >
             {isPaused ? '▶️ Resume' : '⏸️ Pause'}
           </button>
+          <button
+            className={`log-panel-btn ${gameLogPanel ? 'active' : ''}`}
+            onClick={toggleGameLogPanel}
+            title="Show the game's log in-game (levels: gameBridge.setLogLevel('net', 'debug'))"
+          >
+            🧾 Game log
+          </button>
           <button className="clear-btn" onClick={clearLogs} title="Clear logs">
             🗑️ Clear
           </button>
@@ -0,0 +1,176 @@
+import React, { useEffect, useRef, useState } from 'react';
+import { HUD_ELEMENTS, HUD_SCALE_MAX, HUD_SCALE_MIN, defaultHudLayout } from '../utils/hud-layout';
+
+const clamp = (value, min, max) => Math.min(max, Math.max(min, value));
+
+/**
+ * HudLayoutEditor Component
+ * Full-screen HUD layout mode: drag an element's outline to move it, drag its
+ * corner handle to resize it and untick it to hide it. Nothing is applied
+ * until Save
+ */
+function HudLayoutEditor({ isOpen, layout, onSave, onClose }) {
+  const [draft, setDraft] = useState(layout);
+  // Element being dragged: { key, mode: 'move' | 'resize', startX, startY, start: placement }
+  const drag = useRef(null);
+
+  useEffect(() => {
+    if (isOpen) setDraft(layout);
+  }, [isOpen, layout]);
+
+  useEffect(() => {
+    if (!isOpen) return undefined;
+
+    const handleMove = (e) => {
+      const current = drag.current;
+      if (!current) return;
+      const dx = e.clientX - current.startX;
+      const dy = e.clientY - current.startY;
+      const element = HUD_ELEMENTS.find((el) => el.key === current.key);
+      const changes =
+        current.mode === 'move'
+          ? {
+              x: clamp(current.start.x + dx / window.innerWidth, 0, 1),
+              y: clamp(current.start.y + dy / window.innerHeight, 0, 1),
+            }
+          : {
+              // The handle is the bottom-right corner and the element grows around its center
+              scale: clamp(
+                current.start.scale + 2 * Math.max(dx / element.width, dy / element.height),
+                HUD_SCALE_MIN,
+                HUD_SCALE_MAX
+              ),
+            };
+      setDraft((d) => ({ ...d, [current.key]: { ...d[current.key], ...changes } }));
+    };
+    const handleUp = () => {
+      drag.current = null;
+    };
+    const handleKey = (e) => {
+      if (e.key === 'Escape') onClose();
+    };
+
+    window.addEventListener('pointermove', handleMove);
+    window.addEventListener('pointerup', handleUp);
+    window.addEventListener('keydown', handleKey);
+    return () => {
+      window.removeEventListener('pointermove', handleMove);
+      window.removeEventListener('pointerup', handleUp);
+      window.removeEventListener('keydown', handleKey);
+    };
+  }, [isOpen, onClose]);
+
+  if (!isOpen) return null;
+
+  const startDrag = (e, key, mode) => {
+    e.preventDefault();
+    e.stopPropagation();
+    drag.current = { key, mode, startX: e.clientX, startY: e.clientY, start: draft[key] };
+  };
+  const setVisible = (key, visible) => setDraft((d) => ({ ...d, [key]: { ...d[key], visible } }));
+
+  const buttonStyle = (primary) => ({
+    padding: '6px 16px',
+    borderRadius: 6,
+    border: 'none',
+    fontWeight: 700,
+    background: primary ? '#9c51ff' : '#33334e',
+    color: '#fff',
+    cursor: 'pointer',
+  });
+
+  return (
+    <div
+      style={{
+        position: 'fixed',
+        inset: 0,
+        zIndex: 5000,
+        background: 'rgba(13, 13, 17, 0.6)',
+        backgroundImage:
+          'linear-gradient(rgba(156, 81, 255, 0.08) 1px, transparent 1px), linear-gradient(90deg, rgba(156, 81, 255, 0.08) 1px, transparent 1px)',
+        backgroundSize: '40px 40px',
+        userSelect: 'none',
+      }}
+    >
+      {HUD_ELEMENTS.map(({ key, label, width, height }) => {
+        const { x, y, scale, visible } = draft[key];
+        return (
+          <div
+            key={key}
+            onPointerDown={(e) => startDrag(e, key, 'move')}
+            style={{
+              position: 'absolute',
+              left: `${x * 100}%`,
+              top: `${y * 100}%`,
+              width: width * scale,
+              height: height * scale,
+              transform: 'translate(-50%, -50%)',
+              border: `2px ${visible ? 'solid' : 'dashed'} ${visible ? '#00f294' : '#555566'}`,
+              background: visible ? 'rgba(0, 242, 148, 0.12)' : 'rgba(40, 40, 50, 0.4)',
+              color: visible ? '#00f294' : '#777788',
+              display: 'flex',
+              alignItems: 'center',
+              justifyContent: 'center',
+              fontSize: 13,
+              fontWeight: 700,
+              cursor: 'move',
+            }}
+          >
+            {label} · {Math.round(scale * 100)}%
+            <div
+              onPointerDown={(e) => startDrag(e, key, 'resize')}
+              title="Drag to resize"
+              style={{
+                position: 'absolute',
+                right: -6,
+                bottom: -6,
+                width: 12,
+                height: 12,
+                background: visible ? '#00f294' : '#555566',
+                cursor: 'nwse-resize',
+              }}
+            />
+          </div>
+        );
+      })}
+
+      <div
+        style={{
+          position: 'absolute',
+          top: '50%',
+          left: '50%',
+          transform: 'translate(-50%, -50%)',
+          padding: '16px 20px',
+          background: '#15151d',
+          border: '1px solid #9c51ff',
+          borderRadius: 8,
+          color: '#fff',
+          minWidth: 280,
+        }}
+      >
+        <div style={{ color: '#9c51ff', fontWeight: 900, fontSize: 16, marginBottom: 4 }}>HUD LAYOUT</div>
+        <div style={{ fontSize: 12, color: '#aaa', marginBottom: 10 }}>
+          Drag elements to move them, drag the corner handle to resize
+        </div>
+        {HUD_ELEMENTS.map(({ key, label }) => (
+          <label key={key} style={{ display: 'block', fontSize: 13, marginBottom: 4 }}>
+            <input
+              type="checkbox"
+              checked={draft[key].visible}
+              onChange={(e) => setVisible(key, e.target.checked)}
+              style={{ marginRight: 6 }}
+            />
+            {label}
+          </label>
+        ))}
+        <div style={{ display: 'flex', gap: 8, justifyContent: 'flex-end', marginTop: 12 }}>
+          <button style={buttonStyle(false)} onClick={() => setDraft(defaultHudLayout())}>Reset</button>
+          <button style={buttonStyle(false)} onClick={onClose}>Cancel</button>
+          <button style={buttonStyle(true)} onClick={() => onSave(draft)}>Save</button>
+        </div>
+      </div>
+    </div>
+  );
+}
+
+export default HudLayoutEditor;
@@ -0,0 +1,26 @@
+import React from 'react';
+import './HudSlot.css';
+
+/**
+ * HudSlot Component
+ * Places one web HUD element where the player's HUD layout puts it. Hidden
+ * elements stay mounted (the match timer also watches for the end of the game)
+ */
+function HudSlot({ placement, children }) {
+  const { x, y, scale, visible } = placement;
+  return (
+    <div
+      className="hud-slot"
+      style={{
+        left: `${x * 100}%`,
+        top: `${y * 100}%`,
+        transform: `translate(-50%, -50%) scale(${scale})`,
+        display: visible ? undefined : 'none',
+      }}
+    >
+      {children}
+    </div>
+  );
+}
+
+export default HudSlot;
@@ -1,5 +1,16 @@
 import React, { useState, useEffect } from 'react';
 import './KillFeed.css';
+import PlayerName from './PlayerName';
+
+// Weapon glyphs keyed by the weapon id sent from the game (WeaponKind::id)
+const WEAPON_GLYPHS = {
+  rifle: '︻デ═一',
+  sniper: '︻芫══───',
+  shotgun: '︻═╦═',
+  // Environmental deaths (DamageTracker hazards)
+  fall: '⤓',
+  out_of_bounds: '☠',
+};
 
 /**
  * KillFeed Component
@@ -11,7 +22,7 @@ function KillFeed() {
   useEffect(() => {
     // Listen for kill events from the global window object
     const handleKillEvent = (event) => {
-      const { killer, victim, timestamp } = event.detail;
+      const { killer, victim, assists = [], weapon = 'rifle', headshot = false, hazard = false, timestamp } = event.detail;
 
       // Create a unique ID for this kill event
       const killId = `${timestamp}-${Math.random()}`;
@@ -20,6 +31,10 @@ function KillFeed() {
         id: killId,
         killer,
         victim,
+        assists,
+        weapon,
+        headshot,
+        hazard,
         timestamp,
       };
 
@@ -47,9 +62,22 @@ function KillFeed() {
     <div className="kill-feed">
       {killEvents.map((kill) => (
         <div key={kill.id} className="kill-event">
-          <span className="killer-name">{kill.killer}</span>
-          <span className="kill-icon">💀</span>
-          <span className="victim-name">{kill.victim}</span>
+          {!kill.hazard && <span className="killer-name"><PlayerName username={kill.killer} /></span>}
+          {kill.assists.length > 0 && (
+            <span className="assist-names">
+              {kill.assists.map((assist, i) => (
+                <React.Fragment key={i}>
+                  {i === 0 ? '+ ' : ' + '}
+                  <PlayerName username={assist} />
+                </React.Fragment>
+              ))}
+            </span>
+          )}
+          <span className="kill-weapon" title={kill.weapon}>
+            {WEAPON_GLYPHS[kill.weapon] || '▸'}
+          </span>
+          {kill.headshot && <span className="kill-headshot" title="Headshot">🎯</span>}
+          <span className="victim-name"><PlayerName username={kill.victim} /></span>
         </div>
       ))}
     </div>
@@ -1,4 +1,17 @@
-import React, { useState, useEffect } from 'react';
+import React, { useState, useEffect, useRef } from 'react';
+import toast from 'react-hot-toast';
+import {
+  createSession,
+  noteBalance,
+  noteTopUp,
+  noteReceipt,
+  sessionCost,
+  topUpNeeded,
+  formatSol
+} from '../utils/session-cost';
+
+// How often the session wallet balance is re-read (ms)
+const BALANCE_POLL_MS = 5000;
 
 /**
  * LatencyDisplay Component
@@ -6,10 +19,17 @@ import React, { useState, useEffect } from 'react';
  * Displays WebSocket/Ephemeral Rollup latency/ping on the left side of the screen during gameplay.
  * Measures round-trip time (RTT) by making HTTP RPC calls to the Ephemeral Rollup.
  * Only visible during active gameplay.
+ *
+ * Also shows what the match has cost the session wallet so far, and offers a
+ * top-up while the player is idle (paused, or dead and waiting to respawn) if the wallet is projected
+ * to run dry before the match ends.
  */
-const LatencyDisplay = ({ gamePublicKey, isPlaying }) => {
+const LatencyDisplay = ({ gamePublicKey, isPlaying, isIdle = false }) => {
   const [latency, setLatency] = useState(null);
+  const [session, setSession] = useState(null);
+  const topUpOffered = useRef(false);
   const [isConnected, setIsConnected] = useState(false);
+  const [isDegraded, setIsDegraded] = useState(false); // WebSocket blocked, polling over HTTP
 
   useEffect(() => {
     // Only measure latency during active gameplay
@@ -25,9 +45,10 @@ const LatencyDisplay = ({ gamePublicKey, isPlaying }) => {
       // Check if WebSocket is connected
       if (window.gameBridge && window.gameBridge.getWebSocketStatus) {
         const status = window.gameBridge.getWebSocketStatus();
-        setIsConnected(status === 'connected');
+        setIsConnected(status === 'connected' || status === 'degraded');
+        setIsDegraded(status === 'degraded');
 
-        if (status !== 'connected') {
+        if (status === 'disconnected') {
           setLatency(null);
           return;
         }
@@ -82,9 +103,76 @@ const LatencyDisplay = ({ gamePublicKey, isPlaying }) => {
     };
   }, [isPlaying, gamePublicKey]);
 
+  // Session cost: balance polling plus a count of the bridge's transaction receipts
+  useEffect(() => {
+    setSession(null);
+    topUpOffered.current = false;
+    if (!isPlaying || !gamePublicKey || !window.gameBridge?.getEphemeralWalletInfo) return;
+
+    let cancelled = false;
+    const pollBalance = async () => {
+      const info = await window.gameBridge.getEphemeralWalletInfo();
+      if (cancelled || typeof info?.ephemeralBalance !== 'number') return;
+      setSession((current) =>
+        current ? noteBalance(current, info.ephemeralBalance) : createSession(info.ephemeralBalance)
+      );
+    };
+
+    // Matchmaking transactions are paid by the main wallet, everything else by the session wallet
+    const handleReceipt = (event) => {
+      const { status, type } = event.detail || {};
+      if (status !== 'success' || type === 'Matchmaking') return;
+      setSession((current) => (current ? noteReceipt(current) : current));
+    };
+
+    pollBalance();
+    const interval = setInterval(pollBalance, BALANCE_POLL_MS);
+    window.addEventListener('debug-transaction', handleReceipt);
+    return () => {
+      cancelled = true;
+      clearInterval(interval);
+      window.removeEventListener('debug-transaction', handleReceipt);
+    };
+  }, [isPlaying, gamePublicKey]);
+
+  // Offer a top-up once per shortfall, and only while the player isn't in a fight
+  useEffect(() => {
+    const idle = isIdle || window.gameDeathState?.dead;
+    if (!session || !idle || topUpOffered.current) return;
+    const amount = topUpNeeded(session, window.___match_seconds_remaining ?? null);
+    if (amount <= 0) return;
+
+    topUpOffered.current = true;
+    toast(
+      (t) => (
+        <span>
+          Session wallet is projected to run out before the match ends.{' '}
+          <button
+            onClick={async () => {
+              toast.dismiss(t.id);
+              try {
+                await window.gameBridge.fundEphemeralWallet(amount);
+                setSession((current) => (current ? noteTopUp(current, amount) : current));
+                toast.success(`Topped up ${formatSol(amount)}`);
+              } catch (error) {
+                toast.error('Top-up failed: ' + error.message);
+              }
+              topUpOffered.current = false;
+            }}
+          >
+            Top up {formatSol(amount)}
+          </button>
+        </span>
+      ),
+      { duration: 15000 }
+    );
+  }, [session, isIdle]);
+
   // Don't render anything if not playing
   if (!isPlaying) return null;
 
+  const cost = session ? sessionCost(session) : null;
+
   // Determine latency quality and color
   const getLatencyColor = () => {
     if (!latency || !isConnected) return '#888888'; // Gray for disconnected
@@ -137,8 +225,8 @@ const LatencyDisplay = ({ gamePublicKey, isPlaying }) => {
             width: '8px',
             height: '8px',
             borderRadius: '50%',
-            backgroundColor: isConnected ? '#00f294' : '#ff4444',
-            boxShadow: isConnected ? '0 0 8px #00f294' : '0 0 8px #ff4444',
+            backgroundColor: !isConnected ? '#ff4444' : isDegraded ? '#ffaa00' : '#00f294',
+            boxShadow: `0 0 8px ${!isConnected ? '#ff4444' : isDegraded ? '#ffaa00' : '#00f294'}`,
             animation: isConnected ? 'pulse 2s ease-in-out infinite' : 'none',
           }}
         />
@@ -150,7 +238,7 @@ const LatencyDisplay = ({ gamePublicKey, isPlaying }) => {
             letterSpacing: '0.5px',
           }}
         >
-          {isConnected ? 'CONNECTED' : 'DISCONNECTED'}
+          {!isConnected ? 'DISCONNECTED' : isDegraded ? 'DEGRADED' : 'CONNECTED'}
         </div>
       </div>
 
@@ -165,7 +253,7 @@ const LatencyDisplay = ({ gamePublicKey, isPlaying }) => {
           opacity: 0.8,
         }}
       >
-        EPHEMERAL RPC
+        {isDegraded ? 'HTTP POLLING (WEBSOCKET BLOCKED)' : 'EPHEMERAL RPC'}
       </div>
 
       {/* Latency Value */}
@@ -211,6 +299,23 @@ const LatencyDisplay = ({ gamePublicKey, isPlaying }) => {
         {latencyLabel}
       </div>
 
+      {/* Session cost so far */}
+      {cost && (
+        <div
+          style={{
+            color: '#c8c8dc',
+            fontSize: '10px',
+            marginTop: '8px',
+            paddingTop: '6px',
+            borderTop: '1px solid rgba(200, 200, 220, 0.2)',
+          }}
+        >
+          SESSION COST {formatSol(cost.spent)}
+          <br />
+          {cost.txCount} tx{cost.perTx !== null && ` · ${formatSol(cost.perTx)}/tx`}
+        </div>
+      )}
+
       {/* Pulse animation for connection indicator */}
       <style>
         {`
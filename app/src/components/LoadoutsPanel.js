@@ -0,0 +1,170 @@
+import React, { useState } from 'react';
+import './LoadoutsPanel.css';
+import {
+  WEAPONS,
+  LOADOUT_MODES,
+  MAX_PRESETS,
+  loadLoadouts,
+  saveLoadouts,
+  normalizePreset,
+} from '../utils/loadouts';
+
+/**
+ * Weapons tab
+ * Named loadout presets, plus which one to auto-select per mode and per map
+ */
+function LoadoutsPanel() {
+  const [loadouts, setLoadouts] = useState(loadLoadouts);
+  const [newMapId, setNewMapId] = useState('');
+
+  const update = (next) => {
+    setLoadouts(next);
+    saveLoadouts(next);
+  };
+
+  const updatePreset = (id, changes) => {
+    update({
+      ...loadouts,
+      presets: loadouts.presets.map((preset) => (preset.id === id ? normalizePreset({ ...preset, ...changes }) : preset)),
+    });
+  };
+
+  const toggleWeapon = (preset, weaponId) => {
+    const weapons = preset.weapons.includes(weaponId)
+      ? preset.weapons.filter((id) => id !== weaponId)
+      : [...preset.weapons, weaponId];
+    // A preset always carries at least one weapon
+    if (weapons.length > 0) updatePreset(preset.id, { weapons });
+  };
+
+  const addPreset = () => {
+    const id = `preset-${Date.now()}`;
+    const name = `Loadout ${loadouts.presets.length + 1}`;
+    update({ ...loadouts, presets: [...loadouts.presets, normalizePreset({ id, name, weapons: ['rifle'], primary: 'rifle' })] });
+  };
+
+  const deletePreset = (id) => {
+    // Auto-selections pointing at it fall back to the first preset
+    const without = (assignments) => Object.fromEntries(Object.entries(assignments).filter(([, presetId]) => presetId !== id));
+    update({
+      presets: loadouts.presets.filter((preset) => preset.id !== id),
+      byMode: without(loadouts.byMode),
+      byMap: without(loadouts.byMap),
+    });
+  };
+
+  const assign = (field, key, presetId) => {
+    const assignments = { ...loadouts[field] };
+    if (presetId) {
+      assignments[key] = presetId;
+    } else {
+      delete assignments[key];
+    }
+    update({ ...loadouts, [field]: assignments });
+  };
+
+  const addMapRule = () => {
+    const mapId = newMapId.trim();
+    if (!mapId) return;
+    assign('byMap', mapId, loadouts.presets[0].id);
+    setNewMapId('');
+  };
+
+  const presetSelect = (value, onChange, emptyLabel) => (
+    <select className="loadouts-select" value={value || ''} onChange={(e) => onChange(e.target.value)}>
+      <option value="">{emptyLabel}</option>
+      {loadouts.presets.map((preset) => (
+        <option key={preset.id} value={preset.id}>{preset.name}</option>
+      ))}
+    </select>
+  );
+
+  return (
+    <div className="loadouts">
+      <div className="loadouts-header">
+        <h2 className="loadouts-title">🔫 Weapons</h2>
+        <button className="hud-button" onClick={addPreset} disabled={loadouts.presets.length >= MAX_PRESETS}>
+          + New Loadout
+        </button>
+      </div>
+
+      <div className="loadouts-section">
+        {loadouts.presets.map((preset, index) => (
+          <div key={preset.id} className="loadouts-preset">
+            <input
+              className="loadouts-name"
+              value={preset.name}
+              maxLength={24}
+              onChange={(e) => updatePreset(preset.id, { name: e.target.value })}
+            />
+            <div className="loadouts-weapons">
+              {WEAPONS.map((weapon) => (
+                <label key={weapon.id} className="loadouts-weapon">
+                  <input
+                    type="checkbox"
+                    checked={preset.weapons.includes(weapon.id)}
+                    onChange={() => toggleWeapon(preset, weapon.id)}
+                  />
+                  {weapon.name}
+                </label>
+              ))}
+            </div>
+            <label className="loadouts-primary">
+              Spawn with
+              <select
+                className="loadouts-select"
+                value={preset.primary}
+                onChange={(e) => updatePreset(preset.id, { primary: e.target.value })}
+              >
+                {WEAPONS.filter((weapon) => preset.weapons.includes(weapon.id)).map((weapon) => (
+                  <option key={weapon.id} value={weapon.id}>{weapon.name}</option>
+                ))}
+              </select>
+            </label>
+            {index === 0 ? (
+              <span className="loadouts-default">Default</span>
+            ) : (
+              <button className="loadouts-delete" onClick={() => deletePreset(preset.id)} title="Delete loadout">
+                ✕
+              </button>
+            )}
+          </div>
+        ))}
+      </div>
+
+      <h3 className="loadouts-subtitle">Auto-select by mode</h3>
+      <div className="loadouts-section">
+        {LOADOUT_MODES.map((mode) => (
+          <div key={mode.id} className="loadouts-rule">
+            <span className="loadouts-rule-name">{mode.name}</span>
+            {presetSelect(loadouts.byMode[mode.id], (presetId) => assign('byMode', mode.id, presetId), 'Default')}
+          </div>
+        ))}
+      </div>
+
+      <h3 className="loadouts-subtitle">Auto-select by map (overrides the mode)</h3>
+      <div className="loadouts-section">
+        {Object.entries(loadouts.byMap).map(([mapId, presetId]) => (
+          <div key={mapId} className="loadouts-rule">
+            <span className="loadouts-rule-name">{mapId}</span>
+            {presetSelect(presetId, (next) => assign('byMap', mapId, next), 'Remove')}
+          </div>
+        ))}
+        <div className="loadouts-rule">
+          <input
+            className="loadouts-name"
+            placeholder="Map ID"
+            value={newMapId}
+            onChange={(e) => setNewMapId(e.target.value)}
+            onKeyDown={(e) => e.key === 'Enter' && addMapRule()}
+          />
+          <button className="hud-button" onClick={addMapRule} disabled={!newMapId.trim()}>
+            Add Map
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
+
+export default LoadoutsPanel;
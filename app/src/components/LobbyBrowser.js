@@ -1,5 +1,7 @@
 import React, { useState, useEffect } from "react";
 import "./LobbyBrowser.css";
+import { formatPlays, sortMapsByPlays } from "../utils/map-plays";
+import { isMapShareCode, normalizeShareCode } from "../utils/map-seed";
 
 /**
  * LobbyBrowser - Main lobby interface showing available games
@@ -11,11 +13,15 @@ function LobbyBrowser({
   onRefresh,
   onCreateRoom,
   onJoinRoom,
+  onQuickPlay,
   onJoinAsSpectator,
+  liveGames = [],
+  onSpectateLive,
   onClose,
 }) {
   const [showCreateModal, setShowCreateModal] = useState(false);
   const [selectedMap, setSelectedMap] = useState("");
+  const [shareCode, setShareCode] = useState("");
   const [maxPlayers, setMaxPlayers] = useState(10);
   const [availableMaps, setAvailableMaps] = useState([]);
   const [defaultMaps, setDefaultMaps] = useState([]);
@@ -96,12 +102,20 @@ function LobbyBrowser({
         console.log("ℹ️ No user maps found");
       }
 
+      // Attach play counts so the most played maps come first
+      const playCounts = await window.solanaMapBridge.getMapPlayCounts(
+        [...fetchedDefaultMaps, ...fetchedUserMaps].map((map) => map.id)
+      );
+      const withPlays = (map) => ({ ...map, playCount: playCounts[map.id] });
+      const sortedDefaultMaps = sortMapsByPlays(fetchedDefaultMaps.map(withPlays));
+      const sortedUserMaps = sortMapsByPlays(fetchedUserMaps.map(withPlays));
+
       // Store maps separately
-      setDefaultMaps(fetchedDefaultMaps);
-      setUserMaps(fetchedUserMaps);
+      setDefaultMaps(sortedDefaultMaps);
+      setUserMaps(sortedUserMaps);
       
       // Combine all maps for availability check
-      const allMaps = [...fetchedDefaultMaps, ...fetchedUserMaps];
+      const allMaps = [...sortedDefaultMaps, ...sortedUserMaps];
       setAvailableMaps(allMaps);
 
       // Set the first map as selected if any exist
@@ -125,14 +139,23 @@ function LobbyBrowser({
   };
 
   const handleCreateRoom = () => {
-    if (!selectedMap) {
+    // A share code overrides the selected map: everyone generates the same arena locally
+    const code = normalizeShareCode(shareCode);
+    if (code && !isMapShareCode(code)) {
+      alert("That share code isn't valid. Copy it from the Generate section of the Map Editor.");
+      return;
+    }
+    const map = code || selectedMap;
+    if (!map) {
       alert("Please select a map");
       return;
     }
-    onCreateRoom(selectedMap, maxPlayers);
+    onCreateRoom(map, maxPlayers);
     setShowCreateModal(false);
   };
 
+  const canCreate = !loadingMaps && (availableMaps.length > 0 || isMapShareCode(shareCode));
+
   return (
     <div className="lobby-browser">
       {/* Header */}
@@ -152,6 +175,15 @@ function LobbyBrowser({
         >
           + CREATE ROOM
         </button>
+        {onQuickPlay && (
+          <button
+            className="btn btn-primary"
+            onClick={onQuickPlay}
+            disabled={loading}
+          >
+            ⚡ QUICK PLAY
+          </button>
+        )}
         <button
           className="btn btn-secondary"
           onClick={onRefresh}
@@ -262,6 +294,43 @@ function LobbyBrowser({
         )}
       </div>
 
+      {/* Live Matches - read-only spectating of games in progress */}
+      {onSpectateLive && !loading && liveGames.length > 0 && (
+        <div className="room-list live-match-list">
+          <h3 className="live-match-title">📺 LIVE MATCHES</h3>
+          {liveGames.map((game, index) => (
+            <div key={game.publicKey || index} className="room-card">
+              <div className="room-info">
+                <h3 className="room-name">
+                  {game.hostUsername && game.hostUsername !== "Unknown"
+                    ? `${game.hostUsername}'s Match`
+                    : `Match #${index + 1}`}
+                </h3>
+                <div className="room-details">
+                  <span className="room-detail">
+                    🗺️ {game.mapId || game.map_id || "Default Map"}
+                  </span>
+                  <span className="room-detail">
+                    🔴 {game.teamAScore || 0} - {game.teamBScore || 0} 🔵
+                  </span>
+                  <span className="room-detail">
+                    👥 {game.totalPlayers || game.current_players || 0}
+                  </span>
+                </div>
+              </div>
+              <div className="room-actions">
+                <button
+                  className="btn btn-secondary"
+                  onClick={() => onSpectateLive(game)}
+                >
+                  👁️ SPECTATE
+                </button>
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+
       {/* Create Room Modal */}
       {showCreateModal && (
         <div
@@ -290,6 +359,7 @@ function LobbyBrowser({
                         {defaultMaps.map((map) => (
                           <option key={map.id} value={map.id}>
                             {map.name}
+                            {formatPlays(map) && ` (${formatPlays(map)})`}
                           </option>
                         ))}
                       </optgroup>
@@ -299,6 +369,7 @@ function LobbyBrowser({
                         {userMaps.map((map) => (
                           <option key={map.id} value={map.id}>
                             {map.name}
+                            {formatPlays(map) && ` (${formatPlays(map)})`}
                           </option>
                         ))}
                       </optgroup>
@@ -320,6 +391,22 @@ function LobbyBrowser({
               )}
             </div>
 
+            <div className="form-group">
+              <label>Or a Generated Arena (share code)</label>
+              <input
+                type="text"
+                placeholder="e.g. arena:1f3a9c:3,6,3"
+                value={shareCode}
+                onChange={(e) => setShareCode(e.target.value)}
+                className="form-input"
+              />
+              {shareCode.trim() && !isMapShareCode(shareCode) && (
+                <div className="form-warning">
+                  ⚠️ Not a share code. Generate an arena in the Map Editor and copy its code.
+                </div>
+              )}
+            </div>
+
             <div className="form-group">
               <label>Max Players</label>
               <input
@@ -336,13 +423,10 @@ function LobbyBrowser({
               <button
                 className="btn btn-primary"
                 onClick={handleCreateRoom}
-                disabled={loadingMaps || availableMaps.length === 0}
+                disabled={!canCreate}
                 style={{
-                  opacity: loadingMaps || availableMaps.length === 0 ? 0.5 : 1,
-                  cursor:
-                    loadingMaps || availableMaps.length === 0
-                      ? "not-allowed"
-                      : "pointer",
+                  opacity: canCreate ? 1 : 0.5,
+                  cursor: canCreate ? "pointer" : "not-allowed",
                 }}
               >
                 CREATE
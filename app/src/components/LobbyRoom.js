@@ -1,5 +1,20 @@
-import React from 'react';
+import React, { useEffect, useRef, useState } from 'react';
 import './LobbyRoom.css';
+import PlayerName from './PlayerName';
+import PingBars from './PingBars';
+import { filterChatMessage } from '../utils/chat-filter';
+import {
+  LOBBY_CHAT_MAX_LENGTH,
+  CHAT_SCOPE_ALL,
+  CHAT_SCOPE_TEAM,
+  createChatMessage,
+  isVisibleTo,
+  appendChatMessage
+} from '../utils/lobby-chat';
+import { PRESENCE_INTERVAL_MS, presencePing } from '../utils/ping';
+
+// How often the lobby re-checks the map version while waiting
+const MAP_VERSION_POLL_MS = 15000;
 
 /**
  * LobbyRoom - In-lobby view showing teams and ready states
@@ -12,7 +27,12 @@ function LobbyRoom({
   playerReady,
   onToggleReady,
   onStartGame,
-  onLeaveLobby
+  onLeaveLobby,
+  mutedPlayers = [],
+  onToggleMute,
+  profanityFilter = true,
+  botBackfill = false,
+  onToggleBotBackfill
 }) {
   const {
     lobbyName = 'Game Lobby',
@@ -22,11 +42,116 @@ function LobbyRoom({
     spectators = [],
     teamAReady = [],
     teamBReady = [],
-    maxPlayers = 10
+    maxPlayers = 10,
+    gamePublicKey = null
   } = lobbyData || {};
 
   const totalPlayers = teamA.length + teamB.length;
 
+  // Slots the leader's bots will take when the match starts (only the leader runs them)
+  const slotsPerTeam = Math.floor(maxPlayers / 2);
+  const botSlots = (team) => (isLeader && botBackfill ? Math.max(0, slotsPerTeam - team.length) : 0);
+
+  // Fingerprint of the map data when we entered the lobby, and the latest one
+  // seen on-chain. If the creator republishes the map while we wait, the match
+  // will load the new version, so say so instead of surprising everyone at start.
+  const [mapVersion, setMapVersion] = useState(null);
+  const joinedMapVersion = useRef(null);
+
+  useEffect(() => {
+    joinedMapVersion.current = null;
+    setMapVersion(null);
+    const bridge = window.solanaMapBridge;
+    if (!bridge || !bridge.getMapFingerprint) return;
+
+    let cancelled = false;
+    const checkVersion = async () => {
+      const version = await bridge.getMapFingerprint(mapName);
+      if (cancelled || !version) return;
+      if (joinedMapVersion.current === null) {
+        joinedMapVersion.current = version;
+      }
+      setMapVersion(version);
+    };
+
+    checkVersion();
+    const interval = setInterval(checkVersion, MAP_VERSION_POLL_MS);
+    return () => {
+      cancelled = true;
+      clearInterval(interval);
+    };
+  }, [mapName]);
+
+  const mapUpdated = mapVersion !== null && mapVersion !== joinedMapVersion.current;
+
+  // Lobby chat (history is local to this lobby visit)
+  const myTeam = teamA.includes(currentPlayer) ? 'A' : teamB.includes(currentPlayer) ? 'B' : null;
+  const [chatMessages, setChatMessages] = useState([]);
+  const [chatInput, setChatInput] = useState('');
+  const [chatScope, setChatScope] = useState(CHAT_SCOPE_ALL);
+  const chatChannel = useRef(null);
+  const chatLogEnd = useRef(null);
+
+  useEffect(() => {
+    setChatMessages([]);
+    if (!gamePublicKey || !window.gameBridge?.openLobbyChat) return;
+    const channel = window.gameBridge.openLobbyChat(gamePublicKey, (message) => {
+      setChatMessages((history) => appendChatMessage(history, message));
+    });
+    chatChannel.current = channel;
+    return () => {
+      channel?.close();
+      chatChannel.current = null;
+    };
+  }, [gamePublicKey]);
+
+  // Presence beats: everyone announces themselves over the game relay every couple
+  // of seconds, so a player who stops beating shows up as lagging next to their name
+  const [presence, setPresence] = useState({});
+  const [presenceNow, setPresenceNow] = useState(Date.now());
+
+  useEffect(() => {
+    setPresence({});
+    if (!gamePublicKey || !currentPlayer || !window.gameBridge?.openLobbyPresence) return;
+    const channel = window.gameBridge.openLobbyPresence(gamePublicKey, (beat) => {
+      setPresence((beats) => ({
+        ...beats,
+        [beat.sender]: { receivedAt: Date.now(), latency: typeof beat.latency === 'number' ? beat.latency : null }
+      }));
+    });
+
+    const beat = () => {
+      const latency = window.gameBridge.getRelayLatency ? window.gameBridge.getRelayLatency() : null;
+      channel?.send({}); // Who sent it is the relay's to say
+      setPresence((beats) => ({ ...beats, [currentPlayer]: { receivedAt: Date.now(), latency } }));
+      setPresenceNow(Date.now());
+    };
+    beat();
+    const interval = setInterval(beat, PRESENCE_INTERVAL_MS);
+    return () => {
+      clearInterval(interval);
+      channel?.close();
+    };
+  }, [gamePublicKey, currentPlayer]);
+
+  useEffect(() => {
+    chatLogEnd.current?.scrollIntoView({ block: 'nearest' });
+  }, [chatMessages.length]);
+
+  const sendChat = (e) => {
+    e.preventDefault();
+    const message = createChatMessage(currentPlayer, myTeam, chatInput, chatScope);
+    if (!message) return;
+    chatChannel.current?.send(message);
+    setChatMessages((history) => appendChatMessage(history, message));
+    setChatInput('');
+  };
+
+  const visibleChat = chatMessages
+    .filter((message) => isVisibleTo(message, myTeam))
+    .map((message) => filterChatMessage(message, { profanityFilter, mutedPlayers }))
+    .filter(Boolean);
+
   // Check if all players are ready
   const allReadyStates = [...teamAReady, ...teamBReady];
   const allPlayersReady = totalPlayers >= 2 &&
@@ -44,6 +169,34 @@ function LobbyRoom({
     allPlayersReady
   });
 
+  // Mute toggle for everyone but ourselves (hides their chat, pings and voice-lines)
+  const renderMuteButton = (player) => {
+    if (player === currentPlayer || !onToggleMute) return null;
+    const muted = mutedPlayers.includes(player);
+    return (
+      <button
+        className={`mute-button ${muted ? 'muted' : ''}`}
+        onClick={() => onToggleMute(player)}
+        title={muted ? 'Unmute player' : 'Mute player'}
+      >
+        {muted ? '🔇' : '🔊'}
+      </button>
+    );
+  };
+
+  const renderBotCards = (team) =>
+    Array.from({ length: botSlots(team) }, (_, index) => (
+      <div key={`bot-${index}`} className="player-card bot-card">
+        <div className="player-info">
+          <span className="player-avatar">🤖</span>
+          <span className="player-name">BOT</span>
+        </div>
+        <div className="player-status">
+          <span className="status-waiting">Backfill</span>
+        </div>
+      </div>
+    ));
+
   return (
     <div className="lobby-room">
       {/* Header */}
@@ -64,6 +217,13 @@ function LobbyRoom({
         </div>
       </div>
 
+      {mapUpdated && (
+        <div className="map-updated-warning">
+          ⚠️ The map was updated after this lobby was set up. The match will
+          load the new version.
+        </div>
+      )}
+
       {/* Teams */}
       <div className="teams-container">
         {/* Team A */}
@@ -76,7 +236,7 @@ function LobbyRoom({
             <span className="team-count">{teamA.length} Players</span>
           </div>
           <div className="team-players">
-            {teamA.length === 0 ? (
+            {teamA.length === 0 && botSlots(teamA) === 0 ? (
               <div className="empty-team">Waiting for players...</div>
             ) : (
               teamA.map((player, index) => (
@@ -84,13 +244,15 @@ function LobbyRoom({
                   <div className="player-info">
                     <span className="player-avatar">👤</span>
                     <span className="player-name">
-                      {player}
+                      <PlayerName username={player} />
                       {player === currentPlayer && (
                         <span className="you-badge"> (You)</span>
                       )}
+                      <PingBars ms={presencePing(presence[player], presenceNow)} />
                     </span>
                   </div>
                   <div className="player-status">
+                    {renderMuteButton(player)}
                     {teamAReady[index] ? (
                       <span className="status-ready">✓ Ready</span>
                     ) : (
@@ -100,6 +262,7 @@ function LobbyRoom({
                 </div>
               ))
             )}
+            {renderBotCards(teamA)}
           </div>
         </div>
 
@@ -118,7 +281,7 @@ function LobbyRoom({
             <span className="team-count">{teamB.length} Players</span>
           </div>
           <div className="team-players">
-            {teamB.length === 0 ? (
+            {teamB.length === 0 && botSlots(teamB) === 0 ? (
               <div className="empty-team">Waiting for players...</div>
             ) : (
               teamB.map((player, index) => (
@@ -126,13 +289,15 @@ function LobbyRoom({
                   <div className="player-info">
                     <span className="player-avatar">👤</span>
                     <span className="player-name">
-                      {player}
+                      <PlayerName username={player} />
                       {player === currentPlayer && (
                         <span className="you-badge"> (You)</span>
                       )}
+                      <PingBars ms={presencePing(presence[player], presenceNow)} />
                     </span>
                   </div>
                   <div className="player-status">
+                    {renderMuteButton(player)}
                     {teamBReady[index] ? (
                       <span className="status-ready">✓ Ready</span>
                     ) : (
@@ -142,6 +307,7 @@ function LobbyRoom({
                 </div>
               ))
             )}
+            {renderBotCards(teamB)}
           </div>
         </div>
       </div>
@@ -161,7 +327,7 @@ function LobbyRoom({
               <div key={index} className="spectator-item">
                 <span className="spectator-avatar">👤</span>
                 <span className="spectator-name">
-                  {spectator}
+                  <PlayerName username={spectator} />
                   {spectator === currentPlayer && (
                     <span className="you-badge"> (You)</span>
                   )}
@@ -172,6 +338,57 @@ function LobbyRoom({
         </div>
       )}
 
+      {/* Lobby Chat */}
+      <div className="lobby-chat">
+        <div className="lobby-chat-log">
+          {visibleChat.length === 0 ? (
+            <div className="lobby-chat-empty">No messages yet - say hi to your team</div>
+          ) : (
+            visibleChat.map((message) => (
+              <div key={message.id} className={`lobby-chat-line team-${message.team || 'none'}`}>
+                {message.scope === CHAT_SCOPE_TEAM && <span className="lobby-chat-scope">[TEAM]</span>}
+                <span className="lobby-chat-sender">
+                  <PlayerName username={message.sender} />:
+                </span>
+                <span className="lobby-chat-text">{message.text}</span>
+              </div>
+            ))
+          )}
+          <div ref={chatLogEnd} />
+        </div>
+        <form className="lobby-chat-input" onSubmit={sendChat}>
+          {myTeam && (
+            <button
+              type="button"
+              className={`lobby-chat-scope-toggle ${chatScope === CHAT_SCOPE_TEAM ? 'active' : ''}`}
+              onClick={() => setChatScope(chatScope === CHAT_SCOPE_TEAM ? CHAT_SCOPE_ALL : CHAT_SCOPE_TEAM)}
+              title="Send to everyone or only your team"
+            >
+              {chatScope === CHAT_SCOPE_TEAM ? 'TEAM' : 'ALL'}
+            </button>
+          )}
+          <input
+            type="text"
+            value={chatInput}
+            maxLength={LOBBY_CHAT_MAX_LENGTH}
+            onChange={(e) => setChatInput(e.target.value)}
+            placeholder={gamePublicKey ? 'Type a message...' : 'Chat unavailable for this lobby'}
+            disabled={!gamePublicKey}
+          />
+          <button type="submit" className="btn" disabled={!gamePublicKey || !chatInput.trim()}>
+            Send
+          </button>
+        </form>
+      </div>
+
+      {/* Bot backfill (leader only: the bots run on the leader's client) */}
+      {isLeader && onToggleBotBackfill && (
+        <label className="bot-backfill-toggle" title="Bots take the empty slots when the match starts">
+          <input type="checkbox" checked={botBackfill} onChange={onToggleBotBackfill} />
+          🤖 Fill empty slots with bots
+        </label>
+      )}
+
       {/* Actions */}
       <div className="lobby-room-actions">
         {isLeader ? (
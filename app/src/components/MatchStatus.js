@@ -1,5 +1,13 @@
-import React, { useState, useEffect } from 'react';
+import React, { useState, useEffect, useRef } from 'react';
 import './MatchStatus.css';
+import { getMatchRules, initialMatchState, advanceMatch, overtimeLabel } from '../utils/match-rules';
+import { playStartTime } from '../utils/match-start';
+
+// Format seconds as m:ss
+const formatClock = (seconds) => {
+  const clamped = Math.max(0, Math.floor(seconds));
+  return `${Math.floor(clamped / 60)}:${String(clamped % 60).padStart(2, '0')}`;
+};
 
 /**
  * MatchStatus Component
@@ -12,9 +20,57 @@ function MatchStatus({ gamePublicKey, currentGameState, onGameEnd }) {
     teamBScore: 0,
     timeRemaining: '5:00',
     gameMode: 'Team Deathmatch',
+    overtime: null,
     players: []
   });
   const [hasEnded, setHasEnded] = useState(false);
+  const [rules, setRules] = useState(() => getMatchRules());
+
+  // Overtime state machine and on-chain match timing survive re-renders
+  const matchStateRef = useRef(initialMatchState());
+  const matchTimingRef = useRef(null);
+  // Seconds the match clock stood still during voted timeouts
+  const timeoutSecondsRef = useRef(0);
+
+  // New match: start the rules state machine over
+  useEffect(() => {
+    matchStateRef.current = initialMatchState();
+    matchTimingRef.current = null;
+    timeoutSecondsRef.current = 0;
+    setHasEnded(false);
+  }, [gamePublicKey]);
+
+  // Voted timeouts stop the clock; a passed surrender vote ends the match
+  useEffect(() => {
+    if (!gamePublicKey || currentGameState !== 1 || hasEnded) {
+      return;
+    }
+
+    const handleTimeout = (event) => {
+      timeoutSecondsRef.current += event.detail?.seconds || 0;
+    };
+    const handleSurrender = (event) => {
+      const winningTeam = event.detail?.winningTeam;
+      console.log(`🏳️ Team ${winningTeam === 'A' ? 'B' : 'A'} surrendered`);
+      setHasEnded(true);
+      if (onGameEnd) {
+        onGameEnd({
+          winningTeam,
+          teamAScore: matchData.teamAScore,
+          teamBScore: matchData.teamBScore,
+          mvpPlayer: null,
+          surrendered: true
+        });
+      }
+    };
+
+    window.addEventListener('matchTimeout', handleTimeout);
+    window.addEventListener('matchSurrendered', handleSurrender);
+    return () => {
+      window.removeEventListener('matchTimeout', handleTimeout);
+      window.removeEventListener('matchSurrendered', handleSurrender);
+    };
+  }, [gamePublicKey, currentGameState, hasEnded, onGameEnd, matchData.teamAScore, matchData.teamBScore]);
 
   useEffect(() => {
     if (!gamePublicKey || currentGameState !== 1) {
@@ -34,7 +90,11 @@ function MatchStatus({ gamePublicKey, currentGameState, onGameEnd }) {
           let teamAScore = 0;
           let teamBScore = 0;
 
+          // Assists are tracked client-side by the game (see DamageTracker)
+          const assistCounts = window.___player_assists || {};
+
           players.forEach(player => {
+            player.assists = assistCounts[player.authority] || 0;
             const kills = player.kills || 0;
             // Team is now u8: 1 = Team A (Blue), 2 = Team B (Red)
             const teamName = player.team === 1 ? 'A' : 'B';
@@ -48,19 +108,56 @@ function MatchStatus({ gamePublicKey, currentGameState, onGameEnd }) {
 
           console.log(`📊 Final Scores - Team A: ${teamAScore}, Team B: ${teamBScore}`);
 
+          // Match start / duration come from the game account (fetched once)
+          if (!matchTimingRef.current && window.gameBridge.getGame) {
+            const game = await window.gameBridge.getGame(gamePublicKey);
+            const toNumber = (value) => Number(value?.toString ? value.toString() : value);
+            if (game) {
+              matchTimingRef.current = {
+                // The clock starts when everyone enters the match, after the start countdown
+                start: toNumber(game.matchStartTimestamp)
+                  ? playStartTime(toNumber(game.matchStartTimestamp))
+                  : Date.now() / 1000,
+                duration: toNumber(game.matchDuration) || null,
+              };
+            }
+          }
+          const timing = matchTimingRef.current || { start: Date.now() / 1000, duration: null };
+          const matchRules = getMatchRules('team_deathmatch', timing.duration);
+          setRules(matchRules);
+
+          const elapsed = Date.now() / 1000 - timing.start - timeoutSecondsRef.current;
+          const state = advanceMatch(matchStateRef.current, { a: teamAScore, b: teamBScore }, elapsed, matchRules);
+          if (state.phase === 'overtime' && matchStateRef.current.period !== state.period) {
+            console.log(`⏱️ Scores tied - ${overtimeLabel(state, matchRules)}`);
+          }
+          matchStateRef.current = state;
+
+          let timeRemaining;
+          // Seconds left are also read by the session wallet top-up projection
+          window.___match_seconds_remaining = null;
+          if (state.phase === 'regulation') {
+            window.___match_seconds_remaining = Math.max(0, matchRules.timeLimit - elapsed);
+            timeRemaining = formatClock(matchRules.timeLimit - elapsed);
+          } else if (state.phase === 'overtime' && !state.suddenDeath) {
+            timeRemaining = formatClock(matchRules.overtime.periodLength - (elapsed - state.periodStartedAt));
+          } else {
+            timeRemaining = '--:--';
+          }
+
           setMatchData({
             teamAScore,
             teamBScore,
-            timeRemaining: '5:00', // TODO: Get from game contract
-            gameMode: 'Team Deathmatch - First to 40',
+            timeRemaining,
+            gameMode: `Team Deathmatch - First to ${matchRules.scoreLimit}`,
+            overtime: overtimeLabel(state, matchRules),
             players
           });
 
-          // Check for win condition (40 kills)
-          const WIN_THRESHOLD = 40;
-          if ((teamAScore >= WIN_THRESHOLD || teamBScore >= WIN_THRESHOLD) && !hasEnded) {
+          // Score limit, time limit or an overtime decision ended the match
+          if (state.phase === 'ended' && !hasEnded) {
             setHasEnded(true);
-            const winningTeam = teamAScore >= WIN_THRESHOLD ? 'A' : 'B';
+            const winningTeam = state.winningTeam;
 
             // Find MVP (player with most kills)
             let mvpPlayer = null;
@@ -78,7 +175,8 @@ function MatchStatus({ gamePublicKey, currentGameState, onGameEnd }) {
                 winningTeam,
                 teamAScore,
                 teamBScore,
-                mvpPlayer
+                mvpPlayer,
+                overtimePeriods: state.period
               });
             }
           }
@@ -102,7 +200,7 @@ function MatchStatus({ gamePublicKey, currentGameState, onGameEnd }) {
     return null;
   }
 
-  const WIN_THRESHOLD = 40;
+  const WIN_THRESHOLD = rules.scoreLimit;
   const teamAProgress = (matchData.teamAScore / WIN_THRESHOLD) * 100;
   const teamBProgress = (matchData.teamBScore / WIN_THRESHOLD) * 100;
 
@@ -125,7 +223,10 @@ function MatchStatus({ gamePublicKey, currentGameState, onGameEnd }) {
         <div className="match-center">
           <div className="match-mode">{matchData.gameMode}</div>
           <div className="match-time">{matchData.timeRemaining}</div>
-          {(matchData.teamAScore >= WIN_THRESHOLD || matchData.teamBScore >= WIN_THRESHOLD) && (
+          {matchData.overtime && !hasEnded && (
+            <div className="match-overtime">{matchData.overtime}</div>
+          )}
+          {hasEnded && (
             <div className="match-ending">MATCH ENDING...</div>
           )}
         </div>
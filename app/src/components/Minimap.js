@@ -10,6 +10,8 @@ const Minimap = ({ gamePublicKey }) => {
   const [players, setPlayers] = useState([]);
   const [currentPlayer, setCurrentPlayer] = useState(null);
   const [mapObjects, setMapObjects] = useState([]);
+  // Share of a streamed map received (0 to 1), or -1 once the whole map is in
+  const [streamProgress, setStreamProgress] = useState(-1);
 
   // Map configuration (must match game world coordinates)
   const MAP_SIZE = 50.0; // World map is -25 to +25 on X and Z
@@ -43,34 +45,49 @@ const Minimap = ({ gamePublicKey }) => {
           console.warn('[Minimap] Failed to get player position from Rust:', error);
         }
       }
+
+      // Chunks of a large map still arriving (see streamMapChunks in game-bridge.js)
+      if (window.Module && window.Module._get_map_stream_progress) {
+        setStreamProgress(window.Module._get_map_stream_progress());
+      }
     }, 50); // Update 20 times per second
 
     return () => clearInterval(updateInterval);
   }, []);
 
-  // Get other players' positions from WebSocket updates
+  // Get other players from Rust (fog-of-war applied: teammates + spotted/last-seen enemies)
   useEffect(() => {
     if (!gamePublicKey) return;
 
+    const MAX_PLAYERS = 32;
+    const FLOATS_PER_PLAYER = 6; // x, z, yaw, team, alive, visibility
+
     const updateInterval = setInterval(() => {
-      if (window.___websocket_player_updates) {
-        const updates = window.___websocket_player_updates;
-        const playerList = [];
+      if (!window.Module || !window.Module._get_other_players) return;
 
-        for (const [accountPubkey, update] of Object.entries(updates)) {
-          if (update.parsed) {
-            playerList.push({
-              publicKey: accountPubkey,
-              x: update.parsed.positionX || 0,
-              z: update.parsed.positionZ || 0,
-              yaw: update.parsed.rotationY || 0,
-              team: update.parsed.team || 0,
-              isAlive: update.parsed.isAlive !== undefined ? update.parsed.isAlive : true,
-            });
-          }
+      try {
+        const bufPtr = window.Module._malloc(MAX_PLAYERS * FLOATS_PER_PLAYER * 4);
+        const count = window.Module._get_other_players(bufPtr, MAX_PLAYERS);
+
+        const HEAPF32 = window.Module.HEAPF32;
+        const offset = bufPtr / 4;
+        const playerList = [];
+        for (let i = 0; i < count; i++) {
+          const base = offset + i * FLOATS_PER_PLAYER;
+          playerList.push({
+            x: HEAPF32[base],
+            z: HEAPF32[base + 1],
+            yaw: HEAPF32[base + 2],
+            team: Math.round(HEAPF32[base + 3]),
+            isAlive: HEAPF32[base + 4] > 0.5,
+            visibility: HEAPF32[base + 5],
+          });
         }
 
+        window.Module._free(bufPtr);
         setPlayers(playerList);
+      } catch (error) {
+        console.warn('[Minimap] Failed to get other players from Rust:', error);
       }
     }, 100); // Update 10 times per second
 
@@ -255,6 +272,18 @@ const Minimap = ({ gamePublicKey }) => {
       // Determine player color based on team (Team 1 = Blue, Team 2 = Red)
       const playerColor = player.team === 1 ? 'rgba(0, 150, 255, 0.8)' : 'rgba(255, 50, 50, 0.8)';
 
+      // "Last seen" ghost: fading hollow ring, no direction
+      if (player.visibility < 1) {
+        ctx.globalAlpha = Math.max(0, player.visibility);
+        ctx.strokeStyle = playerColor;
+        ctx.lineWidth = 2;
+        ctx.beginPath();
+        ctx.arc(pos.x, pos.y, 5, 0, Math.PI * 2);
+        ctx.stroke();
+        ctx.globalAlpha = 1;
+        return;
+      }
+
       // Draw player dot
       ctx.fillStyle = playerColor;
       ctx.beginPath();
@@ -333,7 +362,28 @@ const Minimap = ({ gamePublicKey }) => {
       ctx.lineTo(dirEndX + Math.cos(angle2) * arrowSize, dirEndY + Math.sin(angle2) * arrowSize);
       ctx.stroke();
     }
-  }, [currentPlayer, players, mapObjects]);
+
+    // Loading ring around the edge while the rest of a large map streams in
+    if (streamProgress >= 0) {
+      const center = MINIMAP_SIZE / 2;
+      const radius = center - 4;
+      const start = -Math.PI / 2;
+      ctx.lineWidth = 3;
+      ctx.strokeStyle = 'rgba(255, 255, 255, 0.15)';
+      ctx.beginPath();
+      ctx.arc(center, center, radius, 0, Math.PI * 2);
+      ctx.stroke();
+      ctx.strokeStyle = 'rgba(0, 242, 148, 0.9)';
+      ctx.beginPath();
+      ctx.arc(center, center, radius, start, start + Math.PI * 2 * streamProgress);
+      ctx.stroke();
+
+      ctx.fillStyle = 'rgba(255, 255, 255, 0.8)';
+      ctx.font = '10px monospace';
+      ctx.textAlign = 'center';
+      ctx.fillText(`STREAMING MAP ${Math.round(streamProgress * 100)}%`, center, MINIMAP_SIZE - 14);
+    }
+  }, [currentPlayer, players, mapObjects, streamProgress]);
 
   return (
     <div className="minimap-container">
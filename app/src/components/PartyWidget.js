@@ -0,0 +1,68 @@
+import React, { useState } from "react";
+import "./PartyWidget.css";
+import { MAX_PARTY_SIZE } from "../utils/party";
+
+/**
+ * PartyWidget - Menu widget for forming a party before matchmaking
+ * Shows the party code and members; the leader's Quick Play / lobby joins
+ * bring every member along
+ */
+function PartyWidget({ party, currentPlayer, onCreateParty, onJoinParty, onLeaveParty, onQuickPlay }) {
+  const [code, setCode] = useState("");
+
+  if (!party) {
+    return (
+      <div className="party-widget">
+        <div className="party-title">👥 PARTY</div>
+        <button className="party-btn party-btn-primary" onClick={onCreateParty}>
+          Create Party
+        </button>
+        <div className="party-join-row">
+          <input
+            className="party-code-input"
+            placeholder="Party code"
+            value={code}
+            maxLength={6}
+            onChange={(e) => setCode(e.target.value.toUpperCase())}
+          />
+          <button className="party-btn" onClick={() => onJoinParty(code)} disabled={!code.trim()}>
+            Join
+          </button>
+        </div>
+      </div>
+    );
+  }
+
+  const isLeader = party.leader === currentPlayer;
+
+  return (
+    <div className="party-widget">
+      <div className="party-title">
+        👥 PARTY <span className="party-code">{party.id}</span>
+        <span className="party-size">
+          {party.members.length}/{MAX_PARTY_SIZE}
+        </span>
+      </div>
+      <div className="party-members">
+        {party.members.map((member) => (
+          <div key={member} className="party-member">
+            {member === party.leader ? "👑" : "👤"} {member}
+            {member === currentPlayer && <span className="party-you"> (You)</span>}
+          </div>
+        ))}
+      </div>
+      {isLeader ? (
+        <button className="party-btn party-btn-primary" onClick={onQuickPlay}>
+          ⚡ Quick Play
+        </button>
+      ) : (
+        <div className="party-hint">Waiting for {party.leader} to join a lobby...</div>
+      )}
+      <button className="party-btn party-btn-leave" onClick={onLeaveParty}>
+        {isLeader ? "Disband" : "Leave"}
+      </button>
+    </div>
+  );
+}
+
+export default PartyWidget;
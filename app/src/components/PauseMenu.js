@@ -1,7 +1,14 @@
-import React from 'react';
+import React, { useState } from 'react';
 import './PauseMenu.css';
 
-function PauseMenu({ onResume, onQuit }) {
+/**
+ * PauseMenu Component
+ * @param {Array<{authority: string, username: string}>} players - Other players in the match (kick targets)
+ * @param {Function} onCallVote - (kind, target) => void; kind is "timeout", "surrender" or "kick"
+ */
+function PauseMenu({ onResume, onQuit, players = [], onCallVote }) {
+  const [kickTarget, setKickTarget] = useState('');
+
   return (
     <div className="pause-menu-overlay">
       <div className="pause-menu">
@@ -19,6 +26,41 @@ function PauseMenu({ onResume, onQuit }) {
           </button>
         </div>
 
+        {onCallVote && (
+          <div className="pause-menu-votes">
+            <div className="pause-menu-votes-title">Call a vote</div>
+            <div className="pause-menu-votes-row">
+              <button className="pause-btn pause-btn-vote" onClick={() => onCallVote('timeout')}>
+                Timeout
+              </button>
+              <button className="pause-btn pause-btn-vote" onClick={() => onCallVote('surrender')}>
+                Surrender
+              </button>
+            </div>
+            <div className="pause-menu-votes-row">
+              <select
+                className="pause-vote-select"
+                value={kickTarget}
+                onChange={(e) => setKickTarget(e.target.value)}
+              >
+                <option value="">Select a player...</option>
+                {players.map((player) => (
+                  <option key={player.authority} value={player.authority}>
+                    {player.username || `${player.authority.slice(0, 8)}...`}
+                  </option>
+                ))}
+              </select>
+              <button
+                className="pause-btn pause-btn-vote"
+                disabled={!kickTarget}
+                onClick={() => onCallVote('kick', kickTarget)}
+              >
+                Kick
+              </button>
+            </div>
+          </div>
+        )}
+
         <div className="pause-menu-hint">
           <p>Press <span className="key-hint">ESC</span> to resume</p>
         </div>
@@ -0,0 +1,33 @@
+import React from 'react';
+import { pingBars, pingColor } from '../utils/ping';
+
+/**
+ * PingBars Component
+ * Four signal-style bars colored by latency, with the value in the tooltip
+ */
+function PingBars({ ms }) {
+  const bars = pingBars(ms);
+  const color = pingColor(bars);
+  const label = ms === null || ms === undefined ? 'No data' : `${Math.round(ms)} ms`;
+
+  return (
+    <span
+      title={label}
+      style={{ display: 'inline-flex', alignItems: 'flex-end', gap: '2px', height: '12px', marginLeft: '6px' }}
+    >
+      {[1, 2, 3, 4].map((bar) => (
+        <span
+          key={bar}
+          style={{
+            width: '3px',
+            height: `${bar * 3}px`,
+            borderRadius: '1px',
+            background: bar <= bars ? color : 'rgba(200, 200, 220, 0.25)',
+          }}
+        />
+      ))}
+    </span>
+  );
+}
+
+export default PingBars;
@@ -0,0 +1,18 @@
+import React from "react";
+import "./PlayerName.css";
+import { parseTaggedName } from "../utils/clan-tag";
+
+/**
+ * PlayerName - A username with its clan tag (if any) rendered as a dimmer prefix
+ */
+function PlayerName({ username }) {
+  const { tag, name } = parseTaggedName(username);
+  return (
+    <>
+      {tag && <span className="clan-tag">[{tag}]</span>}
+      {name}
+    </>
+  );
+}
+
+export default PlayerName;
@@ -0,0 +1,117 @@
+import React, { useState } from 'react';
+
+// Same order as BotDifficulty::ALL / BotRole::ALL on the game side
+const DIFFICULTIES = [
+  { value: 0, label: 'Easy', hint: 'Slow to react, rarely hits' },
+  { value: 1, label: 'Normal', hint: 'A fair warmup' },
+  { value: 2, label: 'Hard', hint: 'Quick, accurate and pushy' },
+  { value: 3, label: 'Expert', hint: 'Punishes every mistake' },
+];
+const ROLES = [
+  { value: -1, label: 'Mixed' },
+  { value: 0, label: 'Rushers' },
+  { value: 1, label: 'Anchors' },
+  { value: 2, label: 'Objective' },
+];
+const MAX_BOTS = 8;
+const STORAGE_KEY = 'practiceSetup';
+
+function loadSetup() {
+  try {
+    const saved = JSON.parse(localStorage.getItem(STORAGE_KEY));
+    if (saved && typeof saved === 'object') {
+      return { difficulty: 1, role: -1, bots: 3, ...saved };
+    }
+  } catch (e) {
+    // Fall through to the defaults
+  }
+  return { difficulty: 1, role: -1, bots: 3 };
+}
+
+const labelStyle = { color: '#c8c8dc', fontSize: '12px', fontWeight: 700, letterSpacing: '1px', marginBottom: '6px' };
+
+/**
+ * PracticeSetup Component
+ * Picks bot difficulty, role mix and count for an offline practice match;
+ * the last choice is remembered for the next warmup
+ */
+function PracticeSetup({ isOpen, onStart, onClose }) {
+  const [setup, setSetup] = useState(loadSetup);
+  if (!isOpen) return null;
+
+  const update = (changes) => setSetup((current) => ({ ...current, ...changes }));
+  const start = () => {
+    localStorage.setItem(STORAGE_KEY, JSON.stringify(setup));
+    onStart(setup);
+  };
+
+  const choiceButton = (selected) => ({
+    flex: 1,
+    padding: '8px 4px',
+    background: selected ? 'rgba(0, 242, 148, 0.15)' : 'transparent',
+    border: `1px solid ${selected ? '#00f294' : '#3a3a4a'}`,
+    color: selected ? '#00f294' : '#c8c8dc',
+    cursor: 'pointer',
+    fontWeight: 700,
+  });
+
+  return (
+    <div
+      onClick={onClose}
+      style={{
+        position: 'fixed',
+        inset: 0,
+        display: 'flex',
+        alignItems: 'center',
+        justifyContent: 'center',
+        background: 'rgba(13, 13, 17, 0.85)',
+        zIndex: 2000,
+      }}
+    >
+      <div
+        onClick={(e) => e.stopPropagation()}
+        style={{ width: '380px', padding: '24px', background: '#15151d', border: '1px solid #00f294' }}
+      >
+        <div style={{ color: '#00f294', fontSize: '20px', fontWeight: 900, marginBottom: '18px' }}>PRACTICE MATCH</div>
+
+        <div style={labelStyle}>DIFFICULTY</div>
+        <div style={{ display: 'flex', gap: '6px' }}>
+          {DIFFICULTIES.map((d) => (
+            <button key={d.value} style={choiceButton(setup.difficulty === d.value)} onClick={() => update({ difficulty: d.value })}>
+              {d.label}
+            </button>
+          ))}
+        </div>
+        <div style={{ color: '#8a8aa0', fontSize: '12px', margin: '6px 0 16px' }}>
+          {DIFFICULTIES.find((d) => d.value === setup.difficulty)?.hint}
+        </div>
+
+        <div style={labelStyle}>BOT ROLES</div>
+        <div style={{ display: 'flex', gap: '6px', marginBottom: '16px' }}>
+          {ROLES.map((r) => (
+            <button key={r.value} style={choiceButton(setup.role === r.value)} onClick={() => update({ role: r.value })}>
+              {r.label}
+            </button>
+          ))}
+        </div>
+
+        <div style={labelStyle}>BOTS: {setup.bots}</div>
+        <input
+          type="range"
+          min={1}
+          max={MAX_BOTS}
+          value={setup.bots}
+          onChange={(e) => update({ bots: Number(e.target.value) })}
+          style={{ width: '100%', marginBottom: '20px' }}
+        />
+
+        <div style={{ display: 'flex', gap: '8px', justifyContent: 'flex-end' }}>
+          <button className="btn btn-secondary" onClick={onClose}>Cancel</button>
+          <button className="btn btn-primary" onClick={start}>Start</button>
+        </div>
+      </div>
+    </div>
+  );
+}
+
+export default PracticeSetup;
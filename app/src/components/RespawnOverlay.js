@@ -1,4 +1,4 @@
-import React, { useState, useEffect } from 'react';
+import React, { useState, useEffect, useRef } from 'react';
 import './RespawnOverlay.css';
 
 /**
@@ -10,6 +10,7 @@ function RespawnOverlay() {
   const [isDead, setIsDead] = useState(false);
   const [deathTimestamp, setDeathTimestamp] = useState(0);
   const [remainingTime, setRemainingTime] = useState(3.0);
+  const lastTickRef = useRef(null);
 
   useEffect(() => {
     // Poll for death state from game
@@ -40,6 +41,21 @@ function RespawnOverlay() {
     return () => clearInterval(interval);
   }, []);
 
+  // Announcer countdown: tick on each whole second, "go" when it reaches zero
+  useEffect(() => {
+    if (!isDead) {
+      lastTickRef.current = null;
+      return;
+    }
+    const second = Math.ceil(remainingTime);
+    if (second !== lastTickRef.current) {
+      if (lastTickRef.current !== null) {
+        window.gameBridge?.playUiSound?.(second > 0 ? 'countdown_tick' : 'countdown_go');
+      }
+      lastTickRef.current = second;
+    }
+  }, [isDead, remainingTime]);
+
   if (!isDead) {
     return null;
   }
@@ -0,0 +1,120 @@
+import React, { useState, useEffect } from 'react';
+import './Scoreboard.css';
+import PingBars from './PingBars';
+
+// Refresh rate while the scoreboard is held open
+const SCOREBOARD_REFRESH_MS = 1000;
+
+/**
+ * Scoreboard Component
+ * Hold TAB during a match to see every player's K/D/A and ping.
+ * Ping is our RPC latency for ourselves and data staleness for everyone else
+ * (see utils/ping.js), so a lagging player stands out.
+ */
+function Scoreboard({ gamePublicKey, isPlaying }) {
+  const [visible, setVisible] = useState(false);
+  const [rows, setRows] = useState([]);
+
+  // Shown only while TAB is held
+  useEffect(() => {
+    if (!isPlaying) {
+      setVisible(false);
+      return;
+    }
+
+    const handleKeyDown = (event) => {
+      if (event.key === 'Tab') {
+        event.preventDefault();
+        setVisible(true);
+      }
+    };
+    const handleKeyUp = (event) => {
+      if (event.key === 'Tab') setVisible(false);
+    };
+
+    window.addEventListener('keydown', handleKeyDown);
+    window.addEventListener('keyup', handleKeyUp);
+    return () => {
+      window.removeEventListener('keydown', handleKeyDown);
+      window.removeEventListener('keyup', handleKeyUp);
+    };
+  }, [isPlaying]);
+
+  useEffect(() => {
+    if (!visible || !gamePublicKey || !window.gameBridge?.getGamePlayers) return;
+
+    let cancelled = false;
+    const refresh = async () => {
+      const players = await window.gameBridge.getGamePlayers(gamePublicKey);
+      if (cancelled || !Array.isArray(players)) return;
+
+      const localKey = window.gameBridge.getCurrentPlayerEphemeralKey?.();
+      const staleness = window.gameBridge.getPlayerStaleness?.() || {};
+      const localLatency = window.solanaBridge?.getLocalLatency?.() ?? null;
+      const assistCounts = window.___player_assists || {};
+
+      setRows(
+        players
+          .map((player) => {
+            const isLocal = player.authority === localKey;
+            return {
+              authority: player.authority,
+              username: player.username || `${player.authority.slice(0, 8)}...`,
+              team: player.team,
+              kills: player.kills || 0,
+              deaths: player.deaths || 0,
+              assists: assistCounts[player.authority] || 0,
+              ping: isLocal ? localLatency : staleness[player.authority] ?? null,
+              isLocal,
+            };
+          })
+          .sort((a, b) => b.kills - a.kills || a.deaths - b.deaths)
+      );
+    };
+
+    refresh();
+    const interval = setInterval(refresh, SCOREBOARD_REFRESH_MS);
+    return () => {
+      cancelled = true;
+      clearInterval(interval);
+    };
+  }, [visible, gamePublicKey]);
+
+  if (!isPlaying || !visible) return null;
+
+  const renderTeam = (team, title, className) => (
+    <div className={`scoreboard-team ${className}`}>
+      <div className="scoreboard-team-title">{title}</div>
+      <div className="scoreboard-row scoreboard-header">
+        <span className="scoreboard-name">Player</span>
+        <span>K</span>
+        <span>D</span>
+        <span>A</span>
+        <span>Ping</span>
+      </div>
+      {rows
+        .filter((row) => row.team === team)
+        .map((row) => (
+          <div key={row.authority} className={`scoreboard-row ${row.isLocal ? 'scoreboard-local' : ''}`}>
+            <span className="scoreboard-name">{row.username}</span>
+            <span>{row.kills}</span>
+            <span>{row.deaths}</span>
+            <span>{row.assists}</span>
+            <span>
+              <PingBars ms={row.ping} />
+            </span>
+          </div>
+        ))}
+    </div>
+  );
+
+  return (
+    <div className="scoreboard">
+      {/* Team is u8: 1 = Team A (Blue), 2 = Team B (Red) */}
+      {renderTeam(1, 'TEAM A (BLUE)', 'scoreboard-team-a')}
+      {renderTeam(2, 'TEAM B (RED)', 'scoreboard-team-b')}
+    </div>
+  );
+}
+
+export default Scoreboard;
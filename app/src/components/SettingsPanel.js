@@ -1,4 +1,47 @@
 import React, { useState, useEffect } from "react";
+import ConnectionSettings from "./ConnectionSettings";
+
+// Mixer channels shown as volume sliders
+const VOLUME_CHANNELS = [
+  { key: "master", label: "Master" },
+  { key: "effects", label: "Effects" },
+  { key: "music", label: "Music" },
+  { key: "ui", label: "UI" },
+];
+
+// Motion/flash effects with a toggle and intensity slider (see game/src/game/comfort.rs)
+const COMFORT_EFFECTS = [
+  { key: "screenShake", label: "Screen Shake" },
+  { key: "flash", label: "Flashes" },
+  { key: "viewBob", label: "View Bob" },
+];
+
+// Gun placement sliders (see game/src/game/viewmodel.rs for the limits)
+const VIEWMODEL_OFFSETS = [
+  { key: "offsetX", label: "Offset X" },
+  { key: "offsetY", label: "Offset Y" },
+  { key: "offsetZ", label: "Offset Z" },
+];
+
+// In-engine languages (see game/src/i18n)
+const LANGUAGES = [
+  { key: "en", label: "English" },
+  { key: "es", label: "Español" },
+];
+
+// World renderers (see game/src/game/graphics.rs)
+const RENDER_MODES = [
+  { key: "full", label: "Full 3D" },
+  { key: "retro", label: "Retro (low-spec)" },
+];
+
+// Color-blind presets (see game/src/game/palette.rs)
+const COLORBLIND_MODES = [
+  { key: "off", label: "Off" },
+  { key: "deuteranopia", label: "Deuteranopia (green-weak)" },
+  { key: "protanopia", label: "Protanopia (red-weak)" },
+  { key: "tritanopia", label: "Tritanopia (blue-weak)" },
+];
 
 const SettingsPanel = ({
   isOpen,
@@ -6,14 +49,57 @@ const SettingsPanel = ({
   onSave,
   sensitivity,
   musicEnabled,
+  audioVolumes,
+  uiSoundsEnabled,
+  colorblindMode,
+  comfortSettings,
+  viewmodelSettings,
+  language,
+  renderMode,
+  viewDistance,
+  filmEffects,
+  streamerSafe,
+  exportMatchEvents,
+  profanityFilter,
+  mutedPlayers,
+  onEditHudLayout,
 }) => {
   const [sens, setSens] = useState(sensitivity ?? 1.0);
   const [music, setMusic] = useState(musicEnabled ?? true);
+  const [volumes, setVolumes] = useState(audioVolumes ?? {});
+  const [uiSounds, setUiSounds] = useState(uiSoundsEnabled ?? true);
+  const [colorblind, setColorblind] = useState(colorblindMode ?? "off");
+  const [comfort, setComfort] = useState(comfortSettings ?? {});
+  const [viewmodel, setViewmodel] = useState(viewmodelSettings ?? {});
+  const [lang, setLang] = useState(language ?? "en");
+  const [render, setRender] = useState(renderMode ?? "full");
+  const [view, setView] = useState(viewDistance ?? {});
+  const [film, setFilm] = useState(filmEffects ?? false);
+  const [safe, setSafe] = useState(streamerSafe ?? false);
+  const [exportEvents, setExportEvents] = useState(exportMatchEvents ?? false);
+  const [filter, setFilter] = useState(profanityFilter ?? true);
+  const [muted, setMuted] = useState(mutedPlayers ?? []);
+  const [connection, setConnection] = useState(null);
 
   useEffect(() => {
     setSens(sensitivity ?? 1.0);
     setMusic(musicEnabled ?? true);
-  }, [sensitivity, musicEnabled, isOpen]);
+    setVolumes(audioVolumes ?? {});
+    setUiSounds(uiSoundsEnabled ?? true);
+    setColorblind(colorblindMode ?? "off");
+    setComfort(comfortSettings ?? {});
+    setViewmodel(viewmodelSettings ?? {});
+    setLang(language ?? "en");
+    setRender(renderMode ?? "full");
+    setView(viewDistance ?? {});
+    setFilm(filmEffects ?? false);
+    setSafe(streamerSafe ?? false);
+    setExportEvents(exportMatchEvents ?? false);
+    setFilter(profanityFilter ?? true);
+    setMuted(mutedPlayers ?? []);
+    const saved = window.gameBridge?.getConnectionSettings?.();
+    setConnection(saved ? { mode: saved.mode, cluster: saved.cluster, rpcId: saved.rpcId, rollupId: saved.rollupId } : null);
+  }, [sensitivity, musicEnabled, audioVolumes, uiSoundsEnabled, colorblindMode, comfortSettings, viewmodelSettings, language, renderMode, viewDistance, filmEffects, streamerSafe, exportMatchEvents, profanityFilter, mutedPlayers, isOpen]);
 
   if (!isOpen) return null;
 
@@ -77,6 +163,341 @@ const SettingsPanel = ({
           </label>
         </div>
 
+        <div style={{ margin: "24px 0" }}>
+          <label style={{ fontWeight: 700 }}>
+            <input
+              type="checkbox"
+              checked={uiSounds}
+              onChange={(e) => setUiSounds(e.target.checked)}
+              style={{ marginRight: 8 }}
+            />
+            Announcer & UI Sounds
+          </label>
+          <div style={{ fontSize: 12, color: "#aaa", marginTop: 4 }}>
+            Countdowns, kill confirmations, low ammo/health warnings and menu clicks
+          </div>
+        </div>
+
+        <div style={{ margin: "24px 0" }}>
+          <label style={{ display: "block", fontWeight: 700, marginBottom: 8 }}>
+            Volume
+          </label>
+          {VOLUME_CHANNELS.map(({ key, label }) => (
+            <div
+              key={key}
+              style={{ display: "flex", alignItems: "center", gap: 10, marginBottom: 6 }}
+            >
+              <span style={{ width: 64, fontSize: 13 }}>{label}</span>
+              <input
+                type="range"
+                min={0}
+                max={1}
+                step={0.01}
+                value={volumes[key] ?? 1}
+                disabled={(key === "music" && !music) || (key === "ui" && !uiSounds)}
+                onChange={(e) =>
+                  setVolumes((v) => ({ ...v, [key]: parseFloat(e.target.value) }))
+                }
+                style={{ flex: 1 }}
+              />
+              <span style={{ width: 40, textAlign: "right", fontSize: 12, color: "#aaa" }}>
+                {Math.round((volumes[key] ?? 1) * 100)}%
+              </span>
+            </div>
+          ))}
+        </div>
+
+        <div style={{ margin: "24px 0" }}>
+          <label style={{ display: "block", fontWeight: 700, marginBottom: 8 }}>
+            Motion &amp; Flashes
+          </label>
+          {COMFORT_EFFECTS.map(({ key, label }) => {
+            const setting = comfort[key] ?? { enabled: true, intensity: 1 };
+            const update = (change) => setComfort((c) => ({ ...c, [key]: { ...setting, ...change } }));
+            return (
+              <div
+                key={key}
+                style={{ display: "flex", alignItems: "center", gap: 10, marginBottom: 6 }}
+              >
+                <label style={{ width: 120, fontSize: 13 }}>
+                  <input
+                    type="checkbox"
+                    checked={setting.enabled}
+                    onChange={(e) => update({ enabled: e.target.checked })}
+                    style={{ marginRight: 6 }}
+                  />
+                  {label}
+                </label>
+                <input
+                  type="range"
+                  min={0}
+                  max={1}
+                  step={0.05}
+                  value={setting.intensity}
+                  disabled={!setting.enabled}
+                  onChange={(e) => update({ intensity: parseFloat(e.target.value) })}
+                  style={{ flex: 1 }}
+                />
+                <span style={{ width: 40, textAlign: "right", fontSize: 12, color: "#aaa" }}>
+                  {setting.enabled ? `${Math.round(setting.intensity * 100)}%` : "Off"}
+                </span>
+              </div>
+            );
+          })}
+          <div style={{ fontSize: 12, color: "#aaa", marginTop: 4 }}>
+            Tone down camera shake, shooting flashes and weapon bob for photosensitive or motion-sick players
+          </div>
+        </div>
+
+        <div style={{ margin: "24px 0" }}>
+          <label style={{ display: "block", fontWeight: 700, marginBottom: 8 }}>
+            HUD Layout
+          </label>
+          <button
+            onClick={onEditHudLayout}
+            style={{
+              padding: "6px 16px",
+              borderRadius: 6,
+              border: "none",
+              background: "#33334e",
+              color: "#fff",
+              cursor: "pointer",
+            }}
+          >
+            Edit HUD Layout
+          </button>
+          <div style={{ fontSize: 12, color: "#aaa", marginTop: 4 }}>
+            Move, resize or hide the health bar, ammo, kill feed, minimap and match timer
+          </div>
+        </div>
+
+        <div style={{ margin: "24px 0" }}>
+          <label style={{ display: "block", fontWeight: 700, marginBottom: 8 }}>
+            Weapon Viewmodel
+          </label>
+          <label style={{ display: "block", fontSize: 13, marginBottom: 8 }}>
+            <input
+              type="checkbox"
+              checked={!!viewmodel.leftHanded}
+              onChange={(e) => setViewmodel((v) => ({ ...v, leftHanded: e.target.checked }))}
+              style={{ marginRight: 6 }}
+            />
+            Left-handed
+          </label>
+          {VIEWMODEL_OFFSETS.map(({ key, label }) => (
+            <div
+              key={key}
+              style={{ display: "flex", alignItems: "center", gap: 10, marginBottom: 6 }}
+            >
+              <span style={{ width: 120, fontSize: 13 }}>{label}</span>
+              <input
+                type="range"
+                min={-0.2}
+                max={0.2}
+                step={0.01}
+                value={viewmodel[key] ?? 0}
+                onChange={(e) => setViewmodel((v) => ({ ...v, [key]: parseFloat(e.target.value) }))}
+                style={{ flex: 1 }}
+              />
+              <span style={{ width: 40, textAlign: "right", fontSize: 12, color: "#aaa" }}>
+                {(viewmodel[key] ?? 0).toFixed(2)}
+              </span>
+            </div>
+          ))}
+          <div style={{ display: "flex", alignItems: "center", gap: 10, marginBottom: 6 }}>
+            <span style={{ width: 120, fontSize: 13 }}>Viewmodel FOV</span>
+            <input
+              type="range"
+              min={50}
+              max={90}
+              step={1}
+              value={viewmodel.fov ?? 70}
+              onChange={(e) => setViewmodel((v) => ({ ...v, fov: parseFloat(e.target.value) }))}
+              style={{ flex: 1 }}
+            />
+            <span style={{ width: 40, textAlign: "right", fontSize: 12, color: "#aaa" }}>
+              {Math.round(viewmodel.fov ?? 70)}°
+            </span>
+          </div>
+          <div style={{ fontSize: 12, color: "#aaa", marginTop: 4 }}>
+            Higher viewmodel FOV makes the gun look smaller and further away
+          </div>
+        </div>
+
+        <div style={{ margin: "24px 0" }}>
+          <label style={{ display: "block", fontWeight: 700, marginBottom: 8 }}>
+            Graphics
+          </label>
+          <select
+            value={render}
+            onChange={(e) => setRender(e.target.value)}
+            style={{ width: "100%", background: "#232336", color: "#fff", border: "1px solid #33334e", borderRadius: 6, padding: 4 }}
+          >
+            {RENDER_MODES.map(({ key, label }) => (
+              <option key={key} value={key}>
+                {label}
+              </option>
+            ))}
+          </select>
+          <div style={{ fontSize: 12, color: "#aaa", marginTop: 4 }}>
+            Retro draws the map as flat walls and players as sprites, for devices that can't hold 30 fps in 3D
+          </div>
+          <div style={{ display: "flex", alignItems: "center", gap: 10, marginTop: 10, marginBottom: 6 }}>
+            <span style={{ width: 120, fontSize: 13 }}>Draw Distance</span>
+            <input
+              type="range"
+              min={20}
+              max={100}
+              step={5}
+              value={view.drawDistance ?? 100}
+              onChange={(e) => setView((v) => ({ ...v, drawDistance: parseFloat(e.target.value) }))}
+              style={{ flex: 1 }}
+            />
+            <span style={{ width: 40, textAlign: "right", fontSize: 12, color: "#aaa" }}>
+              {Math.round(view.drawDistance ?? 100)}
+            </span>
+          </div>
+          <label style={{ display: "block", fontSize: 13 }}>
+            <input
+              type="checkbox"
+              checked={view.fog ?? true}
+              onChange={(e) => setView((v) => ({ ...v, fog: e.target.checked }))}
+              style={{ marginRight: 6 }}
+            />
+            Distance fog
+          </label>
+          <div style={{ fontSize: 12, color: "#aaa", marginTop: 4 }}>
+            A shorter draw distance skips far-away map geometry on slower devices; fog fades it into the sky instead of popping
+          </div>
+          <label style={{ display: "block", fontSize: 13, marginTop: 10 }}>
+            <input
+              type="checkbox"
+              checked={film}
+              onChange={(e) => setFilm(e.target.checked)}
+              style={{ marginRight: 6 }}
+            />
+            Film grain &amp; chromatic aberration
+          </label>
+        </div>
+
+        <div style={{ margin: "24px 0" }}>
+          <label style={{ display: "block", fontWeight: 700, marginBottom: 8 }}>
+            Color-Blind Mode
+          </label>
+          <select
+            value={colorblind}
+            onChange={(e) => setColorblind(e.target.value)}
+            style={{ width: "100%", background: "#232336", color: "#fff", border: "1px solid #33334e", borderRadius: 6, padding: 4 }}
+          >
+            {COLORBLIND_MODES.map(({ key, label }) => (
+              <option key={key} value={key}>
+                {label}
+              </option>
+            ))}
+          </select>
+          <div style={{ fontSize: 12, color: "#aaa", marginTop: 4 }}>
+            Remaps team colors, hit effects, minimap markers and HUD accents
+          </div>
+        </div>
+
+        <div style={{ margin: "24px 0" }}>
+          <label style={{ display: "block", fontWeight: 700, marginBottom: 8 }}>
+            Language
+          </label>
+          <select
+            value={lang}
+            onChange={(e) => setLang(e.target.value)}
+            style={{ width: "100%", background: "#232336", color: "#fff", border: "1px solid #33334e", borderRadius: 6, padding: 4 }}
+          >
+            {LANGUAGES.map(({ key, label }) => (
+              <option key={key} value={key}>
+                {label}
+              </option>
+            ))}
+          </select>
+          <div style={{ fontSize: 12, color: "#aaa", marginTop: 4 }}>
+            In-game HUD, announcements and map editor text
+          </div>
+        </div>
+
+        <div style={{ margin: "24px 0" }}>
+          <label style={{ fontWeight: 700 }}>
+            <input
+              type="checkbox"
+              checked={safe}
+              onChange={(e) => setSafe(e.target.checked)}
+              style={{ marginRight: 8 }}
+            />
+            Streamer-Safe Effects
+          </label>
+          <div style={{ fontSize: 12, color: "#aaa", marginTop: 4 }}>
+            Replaces blood hit effects with neutral sparks
+          </div>
+        </div>
+
+        <div style={{ margin: "24px 0" }}>
+          <label style={{ fontWeight: 700 }}>
+            <input
+              type="checkbox"
+              checked={exportEvents}
+              onChange={(e) => setExportEvents(e.target.checked)}
+              style={{ marginRight: 8 }}
+            />
+            Export Match Event Log
+          </label>
+          <div style={{ fontSize: 12, color: "#aaa", marginTop: 4 }}>
+            Downloads kills, shots, positions (1/s) and round events as JSON + CSV when a match ends
+          </div>
+        </div>
+
+        <div style={{ margin: "24px 0" }}>
+          <label style={{ fontWeight: 700 }}>
+            <input
+              type="checkbox"
+              checked={filter}
+              onChange={(e) => setFilter(e.target.checked)}
+              style={{ marginRight: 8 }}
+            />
+            Profanity Filter
+          </label>
+          <div style={{ fontSize: 12, color: "#aaa", marginTop: 4 }}>
+            Masks offensive words in chat
+          </div>
+          <div style={{ fontWeight: 700, marginTop: 16, marginBottom: 6 }}>
+            Muted Players
+          </div>
+          {muted.length === 0 ? (
+            <div style={{ fontSize: 12, color: "#aaa" }}>
+              Nobody muted. Mute players from the lobby to hide their chat, pings and voice-lines.
+            </div>
+          ) : (
+            muted.map((player) => (
+              <div
+                key={player}
+                style={{ display: "flex", alignItems: "center", justifyContent: "space-between", marginBottom: 4 }}
+              >
+                <span style={{ fontSize: 13 }}>🔇 {player}</span>
+                <button
+                  onClick={() => setMuted((list) => list.filter((p) => p !== player))}
+                  style={{
+                    padding: "2px 10px",
+                    borderRadius: 6,
+                    border: "none",
+                    background: "#33334e",
+                    color: "#fff",
+                    cursor: "pointer",
+                    fontSize: 12,
+                  }}
+                >
+                  Unmute
+                </button>
+              </div>
+            ))
+          )}
+        </div>
+
+        <ConnectionSettings value={connection} onChange={setConnection} />
+
         <div
           style={{
             display: "flex",
@@ -100,7 +521,7 @@ const SettingsPanel = ({
             Close
           </button>
           <button
-            onClick={() => onSave({ sensitivity: sens, musicEnabled: music })}
+            onClick={() => onSave({ sensitivity: sens, musicEnabled: music, audioVolumes: volumes, uiSoundsEnabled: uiSounds, colorblindMode: colorblind, comfortSettings: comfort, viewmodelSettings: viewmodel, language: lang, renderMode: render, viewDistance: view, filmEffects: film, streamerSafe: safe, exportMatchEvents: exportEvents, profanityFilter: filter, mutedPlayers: muted, connection })}
             style={{
               padding: "8px 24px",
               borderRadius: 8,
@@ -0,0 +1,50 @@
+import React, { useEffect, useState } from 'react';
+
+/**
+ * StartCountdown Component
+ * Full-screen "MATCH STARTS IN 5..4..3" shown between the leader starting the
+ * game and the synchronized moment every client enters the match
+ */
+function StartCountdown({ playAtMs }) {
+  const [now, setNow] = useState(Date.now());
+
+  useEffect(() => {
+    const interval = setInterval(() => setNow(Date.now()), 100);
+    return () => clearInterval(interval);
+  }, []);
+
+  const seconds = Math.ceil((playAtMs - now) / 1000);
+  if (seconds <= 0) return null;
+
+  return (
+    <div
+      style={{
+        position: 'fixed',
+        inset: 0,
+        display: 'flex',
+        flexDirection: 'column',
+        alignItems: 'center',
+        justifyContent: 'center',
+        background: 'rgba(13, 13, 17, 0.85)',
+        zIndex: 2000,
+        pointerEvents: 'none',
+      }}
+    >
+      <div style={{ color: '#c8c8dc', fontSize: '24px', fontWeight: 700, letterSpacing: '2px' }}>
+        MATCH STARTS IN
+      </div>
+      <div
+        style={{
+          color: '#00f294',
+          fontSize: '120px',
+          fontWeight: 900,
+          textShadow: '0 0 30px rgba(0, 242, 148, 0.6)',
+        }}
+      >
+        {seconds}
+      </div>
+    </div>
+  );
+}
+
+export default StartCountdown;
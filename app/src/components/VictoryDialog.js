@@ -1,11 +1,12 @@
 import React from 'react';
 import './VictoryDialog.css';
+import PlayerName from './PlayerName';
 
 /**
  * VictoryDialog Component
  * Displays match results with winning team and MVP
  */
-function VictoryDialog({ winningTeam, teamAScore, teamBScore, mvpPlayer, onClose }) {
+function VictoryDialog({ winningTeam, teamAScore, teamBScore, mvpPlayer, overtimePeriods = 0, onClose }) {
   const isTeamAWinner = winningTeam === 'A';
   const winningColor = isTeamAWinner ? '#00d9ff' : '#ff4444';
   const winningTeamName = isTeamAWinner ? 'Team A (Blue)' : 'Team B (Red)';
@@ -19,7 +20,7 @@ function VictoryDialog({ winningTeam, teamAScore, teamBScore, mvpPlayer, onClose
             🏆 VICTORY! 🏆
           </h1>
           <p className="victory-subtitle" style={{ color: winningColor }}>
-            {winningTeamName} Wins!
+            {winningTeamName} Wins{overtimePeriods > 0 ? ' in Overtime' : ''}!
           </p>
         </div>
 
@@ -42,9 +43,12 @@ function VictoryDialog({ winningTeam, teamAScore, teamBScore, mvpPlayer, onClose
             <div className="mvp-section">
               <div className="mvp-badge">⭐ MVP ⭐</div>
               <div className="mvp-player">
-                <div className="mvp-name">{mvpPlayer.username}</div>
+                <div className="mvp-name"><PlayerName username={mvpPlayer.username} /></div>
                 <div className="mvp-stats">
                   <span className="mvp-kills">{mvpPlayer.kills} Kills</span>
+                  {mvpPlayer.assists !== undefined && (
+                    <span className="mvp-assists">{mvpPlayer.assists} Assists</span>
+                  )}
                   {mvpPlayer.deaths !== undefined && (
                     <span className="mvp-deaths">{mvpPlayer.deaths} Deaths</span>
                   )}
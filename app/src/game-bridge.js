@@ -9,6 +9,14 @@ import websocketGameManager from "./websocket-game-manager";
 import { publicKey, u64, bool } from "@solana/buffer-layout-utils";
 import * as BufferLayout from "@solana/buffer-layout";
 import { debug } from "./utils/debug-config";
+import { isBlockedGameEvent } from "./utils/chat-filter";
+import { chainNow, playStartTime } from "./utils/match-start";
+import { startActivityLog, getActivityLog } from "./utils/activity-log";
+import { toBridgeError } from "./utils/bridge-error";
+import { createEditSessionCode, normalizeEditSessionCode, getEditPeerId, openEditSession } from "./utils/edit-session";
+import { isMapShareCode, normalizeShareCode } from "./utils/map-seed";
+import { openRelayChannel, getRelayLatency } from "./utils/relay";
+import { relayPathLatency } from "./utils/ping";
 
 const { u32, u8, struct, f32 } = BufferLayout;
 
@@ -21,6 +29,164 @@ const previousBulletCounts = {};
 // Track if we've played a sound recently for a player (debounce)
 const recentSoundPlays = {};
 
+// Gameplay events (grenade throws, ...) received from other clients, drained by the game each frame
+let pendingGameEvents = [];
+
+// Map editor collaborative session this tab is in (see utils/edit-session.js)
+let editSession = null;
+
+// One relay channel per match (see utils/relay.js); events carry absolute
+// timestamps so every client can replay them on its own clock
+const gameEventChannels = {};
+
+// Convert every `...At` timestamp field of a game event (seconds since page load <-> epoch ms)
+function convertEventTimestamps(event, convert) {
+  const converted = { ...event };
+  Object.keys(event)
+    .filter((key) => key.endsWith("At") && typeof event[key] === "number")
+    .forEach((key) => {
+      converted[key] = convert(event[key]);
+    });
+  return converted;
+}
+
+function getGameEventChannel(gameId) {
+  if (!gameEventChannels[gameId]) {
+    const channel = openRelayChannel(`game-events:${gameId}`, (event, from) => window.gameBridge?.onRemoteGameEvent?.(event, from));
+    if (!channel) return null;
+    gameEventChannels[gameId] = channel;
+  }
+  return gameEventChannels[gameId];
+}
+
+// Player updates the chain doesn't carry (backfill bots run by the lobby leader),
+// relayed per match and merged into window.___websocket_player_updates
+const playerUpdateChannels = {};
+
+// Backfill bot authorities start with this (game/src/game/backfill.rs), so relayed
+// updates can never stand in for a player who is on chain
+const BOT_AUTHORITY_PREFIX = "bot:";
+
+// A bot belongs to whoever published it, until they go quiet this long (the game's BOT_STATE_TIMEOUT)
+const BOT_OWNER_TIMEOUT_MS = 3000;
+
+function isBotAuthority(authority) {
+  return typeof authority === "string" && authority.startsWith(BOT_AUTHORITY_PREFIX);
+}
+
+/** Relay key of the client running `bot`, null if nobody has published it lately */
+function relayedBotOwner(bot) {
+  const update = window.___websocket_player_updates?.[bot];
+  if (!update?.relayedBy || Date.now() - update.timestamp > BOT_OWNER_TIMEOUT_MS) return null;
+  return update.relayedBy;
+}
+
+function storeRelayedPlayerUpdates(updates, from) {
+  if (!Array.isArray(updates)) return;
+  if (!window.___websocket_player_updates) {
+    window.___websocket_player_updates = {};
+  }
+  updates
+    .filter((update) => update && isBotAuthority(update.authority))
+    .filter((update) => (relayedBotOwner(update.authority) ?? from) === from)
+    .forEach((update) => {
+      window.___websocket_player_updates[update.authority] = {
+        timestamp: Date.now(),
+        receivedAt: performance.now() / 1000, // The game's clock, to tell fresh updates from stale ones
+        relayedBy: from,
+        parsed: update,
+      };
+    });
+}
+
+/**
+ * Whether a bot hit / kill event comes from a client that can vouch for it: a
+ * bot's shots and death from the client running it, a player's hits on a bot
+ * and death to one from that player
+ */
+function isVouchedBotEvent(event, from) {
+  switch (event.type) {
+    case "botHit":
+      return isBotAuthority(event.attacker) ? relayedBotOwner(event.attacker) === from : event.attacker === from;
+    case "botKill":
+      return isBotAuthority(event.victim) ? relayedBotOwner(event.victim) === from : event.victim === from;
+    default:
+      return true;
+  }
+}
+
+// Lobby members by the key the relay stamps on their messages (the player's on-chain
+// signing key), fetched again when someone the last fetch didn't know speaks up
+const lobbyRosters = {};
+const LOBBY_ROSTER_REFRESH_MS = 2000;
+
+function loadLobbyRoster(gameId) {
+  const cached = lobbyRosters[gameId];
+  if (cached && Date.now() - cached.fetchedAt < LOBBY_ROSTER_REFRESH_MS) return cached.members;
+  const members = solanaBridge
+    .getAllPlayersInGame(gameId)
+    .then((players) => Object.fromEntries((Array.isArray(players) ? players : []).map((player) => [player.signingKey, player])))
+    .catch(() => ({}));
+  lobbyRosters[gameId] = { fetchedAt: Date.now(), members };
+  return members;
+}
+
+/** The lobby player who signed in to the relay as `key`, null for anyone not in the lobby */
+async function findLobbyMember(gameId, key) {
+  const known = (await lobbyRosters[gameId]?.members)?.[key];
+  return known || (await loadLobbyRoster(gameId))[key] || null;
+}
+
+function getPlayerUpdateChannel(gameId) {
+  if (!playerUpdateChannels[gameId]) {
+    const channel = openRelayChannel(`player-updates:${gameId}`, storeRelayedPlayerUpdates);
+    if (!channel) return null;
+    playerUpdateChannels[gameId] = channel;
+  }
+  return playerUpdateChannels[gameId];
+}
+
+// Base64 for map data handed to the game; built in slices because spreading a large
+// map into String.fromCharCode overflows the call stack
+function bytesToBase64(bytes) {
+  const SLICE = 0x8000;
+  let binary = "";
+  for (let i = 0; i < bytes.length; i += SLICE) {
+    binary += String.fromCharCode.apply(null, bytes.subarray(i, i + SLICE));
+  }
+  return btoa(binary);
+}
+
+/** Attempts per chunk before a streamed map gives up on it */
+const MAP_CHUNK_ATTEMPTS = 3;
+
+/**
+ * Fetch the later chunks of a chunked map in order (nearest the center first)
+ * and queue them for the game, which adds one per frame to the running map
+ * @param {string} mapId - The map ID (of the head)
+ * @param {string} mapName - The map's name
+ * @param {number} chunks - Chunk count from the map's manifest
+ */
+async function streamMapChunks(mapId, mapName, chunks) {
+  for (let index = 1; index < chunks; index++) {
+    let bytes = null;
+    for (let attempt = 0; attempt < MAP_CHUNK_ATTEMPTS && !bytes; attempt++) {
+      bytes = await solanaBridge.getMapChunk(mapId, index, mapName);
+    }
+    if (!window.Module || !window.Module.mapChunkQueue) {
+      return;
+    }
+    // A chunk that never arrives is still queued (without data) so the stream can finish
+    if (!bytes) {
+      debug.error(`[Game Bridge] ❌ Giving up on chunk ${index} of map ${mapId}`);
+    }
+    window.Module.mapChunkQueue.push(
+      JSON.stringify({ mapId, index, data: bytes ? bytesToBase64(bytes) : null })
+    );
+    debug.log("GAME_BRIDGE", `[Game Bridge] Queued chunk ${index + 1}/${chunks} of map ${mapId}`);
+  }
+}
+
 /**
  * Play 3D positional audio for other players shooting
  * @param {number} x - X position of the sound
@@ -84,6 +250,8 @@ export function initGameBridge() {
     return;
   }
 
+  startActivityLog();
+
   // Expose functions to the game through window.gameBridge
   window.gameBridge = {
     // UI update callbacks (set by React App)
@@ -263,22 +431,36 @@ export function initGameBridge() {
 
     getMapDataById: async (mapId) => {
       debug.log("GAME_BRIDGE", "[Game Bridge] getMapDataById called:", mapId);
-      const result = await solanaBridge.getMapData(mapId, "borsh");
+
+      // Generated arenas have no chain data: the game rebuilds them from the share code
+      if (isMapShareCode(mapId)) {
+        const shareCode = normalizeShareCode(mapId);
+        if (window.Module) {
+          window.Module.mapDataResult = JSON.stringify({ success: true, shareCode });
+        }
+        return shareCode;
+      }
+
+      // Large maps start on their head chunk; the other chunks stream in during the match
+      const head = await solanaBridge.getMapHead(mapId);
+      const result = head ? head.bytes : null;
       debug.log(
         "GAME_BRIDGE",
         "[Game Bridge] getMapDataById result:",
-        result ? `${result.length} bytes` : "null"
+        result ? `${result.length} bytes, ${head.chunks} chunk(s)` : "null"
       );
 
       // The Rust game expects Module.mapDataResult to be set with base64-encoded data
       if (result && window.Module) {
         try {
-          // Convert Uint8Array to base64 string
-          const base64 = btoa(String.fromCharCode.apply(null, result));
+          // Convert Uint8Array to base64 string (the game decodes it over several frames)
+          const base64 = bytesToBase64(result);
           // Set Module.mapDataResult as JSON string expected by Rust
           window.Module.mapDataResult = JSON.stringify({
             success: true,
             data: base64,
+            mapId,
+            chunks: head.chunks,
           });
           debug.log(
             "GAME_BRIDGE",
@@ -286,20 +468,24 @@ export function initGameBridge() {
             result.length,
             "bytes as base64"
           );
+          window.Module.mapChunkQueue = [];
+          if (head.chunks > 1) {
+            streamMapChunks(mapId, head.name, head.chunks);
+          }
         } catch (error) {
           debug.error(
             "[Game Bridge] ❌ Failed to set Module.mapDataResult:",
             error
           );
           window.Module.mapDataResult = JSON.stringify({
-            error: error.message,
+            error: toBridgeError(error),
           });
         }
       } else if (!result) {
         debug.warn("GAME_BRIDGE", "[Game Bridge] ⚠️ No map data to set");
         if (window.Module) {
           window.Module.mapDataResult = JSON.stringify({
-            error: "Failed to fetch map data",
+            error: toBridgeError("Failed to fetch map data"),
           });
         }
       }
@@ -350,6 +536,119 @@ export function initGameBridge() {
       return await solanaBridge.sendPlayerInput(input);
     },
 
+    getInputChecksumLog: (gameId) => {
+      return solanaBridge.getInputChecksumLog(gameId);
+    },
+
+    // Gameplay event relay (grenade throws, weapon drops). Timestamps are converted
+    // between the game's local clock (seconds since page load) and epoch milliseconds.
+    sendGameEvent: (gameId, event) => {
+      const channel = getGameEventChannel(gameId);
+      if (!channel) return;
+      channel.send(convertEventTimestamps(event, (seconds) => performance.timeOrigin + seconds * 1000));
+    },
+
+    // Player updates for accounts that aren't on chain (backfill bots), same shape as a parsed GamePlayer
+    sendPlayerUpdates: (gameId, updates) => {
+      getPlayerUpdateChannel(gameId)?.send(updates);
+    },
+
+    // Lobby chat relay (pre-match only, separate from the in-match event channel). The sender
+    // and their team are the lobby player the relay vouches for, whatever the message claims.
+    openLobbyChat: (gameId, onMessage) =>
+      openRelayChannel(`lobby-chat:${gameId}`, async (message, from) => {
+        if (!message || typeof message !== "object") return;
+        const member = await findLobbyMember(gameId, from);
+        if (member) onMessage({ ...message, sender: member.username, team: member.team });
+      }),
+
+    // Lobby presence beats (who is still connected, and the relay latency to them) for the ping bars.
+    // Every beat carries the sender's round trip to the relay, which onBeat receives combined with ours.
+    openLobbyPresence: (gameId, onBeat) => {
+      const channel = openRelayChannel(`lobby-presence:${gameId}`, async (beat, from) => {
+        if (!beat || typeof beat !== "object") return;
+        const member = await findLobbyMember(gameId, from);
+        if (!member) return;
+        onBeat({ sender: member.username, latency: relayPathLatency(beat.relayLatency, getRelayLatency()) });
+      });
+      if (!channel) return null;
+      return {
+        send: (beat) => channel.send({ ...beat, relayLatency: getRelayLatency() }),
+        close: channel.close,
+      };
+    },
+
+    // Our round trip to the game relay in ms (null until measured)
+    getRelayLatency: () => getRelayLatency(),
+
+    // Milliseconds since the last account update received for each player, by authority
+    getPlayerStaleness: () => {
+      const now = Date.now();
+      const staleness = {};
+      Object.values(window.___websocket_player_updates || {}).forEach((update) => {
+        const authority = update.parsed?.authority;
+        if (authority) staleness[authority] = now - update.timestamp;
+      });
+      return staleness;
+    },
+
+    // Recent gameplay transactions with status and signature, newest first (see utils/activity-log.js)
+    getActivityLog: () => getActivityLog(),
+
+    // Entry point for events from the match's relay channel; `from` is the sender's relay key
+    // (their ephemeral key, the game's player authority), stamped on the event for the game
+    onRemoteGameEvent: (event, from) => {
+      if (!event || typeof event !== "object" || typeof from !== "string") return;
+      // Pings/voice-lines from players muted in settings never reach the game
+      if (isBlockedGameEvent(event, window.__muted_players)) return;
+      if (!isVouchedBotEvent(event, from)) return;
+      const local = convertEventTimestamps(event, (epochMs) => (epochMs - performance.timeOrigin) / 1000);
+      pendingGameEvents.push({ ...local, from });
+    },
+
+    // Call a timeout / surrender / kick vote from the pause menu (the game announces it)
+    requestVote: (kind, target = null) => {
+      pendingGameEvents.push({ type: "voteRequest", kind, target });
+    },
+
+    getGameEvents: () => {
+      const events = pendingGameEvents;
+      pendingGameEvents = [];
+      return JSON.stringify(events);
+    },
+
+    // Map editor collaborative sessions. Host/join return what the editor needs
+    // to know about itself ("CODE:peerId" / peerId), or "" if unavailable.
+    hostEditSession: (name) => {
+      const code = createEditSessionCode();
+      const peerId = window.gameBridge.joinEditSession(code, name);
+      return peerId ? `${code}:${peerId}` : "";
+    },
+
+    joinEditSession: (code, name) => {
+      window.gameBridge.leaveEditSession();
+      const channel = openEditSession(normalizeEditSessionCode(code), { name: String(name || "").slice(0, 24) });
+      if (!channel) return "";
+      editSession = channel;
+      return getEditPeerId();
+    },
+
+    leaveEditSession: () => {
+      editSession?.close();
+      editSession = null;
+    },
+
+    // `json` is one message from the editor (map/collab.rs EditMessage)
+    sendEditMessage: (json) => {
+      try {
+        editSession?.send(JSON.parse(json));
+      } catch (error) {
+        console.error("Invalid edit session message:", error);
+      }
+    },
+
+    getEditMessages: () => JSON.stringify(editSession ? editSession.drain() : []),
+
     // Shooting functions
     shootPlayer: async (damage, gameId, otherPlayerPdas) => {
       console.log(
@@ -448,6 +747,68 @@ export function initGameBridge() {
       }
     },
 
+    // Watch a live match read-only: no join transaction, no player account mutation
+    startSpectating: async (gamePubkey, mapId) => {
+      debug.log("GAME_BRIDGE", "[Game Bridge] startSpectating called:", gamePubkey, mapId);
+      if (!window.Module || !window.Module._start_spectating_js) {
+        console.warn("⚠️ Module._start_spectating_js not available");
+        return { success: false, error: "Game not ready" };
+      }
+      try {
+        window.gameBridge.setCurrentGame(gamePubkey);
+        await window.gameBridge.connectWebSocket();
+        await window.gameBridge.subscribeToGamePlayers(gamePubkey);
+        if (mapId) {
+          await window.gameBridge.getMapDataById(mapId);
+        }
+        window.Module._start_spectating_js();
+        return { success: true };
+      } catch (error) {
+        console.error("[Game Bridge] ❌ Failed to start spectating:", error);
+        return { success: false, error: error.message };
+      }
+    },
+    stopSpectating: async (gamePubkey) => {
+      window.gameBridge.stopGameMode();
+      if (gamePubkey) {
+        await window.gameBridge.unsubscribeFromGamePlayers(gamePubkey);
+      }
+    },
+
+    // Offline modes (tutorial, shooting range, practice match): no wallet, match or transactions involved
+    startTutorial: () => {
+      if (!window.Module || !window.Module._start_tutorial_js) {
+        console.warn("⚠️ Module._start_tutorial_js not available");
+        return { success: false, error: "Game not ready" };
+      }
+      window.Module._start_tutorial_js();
+      return { success: true };
+    },
+    startShootingRange: () => {
+      if (!window.Module || !window.Module._start_shooting_range_js) {
+        console.warn("⚠️ Module._start_shooting_range_js not available");
+        return { success: false, error: "Game not ready" };
+      }
+      window.Module._start_shooting_range_js();
+      return { success: true };
+    },
+    // difficulty: 0 easy .. 3 expert; role: 0 rusher, 1 anchor, 2 objective, -1 mixed
+    startPracticeMatch: ({ difficulty, role, bots }) => {
+      if (!window.Module || !window.Module._start_practice_match_js) {
+        console.warn("⚠️ Module._start_practice_match_js not available");
+        return { success: false, error: "Game not ready" };
+      }
+      window.Module._start_practice_match_js(difficulty, role, bots);
+      return { success: true };
+    },
+    stopOfflineMode: () => {
+      window.gameBridge.stopGameMode();
+    },
+    // Called by the game when the player confirms the final tutorial step
+    onTutorialComplete: () => {
+      window.dispatchEvent(new CustomEvent("tutorialComplete"));
+    },
+
     // Settings bridge (JS overlay → Rust via globals polled in main.rs)
     openSettings: () => {
       try {
@@ -484,6 +845,224 @@ export function initGameBridge() {
       }
     },
 
+    // Audio mixer channels (master/effects/music/ui), applied via _set_audio_volume_js
+    getAudioVolumes: () => {
+      return { ...DEFAULT_AUDIO_VOLUMES, ...(window.__audio_volumes || {}) };
+    },
+    setAudioVolume: (channel, value) => {
+      try {
+        const volume = Math.max(0, Math.min(1, Number(value)));
+        window.__audio_volumes = { ...(window.__audio_volumes || {}), [channel]: volume };
+        try {
+          localStorage.setItem("audioVolumes", JSON.stringify(window.__audio_volumes));
+        } catch (_) {}
+        applyAudioVolume(channel, volume);
+      } catch (e) {
+        console.warn("Failed to set audio volume:", e);
+      }
+    },
+
+    // Announcer/UI sounds ("click", "hover", "countdown_tick", "countdown_go", ...)
+    setUiSoundsEnabled: (enabled) => {
+      window.__ui_sounds_enabled = !!enabled;
+      try {
+        localStorage.setItem("uiSoundsEnabled", String(!!enabled));
+      } catch (_) {}
+      if (window.Module && window.Module._set_ui_sounds_enabled_js) {
+        window.Module._set_ui_sounds_enabled_js(!!enabled);
+      }
+    },
+
+    // Color-blind palette ("off", "deuteranopia", "protanopia", "tritanopia"), applied via _set_colorblind_mode_js
+    setColorblindMode: (mode) => {
+      const modeId = COLORBLIND_MODE_IDS[mode];
+      if (modeId === undefined) {
+        console.warn("Unknown color-blind mode:", mode);
+        return;
+      }
+      window.__colorblind_mode = mode;
+      if (window.Module && window.Module._set_colorblind_mode_js) {
+        window.Module._set_colorblind_mode_js(modeId);
+      }
+    },
+
+    // Motion/flash comfort ("screenShake", "flash", "viewBob"), applied via _set_comfort_setting_js
+    setComfortSetting: (effect, { enabled, intensity }) => {
+      const effectId = COMFORT_EFFECT_IDS[effect];
+      if (effectId === undefined) {
+        console.warn("Unknown comfort effect:", effect);
+        return;
+      }
+      const value = Math.max(0, Math.min(1, Number(intensity)));
+      if (window.Module && window.Module._set_comfort_setting_js) {
+        window.Module._set_comfort_setting_js(effectId, !!enabled, value);
+      }
+    },
+
+    // Gun placement: left-handed mirroring, offsets (units) and viewmodel FOV (degrees), applied via _set_viewmodel_js
+    setViewmodel: ({ leftHanded, offsetX, offsetY, offsetZ, fov }) => {
+      if (window.Module && window.Module._set_viewmodel_js) {
+        window.Module._set_viewmodel_js(!!leftHanded, Number(offsetX), Number(offsetY), Number(offsetZ), Number(fov));
+      }
+    },
+
+    // World renderer ("full", "retro"), applied via _set_render_mode_js
+    setRenderMode: (mode) => {
+      const modeId = RENDER_MODE_IDS[mode];
+      if (modeId === undefined) {
+        console.warn("Unknown render mode:", mode);
+        return;
+      }
+      if (window.Module && window.Module._set_render_mode_js) {
+        window.Module._set_render_mode_js(modeId);
+      }
+    },
+
+    // Web UI tab ("mapeditor", "lobby"), applied via _set_menu_tab_js; the lobby gets the orbiting map backdrop
+    setMenuTab: (tab) => {
+      const tabId = MENU_TAB_IDS[tab];
+      if (tabId === undefined) {
+        return;
+      }
+      if (window.Module && window.Module._set_menu_tab_js) {
+        window.Module._set_menu_tab_js(tabId);
+      }
+    },
+
+    // Draw distance (units) and distance fog toward the sky color, applied via _set_view_distance_js
+    setViewDistance: ({ drawDistance, fog }) => {
+      if (window.Module && window.Module._set_view_distance_js) {
+        window.Module._set_view_distance_js(Number(drawDistance), !!fog);
+      }
+    },
+
+    // Film grain and chromatic aberration over the world, applied via _set_film_effects_js
+    setFilmEffects: (enabled) => {
+      if (window.Module && window.Module._set_film_effects_js) {
+        window.Module._set_film_effects_js(!!enabled);
+      }
+    },
+
+    // HUD layout (see utils/hud-layout.js), applied via _set_hud_layout_js; the game places the health bar with it
+    setHudLayout: (layout) => {
+      if (window.Module && window.Module._set_hud_layout_js) {
+        const stringPtr = createStringPointer(JSON.stringify(layout));
+        window.Module._set_hud_layout_js(stringPtr);
+        freeStringPointer(stringPtr);
+      }
+    },
+
+    // In-engine language for HUD, announcements and the map editor ("en", "es"), applied via _set_language_js
+    setLanguage: (code) => {
+      if (window.Module && window.Module._set_language_js) {
+        const stringPtr = createStringPointer(code);
+        window.Module._set_language_js(stringPtr);
+        freeStringPointer(stringPtr);
+      }
+    },
+    playUiSound: (soundId) => {
+      if (window.Module && window.Module._play_ui_sound_js) {
+        const stringPtr = createStringPointer(soundId);
+        window.Module._play_ui_sound_js(stringPtr);
+        freeStringPointer(stringPtr);
+      }
+    },
+
+    // Connection settings: cluster plus RPC/rollup endpoint choice, auto-selected from the Rust latency probe
+    getConnectionSettings: () => {
+      return {
+        ...solanaBridge.loadConnectionSettings(),
+        clusters: solanaBridge.CLUSTERS,
+        endpoints: solanaBridge.ENDPOINTS,
+        active: solanaBridge.getActiveEndpoints(),
+        probe: window.endpointProbeReport || null,
+      };
+    },
+    setConnectionSettings: (settings) => {
+      const active = solanaBridge.applyConnectionSettings(settings);
+      websocketGameManager.setEndpoints({ wsUrl: active.rollupWsUrl, httpUrl: active.rollupUrl });
+      window.gameBridge.onConnectionSettingsChanged?.(window.gameBridge.getConnectionSettings());
+      return active;
+    },
+    // Switch cluster (localnet / devnet / mainnet-beta); the settings panel confirms with the player first
+    setCluster: (clusterId) => {
+      return window.gameBridge.setConnectionSettings({ cluster: clusterId });
+    },
+    // Ask the game to re-run its latency probe (picked up by EndpointProbe in main.rs)
+    reprobeEndpoints: () => {
+      window.__endpoint_probe_requested = true;
+    },
+    // Called from Rust when a probe finishes: { latencies: {id: ms|null}, bestRpc, bestRollup }
+    onEndpointProbeResults: (report) => {
+      debug.log("GAME_BRIDGE", "[Game Bridge] Endpoint probe results:", report);
+      const settings = solanaBridge.loadConnectionSettings();
+      // A probe started before a cluster switch measured the old cluster's endpoints
+      if (report.cluster && report.cluster !== settings.cluster) {
+        window.gameBridge.reprobeEndpoints();
+        return;
+      }
+      if (settings.mode === "auto") {
+        window.gameBridge.setConnectionSettings({
+          rpcId: report.bestRpc || settings.rpcId,
+          rollupId: report.bestRollup || settings.rollupId,
+        });
+      } else {
+        window.gameBridge.onConnectionSettingsChanged?.(window.gameBridge.getConnectionSettings());
+      }
+    },
+
+    // Game log verbosity per category ("net", "solana", "editor", "render", "game" or "all"),
+    // level "off", "error", "warn", "info" (default) or "debug", applied via _set_log_level_js
+    // Usage from the console: gameBridge.setLogLevel("net", "debug")
+    setLogLevel: (category, level) => {
+      const categoryId = category === "all" ? -1 : LOG_CATEGORY_IDS[category];
+      const levelId = LOG_LEVEL_IDS[level];
+      if (categoryId === undefined || levelId === undefined) {
+        console.warn("Unknown log category or level:", category, level);
+        return;
+      }
+      if (window.Module && window.Module._set_log_level_js) {
+        window.Module._set_log_level_js(categoryId, levelId);
+      }
+    },
+
+    // Mirror game log lines into a panel in the canvas, applied via _set_log_panel_js
+    setLogPanel: (open) => {
+      if (window.Module && window.Module._set_log_panel_js) {
+        window.Module._set_log_panel_js(!!open);
+      }
+    },
+
+    // Called from Rust on a panic or fatal error: { reason, location, panic, uptime, breadcrumbs }
+    onCrashReport: (report) => {
+      console.error("[Game Bridge] Crash report:", report);
+      window.dispatchEvent(new CustomEvent("gameCrashReport", { detail: report }));
+    },
+
+    // Netcode tunables (interp/extrapolation/reconciliation), polled by main.rs
+    // Usage from the console: gameBridge.setNetcodeConfig({ positionInterpSpeed: 20 })
+    getNetcodeConfig: () => {
+      return { ...(window.__netcode_config || {}) };
+    },
+    setNetcodeConfig: (partial) => {
+      try {
+        window.__netcode_config = { ...(window.__netcode_config || {}), ...partial };
+        window.__netcode_config_dirty = true;
+        try {
+          localStorage.setItem("netcodeConfig", JSON.stringify(window.__netcode_config));
+        } catch (_) {}
+      } catch (e) {
+        console.warn("Failed to set netcode config:", e);
+      }
+    },
+    resetNetcodeConfig: () => {
+      window.__netcode_config = {};
+      window.__netcode_config_dirty = true;
+      try {
+        localStorage.removeItem("netcodeConfig");
+      } catch (_) {}
+    },
+
     stopGameMode: () => {
       if (window.Module && window.Module._stop_game) {
         window.Module._stop_game();
@@ -517,7 +1096,91 @@ export function initGameBridge() {
       }
     },
 
+    // Lobby leader only, after setCurrentGame: run bots in the empty slots of teams A and B
+    setBotBackfill: (teamA, teamB) => {
+      debug.log("GAME_BRIDGE", "[Game Bridge] setBotBackfill called:", teamA, teamB);
+      if (window.Module && window.Module._set_bot_backfill_js) {
+        window.Module._set_bot_backfill_js(teamA, teamB);
+      } else {
+        debug.warn("GAME_BRIDGE", "⚠️ Module._set_bot_backfill_js not available");
+      }
+    },
+
+    setGameMode: (mode) => {
+      debug.log("GAME_BRIDGE", "[Game Bridge] setGameMode called:", mode);
+      if (window.Module && window.Module._set_game_mode_js) {
+        const stringPtr = createStringPointer(mode);
+        window.Module._set_game_mode_js(stringPtr);
+        freeStringPointer(stringPtr);
+      } else {
+        debug.warn("GAME_BRIDGE", "⚠️ Module._set_game_mode_js not available");
+      }
+    },
+
+    // Spawn loadout for the next match: weapon ids (["rifle", "shotgun"]) and the one in hand
+    setLoadout: (weapons, primary) => {
+      debug.log("GAME_BRIDGE", "[Game Bridge] setLoadout called:", weapons, primary);
+      if (window.Module && window.Module._set_loadout_js) {
+        const weaponsPtr = createStringPointer(weapons.join(","));
+        const primaryPtr = createStringPointer(primary);
+        window.Module._set_loadout_js(weaponsPtr, primaryPtr);
+        freeStringPointer(weaponsPtr);
+        freeStringPointer(primaryPtr);
+      } else {
+        debug.warn("GAME_BRIDGE", "⚠️ Module._set_loadout_js not available");
+      }
+    },
+
+    // Mirror the on-chain match start so the freeze countdown ends at the same time for everyone
+    // The freeze starts when clients enter Playing (match start + start countdown);
+    // the value is negative while the start countdown is still running
+    syncRoundStart: async (gamePubkey, clockOffset = 0) => {
+      if (!window.Module || !window.Module._sync_freeze_time_js) {
+        debug.warn("GAME_BRIDGE", "⚠️ Module._sync_freeze_time_js not available");
+        return;
+      }
+      const game = await solanaBridge.getGame(gamePubkey);
+      const raw = game?.matchStartTimestamp;
+      const matchStart = Number(raw?.toString ? raw.toString() : raw);
+      if (!matchStart) return;
+      window.Module._sync_freeze_time_js(chainNow(clockOffset) - playStartTime(matchStart));
+    },
+
+    // Start the round-end presentation (slow-mo, camera pan, banner)
+    // Resolves when the game dispatches 'roundPresentationDone' (or after a fallback timeout)
+    endRound: (winningTeam) => {
+      return new Promise((resolve) => {
+        if (!window.Module || !window.Module._end_round_js) {
+          resolve();
+          return;
+        }
+        const timeout = setTimeout(done, 5000);
+        function done() {
+          clearTimeout(timeout);
+          window.removeEventListener("roundPresentationDone", done);
+          resolve();
+        }
+        window.addEventListener("roundPresentationDone", done);
+        window.Module._end_round_js(winningTeam === "A" ? 1 : 2);
+      });
+    },
+
     // WebSocket real-time game state functions
+    // Player-data transport: "connected" (WebSocket), "degraded" (HTTP polling) or "disconnected"
+    getWebSocketStatus: () => {
+      switch (websocketGameManager.mode) {
+        case "websocket":
+          return "connected";
+        case "http":
+          return "degraded";
+        default:
+          return "disconnected";
+      }
+    },
+    onNetworkModeChange: (listener) => {
+      return websocketGameManager.onModeChange(listener);
+    },
+
     connectWebSocket: async () => {
       debug.log("WEBSOCKET", "[Game Bridge] connectWebSocket called");
       try {
@@ -541,6 +1204,9 @@ export function initGameBridge() {
         "[Game Bridge] subscribeToGamePlayers called:",
         gamePubkey
       );
+      // Listen for the match's gameplay events and relayed player updates from the start, not only once we send one
+      getGameEventChannel(gamePubkey);
+      getPlayerUpdateChannel(gamePubkey);
       try {
         // First, get all players in the game
         // Retry mechanism: GamePlayer accounts might not be created immediately when game starts
@@ -790,6 +1456,10 @@ export function initGameBridge() {
         "[Game Bridge] unsubscribeFromGamePlayers called:",
         gamePubkey
       );
+      gameEventChannels[gamePubkey]?.close();
+      delete gameEventChannels[gamePubkey];
+      playerUpdateChannels[gamePubkey]?.close();
+      delete playerUpdateChannels[gamePubkey];
       try {
         // Get all players in the game
         const players = await solanaBridge.getGamePlayers(gamePubkey);
@@ -877,9 +1547,94 @@ export function initGameBridge() {
     },
   };
 
+  // Restore persisted netcode tunables so they apply from the first frame
+  try {
+    const savedNetcode = localStorage.getItem("netcodeConfig");
+    if (savedNetcode) {
+      window.__netcode_config = JSON.parse(savedNetcode);
+      window.__netcode_config_dirty = true;
+    }
+  } catch (e) {
+    console.warn("Failed to restore netcode config:", e);
+  }
+
+  // Point the WebSocket manager at the saved rollup endpoint
+  try {
+    const active = solanaBridge.getActiveEndpoints();
+    websocketGameManager.setEndpoints({ wsUrl: active.rollupWsUrl, httpUrl: active.rollupUrl });
+  } catch (e) {
+    console.warn("Failed to restore connection settings:", e);
+  }
+
+  // Restore persisted mixer volumes (re-applied once the runtime is ready)
+  try {
+    const savedVolumes = localStorage.getItem("audioVolumes");
+    if (savedVolumes) {
+      window.__audio_volumes = JSON.parse(savedVolumes);
+    }
+    Object.entries(window.__audio_volumes || {}).forEach(([channel, volume]) =>
+      applyAudioVolume(channel, volume)
+    );
+    const savedUiSounds = localStorage.getItem("uiSoundsEnabled");
+    if (savedUiSounds !== null && window.Module && window.Module._set_ui_sounds_enabled_js) {
+      window.Module._set_ui_sounds_enabled_js(savedUiSounds === "true");
+    }
+  } catch (e) {
+    console.warn("Failed to restore audio volumes:", e);
+  }
+
   console.log("✅ Game bridge initialized");
 }
 
+/** Default mixer volumes (must match Mixer::default in game/src/audio/mixer.rs) */
+export const DEFAULT_AUDIO_VOLUMES = { master: 1.0, effects: 0.3, music: 0.5, ui: 0.6 };
+
+/** Default motion/flash comfort settings (must match ComfortSetting::default in game/src/game/comfort.rs) */
+export const DEFAULT_COMFORT_SETTINGS = {
+  screenShake: { enabled: true, intensity: 1.0 },
+  flash: { enabled: true, intensity: 1.0 },
+  viewBob: { enabled: true, intensity: 1.0 },
+};
+
+/** Default gun placement (must match ViewmodelSettings::default in game/src/game/viewmodel.rs) */
+export const DEFAULT_VIEWMODEL_SETTINGS = { leftHanded: false, offsetX: 0, offsetY: 0, offsetZ: 0, fov: 70 };
+
+/** Default view distance (must match ViewDistance::default in game/src/game/graphics.rs) */
+export const DEFAULT_VIEW_DISTANCE = { drawDistance: 100, fog: true };
+
+/** Comfort effect ids understood by _set_comfort_setting_js */
+const COMFORT_EFFECT_IDS = { screenShake: 0, flash: 1, viewBob: 2 };
+
+/** Color-blind mode ids understood by _set_colorblind_mode_js */
+const COLORBLIND_MODE_IDS = { off: 0, deuteranopia: 1, protanopia: 2, tritanopia: 3 };
+
+/** Render mode ids understood by _set_render_mode_js */
+const RENDER_MODE_IDS = { full: 0, retro: 1 };
+
+/** Log category and level ids understood by _set_log_level_js */
+const LOG_CATEGORY_IDS = { net: 0, solana: 1, editor: 2, render: 3, game: 4 };
+const LOG_LEVEL_IDS = { off: 0, error: 1, warn: 2, info: 3, debug: 4 };
+
+/** Menu tab ids understood by _set_menu_tab_js */
+const MENU_TAB_IDS = { mapeditor: 0, lobby: 1 };
+
+/** Mixer channel ids understood by _set_audio_volume_js */
+const AUDIO_CHANNEL_IDS = { master: 0, effects: 1, music: 2, ui: 3 };
+
+/**
+ * Push a mixer channel volume into the game (no-op until the runtime is ready)
+ */
+function applyAudioVolume(channel, volume) {
+  const channelId = AUDIO_CHANNEL_IDS[channel];
+  if (channelId === undefined) {
+    console.warn("Unknown audio channel:", channel);
+    return;
+  }
+  if (window.Module && window.Module._set_audio_volume_js) {
+    window.Module._set_audio_volume_js(channelId, volume);
+  }
+}
+
 /**
  * Call a game function from JavaScript
  * (For future use if you want to call C functions exported from the game)
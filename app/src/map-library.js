@@ -0,0 +1,94 @@
+/**
+ * Local Map Library
+ * Named editor saves kept in IndexedDB so web builds get the same multi-slot
+ * library native builds keep in their maps/ folder. Map bytes are the same
+ * Borsh encoding the editor exports as .fpssomap.
+ */
+
+const DB_NAME = "fpsdotso-maps";
+const DB_VERSION = 1;
+const STORE_NAME = "map-library";
+
+/**
+ * Open IndexedDB connection
+ */
+function openDB() {
+  return new Promise((resolve, reject) => {
+    const request = indexedDB.open(DB_NAME, DB_VERSION);
+
+    request.onerror = () => reject(request.error);
+    request.onsuccess = () => resolve(request.result);
+
+    request.onupgradeneeded = (event) => {
+      const db = event.target.result;
+      if (!db.objectStoreNames.contains(STORE_NAME)) {
+        db.createObjectStore(STORE_NAME, { keyPath: "name" });
+      }
+    };
+  });
+}
+
+/**
+ * Run one request against the library store
+ * @param {"readonly"|"readwrite"} mode - Transaction mode
+ * @param {Function} makeRequest - Gets the object store, returns an IDBRequest
+ */
+async function withStore(mode, makeRequest) {
+  const db = await openDB();
+  return new Promise((resolve, reject) => {
+    const transaction = db.transaction([STORE_NAME], mode);
+    const request = makeRequest(transaction.objectStore(STORE_NAME));
+
+    request.onsuccess = () => resolve(request.result);
+    request.onerror = () => reject(request.error);
+  });
+}
+
+/**
+ * List saved maps without their data
+ * @returns {Array<{name: string, modified: number, object_count: number, bytes: number}>}
+ *   Entries (snake_case to match the Rust LibraryEntry), modified in Unix seconds
+ */
+export async function listMaps() {
+  const records = await withStore("readonly", (store) => store.getAll());
+  return records.map(({ name, modified, objectCount, data }) => ({
+    name,
+    modified,
+    object_count: objectCount,
+    bytes: data.length,
+  }));
+}
+
+/**
+ * Save (or overwrite) a map
+ * @param {string} name - Save name
+ * @param {Uint8Array} data - Borsh-serialized map
+ * @param {number} objectCount - Object count shown in the Load dialog
+ */
+export async function saveMap(name, data, objectCount) {
+  const record = {
+    name,
+    modified: Math.floor(Date.now() / 1000),
+    objectCount,
+    data: Array.from(data),
+  };
+  await withStore("readwrite", (store) => store.put(record));
+}
+
+/**
+ * Load a saved map
+ * @param {string} name - Save name
+ * @returns {Uint8Array|null} The map bytes or null if there is no such save
+ */
+export async function loadMap(name) {
+  const record = await withStore("readonly", (store) => store.get(name));
+  return record ? new Uint8Array(record.data) : null;
+}
+
+/**
+ * Remove a saved map
+ * @param {string} name - Save name
+ */
+export async function deleteMap(name) {
+  await withStore("readwrite", (store) => store.delete(name));
+}
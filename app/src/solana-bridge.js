@@ -14,6 +14,10 @@ import {
   showMapRegistryTransaction 
 } from "./utils/toast-notifications.js";
 import { logTransaction, logTransactionPromise } from "./utils/debug-logger.js";
+import { formatTaggedName, validateTaggedName } from "./utils/clan-tag.js";
+import { loadSolanaClient, signAndSendAll } from "./utils/solana-client.js";
+import { BridgeErrorKind, bridgeError } from "./utils/bridge-error.js";
+import { chunkMapId, readMapManifest, tablesMapId } from "./utils/map-chunks.js";
 
 // Program IDs from the IDLs
 const PROGRAM_ID = new PublicKey(mapRegistryIdl.address);
@@ -50,11 +54,97 @@ class NodeWallet {
 // Magicblock Delegation Program (as defined in IDL)
 const DELEGATION_PROGRAM_ID = new PublicKey("DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh");
 
-// Cluster/Network configuration
-const RPC_URL = process.env.REACT_APP_SOLANA_RPC_URL || "http://127.0.0.1:8899";
-const EPHEMERAL_RPC_URL = process.env.REACT_APP_EPHEMERAL_RPC_URL || "http://127.0.0.1:8899";
+// Cluster/Network configuration (defaults from the environment)
+const DEFAULT_CLUSTER = process.env.REACT_APP_SOLANA_CLUSTER || "localnet";
+const DEFAULT_RPC_URL = process.env.REACT_APP_SOLANA_RPC_URL || "http://127.0.0.1:8899";
+const DEFAULT_EPHEMERAL_RPC_URL = process.env.REACT_APP_EPHEMERAL_RPC_URL || "http://127.0.0.1:8899";
+const DEFAULT_EPHEMERAL_WS_URL = process.env.REACT_APP_EPHEMERAL_WEBSOCKET_RPC_URL || "ws://127.0.0.1:7800";
+
+/**
+ * Clusters the session can run on. Wallets, lobbies and maps are separate per
+ * cluster; realFunds marks the one where transactions cost real SOL.
+ */
+export const CLUSTERS = [
+  { id: "localnet", label: "Localnet", color: "#888888", realFunds: false },
+  { id: "devnet", label: "Devnet", color: "#00d9ff", realFunds: false },
+  { id: "mainnet-beta", label: "Mainnet", color: "#ff4444", realFunds: true },
+];
+
+/**
+ * Endpoints offered in the connection settings (probed for latency by the game at startup)
+ * kind: "rpc" = base-layer Solana RPC, "rollup" = ephemeral rollup RPC (wsUrl used for subscriptions)
+ * cluster: which cluster the endpoint serves ("Default" ones serve the build's cluster)
+ */
+export const ENDPOINTS = [
+  { id: "rpc-default", cluster: DEFAULT_CLUSTER, kind: "rpc", region: "Default", url: DEFAULT_RPC_URL },
+  { id: "rpc-devnet", cluster: "devnet", kind: "rpc", region: "Solana Devnet", url: "https://api.devnet.solana.com" },
+  { id: "rpc-mainnet", cluster: "mainnet-beta", kind: "rpc", region: "Solana Mainnet", url: "https://api.mainnet-beta.solana.com" },
+  { id: "er-default", cluster: DEFAULT_CLUSTER, kind: "rollup", region: "Default", url: DEFAULT_EPHEMERAL_RPC_URL, wsUrl: DEFAULT_EPHEMERAL_WS_URL },
+  { id: "er-us", cluster: "devnet", kind: "rollup", region: "US", url: "https://devnet-us.magicblock.app", wsUrl: "wss://devnet-us.magicblock.app" },
+  { id: "er-eu", cluster: "devnet", kind: "rollup", region: "Europe", url: "https://devnet-eu.magicblock.app", wsUrl: "wss://devnet-eu.magicblock.app" },
+  { id: "er-as", cluster: "devnet", kind: "rollup", region: "Asia", url: "https://devnet-as.magicblock.app", wsUrl: "wss://devnet-as.magicblock.app" },
+  { id: "er-mainnet-us", cluster: "mainnet-beta", kind: "rollup", region: "US", url: "https://us.magicblock.app", wsUrl: "wss://us.magicblock.app" },
+  { id: "er-mainnet-eu", cluster: "mainnet-beta", kind: "rollup", region: "Europe", url: "https://eu.magicblock.app", wsUrl: "wss://eu.magicblock.app" },
+  { id: "er-mainnet-as", cluster: "mainnet-beta", kind: "rollup", region: "Asia", url: "https://as.magicblock.app", wsUrl: "wss://as.magicblock.app" },
+];
+
+// Connection settings: mode "auto" follows the latency probe, "manual" keeps the picked endpoints
+const CONNECTION_SETTINGS_KEY = "connectionSettings";
+export const DEFAULT_CONNECTION_SETTINGS = { mode: "auto", cluster: DEFAULT_CLUSTER, rpcId: "rpc-default", rollupId: "er-default" };
+
+export function loadConnectionSettings() {
+  try {
+    const saved = JSON.parse(localStorage.getItem(CONNECTION_SETTINGS_KEY) || "null");
+    return resolveConnectionSettings({ ...DEFAULT_CONNECTION_SETTINGS, ...(saved || {}) });
+  } catch (e) {
+    return { ...DEFAULT_CONNECTION_SETTINGS };
+  }
+}
+
+export function findCluster(id) {
+  return CLUSTERS.find((cluster) => cluster.id === id);
+}
+
+function findEndpoint(id) {
+  return ENDPOINTS.find((endpoint) => endpoint.id === id);
+}
+
+function endpointUrl(id, fallback) {
+  return findEndpoint(id)?.url || fallback;
+}
+
+/** Endpoints serving a cluster, optionally of one kind */
+export function clusterEndpoints(cluster, kind = null) {
+  return ENDPOINTS.filter((endpoint) => endpoint.cluster === cluster && (!kind || endpoint.kind === kind));
+}
+
+/**
+ * Keep the picked endpoints inside the chosen cluster
+ * An endpoint from another cluster (e.g. right after switching) falls back to the cluster's first one.
+ */
+function resolveConnectionSettings(settings) {
+  const cluster = findCluster(settings.cluster) ? settings.cluster : DEFAULT_CLUSTER;
+  const pick = (id, kind) =>
+    findEndpoint(id)?.cluster === cluster ? id : clusterEndpoints(cluster, kind)[0]?.id || id;
+  return { ...settings, cluster, rpcId: pick(settings.rpcId, "rpc"), rollupId: pick(settings.rollupId, "rollup") };
+}
+
+// Publish the endpoint list for the Rust latency probe (only the active cluster is probed)
+function publishEndpoints(cluster) {
+  if (typeof window === "undefined") return;
+  window.__fpsCluster = cluster;
+  window.__fpsEndpoints = clusterEndpoints(cluster).map(({ id, cluster, kind, region, url }) => ({ id, cluster, kind, region, url }));
+}
+
+const initialConnectionSettings = loadConnectionSettings();
+let CLUSTER = initialConnectionSettings.cluster;
+let RPC_URL = endpointUrl(initialConnectionSettings.rpcId, DEFAULT_RPC_URL);
+let EPHEMERAL_RPC_URL = endpointUrl(initialConnectionSettings.rollupId, DEFAULT_EPHEMERAL_RPC_URL);
+let EPHEMERAL_WS_URL = findEndpoint(initialConnectionSettings.rollupId)?.wsUrl || DEFAULT_EPHEMERAL_WS_URL;
+console.log(`🌐 Using Solana cluster: ${CLUSTER}`);
 console.log(`🌐 Using Solana RPC URL: ${RPC_URL}`);
 console.log(`⚡ Using Ephemeral RPC URL: ${EPHEMERAL_RPC_URL}`);
+publishEndpoints(CLUSTER);
 
 // Global state
 let connection = null;
@@ -116,6 +206,8 @@ export function getModelType(typeName) {
 
 /**
  * Deserialize Borsh-encoded Map data from Rust game
+ * Only the objects are read: names/tags, ambiance, glows, collision flags,
+ * triggers, script and ambient sounds after the spawn point are skipped
  * @param {Uint8Array} bytes - Borsh-serialized Map data
  * @returns {Array<Object>} Array of MapObject structures
  */
@@ -288,7 +380,8 @@ export function mapObjectToGameObject(mapObj) {
 
 /**
  * Serialize MapObjects back to Borsh format matching Rust Map struct
- * This creates a complete Map struct that Rust can deserialize
+ * This creates a complete Map struct that Rust can deserialize, with the
+ * side tables after the spawn point left out (the game reads them as empty)
  * @param {string} mapName - Name of the map
  * @param {Array<Object>} mapObjects - Array of MapObject structures from Solana
  * @returns {Uint8Array} Borsh-serialized Map data
@@ -499,6 +592,97 @@ export function adjustInputRateBasedOnLatency(latencyMs) {
   }
 }
 
+/**
+ * Recent average round trip to the ephemeral rollup, in ms (null before any measurement)
+ */
+export function getLocalLatency() {
+  if (recentLatencies.length === 0) return null;
+  return recentLatencies.reduce((a, b) => a + b, 0) / recentLatencies.length;
+}
+
+/**
+ * Currently active cluster and endpoint URLs
+ * @returns {{cluster: string, rpcUrl: string, rollupUrl: string, rollupWsUrl: string}}
+ */
+export function getActiveEndpoints() {
+  return { cluster: CLUSTER, rpcUrl: RPC_URL, rollupUrl: EPHEMERAL_RPC_URL, rollupWsUrl: EPHEMERAL_WS_URL };
+}
+
+/**
+ * Save connection settings and switch to the chosen cluster / endpoints
+ * Existing connections are rebuilt; programs are re-created lazily on next use.
+ * @param {{mode: string, cluster: string, rpcId: string, rollupId: string}} settings
+ * @returns {{cluster: string, rpcUrl: string, rollupUrl: string, rollupWsUrl: string}} What is now in use
+ */
+export function applyConnectionSettings(settings) {
+  const next = resolveConnectionSettings({ ...loadConnectionSettings(), ...settings });
+  localStorage.setItem(CONNECTION_SETTINGS_KEY, JSON.stringify(next));
+
+  const rpcUrl = endpointUrl(next.rpcId, DEFAULT_RPC_URL);
+  const rollupUrl = endpointUrl(next.rollupId, DEFAULT_EPHEMERAL_RPC_URL);
+  const clusterChanged = next.cluster !== CLUSTER;
+  if (!clusterChanged && rpcUrl === RPC_URL && rollupUrl === EPHEMERAL_RPC_URL) {
+    return getActiveEndpoints();
+  }
+
+  console.log(`🌐 Switching endpoints: cluster=${next.cluster} rpc=${rpcUrl} rollup=${rollupUrl}`);
+  CLUSTER = next.cluster;
+  RPC_URL = rpcUrl;
+  EPHEMERAL_RPC_URL = rollupUrl;
+  EPHEMERAL_WS_URL = findEndpoint(next.rollupId)?.wsUrl || DEFAULT_EPHEMERAL_WS_URL;
+
+  if (clusterChanged) {
+    // Accounts from the old cluster mean nothing on the new one
+    publishEndpoints(CLUSTER);
+    playerDataCache.clear();
+    if (typeof window !== "undefined") {
+      window.endpointProbeReport = null;
+      window.__endpoint_probe_requested = true;
+      window.dispatchEvent(new CustomEvent("clusterChanged", { detail: { cluster: CLUSTER } }));
+    }
+  }
+
+  if (connection) {
+    connection = new Connection(RPC_URL, "confirmed");
+    ephemeralConnection = new Connection(EPHEMERAL_RPC_URL, "confirmed");
+    lastConnectionRefresh = Date.now();
+
+    if (wallet) {
+      provider = new AnchorProvider(connection, wallet, { commitment: "confirmed" });
+      program = new Program(mapRegistryIdl, provider);
+      matchmakingProgram = new Program(matchmakingIdl, provider);
+    }
+    ephemeralProvider = null;
+    gameProgram = null; // Re-created against the new rollup on next use
+
+    if (typeof window !== "undefined") {
+      window.solanaConnection = connection;
+      window.ephemeralConnection = ephemeralConnection;
+    }
+  }
+
+  return getActiveEndpoints();
+}
+
+/** Cluster the session is on ("localnet", "devnet" or "mainnet-beta") */
+export function getCluster() {
+  return CLUSTER;
+}
+
+/**
+ * Switch the session to another cluster, using that cluster's default endpoints
+ * Callers are expected to have confirmed the switch with the player.
+ * @param {string} clusterId - One of CLUSTERS
+ * @returns {{cluster: string, rpcUrl: string, rollupUrl: string, rollupWsUrl: string}|null} Null for an unknown cluster
+ */
+export function setCluster(clusterId) {
+  if (!findCluster(clusterId)) {
+    console.warn(`⚠️ Unknown cluster: ${clusterId}`);
+    return null;
+  }
+  return applyConnectionSettings({ cluster: clusterId });
+}
+
 /**
  * Initialize the Solana connection and Anchor program
  * This must be called before any other functions
@@ -528,6 +712,7 @@ export async function initSolanaClient() {
         measureLatency: measureLatency,
         refreshEphemeralConnection: refreshEphemeralConnection,
         adjustInputRateBasedOnLatency: adjustInputRateBasedOnLatency,
+        getLocalLatency: getLocalLatency,
       };
     }
 
@@ -721,8 +906,37 @@ export async function initializeRegistry() {
   }
 }
 
+/**
+ * Map bytes the solana-client module takes: Borsh bytes from Rust as-is, game objects encoded
+ * @param {Array<Object>|Uint8Array} mapObjectsOrBytes - Array of game objects OR Borsh-serialized Map data
+ */
+function toMapBytes(name, mapObjectsOrBytes) {
+  if (mapObjectsOrBytes instanceof Uint8Array) {
+    return mapObjectsOrBytes;
+  }
+  if (Array.isArray(mapObjectsOrBytes)) {
+    return serializeMapToBorsh(name, mapObjectsOrBytes.map((obj) => gameObjectToMapObject(obj)));
+  }
+  throw new Error("Invalid mapObjectsOrBytes: must be Uint8Array or Array");
+}
+
+/**
+ * Objects stored in the data account of one map id
+ */
+async function fetchMapDataObjects(mapId) {
+  const [mapDataPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("fps.so map-data"), Buffer.from(mapId)],
+    program.programId
+  );
+  const mapData = await program.account.mapData.fetch(mapDataPda);
+  return mapData.objects;
+}
+
 /**
  * Create a new map
+ * The solana-client module builds the transactions (registry initialization, the
+ * head, past 500 objects one per chunk, and the side-table accounts - see
+ * utils/map-chunks.js); the wallet signs them here
  * @param {string} mapId - Unique identifier for the map
  * @param {string} name - Display name
  * @param {string} description - Map description
@@ -736,89 +950,34 @@ export async function createMap(
   isDefault,
   mapObjectsOrBytes
 ) {
-  if (!program || !wallet) {
-    console.error("Program not initialized or wallet not connected");
+  if (!connection || !wallet) {
+    console.error("Solana client not initialized or wallet not connected");
     return null;
   }
 
   try {
     console.log(`📝 Creating map: ${name} (${mapId})`);
 
-    // Ensure registry is initialized first
-    await initializeRegistry();
-
-    let mapData;
-
-    // Check if input is Uint8Array (Borsh bytes from Rust) or object array
-    if (mapObjectsOrBytes instanceof Uint8Array) {
-      console.log("📦 Input is Borsh-encoded bytes, deserializing...");
-      // Deserialize Borsh bytes to get MapObject array
-      mapData = deserializeMapFromBorsh(mapObjectsOrBytes);
-    } else if (Array.isArray(mapObjectsOrBytes)) {
-      console.log(
-        `📦 Input is object array, converting ${mapObjectsOrBytes.length} objects...`
-      );
-      // Convert game objects to MapObject structures
-      mapData = mapObjectsOrBytes.map((obj, idx) => {
-        try {
-          const converted = gameObjectToMapObject(obj);
-          console.log(`✓ Converted object ${idx}:`, converted);
-          return converted;
-        } catch (err) {
-          console.error(`❌ Failed to convert object ${idx}:`, obj, err);
-          throw err;
-        }
-      });
-    } else {
-      throw new Error("Invalid mapObjectsOrBytes: must be Uint8Array or Array");
-    }
-
-    console.log("📦 Final map data for Solana:", mapData.length, "objects");
-
-    // Derive PDAs
-    const [mapMetadataPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("fps.so map-metadata"), Buffer.from(mapId)],
-      program.programId
-    );
-
-    const [mapDataPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("fps.so map-data"), Buffer.from(mapId)],
-      program.programId
-    );
-
-    const [registryPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("fps.so map-registry")],
-      program.programId
-    );
-
-    const [userMapIndexPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("fps.so user-map-index"), wallet.publicKey.toBuffer()],
-      program.programId
+    const client = await loadSolanaClient();
+    const transactions = await client.createMap(
+      RPC_URL,
+      wallet.publicKey.toString(),
+      mapId,
+      name,
+      description,
+      isDefault,
+      toMapBytes(name, mapObjectsOrBytes)
     );
+    console.log(`📦 Map split into ${transactions.length} transaction(s)`);
 
     const tx = await showMapRegistryTransaction(
       `Creating map "${name}"`,
-      program.methods
-        .createMap(mapId, name, description, isDefault, mapData)
-        .accounts({
-          mapMetadata: mapMetadataPda,
-          mapDataAccount: mapDataPda,
-          mapRegistry: registryPda,
-          userMapIndex: userMapIndexPda,
-          user: wallet.publicKey,
-          systemProgram: web3.SystemProgram.programId,
-        })
-        .rpc(),
+      signAndSendAll(connection, wallet, transactions).then((signatures) => signatures[0]),
       'createMap' // Function name
     );
 
     console.log("✅ Map created! Transaction:", tx);
-    return {
-      mapId,
-      transaction: tx,
-      mapMetadataPda: mapMetadataPda.toString(),
-      mapDataPda: mapDataPda.toString(),
-    };
+    return { mapId, transaction: tx };
   } catch (error) {
     console.error("❌ Failed to create map:", error);
     console.error("Error details:", error.logs || error.message);
@@ -853,46 +1012,35 @@ export async function getMapMetadata(mapId) {
 
 /**
  * Fetch map data
+ * The solana-client module joins the objects of every chunk of a chunked map
+ * and the side tables (see getMapHead for streaming instead)
  * @param {string} mapId - The map ID to fetch
  * @param {string} format - Return format: 'gameObjects', 'mapObjects', or 'borsh'
  * @returns {Array<Object>|Uint8Array} Array of objects or Borsh bytes depending on format
  */
 export async function getMapData(mapId, format = "gameObjects") {
-  if (!program) {
-    console.error("Program not initialized");
+  if (!connection) {
+    console.error("Solana client not initialized");
     return null;
   }
 
   try {
-    // Fetch both metadata and data
-    const [mapMetadataPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("fps.so map-metadata"), Buffer.from(mapId)],
-      program.programId
-    );
-
-    const [mapDataPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("fps.so map-data"), Buffer.from(mapId)],
-      program.programId
-    );
-
-    const [metadata, mapData] = await Promise.all([
-      program.account.mapMetadata.fetch(mapMetadataPda),
-      program.account.mapData.fetch(mapDataPda),
-    ]);
-
-    console.log("📊 Map data fetched:", mapData.objects.length, "objects");
+    const client = await loadSolanaClient();
+    const bytes = await client.getMapData(RPC_URL, mapId);
+    console.log("📊 Map data fetched:", bytes.length, "bytes");
 
     // Return in requested format
     if (format === "borsh") {
-      // Serialize to Borsh bytes matching Rust Map struct
-      return serializeMapToBorsh(metadata.name, mapData.objects);
-    } else if (format === "gameObjects") {
+      // Borsh bytes matching Rust Map struct
+      return bytes;
+    }
+    const objects = deserializeMapFromBorsh(bytes);
+    if (format === "gameObjects") {
       // Convert to game object format
-      return mapData.objects.map((obj) => mapObjectToGameObject(obj));
-    } else {
-      // Return raw MapObjects from Solana
-      return mapData.objects;
+      return objects.map((obj) => mapObjectToGameObject(obj));
     }
+    // Return raw MapObjects
+    return objects;
   } catch (error) {
     console.error("❌ Failed to fetch map data:", error);
     return null;
@@ -900,30 +1048,69 @@ export async function getMapData(mapId, format = "gameObjects") {
 }
 
 /**
- * Get all maps created by a user
- * @param {string} userPublicKey - User's public key (optional, defaults to connected wallet)
+ * Fetch only the head chunk of a map, to start a match before the rest streams in
+ * The solana-client module adds the map's side tables, which cover the later chunks too
+ * @param {string} mapId - The map ID to fetch
+ * @returns {{bytes: Uint8Array, name: string, chunks: number}|null} Borsh bytes of the head chunk and the chunk count
  */
-export async function getUserMaps(userPublicKey = null) {
+export async function getMapHead(mapId) {
+  if (!connection) {
+    console.error("Solana client not initialized");
+    return null;
+  }
+
+  try {
+    const client = await loadSolanaClient();
+    const head = await client.getMapHead(RPC_URL, mapId);
+
+    console.log("📊 Map head fetched:", head.bytes.length, "bytes, chunk 1 of", head.chunks);
+    return head;
+  } catch (error) {
+    console.error("❌ Failed to fetch map head:", error);
+    return null;
+  }
+}
+
+/**
+ * Fetch one of the later chunks of a chunked map
+ * @param {string} mapId - The map ID (of the head)
+ * @param {number} index - Chunk index, from 1
+ * @param {string} mapName - Name written into the Borsh header
+ * @returns {Uint8Array|null} Borsh bytes holding the chunk's objects
+ */
+export async function getMapChunk(mapId, index, mapName) {
   if (!program) {
     console.error("Program not initialized");
     return null;
   }
 
   try {
-    const pubkey = userPublicKey
-      ? new PublicKey(userPublicKey)
-      : wallet.publicKey;
+    const objects = await fetchMapDataObjects(chunkMapId(mapId, index));
+    return serializeMapToBorsh(mapName, objects);
+  } catch (error) {
+    console.error(`❌ Failed to fetch chunk ${index} of map ${mapId}:`, error);
+    return null;
+  }
+}
 
-    const [userMapIndexPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("fps.so user-map-index"), pubkey.toBuffer()],
-      program.programId
-    );
+/**
+ * Get all maps created by a user
+ * Chunk accounts of large maps are left out by the solana-client module
+ * @param {string} userPublicKey - User's public key (optional, defaults to connected wallet)
+ * @returns {{owner: string, mapCount: number, mapIds: Array<string>}|null}
+ */
+export async function getUserMaps(userPublicKey = null) {
+  if (!connection) {
+    console.error("Solana client not initialized");
+    return null;
+  }
 
-    const userMapIndex = await program.account.userMapIndex.fetch(
-      userMapIndexPda
-    );
-    console.log("📊 User maps:", userMapIndex);
-    return userMapIndex;
+  try {
+    const owner = userPublicKey ? String(userPublicKey) : wallet.publicKey.toString();
+    const client = await loadSolanaClient();
+    const userMaps = await client.getUserMaps(RPC_URL, owner);
+    console.log("📊 User maps:", userMaps);
+    return userMaps;
   } catch (error) {
     console.error("❌ Failed to fetch user maps:", error);
     return null;
@@ -1000,44 +1187,38 @@ export async function updateMapMetadata(
 }
 
 /**
- * Update map data (replaces all objects)
- * @param {string} mapId - The map ID to update
- * @param {Array<Object>} mapObjects - New array of game objects
+ * Update map data in place (replaces all objects and side tables)
+ * The solana-client module plans the chunk and side-table account rewrites,
+ * creations and deletions;
+ * the wallet signs them here
+ * @param {string} mapId - The map ID to update (must be owned by the connected wallet)
+ * @param {Array<Object>|Uint8Array} mapObjectsOrBytes - Array of game objects OR Borsh-serialized Map data
+ * @returns {{transaction: string}|null}
  */
-export async function updateMapData(mapId, mapObjects) {
-  if (!program || !wallet) {
-    console.error("Program not initialized or wallet not connected");
+export async function updateMapData(mapId, mapObjectsOrBytes) {
+  if (!connection || !wallet) {
+    console.error("Solana client not initialized or wallet not connected");
     return null;
   }
 
   try {
     console.log(`📝 Updating map data: ${mapId}`);
-    console.log(`📦 New map objects count: ${mapObjects.length}`);
 
-    // Convert game objects to MapObject structures
-    const mapData = mapObjects.map((obj) => gameObjectToMapObject(obj));
-
-    // Derive PDAs
-    const [mapMetadataPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("fps.so map-metadata"), Buffer.from(mapId)],
-      program.programId
+    const client = await loadSolanaClient();
+    const transactions = await client.updateMap(
+      RPC_URL,
+      wallet.publicKey.toString(),
+      mapId,
+      toMapBytes(mapId, mapObjectsOrBytes)
     );
+    console.log(`📦 Map update split into ${transactions.length} transaction(s)`);
 
-    const [mapDataPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("fps.so map-data"), Buffer.from(mapId)],
-      program.programId
+    const tx = await showMapRegistryTransaction(
+      `Updating map "${mapId}"`,
+      signAndSendAll(connection, wallet, transactions).then((signatures) => signatures[0]),
+      'updateMapData' // Function name
     );
 
-    const tx = await program.methods
-      .updateMapData(mapData)
-      .accounts({
-        mapMetadata: mapMetadataPda,
-        mapDataAccount: mapDataPda,
-        user: wallet.publicKey,
-        systemProgram: web3.SystemProgram.programId,
-      })
-      .rpc();
-
     console.log("✅ Map data updated! Transaction:", tx);
     return { transaction: tx };
   } catch (error) {
@@ -1047,6 +1228,60 @@ export async function updateMapData(mapId, mapObjects) {
   }
 }
 
+/**
+ * Fingerprint of a map's on-chain data (FNV-1a of the head's data account and
+ * its side-table accounts)
+ * The map registry keeps no version counter, so lobbies compare fingerprints
+ * to notice the creator republishing the map while they wait
+ * @param {string} mapId - The map ID to fingerprint
+ * @returns {string|null} 8 hex digits, null if the map data can't be fetched
+ */
+export async function getMapFingerprint(mapId) {
+  if (!program) return null;
+
+  try {
+    const [mapMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fps.so map-metadata"), Buffer.from(mapId)],
+      program.programId
+    );
+    const metadata = await program.account.mapMetadata.fetchNullable(mapMetadataPda);
+    if (!metadata) return null;
+    const { tables } = readMapManifest(metadata.description);
+    const ids = [mapId];
+    for (let i = 1; i <= tables; i++) {
+      ids.push(tablesMapId(mapId, i));
+    }
+
+    const dataPdas = ids.map((id) => PublicKey.findProgramAddressSync(
+      [Buffer.from("fps.so map-data"), Buffer.from(id)],
+      program.programId
+    )[0]);
+    const accounts = await connection.getMultipleAccountsInfo(dataPdas);
+    if (accounts.some((accountInfo) => !accountInfo)) return null;
+
+    let hash = 0x811c9dc5;
+    for (const accountInfo of accounts) {
+      for (const byte of accountInfo.data) {
+        hash = Math.imul(hash ^ byte, 0x01000193);
+      }
+    }
+    return (hash >>> 0).toString(16).padStart(8, "0");
+  } catch (error) {
+    console.error("❌ Failed to fingerprint map:", error);
+    return null;
+  }
+}
+
+/**
+ * Lobbies still waiting to start that are set up with a map
+ * Used to warn when republishing a map other players are about to play
+ * @param {string} mapId - The map ID to look for
+ */
+export async function getLobbiesUsingMap(mapId) {
+  const games = await getAllGames(0);
+  return games.filter((game) => game.mapId === mapId);
+}
+
 /**
  * Delete a map (only the creator can delete)
  * @param {string} mapId - The map ID to delete
@@ -1076,8 +1311,9 @@ export async function deleteMap(mapId) {
       program.programId
     );
 
-    // Fetch metadata to get creator
+    // Fetch metadata to get creator (and the chunks and side tables to delete with the map)
     const metadata = await program.account.mapMetadata.fetch(mapMetadataPda);
+    const { chunks, tables } = readMapManifest(metadata.description);
 
     const tx = await program.methods
       .deleteMap()
@@ -1091,6 +1327,13 @@ export async function deleteMap(mapId) {
       })
       .rpc();
 
+    for (let i = 1; i < chunks; i++) {
+      await deleteMap(chunkMapId(mapId, i));
+    }
+    for (let i = 1; i <= tables; i++) {
+      await deleteMap(tablesMapId(mapId, i));
+    }
+
     console.log("✅ Map deleted! Transaction:", tx);
     return { transaction: tx };
   } catch (error) {
@@ -1129,8 +1372,9 @@ export async function getPlayerStats(playerId) {
 /**
  * Initialize a new player
  * @param {string} username - Player's username
+ * @param {string} clanTag - Optional clan tag (normalized, validated with the username)
  */
-export async function initPlayer(username) {
+export async function initPlayer(username, clanTag = "") {
   if (!matchmakingProgram || !wallet) {
     console.error(
       "Matchmaking program not initialized or wallet not connected"
@@ -1142,6 +1386,13 @@ export async function initPlayer(username) {
   const ephemeralKeypair = EphemeralWallet.getEphemeralKeypair();
 
   try {
+    // The clan tag is stored as part of the on-chain username ("[TAG] name")
+    const tagError = validateTaggedName(clanTag, username);
+    if (tagError) {
+      throw new Error(tagError);
+    }
+    username = formatTaggedName(clanTag, username);
+
     console.log(`📝 Initializing player: ${username}`);
 
     // Validate username length (3-32 characters as per smart contract)
@@ -1263,7 +1514,7 @@ export async function initGame(mapId) {
     if (currentGame) {
       console.warn("⚠️ Player is already in a game:", currentGame);
       return {
-        error: "PlayerAlreadyInGame",
+        error: bridgeError(BridgeErrorKind.PlayerAlreadyInGame, { currentGame }),
         message:
           "You are already in a game. Please leave the current game first.",
         currentGame: currentGame,
@@ -1362,7 +1613,7 @@ export async function leaveCurrentGame() {
     if (!currentGame) {
       console.warn("⚠️ Player is not in any game");
       return {
-        error: "NotInGame",
+        error: bridgeError(BridgeErrorKind.NotInGame),
         message: "You are not currently in any game.",
       };
     }
@@ -1419,7 +1670,7 @@ export async function joinGame(gamePubkey) {
     if (currentGame) {
       console.warn("⚠️ Player is already in a game:", currentGame);
       return {
-        error: "PlayerAlreadyInGame",
+        error: bridgeError(BridgeErrorKind.PlayerAlreadyInGame, { currentGame }),
         message:
           "You are already in a game. Please leave the current game first.",
         currentGame: currentGame,
@@ -1477,7 +1728,7 @@ export async function joinAsSpectator(gamePubkey) {
     if (currentGame) {
       console.warn("⚠️ Player is already in a game:", currentGame);
       return {
-        error: "PlayerAlreadyInGame",
+        error: bridgeError(BridgeErrorKind.PlayerAlreadyInGame, { currentGame }),
         message:
           "You are already in a game. Please leave the current game first.",
         currentGame: currentGame,
@@ -1858,6 +2109,24 @@ export async function getPlayer(userPublicKey = null) {
  * Get game account data
  * @param {string} gamePubkey - The game's public key
  */
+/**
+ * Seconds to add to the local clock to get the cluster clock (latest block time)
+ * Block times are whole seconds, so this is only accurate to about a second
+ */
+export async function getChainClockOffset() {
+  const chainConnection = matchmakingProgram?.provider?.connection || connection;
+  if (!chainConnection) return 0;
+
+  try {
+    const slot = await chainConnection.getSlot();
+    const blockTime = await chainConnection.getBlockTime(slot);
+    return blockTime ? blockTime - Date.now() / 1000 : 0;
+  } catch (error) {
+    console.warn("⚠️ Failed to read chain clock, using local time:", error);
+    return 0;
+  }
+}
+
 export async function getGame(gamePubkey) {
   if (!matchmakingProgram) {
     console.error("Matchmaking program not initialized");
@@ -2169,6 +2438,70 @@ export async function getAvailableGames() {
   }
 }
 
+/**
+ * Count the matches played on each map
+ * The map registry has no play counter, so this counts the matchmaking program's
+ * Game accounts whose match started (active, ended or paused) on each map
+ * @param {string[]} mapIds - The map IDs to count
+ * @returns {Object<string, number>} Play counts keyed by map ID, 0 for maps nobody played
+ */
+export async function getMapPlayCounts(mapIds) {
+  const counts = {};
+  for (const mapId of mapIds) {
+    counts[mapId] = 0;
+  }
+
+  if (!matchmakingProgram || !connection || mapIds.length === 0) {
+    return counts;
+  }
+
+  try {
+    const accounts = await connection.getProgramAccounts(matchmakingProgram.programId, {
+      filters: [{ memcmp: matchmakingProgram.coder.accounts.memcmp("game") }],
+    });
+    for (const { account } of accounts) {
+      let game;
+      try {
+        game = matchmakingProgram.coder.accounts.decode("game", account.data);
+      } catch (_) {
+        continue; // Accounts from an older program layout
+      }
+      // 0 = waiting: the lobby never got to play
+      if (game.gameState !== 0 && counts[game.mapId] !== undefined) {
+        counts[game.mapId] += 1;
+      }
+    }
+  } catch (error) {
+    console.error("❌ Failed to count map plays:", error);
+  }
+
+  return counts;
+}
+
+/**
+ * Get live games that can be spectated (active, not private)
+ */
+export async function getLiveGames() {
+  if (!matchmakingProgram) {
+    console.error("Matchmaking program not initialized");
+    return [];
+  }
+
+  try {
+    const allGames = await getAllGames();
+    const liveGames = allGames.filter(
+      (game) =>
+        game.gameState === 1 && // active (0 = waiting, 1 = active, 2 = ended, 3 = paused)
+        !game.isPrivate
+    );
+    console.log(`📺 Found ${liveGames.length} live games to spectate`);
+    return liveGames;
+  } catch (error) {
+    console.error("❌ Failed to fetch live games:", error);
+    return [];
+  }
+}
+
 /**
  * Check if Solana client is ready
  */
@@ -2391,6 +2724,7 @@ export async function getAllPlayersInGame(gamePubkey) {
         gamePlayers.push({
           publicKey: playerPubkey.toString(),
           username: playerAccount.username,
+          signingKey: playerAccount.signingKey.toString(), // What the game relay knows them by
           team: "A",
           level: playerAccount.level,
           matches: playerAccount.totalMatchesPlayed,
@@ -2420,6 +2754,7 @@ export async function getAllPlayersInGame(gamePubkey) {
         gamePlayers.push({
           publicKey: playerPubkey.toString(),
           username: playerAccount.username,
+          signingKey: playerAccount.signingKey.toString(),
           team: "B",
           level: playerAccount.level,
           matches: playerAccount.totalMatchesPlayed,
@@ -2649,10 +2984,39 @@ export function getCurrentPlayerEphemeralKey() {
   return ephemeralKey;
 }
 
+// Recent input ticks with their chained checksums (computed in Rust), so a
+// validator can replay the inputs behind each processInput transaction
+const INPUT_CHECKSUM_LOG_SIZE = 600; // ~30s at 20 inputs/s
+let inputChecksumLog = [];
+
+function recordInputChecksum(input, signature) {
+  if (typeof input.tick !== "number" || typeof input.checksum !== "number") return;
+  inputChecksumLog.push({
+    tick: input.tick,
+    checksum: input.checksum,
+    gameId: input.gameId,
+    signature: signature || null,
+  });
+  if (inputChecksumLog.length > INPUT_CHECKSUM_LOG_SIZE) {
+    inputChecksumLog = inputChecksumLog.slice(-INPUT_CHECKSUM_LOG_SIZE);
+  }
+}
+
+/**
+ * Get the recent input tick/checksum log (for movement validation)
+ * @param {string} [gameId] - Only return entries for this game
+ * @returns {Array<{tick: number, checksum: number, gameId: string, signature: string|null}>}
+ */
+export function getInputChecksumLog(gameId) {
+  return gameId
+    ? inputChecksumLog.filter((entry) => entry.gameId === gameId)
+    : [...inputChecksumLog];
+}
+
 /**
  * Send player input to game program (movement and rotation)
  * This uses the ephemeral wallet and ephemeral RPC for high-speed transactions
- * @param {Object} input - Player input {forward, backward, left, right, rotationX, rotationY, rotationZ, deltaTime, gameId}
+ * @param {Object} input - Player input {forward, backward, left, right, rotationX, rotationY, rotationZ, deltaTime, gameId, tick, checksum}
  * @returns {string} Transaction signature
  */
 export async function sendPlayerInput(input) {
@@ -2730,6 +3094,7 @@ export async function sendPlayerInput(input) {
       'processInput' // Function name
     );
 
+    recordInputChecksum(input, tx);
     return tx;
   } catch (error) {
     //console.error("❌ Failed to send player input:", error);
@@ -2793,7 +3158,7 @@ export async function shootPlayer(damage, gameIdPubkey, otherPlayerPdas = []) {
 
     // Call shoot instruction with kill_score parameter (100 points per kill)
     const killScore = 100;
-    const tx = await gameProgram.methods
+    const txPromise = gameProgram.methods
       .shoot(damage, killScore)
       .accounts({
         shooter: gamePlayerPda,
@@ -2801,6 +3166,13 @@ export async function shootPlayer(damage, gameIdPubkey, otherPlayerPdas = []) {
       })
       .remainingAccounts(remainingAccounts)
       .rpc({ skipPreflight: true });
+    const tx = await logTransactionPromise(
+      'Game',
+      'Shoot',
+      EPHEMERAL_RPC_URL,
+      txPromise,
+      'shootPlayer'
+    );
 
     console.log(`✅ Shoot transaction:`, tx);
 
@@ -2857,13 +3229,20 @@ export async function awardKill(scorePoints, gameIdPubkey) {
     );
 
     // Call award_kill instruction
-    const tx = await gameProgram.methods
+    const txPromise = gameProgram.methods
       .awardKill(scorePoints)
       .accounts({
         shooter: gamePlayerPda,
         authority: ephemeralPublicKey,
       })
       .rpc({ skipPreflight: true });
+    const tx = await logTransactionPromise(
+      'Game',
+      'Award Kill',
+      EPHEMERAL_RPC_URL,
+      txPromise,
+      'awardKill'
+    );
 
     console.log(`✅ Kill awarded, transaction:`, tx);
     return tx;
@@ -2917,13 +3296,20 @@ export async function respawnPlayer(gameIdPubkey, spawnX, spawnY, spawnZ) {
     );
 
     // Call respawn_player instruction
-    const tx = await gameProgram.methods
+    const txPromise = gameProgram.methods
       .respawnPlayer(spawnX, spawnY, spawnZ)
       .accounts({
         gamePlayer: gamePlayerPda,
         authority: ephemeralPublicKey,
       })
       .rpc({ skipPreflight: true });
+    const tx = await logTransactionPromise(
+      'Game',
+      'Respawn',
+      EPHEMERAL_RPC_URL,
+      txPromise,
+      'respawnPlayer'
+    );
 
     console.log(`✅ Player respawned, transaction:`, tx);
     return tx;
@@ -2984,13 +3370,20 @@ export async function startReload(gameIdPubkey) {
     console.log("🔄 Authority:", ephemeralPublicKey.toString());
 
     // Call start_reload instruction
-    const tx = await gameProgram.methods
+    const txPromise = gameProgram.methods
       .startReload()
       .accounts({
         gamePlayer: gamePlayerPda,
         authority: ephemeralPublicKey,
       })
       .rpc({ skipPreflight: true });
+    const tx = await logTransactionPromise(
+      'Game',
+      'Start Reload',
+      EPHEMERAL_RPC_URL,
+      txPromise,
+      'startReload'
+    );
 
     console.log(`✅ Reload started, transaction:`, tx);
     return tx;
@@ -3041,13 +3434,20 @@ export async function finishReload(gameIdPubkey) {
     );
 
     // Call reload instruction to complete the reload
-    const tx = await gameProgram.methods
+    const txPromise = gameProgram.methods
       .reload()
       .accounts({
         gamePlayer: gamePlayerPda,
         authority: ephemeralPublicKey,
       })
       .rpc({ skipPreflight: true });
+    const tx = await logTransactionPromise(
+      'Game',
+      'Finish Reload',
+      EPHEMERAL_RPC_URL,
+      txPromise,
+      'finishReload'
+    );
 
     console.log(`✅ Reload complete, transaction:`, tx);
     return tx;
@@ -0,0 +1,103 @@
+/**
+ * Activity log
+ *
+ * Recent gameplay transactions (join, shoot, kill, respawn, reload) collected
+ * from the 'debug-transaction' events the bridge already emits, so players can
+ * see what their session wallet signed and whether it landed. Unlike the Debug
+ * Console this skips the per-frame input stream.
+ */
+
+// Bridge functions whose transactions show up in the Activity panel
+export const ACTIVITY_FUNCTIONS = {
+  joinGame: "Join",
+  shootPlayer: "Shoot",
+  awardKill: "Kill",
+  respawnPlayer: "Respawn",
+  startReload: "Reload",
+  finishReload: "Reload done",
+};
+
+// Entries kept (newest first)
+export const ACTIVITY_LOG_SIZE = 50;
+
+// debug-transaction status -> what the panel shows
+const STATUS_LABELS = { pending: "pending", success: "confirmed", error: "failed" };
+
+let entries = [];
+let nextId = 1;
+let started = false;
+const listeners = new Set();
+
+/**
+ * Fold a debug-transaction event into the log
+ * Pending events carry no signature yet, so a result settles the oldest
+ * pending entry for the same function.
+ * @param {Array} log - Current entries, newest first
+ * @param {object} detail - debug-transaction event detail
+ * @returns {Array} New entries (the same array if the event isn't tracked)
+ */
+export function applyTransaction(log, detail, id = 0) {
+  const label = ACTIVITY_FUNCTIONS[detail.functionName];
+  const status = STATUS_LABELS[detail.status];
+  if (!label || !status) return log;
+
+  if (status !== "pending") {
+    const index = log.findLastIndex(
+      (entry) => entry.functionName === detail.functionName && entry.status === "pending"
+    );
+    if (index !== -1) {
+      const settled = { ...log[index], status, signature: detail.signature || null, error: detail.error || null };
+      return [...log.slice(0, index), settled, ...log.slice(index + 1)];
+    }
+  }
+
+  const entry = {
+    id,
+    functionName: detail.functionName,
+    label,
+    status,
+    signature: detail.signature || null,
+    endpoint: detail.endpoint || null,
+    error: detail.error || null,
+    timestamp: detail.timestamp || new Date().toISOString(),
+  };
+  return [entry, ...log].slice(0, ACTIVITY_LOG_SIZE);
+}
+
+/** Start collecting transactions (safe to call more than once) */
+export function startActivityLog() {
+  if (started) return;
+  started = true;
+
+  window.addEventListener("debug-transaction", (event) => {
+    const updated = applyTransaction(entries, event.detail || {}, nextId);
+    if (updated === entries) return;
+    nextId += 1;
+    entries = updated;
+    listeners.forEach((listener) => listener(entries));
+  });
+}
+
+export function getActivityLog() {
+  return entries;
+}
+
+export function clearActivityLog() {
+  entries = [];
+  listeners.forEach((listener) => listener(entries));
+}
+
+/**
+ * Be told whenever the log changes
+ * @returns {Function} Unsubscribe
+ */
+export function subscribeActivityLog(listener) {
+  listeners.add(listener);
+  return () => listeners.delete(listener);
+}
+
+/** Solscan link for a signature sent to `endpoint` (our clusters are custom RPCs) */
+export function explorerUrl(signature, endpoint) {
+  if (!signature) return null;
+  return `https://solscan.io/tx/${signature}?cluster=custom&customUrl=${encodeURIComponent(endpoint || "")}`;
+}
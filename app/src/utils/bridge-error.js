@@ -0,0 +1,81 @@
+/**
+ * Bridge errors
+ *
+ * Errors handed from the bridge to the game (and the React UI) are tagged
+ * objects, e.g. { kind: "PlayerAlreadyInGame", currentGame: "..." }, mirroring
+ * the Rust `BridgeError` enum in game/src/net/bridge_error.rs. Keep the kinds
+ * and field names in sync with it.
+ */
+
+export const BridgeErrorKind = {
+  BridgeUnavailable: "BridgeUnavailable",
+  WalletNotConnected: "WalletNotConnected",
+  PlayerAlreadyInGame: "PlayerAlreadyInGame", // { currentGame }
+  NotInGame: "NotInGame",
+  InsufficientFunds: "InsufficientFunds",
+  Program: "Program", // { code, name }
+  Rpc: "Rpc", // { message }
+  Unknown: "Unknown", // { message }
+};
+
+export function bridgeError(kind, fields = {}) {
+  return { kind, ...fields };
+}
+
+// "custom program error: 0x1774" in simulation / send errors
+const CUSTOM_PROGRAM_ERROR = /custom program error: 0x([0-9a-f]+)/i;
+const INSUFFICIENT_FUNDS = /insufficient (funds|lamports)|no record of a prior credit/i;
+const RPC_FAILURE = /failed to fetch|networkerror|timed? ?out|429|too many requests|econnrefused|blockhash not found/i;
+
+/**
+ * Classify anything thrown or returned as an error into a tagged bridge error
+ * @param {*} error - Error, Anchor error, tagged error or bare message/kind string
+ */
+export function toBridgeError(error) {
+  if (error && typeof error === "object" && typeof error.kind === "string" && error.kind in BridgeErrorKind) {
+    return error;
+  }
+  if (typeof error === "string") {
+    return error in BridgeErrorKind ? bridgeError(error) : bridgeError(BridgeErrorKind.Unknown, { message: error });
+  }
+
+  // Anchor program errors carry the IDL error code and name
+  const anchorCode = error?.error?.errorCode;
+  if (anchorCode && typeof anchorCode.number === "number") {
+    return bridgeError(BridgeErrorKind.Program, { code: anchorCode.number, name: anchorCode.code || "" });
+  }
+
+  const message = String(error?.message || error || "Unknown error");
+  const custom = message.match(CUSTOM_PROGRAM_ERROR);
+  if (custom) {
+    return bridgeError(BridgeErrorKind.Program, { code: parseInt(custom[1], 16), name: "" });
+  }
+  if (INSUFFICIENT_FUNDS.test(message)) return bridgeError(BridgeErrorKind.InsufficientFunds);
+  if (RPC_FAILURE.test(message)) return bridgeError(BridgeErrorKind.Rpc, { message });
+  return bridgeError(BridgeErrorKind.Unknown, { message });
+}
+
+/** What to tell the player (same wording as BridgeError::user_message in Rust) */
+export function bridgeErrorMessage(error) {
+  const tagged = toBridgeError(error);
+  switch (tagged.kind) {
+    case BridgeErrorKind.BridgeUnavailable:
+      return "Still connecting to Solana - try again in a moment";
+    case BridgeErrorKind.WalletNotConnected:
+      return "Connect your wallet first";
+    case BridgeErrorKind.PlayerAlreadyInGame:
+      return "You're already in a game - leave it first";
+    case BridgeErrorKind.NotInGame:
+      return "You're not in a game";
+    case BridgeErrorKind.InsufficientFunds:
+      return "Not enough SOL to pay for the transaction";
+    case BridgeErrorKind.Program:
+      return tagged.name
+        ? `Transaction rejected: ${tagged.name}`
+        : `Transaction rejected by the program (error ${tagged.code})`;
+    case BridgeErrorKind.Rpc:
+      return "Can't reach the Solana RPC - check your connection settings";
+    default:
+      return tagged.message;
+  }
+}
@@ -0,0 +1,71 @@
+/**
+ * Chat filtering: profanity mask and per-player mutes
+ *
+ * Everything here is client-side only - other players still send whatever they
+ * type, we just choose not to show (or play) it. The mute list is persisted in
+ * settings and exposed on `window.__muted_players` so the game bridge can drop
+ * pings and voice-lines from muted players before they reach the game.
+ */
+
+// Kept short on purpose: catches the common cases without mangling normal words
+const PROFANITY = [
+  "fuck",
+  "shit",
+  "bitch",
+  "cunt",
+  "dick",
+  "bastard",
+  "asshole",
+  "faggot",
+  "nigger",
+  "retard",
+  "whore",
+  "slut",
+];
+
+// Whole words (plus common suffixes like "-ing", "-er", "-s"), case-insensitive
+const PROFANITY_PATTERN = new RegExp(`\\b(${PROFANITY.join("|")})(ing|er|ers|ed|s|es|y)?\\b`, "gi");
+
+// Game events that carry player-authored content and are dropped for muted senders
+export const MUTABLE_EVENT_TYPES = ["ping", "voiceLine"];
+
+/** Replace every profane word with asterisks, keeping the first letter */
+export function maskProfanity(text) {
+  if (!text) return text;
+  return text.replace(PROFANITY_PATTERN, (word) => word[0] + "*".repeat(word.length - 1));
+}
+
+export function isMuted(mutedPlayers, player) {
+  return !!player && (mutedPlayers || []).includes(player);
+}
+
+/** Toggle `player` in the mute list (returns a new list) */
+export function toggleMute(mutedPlayers, player) {
+  const list = mutedPlayers || [];
+  return list.includes(player) ? list.filter((p) => p !== player) : [...list, player];
+}
+
+/**
+ * Prepare a chat message for display
+ * @param {{sender: string, text: string}} message
+ * @param {{profanityFilter: boolean, mutedPlayers: string[]}} settings
+ * @returns {object|null} The message (masked if enabled), or null if the sender is muted
+ */
+export function filterChatMessage(message, { profanityFilter = true, mutedPlayers = [] } = {}) {
+  if (isMuted(mutedPlayers, message.sender)) return null;
+  return profanityFilter ? { ...message, text: maskProfanity(message.text) } : message;
+}
+
+/** Whether a relayed game event should be dropped (ping/voice-line from a muted player) */
+export function isBlockedGameEvent(event, mutedPlayers) {
+  return MUTABLE_EVENT_TYPES.includes(event.type) && isMuted(mutedPlayers, event.sender);
+}
+
+export function loadMutedPlayers() {
+  try {
+    const stored = JSON.parse(localStorage.getItem("mutedPlayers") || "[]");
+    return Array.isArray(stored) ? stored : [];
+  } catch (_) {
+    return [];
+  }
+}
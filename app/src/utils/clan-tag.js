@@ -0,0 +1,57 @@
+/**
+ * Clan tags: optional short prefixes shown before player names
+ *
+ * The player account has no profile fields besides the username, so the tag is
+ * stored on-chain as part of it ("[FPS] alice") when the player is created.
+ * Everything that shows names (nameplates, kill feed, lobby rosters) splits it
+ * back out with `parseTaggedName` to render the tag separately.
+ */
+
+export const CLAN_TAG_MIN_LENGTH = 2;
+export const CLAN_TAG_MAX_LENGTH = 5;
+
+// Same limit the matchmaking program enforces on usernames
+const USERNAME_MAX_LENGTH = 32;
+
+const CLAN_TAG_CHARSET = /^[A-Z0-9]+$/;
+const TAGGED_NAME = /^\[([A-Z0-9]{2,5})\] (.+)$/;
+
+export function normalizeClanTag(tag) {
+  return (tag || "").trim().toUpperCase();
+}
+
+/**
+ * Check a clan tag (already normalized); empty means "no tag"
+ * @returns {string|null} Error message, or null if valid
+ */
+export function validateClanTag(tag) {
+  if (!tag) return null;
+  if (tag.length < CLAN_TAG_MIN_LENGTH || tag.length > CLAN_TAG_MAX_LENGTH) {
+    return `Clan tag must be ${CLAN_TAG_MIN_LENGTH}-${CLAN_TAG_MAX_LENGTH} characters`;
+  }
+  if (!CLAN_TAG_CHARSET.test(tag)) {
+    return "Clan tag can only contain letters A-Z and digits";
+  }
+  return null;
+}
+
+/** Username as stored on-chain ("[TAG] name", or just the name without a tag) */
+export function formatTaggedName(tag, name) {
+  return tag ? `[${tag}] ${name}` : name;
+}
+
+/** Split a stored username into its clan tag (or null) and plain name */
+export function parseTaggedName(username) {
+  const match = TAGGED_NAME.exec(username || "");
+  return match ? { tag: match[1], name: match[2] } : { tag: null, name: username || "" };
+}
+
+/** Validate a tag + name pair before creating the player */
+export function validateTaggedName(tag, name) {
+  const error = validateClanTag(tag);
+  if (error) return error;
+  if (formatTaggedName(tag, name).length > USERNAME_MAX_LENGTH) {
+    return `Username and clan tag together must fit in ${USERNAME_MAX_LENGTH} characters`;
+  }
+  return null;
+}
@@ -0,0 +1,82 @@
+/**
+ * Crash reports from the game
+ *
+ * When the game panics or hits a fatal error it hands the bridge a report
+ * (reason, source location and the last log lines and state changes). Nothing
+ * leaves the browser unless the player agrees: the prompt offers to send it to
+ * REACT_APP_CRASH_REPORT_URL (when configured) or to copy it for a bug report.
+ */
+
+import toast from "react-hot-toast";
+
+const CRASH_REPORT_URL = process.env.REACT_APP_CRASH_REPORT_URL || "";
+
+/** Report plus the browser details that usually matter for a WebGL/WASM crash */
+function withEnvironment(report) {
+  return {
+    ...report,
+    userAgent: navigator.userAgent,
+    url: window.location.href,
+    reportedAt: new Date().toISOString(),
+  };
+}
+
+async function sendCrashReport(report) {
+  const response = await fetch(CRASH_REPORT_URL, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify(withEnvironment(report)),
+  });
+  if (!response.ok) {
+    throw new Error(`HTTP ${response.status}`);
+  }
+}
+
+async function copyCrashReport(report) {
+  await navigator.clipboard.writeText(JSON.stringify(withEnvironment(report), null, 2));
+}
+
+/**
+ * Ask the player what to do with a crash report (one prompt at a time; a newer report replaces it)
+ * @param {Object} report - { reason, location, panic, uptime, breadcrumbs: [{ at, kind, message }] }
+ */
+export function promptCrashReport(report) {
+  const message = report.panic
+    ? "The game crashed. Reload the page to keep playing."
+    : "The game hit an error and may not work until you reload.";
+  toast(
+    (t) => (
+      <div className="toast-content">
+        <div className="toast-message">{message}</div>
+        <div className="toast-message" style={{ opacity: 0.7, fontSize: "0.85em" }}>
+          {report.reason}
+        </div>
+        <div style={{ display: "flex", gap: "8px", marginTop: "8px" }}>
+          {CRASH_REPORT_URL && (
+            <button
+              onClick={() => {
+                toast.dismiss(t.id);
+                sendCrashReport(report)
+                  .then(() => toast.success("Crash report sent - thanks!"))
+                  .catch((error) => toast.error(`Couldn't send the report: ${error.message}`));
+              }}
+            >
+              Send report
+            </button>
+          )}
+          <button
+            onClick={() => {
+              copyCrashReport(report)
+                .then(() => toast.success("Crash report copied"))
+                .catch(() => toast.error("Couldn't copy the report"));
+            }}
+          >
+            Copy details
+          </button>
+          <button onClick={() => toast.dismiss(t.id)}>Dismiss</button>
+        </div>
+      </div>
+    ),
+    { id: "crash-report", duration: Infinity, position: "bottom-right" }
+  );
+}
@@ -0,0 +1,47 @@
+/**
+ * Collaborative map editing sessions
+ *
+ * The editor's host shares a short code; everyone who joins with it talks over
+ * the `editor-session:<code>` relay topic (same transport as parties), so
+ * collaborators can be on different machines. A peer's id is the key the
+ * relay signed it in with, so the bridge takes the sender of every incoming
+ * message from the relay (`from`), adds the display name to outgoing ones and
+ * queues incoming ones for the game, which drains them every frame. Ordering, conflict resolution
+ * and the map itself are handled in the game (map/collab.rs); the host's copy
+ * always wins.
+ */
+
+import { createPartyId, normalizePartyId } from "./party";
+import { openRelayChannel, getRelayIdentity } from "./relay";
+
+/** Short code collaborators type to join (same alphabet as party codes) */
+export const createEditSessionCode = createPartyId;
+export const normalizeEditSessionCode = normalizePartyId;
+
+/** This tab's id in a session: the key the relay stamps on what it sends */
+export const getEditPeerId = getRelayIdentity;
+
+/**
+ * Open the relay channel for a session
+ * @param {string} code
+ * @param {{name: string}} self - Display name added to every message sent
+ * @returns {{send: (message: object) => void, drain: () => object[], close: () => void} | null}
+ */
+export function openEditSession(code, { name }) {
+  let pending = [];
+  const channel = openRelayChannel(`editor-session:${code}`, (data, from) => {
+    if (data && typeof data === "object" && typeof data.type === "string" && typeof from === "string") {
+      pending.push({ ...data, from });
+    }
+  });
+  if (!channel) return null;
+  return {
+    send: (message) => channel.send({ ...message, name }),
+    drain: () => {
+      const messages = pending;
+      pending = [];
+      return messages;
+    },
+    close: channel.close,
+  };
+}
@@ -0,0 +1,62 @@
+/**
+ * HUD layout: where each HUD element sits, how big it is and whether it's shown
+ *
+ * Positions are the element's center as a fraction of the screen, so a layout
+ * survives window resizes. The layout is saved with the other settings in
+ * localStorage and pushed to the game (setHudLayout), which draws the health
+ * bar; the web HUD places the other elements with HudSlot.
+ */
+
+const STORAGE_KEY = "hudLayout";
+
+// Same keys, defaults and scale limits as HudElement / HUD_SCALE_RANGE in game/src/game/hud_layout.rs.
+// width/height are the element's size at scale 1, for the layout editor's outlines
+export const HUD_ELEMENTS = [
+  { key: "health", label: "Health Bar", x: 0.5, y: 0.94, width: 300, height: 45 },
+  { key: "ammo", label: "Ammo", x: 0.92, y: 0.9, width: 170, height: 90 },
+  { key: "killFeed", label: "Kill Feed", x: 0.13, y: 0.3, width: 300, height: 140 },
+  { key: "minimap", label: "Minimap", x: 0.9, y: 0.33, width: 220, height: 280 },
+  { key: "timer", label: "Match Timer", x: 0.5, y: 0.08, width: 360, height: 90 },
+];
+
+export const HUD_SCALE_MIN = 0.5;
+export const HUD_SCALE_MAX = 2;
+
+const clamp = (value, min, max) => Math.min(max, Math.max(min, value));
+
+export function defaultHudLayout() {
+  return Object.fromEntries(
+    HUD_ELEMENTS.map(({ key, x, y }) => [key, { x, y, scale: 1, visible: true }])
+  );
+}
+
+/** Layout with every element present and positions/scales in range */
+export function normalizeHudLayout(layout) {
+  const defaults = defaultHudLayout();
+  return Object.fromEntries(
+    HUD_ELEMENTS.map(({ key }) => {
+      const saved = { ...defaults[key], ...(layout?.[key] || {}) };
+      return [
+        key,
+        {
+          x: clamp(Number(saved.x) || 0, 0, 1),
+          y: clamp(Number(saved.y) || 0, 0, 1),
+          scale: clamp(Number(saved.scale) || 1, HUD_SCALE_MIN, HUD_SCALE_MAX),
+          visible: saved.visible !== false,
+        },
+      ];
+    })
+  );
+}
+
+export function loadHudLayout() {
+  try {
+    return normalizeHudLayout(JSON.parse(localStorage.getItem(STORAGE_KEY) || "null"));
+  } catch (_) {
+    return defaultHudLayout();
+  }
+}
+
+export function saveHudLayout(layout) {
+  localStorage.setItem(STORAGE_KEY, JSON.stringify(layout));
+}
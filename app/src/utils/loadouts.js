@@ -0,0 +1,71 @@
+/**
+ * Loadout presets: named weapon sets picked in the Weapons tab
+ *
+ * The player account has no profile fields besides the username, so presets
+ * are saved with the other settings in localStorage. A preset can be
+ * auto-selected per map or per game mode; the resolved one is pushed to the
+ * game (setLoadout) before every match and applied at each spawn.
+ */
+
+const STORAGE_KEY = "loadoutPresets";
+
+// Same ids and selection order as WeaponKind in the game
+export const WEAPONS = [
+  { id: "rifle", name: "Rifle" },
+  { id: "sniper", name: "Sniper" },
+  { id: "shotgun", name: "Shotgun" },
+];
+
+export const LOADOUT_MODES = [
+  { id: "team_deathmatch", name: "Team Deathmatch" },
+  { id: "free_for_all", name: "Free for All" },
+];
+
+export const MAX_PRESETS = 8;
+
+export function defaultLoadouts() {
+  return {
+    presets: [{ id: "default", name: "Default", weapons: WEAPONS.map((w) => w.id), primary: "rifle" }],
+    // Preset id per mode id / map id; the first preset is used when neither matches
+    byMode: {},
+    byMap: {},
+  };
+}
+
+/** Preset with unknown weapons dropped and the primary forced into the carried set */
+export function normalizePreset(preset) {
+  const weapons = WEAPONS.map((w) => w.id).filter((id) => (preset.weapons || []).includes(id));
+  const carried = weapons.length > 0 ? weapons : ["rifle"];
+  const primary = carried.includes(preset.primary) ? preset.primary : carried[0];
+  return { ...preset, weapons: carried, primary };
+}
+
+export function loadLoadouts() {
+  try {
+    const stored = JSON.parse(localStorage.getItem(STORAGE_KEY) || "null");
+    if (!stored || !Array.isArray(stored.presets) || stored.presets.length === 0) {
+      return defaultLoadouts();
+    }
+    return {
+      presets: stored.presets.map(normalizePreset),
+      byMode: stored.byMode || {},
+      byMap: stored.byMap || {},
+    };
+  } catch (_) {
+    return defaultLoadouts();
+  }
+}
+
+export function saveLoadouts(loadouts) {
+  localStorage.setItem(STORAGE_KEY, JSON.stringify(loadouts));
+}
+
+/**
+ * Preset to spawn with: the map's choice beats the mode's, then the first preset
+ * @param {{presets: object[], byMode: object, byMap: object}} loadouts
+ * @param {{mapId?: string, mode?: string}} match
+ */
+export function resolveLoadout(loadouts, { mapId, mode } = {}) {
+  const find = (id) => id && loadouts.presets.find((preset) => preset.id === id);
+  return find(loadouts.byMap[mapId]) || find(loadouts.byMode[mode]) || loadouts.presets[0];
+}
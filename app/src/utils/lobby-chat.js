@@ -0,0 +1,50 @@
+/**
+ * Lobby chat: text chat while waiting for a match to start
+ *
+ * Separate from in-match events: messages go over their own relay channel per
+ * lobby (opened through `window.gameBridge.openLobbyChat`) and are dropped
+ * once the match starts. Team-only messages are still sent to everyone in the
+ * lobby; other teams just don't show them.
+ */
+
+export const LOBBY_CHAT_MAX_LENGTH = 140;
+
+// Messages kept in the history panel
+export const LOBBY_CHAT_HISTORY = 50;
+
+export const CHAT_SCOPE_ALL = "all";
+export const CHAT_SCOPE_TEAM = "team";
+
+/**
+ * Build a message to send
+ * @param {string} sender - Username of the sender
+ * @param {"A"|"B"|null} team - Sender's team (null for spectators)
+ * @param {string} text - What was typed
+ * @param {string} scope - CHAT_SCOPE_ALL or CHAT_SCOPE_TEAM
+ * @returns {object|null} The message, or null if there is nothing to send
+ */
+export function createChatMessage(sender, team, text, scope = CHAT_SCOPE_ALL) {
+  const trimmed = (text || "").replace(/\s+/g, " ").trim().slice(0, LOBBY_CHAT_MAX_LENGTH);
+  if (!trimmed) return null;
+  return {
+    id: `${Date.now()}-${Math.random().toString(36).slice(2, 8)}`,
+    sender,
+    team,
+    scope: team ? scope : CHAT_SCOPE_ALL,
+    text: trimmed,
+    sentAt: Date.now(),
+  };
+}
+
+/** Whether a player on `team` should see `message` */
+export function isVisibleTo(message, team) {
+  return message.scope !== CHAT_SCOPE_TEAM || message.team === team;
+}
+
+/** Add a message to the history (ignores duplicates, keeps the newest LOBBY_CHAT_HISTORY) */
+export function appendChatMessage(history, message) {
+  if (!message || typeof message.text !== "string" || history.some((m) => m.id === message.id)) {
+    return history;
+  }
+  return [...history, message].slice(-LOBBY_CHAT_HISTORY);
+}
@@ -0,0 +1,46 @@
+/**
+ * Chunked storage for maps too large for one map account
+ *
+ * A map with more than 500 objects is split across several
+ * accounts of the map registry. The head keeps the map's own id and the first
+ * chunk; chunk i (from 1) is stored under `${mapId}~${i}`. The head's
+ * description ends with a manifest, e.g. `[chunks 3 objects 1320]`, so readers
+ * know how many chunks to fetch. The head chunk holds the spawn points and the
+ * objects nearest the center, so a match can start on it alone while the rest
+ * streams in (`MapStream` in game/src/map/stream.rs).
+ *
+ * Side tables (labels, ambiance, glows, collision flags, triggers, the script
+ * and ambient sounds) are packed into extra accounts stored under
+ * `${mapId}~t${i}` (from 1), counted by the manifest too:
+ * `[chunks 1 objects 40 tables 1]` (see solana-client/src/map_tables.rs).
+ *
+ * Splitting and writing chunks happens in the solana-client crate
+ * (solana-client/src/map_chunks.rs); this side only reads them back.
+ */
+
+const CHUNK_SEPARATOR = "~";
+const MANIFEST = /\s*\[chunks (\d+) objects (\d+)(?: tables (\d+))?\]$/;
+
+/** Map id under which chunk `index` of `mapId` is stored (the head is chunk 0) */
+export function chunkMapId(mapId, index) {
+  return index === 0 ? mapId : `${mapId}${CHUNK_SEPARATOR}${index}`;
+}
+
+/** Map id under which side-table account `index` (from 1) of `mapId` is stored */
+export function tablesMapId(mapId, index) {
+  return `${mapId}${CHUNK_SEPARATOR}t${index}`;
+}
+
+/** Chunk, total object and side-table account counts from a head description; one chunk and no tables when there's no manifest */
+export function readMapManifest(description) {
+  const match = MANIFEST.exec(String(description || ""));
+  if (!match) {
+    return { chunks: 1, objects: null, tables: 0 };
+  }
+  return { chunks: Number(match[1]), objects: Number(match[2]), tables: Number(match[3] || 0) };
+}
+
+/** Description without its manifest, as shown to players */
+export function stripMapManifest(description) {
+  return String(description || "").replace(MANIFEST, "");
+}
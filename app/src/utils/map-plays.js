@@ -0,0 +1,20 @@
+/**
+ * Map play counts, read from the matchmaking program's Game accounts
+ *
+ * The map registry keeps no counter of its own, so a map's plays are the
+ * lobbies whose match started on it. The Rust lobby (`sort_maps_by_plays`)
+ * orders its list the same way so both map lists agree.
+ */
+
+/** Most played first, then alphabetical */
+export function sortMapsByPlays(maps) {
+  return [...maps].sort(
+    (a, b) => (b.playCount || 0) - (a.playCount || 0) || (a.name || "").localeCompare(b.name || "")
+  );
+}
+
+/** Short "40 plays" label, or "" for maps nobody has played yet */
+export function formatPlays({ playCount = 0 } = {}) {
+  if (playCount <= 0) return "";
+  return `${playCount} play${playCount === 1 ? "" : "s"}`;
+}
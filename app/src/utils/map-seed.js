@@ -0,0 +1,21 @@
+/**
+ * Map share codes: generated arenas travel as a short code instead of chain data
+ *
+ * A code names one of the game's built-in generators, a seed in hex and the
+ * generator's parameter values, e.g. `arena:1f3a9c:3,6,3.5` (the map editor's
+ * Generate section shows the code for the current layout). A lobby created with
+ * a code as its map skips the on-chain map lookup: every player regenerates the
+ * identical map locally (`map_from_share_code` in game/src/map/generator.rs).
+ */
+
+const SHARE_CODE = /^[a-z]+:[0-9a-f]{1,8}(:[0-9.,-]*)?$/;
+
+/** Trimmed, lowercase form of a typed or pasted code */
+export function normalizeShareCode(code) {
+  return String(code || "").trim().toLowerCase();
+}
+
+/** Whether a lobby's map id is a share code rather than a published map id */
+export function isMapShareCode(mapId) {
+  return SHARE_CODE.test(normalizeShareCode(mapId));
+}
@@ -0,0 +1,114 @@
+/**
+ * Match rules: score/time limits and overtime
+ *
+ * The game program only tracks kills, so deciding when a match is over (and
+ * who won a tied match) happens here. `advanceMatch` is a pure state machine
+ * fed with the polled scores; MatchStatus drives it and renders its state.
+ */
+
+// Overtime modes
+export const OVERTIME_SUDDEN_DEATH = "sudden_death"; // Next kill wins
+export const OVERTIME_MR = "mr"; // First team to `rounds` more kills wins the period
+
+/**
+ * Per game-mode match rules
+ * - scoreLimit: kills needed to win in regulation
+ * - timeLimit: regulation length in seconds (overridden by the on-chain match duration)
+ * - overtime.mode: OVERTIME_SUDDEN_DEATH or OVERTIME_MR
+ * - overtime.rounds: kills needed to take an MR period ("MR3" = first to 3)
+ * - overtime.periodLength: seconds per MR period before another period starts
+ * - overtime.maxPeriods: MR periods before falling back to sudden death
+ */
+const MATCH_RULES = {
+  team_deathmatch: {
+    scoreLimit: 40,
+    timeLimit: 600,
+    overtime: { mode: OVERTIME_MR, rounds: 3, periodLength: 120, maxPeriods: 2 },
+  },
+  free_for_all: {
+    scoreLimit: 30,
+    timeLimit: 600,
+    overtime: { mode: OVERTIME_SUDDEN_DEATH, rounds: 1, periodLength: 0, maxPeriods: 0 },
+  },
+};
+
+export function getMatchRules(gameMode = "team_deathmatch", matchDuration = null) {
+  const rules = MATCH_RULES[gameMode] || MATCH_RULES.team_deathmatch;
+  return matchDuration ? { ...rules, timeLimit: matchDuration } : rules;
+}
+
+export function initialMatchState() {
+  return {
+    phase: "regulation", // "regulation" | "overtime" | "ended"
+    period: 0, // Overtime period (1-based once in overtime)
+    suddenDeath: false,
+    periodStartScores: { a: 0, b: 0 },
+    periodStartedAt: 0,
+    winningTeam: null, // "A" | "B"
+  };
+}
+
+const leader = (a, b) => (a > b ? "A" : b > a ? "B" : null);
+
+function startOvertimePeriod(state, scores, now, rules) {
+  const period = state.period + 1;
+  const suddenDeath =
+    rules.overtime.mode === OVERTIME_SUDDEN_DEATH || period > rules.overtime.maxPeriods;
+  return {
+    ...state,
+    phase: "overtime",
+    period,
+    suddenDeath,
+    periodStartScores: { a: scores.a, b: scores.b },
+    periodStartedAt: now,
+  };
+}
+
+/**
+ * Advance the match with the latest scores
+ * @param {object} state - Previous state (from initialMatchState / advanceMatch)
+ * @param {{a: number, b: number}} scores - Team kill totals
+ * @param {number} elapsed - Seconds since the match started
+ * @param {object} rules - From getMatchRules
+ * @returns {object} Next state (phase "ended" with winningTeam when decided)
+ */
+export function advanceMatch(state, scores, elapsed, rules) {
+  if (state.phase === "ended") return state;
+
+  const end = (winningTeam) => ({ ...state, phase: "ended", winningTeam });
+
+  if (state.phase === "regulation") {
+    const limitReached = scores.a >= rules.scoreLimit || scores.b >= rules.scoreLimit;
+    const timeUp = elapsed >= rules.timeLimit;
+    if (!limitReached && !timeUp) return state;
+
+    const winner = leader(scores.a, scores.b);
+    return winner ? end(winner) : startOvertimePeriod(state, scores, elapsed, rules);
+  }
+
+  // Overtime
+  const winner = leader(scores.a, scores.b);
+  if (state.suddenDeath) {
+    // Scores were tied when sudden death started, so any lead is the deciding kill
+    return winner ? end(winner) : state;
+  }
+
+  const periodA = scores.a - state.periodStartScores.a;
+  const periodB = scores.b - state.periodStartScores.b;
+  if (periodA >= rules.overtime.rounds || periodB >= rules.overtime.rounds) {
+    const periodWinner = leader(periodA, periodB);
+    if (periodWinner) return end(periodWinner);
+  }
+
+  if (elapsed - state.periodStartedAt >= rules.overtime.periodLength) {
+    return winner ? end(winner) : startOvertimePeriod(state, scores, elapsed, rules);
+  }
+  return state;
+}
+
+/** Short label for the HUD ("OVERTIME 1 · MR3", "SUDDEN DEATH") */
+export function overtimeLabel(state, rules) {
+  if (state.phase !== "overtime") return null;
+  if (state.suddenDeath) return "SUDDEN DEATH";
+  return `OVERTIME ${state.period} · MR${rules.overtime.rounds}`;
+}
@@ -0,0 +1,26 @@
+/**
+ * Synchronized match start
+ *
+ * Clients notice the game switching to active whenever their next poll lands,
+ * so entering the match on that edge staggers spawns by up to a few seconds.
+ * Instead everyone enters Playing at the same moment: the on-chain match start
+ * timestamp (cluster clock of the slot start_game landed in) plus a fixed
+ * countdown. Local clocks are corrected by their offset to the chain clock.
+ */
+
+export const MATCH_START_COUNTDOWN = 5;
+
+/** Chain unix time (seconds) at which every client enters Playing */
+export function playStartTime(matchStartTimestamp) {
+  return Number(matchStartTimestamp) + MATCH_START_COUNTDOWN;
+}
+
+/** Current chain time estimated from the local clock */
+export function chainNow(clockOffset = 0, nowMs = Date.now()) {
+  return nowMs / 1000 + clockOffset;
+}
+
+/** Local epoch milliseconds matching a chain unix time */
+export function toLocalMs(chainSeconds, clockOffset = 0) {
+  return (chainSeconds - clockOffset) * 1000;
+}
@@ -0,0 +1,83 @@
+/**
+ * Parties: queue and join lobbies as a group
+ *
+ * A party is a leader plus members who follow the leader into lobbies. Party
+ * state lives only on the clients and is kept in sync over one relay topic
+ * per party (utils/relay.js, the same transport as the in-match game events).
+ * Members are told apart by the key the relay stamps on their messages, not
+ * by the names inside them.
+ *
+ * The matchmaking program has no team argument on join: it puts every joining
+ * player on the team with fewer players. A party therefore only stays together
+ * if that team is behind by at least the party size, which is what
+ * `partyFitsTogether` checks before the leader joins anywhere.
+ */
+
+import { openRelayChannel, getRelayIdentity } from "./relay";
+
+export const MAX_PARTY_SIZE = 5;
+
+// Messages sent on the party channel
+export const PARTY_HELLO = "hello"; // Member -> leader: let me in
+export const PARTY_ROSTER = "roster"; // Leader -> all: current leader + members
+export const PARTY_LEAVE = "leave"; // Member -> leader: I'm out (the member is whoever sent it)
+export const PARTY_DISBAND = "disband"; // Leader -> all: party is gone
+export const PARTY_JOIN = "join"; // Leader -> all: follow me into this lobby
+
+/** This client's key in a party: what the relay stamps on its messages */
+export const getPartyKey = getRelayIdentity;
+
+/** Short code friends type in to join (e.g. "K7Q2XM") */
+export function createPartyId() {
+  const alphabet = "ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+  return Array.from({ length: 6 }, () => alphabet[Math.floor(Math.random() * alphabet.length)]).join("");
+}
+
+export function normalizePartyId(code) {
+  return (code || "").trim().toUpperCase();
+}
+
+/**
+ * Whether a whole party of `size` lands on the same team when joining `game`
+ * @param {object} game - From getAvailableGames (camelCase Game account fields)
+ * @param {number} size - Players joining together (leader included)
+ */
+export function partyFitsTogether(game, size) {
+  const a = game.currentPlayersTeamA || 0;
+  const b = game.currentPlayersTeamB || 0;
+  const maxPerTeam = game.maxPlayersPerTeam || 5;
+  const smaller = Math.min(a, b);
+  if (smaller + size > maxPerTeam) return false;
+  // The smaller team has to stay strictly smaller until the last member is in
+  // (ties go to whichever team the program prefers, so they can't be relied on)
+  return size <= 1 || Math.abs(a - b) >= size;
+}
+
+/**
+ * Lobby for Quick Play: the fullest lobby the party fits into together
+ * @returns {object|null} A game from `games`, or null if none fits
+ */
+export function pickQuickPlayLobby(games, size) {
+  const candidates = (games || []).filter(
+    (game) => game.isJoinable !== false && (game.totalPlayers || 0) > 0 && partyFitsTogether(game, size)
+  );
+  candidates.sort((x, y) => (y.totalPlayers || 0) - (x.totalPlayers || 0));
+  return candidates[0] || null;
+}
+
+/**
+ * Open the relay channel for a party
+ * @param {string} partyId
+ * @param {(message: object, from: string) => void} onMessage - Called with each message and its sender's relay key
+ * @returns {{post: (message: object) => void, close: () => void} | null}
+ */
+export function openPartyChannel(partyId, onMessage) {
+  const channel = openRelayChannel(`party:${partyId}`, (message, from) => {
+    if (message && typeof message === "object" && typeof from === "string") onMessage(message, from);
+  });
+  if (!channel) return null;
+  return {
+    post: (message) => channel.send(message),
+    close: () => channel.close(),
+  };
+}
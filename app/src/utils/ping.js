@@ -0,0 +1,59 @@
+/**
+ * Ping bars for the scoreboard and lobby
+ *
+ * There is no game server to ping, so in a match another player's ping is
+ * how stale their data is: time since we last received an account update
+ * from them. Our own ping is the measured RPC round trip to the ephemeral
+ * rollup. In the lobby, presence beats travel through the game relay, so a
+ * player's ping is the relay path between us (see relayPathLatency).
+ */
+
+// Upper bound (ms) for 4, 3, 2 and 1 bars; anything slower shows no bars
+const PING_BAR_LIMITS = [100, 200, 400, 1500];
+
+// Colors for 0..4 bars
+const PING_COLORS = ["#ff4444", "#ff4444", "#ffaa00", "#00d9ff", "#00f294"];
+
+// Lobby presence beats; a player silent for several beats is lagging or gone
+export const PRESENCE_INTERVAL_MS = 2000;
+export const PRESENCE_TIMEOUT_MS = PRESENCE_INTERVAL_MS * 3;
+
+/**
+ * Number of bars (0-4) for a ping
+ * @param {number|null} ms - Latency or staleness in ms (null = unknown)
+ */
+export function pingBars(ms) {
+  if (ms === null || ms === undefined || Number.isNaN(ms)) return 0;
+  const slower = PING_BAR_LIMITS.filter((limit) => ms >= limit).length;
+  return PING_BAR_LIMITS.length - slower;
+}
+
+export function pingColor(bars) {
+  return PING_COLORS[Math.max(0, Math.min(bars, PING_COLORS.length - 1))];
+}
+
+/**
+ * One-way latency between two players through the game relay
+ * Each side only knows its own round trip to the relay; a beat goes up one
+ * leg and down the other, so the path is half of each round trip.
+ * @param {number|null} theirRtt - Sender's round trip to the relay (ms)
+ * @param {number|null} ourRtt - Our round trip to the relay (ms)
+ * @returns {number|null} Null until both round trips are known
+ */
+export function relayPathLatency(theirRtt, ourRtt) {
+  if (typeof theirRtt !== "number" || typeof ourRtt !== "number") return null;
+  return Math.round((theirRtt + ourRtt) / 2);
+}
+
+/**
+ * Ping shown for a lobby member from their last presence beat
+ * Beats carry the relay path latency to the sender; a missed beat counts as lag.
+ * @param {{receivedAt: number, latency: number|null}|undefined} beat - Last beat heard
+ * @param {number} now - Epoch ms
+ */
+export function presencePing(beat, now = Date.now()) {
+  if (!beat) return null;
+  const overdue = now - beat.receivedAt - PRESENCE_INTERVAL_MS;
+  if (overdue > 0) return Math.max(beat.latency || 0, overdue);
+  return beat.latency;
+}
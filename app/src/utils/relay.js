@@ -0,0 +1,245 @@
+/**
+ * Relay transport for the traffic that isn't on chain: gameplay events, lobby
+ * chat and presence, parties and map editor sessions
+ *
+ * Every client in a match (or party, lobby, editor session) joins the same
+ * topic on the game relay (relay/server.js), and whatever one of them
+ * publishes reaches all the others, on any machine. Senders don't get their
+ * own messages back.
+ *
+ * The relay only lets a connection in once it has signed the relay's
+ * challenge with the ephemeral wallet's key, and hands every message to
+ * handlers together with that key (`from`). Anything that needs to know who
+ * sent a message uses `from`, never a sender field inside the payload. Before
+ * the ephemeral wallet is loaded (the map editor without a wallet) the tab
+ * signs with a throwaway key instead.
+ *
+ * A development build without REACT_APP_GAME_RELAY_URL falls back to one
+ * BroadcastChannel per topic, which only reaches other tabs of the same
+ * browser - enough to try features locally, never used in production.
+ */
+
+import { Keypair } from "@solana/web3.js";
+import { getEphemeralKeypair } from "../ephemeral-wallet";
+
+const RELAY_URL = process.env.REACT_APP_GAME_RELAY_URL || "ws://127.0.0.1:7900";
+const LOCAL_SHIM = !process.env.REACT_APP_GAME_RELAY_URL && process.env.NODE_ENV === "development";
+
+const RECONNECT_DELAY = 1000; // Doubles per failed attempt
+const MAX_RECONNECT_DELAY = 15000;
+const PING_INTERVAL = 5000;
+const MAX_QUEUED_MESSAGES = 200; // Published while (re)connecting, oldest dropped first
+
+// Must match relay/server.js
+const AUTH_PREFIX = "fps.so relay auth:";
+
+// PKCS#8 header that wraps a raw 32-byte ed25519 seed for WebCrypto
+const ED25519_PKCS8_PREFIX = Uint8Array.from([
+  0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x04, 0x22, 0x04, 0x20,
+]);
+
+let throwawayKeypair = null;
+
+/** Key the relay knows this tab by: the ephemeral wallet once it's loaded */
+function relayKeypair() {
+  const ephemeral = getEphemeralKeypair();
+  if (ephemeral) return ephemeral;
+  if (!throwawayKeypair) throwawayKeypair = Keypair.generate();
+  return throwawayKeypair;
+}
+
+/** Base64 ed25519 signature of the relay's challenge */
+async function signChallenge(keypair, nonce) {
+  const pkcs8 = new Uint8Array(ED25519_PKCS8_PREFIX.length + 32);
+  pkcs8.set(ED25519_PKCS8_PREFIX);
+  pkcs8.set(keypair.secretKey.subarray(0, 32), ED25519_PKCS8_PREFIX.length);
+  const key = await crypto.subtle.importKey("pkcs8", pkcs8, { name: "Ed25519" }, false, ["sign"]);
+  const signature = await crypto.subtle.sign({ name: "Ed25519" }, key, new TextEncoder().encode(AUTH_PREFIX + nonce));
+  return btoa(String.fromCharCode(...new Uint8Array(signature)));
+}
+
+class RelayClient {
+  constructor(url) {
+    this.url = url;
+    this.ws = null;
+    this.isConnected = false; // Open and signed in
+    this.keypair = null; // What this connection signed the challenge with
+    this.handlers = new Map(); // topic -> Set of (data, from) => void
+    this.queue = [];
+    this.reconnectDelay = RECONNECT_DELAY;
+    this.reconnectTimer = null;
+    this.pingTimer = null;
+    this.pingId = 0;
+    this.pingSentAt = new Map(); // ping id -> performance.now()
+    this.latency = null; // Last round trip to the relay (ms)
+  }
+
+  connect() {
+    if (this.ws || this.reconnectTimer) return;
+    let socket;
+    try {
+      socket = new WebSocket(this.url);
+    } catch (error) {
+      console.error("❌ Game relay connection failed:", error);
+      this.scheduleReconnect();
+      return;
+    }
+    this.ws = socket;
+    this.keypair = relayKeypair();
+
+    socket.onmessage = (event) => {
+      let message;
+      try {
+        message = JSON.parse(event.data);
+      } catch (_) {
+        return;
+      }
+      // The relay opens with a challenge; nothing is joined or published until it's signed
+      if (message.op === "challenge") {
+        this.authenticate(socket, message.nonce);
+        return;
+      }
+      if (message.op === "ready") {
+        this.isConnected = true;
+        this.reconnectDelay = RECONNECT_DELAY;
+        console.log(`🛰️ Game relay connected: ${this.url} as ${message.key}`);
+        this.handlers.forEach((_, topic) => this.write({ op: "join", topic }));
+        this.queue.splice(0).forEach((queued) => this.write(queued));
+        this.pingTimer = setInterval(() => this.ping(), PING_INTERVAL);
+        this.ping();
+        return;
+      }
+      if (message.op === "pong") {
+        const sentAt = this.pingSentAt.get(message.id);
+        this.pingSentAt.delete(message.id);
+        if (sentAt !== undefined) this.latency = performance.now() - sentAt;
+        return;
+      }
+      this.handlers.get(message.topic)?.forEach((handler) => {
+        try {
+          handler(message.data, message.from);
+        } catch (error) {
+          console.error("❌ Game relay handler failed:", error);
+        }
+      });
+    };
+
+    socket.onclose = () => {
+      if (this.ws !== socket) return;
+      this.ws = null;
+      this.isConnected = false;
+      clearInterval(this.pingTimer);
+      this.pingTimer = null;
+      this.pingSentAt.clear();
+      if (this.handlers.size > 0) this.scheduleReconnect();
+    };
+
+    socket.onerror = () => {
+      // onclose follows and schedules the reconnect
+    };
+  }
+
+  async authenticate(socket, nonce) {
+    const keypair = this.keypair;
+    try {
+      const signature = await signChallenge(keypair, String(nonce));
+      if (this.ws === socket) this.write({ op: "auth", key: keypair.publicKey.toBase58(), signature });
+    } catch (error) {
+      console.error("❌ Game relay sign-in failed:", error);
+      socket.close();
+    }
+  }
+
+  scheduleReconnect() {
+    if (this.reconnectTimer) return;
+    const delay = this.reconnectDelay;
+    this.reconnectDelay = Math.min(this.reconnectDelay * 2, MAX_RECONNECT_DELAY);
+    this.reconnectTimer = setTimeout(() => {
+      this.reconnectTimer = null;
+      if (this.handlers.size > 0) this.connect();
+    }, delay);
+  }
+
+  write(message) {
+    this.ws.send(JSON.stringify(message));
+  }
+
+  ping() {
+    const id = ++this.pingId;
+    this.pingSentAt.set(id, performance.now());
+    this.write({ op: "ping", id });
+  }
+
+  publish(topic, data) {
+    const message = { op: "publish", topic, data };
+    if (this.isConnected) {
+      this.write(message);
+      return;
+    }
+    this.queue.push(message);
+    if (this.queue.length > MAX_QUEUED_MESSAGES) this.queue.shift();
+  }
+
+  subscribe(topic, handler) {
+    // The ephemeral wallet loaded since we signed in: sign in again as it
+    if (this.ws && this.keypair !== relayKeypair()) {
+      this.isConnected = false;
+      this.ws.close();
+    }
+    if (!this.handlers.has(topic)) {
+      this.handlers.set(topic, new Set());
+      if (this.isConnected) this.write({ op: "join", topic });
+    }
+    this.handlers.get(topic).add(handler);
+    this.connect();
+
+    return () => {
+      const topicHandlers = this.handlers.get(topic);
+      if (!topicHandlers) return;
+      topicHandlers.delete(handler);
+      if (topicHandlers.size > 0) return;
+      this.handlers.delete(topic);
+      this.queue = this.queue.filter((message) => message.topic !== topic);
+      if (this.isConnected) this.write({ op: "leave", topic });
+    };
+  }
+}
+
+const relay = new RelayClient(RELAY_URL);
+
+// Same-browser tabs trust each other, so the shim stamps the sender's key itself
+function openLocalChannel(topic, onMessage) {
+  if (typeof BroadcastChannel === "undefined") return null;
+  const channel = new BroadcastChannel(`fps-relay-${topic}`);
+  channel.onmessage = (message) => onMessage(message.data?.data, message.data?.from);
+  return {
+    send: (data) => channel.postMessage({ from: getRelayIdentity(), data }),
+    close: () => channel.close(),
+  };
+}
+
+/**
+ * Join a relay topic
+ * @param {string} topic - e.g. `game-events:<gameId>`
+ * @param {(data: any, from: string) => void} onMessage - Called with what other clients publish and the key they signed in with
+ * @returns {{send: (data: any) => void, close: () => void} | null} Null if no transport is available
+ */
+export function openRelayChannel(topic, onMessage) {
+  if (LOCAL_SHIM) return openLocalChannel(topic, onMessage);
+  if (typeof WebSocket === "undefined") return null;
+  const unsubscribe = relay.subscribe(topic, onMessage);
+  return {
+    send: (data) => relay.publish(topic, data),
+    close: unsubscribe,
+  };
+}
+
+/** Key (base58) the relay stamps on what this tab publishes */
+export function getRelayIdentity() {
+  return relayKeypair().publicKey.toBase58();
+}
+
+/** Round trip to the relay in ms, null before the first measurement (or on the local shim) */
+export function getRelayLatency() {
+  return LOCAL_SHIM ? null : relay.latency;
+}
@@ -0,0 +1,70 @@
+/**
+ * Session cost tracking
+ *
+ * Every input, shot and respawn in a match is a transaction paid by the
+ * ephemeral (session) wallet. Spend is measured from its balance (start
+ * balance + top-ups - current balance) and divided over the transaction
+ * receipts the bridge logged, so the burn rate can be projected to the end of
+ * the match and a top-up offered before the wallet runs dry mid-fight.
+ */
+
+// Ask for a top-up when the projected spend comes within this factor of the balance
+export const TOP_UP_SAFETY_MARGIN = 1.25;
+
+// Top-ups are rounded up to this step (SOL)
+export const TOP_UP_STEP_SOL = 0.05;
+
+// Need this much data before projecting (seconds)
+const MIN_PROJECTION_SECONDS = 20;
+
+export function createSession(balance, now = Date.now()) {
+  return { startedAt: now, startBalance: balance, balance, toppedUp: 0, txCount: 0 };
+}
+
+export function noteBalance(session, balance) {
+  return { ...session, balance };
+}
+
+export function noteTopUp(session, amountSol) {
+  return { ...session, toppedUp: session.toppedUp + amountSol };
+}
+
+export function noteReceipt(session) {
+  return { ...session, txCount: session.txCount + 1 };
+}
+
+/**
+ * What the session has cost so far
+ * @returns {{spent: number, txCount: number, perTx: number|null, burnPerSecond: number}}
+ */
+export function sessionCost(session, now = Date.now()) {
+  const spent = Math.max(0, session.startBalance + session.toppedUp - session.balance);
+  const elapsed = Math.max(1, (now - session.startedAt) / 1000);
+  return {
+    spent,
+    txCount: session.txCount,
+    perTx: session.txCount > 0 ? spent / session.txCount : null,
+    burnPerSecond: spent / elapsed,
+  };
+}
+
+/**
+ * SOL to add so the wallet lasts until the end of the match (0 = enough)
+ * @param {object} session - Session from createSession
+ * @param {number|null} secondsLeft - Match time remaining (null = unknown)
+ */
+export function topUpNeeded(session, secondsLeft, now = Date.now()) {
+  if (secondsLeft === null || secondsLeft === undefined) return 0;
+  if ((now - session.startedAt) / 1000 < MIN_PROJECTION_SECONDS) return 0;
+
+  const projected = sessionCost(session, now).burnPerSecond * secondsLeft * TOP_UP_SAFETY_MARGIN;
+  const shortfall = projected - session.balance;
+  if (shortfall <= 0) return 0;
+  return Math.ceil(shortfall / TOP_UP_STEP_SOL) * TOP_UP_STEP_SOL;
+}
+
+/** "0.00123 SOL" with enough digits for per-transaction fees */
+export function formatSol(amount) {
+  if (amount === null || amount === undefined) return "---";
+  return `${amount < 0.01 ? amount.toFixed(5) : amount.toFixed(3)} SOL`;
+}
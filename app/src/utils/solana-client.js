@@ -0,0 +1,60 @@
+/**
+ * Loader for the solana-client wasm module (the `solana-client` crate)
+ *
+ * The crate encodes maps, derives the map registry PDAs and builds the
+ * transactions; this side only hands them to the wallet to sign and sends
+ * them. build-game.sh writes the module to public/solana-client/, which is
+ * served as-is, so it's imported at runtime rather than bundled.
+ */
+
+import { Transaction } from "@solana/web3.js";
+
+let clientPromise = null;
+
+/** The initialized wasm module (createMap, updateMap, getMapData, getUserMaps) */
+export function loadSolanaClient() {
+  if (!clientPromise) {
+    const url = `${process.env.PUBLIC_URL || ""}/solana-client/solana_client.js`;
+    clientPromise = import(/* webpackIgnore: true */ url)
+      .then(async (module) => {
+        await module.default();
+        return module;
+      })
+      .catch((error) => {
+        clientPromise = null;
+        throw new Error(`Solana client module not available (run build-game.sh): ${error.message}`);
+      });
+  }
+  return clientPromise;
+}
+
+/**
+ * Sign unsigned transactions from the client with the wallet and send them in order
+ * Each one is confirmed before the next is sent, since later ones (chunks) can
+ * depend on accounts the earlier ones create
+ * @param {Connection} connection - Connection the transactions were built against
+ * @param {Object} wallet - Browser wallet (signTransaction, optionally signAllTransactions)
+ * @param {Array<Uint8Array>} unsignedTransactions - Serialized legacy transactions
+ * @returns {Promise<Array<string>>} Signatures, in order
+ */
+export async function signAndSendAll(connection, wallet, unsignedTransactions) {
+  const transactions = Array.from(unsignedTransactions, (bytes) => Transaction.from(bytes));
+  // One wallet prompt for the whole batch where the wallet supports it
+  const signed = wallet.signAllTransactions
+    ? await wallet.signAllTransactions(transactions)
+    : await transactions.reduce(
+        async (done, transaction) => [...(await done), await wallet.signTransaction(transaction)],
+        Promise.resolve([])
+      );
+
+  const signatures = [];
+  for (const transaction of signed) {
+    const signature = await connection.sendRawTransaction(transaction.serialize());
+    const { value } = await connection.confirmTransaction(signature, "confirmed");
+    if (value.err) {
+      throw new Error(`Transaction ${signature} failed: ${JSON.stringify(value.err)}`);
+    }
+    signatures.push(signature);
+  }
+  return signatures;
+}
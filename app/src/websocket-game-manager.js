@@ -8,6 +8,9 @@
 const WEBSOCKET_RPC_URL = process.env.REACT_APP_EPHEMERAL_WEBSOCKET_RPC_URL || "ws://127.0.0.1:7800";
 const HTTP_RPC_URL = process.env.REACT_APP_EPHEMERAL_RPC_URL || "http://127.0.0.1:8899";
 const HTTP_FALLBACK_POLL_INTERVAL = 100; // Poll every 100ms when using HTTP fallback
+const HTTP_FALLBACK_MAX_POLL_INTERVAL = 2000; // Back off to this when polls keep failing
+const HTTP_MAX_ACCOUNTS_PER_REQUEST = 100; // getMultipleAccounts limit
+const WEBSOCKET_UPGRADE_INTERVAL = 15000; // Retry WebSocket every 15s while degraded
 
 class WebSocketGameManager {
   constructor() {
@@ -21,10 +24,61 @@ class WebSocketGameManager {
     this.messageId = 1;
     this.pendingRequests = new Map(); // message_id -> { resolve, reject }
     
+    this.accountCallbacks = new Map(); // account_pubkey -> callback (survives transport switches)
+
     // HTTP fallback state
     this.useHttpFallback = false;
-    this.httpPollingIntervals = new Map(); // account_pubkey -> interval_id
-    this.httpCallbacks = new Map(); // account_pubkey -> callback
+    this.httpCallbacks = new Map(); // account_pubkey -> callback (accounts being polled)
+    this.httpPollTimer = null;
+    this.httpPollDelay = HTTP_FALLBACK_POLL_INTERVAL;
+    this.upgradeTimer = null;
+
+    // "websocket" | "http" (degraded) | "disconnected"
+    this.mode = "disconnected";
+    this.modeListeners = new Set();
+
+    // Endpoints (switchable from the connection settings)
+    this.wsUrl = WEBSOCKET_RPC_URL;
+    this.httpUrl = HTTP_RPC_URL;
+  }
+
+  /**
+   * Set the transport mode and notify listeners (drives the degraded-mode indicator)
+   */
+  setMode(mode) {
+    if (this.mode === mode) return;
+    this.mode = mode;
+    if (typeof window !== "undefined") {
+      window.__network_mode = mode;
+    }
+    this.modeListeners.forEach((listener) => {
+      try {
+        listener(mode);
+      } catch (error) {
+        console.error("❌ Network mode listener failed:", error);
+      }
+    });
+  }
+
+  /**
+   * Listen for transport mode changes
+   * @param {Function} listener - (mode) => void
+   * @returns {Function} Unsubscribe function
+   */
+  onModeChange(listener) {
+    this.modeListeners.add(listener);
+    return () => this.modeListeners.delete(listener);
+  }
+
+  /**
+   * Switch the rollup endpoints used for subscriptions and HTTP polling
+   * Takes effect on the next connect()
+   * @param {{wsUrl?: string, httpUrl?: string}} urls
+   */
+  setEndpoints({ wsUrl, httpUrl }) {
+    if (wsUrl) this.wsUrl = wsUrl;
+    if (httpUrl) this.httpUrl = httpUrl;
+    console.log(`🌐 WebSocket endpoints set: ${this.wsUrl} (HTTP ${this.httpUrl})`);
   }
 
   /**
@@ -39,32 +93,36 @@ class WebSocketGameManager {
       }
 
       try {
-        console.log("🔌 Attempting to connect to WebSocket:", WEBSOCKET_RPC_URL);
-        this.ws = new WebSocket(WEBSOCKET_RPC_URL);
+        console.log("🔌 Attempting to connect to WebSocket:", this.wsUrl);
+        const socket = new WebSocket(this.wsUrl);
+        this.ws = socket;
 
         const connectionTimeout = setTimeout(() => {
           if (!this.isConnected) {
             console.warn("⚠️ WebSocket connection timeout, falling back to HTTP RPC");
-            this.ws.close();
+            socket.close();
             this.enableHttpFallback();
             resolve(); // Resolve anyway since we have fallback
           }
         }, 5000); // 5 second timeout
 
-        this.ws.onopen = () => {
+        socket.onopen = () => {
           clearTimeout(connectionTimeout);
           this.isConnected = true;
           this.useHttpFallback = false;
           this.reconnectAttempts = 0;
           console.log("✅ WebSocket connected successfully");
+          this.setMode("websocket");
+          // Move any accounts we were polling back onto real subscriptions
+          this.upgradeHttpSubscriptions();
           resolve();
         };
 
-        this.ws.onmessage = (event) => {
+        socket.onmessage = (event) => {
           this.handleMessage(event.data);
         };
 
-        this.ws.onerror = (error) => {
+        socket.onerror = (error) => {
           clearTimeout(connectionTimeout);
           console.error("❌ WebSocket error:", error);
           console.log("🔄 Enabling HTTP RPC fallback...");
@@ -72,8 +130,10 @@ class WebSocketGameManager {
           resolve(); // Resolve with fallback enabled
         };
 
-        this.ws.onclose = () => {
+        socket.onclose = () => {
           clearTimeout(connectionTimeout);
+          // Ignore sockets we've already replaced or closed on purpose
+          if (this.ws !== socket) return;
           this.isConnected = false;
           this.handleDisconnect();
         };
@@ -134,6 +194,14 @@ class WebSocketGameManager {
    * Handle disconnection and attempt reconnect
    */
   handleDisconnect() {
+    // Keep live subscriptions flowing over HTTP; the upgrade timer brings the WebSocket back
+    if (this.accountSubscriptions.size > 0 || this.useHttpFallback) {
+      console.warn("⚠️ WebSocket dropped with active subscriptions, switching to HTTP polling");
+      this.enableHttpFallback();
+      return;
+    }
+
+    this.setMode("disconnected");
     if (this.reconnectAttempts < this.maxReconnectAttempts) {
       this.reconnectAttempts++;
       const delay = this.reconnectDelay * Math.pow(2, this.reconnectAttempts - 1); // Exponential backoff
@@ -191,6 +259,8 @@ class WebSocketGameManager {
    * @returns {number} Subscription ID
    */
   async subscribeToAccount(accountPubkey, callback) {
+    this.accountCallbacks.set(accountPubkey, callback);
+
     // Use HTTP fallback if WebSocket is not available
     if (this.useHttpFallback) {
       console.log(`📡 Using HTTP fallback for account: ${accountPubkey.slice(0, 8)}...`);
@@ -224,50 +294,136 @@ class WebSocketGameManager {
   }
 
   /**
-   * Enable HTTP fallback mode
+   * Enable HTTP fallback mode (degraded)
+   * Active WebSocket subscriptions are moved to polling and a periodic upgrade attempt is scheduled.
    */
   enableHttpFallback() {
     if (!this.useHttpFallback) {
       console.log("🔄 HTTP RPC fallback enabled");
       this.useHttpFallback = true;
     }
+    this.setMode("http");
+
+    // Subscriptions on a dead socket won't deliver anything - poll them instead
+    for (const accountPubkey of this.accountSubscriptions.keys()) {
+      const callback = this.accountCallbacks.get(accountPubkey);
+      if (callback) this.subscribeViaHttp(accountPubkey, callback);
+    }
+    this.subscriptions.clear();
+    this.accountSubscriptions.clear();
+
+    if (!this.upgradeTimer) {
+      this.upgradeTimer = setInterval(() => this.tryUpgrade(), WEBSOCKET_UPGRADE_INTERVAL);
+    }
+  }
+
+  /**
+   * Try to get back onto a WebSocket while degraded
+   */
+  tryUpgrade() {
+    if (!this.useHttpFallback || this.isConnected) {
+      this.stopUpgradeTimer();
+      return;
+    }
+    console.log("🔌 Retrying WebSocket connection...");
+    this.connect().catch(() => {}); // Stays on HTTP if it fails again
+  }
+
+  stopUpgradeTimer() {
+    if (this.upgradeTimer) {
+      clearInterval(this.upgradeTimer);
+      this.upgradeTimer = null;
+    }
+  }
+
+  /**
+   * Re-subscribe polled accounts over the (re)connected WebSocket
+   */
+  async upgradeHttpSubscriptions() {
+    this.stopUpgradeTimer();
+    const polled = [...this.httpCallbacks.entries()];
+    if (polled.length === 0) return;
+
+    console.log(`⬆️ Upgrading ${polled.length} polled accounts back to WebSocket`);
+    for (const [accountPubkey, callback] of polled) {
+      try {
+        const subscriptionId = await this.sendRequest("accountSubscribe", [
+          accountPubkey,
+          { encoding: "jsonParsed", commitment: "confirmed" },
+        ]);
+        this.subscriptions.set(subscriptionId, callback);
+        this.accountSubscriptions.set(accountPubkey, subscriptionId);
+        this.httpCallbacks.delete(accountPubkey);
+      } catch (error) {
+        console.error(`❌ Upgrade failed for ${accountPubkey.slice(0, 8)}, staying on HTTP:`, error);
+        this.enableHttpFallback();
+        return;
+      }
+    }
   }
 
   /**
    * Subscribe to account via HTTP polling (fallback)
+   * All polled accounts share one bounded-rate loop (batched getMultipleAccounts, no overlapping requests)
    * @param {string} accountPubkey - The account public key to monitor
    * @param {Function} callback - Callback function when account changes
    * @returns {string} Pseudo-subscription ID (account pubkey)
    */
   subscribeViaHttp(accountPubkey, callback) {
-    // Store callback
     this.httpCallbacks.set(accountPubkey, callback);
+    this.startHttpPolling();
+    return accountPubkey; // Return pubkey as pseudo-subscription ID
+  }
 
-    // Start polling interval
-    const intervalId = setInterval(async () => {
-      try {
-        const accountInfo = await this.fetchAccountViaHttp(accountPubkey);
-        if (accountInfo) {
-          callback(accountInfo);
-        }
-      } catch (error) {
-        console.error(`❌ HTTP polling error for ${accountPubkey.slice(0, 8)}:`, error.message);
+  /**
+   * Start the shared polling loop if it isn't running
+   */
+  startHttpPolling() {
+    if (this.httpPollTimer) return;
+    console.log(`✅ HTTP polling started (${HTTP_FALLBACK_POLL_INTERVAL}ms interval)`);
+
+    const tick = async () => {
+      if (this.httpCallbacks.size === 0) {
+        this.httpPollTimer = null;
+        console.log("🛑 HTTP polling stopped");
+        return;
       }
-    }, HTTP_FALLBACK_POLL_INTERVAL);
-
-    this.httpPollingIntervals.set(accountPubkey, intervalId);
-    console.log(`✅ HTTP polling started for ${accountPubkey.slice(0, 8)}... (${HTTP_FALLBACK_POLL_INTERVAL}ms interval)`);
+      await this.pollHttpAccounts();
+      this.httpPollTimer = setTimeout(tick, this.httpPollDelay);
+    };
+    this.httpPollTimer = setTimeout(tick, 0);
+  }
 
-    return accountPubkey; // Return pubkey as pseudo-subscription ID
+  /**
+   * Fetch every polled account once and deliver it in WebSocket notification format
+   */
+  async pollHttpAccounts() {
+    const pubkeys = [...this.httpCallbacks.keys()];
+    try {
+      for (let i = 0; i < pubkeys.length; i += HTTP_MAX_ACCOUNTS_PER_REQUEST) {
+        const batch = pubkeys.slice(i, i + HTTP_MAX_ACCOUNTS_PER_REQUEST);
+        const values = await this.fetchAccountsViaHttp(batch);
+        batch.forEach((accountPubkey, index) => {
+          const callback = this.httpCallbacks.get(accountPubkey);
+          if (callback && values[index]) {
+            callback({ value: values[index] });
+          }
+        });
+      }
+      this.httpPollDelay = HTTP_FALLBACK_POLL_INTERVAL;
+    } catch (error) {
+      this.httpPollDelay = Math.min(this.httpPollDelay * 2, HTTP_FALLBACK_MAX_POLL_INTERVAL);
+      console.error(`❌ HTTP polling error (next poll in ${this.httpPollDelay}ms):`, error.message);
+    }
   }
 
   /**
-   * Fetch account info via HTTP RPC
-   * @param {string} accountPubkey - The account public key
-   * @returns {Object} Account data in WebSocket notification format
+   * Fetch several accounts via HTTP RPC
+   * @param {Array<string>} accountPubkeys - Account public keys (max 100)
+   * @returns {Array<Object|null>} Account values in request order
    */
-  async fetchAccountViaHttp(accountPubkey) {
-    const response = await fetch(HTTP_RPC_URL, {
+  async fetchAccountsViaHttp(accountPubkeys) {
+    const response = await fetch(this.httpUrl, {
       method: 'POST',
       headers: {
         'Content-Type': 'application/json',
@@ -275,9 +431,9 @@ class WebSocketGameManager {
       body: JSON.stringify({
         jsonrpc: '2.0',
         id: 1,
-        method: 'getAccountInfo',
+        method: 'getMultipleAccounts',
         params: [
-          accountPubkey,
+          accountPubkeys,
           {
             encoding: 'base64',
             commitment: 'confirmed',
@@ -287,15 +443,12 @@ class WebSocketGameManager {
     });
 
     const data = await response.json();
-    
+
     if (data.error) {
       throw new Error(data.error.message);
     }
 
-    // Format to match WebSocket notification structure
-    return {
-      value: data.result.value,
-    };
+    return data.result.value;
   }
 
   /**
@@ -303,13 +456,11 @@ class WebSocketGameManager {
    * @param {string} accountPubkey - The account public key
    */
   async unsubscribeFromAccount(accountPubkey) {
-    // Handle HTTP fallback unsubscribe
-    if (this.httpPollingIntervals.has(accountPubkey)) {
-      const intervalId = this.httpPollingIntervals.get(accountPubkey);
-      clearInterval(intervalId);
-      this.httpPollingIntervals.delete(accountPubkey);
+    this.accountCallbacks.delete(accountPubkey);
+
+    // Handle HTTP fallback unsubscribe (the polling loop stops itself when empty)
+    if (this.httpCallbacks.has(accountPubkey)) {
       this.httpCallbacks.delete(accountPubkey);
-      console.log(`🛑 HTTP polling stopped for ${accountPubkey.slice(0, 8)}...`);
       return;
     }
 
@@ -395,13 +546,14 @@ class WebSocketGameManager {
    * Disconnect and clean up
    */
   disconnect() {
-    // Clean up HTTP polling intervals
-    for (const [accountPubkey, intervalId] of this.httpPollingIntervals.entries()) {
-      clearInterval(intervalId);
-      console.log(`🛑 Stopped HTTP polling for ${accountPubkey.slice(0, 8)}...`);
+    // Clean up HTTP polling and upgrade attempts
+    if (this.httpPollTimer) {
+      clearTimeout(this.httpPollTimer);
+      this.httpPollTimer = null;
     }
-    this.httpPollingIntervals.clear();
     this.httpCallbacks.clear();
+    this.accountCallbacks.clear();
+    this.stopUpgradeTimer();
 
     // Clean up WebSocket
     if (this.ws) {
@@ -414,6 +566,7 @@ class WebSocketGameManager {
     }
     
     this.useHttpFallback = false;
+    this.setMode("disconnected");
   }
 }
 
@@ -19,6 +19,20 @@ rustup target add wasm32-unknown-emscripten
 export EMCC_CFLAGS="-O3 -sUSE_GLFW=3 -sASSERTIONS=1 -sWASM=1 -sASYNCIFY -sGL_ENABLE_GET_PROC_ADDRESS=1"
 
 
+# Map registry client (wasm-bindgen), loaded by the React app at runtime
+if ! command -v wasm-pack &> /dev/null; then
+    echo "Error: wasm-pack is not installed or not in PATH"
+    echo "Please install wasm-pack: https://rustwasm.github.io/wasm-pack/installer/"
+    exit 1
+fi
+
+echo "Building Solana client..."
+if ! wasm-pack build solana-client --release --target web --out-dir ../app/public/solana-client; then
+    echo ""
+    echo "❌ Solana client build failed! Check the error messages above."
+    exit 1
+fi
+
 cargo build --release --target wasm32-unknown-emscripten -p fpsdotso-game
 
 # Check if build succeeded
@@ -10,7 +10,7 @@ fn main() {
     // Only apply these settings to our main package, not dependencies
     if target == "wasm32-unknown-emscripten" && package_name == "fpsdotso-game" {
         // Export main and our custom game control functions
-        println!("cargo:rustc-link-arg=-sEXPORTED_FUNCTIONS=['_main','_start_game','_stop_game','_set_current_game_js','_malloc','_free']");
+        println!("cargo:rustc-link-arg=-sEXPORTED_FUNCTIONS=['_main','_start_game','_stop_game','_start_spectating_js','_start_tutorial_js','_start_shooting_range_js','_start_practice_match_js','_set_current_game_js','_set_bot_backfill_js','_set_game_mode_js','_set_loadout_js','_end_round_js','_sync_freeze_time_js','_set_audio_volume_js','_set_ui_sounds_enabled_js','_play_ui_sound_js','_set_colorblind_mode_js','_set_comfort_setting_js','_set_viewmodel_js','_set_render_mode_js','_set_view_distance_js','_set_film_effects_js','_set_hud_layout_js','_set_language_js','_set_menu_tab_js','_set_log_level_js','_set_log_panel_js','_get_player_position','_get_other_players','_get_map_stream_progress','_malloc','_free']");
         println!("cargo:rustc-link-arg=-sEXPORTED_RUNTIME_METHODS=['cwrap','lengthBytesUTF8','stringToUTF8', 'HEAPF32']");
         //println!("cargo:rustc-link-arg=-sMODULARIZE=1");
 
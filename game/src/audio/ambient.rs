@@ -0,0 +1,97 @@
+use std::ffi::CString;
+use super::mixer::{self, AudioChannel};
+
+// Emscripten bindings for JavaScript interop
+extern "C" {
+    fn emscripten_run_script(script: *const std::os::raw::c_char);
+}
+
+/// How often emitter volumes are recomputed (seconds); walking past an emitter doesn't need per-frame updates
+const AMBIENT_UPDATE_INTERVAL: f32 = 0.1;
+
+/// Looping audio for a map's ambient sound emitters
+///
+/// The game works out each emitter's volume for the listener; this routes them through the
+/// effects channel and pushes changes to looping HTML audio elements (created lazily in
+/// `window.__fpsAmbient`, one per emitter).
+#[derive(Debug, Default)]
+pub struct AmbientPlayer {
+    /// (source, final volume) last pushed for each emitter
+    sent: Vec<(&'static str, f32)>,
+    update_timer: f32,
+}
+
+impl AmbientPlayer {
+    /// Whether it's time to recompute emitter volumes
+    pub fn due(&mut self, delta: f32) -> bool {
+        self.update_timer -= delta;
+        if self.update_timer > 0.0 {
+            return false;
+        }
+        self.update_timer = AMBIENT_UPDATE_INTERVAL;
+        true
+    }
+
+    /// Play each emitter's loop at its volume (0.0 to 1.0, before the effects channel);
+    /// loops of emitters past the end of `emitters` are stopped
+    pub fn set_volumes(&mut self, emitters: &[(&'static str, f32)]) {
+        let gain = mixer::channel_volume(AudioChannel::Effects);
+        let output: Vec<(&'static str, f32)> = emitters.iter().map(|&(src, volume)| (src, (volume * gain).clamp(0.0, 1.0))).collect();
+        let changed = output.len() != self.sent.len()
+            || output.iter().zip(&self.sent).any(|(a, b)| a.0 != b.0 || (a.1 - b.1).abs() > 0.01);
+        if changed {
+            push_volumes(&output);
+            self.sent = output;
+        }
+    }
+
+    /// Silence every loop (leaving the game, switching maps)
+    pub fn stop(&mut self) {
+        self.set_volumes(&[]);
+    }
+}
+
+fn push_volumes(emitters: &[(&'static str, f32)]) {
+    let emitters: Vec<String> = emitters.iter().map(|(src, volume)| format!("['{}', {:.3}]", src, volume)).collect();
+    let js_code = format!(
+        r#"
+        (function() {{
+            try {{
+                window.__fpsAmbient = window.__fpsAmbient || [];
+                var emitters = [{}];
+                emitters.forEach(function(emitter, i) {{
+                    var src = emitter[0], volume = emitter[1];
+                    var el = window.__fpsAmbient[i];
+                    if (el && el.getAttribute('src') !== src) {{
+                        el.pause();
+                        el = window.__fpsAmbient[i] = null;
+                    }}
+                    if (!el) {{
+                        if (volume <= 0) return;
+                        el = new Audio(src);
+                        el.loop = true;
+                        window.__fpsAmbient[i] = el;
+                    }}
+                    el.volume = Math.max(0, Math.min(1, volume));
+                    if (volume > 0 && el.paused) {{
+                        el.play().catch(function() {{}}); // Autoplay may be blocked until user input
+                    }} else if (volume <= 0 && !el.paused) {{
+                        el.pause();
+                    }}
+                }});
+                window.__fpsAmbient.splice(emitters.length).forEach(function(el) {{
+                    if (el) el.pause();
+                }});
+            }} catch (error) {{
+                console.error('Ambient sound error:', error);
+            }}
+        }})();
+        "#,
+        emitters.join(", ")
+    );
+
+    unsafe {
+        let c_str = CString::new(js_code).unwrap();
+        emscripten_run_script(c_str.as_ptr());
+    }
+}
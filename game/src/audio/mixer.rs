@@ -0,0 +1,166 @@
+use std::cell::Cell;
+use std::ffi::CString;
+
+// Emscripten bindings for JavaScript interop
+extern "C" {
+    fn emscripten_run_script(script: *const std::os::raw::c_char);
+}
+
+/// Mixer channel a sound is routed through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioChannel {
+    /// Scales every other channel
+    Master,
+    /// Gunshots, reloads, impacts
+    Effects,
+    /// Background music layers
+    Music,
+    /// Menu clicks, announcer, notifications
+    Ui,
+}
+
+impl AudioChannel {
+    /// Channel from the id used by the JS setter (0 = master, 1 = effects, 2 = music, 3 = UI)
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(AudioChannel::Master),
+            1 => Some(AudioChannel::Effects),
+            2 => Some(AudioChannel::Music),
+            3 => Some(AudioChannel::Ui),
+            _ => None,
+        }
+    }
+}
+
+/// Per-channel volumes (0.0 to 1.0)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mixer {
+    pub master: f32,
+    pub effects: f32,
+    pub music: f32,
+    pub ui: f32,
+    /// Announcer/UI sounds toggle (mutes the UI channel without losing its volume)
+    pub ui_enabled: bool,
+}
+
+impl Default for Mixer {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            effects: 0.3, // Matches the old hardcoded gunshot volume
+            music: 0.5,
+            ui: 0.6,
+            ui_enabled: true,
+        }
+    }
+}
+
+impl Mixer {
+    /// Set a channel's volume (clamped to 0.0 - 1.0)
+    pub fn set_volume(&mut self, channel: AudioChannel, volume: f32) {
+        let volume = volume.clamp(0.0, 1.0);
+        match channel {
+            AudioChannel::Master => self.master = volume,
+            AudioChannel::Effects => self.effects = volume,
+            AudioChannel::Music => self.music = volume,
+            AudioChannel::Ui => self.ui = volume,
+        }
+    }
+
+    /// Final volume for a channel (channel volume scaled by master)
+    pub fn effective_volume(&self, channel: AudioChannel) -> f32 {
+        let channel_volume = match channel {
+            AudioChannel::Master => 1.0,
+            AudioChannel::Effects => self.effects,
+            AudioChannel::Music => self.music,
+            AudioChannel::Ui if self.ui_enabled => self.ui,
+            AudioChannel::Ui => 0.0,
+        };
+        self.master * channel_volume
+    }
+}
+
+// Global mixer (Emscripten is single-threaded)
+thread_local! {
+    static MIXER: Cell<Mixer> = Cell::new(Mixer::default());
+}
+
+/// Set a channel volume on the global mixer
+pub fn set_channel_volume(channel: AudioChannel, volume: f32) {
+    MIXER.with(|mixer| {
+        let mut current = mixer.get();
+        current.set_volume(channel, volume);
+        mixer.set(current);
+    });
+}
+
+/// Enable or disable announcer/UI sounds on the global mixer
+pub fn set_ui_sounds_enabled(enabled: bool) {
+    MIXER.with(|mixer| {
+        let mut current = mixer.get();
+        current.ui_enabled = enabled;
+        mixer.set(current);
+    });
+}
+
+/// Final volume for a channel on the global mixer
+pub fn channel_volume(channel: AudioChannel) -> f32 {
+    MIXER.with(|mixer| mixer.get().effective_volume(channel))
+}
+
+/// Play a one-shot sound through a mixer channel
+///
+/// `volume` is the sound's own level (0.0 to 1.0) before the channel and
+/// master volumes are applied. Each source is cached in `window.__fpsSounds`
+/// and cloned per play so sounds can overlap.
+pub fn play_sound(src: &str, channel: AudioChannel, volume: f32) {
+    let volume = (volume * channel_volume(channel)).clamp(0.0, 1.0);
+    if volume <= 0.0 {
+        return;
+    }
+
+    let js_code = format!(
+        r#"
+        (function() {{
+            try {{
+                window.__fpsSounds = window.__fpsSounds || {{}};
+                var src = '{}';
+                if (!window.__fpsSounds[src]) {{
+                    window.__fpsSounds[src] = new Audio(src);
+                    window.__fpsSounds[src].load();
+                }}
+                // Clone to allow overlapping sounds
+                var audio = window.__fpsSounds[src].cloneNode();
+                audio.volume = {:.3};
+                audio.play().catch(function(e) {{ console.error('Sound play error:', src, e); }});
+            }} catch (error) {{
+                console.error('Sound error:', error);
+            }}
+        }})();
+        "#,
+        src, volume
+    );
+
+    unsafe {
+        let c_str = CString::new(js_code).unwrap();
+        emscripten_run_script(c_str.as_ptr());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_volume_scaled_by_master() {
+        let mut mixer = Mixer::default();
+        mixer.set_volume(AudioChannel::Master, 0.5);
+        mixer.set_volume(AudioChannel::Effects, 2.0);
+        assert_eq!(mixer.effects, 1.0);
+        assert_eq!(mixer.effective_volume(AudioChannel::Effects), 0.5);
+        assert_eq!(mixer.effective_volume(AudioChannel::Master), 0.5);
+
+        mixer.ui_enabled = false;
+        assert_eq!(mixer.effective_volume(AudioChannel::Ui), 0.0);
+    }
+}
@@ -0,0 +1,9 @@
+mod music;
+mod ambient;
+pub mod mixer;
+mod ui_sounds;
+
+pub use music::{MusicController, MusicContext};
+pub use ambient::AmbientPlayer;
+pub use mixer::AudioChannel;
+pub use ui_sounds::{UiSound, play_ui_sound};
@@ -0,0 +1,209 @@
+use std::ffi::{CStr, CString};
+use super::mixer::{self, AudioChannel};
+
+// Emscripten bindings for JavaScript interop
+extern "C" {
+    fn emscripten_run_script(script: *const std::os::raw::c_char);
+    fn emscripten_run_script_string(script: *const std::os::raw::c_char) -> *const std::os::raw::c_char;
+}
+
+/// Where the player currently is, which decides the music bed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MusicContext {
+    /// Lobby / menus
+    Menu,
+    /// Map editor
+    Editor,
+    /// In a match
+    InGame,
+}
+
+/// Music layers, played simultaneously and crossfaded by volume
+///
+/// The loops in app/public/assets/music/ share tempo, key and length, so the layers stay in sync.
+const LAYERS: [(&str, &str); 3] = [
+    ("ambient", "/assets/music/ambient.wav"),
+    ("explore", "/assets/music/explore.wav"),
+    ("combat", "/assets/music/combat.wav"),
+];
+
+/// Seconds without nearby shots before combat intensity starts to fall
+const COMBAT_HOLD_TIME: f32 = 4.0;
+
+/// Combat intensity lost per second once the hold time has passed
+const COMBAT_DECAY_RATE: f32 = 0.15;
+
+/// Crossfade speed (volume units per second)
+const CROSSFADE_SPEED: f32 = 0.8;
+
+/// How often (seconds) the music toggle is re-read from JS
+const SETTINGS_POLL_INTERVAL: f32 = 0.5;
+
+/// Rust-driven music controller
+///
+/// Decides per-layer volumes from the current context and combat intensity,
+/// crossfades between them, and pushes the result to looping HTML audio
+/// elements (created lazily in `window.__fpsMusic`).
+pub struct MusicController {
+    context: MusicContext,
+
+    /// 0.0 (calm) to 1.0 (heavy fighting nearby)
+    combat_intensity: f32,
+
+    /// Seconds since the last nearby shot
+    time_since_combat: f32,
+
+    /// Current (faded) layer volumes, before the mixer is applied
+    layer_volumes: [f32; 3],
+
+    /// Last volumes pushed to JS (to avoid re-sending every frame)
+    sent_volumes: [f32; 3],
+
+    /// Music on/off toggle from the settings panel (off while the tracks are missing)
+    pub enabled: bool,
+
+    settings_poll_timer: f32,
+}
+
+impl MusicController {
+    pub fn new() -> Self {
+        Self {
+            context: MusicContext::Menu,
+            combat_intensity: 0.0,
+            time_since_combat: COMBAT_HOLD_TIME,
+            layer_volumes: [0.0; 3],
+            sent_volumes: [-1.0; 3],
+            enabled: true,
+            settings_poll_timer: 0.0,
+        }
+    }
+
+    /// Switch the music bed (combat intensity resets when leaving a match)
+    pub fn set_context(&mut self, context: MusicContext) {
+        if context != self.context {
+            log_info!(Game, "🎵 Music context: {:?} -> {:?}", self.context, context);
+            if context != MusicContext::InGame {
+                self.combat_intensity = 0.0;
+            }
+            self.context = context;
+        }
+    }
+
+    /// Report combat noise (shots fired), 1.0 = right next to the player
+    pub fn add_combat_noise(&mut self, proximity: f32) {
+        if proximity <= 0.0 || self.context != MusicContext::InGame {
+            return;
+        }
+        self.combat_intensity = (self.combat_intensity + proximity * 0.5).min(1.0);
+        self.time_since_combat = 0.0;
+    }
+
+    /// Target volume of each layer for the current state
+    fn target_volumes(&self) -> [f32; 3] {
+        match self.context {
+            MusicContext::Menu | MusicContext::Editor => [1.0, 0.0, 0.0],
+            MusicContext::InGame => {
+                // Combat layer ducks in over exploration as intensity rises
+                let combat = self.combat_intensity;
+                [0.0, 1.0 - combat * 0.6, combat]
+            }
+        }
+    }
+
+    /// Advance fades and push volume changes to the browser
+    pub fn update(&mut self, delta: f32) {
+        self.settings_poll_timer -= delta;
+        if self.settings_poll_timer <= 0.0 {
+            self.settings_poll_timer = SETTINGS_POLL_INTERVAL;
+            self.sync_settings_from_js();
+        }
+
+        // Combat intensity holds for a bit after the last shot, then decays
+        self.time_since_combat += delta;
+        if self.time_since_combat > COMBAT_HOLD_TIME {
+            self.combat_intensity = (self.combat_intensity - COMBAT_DECAY_RATE * delta).max(0.0);
+        }
+
+        // Crossfade each layer towards its target
+        let targets = self.target_volumes();
+        let step = CROSSFADE_SPEED * delta;
+        for (volume, target) in self.layer_volumes.iter_mut().zip(targets) {
+            if *volume < target {
+                *volume = (*volume + step).min(target);
+            } else {
+                *volume = (*volume - step).max(target);
+            }
+        }
+
+        let output = self.output_volumes();
+        let changed = output.iter().zip(self.sent_volumes).any(|(a, b)| (a - b).abs() > 0.01);
+        if changed {
+            self.push_volumes(output);
+            self.sent_volumes = output;
+        }
+    }
+
+    /// Final per-layer volumes (after the mixer's music channel / toggle)
+    fn output_volumes(&self) -> [f32; 3] {
+        let gain = if self.enabled { mixer::channel_volume(AudioChannel::Music) } else { 0.0 };
+        self.layer_volumes.map(|v| v * gain)
+    }
+
+    /// Read the music toggle set by the React settings panel
+    fn sync_settings_from_js(&mut self) {
+        let js_code = CString::new("String(window.musicEnabled !== false)").unwrap();
+
+        unsafe {
+            let result_ptr = emscripten_run_script_string(js_code.as_ptr());
+            if result_ptr.is_null() {
+                return;
+            }
+            if let Ok(result) = CStr::from_ptr(result_ptr).to_str() {
+                self.enabled = result != "false";
+            }
+        }
+    }
+
+    /// Apply layer volumes to the looping audio elements
+    fn push_volumes(&self, volumes: [f32; 3]) {
+        let layers: Vec<String> = LAYERS
+            .iter()
+            .zip(volumes)
+            .map(|((name, src), volume)| format!("['{}', '{}', {:.3}]", name, src, volume))
+            .collect();
+
+        let js_code = format!(
+            r#"
+            (function() {{
+                try {{
+                    window.__fpsMusic = window.__fpsMusic || {{}};
+                    [{}].forEach(function(layer) {{
+                        var name = layer[0], src = layer[1], volume = layer[2];
+                        var el = window.__fpsMusic[name];
+                        if (!el) {{
+                            if (volume <= 0) return;
+                            el = new Audio(src);
+                            el.loop = true;
+                            window.__fpsMusic[name] = el;
+                        }}
+                        el.volume = Math.max(0, Math.min(1, volume));
+                        if (volume > 0 && el.paused) {{
+                            el.play().catch(function() {{}}); // Autoplay may be blocked until user input
+                        }} else if (volume <= 0 && !el.paused) {{
+                            el.pause();
+                        }}
+                    }});
+                }} catch (error) {{
+                    console.error('Music error:', error);
+                }}
+            }})();
+            "#,
+            layers.join(", ")
+        );
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            emscripten_run_script(c_str.as_ptr());
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use super::mixer::{self, AudioChannel};
+
+/// Announcer and interface sounds (all routed through the UI channel)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiSound {
+    /// Menu button click
+    Click,
+    /// Menu button hover
+    Hover,
+    /// One second of a countdown
+    CountdownTick,
+    /// Countdown reached zero
+    CountdownGo,
+    /// Announcer stinger when the match starts
+    MatchStart,
+    /// Local player got a kill
+    KillConfirm,
+    /// Magazine is almost empty
+    LowAmmo,
+    /// Health dropped into the danger zone
+    LowHealth,
+}
+
+impl UiSound {
+    /// Sound from the id used by the JS side (`gameBridge.playUiSound`)
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "click" => Some(UiSound::Click),
+            "hover" => Some(UiSound::Hover),
+            "countdown_tick" => Some(UiSound::CountdownTick),
+            "countdown_go" => Some(UiSound::CountdownGo),
+            "match_start" => Some(UiSound::MatchStart),
+            "kill_confirm" => Some(UiSound::KillConfirm),
+            "low_ammo" => Some(UiSound::LowAmmo),
+            "low_health" => Some(UiSound::LowHealth),
+            _ => None,
+        }
+    }
+
+    /// Sample path and base volume
+    fn sample(&self) -> (&'static str, f32) {
+        match self {
+            UiSound::Click => ("/assets/ui/audio/click.wav", 0.6),
+            UiSound::Hover => ("/assets/ui/audio/hover.wav", 0.3),
+            UiSound::CountdownTick => ("/assets/ui/audio/countdown-tick.wav", 0.8),
+            UiSound::CountdownGo => ("/assets/ui/audio/countdown-go.wav", 1.0),
+            UiSound::MatchStart => ("/assets/ui/audio/announcer-match-start.wav", 1.0),
+            UiSound::KillConfirm => ("/assets/ui/audio/kill-confirm.wav", 0.8),
+            UiSound::LowAmmo => ("/assets/ui/audio/low-ammo.wav", 0.7),
+            UiSound::LowHealth => ("/assets/ui/audio/low-health.wav", 0.8),
+        }
+    }
+}
+
+/// Play an announcer/UI sound (silent when UI sounds are toggled off)
+pub fn play_ui_sound(sound: UiSound) {
+    let (src, volume) = sound.sample();
+    mixer::play_sound(src, AudioChannel::Ui, volume);
+}
@@ -0,0 +1,204 @@
+//! Logging facade: levels and categories with runtime verbosity
+//!
+//! `log_error!`, `log_warn!`, `log_info!` and `log_debug!` take a category and
+//! a format string, e.g. `log_warn!(Net, "Unknown game event type: {:?}", kind)`.
+//! Each category has its own verbosity (Info by default), adjustable from the
+//! browser console through the bridge (`gameBridge.setLogLevel("net", "debug")`).
+//! Lines that pass go to the console, the crash breadcrumbs and the in-game log panel.
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+use super::BreadcrumbKind;
+
+/// Lines the in-game log panel keeps
+pub const LOG_PANEL_LINES: usize = 14;
+
+/// How much gets logged; a category logs every level up to its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 5] = [LogLevel::Off, LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug];
+
+    /// Id used by the JS bridge (0 = off, 1 = error, 2 = warn, 3 = info, 4 = debug)
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.get(id as usize).copied()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogCategory {
+    /// Player sync, netcode, RPC endpoints
+    Net,
+    /// Lobby and match transactions through the Solana bridge
+    Solana,
+    /// Map editor and map library
+    Editor,
+    /// Rendering, fonts, captures
+    Render,
+    /// Gameplay and settings
+    Game,
+}
+
+impl LogCategory {
+    pub const ALL: [LogCategory; 5] = [LogCategory::Net, LogCategory::Solana, LogCategory::Editor, LogCategory::Render, LogCategory::Game];
+
+    /// Id used by the JS bridge, in `ALL` order
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.get(id as usize).copied()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogCategory::Net => "net",
+            LogCategory::Solana => "solana",
+            LogCategory::Editor => "editor",
+            LogCategory::Render => "render",
+            LogCategory::Game => "game",
+        }
+    }
+}
+
+/// Verbosity of each category, in `LogCategory::ALL` order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogFilter {
+    levels: [LogLevel; LogCategory::ALL.len()],
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self { levels: [LogLevel::Info; LogCategory::ALL.len()] }
+    }
+}
+
+impl LogFilter {
+    /// Set one category's verbosity, or every category's for `None`
+    pub fn set(&mut self, category: Option<LogCategory>, level: LogLevel) {
+        match category {
+            Some(category) => self.levels[category as usize] = level,
+            None => self.levels = [level; LogCategory::ALL.len()],
+        }
+    }
+
+    pub fn enabled(&self, category: LogCategory, level: LogLevel) -> bool {
+        level != LogLevel::Off && level <= self.levels[category as usize]
+    }
+}
+
+/// A line in the in-game log panel
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub level: LogLevel,
+    pub category: LogCategory,
+    pub text: String,
+}
+
+// Emscripten is single-threaded
+thread_local! {
+    static FILTER: Cell<LogFilter> = Cell::new(LogFilter::default());
+    static PANEL_OPEN: Cell<bool> = const { Cell::new(false) };
+    static PANEL_LINES: RefCell<VecDeque<LogLine>> = const { RefCell::new(VecDeque::new()) };
+}
+
+pub fn set_log_level(category: Option<LogCategory>, level: LogLevel) {
+    FILTER.with(|filter| {
+        let mut updated = filter.get();
+        updated.set(category, level);
+        filter.set(updated);
+    });
+}
+
+/// Mirror log lines into the in-game panel
+pub fn set_log_panel(open: bool) {
+    PANEL_OPEN.with(|panel| panel.set(open));
+}
+
+pub fn log_panel_open() -> bool {
+    PANEL_OPEN.with(|panel| panel.get())
+}
+
+/// Most recent lines for the panel, oldest first
+pub fn log_panel_lines() -> Vec<LogLine> {
+    PANEL_LINES.with(|lines| lines.borrow().iter().cloned().collect())
+}
+
+/// Write a line if its category logs `level` (use the `log_*!` macros)
+pub fn write_log(category: LogCategory, level: LogLevel, args: fmt::Arguments) {
+    if !FILTER.with(|filter| filter.get()).enabled(category, level) {
+        return;
+    }
+    let text = args.to_string();
+    let line = format!("[{}] {}", category.name(), text);
+    if level <= LogLevel::Warn {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+    super::record(BreadcrumbKind::Log, &line);
+
+    // Kept while the panel is closed too, so opening it shows what just happened
+    PANEL_LINES.with(|lines| {
+        if let Ok(mut lines) = lines.try_borrow_mut() {
+            if lines.len() == LOG_PANEL_LINES {
+                lines.pop_front();
+            }
+            lines.push_back(LogLine { level, category, text });
+        }
+    });
+}
+
+macro_rules! log_error {
+    ($category:ident, $($arg:tt)*) => {
+        $crate::diagnostics::write_log($crate::diagnostics::LogCategory::$category, $crate::diagnostics::LogLevel::Error, format_args!($($arg)*))
+    };
+}
+
+macro_rules! log_warn {
+    ($category:ident, $($arg:tt)*) => {
+        $crate::diagnostics::write_log($crate::diagnostics::LogCategory::$category, $crate::diagnostics::LogLevel::Warn, format_args!($($arg)*))
+    };
+}
+
+macro_rules! log_info {
+    ($category:ident, $($arg:tt)*) => {
+        $crate::diagnostics::write_log($crate::diagnostics::LogCategory::$category, $crate::diagnostics::LogLevel::Info, format_args!($($arg)*))
+    };
+}
+
+macro_rules! log_debug {
+    ($category:ident, $($arg:tt)*) => {
+        $crate::diagnostics::write_log($crate::diagnostics::LogCategory::$category, $crate::diagnostics::LogLevel::Debug, format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_categories_log_up_to_their_own_level() {
+        let mut filter = LogFilter::default();
+        assert!(filter.enabled(LogCategory::Net, LogLevel::Info));
+        assert!(!filter.enabled(LogCategory::Net, LogLevel::Debug));
+
+        filter.set(Some(LogCategory::Net), LogLevel::Debug);
+        filter.set(Some(LogCategory::Render), LogLevel::Warn);
+        assert!(filter.enabled(LogCategory::Net, LogLevel::Debug));
+        assert!(filter.enabled(LogCategory::Render, LogLevel::Error));
+        assert!(!filter.enabled(LogCategory::Render, LogLevel::Info));
+        assert!(!filter.enabled(LogCategory::Game, LogLevel::Debug));
+
+        // Off silences everything, and nothing is ever logged "at" Off
+        filter.set(None, LogLevel::Off);
+        assert!(!filter.enabled(LogCategory::Net, LogLevel::Error));
+        filter.set(None, LogLevel::Debug);
+        assert!(!filter.enabled(LogCategory::Net, LogLevel::Off));
+    }
+}
@@ -0,0 +1,37 @@
+use raylib::prelude::*;
+use crate::game::fonts::draw_hud_text;
+use crate::game::palette;
+use super::log::{log_panel_lines, log_panel_open, LogLevel, LOG_PANEL_LINES};
+
+const PANEL_WIDTH: i32 = 640;
+const LINE_HEIGHT: i32 = 16;
+const FONT_SIZE: i32 = 14;
+const PADDING: i32 = 8;
+
+/// Longer lines are cut off at the panel's edge
+const MAX_LINE_CHARS: usize = 90;
+
+/// Recent log lines in the bottom-left corner, over the game and the editor
+pub fn draw_log_panel(d: &mut RaylibDrawHandle) {
+    if !log_panel_open() {
+        return;
+    }
+    let height = LOG_PANEL_LINES as i32 * LINE_HEIGHT + PADDING * 2;
+    let top = d.get_screen_height() - height - 10;
+    d.draw_rectangle(10, top, PANEL_WIDTH, height, Color::new(8, 6, 16, 200));
+    d.draw_rectangle_lines(10, top, PANEL_WIDTH, height, Color::new(153, 69, 255, 160));
+
+    let palette = palette::current();
+    for (i, line) in log_panel_lines().iter().enumerate() {
+        let color = match line.level {
+            LogLevel::Error => palette.negative,
+            LogLevel::Warn => palette.health_mid,
+            LogLevel::Debug => Color::new(150, 150, 170, 255),
+            _ => Color::new(220, 220, 230, 255),
+        };
+        // The HUD font has no emoji
+        let text: String = line.text.chars().filter(|c| c.is_ascii() || c.is_alphabetic()).take(MAX_LINE_CHARS).collect();
+        let text = format!("[{}] {}", line.category.name(), text.trim());
+        draw_hud_text(d, &text, 10 + PADDING, top + PADDING + i as i32 * LINE_HEIGHT, FONT_SIZE, color);
+    }
+}
@@ -0,0 +1,201 @@
+//! Logging and crash breadcrumbs for errors in the field
+//!
+//! Log lines (see `log`) and `transition`s are kept in a small ring buffer of
+//! the most recent breadcrumbs. When the game panics or hits a fatal error,
+//! the buffer is serialized into a `CrashReport` and handed to
+//! `window.gameBridge.onCrashReport`; the web UI asks the player before
+//! sending it anywhere.
+
+#[macro_use]
+mod log;
+mod log_panel;
+
+pub use log::{LogCategory, LogLevel, set_log_level, set_log_panel, write_log};
+pub use log_panel::draw_log_panel;
+
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::ffi::CString;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+// Emscripten bindings for JavaScript interop
+extern "C" {
+    fn emscripten_run_script(script: *const std::os::raw::c_char);
+}
+
+/// Breadcrumbs kept for a report; older ones are dropped
+pub const MAX_BREADCRUMBS: usize = 120;
+
+/// Longest message kept per breadcrumb (bytes), so a runaway log line can't bloat the report
+const MAX_MESSAGE_LEN: usize = 240;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BreadcrumbKind {
+    /// A console log line
+    Log,
+    /// A key state change (game mode, menu tab, map loaded)
+    Transition,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Breadcrumb {
+    /// Seconds since the game started
+    pub at: f64,
+    pub kind: BreadcrumbKind,
+    pub message: String,
+}
+
+/// What the bridge gets when something goes wrong: why, where, and the breadcrumbs leading up to it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    pub reason: String,
+    /// Source location of a panic
+    pub location: Option<String>,
+    /// Panics end the session; fatal errors leave it running but broken
+    pub panic: bool,
+    pub uptime: f64,
+    pub breadcrumbs: Vec<Breadcrumb>,
+}
+
+/// Ring buffer of the most recent breadcrumbs
+#[derive(Debug, Clone, Default)]
+pub struct Breadcrumbs {
+    entries: VecDeque<Breadcrumb>,
+}
+
+impl Breadcrumbs {
+    pub fn push(&mut self, at: f64, kind: BreadcrumbKind, message: &str) {
+        if self.entries.len() == MAX_BREADCRUMBS {
+            self.entries.pop_front();
+        }
+        let mut message = message.trim().to_string();
+        if message.len() > MAX_MESSAGE_LEN {
+            let mut end = MAX_MESSAGE_LEN;
+            while !message.is_char_boundary(end) {
+                end -= 1;
+            }
+            message.truncate(end);
+            message.push('…');
+        }
+        self.entries.push_back(Breadcrumb { at, kind, message });
+    }
+
+    /// Report for `reason` with everything recorded so far, oldest first
+    pub fn report(&self, reason: &str, location: Option<String>, panic: bool, uptime: f64) -> CrashReport {
+        CrashReport {
+            reason: reason.to_string(),
+            location,
+            panic,
+            uptime,
+            breadcrumbs: self.entries.iter().cloned().collect(),
+        }
+    }
+}
+
+// Emscripten is single-threaded
+thread_local! {
+    static BREADCRUMBS: RefCell<Breadcrumbs> = RefCell::new(Breadcrumbs::default());
+}
+
+static START: OnceLock<Instant> = OnceLock::new();
+
+fn uptime() -> f64 {
+    START.get_or_init(Instant::now).elapsed().as_secs_f64()
+}
+
+fn record(kind: BreadcrumbKind, message: &str) {
+    let at = uptime();
+    // A panic while recording leaves the buffer borrowed; the report just goes without this line
+    BREADCRUMBS.with(|crumbs| {
+        if let Ok(mut crumbs) = crumbs.try_borrow_mut() {
+            crumbs.push(at, kind, message);
+        }
+    });
+}
+
+/// Note a key state change, e.g. "mode: Playing"
+pub fn transition(message: &str) {
+    record(BreadcrumbKind::Transition, message);
+}
+
+/// Report an error the game can't recover from without a reload (the session keeps running)
+pub fn fatal(reason: &str) {
+    log_error!(Game, "💥 Fatal: {}", reason);
+    report(reason, None, false);
+}
+
+/// Report panics through the bridge, then fall through to the default hook (which logs to the console)
+pub fn install_panic_hook() {
+    START.get_or_init(Instant::now);
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let payload = info.payload();
+        let reason = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic".to_string());
+        let location = info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+        report(&reason, location, true);
+        default_hook(info);
+    }));
+}
+
+fn report(reason: &str, location: Option<String>, panic: bool) {
+    let uptime = uptime();
+    let report = BREADCRUMBS.with(|crumbs| match crumbs.try_borrow() {
+        Ok(crumbs) => crumbs.report(reason, location.clone(), panic, uptime),
+        Err(_) => Breadcrumbs::default().report(reason, location.clone(), panic, uptime),
+    });
+    let Ok(json) = serde_json::to_string(&report) else {
+        return;
+    };
+    // JSON is a valid JS literal, so the report goes over as an object
+    let js_code = format!(
+        r#"
+        (function() {{
+            try {{
+                if (window.gameBridge && window.gameBridge.onCrashReport) {{
+                    window.gameBridge.onCrashReport({});
+                }}
+            }} catch (error) {{
+                console.error('Crash report error:', error);
+            }}
+        }})();
+        "#,
+        json
+    );
+    if let Ok(c_str) = CString::new(js_code) {
+        unsafe {
+            emscripten_run_script(c_str.as_ptr());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breadcrumbs_keep_the_most_recent_entries() {
+        let mut crumbs = Breadcrumbs::default();
+        for i in 0..MAX_BREADCRUMBS + 5 {
+            crumbs.push(i as f64, BreadcrumbKind::Log, &format!("line {}", i));
+        }
+        crumbs.push(200.0, BreadcrumbKind::Transition, &"é".repeat(MAX_MESSAGE_LEN));
+        let report = crumbs.report("boom", Some("main.rs:1:1".to_string()), true, 201.0);
+        assert_eq!(report.breadcrumbs.len(), MAX_BREADCRUMBS);
+        assert_eq!(report.breadcrumbs[0].message, "line 6");
+        let last = report.breadcrumbs.last().unwrap();
+        assert_eq!(last.kind, BreadcrumbKind::Transition);
+        assert!(last.message.len() <= MAX_MESSAGE_LEN + '…'.len_utf8());
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["breadcrumbs"][0]["kind"], "log");
+        assert_eq!(json["location"], "main.rs:1:1");
+    }
+}
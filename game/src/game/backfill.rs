@@ -0,0 +1,80 @@
+use raylib::prelude::*;
+use super::bots::{BotDifficulty, BotRole, TrainingBot};
+
+/// Backfill bot authorities start with this, so they never collide with a player's key
+pub const BOT_AUTHORITY_PREFIX: &str = "bot:";
+
+/// Most slots backfill fills on one team (the largest lobby is 5 a side)
+pub const MAX_BACKFILL_PER_TEAM: usize = 5;
+
+/// Backfill bot names, handed out in fill order
+const BOT_CALLSIGNS: [&str; 2 * MAX_BACKFILL_PER_TEAM] = [
+    "Alpha", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliet",
+];
+
+/// Whether `authority` belongs to a backfill bot rather than a player
+pub fn is_bot_authority(authority: &str) -> bool {
+    authority.starts_with(BOT_AUTHORITY_PREFIX)
+}
+
+/// Display name of a backfill bot, tagged so nobody takes it for a player
+fn bot_display_name(callsign: &str) -> String {
+    format!("[BOT] {}", callsign)
+}
+
+/// A bot holding an empty lobby slot
+///
+/// Backfill bots are simulated on the lobby leader's client only; everyone else sees them
+/// through the leader's state updates, the same way they see remote players
+#[derive(Debug, Clone)]
+pub struct BackfillBot {
+    pub authority: String,
+    pub name: String,
+    /// Team whose slot it fills (1 or 2, as on-chain)
+    pub team: u8,
+    pub bot: TrainingBot,
+    /// Facing in radians (same convention as a player's rotationY)
+    pub yaw: f32,
+}
+
+/// Bots for the empty slots: `open` is the number of free slots on teams 1 and 2
+///
+/// Roles cycle so a team gets a mix; callers move the bots to their team's spawn
+pub fn backfill_roster(open: [usize; 2]) -> Vec<BackfillBot> {
+    let teams = open
+        .iter()
+        .zip([1u8, 2])
+        .flat_map(|(&count, team)| std::iter::repeat_n(team, count.min(MAX_BACKFILL_PER_TEAM)));
+    teams
+        .enumerate()
+        .map(|(i, team)| {
+            let id = i as u32 + 1;
+            let role = BotRole::ALL[i % BotRole::ALL.len()];
+            BackfillBot {
+                authority: format!("{}{}", BOT_AUTHORITY_PREFIX, id),
+                name: bot_display_name(BOT_CALLSIGNS[i]),
+                team,
+                bot: TrainingBot::combatant(id, Vector3::zero(), BotDifficulty::Normal, role),
+                yaw: 0.0,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roster_fills_both_teams_with_tagged_bots() {
+        let roster = backfill_roster([1, 3]);
+        assert_eq!(roster.iter().map(|b| b.team).collect::<Vec<_>>(), vec![1, 2, 2, 2]);
+        assert_eq!(roster[0].name, "[BOT] Alpha");
+        assert_eq!(roster[3].authority, "bot:4");
+        assert!(roster.iter().all(|b| is_bot_authority(&b.authority) && b.bot.profile().is_some()));
+        assert!(!is_bot_authority("7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU"));
+
+        assert_eq!(backfill_roster([0, 0]).len(), 0);
+        assert_eq!(backfill_roster([9, 9]).len(), 2 * MAX_BACKFILL_PER_TEAM);
+    }
+}
@@ -0,0 +1,374 @@
+use raylib::prelude::*;
+use crate::i18n::t;
+use super::BASE_MOVE_SPEED;
+
+/// Seconds a downed training bot stays down before standing back up
+pub const BOT_RESPAWN_DELAY: f32 = 3.0;
+
+/// Health of a fresh training bot
+pub const BOT_MAX_HEALTH: f32 = 100.0;
+
+/// Damage of a practice bot's hit on the player
+pub const BOT_SHOT_DAMAGE: f32 = 12.0;
+
+/// Seconds between a practice bot's shots at full aggression (calmer bots fire up to twice as slowly)
+const BOT_FIRE_INTERVAL: f32 = 0.45;
+
+/// Practice bots stop closing in this far from the player at full aggression, and twice as far at none
+const BOT_ENGAGE_DISTANCE: f32 = 6.0;
+
+/// Practice bots count as arrived within this distance of where they're heading
+const BOT_ARRIVE_DISTANCE: f32 = 0.5;
+
+/// How hard a practice bot plays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotDifficulty {
+    Easy,
+    Normal,
+    Hard,
+    Expert,
+}
+
+/// The knobs a difficulty sets
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BotSkill {
+    /// Seconds from first seeing the player to the first shot
+    pub reaction_time: f32,
+    /// Chance each shot hits (0 - 1)
+    pub accuracy: f32,
+    /// How close the bot pushes and how fast it fires (0 - 1)
+    pub aggression: f32,
+    /// Movement speed and strafing while in a fight (0 - 1)
+    pub movement: f32,
+}
+
+impl BotDifficulty {
+    pub const ALL: [BotDifficulty; 4] = [BotDifficulty::Easy, BotDifficulty::Normal, BotDifficulty::Hard, BotDifficulty::Expert];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BotDifficulty::Easy => t("practice.difficulty.easy"),
+            BotDifficulty::Normal => t("practice.difficulty.normal"),
+            BotDifficulty::Hard => t("practice.difficulty.hard"),
+            BotDifficulty::Expert => t("practice.difficulty.expert"),
+        }
+    }
+
+    pub fn skill(&self) -> BotSkill {
+        match self {
+            BotDifficulty::Easy => BotSkill { reaction_time: 0.9, accuracy: 0.2, aggression: 0.3, movement: 0.4 },
+            BotDifficulty::Normal => BotSkill { reaction_time: 0.55, accuracy: 0.35, aggression: 0.5, movement: 0.65 },
+            BotDifficulty::Hard => BotSkill { reaction_time: 0.35, accuracy: 0.5, aggression: 0.7, movement: 0.85 },
+            BotDifficulty::Expert => BotSkill { reaction_time: 0.2, accuracy: 0.65, aggression: 0.85, movement: 1.0 },
+        }
+    }
+}
+
+/// How a practice bot spends the round
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotRole {
+    /// Hunts the player down
+    Rusher,
+    /// Holds its spawn spot and fights from there
+    Anchor,
+    /// Takes the objective point and holds it
+    Objective,
+}
+
+impl BotRole {
+    pub const ALL: [BotRole; 3] = [BotRole::Rusher, BotRole::Anchor, BotRole::Objective];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BotRole::Rusher => t("practice.role.rusher"),
+            BotRole::Anchor => t("practice.role.anchor"),
+            BotRole::Objective => t("practice.role.objective"),
+        }
+    }
+}
+
+/// What a practice bot knows this frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BotSenses {
+    /// The player, if alive
+    pub player: Option<Vector3>,
+    /// Whether the bot has a clear line to the player
+    pub sees_player: bool,
+    /// Point objective bots head for
+    pub objective: Vector3,
+}
+
+/// What a practice bot wants to do this frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BotIntent {
+    /// Where it wants to stand (the caller checks it against the map)
+    pub move_to: Vector3,
+    /// Fired this frame: whether the shot hits
+    pub shot: Option<bool>,
+}
+
+/// Fighting state of a practice bot
+#[derive(Debug, Clone, PartialEq)]
+struct BotBrain {
+    difficulty: BotDifficulty,
+    role: BotRole,
+    /// Seconds the player has been in sight
+    spotted: f32,
+    /// Seconds until the next shot may go off
+    cooldown: f32,
+    /// Clock driving the strafe
+    strafe: f32,
+    /// Xorshift state for hit rolls (seeded from the id, so a session replays the same)
+    rng: u32,
+}
+
+impl BotBrain {
+    fn roll(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        (self.rng >> 8) as f32 / (1u32 << 24) as f32
+    }
+}
+
+/// Offline bot for the tutorial and practice modes
+///
+/// Target bots don't shoot back: they stand still or patrol between two points, soak up
+/// damage locally (no chain involved) and stand back up after a short delay. Practice bots
+/// (`combatant`) also move and fire at the player, as their difficulty and role dictate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainingBot {
+    pub id: u32,
+    /// Feet position (same convention as `OtherPlayer::position`)
+    pub position: Vector3,
+    /// One end of the patrol (and where a static bot stands)
+    pub home: Vector3,
+    /// Other end of the patrol, None for a static bot
+    pub patrol_to: Option<Vector3>,
+    /// Patrol speed (units per second)
+    pub speed: f32,
+    pub health: f32,
+    /// Seconds until the bot stands back up (0 while alive)
+    respawn_timer: f32,
+    /// Patrol progress, 0.0 (home) to 1.0 (patrol_to)
+    progress: f32,
+    heading_out: bool,
+    /// Whether the bot stands back up after being downed
+    pub respawns: bool,
+    /// Set for practice bots that fight back
+    brain: Option<BotBrain>,
+}
+
+impl TrainingBot {
+    /// A bot that stands still at `position`
+    pub fn stationary(id: u32, position: Vector3) -> Self {
+        Self {
+            id,
+            position,
+            home: position,
+            patrol_to: None,
+            speed: 0.0,
+            health: BOT_MAX_HEALTH,
+            respawn_timer: 0.0,
+            progress: 0.0,
+            heading_out: true,
+            respawns: true,
+            brain: None,
+        }
+    }
+
+    /// A bot that walks back and forth between `from` and `to`
+    pub fn patrolling(id: u32, from: Vector3, to: Vector3, speed: f32) -> Self {
+        Self { patrol_to: Some(to), speed, ..Self::stationary(id, from) }
+    }
+
+    /// A practice bot that fights back, spawning at `position`
+    pub fn combatant(id: u32, position: Vector3, difficulty: BotDifficulty, role: BotRole) -> Self {
+        let skill = difficulty.skill();
+        let brain = BotBrain {
+            difficulty,
+            role,
+            spotted: 0.0,
+            cooldown: 0.0,
+            strafe: id as f32,
+            rng: id.wrapping_mul(2654435761) | 1,
+        };
+        Self { speed: BASE_MOVE_SPEED * (0.5 + 0.5 * skill.movement), brain: Some(brain), ..Self::stationary(id, position) }
+    }
+
+    /// Difficulty and role of a practice bot
+    pub fn profile(&self) -> Option<(BotDifficulty, BotRole)> {
+        self.brain.as_ref().map(|brain| (brain.difficulty, brain.role))
+    }
+
+    /// Stay down once killed (tutorial targets that have to be cleared)
+    pub fn without_respawn(mut self) -> Self {
+        self.respawns = false;
+        self
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.health > 0.0
+    }
+
+    /// Advance the patrol and the respawn timer
+    pub fn update(&mut self, delta: f32) {
+        if !self.is_alive() {
+            if self.respawns {
+                self.respawn_timer -= delta;
+                if self.respawn_timer <= 0.0 {
+                    self.reset();
+                }
+            }
+            return;
+        }
+
+        let Some(to) = self.patrol_to else {
+            return;
+        };
+        let length = (to - self.home).length();
+        if length <= f32::EPSILON {
+            return;
+        }
+
+        let step = self.speed * delta / length;
+        self.progress += if self.heading_out { step } else { -step };
+        if self.progress >= 1.0 {
+            self.progress = 1.0;
+            self.heading_out = false;
+        } else if self.progress <= 0.0 {
+            self.progress = 0.0;
+            self.heading_out = true;
+        }
+        self.position = self.home.lerp(to, self.progress);
+    }
+
+    /// Decide where a practice bot heads and whether it fires (None for target bots and downed ones)
+    pub fn think(&mut self, delta: f32, senses: BotSenses) -> Option<BotIntent> {
+        let position = self.position;
+        let (home, speed) = (self.home, self.speed);
+        let brain = self.brain.as_mut().filter(|_| self.health > 0.0)?;
+        let skill = brain.difficulty.skill();
+
+        // Reaction time runs while the player stays in sight; losing them starts it over
+        let sighted = senses.player.filter(|_| senses.sees_player);
+        brain.spotted = if sighted.is_some() { brain.spotted + delta } else { 0.0 };
+        brain.cooldown = (brain.cooldown - delta).max(0.0);
+        let mut shot = None;
+        if sighted.is_some() && brain.spotted >= skill.reaction_time && brain.cooldown <= 0.0 {
+            brain.cooldown = BOT_FIRE_INTERVAL * (2.0 - skill.aggression);
+            shot = Some(brain.roll() < skill.accuracy);
+        }
+
+        let engage = BOT_ENGAGE_DISTANCE * (2.0 - skill.aggression);
+        let goal = match brain.role {
+            // Rushers always know where the player is; they just stop at fighting distance
+            BotRole::Rusher => senses.player.filter(|&player| position.distance_to(player) > engage).unwrap_or(position),
+            BotRole::Anchor => home,
+            BotRole::Objective => senses.objective,
+        };
+        let mut step = Vector3::zero();
+        let to_goal = Vector3::new(goal.x - position.x, 0.0, goal.z - position.z);
+        if to_goal.length() > BOT_ARRIVE_DISTANCE {
+            step = to_goal.normalized() * speed * delta;
+        }
+        // Side to side while trading shots, wider and quicker for better movers
+        if let Some(player) = sighted {
+            brain.strafe += delta * (1.0 + 2.0 * skill.movement);
+            let to_player = Vector3::new(player.x - position.x, 0.0, player.z - position.z).normalized();
+            let side = Vector3::new(-to_player.z, 0.0, to_player.x);
+            step += side * (brain.strafe.sin() * speed * skill.movement * delta);
+        }
+        Some(BotIntent { move_to: position + step, shot })
+    }
+
+    /// Apply a hit, returning true if it downed the bot
+    pub fn apply_damage(&mut self, damage: f32) -> bool {
+        if !self.is_alive() {
+            return false;
+        }
+        self.health = (self.health - damage).max(0.0);
+        if self.health > 0.0 {
+            return false;
+        }
+        self.respawn_timer = BOT_RESPAWN_DELAY;
+        true
+    }
+
+    /// Full health back at the start of the patrol
+    pub fn reset(&mut self) {
+        self.health = BOT_MAX_HEALTH;
+        self.respawn_timer = 0.0;
+        self.progress = 0.0;
+        self.heading_out = true;
+        self.position = self.home;
+        if let Some(ref mut brain) = self.brain {
+            brain.spotted = 0.0;
+            brain.cooldown = 0.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patrol_bounces_between_ends() {
+        let from = Vector3::new(0.0, 0.0, 0.0);
+        let to = Vector3::new(4.0, 0.0, 0.0);
+        let mut bot = TrainingBot::patrolling(1, from, to, 2.0);
+
+        bot.update(1.0);
+        assert_eq!(bot.position, Vector3::new(2.0, 0.0, 0.0));
+        bot.update(1.5);
+        assert_eq!(bot.position, to);
+        bot.update(1.0);
+        assert_eq!(bot.position, Vector3::new(2.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_downed_bot_respawns_only_if_allowed() {
+        let mut bot = TrainingBot::stationary(1, Vector3::new(1.0, 0.0, 1.0));
+        assert!(!bot.apply_damage(60.0));
+        assert!(bot.apply_damage(60.0));
+        assert!(!bot.apply_damage(60.0));
+
+        bot.update(BOT_RESPAWN_DELAY);
+        assert!(bot.is_alive());
+        assert_eq!(bot.health, BOT_MAX_HEALTH);
+
+        let mut target = TrainingBot::stationary(2, Vector3::new(0.0, 0.0, 0.0)).without_respawn();
+        target.apply_damage(BOT_MAX_HEALTH);
+        target.update(BOT_RESPAWN_DELAY * 2.0);
+        assert!(!target.is_alive());
+    }
+
+    #[test]
+    fn test_combatants_react_before_firing_and_move_by_role() {
+        let player = Vector3::new(30.0, 0.0, 0.0);
+        let senses = BotSenses { player: Some(player), sees_player: true, objective: Vector3::new(0.0, 0.0, 10.0) };
+        let skill = BotDifficulty::Easy.skill();
+
+        let mut rusher = TrainingBot::combatant(1, Vector3::zero(), BotDifficulty::Easy, BotRole::Rusher);
+        let intent = rusher.think(skill.reaction_time * 0.5, senses).unwrap();
+        assert_eq!(intent.shot, None);
+        assert!(intent.move_to.x > 0.0, "rushers close in");
+        assert!(rusher.think(skill.reaction_time * 0.5, senses).unwrap().shot.is_some());
+        assert_eq!(rusher.think(0.01, senses).unwrap().shot, None, "then waits out the fire interval");
+
+        // Out of sight: no shots, and the reaction starts over
+        let hidden = BotSenses { sees_player: false, ..senses };
+        assert_eq!(rusher.think(1.0, hidden).unwrap().shot, None);
+        assert_eq!(rusher.think(skill.reaction_time * 0.5, senses).unwrap().shot, None);
+
+        let mut objective = TrainingBot::combatant(2, Vector3::zero(), BotDifficulty::Expert, BotRole::Objective);
+        assert!(objective.think(0.1, hidden).unwrap().move_to.z > 0.0);
+        let mut anchor = TrainingBot::combatant(3, Vector3::zero(), BotDifficulty::Expert, BotRole::Anchor);
+        assert_eq!(anchor.think(0.1, hidden).unwrap().move_to, Vector3::zero());
+
+        anchor.apply_damage(BOT_MAX_HEALTH);
+        assert_eq!(anchor.think(0.1, senses), None);
+        assert_eq!(TrainingBot::stationary(4, Vector3::zero()).think(0.1, senses), None);
+    }
+}
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use super::movement::MAP_BOUNDARY;
+
+/// Out-of-bounds settings: a dead zone along the world edge nobody may linger in
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OutOfBoundsRules {
+    /// Width of the dead zone inside each world edge (0 disables it)
+    pub dead_zone: f32,
+
+    /// Seconds a player may spend in the dead zone before it starts hurting
+    pub grace_period: f32,
+
+    /// Damage per second once the grace period is up
+    pub damage_per_second: f32,
+}
+
+impl Default for OutOfBoundsRules {
+    fn default() -> Self {
+        Self {
+            dead_zone: 3.0,
+            grace_period: 5.0,
+            damage_per_second: 15.0,
+        }
+    }
+}
+
+/// What the out-of-bounds check says about the player this frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundaryStatus {
+    Inside,
+    /// In the dead zone; it starts hurting after this many seconds
+    Warning { seconds_left: f32 },
+    /// Grace period is up: take this much damage this frame
+    Damaging { damage: f32 },
+}
+
+/// How long the local player has been in the dead zone
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BoundaryTimer {
+    outside_for: f32,
+}
+
+impl BoundaryTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether (x, z) lies in the dead zone (or beyond the world edge)
+    pub fn in_dead_zone(x: f32, z: f32, rules: &OutOfBoundsRules) -> bool {
+        rules.dead_zone > 0.0 && x.abs().max(z.abs()) > MAP_BOUNDARY - rules.dead_zone
+    }
+
+    /// Advance by one frame with the player's feet at (x, z)
+    pub fn update(&mut self, x: f32, z: f32, delta: f32, rules: &OutOfBoundsRules) -> BoundaryStatus {
+        if !Self::in_dead_zone(x, z, rules) {
+            self.outside_for = 0.0;
+            return BoundaryStatus::Inside;
+        }
+
+        let before = self.outside_for;
+        self.outside_for += delta;
+        if self.outside_for <= rules.grace_period {
+            BoundaryStatus::Warning { seconds_left: rules.grace_period - self.outside_for }
+        } else {
+            // Only the part of this frame past the grace period hurts
+            let hurting = self.outside_for - before.max(rules.grace_period);
+            BoundaryStatus::Damaging { damage: hurting * rules.damage_per_second }
+        }
+    }
+
+    /// Start over (respawn, new round)
+    pub fn reset(&mut self) {
+        self.outside_for = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dead_zone_warns_then_hurts_and_resets_on_return() {
+        let rules = OutOfBoundsRules::default();
+        let mut timer = BoundaryTimer::new();
+        let edge = MAP_BOUNDARY - 1.0;
+
+        assert_eq!(timer.update(0.0, 10.0, 1.0, &rules), BoundaryStatus::Inside);
+        assert_eq!(timer.update(edge, 0.0, 2.0, &rules), BoundaryStatus::Warning { seconds_left: 3.0 });
+        // The last 3s of warning, then 1s of damage
+        assert_eq!(timer.update(0.0, -edge, 4.0, &rules), BoundaryStatus::Damaging { damage: rules.damage_per_second });
+        assert_eq!(timer.update(edge, edge, 1.0, &rules), BoundaryStatus::Damaging { damage: rules.damage_per_second });
+
+        // Stepping back in restarts the grace period
+        assert_eq!(timer.update(0.0, 0.0, 0.5, &rules), BoundaryStatus::Inside);
+        assert_eq!(timer.update(edge, 0.0, 1.0, &rules), BoundaryStatus::Warning { seconds_left: 4.0 });
+
+        let disabled = OutOfBoundsRules { dead_zone: 0.0, ..rules };
+        assert_eq!(timer.update(edge, 0.0, 10.0, &disabled), BoundaryStatus::Inside);
+    }
+}
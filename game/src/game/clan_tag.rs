@@ -0,0 +1,34 @@
+/// Longest clan tag the client accepts (see app/src/utils/clan-tag.js)
+const MAX_TAG_LENGTH: usize = 5;
+
+/// Split an on-chain username ("[TAG] name") into its clan tag and plain name
+///
+/// Names without a well-formed tag (2-5 uppercase letters/digits) come back
+/// unchanged with no tag.
+pub fn split_clan_tag(username: &str) -> (Option<&str>, &str) {
+    let parsed = username.strip_prefix('[').and_then(|rest| rest.split_once("] "));
+    match parsed {
+        Some((tag, name))
+            if (2..=MAX_TAG_LENGTH).contains(&tag.len())
+                && tag.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+                && !name.is_empty() =>
+        {
+            (Some(tag), name)
+        }
+        _ => (None, username),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_clan_tag() {
+        assert_eq!(split_clan_tag("[FPS] alice"), (Some("FPS"), "alice"));
+        assert_eq!(split_clan_tag("bob"), (None, "bob"));
+        assert_eq!(split_clan_tag("[toolong] carol"), (None, "[toolong] carol"));
+        assert_eq!(split_clan_tag("[ab] dave"), (None, "[ab] dave"));
+        assert_eq!(split_clan_tag("[X1] "), (None, "[X1] "));
+    }
+}
@@ -0,0 +1,103 @@
+use std::cell::Cell;
+
+/// Motion / flash effect that can be toned down for photosensitive or motion-sick players
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComfortEffect {
+    /// Camera shake from gameplay events
+    ScreenShake,
+    /// Full-screen flash overlays and muzzle flashes
+    Flash,
+    /// Viewmodel bob while walking
+    ViewBob,
+}
+
+impl ComfortEffect {
+    /// Id used by the JS bridge (0 = screen shake, 1 = flash, 2 = view bob)
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(ComfortEffect::ScreenShake),
+            1 => Some(ComfortEffect::Flash),
+            2 => Some(ComfortEffect::ViewBob),
+            _ => None,
+        }
+    }
+}
+
+/// On/off toggle plus intensity (0.0 - 1.0) for one effect
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComfortSetting {
+    pub enabled: bool,
+    pub intensity: f32,
+}
+
+impl Default for ComfortSetting {
+    fn default() -> Self {
+        Self { enabled: true, intensity: 1.0 }
+    }
+}
+
+/// Accessibility multipliers every shake / flash / bob source is scaled by
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Comfort {
+    pub screen_shake: ComfortSetting,
+    pub flash: ComfortSetting,
+    pub view_bob: ComfortSetting,
+}
+
+impl Comfort {
+    pub fn set(&mut self, effect: ComfortEffect, enabled: bool, intensity: f32) {
+        let setting = ComfortSetting { enabled, intensity: intensity.clamp(0.0, 1.0) };
+        match effect {
+            ComfortEffect::ScreenShake => self.screen_shake = setting,
+            ComfortEffect::Flash => self.flash = setting,
+            ComfortEffect::ViewBob => self.view_bob = setting,
+        }
+    }
+
+    /// Multiplier for an effect (0 when toggled off)
+    pub fn scale(&self, effect: ComfortEffect) -> f32 {
+        let setting = match effect {
+            ComfortEffect::ScreenShake => self.screen_shake,
+            ComfortEffect::Flash => self.flash,
+            ComfortEffect::ViewBob => self.view_bob,
+        };
+        if setting.enabled { setting.intensity } else { 0.0 }
+    }
+}
+
+// Global comfort settings (Emscripten is single-threaded)
+thread_local! {
+    static COMFORT: Cell<Comfort> = Cell::new(Comfort::default());
+}
+
+/// Update one effect's toggle and intensity
+pub fn set_comfort(effect: ComfortEffect, enabled: bool, intensity: f32) {
+    COMFORT.with(|comfort| {
+        let mut current = comfort.get();
+        current.set(effect, enabled, intensity);
+        comfort.set(current);
+    });
+}
+
+/// Multiplier to scale an effect by (0.0 - 1.0)
+pub fn comfort_scale(effect: ComfortEffect) -> f32 {
+    COMFORT.with(|comfort| comfort.get().scale(effect))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_effect_scales_to_zero() {
+        let mut comfort = Comfort::default();
+        assert_eq!(comfort.scale(ComfortEffect::Flash), 1.0);
+
+        comfort.set(ComfortEffect::Flash, true, 1.5);
+        assert_eq!(comfort.scale(ComfortEffect::Flash), 1.0);
+        comfort.set(ComfortEffect::ViewBob, true, 0.4);
+        assert_eq!(comfort.scale(ComfortEffect::ViewBob), 0.4);
+        comfort.set(ComfortEffect::ViewBob, false, 0.4);
+        assert_eq!(comfort.scale(ComfortEffect::ViewBob), 0.0);
+    }
+}
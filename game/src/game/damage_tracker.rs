@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use super::WeaponKind;
+
+/// Minimum damage an attacker must deal to a victim to earn an assist
+pub const ASSIST_DAMAGE_THRESHOLD: f32 = 25.0;
+
+/// How long (seconds) damage counts towards an assist
+pub const ASSIST_WINDOW: f64 = 10.0;
+
+/// How long (seconds) after a shot a health drop is attributed to the shooter
+const SHOT_ATTRIBUTION_WINDOW: f64 = 1.0;
+
+/// A single damage contribution against a victim
+#[derive(Debug, Clone)]
+struct DamageRecord {
+    attacker: String,
+    amount: f32,
+    time: f64,
+    weapon: WeaponKind,
+    headshot: bool,
+}
+
+/// Hit metadata reported by the hit pipeline, waiting for the matching health drop
+#[derive(Debug, Clone)]
+struct PendingHit {
+    attacker: String,
+    victim: String,
+    weapon: WeaponKind,
+    headshot: bool,
+    time: f64,
+}
+
+/// Last observed combat-relevant state of a player
+#[derive(Debug, Clone, Copy)]
+struct PlayerSnapshot {
+    health: f32,
+    bullet_count: u8,
+    last_shot_time: f64,
+}
+
+/// Something other than a player that can deal the killing blow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hazard {
+    Fall,
+    /// Stayed in the dead zone along the world edge past its grace period
+    OutOfBounds,
+}
+
+impl Hazard {
+    /// Stable id (kill feed glyph, match logs, game events)
+    pub fn id(self) -> &'static str {
+        match self {
+            Hazard::Fall => "fall",
+            Hazard::OutOfBounds => "out_of_bounds",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "fall" => Some(Hazard::Fall),
+            "out_of_bounds" => Some(Hazard::OutOfBounds),
+            _ => None,
+        }
+    }
+}
+
+/// A resolved kill with everyone who contributed to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct KillEvent {
+    /// Authority of the killer (None if the kill couldn't be attributed)
+    pub killer: Option<String>,
+    pub victim: String,
+    /// Authorities of players who dealt enough damage to earn an assist
+    pub assists: Vec<String>,
+    /// Weapon that dealt the killing blow
+    pub weapon: WeaponKind,
+    /// Whether the killing blow was a headshot
+    pub headshot: bool,
+    /// Set when the environment dealt the killing blow (`killer` is then None)
+    pub hazard: Option<Hazard>,
+    pub timestamp: f64,
+}
+
+impl KillEvent {
+    /// Weapon or hazard id for the kill feed and logs
+    pub fn cause_id(&self) -> &'static str {
+        self.hazard.map_or(self.weapon.id(), Hazard::id)
+    }
+}
+
+/// Result of feeding a player update into the tracker
+#[derive(Debug, Clone, Default)]
+pub struct PlayerObservation {
+    /// The player fired since the last update (bullet count went down)
+    pub fired: bool,
+    /// The player died in this update
+    pub kill: Option<KillEvent>,
+    /// Who the player's health drop in this update was put on, if anyone
+    pub attacker: Option<String>,
+}
+
+/// Tracks damage contributions per attacker so kills can award assists
+///
+/// The game program only reports health, not who dealt the damage, so a
+/// health drop is attributed to a matching hit reported by the hit pipeline
+/// (`note_hit`), falling back to whichever other player fired most recently
+/// (detected from their bullet count going down).
+#[derive(Debug, Default)]
+pub struct DamageTracker {
+    snapshots: HashMap<String, PlayerSnapshot>,
+    records: HashMap<String, Vec<DamageRecord>>,
+    pending_hits: Vec<PendingHit>,
+    assist_counts: HashMap<String, u32>,
+}
+
+impl DamageTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report a hit from the hit pipeline (weapon and headshot flag) for later attribution
+    pub fn note_hit(&mut self, attacker: &str, victim: &str, weapon: WeaponKind, headshot: bool, now: f64) {
+        self.pending_hits.retain(|h| now - h.time <= SHOT_ATTRIBUTION_WINDOW);
+        self.pending_hits.push(PendingHit {
+            attacker: attacker.to_string(),
+            victim: victim.to_string(),
+            weapon,
+            headshot,
+            time: now,
+        });
+    }
+
+    /// Feed a player state update (detects shots, damage and deaths)
+    pub fn observe_player(&mut self, authority: &str, health: f32, bullet_count: u8, now: f64) -> PlayerObservation {
+        let previous = self.snapshots.get(authority).copied();
+
+        let mut snapshot = PlayerSnapshot {
+            health,
+            bullet_count,
+            last_shot_time: previous.map_or(f64::MIN, |p| p.last_shot_time),
+        };
+
+        let mut observation = PlayerObservation::default();
+
+        if let Some(prev) = previous {
+            if bullet_count < prev.bullet_count {
+                snapshot.last_shot_time = now;
+                observation.fired = true;
+            }
+
+            if health < prev.health {
+                let amount = prev.health - health;
+                if let Some(hit) = self.take_pending_hit(authority, now) {
+                    self.record_damage(authority, &hit.attacker, amount, now, hit.weapon, hit.headshot);
+                    observation.attacker = Some(hit.attacker);
+                } else if let Some(attacker) = self.most_recent_shooter(authority, now) {
+                    self.record_damage(authority, &attacker, amount, now, WeaponKind::default(), false);
+                    observation.attacker = Some(attacker);
+                }
+
+                if prev.health > 0.0 && health <= 0.0 {
+                    observation.kill = Some(self.register_kill(authority, now));
+                }
+            }
+        }
+
+        self.snapshots.insert(authority.to_string(), snapshot);
+        observation
+    }
+
+    /// Record damage dealt by an attacker to a victim
+    pub fn record_damage(&mut self, victim: &str, attacker: &str, amount: f32, now: f64, weapon: WeaponKind, headshot: bool) {
+        if victim == attacker || amount <= 0.0 {
+            return;
+        }
+
+        self.records.entry(victim.to_string()).or_default().push(DamageRecord {
+            attacker: attacker.to_string(),
+            amount,
+            time: now,
+            weapon,
+            headshot,
+        });
+    }
+
+    /// Resolve a victim's death into a kill event and award assists
+    pub fn register_kill(&mut self, victim: &str, now: f64) -> KillEvent {
+        self.resolve_death(victim, None, now)
+    }
+
+    /// Resolve a death the environment caused: nobody gets the kill, but recent attackers still get assists
+    pub fn register_hazard_death(&mut self, victim: &str, hazard: Hazard, now: f64) -> KillEvent {
+        self.resolve_death(victim, Some(hazard), now)
+    }
+
+    fn resolve_death(&mut self, victim: &str, hazard: Option<Hazard>, now: f64) -> KillEvent {
+        let records = self.records.remove(victim).unwrap_or_default();
+        let recent: Vec<&DamageRecord> = records.iter().filter(|r| now - r.time <= ASSIST_WINDOW).collect();
+
+        // The last player to deal damage gets the kill (unless a hazard finished them off)
+        let killing_blow = if hazard.is_some() { None } else { recent.last() };
+        let killer = killing_blow.map(|r| r.attacker.clone());
+
+        // Sum damage per attacker, preserving first-hit order
+        let mut totals: Vec<(String, f32)> = Vec::new();
+        for record in &recent {
+            match totals.iter_mut().find(|(attacker, _)| *attacker == record.attacker) {
+                Some((_, total)) => *total += record.amount,
+                None => totals.push((record.attacker.clone(), record.amount)),
+            }
+        }
+
+        let assists: Vec<String> = totals
+            .into_iter()
+            .filter(|(attacker, total)| Some(attacker) != killer.as_ref() && *total >= ASSIST_DAMAGE_THRESHOLD)
+            .map(|(attacker, _)| attacker)
+            .collect();
+
+        for assister in &assists {
+            *self.assist_counts.entry(assister.clone()).or_insert(0) += 1;
+        }
+
+        KillEvent {
+            killer,
+            victim: victim.to_string(),
+            assists,
+            weapon: killing_blow.map_or(WeaponKind::default(), |r| r.weapon),
+            headshot: killing_blow.is_some_and(|r| r.headshot),
+            hazard,
+            timestamp: now,
+        }
+    }
+
+    /// Total assists earned by a player this match
+    pub fn assists_for(&self, authority: &str) -> u32 {
+        self.assist_counts.get(authority).copied().unwrap_or(0)
+    }
+
+    /// Forget all tracked state (new match)
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+        self.records.clear();
+        self.pending_hits.clear();
+        self.assist_counts.clear();
+    }
+
+    /// Take the oldest recent hit reported against a victim
+    fn take_pending_hit(&mut self, victim: &str, now: f64) -> Option<PendingHit> {
+        self.pending_hits.retain(|h| now - h.time <= SHOT_ATTRIBUTION_WINDOW);
+        let index = self.pending_hits.iter().position(|h| h.victim == victim)?;
+        Some(self.pending_hits.remove(index))
+    }
+
+    /// The other player who fired most recently within the attribution window
+    fn most_recent_shooter(&self, victim: &str, now: f64) -> Option<String> {
+        self.snapshots
+            .iter()
+            .filter(|(authority, s)| authority.as_str() != victim && now - s.last_shot_time <= SHOT_ATTRIBUTION_WINDOW)
+            .max_by(|(_, a), (_, b)| a.last_shot_time.total_cmp(&b.last_shot_time))
+            .map(|(authority, _)| authority.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assist_awarded_above_threshold() {
+        let mut tracker = DamageTracker::new();
+        tracker.record_damage("victim", "a", 50.0, 1.0, WeaponKind::Rifle, false);
+        tracker.record_damage("victim", "b", 10.0, 2.0, WeaponKind::Rifle, false);
+        tracker.record_damage("victim", "c", 50.0, 3.0, WeaponKind::Rifle, true);
+
+        let kill = tracker.register_kill("victim", 3.0);
+        assert_eq!(kill.killer.as_deref(), Some("c"));
+        assert_eq!(kill.assists, vec!["a".to_string()]);
+        assert!(kill.headshot);
+        assert_eq!(tracker.assists_for("a"), 1);
+        assert_eq!(tracker.assists_for("b"), 0);
+    }
+
+    #[test]
+    fn test_health_drop_attributed_to_recent_shooter() {
+        let mut tracker = DamageTracker::new();
+        tracker.observe_player("shooter", 100.0, 10, 0.0);
+        tracker.observe_player("victim", 25.0, 10, 0.0);
+
+        assert!(tracker.observe_player("shooter", 100.0, 9, 1.0).fired);
+        let observation = tracker.observe_player("victim", 0.0, 10, 1.2);
+        assert_eq!(observation.attacker.as_deref(), Some("shooter"));
+        let kill = observation.kill.expect("death should produce a kill");
+        assert_eq!(kill.killer.as_deref(), Some("shooter"));
+        assert!(kill.assists.is_empty());
+        assert!(!kill.headshot);
+    }
+
+    #[test]
+    fn test_hazard_death_has_no_killer_but_keeps_assists() {
+        let mut tracker = DamageTracker::new();
+        tracker.record_damage("victim", "a", 60.0, 1.0, WeaponKind::Rifle, true);
+        tracker.record_damage("victim", "b", 10.0, 2.0, WeaponKind::Rifle, false);
+
+        let kill = tracker.register_hazard_death("victim", Hazard::Fall, 3.0);
+        assert_eq!(kill.killer, None);
+        assert_eq!(kill.assists, vec!["a".to_string()]);
+        assert!(!kill.headshot);
+        assert_eq!(kill.cause_id(), "fall");
+    }
+
+    #[test]
+    fn test_pending_hit_carries_headshot() {
+        let mut tracker = DamageTracker::new();
+        tracker.observe_player("victim", 25.0, 10, 0.0);
+        tracker.note_hit("shooter", "victim", WeaponKind::Rifle, true, 0.5);
+
+        let kill = tracker.observe_player("victim", 0.0, 10, 0.8).kill.unwrap();
+        assert_eq!(kill.killer.as_deref(), Some("shooter"));
+        assert!(kill.headshot);
+    }
+}
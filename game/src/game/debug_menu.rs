@@ -1,3 +1,4 @@
+use crate::i18n::{t, tr};
 use crate::map::Map;
 
 /// Debug menu for game development
@@ -19,7 +20,7 @@ impl DebugMenu {
     pub fn new() -> Self {
         Self {
             map_path: String::new(),
-            status_message: "No map loaded".to_string(),
+            status_message: t("debug_menu.no_map").to_string(),
             show_file_browser: false,
             available_maps: Vec::new(),
         }
@@ -80,7 +81,7 @@ impl DebugMenu {
             emscripten_run_script(c_str.as_ptr());
         }
 
-        self.status_message = "Select a map file from your computer...".to_string();
+        self.status_message = t("debug_menu.select_file").to_string();
     }
 
     /// Check if a map file has been loaded from web and load it
@@ -122,7 +123,7 @@ impl DebugMenu {
 
                         match map_result {
                             Ok(loaded_map) => {
-                                self.status_message = format!("Map '{}' loaded successfully!", filename);
+                                self.status_message = tr!("debug_menu.loaded", name = filename);
                                 self.map_path = filename;
 
                                 // Clear the JavaScript variables
@@ -132,7 +133,7 @@ impl DebugMenu {
                                 return Some(loaded_map);
                             }
                             Err(e) => {
-                                self.status_message = format!("Failed to parse map: {}", e);
+                                self.status_message = tr!("debug_menu.parse_failed", error = e);
 
                                 // Clear the JavaScript variables even on error
                                 let clear_js = CString::new("delete Module.loadedWebMapData; delete Module.loadedWebMapName;").unwrap();
@@ -189,24 +190,24 @@ impl DebugMenu {
             loaded_map = Some(web_map);
         }
 
-        ui.window("Debug Menu")
+        ui.window(format!("{}###debug_menu", t("debug_menu.window")))
             .position([window_width / 2.0 - 300.0, window_height / 2.0 - 200.0], imgui::Condition::FirstUseEver)
             .size([600.0, 400.0], imgui::Condition::FirstUseEver)
             .build(|| {
-                ui.text_colored([0.2, 1.0, 0.5, 1.0], "FPS.SO - Debug Menu");
+                ui.text_colored([0.2, 1.0, 0.5, 1.0], t("debug_menu.title"));
                 ui.separator();
                 ui.dummy([0.0, 10.0]);
 
-                ui.text("Load Map:");
+                ui.text(t("debug_menu.load_label"));
                 ui.input_text("##map_path", &mut self.map_path)
-                    .hint("Enter map file path...")
+                    .hint(t("debug_menu.path_hint"))
                     .build();
 
                 ui.same_line();
 
                 #[cfg(target_os = "emscripten")]
                 {
-                    if ui.button("Browse (Web)") {
+                    if ui.button(t("debug_menu.browse_web")) {
                         // Trigger web file selection dialog
                         self.trigger_web_file_picker();
                     }
@@ -214,7 +215,7 @@ impl DebugMenu {
 
                 #[cfg(not(target_os = "emscripten"))]
                 {
-                    if ui.button("Browse") {
+                    if ui.button(t("debug_menu.browse")) {
                         self.scan_maps();
                         self.show_file_browser = !self.show_file_browser;
                     }
@@ -228,11 +229,11 @@ impl DebugMenu {
                         .size([0.0, 150.0])
                         .border(true)
                         .build(|| {
-                            ui.text("Available Maps:");
+                            ui.text(t("debug_menu.available"));
                             ui.separator();
 
                             if self.available_maps.is_empty() {
-                                ui.text_colored([0.7, 0.7, 0.0, 1.0], "No .map files found in 'maps/' directory");
+                                ui.text_colored([0.7, 0.7, 0.0, 1.0], t("debug_menu.none_found"));
                             } else {
                                 for map_path in &self.available_maps.clone() {
                                     // Get just the filename
@@ -257,20 +258,20 @@ impl DebugMenu {
                 ui.dummy([0.0, 10.0]);
 
                 if !can_load {
-                    ui.text_disabled("LOAD MAP");
+                    ui.text_disabled(t("debug_menu.load_map"));
                 } else {
                     let _load_color = ui.push_style_color(imgui::StyleColor::Button, [0.2, 0.8, 0.4, 1.0]);
                     let _load_hover = ui.push_style_color(imgui::StyleColor::ButtonHovered, [0.3, 0.9, 0.5, 1.0]);
 
-                    if ui.button("LOAD MAP") {
+                    if ui.button(t("debug_menu.load_map")) {
                         // Try to load the map
                         match Map::load(&self.map_path) {
                             Ok(map) => {
-                                self.status_message = format!("Map '{}' loaded successfully!", map.name);
+                                self.status_message = tr!("debug_menu.loaded", name = map.name);
                                 loaded_map = Some(map);
                             }
                             Err(e) => {
-                                self.status_message = format!("Failed to load map: {}", e);
+                                self.status_message = tr!("debug_menu.load_failed", error = e);
                             }
                         }
                     }
@@ -284,16 +285,16 @@ impl DebugMenu {
                 ui.dummy([0.0, 10.0]);
 
                 // Status
-                ui.text("Status:");
+                ui.text(t("debug_menu.status"));
                 ui.text_wrapped(&self.status_message);
 
                 ui.dummy([0.0, 20.0]);
 
                 // Instructions
-                ui.text_colored([0.7, 0.7, 0.7, 1.0], "Instructions:");
-                ui.text_colored([0.7, 0.7, 0.7, 1.0], "• Load a map to start playing");
-                ui.text_colored([0.7, 0.7, 0.7, 1.0], "• WASD to move, Mouse to look");
-                ui.text_colored([0.7, 0.7, 0.7, 1.0], "• ESC to return to this menu");
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], t("debug_menu.instructions"));
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], t("debug_menu.help_load"));
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], t("debug_menu.help_move"));
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], t("debug_menu.help_esc"));
             });
 
         loaded_map
@@ -0,0 +1,274 @@
+use raylib::prelude::*;
+use std::collections::HashMap;
+
+/// How far away a spray can be placed (units)
+pub const SPRAY_RANGE: f32 = 4.0;
+
+/// Side length of a spray decal (units)
+pub const SPRAY_SIZE: f32 = 0.9;
+
+/// Minimum time between two sprays by the same player (seconds)
+pub const SPRAY_COOLDOWN: f64 = 10.0;
+
+/// Sprays a team can have on the map at once; the oldest one is painted over
+pub const MAX_SPRAYS_PER_TEAM: usize = 5;
+
+/// Radial menu cursor distance (px) below which nothing is selected
+pub const RADIAL_DEADZONE: f32 = 40.0;
+
+/// Third-person gesture played on a player model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emote {
+    Wave,
+    Point,
+    Salute,
+    Cheer,
+}
+
+impl Emote {
+    /// Id sent in emote events
+    pub fn id(&self) -> &'static str {
+        match self {
+            Emote::Wave => "wave",
+            Emote::Point => "point",
+            Emote::Salute => "salute",
+            Emote::Cheer => "cheer",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "wave" => Some(Emote::Wave),
+            "point" => Some(Emote::Point),
+            "salute" => Some(Emote::Salute),
+            "cheer" => Some(Emote::Cheer),
+            _ => None,
+        }
+    }
+
+    /// Localization key for the radial menu label
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            Emote::Wave => "emote.wave",
+            Emote::Point => "emote.point",
+            Emote::Salute => "emote.salute",
+            Emote::Cheer => "emote.cheer",
+        }
+    }
+
+    /// How long the gesture plays (seconds)
+    pub fn duration(&self) -> f32 {
+        match self {
+            Emote::Wave => 2.0,
+            Emote::Point => 1.5,
+            Emote::Salute => 1.8,
+            Emote::Cheer => 2.2,
+        }
+    }
+
+    /// Arm pose at `progress` (0.0 - 1.0 through the gesture)
+    pub fn arm_pose(&self, progress: f32) -> ArmPose {
+        // Raise quickly, hold, lower over the last 20%
+        let raise = (progress / 0.2).min(1.0).min((1.0 - progress) / 0.2).clamp(0.0, 1.0);
+        let cycle = progress * self.duration() * std::f32::consts::TAU;
+        match self {
+            Emote::Wave => ArmPose { lift: 2.6 * raise, spread: 0.5 + (cycle * 1.5).sin() * 0.35 * raise, both: false },
+            Emote::Point => ArmPose { lift: 1.5 * raise, spread: 0.0, both: false },
+            Emote::Salute => ArmPose { lift: 2.3 * raise, spread: -0.9 * raise, both: false },
+            Emote::Cheer => ArmPose { lift: (2.7 + (cycle * 2.0).sin() * 0.2) * raise, spread: 0.3, both: true },
+        }
+    }
+}
+
+/// Gesture arm orientation relative to the body
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArmPose {
+    /// Forward rotation from hanging down (radians, PI = straight up)
+    pub lift: f32,
+    /// Sideways rotation away from the body (radians, negative = across the chest)
+    pub spread: f32,
+    /// Mirror the pose on the left arm too
+    pub both: bool,
+}
+
+/// Entry in the spray / emote radial menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RadialSlot {
+    Spray,
+    Emote(Emote),
+}
+
+impl RadialSlot {
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            RadialSlot::Spray => "radial.spray",
+            RadialSlot::Emote(emote) => emote.label_key(),
+        }
+    }
+}
+
+/// Radial menu entries, clockwise from the top
+pub const RADIAL_SLOTS: [RadialSlot; 5] = [
+    RadialSlot::Spray,
+    RadialSlot::Emote(Emote::Wave),
+    RadialSlot::Emote(Emote::Point),
+    RadialSlot::Emote(Emote::Salute),
+    RadialSlot::Emote(Emote::Cheer),
+];
+
+/// Screen-space direction (y down) of the middle of a radial slot
+pub fn radial_slot_direction(index: usize) -> Vector2 {
+    let angle = index as f32 / RADIAL_SLOTS.len() as f32 * std::f32::consts::TAU;
+    Vector2::new(angle.sin(), -angle.cos())
+}
+
+/// Slot the radial cursor (offset from the menu center, y down) points at
+pub fn radial_slot_at(cursor: Vector2) -> Option<usize> {
+    if cursor.length() < RADIAL_DEADZONE {
+        return None;
+    }
+    // Clockwise angle from straight up, shifted by half a slot so each slot is centered on its direction
+    let slot_angle = std::f32::consts::TAU / RADIAL_SLOTS.len() as f32;
+    let angle = cursor.x.atan2(-cursor.y).rem_euclid(std::f32::consts::TAU);
+    Some(((angle + slot_angle / 2.0) / slot_angle) as usize % RADIAL_SLOTS.len())
+}
+
+/// A team-colored decal on a wall or the floor
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spray {
+    pub owner: String,
+    /// 1 = team A, 2 = team B
+    pub team: u8,
+    pub position: Vector3,
+    /// Unit normal of the surface it's painted on
+    pub normal: Vector3,
+    /// Local time (seconds) it was placed
+    pub placed_at: f64,
+}
+
+/// All sprays in the match
+///
+/// Every player has at most one spray up (a new one replaces it) and each
+/// team at most `MAX_SPRAYS_PER_TEAM`, so a team can't wallpaper the map.
+#[derive(Debug, Default)]
+pub struct SprayField {
+    sprays: Vec<Spray>,
+}
+
+impl SprayField {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `owner` may spray again at `now`
+    pub fn can_spray(&self, owner: &str, now: f64) -> bool {
+        self.sprays
+            .iter()
+            .find(|s| s.owner == owner)
+            .map_or(true, |s| now - s.placed_at >= SPRAY_COOLDOWN)
+    }
+
+    pub fn add(&mut self, spray: Spray) {
+        self.sprays.retain(|s| s.owner != spray.owner);
+        let team_count = self.sprays.iter().filter(|s| s.team == spray.team).count();
+        if team_count >= MAX_SPRAYS_PER_TEAM {
+            let oldest = self.sprays
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| s.team == spray.team)
+                .min_by(|(_, a), (_, b)| a.placed_at.total_cmp(&b.placed_at))
+                .map(|(i, _)| i);
+            if let Some(i) = oldest {
+                self.sprays.remove(i);
+            }
+        }
+        self.sprays.push(spray);
+    }
+
+    pub fn clear(&mut self) {
+        self.sprays.clear();
+    }
+
+    pub fn sprays(&self) -> &[Spray] {
+        &self.sprays
+    }
+}
+
+/// Gestures currently playing, by player
+#[derive(Debug, Default)]
+pub struct EmoteBoard {
+    playing: HashMap<String, (Emote, f64)>,
+}
+
+impl EmoteBoard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start (or restart) a gesture for `player`
+    pub fn play(&mut self, player: String, emote: Emote, started_at: f64) {
+        self.playing.insert(player, (emote, started_at));
+    }
+
+    /// Gesture `player` is doing at `now` and how far through it they are (0.0 - 1.0)
+    pub fn progress(&self, player: &str, now: f64) -> Option<(Emote, f32)> {
+        let &(emote, started_at) = self.playing.get(player)?;
+        let progress = ((now - started_at) as f32 / emote.duration()).max(0.0);
+        (progress < 1.0).then_some((emote, progress))
+    }
+
+    /// Drop finished gestures
+    pub fn expire(&mut self, now: f64) {
+        self.playing.retain(|_, (emote, started_at)| now - *started_at < emote.duration() as f64);
+    }
+
+    pub fn clear(&mut self) {
+        self.playing.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spray(owner: &str, team: u8, placed_at: f64) -> Spray {
+        Spray {
+            owner: owner.to_string(),
+            team,
+            position: Vector3::zero(),
+            normal: Vector3::new(0.0, 1.0, 0.0),
+            placed_at,
+        }
+    }
+
+    #[test]
+    fn test_sprays_are_limited_per_player_and_team() {
+        let mut field = SprayField::new();
+        field.add(spray("a", 1, 0.0));
+        assert!(!field.can_spray("a", 5.0));
+        assert!(field.can_spray("a", SPRAY_COOLDOWN));
+
+        // A player's new spray replaces their old one
+        field.add(spray("a", 1, 20.0));
+        assert_eq!(field.sprays().len(), 1);
+
+        // Past the team cap the team's oldest spray goes, the other team's stay
+        field.add(spray("enemy", 2, 1.0));
+        for i in 0..MAX_SPRAYS_PER_TEAM {
+            field.add(spray(&format!("p{}", i), 1, 30.0 + i as f64));
+        }
+        assert_eq!(field.sprays().iter().filter(|s| s.team == 1).count(), MAX_SPRAYS_PER_TEAM);
+        assert!(!field.sprays().iter().any(|s| s.owner == "a"));
+        assert!(field.sprays().iter().any(|s| s.owner == "enemy"));
+    }
+
+    #[test]
+    fn test_radial_slots_follow_the_cursor() {
+        assert_eq!(radial_slot_at(Vector2::new(5.0, -5.0)), None);
+        assert_eq!(radial_slot_at(Vector2::new(0.0, -100.0)), Some(0));
+        assert_eq!(radial_slot_at(Vector2::new(-10.0, -100.0)), Some(0));
+        for i in 0..RADIAL_SLOTS.len() {
+            assert_eq!(radial_slot_at(radial_slot_direction(i) * 100.0), Some(i));
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use raylib::prelude::*;
+
+use super::smoke::{simulate_flight, ThrowEvent};
+
+/// Distance (units) past which a flash no longer blinds
+pub const FLASH_RANGE: f32 = 20.0;
+
+/// Blindness from a point-blank flash looked at head-on (seconds)
+pub const FLASH_MAX_DURATION: f32 = 4.0;
+
+/// Exposure below which a flash is shrugged off
+const MIN_EXPOSURE: f32 = 0.05;
+
+/// Share of the blindness spent fully white before it fades
+const WHITEOUT_HOLD: f32 = 0.35;
+
+/// How much longer than the whiteout the ears keep ringing
+const RING_EXTRA: f32 = 1.5;
+
+/// A flash grenade in flight
+///
+/// Like smoke, flight and detonation are simulated deterministically from the throw.
+#[derive(Debug, Clone)]
+pub struct FlashGrenade {
+    pub thrower: String,
+    pub detonation_point: Vector3,
+    pub detonates_at: f64,
+}
+
+impl FlashGrenade {
+    pub fn from_throw(event: &ThrowEvent, blocked: impl Fn(Vector3, Vector3) -> bool) -> Self {
+        let (point, flight_time) = simulate_flight(event, blocked);
+        Self {
+            thrower: event.thrower.clone(),
+            // Burst a little above where it lands so low lips of geometry don't hide it
+            detonation_point: point + Vector3::new(0.0, 0.3, 0.0),
+            detonates_at: event.thrown_at + flight_time as f64,
+        }
+    }
+}
+
+/// How hard a flash at `blast` hits a viewer at `eye` looking along `forward` (0 to 1)
+///
+/// Scales with how directly the viewer faces the blast and how close it is; a blast
+/// behind geometry (`blocked`) or behind the viewer does nothing.
+pub fn flash_exposure(eye: Vector3, forward: Vector3, blast: Vector3, blocked: bool) -> f32 {
+    let to_blast = blast - eye;
+    let distance = to_blast.length();
+    if blocked || distance >= FLASH_RANGE {
+        return 0.0;
+    }
+    let facing = if distance > 0.01 { forward.normalized().dot(to_blast / distance) } else { 1.0 };
+    // Full strength head-on, a quarter side-on, none from behind
+    let view = ((facing + 1.0) / 2.0).powi(2);
+    let proximity = 1.0 - distance / FLASH_RANGE;
+    let exposure = (view * (0.4 + 0.6 * proximity)).clamp(0.0, 1.0);
+    if exposure < MIN_EXPOSURE {
+        0.0
+    } else {
+        exposure
+    }
+}
+
+/// The local player's blindness after being flashed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Blindness {
+    /// Exposure of the flash that caused it (0 to 1)
+    pub strength: f32,
+    pub started_at: f64,
+    /// Seconds until the screen is clear again
+    pub duration: f32,
+}
+
+impl Blindness {
+    pub fn new(strength: f32, now: f64) -> Self {
+        Self { strength, started_at: now, duration: FLASH_MAX_DURATION * strength }
+    }
+
+    /// White overlay opacity at `now` (0 to 1): held, then fading out
+    pub fn whiteout(&self, now: f64) -> f32 {
+        let progress = (now - self.started_at) as f32 / self.duration.max(0.01);
+        if !(0.0..1.0).contains(&progress) {
+            return 0.0;
+        }
+        let fade = ((1.0 - progress) / (1.0 - WHITEOUT_HOLD)).min(1.0);
+        (self.strength * 1.5).min(1.0) * fade
+    }
+
+    /// How muffled world sounds are at `now` (0 to 1); outlasts the whiteout
+    pub fn muffle(&self, now: f64) -> f32 {
+        let progress = (now - self.started_at) as f32 / (self.duration * RING_EXTRA).max(0.01);
+        if !(0.0..1.0).contains(&progress) {
+            return 0.0;
+        }
+        self.strength * (1.0 - progress)
+    }
+
+    pub fn is_finished(&self, now: f64) -> bool {
+        (now - self.started_at) as f32 >= self.duration * RING_EXTRA
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exposure_follows_view_angle_and_cover() {
+        let eye = Vector3::new(0.0, 1.6, 0.0);
+        let blast = Vector3::new(5.0, 1.0, 0.0);
+        let at_blast = Vector3::new(1.0, 0.0, 0.0);
+        let sideways = Vector3::new(0.0, 0.0, 1.0);
+        let away = Vector3::new(-1.0, 0.0, 0.0);
+
+        let head_on = flash_exposure(eye, at_blast, blast, false);
+        let side_on = flash_exposure(eye, sideways, blast, false);
+        assert!(head_on > 0.7);
+        assert!(side_on > 0.0 && side_on < head_on * 0.5);
+        assert_eq!(flash_exposure(eye, away, blast, false), 0.0);
+        assert_eq!(flash_exposure(eye, at_blast, blast, true), 0.0, "behind a wall");
+        assert_eq!(flash_exposure(eye, at_blast, Vector3::new(FLASH_RANGE + 1.0, 1.0, 0.0), false), 0.0);
+
+        // A harder flash whites out longer, and the ringing outlasts the whiteout
+        let strong = Blindness::new(head_on, 10.0);
+        let weak = Blindness::new(side_on, 10.0);
+        assert!(strong.duration > weak.duration);
+        assert!(strong.whiteout(10.1) > 0.9);
+        assert_eq!(strong.whiteout(10.0 + strong.duration as f64), 0.0);
+        assert!(strong.muffle(10.0 + strong.duration as f64) > 0.0);
+        assert!(strong.is_finished(10.0 + (strong.duration * RING_EXTRA) as f64));
+    }
+}
@@ -0,0 +1,119 @@
+use raylib::prelude::*;
+use std::cell::RefCell;
+
+/// TTF used for HUD text (Latin, Greek and Cyrillic coverage for translations and usernames)
+pub const HUD_FONT_PATH: &str = "/assets/fonts/DejaVuSans-Bold.ttf";
+
+/// Pixel sizes the font is rasterized at: small labels, prompts, and big banners.
+/// Text is drawn from the smallest atlas at least as big as the requested size
+/// (downscaling stays crisp, upscaling the default bitmap font did not).
+pub const ATLAS_SIZES: [i32; 3] = [16, 32, 64];
+
+/// Letter spacing as a fraction of the font size
+const LETTER_SPACING: f32 = 0.05;
+
+/// Unicode blocks baked into every atlas; anything else renders as '?'
+const CODEPOINT_RANGES: [(u32, u32); 6] = [
+    (0x0020, 0x007E), // Basic Latin
+    (0x00A0, 0x017F), // Latin-1 Supplement + Latin Extended-A (á, ñ, ü, ł, ő...)
+    (0x0370, 0x03FF), // Greek
+    (0x0400, 0x04FF), // Cyrillic
+    (0x2010, 0x2026), // Dashes, quotes, bullet, ellipsis
+    (0x2190, 0x2193), // Arrows
+];
+
+/// Every character the HUD atlases contain
+pub fn hud_characters() -> String {
+    CODEPOINT_RANGES
+        .iter()
+        .flat_map(|&(first, last)| first..=last)
+        .filter_map(char::from_u32)
+        .collect()
+}
+
+/// Index into `ATLAS_SIZES` to draw `size` px text with
+pub fn atlas_index(size: i32) -> usize {
+    ATLAS_SIZES
+        .iter()
+        .position(|&atlas| atlas >= size)
+        .unwrap_or(ATLAS_SIZES.len() - 1)
+}
+
+/// One font rasterized at each of `ATLAS_SIZES`
+pub struct HudFonts {
+    atlases: Vec<Font>,
+}
+
+// Loaded once after the window opens (Emscripten is single-threaded)
+thread_local! {
+    static FONTS: RefCell<Option<HudFonts>> = const { RefCell::new(None) };
+}
+
+/// Rasterize the HUD font; on failure HUD text keeps using raylib's default font
+pub fn load_hud_fonts(rl: &mut RaylibHandle, thread: &RaylibThread) {
+    let characters = hud_characters();
+    let mut atlases = Vec::with_capacity(ATLAS_SIZES.len());
+    for size in ATLAS_SIZES {
+        match rl.load_font_ex(thread, HUD_FONT_PATH, size, Some(&characters)) {
+            Ok(font) => {
+                // Bilinear so in-between sizes scale down smoothly
+                unsafe {
+                    raylib::ffi::SetTextureFilter(font.texture, raylib::ffi::TextureFilter::TEXTURE_FILTER_BILINEAR as i32);
+                }
+                atlases.push(font);
+            }
+            Err(e) => {
+                log_warn!(Render, "⚠️ Failed to load HUD font {} at {}px: {}", HUD_FONT_PATH, size, e);
+                return;
+            }
+        }
+    }
+    log_info!(Render, "🔤 HUD font loaded ({} glyphs, {:?} px atlases)", characters.chars().count(), ATLAS_SIZES);
+    FONTS.with(|fonts| *fonts.borrow_mut() = Some(HudFonts { atlases }));
+}
+
+/// Draw HUD text with the TTF font (falls back to the default font)
+pub fn draw_hud_text(d: &mut impl RaylibDraw, text: &str, x: i32, y: i32, size: i32, color: Color) {
+    FONTS.with(|fonts| match fonts.borrow().as_ref() {
+        Some(fonts) => {
+            let font = &fonts.atlases[atlas_index(size)];
+            let size = size as f32;
+            d.draw_text_ex(font, text, Vector2::new(x as f32, y as f32), size, size * LETTER_SPACING, color);
+        }
+        None => d.draw_text(text, x, y, size, color),
+    });
+}
+
+/// Width in pixels of HUD text drawn with `draw_hud_text`
+pub fn measure_hud_text(rl: &RaylibHandle, text: &str, size: i32) -> i32 {
+    FONTS.with(|fonts| match fonts.borrow().as_ref() {
+        Some(fonts) => {
+            let font = &fonts.atlases[atlas_index(size)];
+            let size = size as f32;
+            measure_text_ex(font, text, size, size * LETTER_SPACING).x.ceil() as i32
+        }
+        None => rl.measure_text(text, size),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atlas_never_upscales_below_largest() {
+        assert_eq!(atlas_index(12), 0);
+        assert_eq!(atlas_index(16), 0);
+        assert_eq!(atlas_index(24), 1);
+        assert_eq!(atlas_index(64), 2);
+        assert_eq!(atlas_index(96), 2);
+    }
+
+    #[test]
+    fn test_characters_cover_translations() {
+        let characters = hud_characters();
+        for c in "ÁÉÍÑÓÚáéíñóú¿¡°…Жλ".chars() {
+            assert!(characters.contains(c), "{}", c);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use super::movement::MovementTuning;
+use super::boundary::OutOfBoundsRules;
+
+/// Passive health regeneration settings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HealthRegenConfig {
+    /// Whether passive regen is active in this mode
+    pub enabled: bool,
+
+    /// Seconds without taking damage before regen starts
+    pub delay: f32,
+
+    /// Health regenerated per second once regen has started
+    pub rate: f32,
+}
+
+impl Default for HealthRegenConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            delay: 5.0,
+            rate: 10.0,
+        }
+    }
+}
+
+/// Per game-mode gameplay rules
+///
+/// The on-chain game program is authoritative for damage and has no healing
+/// instruction, so regen is applied client-side on top of the last health
+/// value reported by the chain and discarded as soon as the chain reports
+/// new damage (see `Player::apply_server_health`). The HUD shows it as a
+/// predicted segment apart from the confirmed health.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GameRules {
+    /// Game mode identifier (e.g. "team_deathmatch")
+    pub mode: String,
+
+    /// Health regeneration settings for this mode
+    pub health_regen: HealthRegenConfig,
+
+    /// Seconds of round-start freeze (movement and shooting locked), 0 to disable
+    pub freeze_time: f32,
+
+    /// Jump height, air control, coyote time and fall damage
+    pub movement: MovementTuning,
+
+    /// Dead zone along the world edge: warning, grace period and damage
+    pub out_of_bounds: OutOfBoundsRules,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self::for_mode("team_deathmatch")
+    }
+}
+
+impl GameRules {
+    /// Preset rules for a known game mode (unknown modes get regen disabled)
+    pub fn for_mode(mode: &str) -> Self {
+        let health_regen = match mode {
+            "team_deathmatch" | "free_for_all" => HealthRegenConfig {
+                enabled: true,
+                ..HealthRegenConfig::default()
+            },
+            _ => HealthRegenConfig::default(),
+        };
+
+        // Free-for-all gets a shorter freeze since nobody needs to plan with a team
+        let freeze_time = match mode {
+            "free_for_all" => 3.0,
+            _ => 5.0,
+        };
+
+        Self {
+            mode: mode.to_string(),
+            health_regen,
+            freeze_time,
+            movement: MovementTuning::default(),
+            out_of_bounds: OutOfBoundsRules::default(),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Lobby backdrop: a slow camera orbit over the last-played map (or a small showcase diorama)
+//! drawn behind the web lobby panels
+
+use raylib::prelude::*;
+use crate::map::{Map, MapObject, ModelType, WORLD_SIZE};
+use super::GameState;
+
+/// Orbit speed (radians per second): one lap takes a bit over two minutes
+const ORBIT_SPEED: f32 = 0.05;
+
+/// Camera distance from the map center and height above the ground
+const ORBIT_RADIUS: f32 = 30.0;
+const ORBIT_HEIGHT: f32 = 14.0;
+
+/// How far the camera drifts up and down on top of the orbit
+const ORBIT_BOB: f32 = 1.5;
+
+/// Darkening over the scene so the lobby panels stay readable
+const BACKDROP_DIM_ALPHA: u8 = 110;
+
+/// Solana accents for the showcase
+const PURPLE: [u8; 3] = [153, 69, 255];
+const GREEN: [u8; 3] = [0, 242, 148];
+
+impl GameState {
+    /// Map behind the lobby: the last one played, else the showcase diorama
+    pub fn backdrop_map(&self) -> &Map {
+        self.map.as_ref().unwrap_or(&self.showcase)
+    }
+
+    /// Orbit the backdrop map; the sky is cleared to its ambiance by the caller
+    pub fn render_lobby_backdrop(&self, d: &mut RaylibDrawHandle) {
+        let map = self.backdrop_map();
+        let time = d.get_time() as f32;
+        let angle = time * ORBIT_SPEED;
+        let eye = Vector3::new(angle.cos() * ORBIT_RADIUS, ORBIT_HEIGHT + (time * 0.2).sin() * ORBIT_BOB, angle.sin() * ORBIT_RADIUS);
+        let camera = Camera3D::perspective(eye, Vector3::new(0.0, 1.5, 0.0), Vector3::new(0.0, 1.0, 0.0), 50.0);
+
+        {
+            let mut d3d = d.begin_mode3D(camera);
+            d3d.draw_plane(Vector3::new(0.0, -0.01, 0.0), Vector2::new(WORLD_SIZE, WORLD_SIZE), map.ambiance.lit(Color::new(45, 45, 50, 255)));
+            d3d.draw_grid(50, 1.0);
+            Self::draw_boundary_walls(&mut d3d);
+
+            // Like a match: trigger volumes stay hidden
+            for (index, object) in map.objects.iter().enumerate() {
+                if map.trigger(index).is_none() {
+                    object.draw_tinted(&mut d3d, map.shade_object(index, object.distance_to(eye)));
+                }
+            }
+            for glow in &map.glows {
+                if let Some(object) = map.objects.get(glow.object as usize) {
+                    object.draw_glow_halo(&mut d3d, glow.halo(map.ambiance.fog_amount(object.distance_to(eye))));
+                }
+            }
+        }
+
+        let (width, height) = (d.get_screen_width(), d.get_screen_height());
+        d.draw_rectangle(0, 0, width, height, Color::new(8, 6, 16, BACKDROP_DIM_ALPHA));
+    }
+}
+
+/// Glowing plinth in a ring of neon pillars, with a few crates around it
+pub(super) fn showcase_map() -> Map {
+    let mut map = Map::new("Showcase".to_string());
+
+    let mut add = |model_type: ModelType, position: Vector3, scale: Vector3, color: Color, glow: Option<([u8; 3], u8)>| {
+        let mut object = MapObject::new(model_type);
+        object.set_position(position);
+        object.set_scale(scale);
+        object.set_color(color);
+        map.add_object(object);
+        if let Some((color, intensity)) = glow {
+            map.set_object_glow(map.objects.len() - 1, color, intensity);
+        }
+    };
+
+    let stone = Color::new(60, 70, 90, 255);
+    let crate_color = Color::new(110, 100, 80, 255);
+
+    // Plinth with a floating orb over it
+    add(ModelType::Cylinder, Vector3::new(0.0, 0.0, 0.0), Vector3::new(6.0, 0.6, 6.0), stone, None);
+    add(ModelType::Cylinder, Vector3::new(0.0, 0.6, 0.0), Vector3::new(4.0, 0.2, 4.0), Color::new(PURPLE[0], PURPLE[1], PURPLE[2], 255), Some((PURPLE, 60)));
+    add(ModelType::Sphere, Vector3::new(0.0, 3.0, 0.0), Vector3::new(1.5, 1.5, 1.5), Color::new(GREEN[0], GREEN[1], GREEN[2], 255), Some((GREEN, 80)));
+
+    // Pillars capped with green and purple lights in turn
+    for i in 0..8 {
+        let angle = i as f32 / 8.0 * std::f32::consts::TAU;
+        let position = Vector3::new(angle.cos() * 10.0, 2.0, angle.sin() * 10.0);
+        let glow = if i % 2 == 0 { GREEN } else { PURPLE };
+        add(ModelType::Cube, position, Vector3::new(1.0, 4.0, 1.0), stone, None);
+        add(ModelType::Cube, position + Vector3::new(0.0, 2.2, 0.0), Vector3::new(1.2, 0.3, 1.2), stone, Some((glow, 50)));
+    }
+
+    // Crates stacked here and there
+    for (x, z, stack) in [(-15.0, 6.0, 2), (14.0, -8.0, 1), (6.0, 15.0, 2), (-9.0, -14.0, 1)] {
+        for level in 0..stack {
+            add(ModelType::Cube, Vector3::new(x, 0.75 + level as f32 * 1.5, z), Vector3::new(1.5, 1.5, 1.5), crate_color, None);
+        }
+    }
+
+    map
+}
@@ -0,0 +1,399 @@
+//! Bot backfill: the lobby leader's client runs bots in the lobby's empty slots and publishes
+//! their state as player updates, so every client draws, hits and credits them like players.
+//! Damage between a bot and a player is applied on the client that owns the victim (a bot's
+//! player victims take it like hazard damage, the game program never hears about it)
+
+use raylib::prelude::*;
+use crate::game::{backfill_roster, is_bot_authority, BackfillBot, BotSenses, ShakeSource, ThreatKind, WeaponKind};
+use crate::game::bots::{BOT_MAX_HEALTH, BOT_SHOT_DAMAGE};
+use crate::map::ModelType;
+use super::{GameState, OtherPlayer, BulletTrail, emscripten_get_now};
+use super::combat::GUNSHOT_SOUND;
+use super::effects::{DAMAGE_FLASH_DURATION, DAMAGE_FOR_FULL_SHAKE};
+use super::practice::BOT_HEIGHT;
+use super::practice_match::{bot_tracer, slide_bot};
+
+/// How often the leader publishes bot state (seconds)
+const BOT_STATE_INTERVAL: f32 = 0.1;
+
+/// Bots nobody has published for this long are gone (the leader left)
+const BOT_STATE_TIMEOUT: f64 = 3.0;
+
+/// Bots aim at the chest of whoever they fight
+const BOT_AIM_HEIGHT: f32 = 1.2;
+
+/// A backfill bot as the leader publishes it, in the shape of a parsed GamePlayer update
+struct BotState {
+    authority: String,
+    name: String,
+    team: u8,
+    position: Vector3,
+    yaw: f32,
+    health: f32,
+}
+
+impl BotState {
+    fn of(backfill: &BackfillBot) -> Self {
+        Self {
+            authority: backfill.authority.clone(),
+            name: backfill.name.clone(),
+            team: backfill.team,
+            position: backfill.bot.position,
+            yaw: backfill.yaw,
+            health: backfill.bot.health,
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "authority": self.authority,
+            "username": self.name,
+            "team": self.team,
+            "positionX": self.position.x,
+            "positionY": self.position.y,
+            "positionZ": self.position.z,
+            "rotationY": self.yaw,
+            "health": self.health,
+            "isAlive": self.health > 0.0,
+        })
+    }
+
+    fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let authority = value.get("authority")?.as_str().filter(|id| is_bot_authority(id))?;
+        let coordinate = |key: &str| value.get(key).and_then(|v| v.as_f64()).map(|v| v as f32);
+        Some(Self {
+            authority: authority.to_string(),
+            name: value.get("username")?.as_str()?.to_string(),
+            team: value.get("team")?.as_u64()? as u8,
+            position: Vector3::new(coordinate("positionX")?, coordinate("positionY")?, coordinate("positionZ")?),
+            yaw: value.get("rotationY").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32,
+            health: value.get("health").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32,
+        })
+    }
+}
+
+/// Someone a backfill bot can fight
+struct BotTarget {
+    authority: String,
+    team: u8,
+    position: Vector3,
+}
+
+impl GameState {
+    /// Fill `open` empty slots on teams 1 and 2 with bots run by this client (the lobby leader's)
+    pub fn set_bot_backfill(&mut self, open: [usize; 2]) {
+        self.backfill = backfill_roster(open);
+        self.backfill_sync_timer = 0.0;
+        log_info!(Game, "🤖 Bot backfill: {} bots", self.backfill.len());
+        self.place_backfill_bots();
+    }
+
+    /// Send every backfill bot home to one of its team's spawn points
+    pub(super) fn place_backfill_bots(&mut self) {
+        let Some(ref map) = self.map else {
+            return;
+        };
+        for (i, backfill) in self.backfill.iter_mut().enumerate() {
+            let model = if backfill.team == 1 { ModelType::SpawnPointBlue } else { ModelType::SpawnPointRed };
+            let spawns: Vec<Vector3> = map.objects.iter()
+                .filter(|obj| obj.model_type == model)
+                .map(|obj| obj.get_position())
+                .collect();
+            // Same fallback as call_respawn when the map has no spawn points for the team
+            let fallback = Vector3::new(if backfill.team == 1 { -10.0 } else { 10.0 }, 0.0, 0.0);
+            let spawn = spawns.get(i % spawns.len().max(1)).copied().unwrap_or(fallback);
+            // Bots sharing a spawn point stand side by side
+            let row = (i / spawns.len().max(1)) as f32;
+            backfill.bot.home = spawn + Vector3::new(row * 1.5, 0.0, 0.0);
+            backfill.bot.reset();
+        }
+    }
+
+    /// Run the leader's bots and publish them; everyone else drops bots whose leader went quiet
+    pub(super) fn update_backfill(&mut self, delta: f32) {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        if self.backfill.is_empty() {
+            self.other_players.retain(|p| !is_bot_authority(&p.authority) || now - p.last_update_time < BOT_STATE_TIMEOUT);
+            return;
+        }
+        let Some(index) = self.map_index.as_ref() else {
+            return;
+        };
+        let objective = self.map.as_ref().and_then(|map| map.tag_anchor("objective")).unwrap_or_else(Vector3::zero);
+
+        // Players and the other team's bots are fair game
+        let local = self.get_current_ephemeral_key();
+        let mut targets: Vec<BotTarget> = self.other_players.iter()
+            .filter(|p| p.is_alive && !is_bot_authority(&p.authority))
+            .map(|p| BotTarget { authority: p.authority.clone(), team: p.team.parse().unwrap_or(0), position: p.position })
+            .collect();
+        if let Some(player) = self.player.as_ref().filter(|p| !p.is_dead) {
+            targets.push(BotTarget { authority: local.clone(), team: self.current_player_team, position: player.position });
+        }
+        targets.extend(self.backfill.iter().filter(|b| b.bot.is_alive()).map(|b| BotTarget {
+            authority: b.authority.clone(),
+            team: b.team,
+            position: b.bot.position,
+        }));
+
+        let aim = Vector3::new(0.0, BOT_AIM_HEIGHT, 0.0);
+        let mut shots = Vec::new();
+        for backfill in &mut self.backfill {
+            backfill.bot.update(delta);
+            let bot = &mut backfill.bot;
+            let eye = bot.position + Vector3::new(0.0, BOT_HEIGHT * 0.9, 0.0);
+
+            // The nearest enemy in sight, else the nearest one
+            let target = targets.iter()
+                .filter(|t| t.team != backfill.team)
+                .map(|t| (t, !index.is_line_blocked(eye, t.position + aim)))
+                .min_by(|(a, a_seen), (b, b_seen)| {
+                    b_seen.cmp(a_seen).then(a.position.distance_to(bot.position).total_cmp(&b.position.distance_to(bot.position)))
+                });
+            let senses = BotSenses {
+                player: target.map(|(t, _)| t.position),
+                sees_player: target.is_some_and(|(_, seen)| seen),
+                objective,
+            };
+            let Some(intent) = bot.think(delta, senses) else {
+                continue;
+            };
+
+            let from = bot.position;
+            bot.position = slide_bot(index, from, intent.move_to);
+            let facing = match target {
+                Some((t, true)) => t.position - bot.position,
+                _ => bot.position - from,
+            };
+            if facing.x != 0.0 || facing.z != 0.0 {
+                backfill.yaw = facing.z.atan2(facing.x);
+            }
+
+            let (Some(hit), Some((target, true))) = (intent.shot, target) else {
+                continue;
+            };
+            let muzzle = bot.position + Vector3::new(0.0, BOT_HEIGHT * 0.8, 0.0);
+            shots.push((bot_tracer(muzzle, target.position + aim, hit), hit.then(|| (backfill.authority.clone(), target.authority.clone()))));
+        }
+
+        for ((start, end), hit) in shots {
+            self.bullet_trails.push(BulletTrail { start, end, timer: 0.1 });
+            self.play_world_sound(GUNSHOT_SOUND, start, 0.6);
+            let Some((attacker, victim)) = hit else {
+                continue;
+            };
+            if victim == local {
+                self.take_bot_hit(&attacker, BOT_SHOT_DAMAGE);
+            } else if is_bot_authority(&victim) {
+                self.damage_backfill_bot(&victim, &attacker, BOT_SHOT_DAMAGE, WeaponKind::default(), false);
+            } else {
+                self.send_bot_hit(&victim, &attacker, BOT_SHOT_DAMAGE, WeaponKind::default(), false);
+            }
+        }
+
+        self.mirror_backfill_bots(now);
+        self.backfill_sync_timer += delta;
+        if self.backfill_sync_timer >= BOT_STATE_INTERVAL {
+            self.backfill_sync_timer = 0.0;
+            self.publish_backfill_bots();
+        }
+    }
+
+    /// The leader's bots go into other_players like everyone else, so drawing and hit tests just work
+    fn mirror_backfill_bots(&mut self, now: f64) {
+        let states: Vec<BotState> = self.backfill.iter().map(BotState::of).collect();
+        for state in states {
+            self.upsert_bot_player(&state, now);
+            // Our own bots are exactly where we simulate them
+            if let Some(player) = self.other_players.iter_mut().find(|p| p.authority == state.authority) {
+                player.position = state.position;
+                player.velocity = Vector3::zero();
+            }
+        }
+    }
+
+    fn publish_backfill_bots(&self) {
+        let bots: Vec<serde_json::Value> = self.backfill.iter().map(|b| BotState::of(b).to_json()).collect();
+        self.send_player_updates(&bots);
+    }
+
+    /// Add or move a bot in other_players, smoothing it like a remote player
+    fn upsert_bot_player(&mut self, state: &BotState, now: f64) {
+        let BotState { ref authority, ref name, team, position, yaw, health } = *state;
+        let rotation = Vector3::new(0.0, yaw, 0.0);
+        let team = team.to_string();
+        if let Some(existing) = self.other_players.iter_mut().find(|p| &p.authority == authority) {
+            let time_delta = now - existing.last_update_time;
+            if time_delta > 0.001 {
+                existing.velocity = (position - existing.target_position) / time_delta as f32;
+            }
+            existing.target_position = position;
+            existing.target_rotation = rotation;
+            existing.is_alive = health > 0.0;
+            existing.health = health;
+            existing.last_update_time = now;
+            return;
+        }
+        log_info!(Game, "➕ Added backfill bot: {} ({})", name, authority);
+        self.match_log.note_player(authority, name, &team);
+        self.other_players.push(OtherPlayer {
+            authority: authority.clone(),
+            username: name.clone(),
+            team,
+            position,
+            rotation,
+            is_alive: health > 0.0,
+            health,
+            sliding: false,
+            target_position: position,
+            target_rotation: rotation,
+            velocity: Vector3::zero(),
+            last_update_time: now,
+        });
+    }
+
+    /// The leader published where one of its bots is
+    ///
+    /// The update stays in the player update map, so it's seen every frame until the next one
+    /// arrives: only apply it once, and not at all once it's stale (the leader left).
+    pub(super) fn apply_bot_update(&mut self, update: &serde_json::Value, received_at: Option<f64>) {
+        // The leader already has its own bots
+        if !self.backfill.is_empty() {
+            return;
+        }
+        let Some(state) = BotState::from_json(update) else {
+            log_warn!(Game, "⚠️ Ignoring malformed bot update: {}", update);
+            return;
+        };
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        let received_at = received_at.unwrap_or(now);
+        if now - received_at >= BOT_STATE_TIMEOUT {
+            return;
+        }
+        let seen = self.other_players.iter().any(|p| p.authority == state.authority && p.last_update_time >= received_at);
+        if !seen {
+            self.upsert_bot_player(&state, received_at);
+        }
+    }
+
+    /// Our shot landed on a backfill bot: the leader applies it (straight away if that's us)
+    pub(super) fn hit_backfill_bot(&mut self, bot: &str, damage: f32, weapon: WeaponKind, headshot: bool) {
+        let local = self.get_current_ephemeral_key();
+        if self.backfill.iter().any(|b| b.authority == bot) {
+            self.damage_backfill_bot(bot, &local, damage, weapon, headshot);
+        } else {
+            self.send_bot_hit(bot, &local, damage, weapon, headshot);
+        }
+    }
+
+    fn send_bot_hit(&self, target: &str, attacker: &str, damage: f32, weapon: WeaponKind, headshot: bool) {
+        self.send_game_event(serde_json::json!({
+            "type": "botHit",
+            "target": target,
+            "attacker": attacker,
+            "damage": damage,
+            "weapon": weapon.id(),
+            "headshot": headshot,
+        }));
+    }
+
+    /// A hit between a bot and a player, for whichever of them this client owns
+    pub(super) fn apply_bot_hit_event(&mut self, event: &serde_json::Value) {
+        let target = event.get("target").and_then(|v| v.as_str());
+        let attacker = event.get("attacker").and_then(|v| v.as_str());
+        let damage = event.get("damage").and_then(|v| v.as_f64());
+        let (Some(target), Some(attacker), Some(damage)) = (target, attacker, damage) else {
+            log_warn!(Game, "⚠️ Ignoring malformed bot hit event: {}", event);
+            return;
+        };
+        let weapon = event.get("weapon").and_then(|v| v.as_str()).and_then(WeaponKind::from_id).unwrap_or_default();
+        let headshot = event.get("headshot").and_then(|v| v.as_bool()).unwrap_or(false);
+
+        if target == self.get_current_ephemeral_key() {
+            // Only bots hurt players this way
+            if is_bot_authority(attacker) {
+                self.take_bot_hit(attacker, damage as f32);
+            }
+        } else if self.backfill.iter().any(|b| b.authority == target) {
+            self.damage_backfill_bot(target, attacker, damage as f32, weapon, headshot);
+        }
+    }
+
+    /// Hurt one of our bots; downing it is announced to everyone as a bot kill
+    fn damage_backfill_bot(&mut self, bot: &str, attacker: &str, damage: f32, weapon: WeaponKind, headshot: bool) {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        let Some(backfill) = self.backfill.iter_mut().find(|b| b.authority == bot) else {
+            return;
+        };
+        if !backfill.bot.is_alive() {
+            return;
+        }
+        self.damage_tracker.record_damage(bot, attacker, damage, now, weapon, headshot);
+        if backfill.bot.apply_damage(damage) {
+            self.send_bot_kill(bot, attacker, weapon, headshot);
+            self.announce_bot_kill(bot, now);
+        }
+    }
+
+    /// A bot shot the local player: hazard-style damage, with the bot as the attacker
+    fn take_bot_hit(&mut self, attacker: &str, damage: f32) {
+        if let Some(source) = self.threat_source(attacker) {
+            self.note_threat(ThreatKind::Damage, source, damage);
+        }
+        let local = self.get_current_ephemeral_key();
+        let Some(player) = self.player.as_mut().filter(|p| !p.is_dead) else {
+            return;
+        };
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        player.apply_hazard_damage(damage, now);
+        self.damage_flash_timer = DAMAGE_FLASH_DURATION;
+        self.shake.add(ShakeSource::Damage, damage / DAMAGE_FOR_FULL_SHAKE);
+        self.damage_tracker.record_damage(&local, attacker, damage, now, WeaponKind::default(), false);
+
+        if !player.died_from_hazard() {
+            return;
+        }
+        // Respawns like a hazard death once the timer runs out (see process_single_player_update)
+        player.is_dead = true;
+        player.death_timestamp = now;
+        self.update_death_state_js(true, now);
+        self.drop_weapon_on_death();
+
+        self.send_bot_kill(&local, attacker, WeaponKind::default(), false);
+        self.announce_bot_kill(&local, now);
+    }
+
+    fn send_bot_kill(&self, victim: &str, killer: &str, weapon: WeaponKind, headshot: bool) {
+        self.send_game_event(serde_json::json!({
+            "type": "botKill",
+            "victim": victim,
+            "killer": killer,
+            "weapon": weapon.id(),
+            "headshot": headshot,
+        }));
+    }
+
+    /// A bot went down or downed a player on another client
+    pub(super) fn apply_bot_kill_event(&mut self, event: &serde_json::Value) {
+        let victim = event.get("victim").and_then(|v| v.as_str());
+        let killer = event.get("killer").and_then(|v| v.as_str());
+        let (Some(victim), Some(killer)) = (victim, killer) else {
+            log_warn!(Game, "⚠️ Ignoring malformed bot kill event: {}", event);
+            return;
+        };
+        let weapon = event.get("weapon").and_then(|v| v.as_str()).and_then(WeaponKind::from_id).unwrap_or_default();
+        let headshot = event.get("headshot").and_then(|v| v.as_bool()).unwrap_or(false);
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        // We only saw the killing blow; earlier hits on the victim still count for assists
+        self.damage_tracker.record_damage(victim, killer, BOT_MAX_HEALTH, now, weapon, headshot);
+        self.announce_bot_kill(victim, now);
+    }
+
+    /// Kill feed and match log entry for a death involving a backfill bot
+    fn announce_bot_kill(&mut self, victim: &str, now: f64) {
+        let kill = self.damage_tracker.register_kill(victim, now);
+        self.match_log.record_kill(now, &kill);
+        let local = self.get_current_ephemeral_key();
+        self.publish_kill_event(&kill, &local);
+    }
+}
@@ -0,0 +1,110 @@
+//! Highlight capture: F12 screenshot (framebuffer readback -> PNG) and F8 "save last 15 seconds" replay clip
+
+use raylib::prelude::*;
+use crate::game::ReplayPlayer;
+use crate::audio::{play_ui_sound, UiSound};
+use super::{GameState, emscripten_get_now};
+
+impl GameState {
+    /// F12 queues a screenshot for the end of this frame's render, F8 saves the replay clip
+    pub(super) fn update_capture_input(&mut self, rl: &RaylibHandle) {
+        if rl.is_key_pressed(KeyboardKey::KEY_F12) {
+            self.screenshot_requested = true;
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_F8) {
+            self.save_replay_clip();
+        }
+    }
+
+    /// Snapshot every player into the rolling replay buffer at `REPLAY_TICK_RATE`
+    pub(super) fn record_replay_frame(&mut self) {
+        if self.current_game_pubkey.is_none() {
+            return;
+        }
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        if !self.replay.frame_due(now) {
+            return;
+        }
+
+        let mut players: Vec<ReplayPlayer> = self.other_players.iter()
+            .map(|p| ReplayPlayer {
+                authority: p.authority.clone(),
+                username: p.username.clone(),
+                team: p.team.clone(),
+                x: p.position.x,
+                y: p.position.y,
+                z: p.position.z,
+                yaw: p.rotation.y,
+                pitch: p.rotation.x,
+                health: p.health,
+                alive: p.is_alive,
+            })
+            .collect();
+        // Spectators have no local player
+        if let Some(player) = &self.player {
+            let authority = self.get_current_ephemeral_key();
+            players.push(ReplayPlayer {
+                username: self.match_log.username(&authority).unwrap_or("You").to_string(),
+                authority,
+                team: self.current_player_team.to_string(),
+                x: player.position.x,
+                y: player.position.y,
+                z: player.position.z,
+                yaw: player.yaw,
+                pitch: player.pitch,
+                health: player.health,
+                alive: !player.is_dead,
+            });
+        }
+        self.replay.record(now, players);
+    }
+
+    /// Download the last `CLIP_DURATION` seconds as a replay file
+    fn save_replay_clip(&mut self) {
+        let Some(game) = self.current_game_pubkey.clone() else {
+            return;
+        };
+        if self.replay.is_empty() {
+            return;
+        }
+
+        self.captures_taken += 1;
+        let filename = format!("clip-{}-{}.replay.json", game.get(..8).unwrap_or(&game), self.captures_taken);
+        let map = self.map.as_ref().map(|map| map.name.as_str()).unwrap_or("");
+        let replay = self.replay.export(&game, map);
+        match crate::net::download_file(&filename, "application/json", replay.as_bytes()) {
+            Ok(()) => {
+                log_info!(Render, "🎬 Saved last {:.0}s as {}", self.replay.duration(), filename);
+                play_ui_sound(UiSound::Click);
+            }
+            Err(e) => log_warn!(Render, "⚠️ Failed to save clip: {}", e),
+        }
+    }
+
+    /// Read back the finished frame and download it as a PNG (call after `render`, before the frame ends)
+    pub fn capture_pending_screenshot(&mut self, rl: &RaylibHandle, thread: &RaylibThread) {
+        if !self.screenshot_requested {
+            return;
+        }
+        self.screenshot_requested = false;
+
+        self.captures_taken += 1;
+        let filename = format!("screenshot-{}.png", self.captures_taken);
+        // raylib only encodes PNGs to a file: go through the (in-memory on the web) temp folder
+        let path = std::env::temp_dir().join(&filename);
+        let image = rl.load_image_from_screen(thread);
+        if !image.export_image(&path.to_string_lossy()) {
+            log_warn!(Render, "⚠️ Failed to encode screenshot");
+            return;
+        }
+        let result = std::fs::read(&path).and_then(|png| crate::net::download_file(&filename, "image/png", &png));
+        let _ = std::fs::remove_file(&path);
+        match result {
+            Ok(()) => {
+                log_info!(Render, "📸 Screenshot saved: {}", filename);
+                play_ui_sound(UiSound::Click);
+            }
+            Err(e) => log_warn!(Render, "⚠️ Failed to save screenshot: {}", e),
+        }
+    }
+}
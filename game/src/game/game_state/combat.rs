@@ -0,0 +1,852 @@
+//! Combat: shooting, ammo/reload state and death/respawn flow
+
+use raylib::prelude::*;
+use crate::game::{KillEvent, Player, ShakeSource, WeaponKind, BASE_FOV, aggregate_pellet_damage, capsule_height, is_bot_authority};
+use crate::audio::{mixer, play_ui_sound, AudioChannel, UiSound};
+use super::{GameState, BulletTrail, OtherPlayer, PlayerHit, emscripten_run_script, emscripten_run_script_string, emscripten_get_now};
+use super::practice::MAGAZINE_SIZE;
+
+/// Gunshot sample (played on the effects channel)
+pub(super) const GUNSHOT_SOUND: &str = "/assets/gun/audio/submachinegun-gunshot.mp3";
+
+/// Heavy sniper report
+const SNIPER_SHOT_SOUND: &str = "/assets/gun/audio/sniper-gunshot.wav";
+
+/// Shotgun blast
+const SHOTGUN_SHOT_SOUND: &str = "/assets/gun/audio/shotgun-gunshot.wav";
+
+/// Remaining rounds at which the low-ammo warning plays
+pub(super) const LOW_AMMO_THRESHOLD: u8 = 3;
+
+/// Trigger click when firing with an empty magazine
+const DRY_FIRE_SOUND: &str = "/assets/gun/audio/dry-fire.wav";
+
+/// Played when equipping a different weapon
+const WEAPON_SWITCH_SOUND: &str = "/assets/gun/audio/weapon-switch.wav";
+
+/// Reload sound cues, keyed to the reload animation stages in `draw_gun_viewmodel`
+/// (magazine ejects at 0.25, locks in at 0.6, charging handle at 0.75)
+const RELOAD_SOUND_CUES: [(f32, &str); 3] = [
+    (0.25, "/assets/gun/audio/reload-mag-out.wav"),
+    (0.6, "/assets/gun/audio/reload-mag-in.wav"),
+    (0.75, "/assets/gun/audio/reload-charging-handle.wav"),
+];
+
+impl GameState {
+    /// Handle shooting - play sound and trigger visual effects
+    pub fn shoot(&mut self) {
+        // Respect the equipped weapon's fire rate
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        if now - self.last_shot_time < self.current_weapon.fire_interval() {
+            return;
+        }
+
+        // Check bullet count first
+        let bullet_count = self.bullet_count();
+        
+        // If no bullets, show reload prompt and prevent shooting
+        if bullet_count == 0 {
+            self.show_reload_prompt = true;
+            mixer::play_sound(DRY_FIRE_SOUND, AudioChannel::Effects, 0.8);
+            return; // Don't shoot
+        }
+
+        self.last_shot_time = now;
+        if self.offline {
+            self.current_bullet_count -= 1;
+        }
+
+        // Play the gunshot via Web Audio (more reliable than raylib's audio system for WASM)
+        let gunshot = match self.current_weapon {
+            WeaponKind::Rifle => GUNSHOT_SOUND,
+            WeaponKind::Sniper => SNIPER_SHOT_SOUND,
+            WeaponKind::Shotgun => SHOTGUN_SHOT_SOUND,
+        };
+        mixer::play_sound(gunshot, AudioChannel::Effects, 1.0);
+
+        // Warn once per magazine when ammo runs low
+        if bullet_count - 1 == LOW_AMMO_THRESHOLD {
+            play_ui_sound(UiSound::LowAmmo);
+        }
+
+        // Our own shots count as full-intensity combat for the music
+        self.combat_noise += 1.0;
+
+        // Create bullet trails from gun muzzle
+        let mut local_hits = Vec::new();
+        let mut bot_hits = Vec::new();
+        if let Some(ref player) = self.player {
+            // Calculate gun muzzle position (in front of camera), including scope sway
+            let (sway_yaw, sway_pitch) = self.scope.sway();
+            let yaw_rad = (player.yaw + sway_yaw).to_radians();
+            let pitch_rad = (player.pitch + sway_pitch).to_radians();
+
+            // Direction the gun is pointing
+            let direction = Vector3::new(
+                yaw_rad.cos() * pitch_rad.cos(),
+                pitch_rad.sin(),
+                yaw_rad.sin() * pitch_rad.cos(),
+            );
+
+            // Right vector for gun offset
+            let right = Vector3::new(
+                (yaw_rad + 90.0_f32.to_radians()).cos(),
+                0.0,
+                (yaw_rad + 90.0_f32.to_radians()).sin(),
+            );
+
+            // Up vector
+            let up = right.cross(direction).normalized();
+
+            // Camera/eye position
+            let camera_pos = Vector3::new(
+                player.position.x,
+                player.position.y + player.eye_height(),
+                player.position.z,
+            );
+
+            // Gun muzzle position (in front and to the right, at barrel end)
+            let muzzle_pos = camera_pos + direction * 0.8 + right * 0.35 + up * -0.3 + direction * 0.6;
+
+            // One independent raycast per pellet (a single ray for non-spread weapons)
+            let max_distance = 100.0; // Maximum bullet travel distance
+            for ray_direction in self.current_weapon.pellet_directions(direction, now as f32) {
+                let mut hit_pos = muzzle_pos + ray_direction * max_distance;
+
+                // Map geometry that stops bullets ends the ray (non-solid and player-clip objects don't)
+                let wall = self.map_index.as_ref().and_then(|index| index.raycast(Ray::new(camera_pos, ray_direction), max_distance));
+                let wall_distance = wall.map_or(max_distance, |(point, _)| point.distance_to(camera_pos));
+                if let Some((point, _)) = wall {
+                    hit_pos = point;
+                }
+
+                // Client-side hit test against other players (from the eye, like the crosshair)
+                // The chain decides the actual damage; this only supplies kill feed metadata
+                if let Some(hit) = Self::hit_test_players(camera_pos, ray_direction, &self.other_players).filter(|hit| hit.distance < wall_distance) {
+                    hit_pos = camera_pos + ray_direction * hit.distance;
+                    local_hits.push((hit, hit_pos));
+                } else if let Some((index, distance, headshot)) = Self::hit_test_bots(camera_pos, ray_direction, &self.bots).filter(|(_, distance, _)| *distance < wall_distance) {
+                    // Offline sessions: damage is applied locally to training bots
+                    hit_pos = camera_pos + ray_direction * distance;
+                    bot_hits.push((index, self.current_weapon.damage_at(distance), hit_pos, headshot));
+                }
+
+                // Create bullet trail
+                self.bullet_trails.push(BulletTrail {
+                    start: muzzle_pos,
+                    end: hit_pos,
+                    timer: 0.1, // Trail visible for 0.1 seconds
+                });
+            }
+
+            log_info!(Game, "🔫 Bang! {:?} fired from {:?}", self.current_weapon, muzzle_pos);
+        }
+
+        // Bot damage is summed per bot (shotgun pellets) so each gets a single damage number
+        let mut bot_damage: Vec<(usize, f32, bool)> = Vec::new();
+        for &(index, damage, impact, headshot) in &bot_hits {
+            self.spawn_hit_effect(impact, headshot);
+            match bot_damage.iter_mut().find(|(i, _, _)| *i == index) {
+                Some(entry) => {
+                    entry.1 += damage;
+                    entry.2 |= headshot;
+                }
+                None => bot_damage.push((index, damage, headshot)),
+            }
+        }
+        if self.offline {
+            self.damage_bots(&bot_damage, now);
+        }
+
+        // Remember the hits (weapon + headshot) so the kill feed can attribute the health drop
+        let local_authority = self.get_current_ephemeral_key();
+        for (hit, impact) in &local_hits {
+            self.spawn_hit_effect(*impact, hit.headshot);
+        }
+        // Backfill bots aren't on-chain: their damage goes to the lobby leader, not the shoot instruction
+        let (backfill_damage, damage_by_target): (Vec<_>, Vec<_>) = aggregate_pellet_damage(
+            local_hits.iter().map(|(hit, _)| (hit.authority.as_str(), self.current_weapon.damage_at(hit.distance))),
+        )
+        .into_iter()
+        .partition(|(target, _)| is_bot_authority(target));
+        for (target, damage) in &backfill_damage {
+            let headshot = local_hits.iter().any(|(hit, _)| &hit.authority == target && hit.headshot);
+            self.hit_backfill_bot(target, *damage, self.current_weapon, headshot);
+        }
+        for (target, damage) in &damage_by_target {
+            let headshot = local_hits.iter().any(|(hit, _)| &hit.authority == target && hit.headshot);
+            log_info!(Game, "🎯 Hit {} for {:.0}{}", target, damage, if headshot { " (headshot)" } else { "" });
+            self.damage_tracker.note_hit(&local_authority, target, self.current_weapon, headshot, now);
+        }
+
+        // The shoot instruction takes a single damage value: report everything that
+        // landed on the most-hit target (a miss reports one point-blank hit)
+        let shot_damage = damage_by_target
+            .first()
+            .map(|(_, damage)| damage.round().min(u8::MAX as f32) as u8)
+            .unwrap_or_else(|| self.current_weapon.damage());
+
+        // Call blockchain shooting function
+        if let Some(ref game_pubkey) = self.current_game_pubkey {
+            self.call_blockchain_shoot(game_pubkey, shot_damage);
+        }
+
+        // Trigger muzzle flash (lasts 0.05 seconds)
+        self.muzzle_flash_timer = 0.05;
+
+        // Trigger screen flash (lasts 0.1 seconds)
+        self.screen_flash_timer = 0.1;
+
+        self.shake.add(ShakeSource::Fire, self.current_weapon.fire_shake());
+    }
+
+    /// Ray test against player capsules (body box + head sphere), returns the closest hit
+    pub(super) fn hit_test_players(origin: Vector3, direction: Vector3, players: &[OtherPlayer]) -> Option<PlayerHit> {
+        let ray = Ray::new(origin, direction);
+        let mut closest: Option<PlayerHit> = None;
+
+        for player in players.iter().filter(|p| p.is_alive) {
+            if let Some((distance, headshot)) = Self::hit_test_capsule(ray, player.position, capsule_height(player.sliding)) {
+                if closest.as_ref().map_or(true, |c| distance < c.distance) {
+                    closest = Some(PlayerHit { authority: player.authority.clone(), distance, headshot });
+                }
+            }
+        }
+
+        closest
+    }
+
+    /// Ray test against a single player capsule `height` tall at `position` (feet), returns (distance, headshot)
+    pub(super) fn hit_test_capsule(ray: Ray, position: Vector3, height: f32) -> Option<(f32, bool)> {
+        // Same proportions as draw_other_players (body, head sphere on top)
+        let radius = 0.3;
+
+        let head = get_ray_collision_sphere(ray, Vector3::new(position.x, position.y + height, position.z), radius * 0.8);
+        let body = get_ray_collision_box(ray, BoundingBox::new(
+            Vector3::new(position.x - radius, position.y, position.z - radius),
+            Vector3::new(position.x + radius, position.y + height, position.z + radius),
+        ));
+
+        match (head.hit, body.hit) {
+            (true, true) if body.distance < head.distance => Some((body.distance, false)),
+            (true, _) => Some((head.distance, true)),
+            (false, true) => Some((body.distance, false)),
+            (false, false) => None,
+        }
+    }
+
+    /// Call blockchain shoot instruction via JavaScript
+    pub(super) fn call_blockchain_shoot(&self, game_pubkey: &str, damage: u8) {
+        use std::os::raw::c_char;
+        use std::ffi::CString;
+
+        let js_code = format!(
+            r#"
+            (async () => {{
+                try {{
+                    if (window.gameBridge && window.gameBridge.shootPlayer && window.gameBridge.getOtherPlayerPDAs) {{
+                        // Get all other player PDAs for hit detection
+                        const otherPlayerPdas = await window.gameBridge.getOtherPlayerPDAs('{}');
+
+                        // Call shoot instruction with the damage this shot dealt
+                        const result = await window.gameBridge.shootPlayer({}, '{}', otherPlayerPdas);
+                        console.log('🎯 Shoot result:', result);
+
+                        // TODO: Check if we got a kill and call awardKill if needed
+                        // This would require checking health changes in the returned data
+                    }}
+                }} catch (error) {{
+                    console.error('Error calling blockchain shoot:', error);
+                }}
+            }})();
+            "#,
+            game_pubkey,
+            damage,
+            game_pubkey
+        );
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            emscripten_run_script(c_str.as_ptr());
+        }
+    }
+
+    /// Rounds left in the magazine (tracked locally in offline sessions)
+    pub(super) fn bullet_count(&mut self) -> u8 {
+        if self.offline {
+            return self.current_bullet_count;
+        }
+        self.get_bullet_count_from_websocket()
+    }
+
+    /// Get current bullet count from WebSocket data
+    pub(super) fn get_bullet_count_from_websocket(&mut self) -> u8 {
+        use std::ffi::CString;
+
+        // Use the simple global variable that game-bridge.js sets
+        let js_code = r#"
+            (() => {
+                try {
+                    // Check the simple global variable first (set by game-bridge.js)
+                    if (typeof window.___current_player_bullet_count === 'number') {
+                        console.log('[Rust] Reading bullet count:', window.___current_player_bullet_count);
+                        return window.___current_player_bullet_count;
+                    }
+                    
+                    // Fallback: Try to read from WebSocket updates
+                    const ephemeralKey = window.gameBridge?.getCurrentPlayerEphemeralKey();
+                    if (!ephemeralKey || !window.___websocket_player_updates) {
+                        console.log('[Rust] No ephemeral key or websocket updates, defaulting to 10');
+                        return 10;
+                    }
+                    
+                    for (const [accountPubkey, update] of Object.entries(window.___websocket_player_updates)) {
+                        if (update.parsed && update.parsed.authority === ephemeralKey) {
+                            console.log('[Rust] Found player data, bullet count:', update.parsed.bulletCount);
+                            return update.parsed.bulletCount || 10;
+                        }
+                    }
+                    
+                    console.log('[Rust] Player not found in websocket updates, defaulting to 10');
+                    return 10;
+                } catch (e) {
+                    console.error('[Rust] Error getting bullet count:', e);
+                    return 10;
+                }
+            })();
+        "#;
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            let result_ptr = emscripten_run_script_string(c_str.as_ptr());
+
+            if !result_ptr.is_null() {
+                let result_str = std::ffi::CStr::from_ptr(result_ptr)
+                    .to_string_lossy();
+                
+                if let Ok(count) = result_str.parse::<u8>() {
+                    // Update internal state
+                    self.current_bullet_count = count;
+                    
+                    // Also update the UI via game bridge
+                    let update_ui_code = format!(r#"
+                        (() => {{
+                            if (window.gameBridge && window.gameBridge.updateUIAmmo) {{
+                                window.gameBridge.updateUIAmmo({});
+                            }}
+                        }})();
+                    "#, count);
+                    
+                    let update_c_str = CString::new(update_ui_code).unwrap();
+                    emscripten_run_script(update_c_str.as_ptr());
+                    
+                    log_info!(Game, "🔫 Rust: Bullet count updated to: {} (UI notified)", count);
+                    return count;
+                }
+            }
+        }
+
+        log_warn!(Game, "⚠️ Rust: Failed to get bullet count, defaulting to 10");
+        10
+    }
+
+    /// Get reload timestamp from WebSocket to check if reloading
+    pub(super) fn get_reload_timestamp(&self) -> u64 {
+        use std::ffi::CString;
+
+        let js_code = r#"
+            (() => {
+                try {
+                    const ephemeralKey = window.gameBridge?.getCurrentPlayerEphemeralKey();
+                    if (!ephemeralKey || !window.___websocket_player_updates) {
+                        return 0;
+                    }
+                    
+                    for (const [accountPubkey, update] of Object.entries(window.___websocket_player_updates)) {
+                        if (update.parsed && update.parsed.authority === ephemeralKey) {
+                            const reloadTimestamp = update.parsed.reloadStartTimestamp || 0;
+                            return reloadTimestamp;
+                        }
+                    }
+                    return 0;
+                } catch (e) {
+                    console.error('❌ JS: Error getting reload timestamp:', e);
+                    return 0;
+                }
+            })();
+        "#;
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            let result_ptr = emscripten_run_script_string(c_str.as_ptr());
+
+            if !result_ptr.is_null() {
+                let result_str = std::ffi::CStr::from_ptr(result_ptr)
+                    .to_string_lossy();
+                
+                if let Ok(timestamp) = result_str.parse::<u64>() {
+                    return timestamp;
+                }
+            }
+        }
+
+        0
+    }
+
+    /// Display name for a player authority ("You" for the local player)
+    pub(super) fn display_name(&self, authority: &str, local_authority: &str) -> String {
+        if authority == local_authority {
+            return "You".to_string();
+        }
+        self.other_players
+            .iter()
+            .find(|p| p.authority == authority)
+            .map(|p| p.username.clone())
+            .unwrap_or_else(|| format!("{}...", &authority[..authority.len().min(8)]))
+    }
+
+    /// Dispatch a kill event to the React kill feed and update assist counts for the scoreboard
+    pub(super) fn publish_kill_event(&self, kill: &KillEvent, local_authority: &str) {
+        use std::ffi::CString;
+
+        // Hazard deaths have no killer to show
+        let killer_name = match (kill.killer.as_deref(), kill.hazard) {
+            (Some(killer), _) => self.display_name(killer, local_authority),
+            (None, Some(_)) => String::new(),
+            (None, None) => "Unknown".to_string(),
+        };
+        let victim_name = self.display_name(&kill.victim, local_authority);
+        let assist_names: Vec<String> = kill.assists.iter()
+            .map(|a| self.display_name(a, local_authority))
+            .collect();
+        let assist_counts: serde_json::Map<String, serde_json::Value> = kill.assists.iter()
+            .map(|a| (a.clone(), serde_json::Value::from(self.damage_tracker.assists_for(a))))
+            .collect();
+
+        if kill.killer.as_deref() == Some(local_authority) {
+            play_ui_sound(UiSound::KillConfirm);
+        }
+
+        log_info!(Game, "💀 Kill: {} {} ▸ {}", killer_name,
+            if assist_names.is_empty() { String::new() } else { format!("+ {}", assist_names.join(" + ")) },
+            victim_name);
+
+        let detail = serde_json::json!({
+            "weapon": kill.cause_id(),
+            "hazard": kill.hazard.is_some(),
+            "headshot": kill.headshot,
+            "killer": killer_name,
+            "victim": victim_name,
+            "killerAuthority": kill.killer,
+            "victimAuthority": kill.victim,
+            "assists": assist_names,
+            "assistAuthorities": kill.assists,
+            "timestamp": kill.timestamp,
+        });
+
+        let js_code = format!(
+            r#"
+            (function() {{
+                window.___player_assists = window.___player_assists || {{}};
+                Object.assign(window.___player_assists, {});
+                window.dispatchEvent(new CustomEvent('killEvent', {{ detail: {} }}));
+            }})();
+            "#,
+            serde_json::Value::Object(assist_counts),
+            detail
+        );
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            emscripten_run_script(c_str.as_ptr());
+        }
+    }
+
+    /// Tick passive health regeneration according to the current game rules
+    pub(super) fn update_health_regen(&mut self, delta: f32) {
+        let regen = self.rules.health_regen;
+        if let Some(player) = &mut self.player {
+            if !regen.enabled {
+                player.is_regenerating = false;
+                return;
+            }
+
+            let current_time = unsafe { emscripten_get_now() / 1000.0 };
+            player.update_health_regen(regen.delay, regen.rate, delta, current_time);
+        }
+    }
+
+    /// Drive the reload state machine (blockchain sync, animation progress, R key)
+    pub(super) fn update_reload(&mut self, rl: &RaylibHandle) {
+        // Handle reload animation and progress
+        // First, check if we should be in reload state (handles rejoin case)
+        // Offline sessions have no on-chain reload to sync with
+        let reload_timestamp = if self.offline { 0 } else { self.get_reload_timestamp() };
+        
+        // If reload_timestamp exists but we're not tracking it, sync the state
+        if reload_timestamp > 0 && !self.reload_initiated {
+            // Check if the reload is already complete (more than 1 second has passed)
+            use std::ffi::CString;
+            
+            let js_code = r#"
+                (() => {
+                    try {
+                        return Math.floor(Date.now() / 1000);
+                    } catch (e) {
+                        return 0;
+                    }
+                })();
+            "#;
+            
+            let current_time = unsafe {
+                let c_str = CString::new(js_code).unwrap();
+                let result_ptr = emscripten_run_script_string(c_str.as_ptr());
+                
+                if !result_ptr.is_null() {
+                    let result_str = std::ffi::CStr::from_ptr(result_ptr).to_string_lossy();
+                    result_str.parse::<u64>().unwrap_or(0)
+                } else {
+                    0
+                }
+            };
+            
+            let elapsed = current_time.saturating_sub(reload_timestamp);
+            
+            if elapsed >= 1 {
+                // Reload is already complete, finish it immediately
+                self.reload_initiated = true;
+                self.finish_reload();
+            } else {
+                // Reload is still in progress, sync the state
+                self.reload_initiated = true;
+                self.reload_progress = (elapsed as f32).min(1.0);
+            }
+        }
+        
+        if self.reload_initiated {
+            // Use local time to drive the animation immediately
+            let current_time = unsafe { emscripten_get_now() / 1000.0 }; // Convert ms to seconds
+            let local_elapsed = current_time - self.reload_start_time;
+            
+            // Update reload progress based on local time (1 second duration)
+            let previous_progress = self.reload_progress;
+            self.reload_progress = (local_elapsed as f32).min(1.0);
+            Self::play_reload_stage_sounds(previous_progress, self.reload_progress);
+            
+            // Check blockchain state for actual completion
+            if reload_timestamp > 0 {
+                // Get current blockchain timestamp from JavaScript (Solana Clock)
+                use std::ffi::CString;
+                
+                let js_code = r#"
+                    (() => {
+                        try {
+                            return Math.floor(Date.now() / 1000);
+                        } catch (e) {
+                            console.error('Failed to get current timestamp:', e);
+                            return 0;
+                        }
+                    })();
+                "#;
+                
+                let blockchain_time = unsafe {
+                    let c_str = CString::new(js_code).unwrap();
+                    let result_ptr = emscripten_run_script_string(c_str.as_ptr());
+                    
+                    if !result_ptr.is_null() {
+                        let result_str = std::ffi::CStr::from_ptr(result_ptr).to_string_lossy();
+                        result_str.parse::<u64>().unwrap_or(0)
+                    } else {
+                        0
+                    }
+                };
+                
+                if blockchain_time > 0 {
+                    let blockchain_elapsed = blockchain_time.saturating_sub(reload_timestamp);
+                    
+                    // Auto-finish reload after blockchain confirms 1 second has passed
+                    if blockchain_elapsed >= 1 {
+                        self.finish_reload();
+                    }
+                }
+            }
+            
+            // Also finish locally after 1 second if blockchain hasn't responded yet
+            // This ensures the animation completes smoothly even with network latency
+            if local_elapsed >= 1.0 {
+                self.finish_reload();
+            }
+        }
+
+        // Handle R key press for manual reload
+        if rl.is_key_pressed(KeyboardKey::KEY_R) {
+            let bullet_count = self.bullet_count();
+            if bullet_count < MAGAZINE_SIZE && !self.reload_initiated {
+                self.start_reload();
+            }
+        }
+    }
+
+    /// Play the reload sound cues whose stage was entered between two progress values
+    fn play_reload_stage_sounds(previous_progress: f32, progress: f32) {
+        for (stage_start, sound) in RELOAD_SOUND_CUES {
+            if previous_progress < stage_start && progress >= stage_start {
+                mixer::play_sound(sound, AudioChannel::Effects, 0.9);
+            }
+        }
+    }
+
+    /// Handle number keys for weapon selection (1 = first weapon, ...), carried weapons only
+    pub(super) fn update_weapon_switch(&mut self, rl: &RaylibHandle) {
+        const WEAPON_KEYS: [KeyboardKey; 3] = [
+            KeyboardKey::KEY_ONE,
+            KeyboardKey::KEY_TWO,
+            KeyboardKey::KEY_THREE,
+        ];
+
+        for (key, weapon) in WEAPON_KEYS.iter().zip(WeaponKind::ALL) {
+            if rl.is_key_pressed(*key) && self.carried_weapons.contains(&weapon) {
+                self.switch_weapon(weapon);
+            }
+        }
+    }
+
+    /// Equip a weapon (ignored while reloading or if already equipped)
+    pub(super) fn switch_weapon(&mut self, weapon: WeaponKind) {
+        if weapon == self.current_weapon || self.reload_initiated {
+            return;
+        }
+
+        log_info!(Game, "🔫 Switching weapon: {:?} -> {:?}", self.current_weapon, weapon);
+        self.current_weapon = weapon;
+        self.scope.reset();
+        mixer::play_sound(WEAPON_SWITCH_SOUND, AudioChannel::Effects, 0.8);
+    }
+
+    /// Right mouse scopes in (scoped weapons only), Shift holds breath while scoped
+    pub(super) fn update_scope(&mut self, rl: &RaylibHandle, delta: f32, round_live: bool) {
+        let scoped_fov = self.current_weapon.scope_fov();
+        let alive = self.player.as_ref().is_some_and(|p| !p.is_dead);
+        let wants_scope = round_live
+            && alive
+            && scoped_fov.is_some()
+            && !self.reload_initiated
+            && rl.is_mouse_button_down(MouseButton::MOUSE_BUTTON_RIGHT);
+        let wants_hold_breath = rl.is_key_down(KeyboardKey::KEY_LEFT_SHIFT) || rl.is_key_down(KeyboardKey::KEY_RIGHT_SHIFT);
+
+        self.scope.update(delta, wants_scope, wants_hold_breath);
+
+        // Slow the mouse down in proportion to the zoom
+        if let Some(ref mut player) = self.player {
+            player.scoped_look_scale = match scoped_fov {
+                Some(fov) if wants_scope => Some(self.scope.fov(fov) / BASE_FOV),
+                _ => None,
+            };
+        }
+    }
+
+    /// Camera for the local player with scope zoom and sway applied
+    pub(super) fn scoped_camera(&self, player: &Player) -> Camera3D {
+        let Some(scoped_fov) = self.current_weapon.scope_fov() else {
+            return player.camera;
+        };
+
+        let (sway_yaw, sway_pitch) = self.scope.sway();
+        let yaw_rad = (player.yaw + sway_yaw).to_radians();
+        let pitch_rad = (player.pitch + sway_pitch).to_radians();
+        let direction = Vector3::new(
+            yaw_rad.cos() * pitch_rad.cos(),
+            pitch_rad.sin(),
+            yaw_rad.sin() * pitch_rad.cos(),
+        );
+
+        Camera3D::perspective(
+            player.camera.position,
+            player.camera.position + direction,
+            Vector3::new(0.0, 1.0, 0.0),
+            self.scope.fov(scoped_fov),
+        )
+    }
+
+    /// Start reload process (Step 1: Call blockchain to record timestamp)
+    pub(super) fn start_reload(&mut self) {
+        if self.reload_initiated {
+            return; // Already reloading
+        }
+
+        if let Some(ref game_pubkey) = self.current_game_pubkey {
+            use std::ffi::CString;
+
+            let js_code = format!(r#"
+                (async () => {{
+                    try {{
+                        if (window.gameBridge && window.gameBridge.startReload) {{
+                            await window.gameBridge.startReload('{}');
+                        }}
+                    }} catch (e) {{
+                        console.error('❌ Start reload failed:', e);
+                    }}
+                }})();
+            "#, game_pubkey);
+
+            unsafe {
+                let c_str = CString::new(js_code).unwrap();
+                emscripten_run_script(c_str.as_ptr());
+            }
+        } else if !self.offline {
+            return;
+        }
+
+        // Start the animation immediately using local time
+        self.reload_initiated = true;
+        self.reload_progress = 0.0;
+        self.reload_start_time = unsafe { emscripten_get_now() / 1000.0 }; // Store start time in seconds
+        self.show_reload_prompt = false; // Hide prompt when reload starts
+    }
+
+    /// Finish reload process (Step 2: Call blockchain to refill ammo after 1 second)
+    pub(super) fn finish_reload(&mut self) {
+        if !self.reload_initiated {
+            return;
+        }
+
+        if let Some(ref game_pubkey) = self.current_game_pubkey {
+            use std::ffi::CString;
+
+            let js_code = format!(r#"
+                (async () => {{
+                    try {{
+                        if (window.gameBridge && window.gameBridge.finishReload) {{
+                            await window.gameBridge.finishReload('{}');
+                        }}
+                    }} catch (e) {{
+                        console.error('❌ Finish reload failed:', e);
+                    }}
+                }})();
+            "#, game_pubkey);
+
+            unsafe {
+                let c_str = CString::new(js_code).unwrap();
+                emscripten_run_script(c_str.as_ptr());
+            }
+        } else if self.offline {
+            self.current_bullet_count = MAGAZINE_SIZE;
+        } else {
+            return;
+        }
+
+        self.reload_initiated = false;
+        self.reload_progress = 0.0;
+        self.reload_start_time = 0.0;
+    }
+
+    /// Call blockchain respawn instruction via JavaScript
+    pub(super) fn call_respawn(&mut self, game_pubkey: &str) {
+        use std::os::raw::c_char;
+        use std::ffi::CString;
+        use crate::map::ModelType;
+
+        log_info!(Game, "🎯 call_respawn ENTERED! game_pubkey: {}", game_pubkey);
+
+        // Use the stored team value
+        let team = self.current_player_team;
+        log_info!(Game, "👥 Current player team: {}", team);
+
+        // Get spawn position from the loaded map
+        let (spawn_x, spawn_y, spawn_z) = if let Some(ref map) = self.map {
+            // Filter spawn points by team from the loaded map
+            let target_model_type = if team == 1 {
+                ModelType::SpawnPointBlue
+            } else {
+                ModelType::SpawnPointRed
+            };
+
+            // Collect all spawn points for the team
+            let team_spawn_points: Vec<&crate::map::MapObject> = map.objects
+                .iter()
+                .filter(|obj| obj.model_type == target_model_type)
+                .collect();
+
+            if !team_spawn_points.is_empty() {
+                // Pick a random spawn point
+                use std::time::{SystemTime, UNIX_EPOCH};
+                let seed = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs() as usize;
+                let random_index = seed % team_spawn_points.len();
+                let spawn_point = team_spawn_points[random_index];
+
+                let pos = spawn_point.get_position();
+                log_info!(Game, "✅ Using map spawn point: ({:.2}, {:.2}, {:.2}) from {} available", 
+                    pos.x, pos.y, pos.z, team_spawn_points.len());
+                (pos.x, pos.y, pos.z)
+            } else {
+                // No spawn points found for team, use default
+                let default_x = if team == 0 { -10.0 } else { 10.0 };
+                log_warn!(Game, "⚠️ No spawn points found for team {}, using default ({:.2}, 1.0, 0.0)", 
+                    team, default_x);
+                (default_x, 1.0, 0.0)
+            }
+        } else {
+            // No map loaded, use default spawn position
+            let default_x = if team == 0 { -10.0 } else { 10.0 };
+            log_warn!(Game, "⚠️ No map loaded, using default spawn ({:.2}, 1.0, 0.0)", default_x);
+            (default_x, 1.0, 0.0)
+        };
+
+        // Call JavaScript to execute respawn transaction
+        let js_code = format!(
+            r#"
+            (async () => {{
+                try {{
+                    if (!window.gameBridge || !window.gameBridge.respawnPlayer) {{
+                        console.error('❌ window.gameBridge.respawnPlayer not available!');
+                        return;
+                    }}
+                    
+                    console.log('🎯 Calling respawnPlayer with coordinates: ({}, {}, {})', {}, {}, {});
+                    const result = await window.gameBridge.respawnPlayer('{}', {}, {}, {});
+                    console.log('✅ Respawn transaction sent:', result);
+                }} catch (error) {{
+                    console.error('❌ Error calling respawn:', error);
+                    console.error('Error stack:', error.stack);
+                }}
+            }})();
+            "#,
+            spawn_x, spawn_y, spawn_z,
+            spawn_x, spawn_y, spawn_z,
+            game_pubkey,
+            spawn_x, spawn_y, spawn_z
+        );
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            emscripten_run_script(c_str.as_ptr());
+        }
+
+        // Mark that we've attempted respawn (to avoid spamming)
+        if let Some(ref mut player) = self.player {
+            player.death_timestamp = -1.0; // Negative means respawn requested
+            // The chain never saw a hazard death, so it's forgotten here rather than by the respawn
+            player.clear_hazard_damage();
+            log_info!(Game, "✅ Set death_timestamp to -1.0 to prevent duplicate calls");
+        }
+    }
+
+    /// Expose death state to JavaScript for React overlay
+    pub(super) fn update_death_state_js(&self, is_dead: bool, timestamp: f64) {
+        use std::os::raw::c_char;
+        use std::ffi::CString;
+
+        let js_code = format!(
+            r#"window.gameDeathState = {{ dead: {}, timestamp: {} }};"#,
+            is_dead, timestamp
+        );
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            emscripten_run_script(c_str.as_ptr());
+        }
+    }
+}
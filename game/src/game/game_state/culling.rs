@@ -0,0 +1,84 @@
+//! Potentially-visible-set culling: map objects and remote players hidden behind walls
+
+use raylib::prelude::*;
+use super::{GameState, OtherPlayer};
+use crate::map::Map;
+use crate::game::graphics::view_distance;
+
+/// Player capsule height used for the PVS check (feet to head)
+const PLAYER_CULL_HEIGHT: f32 = 1.8;
+
+impl GameState {
+    /// Whether a remote player standing at `feet` may be visible from `eye` (true without a PVS)
+    pub(super) fn may_see_player(&self, eye: Vector3, feet: Vector3) -> bool {
+        self.pvs.as_ref().map_or(true, |pvs| {
+            let top = Vector3::new(feet.x, feet.y + PLAYER_CULL_HEIGHT, feet.z);
+            pvs.can_see_box(eye, &BoundingBox::new(feet, top))
+        })
+    }
+
+    /// Distance from `eye` to map object `index` if it's within the draw distance and not hidden by the PVS
+    fn map_object_in_view(&self, map: &Map, index: usize, eye: Vector3) -> Option<f32> {
+        // Trigger volumes are invisible in-game
+        if map.trigger(index).is_some() {
+            return None;
+        }
+        let object = &map.objects[index];
+        let distance = object.distance_to(eye);
+        if !view_distance().in_range(distance) {
+            return None;
+        }
+        // Objects without collision bounds (spawn markers and the like) are always drawn
+        let visible = match (&self.pvs, object.collision_bounds()) {
+            (Some(pvs), Some(bounds)) => pvs.can_see_box(eye, &bounds),
+            _ => true,
+        };
+        visible.then_some(distance)
+    }
+
+    /// Draw the loaded map minus objects past the draw distance or hidden by the PVS from `eye`,
+    /// shaded by the map's ambiance and faded into the sky near the cutoff
+    pub(super) fn draw_map_culled(&self, d3d: &mut RaylibMode3D<RaylibDrawHandle>, eye: Vector3) {
+        let Some(ref map) = self.map else {
+            return;
+        };
+        let view = view_distance();
+        let sky = map.ambiance.sky_color();
+        for (index, object) in map.objects.iter().enumerate() {
+            if let Some(distance) = self.map_object_in_view(map, index, eye) {
+                object.draw_tinted(d3d, view.apply(map.shade_object(index, distance), sky, distance));
+            }
+        }
+    }
+
+    /// Halos around glowing map objects in view; drawn once the rest of the scene is down
+    pub(super) fn draw_map_glow(&self, d3d: &mut RaylibMode3D<RaylibDrawHandle>, eye: Vector3) {
+        let Some(ref map) = self.map else {
+            return;
+        };
+        let view = view_distance();
+        // Glows loaded from a hand-edited file may point past the objects
+        for glow in map.glows.iter().filter(|glow| (glow.object as usize) < map.objects.len()) {
+            let index = glow.object as usize;
+            if let Some(distance) = self.map_object_in_view(map, index, eye) {
+                map.objects[index].draw_glow_halo(d3d, glow.halo(view.fog_amount(distance)));
+            }
+        }
+    }
+
+    /// Remote players to draw from `eye`, minus the ones walls hide and the one a
+    /// spectator is looking through
+    pub(super) fn players_in_view(&self, eye: Vector3, except: Option<&str>) -> Vec<OtherPlayer> {
+        self.other_players
+            .iter()
+            .filter(|p| except != Some(p.authority.as_str()) && self.may_see_player(eye, p.position))
+            .cloned()
+            .collect()
+    }
+
+    /// Whether a remote player is worth per-frame smoothing: only ones the local player
+    /// may see are dead-reckoned, the rest just jump to their latest network position
+    pub(super) fn in_interest(&self, player: &OtherPlayer) -> bool {
+        self.player.as_ref().map_or(true, |local| self.may_see_player(local.camera.position, player.position))
+    }
+}
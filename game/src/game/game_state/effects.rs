@@ -0,0 +1,176 @@
+//! Short-lived visual effects (muzzle/screen/damage flash timers, screen shake, bullet trails, hit particles)
+
+use raylib::prelude::*;
+use super::{GameState, BulletTrail, HitParticle};
+use crate::game::{palette, ShakeSource};
+use crate::game::comfort::{comfort_scale, ComfortEffect};
+
+/// Seconds the red edge flash lasts after taking damage the HUD calls out (falls)
+pub(super) const DAMAGE_FLASH_DURATION: f32 = 0.4;
+
+/// Damage taken in one hit that shakes the camera as hard as a hit can
+pub(super) const DAMAGE_FOR_FULL_SHAKE: f32 = 50.0;
+
+/// Distance at which a grenade burst no longer shakes the camera
+const BLAST_SHAKE_RADIUS: f32 = 12.0;
+
+impl GameState {
+    /// Tick down muzzle/screen/damage flash timers and expire bullet trails
+    pub(super) fn update_effects(&mut self, delta: f32) {
+        // Update effect timers
+        if self.muzzle_flash_timer > 0.0 {
+            self.muzzle_flash_timer -= delta;
+        }
+        if self.screen_flash_timer > 0.0 {
+            self.screen_flash_timer -= delta;
+        }
+        if self.damage_flash_timer > 0.0 {
+            self.damage_flash_timer -= delta;
+        }
+        self.shake.update(delta);
+        self.threats.update(delta);
+
+        // Update bullet trails
+        for trail in &mut self.bullet_trails {
+            trail.timer -= delta;
+        }
+        // Remove expired trails
+        self.bullet_trails.retain(|trail| trail.timer > 0.0);
+
+        // Update hit particles (blood droplets fall, sparks mostly fly)
+        let gravity = if self.streamer_safe { -4.0 } else { -9.8 };
+        for particle in &mut self.hit_particles {
+            particle.velocity.y += gravity * delta;
+            particle.position = particle.position + particle.velocity * delta;
+            particle.timer -= delta;
+        }
+        self.hit_particles.retain(|p| p.timer > 0.0 && p.position.y > -0.05);
+    }
+
+    /// Shake the camera for a grenade bursting at `point`, harder the closer it is
+    pub(super) fn shake_from_blast(&mut self, point: Vector3) {
+        let Some(ref player) = self.player else {
+            return;
+        };
+        let distance = player.camera.position.distance_to(point);
+        self.shake.add(ShakeSource::Blast, 1.0 - distance / BLAST_SHAKE_RADIUS);
+    }
+
+    /// `camera` turned by the current screen shake (scaled by the comfort setting)
+    pub(super) fn shaken(&self, camera: Camera3D) -> Camera3D {
+        let (yaw, pitch, roll) = self.shake.offset(comfort_scale(ComfortEffect::ScreenShake));
+        if (yaw, pitch, roll) == (0.0, 0.0, 0.0) {
+            return camera;
+        }
+        let view = camera.target - camera.position;
+        let right = view.cross(camera.up).normalized();
+        let view = rotate_around(rotate_around(view, camera.up, yaw), right, pitch);
+        let mut shaken = camera;
+        shaken.target = camera.position + view;
+        shaken.up = rotate_around(camera.up, view.normalized(), roll);
+        shaken
+    }
+
+    /// Spawn hit feedback on a player at the impact point
+    /// Blood by default, neutral sparks in streamer-safe mode
+    pub(super) fn spawn_hit_effect(&mut self, position: Vector3, headshot: bool) {
+        let count = if headshot { 18 } else { 12 };
+        let palette = palette::current();
+        let (speed, lifetime) = if self.streamer_safe { (4.0, 0.25) } else { (2.0, 0.6) };
+
+        for i in 0..count {
+            // Golden-angle spiral gives an even spray without needing an RNG
+            let angle = i as f32 * 2.399_963;
+            let elevation = ((i as f32 * 0.618_034).fract() - 0.3) * 1.5;
+            let direction = Vector3::new(angle.cos(), elevation, angle.sin()).normalized();
+            let jitter = 0.6 + (i as f32 * 0.377).fract() * 0.8;
+
+            let color = if self.streamer_safe {
+                Color::new(255, 240, 180, 255) // Warm white spark
+            } else if i % 3 == 0 {
+                palette.blood_dark
+            } else {
+                palette.blood
+            };
+
+            self.hit_particles.push(HitParticle {
+                position,
+                velocity: direction * speed * jitter,
+                timer: lifetime * jitter,
+                color,
+            });
+        }
+    }
+
+    /// Draw hit-feedback particles
+    pub(super) fn draw_hit_particles(d3d: &mut RaylibMode3D<RaylibDrawHandle>, particles: &[HitParticle], streamer_safe: bool) {
+        for particle in particles {
+            let alpha = (particle.timer / 0.3).min(1.0);
+            let color = Color::new(particle.color.r, particle.color.g, particle.color.b, (255.0 * alpha) as u8);
+
+            if streamer_safe {
+                // Short streaks along the velocity read as sparks
+                let tail = particle.position - particle.velocity * 0.03;
+                d3d.draw_line_3D(tail, particle.position, color);
+            } else {
+                d3d.draw_cube(particle.position, 0.04, 0.04, 0.04, color);
+            }
+        }
+    }
+
+    /// Draw bullet trails/tracers
+    pub(super) fn draw_bullet_trails(d3d: &mut RaylibMode3D<RaylibDrawHandle>, trails: &[BulletTrail]) {
+        for trail in trails {
+            // Calculate alpha based on remaining time (fade out effect)
+            let alpha = ((trail.timer / 0.1) * 255.0) as u8;
+
+            // Draw trail as a bright yellow/orange line
+            let trail_color = Color::new(255, 220, 100, alpha);
+
+            // Draw the main trail line
+            d3d.draw_line_3D(trail.start, trail.end, trail_color);
+
+            // Draw a thicker glow around the trail for better visibility
+            // We do this by drawing multiple slightly offset lines
+            let direction = (trail.end - trail.start).normalized();
+            let perpendicular1 = Vector3::new(-direction.z, 0.0, direction.x).normalized();
+            let perpendicular2 = direction.cross(perpendicular1).normalized();
+
+            let offset = 0.02; // Small offset for glow effect
+
+            // Draw glow lines with lower alpha
+            let glow_alpha = alpha / 3;
+            let glow_color = Color::new(255, 180, 50, glow_alpha);
+
+            d3d.draw_line_3D(
+                trail.start + perpendicular1 * offset,
+                trail.end + perpendicular1 * offset,
+                glow_color
+            );
+            d3d.draw_line_3D(
+                trail.start - perpendicular1 * offset,
+                trail.end - perpendicular1 * offset,
+                glow_color
+            );
+            d3d.draw_line_3D(
+                trail.start + perpendicular2 * offset,
+                trail.end + perpendicular2 * offset,
+                glow_color
+            );
+            d3d.draw_line_3D(
+                trail.start - perpendicular2 * offset,
+                trail.end - perpendicular2 * offset,
+                glow_color
+            );
+
+            // Draw impact point (small sphere at the end)
+            d3d.draw_sphere(trail.end, 0.05, Color::new(255, 100, 0, alpha));
+        }
+    }
+}
+
+/// `v` rotated `degrees` around the unit `axis` (Rodrigues' formula)
+fn rotate_around(v: Vector3, axis: Vector3, degrees: f32) -> Vector3 {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    v * cos + axis.cross(v) * sin + axis * (axis.dot(v) * (1.0 - cos))
+}
@@ -0,0 +1,47 @@
+//! Match event log export (kills, shots, 1 Hz positions, round events) for stat tools
+
+use super::{GameState, emscripten_get_now};
+
+impl GameState {
+    /// Log every player's position once per `POSITION_SAMPLE_INTERVAL`
+    pub(super) fn sample_match_positions(&mut self) {
+        if self.current_game_pubkey.is_none() {
+            return;
+        }
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        if !self.match_log.position_sample_due(now) {
+            return;
+        }
+
+        // Spectators have no local player; everyone else is in other_players
+        let local = self.player.as_ref().map(|player| {
+            (self.get_current_ephemeral_key(), self.current_player_team.to_string(), player.position, !player.is_dead)
+        });
+        let others = self.other_players.iter()
+            .map(|p| (p.authority.as_str(), p.team.as_str(), p.position, p.is_alive));
+        let local = local.iter().map(|(authority, team, position, alive)| (authority.as_str(), team.as_str(), *position, *alive));
+        self.match_log.record_positions(now, others.chain(local));
+    }
+
+    /// Download the match log as `match-<game>.json` and `match-<game>.csv`
+    pub(super) fn export_match_log(&self) {
+        let Some(game) = self.current_game_pubkey.as_deref() else {
+            return;
+        };
+        if self.match_log.is_empty() {
+            return;
+        }
+
+        let stem = format!("match-{}", game.get(..8).unwrap_or(game));
+        let files = [
+            (format!("{}.json", stem), "application/json", self.match_log.to_json(game)),
+            (format!("{}.csv", stem), "text/csv", self.match_log.to_csv()),
+        ];
+        for (filename, mime, contents) in files {
+            match crate::net::download_file(&filename, mime, contents.as_bytes()) {
+                Ok(()) => log_info!(Game, "📊 Exported match events: {}", filename),
+                Err(e) => log_warn!(Game, "⚠️ Failed to export match events: {}", e),
+            }
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Utility grenades: throwing, syncing throw events and drawing their effects
+
+use raylib::prelude::*;
+use crate::game::{Blindness, FlashGrenade, SmokeGrenade, ThrowEvent, flash_exposure};
+use crate::audio::{mixer, AudioChannel};
+use super::{GameState, emscripten_get_now};
+use super::net_sync::event_vector;
+
+/// Smoke grenades carried per life
+const SMOKES_PER_LIFE: u8 = 1;
+
+/// Flash grenades carried per life
+const FLASHES_PER_LIFE: u8 = 2;
+
+/// Played when a smoke cloud bursts
+const SMOKE_POP_SOUND: &str = "/assets/grenade/audio/smoke-pop.wav";
+
+/// Played where a flash grenade bursts
+const FLASH_BANG_SOUND: &str = "/assets/grenade/audio/flash-bang.wav";
+
+/// Ringing in the ears of a flashed player (not positional, scaled by the exposure)
+const FLASH_RING_SOUND: &str = "/assets/grenade/audio/flash-ring.wav";
+
+/// Share of world sound volume lost at full muffle
+const MAX_MUFFLE: f32 = 0.85;
+
+/// Smoke tint (slightly blue-grey so it reads against the dark ground)
+const SMOKE_COLOR: (u8, u8, u8) = (190, 195, 205);
+
+impl GameState {
+    /// Throw a smoke grenade with G or a flash grenade with F
+    pub(super) fn update_grenade_throw(&mut self, rl: &RaylibHandle) {
+        let kind = if rl.is_key_pressed(KeyboardKey::KEY_G) && self.smoke_grenades_left > 0 {
+            "smoke"
+        } else if rl.is_key_pressed(KeyboardKey::KEY_F) && self.flash_grenades_left > 0 {
+            "flash"
+        } else {
+            return;
+        };
+        let Some(ref player) = self.player else {
+            return;
+        };
+        if player.is_dead {
+            return;
+        }
+
+        let direction = (player.camera.target - player.camera.position).normalized();
+        let event = ThrowEvent {
+            thrower: self.get_current_ephemeral_key(),
+            origin: player.camera.position + direction * 0.5,
+            direction,
+            thrown_at: unsafe { emscripten_get_now() / 1000.0 },
+        };
+        if kind == "smoke" {
+            self.smoke_grenades_left -= 1;
+            log_info!(Game, "💨 Smoke out! ({} left)", self.smoke_grenades_left);
+        } else {
+            self.flash_grenades_left -= 1;
+            log_info!(Game, "⚡ Flash out! ({} left)", self.flash_grenades_left);
+        }
+
+        self.send_game_event(serde_json::json!({
+            "type": kind,
+            "thrower": event.thrower,
+            "origin": [event.origin.x, event.origin.y, event.origin.z],
+            "direction": [event.direction.x, event.direction.y, event.direction.z],
+            "thrownAt": event.thrown_at,
+        }));
+        if kind == "smoke" {
+            self.spawn_smoke(&event);
+        } else {
+            self.spawn_flash(&event);
+        }
+    }
+
+    /// Start simulating a smoke grenade (ours or a remote throw)
+    pub(super) fn spawn_smoke(&mut self, event: &ThrowEvent) {
+        let blocked = |from: Vector3, to: Vector3| {
+            self.map_index.as_ref().is_some_and(|index| index.is_line_blocked(from, to))
+        };
+        let grenade = SmokeGrenade::from_throw(event, blocked);
+        self.pending_smoke_pops.push(grenade.detonates_at);
+        self.smoke.add(grenade);
+    }
+
+    /// Start simulating a flash grenade (ours or a remote throw)
+    pub(super) fn spawn_flash(&mut self, event: &ThrowEvent) {
+        let blocked = |from: Vector3, to: Vector3| {
+            self.map_index.as_ref().is_some_and(|index| index.is_line_blocked(from, to))
+        };
+        self.pending_flashes.push(FlashGrenade::from_throw(event, blocked));
+    }
+
+    /// Apply a grenade throw (smoke or flash) received from another player
+    pub(super) fn apply_grenade_event(&mut self, event: &serde_json::Value) {
+        let (Some(origin), Some(direction), Some(thrown_at)) = (
+            event_vector(event, "origin"),
+            event_vector(event, "direction"),
+            event.get("thrownAt").and_then(|v| v.as_f64()),
+        ) else {
+            log_warn!(Game, "⚠️ Ignoring malformed grenade event: {}", event);
+            return;
+        };
+        let thrower = event.get("thrower").and_then(|v| v.as_str()).unwrap_or("unknown").to_string();
+
+        let throw = ThrowEvent { thrower, origin, direction: direction.normalized(), thrown_at };
+        if event.get("type").and_then(|t| t.as_str()) == Some("flash") {
+            self.spawn_flash(&throw);
+        } else {
+            self.spawn_smoke(&throw);
+        }
+    }
+
+    /// Advance grenade effects (detonation sounds and shake, expiry)
+    pub(super) fn update_grenades(&mut self) {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+
+        let mut detonated = Vec::new();
+        self.pending_smoke_pops.retain(|&at| {
+            if now >= at {
+                detonated.push(at);
+                false
+            } else {
+                true
+            }
+        });
+        for at in detonated {
+            let Some(point) = self.smoke.grenades().iter().find(|g| g.detonates_at == at).map(|g| g.detonation_point) else {
+                continue;
+            };
+            self.play_world_sound(SMOKE_POP_SOUND, point, 0.8);
+            self.shake_from_blast(point);
+        }
+
+        self.smoke.expire(now);
+
+        let (burst, in_flight): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.pending_flashes).into_iter().partition(|g| now >= g.detonates_at);
+        self.pending_flashes = in_flight;
+        for grenade in burst {
+            self.play_world_sound(FLASH_BANG_SOUND, grenade.detonation_point, 1.0);
+            self.shake_from_blast(grenade.detonation_point);
+            self.apply_flash(&grenade, now);
+        }
+        if self.blindness.is_some_and(|b| b.is_finished(now)) {
+            self.blindness = None;
+        }
+    }
+
+    /// Blind the local player by how directly they were looking at a bursting flash
+    fn apply_flash(&mut self, grenade: &FlashGrenade, now: f64) {
+        let Some(ref player) = self.player else {
+            return;
+        };
+        if player.is_dead {
+            return;
+        }
+        let eye = player.camera.position;
+        let forward = player.camera.target - player.camera.position;
+        let blocked = self.is_line_blocked(eye, grenade.detonation_point);
+        let exposure = flash_exposure(eye, forward, grenade.detonation_point, blocked);
+        if exposure <= 0.0 {
+            return;
+        }
+
+        // A weaker flash doesn't cut short a stronger one still whiting out the screen
+        let flashed = Blindness::new(exposure, now);
+        if !self.blindness.is_some_and(|b| b.whiteout(now) > flashed.whiteout(now)) {
+            log_info!(Game, "⚡ Flashed by {} ({:.0}%)", grenade.thrower, exposure * 100.0);
+            mixer::play_sound(FLASH_RING_SOUND, AudioChannel::Effects, exposure);
+            self.blindness = Some(flashed);
+        }
+    }
+
+    /// Volume multiplier for world sounds while the local player's ears ring
+    pub(super) fn flash_muffle_gain(&self) -> f32 {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        1.0 - self.blindness.map_or(0.0, |b| b.muffle(now)) * MAX_MUFFLE
+    }
+
+    /// How white a flash has washed out the local player's screen (0 - 1)
+    pub(super) fn flash_whiteout(&self) -> f32 {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        self.blindness.map_or(0.0, |b| b.whiteout(now))
+    }
+
+    /// Whether smoke blocks the line between two points right now
+    pub(super) fn smoke_blocks(&self, from: Vector3, to: Vector3) -> bool {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        self.smoke.blocks_line(from, to, now)
+    }
+
+    /// Draw smoke clouds as layered camera-facing billboards (back to front)
+    pub(super) fn draw_smoke(&self, d3d: &mut RaylibMode3D<RaylibDrawHandle>, camera: &Camera3D) {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        let mut puffs: Vec<_> = self.smoke.grenades().iter().flat_map(|g| g.puffs(now)).collect();
+        if puffs.is_empty() {
+            return;
+        }
+        puffs.sort_by(|a, b| {
+            let da = (a.position - camera.position).length();
+            let db = (b.position - camera.position).length();
+            db.total_cmp(&da)
+        });
+
+        let forward = (camera.target - camera.position).normalized();
+        let right = forward.cross(camera.up).normalized();
+        let up = right.cross(forward).normalized();
+
+        for puff in puffs {
+            let alpha = (puff.opacity * 255.0) as u8;
+            let color = Color::new(SMOKE_COLOR.0, SMOKE_COLOR.1, SMOKE_COLOR.2, alpha);
+            // Octagon fan facing the camera
+            const SIDES: usize = 8;
+            let corner = |i: usize| {
+                let angle = i as f32 / SIDES as f32 * std::f32::consts::TAU;
+                puff.position + right * (angle.cos() * puff.size) + up * (angle.sin() * puff.size)
+            };
+            for i in 0..SIDES {
+                d3d.draw_triangle3D(puff.position, corner(i), corner(i + 1), color);
+            }
+        }
+    }
+
+    /// Reset utility for a new life
+    pub(super) fn refill_grenades(&mut self) {
+        self.smoke_grenades_left = SMOKES_PER_LIFE;
+        self.flash_grenades_left = FLASHES_PER_LIFE;
+    }
+
+    /// Remaining grenades (for the HUD)
+    pub(super) fn grenade_hud_text(&self) -> String {
+        let smokes = crate::i18n::tr!("hud.smoke_count", count = self.smoke_grenades_left);
+        let flashes = crate::i18n::tr!("hud.flash_count", count = self.flash_grenades_left);
+        format!("{}   {}", smokes, flashes)
+    }
+}
@@ -0,0 +1,112 @@
+//! Environmental hazards: hard landings and lingering in the out-of-bounds dead zone
+//! hurt the local player, and dying to either is announced as a hazard death (the
+//! game program never hears about it, see `Player::hazard_damage`)
+
+use raylib::prelude::*;
+use crate::game::{BoundaryStatus, Hazard, ShakeSource};
+use crate::game::fonts::{draw_hud_text, measure_hud_text};
+use crate::i18n::{t, tr};
+use super::{GameState, emscripten_get_now};
+use super::effects::{DAMAGE_FLASH_DURATION, DAMAGE_FOR_FULL_SHAKE};
+
+impl GameState {
+    /// Apply the damage of this frame's landings to the local player
+    pub(super) fn update_fall_damage(&mut self) {
+        let Some(player) = self.player.as_mut() else {
+            return;
+        };
+        let damage = player.take_landing_damage();
+        if damage > 0.0 {
+            log_info!(Game, "🪂 Fall damage: {:.0}", damage);
+            self.take_hazard_damage(damage, Hazard::Fall);
+        }
+    }
+
+    /// Tick the dead-zone timer; once its grace period is up it hurts every frame
+    pub(super) fn update_out_of_bounds(&mut self, delta: f32) {
+        let rules = self.rules.out_of_bounds;
+        let Some(player) = self.player.as_ref() else {
+            return;
+        };
+        if player.is_dead {
+            self.boundary.reset();
+            self.boundary_status = BoundaryStatus::Inside;
+            return;
+        }
+        self.boundary_status = self.boundary.update(player.position.x, player.position.z, delta, &rules);
+        if let BoundaryStatus::Damaging { damage } = self.boundary_status {
+            self.take_hazard_damage(damage, Hazard::OutOfBounds);
+        }
+    }
+
+    /// Hurt the local player (red edge flash); a killing blow is a hazard death
+    fn take_hazard_damage(&mut self, damage: f32, hazard: Hazard) {
+        let offline = self.offline;
+        let Some(player) = self.player.as_mut() else {
+            return;
+        };
+        // Offline sessions have no death and respawn, so hazards there stop at 1 HP
+        let damage = if offline { damage.min(player.health - 1.0).max(0.0) } else { damage };
+        if damage <= 0.0 || player.is_dead {
+            return;
+        }
+
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        player.apply_hazard_damage(damage, now);
+        self.damage_flash_timer = DAMAGE_FLASH_DURATION;
+        self.shake.add(ShakeSource::Damage, damage / DAMAGE_FOR_FULL_SHAKE);
+
+        if !player.died_from_hazard() {
+            return;
+        }
+        // Respawns like any other death once the timer runs out (see process_single_player_update)
+        player.is_dead = true;
+        player.death_timestamp = now;
+        self.update_death_state_js(true, now);
+        self.drop_weapon_on_death();
+
+        let local = self.get_current_ephemeral_key();
+        self.send_game_event(serde_json::json!({ "type": "hazardDeath", "player": local, "hazard": hazard.id() }));
+        self.announce_hazard_death(&local, hazard, now);
+    }
+
+    /// Another player died to a hazard
+    pub(super) fn apply_hazard_death_event(&mut self, event: &serde_json::Value) {
+        let player = event.get("player").and_then(|v| v.as_str());
+        let hazard = event.get("hazard").and_then(|v| v.as_str()).and_then(Hazard::from_id);
+        let (Some(player), Some(hazard)) = (player, hazard) else {
+            log_warn!(Game, "⚠️ Ignoring malformed hazard death event: {}", event);
+            return;
+        };
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        self.announce_hazard_death(player, hazard, now);
+    }
+
+    /// Kill feed and match log entry for a hazard death (recent attackers still get assists)
+    fn announce_hazard_death(&mut self, victim: &str, hazard: Hazard, now: f64) {
+        let kill = self.damage_tracker.register_hazard_death(victim, hazard, now);
+        self.match_log.record_kill(now, &kill);
+        let local = self.get_current_ephemeral_key();
+        self.publish_kill_event(&kill, &local);
+    }
+
+    /// "Return to the battlefield" with the grace countdown, or a damage notice once it's up
+    pub(super) fn draw_out_of_bounds_warning(&self, d: &mut RaylibDrawHandle) {
+        let detail = match self.boundary_status {
+            BoundaryStatus::Inside => return,
+            BoundaryStatus::Warning { seconds_left } => tr!("hud.out_of_bounds_timer", seconds = seconds_left.ceil() as u32),
+            BoundaryStatus::Damaging { .. } => t("hud.out_of_bounds_damage").to_string(),
+        };
+
+        let screen_width = d.get_screen_width();
+        let top = d.get_screen_height() / 3;
+        let pulse = ((unsafe { emscripten_get_now() } / 250.0).sin() * 0.3 + 0.7) as f32;
+        let red = Color::new(255, 70, 60, (255.0 * pulse) as u8);
+
+        let title = t("hud.out_of_bounds");
+        let title_width = measure_hud_text(d, title, 32);
+        draw_hud_text(d, title, (screen_width - title_width) / 2, top, 32, red);
+        let detail_width = measure_hud_text(d, &detail, 20);
+        draw_hud_text(d, &detail, (screen_width - detail_width) / 2, top + 40, 20, Color::new(255, 255, 255, 230));
+    }
+}
@@ -0,0 +1,393 @@
+//! 2D HUD overlay (crosshair, health bar, minimap) and minimap fog-of-war
+
+use raylib::prelude::*;
+use super::{GameState, emscripten_get_now};
+use crate::game::palette;
+use crate::game::fonts::{draw_hud_text, measure_hud_text};
+use crate::i18n::t;
+use crate::game::{Player, VisibilityTracker, Observer, MarkerKind, split_clan_tag};
+use crate::game::hud_layout::{hud_layout, HudElement};
+
+/// Nameplates are only drawn for players closer than this (units)
+const NAMEPLATE_DISTANCE: f32 = 30.0;
+
+/// Health bar size at HUD scale 1 (pixels)
+const HEALTH_BAR_WIDTH: i32 = 300;
+const HEALTH_BAR_HEIGHT: i32 = 25;
+
+/// Where the health bar goes in the player's HUD layout: (x, y, width, height, scale)
+fn health_bar_rect(screen_width: i32, screen_height: i32) -> (i32, i32, i32, i32, f32) {
+    let layout = hud_layout();
+    let (x, y, width, height) = layout.rect(HudElement::Health, HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT, screen_width, screen_height);
+    (x, y, width, height, layout.placement(HudElement::Health).scale)
+}
+
+impl GameState {
+    /// Draw crosshair at center of screen
+    pub(super) fn draw_crosshair(d: &mut RaylibDrawHandle) {
+        let screen_width = d.get_screen_width();
+        let screen_height = d.get_screen_height();
+        let center_x = screen_width / 2;
+        let center_y = screen_height / 2;
+
+        let crosshair_size = 10;
+        let crosshair_thickness = 2;
+        let gap = 5;
+
+        // Crosshair color (white with slight transparency)
+        let color = Color::new(255, 255, 255, 200);
+
+        // Draw horizontal line (left and right)
+        d.draw_rectangle(center_x - crosshair_size - gap, center_y - crosshair_thickness / 2, crosshair_size, crosshair_thickness, color);
+        d.draw_rectangle(center_x + gap, center_y - crosshair_thickness / 2, crosshair_size, crosshair_thickness, color);
+
+        // Draw vertical line (top and bottom)
+        d.draw_rectangle(center_x - crosshair_thickness / 2, center_y - crosshair_size - gap, crosshair_thickness, crosshair_size, color);
+        d.draw_rectangle(center_x - crosshair_thickness / 2, center_y + gap, crosshair_thickness, crosshair_size, color);
+
+        // Draw center dot
+        d.draw_circle(center_x, center_y, 2.0, color);
+    }
+
+    /// Sniper scope: black surround with a circular lens, reticle and breath meter
+    pub(super) fn draw_scope_overlay(&self, d: &mut RaylibDrawHandle) {
+        let screen_width = d.get_screen_width();
+        let screen_height = d.get_screen_height();
+        let center = Vector2::new(screen_width as f32 / 2.0, screen_height as f32 / 2.0);
+        let radius = screen_width.min(screen_height) as f32 * 0.45;
+        let black = Color::new(0, 0, 0, 255);
+
+        // Blank out everything outside the lens square, then the square's corners
+        let left = (center.x - radius) as i32;
+        let right = (center.x + radius) as i32;
+        d.draw_rectangle(0, 0, left, screen_height, black);
+        d.draw_rectangle(right, 0, screen_width - right, screen_height, black);
+        d.draw_ring(center, radius, radius * 1.5, 0.0, 360.0, 72, black);
+
+        // Reticle: thin full-width lines with thick outer posts
+        let line_color = Color::new(0, 0, 0, 230);
+        d.draw_line_v(Vector2::new(center.x - radius, center.y), Vector2::new(center.x + radius, center.y), line_color);
+        d.draw_line_v(Vector2::new(center.x, center.y - radius), Vector2::new(center.x, center.y + radius), line_color);
+        let post = radius * 0.55;
+        d.draw_rectangle((center.x - radius) as i32, center.y as i32 - 2, (radius - post) as i32, 4, line_color);
+        d.draw_rectangle((center.x + post) as i32, center.y as i32 - 2, (radius - post) as i32, 4, line_color);
+        d.draw_rectangle(center.x as i32 - 2, (center.y + post) as i32, 4, (radius - post) as i32, line_color);
+
+        // Breath meter in the lower part of the lens
+        let meter_width = 200;
+        let meter_x = (screen_width - meter_width) / 2;
+        let meter_y = (center.y + radius * 0.7) as i32;
+        let fill = (meter_width as f32 * self.scope.breath_fraction()) as i32;
+        let fill_color = if self.scope.is_exhausted() {
+            palette::current().negative
+        } else if self.scope.is_holding_breath() {
+            Color::new(120, 200, 255, 255)
+        } else {
+            Color::new(200, 200, 220, 255)
+        };
+        d.draw_rectangle(meter_x, meter_y, meter_width, 6, Color::new(40, 40, 50, 255));
+        d.draw_rectangle(meter_x, meter_y, fill, 6, fill_color);
+        draw_hud_text(d, t("hud.hold_breath"), meter_x, meter_y + 10, 12, Color::new(200, 200, 220, 255));
+    }
+
+    /// Draw health bar where the HUD layout puts it (bottom center by default)
+    pub(super) fn draw_health_bar(d: &mut RaylibDrawHandle, player: &Player) {
+        if !hud_layout().placement(HudElement::Health).visible {
+            return;
+        }
+        let (bar_x, bar_y, bar_width, bar_height, scale) = health_bar_rect(d.get_screen_width(), d.get_screen_height());
+        let font_size = |size: f32| (size * scale) as i32;
+
+        // Background (dark)
+        d.draw_rectangle(bar_x - 2, bar_y - 2, bar_width + 4, bar_height + 4, Color::new(0, 0, 0, 180));
+        d.draw_rectangle(bar_x, bar_y, bar_width, bar_height, Color::new(40, 40, 50, 200));
+
+        // Health fill (gradient from green to red based on health percentage); local regen
+        // isn't on chain yet, so only the confirmed part is solid
+        let predicted = player.predicted_regen();
+        let confirmed = player.health - predicted;
+        let health_percent = confirmed / player.max_health;
+        let fill_width = (bar_width as f32 * health_percent) as i32;
+
+        // Color based on health percentage (green / yellow / red by default)
+        let palette = palette::current();
+        let health_color = palette.health(health_percent);
+
+        d.draw_rectangle(bar_x, bar_y, fill_width, bar_height, health_color);
+
+        // Predicted regen: a pulsing translucent segment after the confirmed fill
+        if predicted >= 1.0 {
+            let pulse = ((unsafe { emscripten_get_now() } / 250.0).sin() * 0.5 + 0.5) as f32;
+            let alpha = (50.0 + 70.0 * pulse) as u8;
+            let pending_width = (bar_width as f32 * player.health / player.max_health) as i32 - fill_width;
+            d.draw_rectangle(bar_x + fill_width, bar_y, pending_width, bar_height, Color::new(palette.positive.r, palette.positive.g, palette.positive.b, alpha));
+            draw_hud_text(
+                d,
+                &format!("+{:.0}", predicted),
+                bar_x + bar_width + 8,
+                bar_y + (bar_height - font_size(16.0)) / 2,
+                font_size(16.0),
+                palette.positive,
+            );
+        }
+
+        // Border
+        d.draw_rectangle_lines(bar_x, bar_y, bar_width, bar_height, Color::new(150, 150, 170, 255));
+
+        // Health text (confirmed health; the predicted regen is shown next to the bar)
+        let health_text = format!("{:.0} / {:.0}", confirmed, player.max_health);
+        let text_size = font_size(16.0);
+        let text_width = measure_hud_text(d, &health_text, text_size);
+        draw_hud_text(
+            d,
+            &health_text,
+            bar_x + (bar_width - text_width) / 2,
+            bar_y + (bar_height - text_size) / 2,
+            text_size,
+            Color::WHITE,
+        );
+
+        // "HEALTH" label
+        draw_hud_text(d, t("hud.health"), bar_x + 5, bar_y - font_size(20.0), font_size(12.0), Color::new(200, 200, 220, 255));
+    }
+
+    /// "Press R to Reload" prompt (centered at top of screen, whatever the HUD layout)
+    pub(super) fn draw_reload_prompt(d: &mut RaylibDrawHandle) {
+        let screen_width = d.get_screen_width();
+        let screen_height = d.get_screen_height();
+        let prompt_text = t("hud.reload_prompt");
+        let text_width = measure_hud_text(d, prompt_text, 30);
+        
+        // Draw centered at top-center of screen with pulsing effect
+        let pulse = ((unsafe { emscripten_get_now() } / 500.0).sin() * 0.3 + 0.7) as f32;
+        let alpha = (255.0 * pulse) as u8;
+        
+        draw_hud_text(
+            d,
+            prompt_text,
+            (screen_width - text_width) / 2,
+            screen_height / 4,
+            30,
+            Color::new(255, 255, 0, alpha), // Yellow with pulsing alpha
+        );
+    }
+
+    /// Names above players in plain sight (walls and smoke hide them)
+    pub(super) fn draw_nameplates(&self, d: &mut RaylibDrawHandle, camera: &Camera3D) {
+        let local_team = self.current_player_team.to_string();
+        let palette = palette::current();
+        let forward = (camera.target - camera.position).normalized();
+
+        for other in self.other_players.iter().filter(|p| p.is_alive) {
+            let head = other.position + Vector3::new(0.0, 2.1, 0.0);
+            let to_head = head - camera.position;
+            if to_head.length() > NAMEPLATE_DISTANCE || to_head.dot(forward) <= 0.0 {
+                continue;
+            }
+            let wall_blocked = self.is_line_blocked(camera.position, head);
+            if wall_blocked || self.smoke_blocks(camera.position, head) {
+                continue;
+            }
+
+            let screen = d.get_world_to_screen(head, *camera);
+            let color = if other.team == local_team { palette.friendly } else { palette.enemy };
+            // Clan tag in a dimmer gold before the name, centered as one label
+            let (tag, name) = split_clan_tag(&other.username);
+            let tag_text = tag.map(|t| format!("[{}] ", t)).unwrap_or_default();
+            let tag_width = measure_hud_text(d, &tag_text, 14);
+            let left = screen.x as i32 - (tag_width + measure_hud_text(d, name, 14)) / 2;
+            if !tag_text.is_empty() {
+                draw_hud_text(d, &tag_text, left, screen.y as i32, 14, Color::new(230, 200, 120, 200));
+            }
+            draw_hud_text(d, name, left + tag_width, screen.y as i32, 14, color);
+        }
+    }
+
+    /// Remaining utility next to the health bar
+    pub(super) fn draw_grenade_count(&self, d: &mut RaylibDrawHandle) {
+        let text = self.grenade_hud_text();
+        let color = if self.smoke_grenades_left > 0 || self.flash_grenades_left > 0 {
+            Color::new(200, 200, 220, 255)
+        } else {
+            Color::new(110, 110, 120, 255)
+        };
+        // Follows the health bar around the HUD layout
+        let (bar_x, bar_y, bar_width, _, scale) = health_bar_rect(d.get_screen_width(), d.get_screen_height());
+        draw_hud_text(d, &text, bar_x + bar_width + 16, bar_y + (5.0 * scale) as i32, (16.0 * scale) as i32, color);
+    }
+
+    /// Spot enemies seen by the local player or a living teammate (fog-of-war)
+    pub(super) fn update_enemy_visibility(&mut self) {
+        let Some(ref player) = self.player else {
+            return;
+        };
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        let local_team = self.current_player_team.to_string();
+
+        let mut observers = Vec::new();
+        if !player.is_dead {
+            observers.push(Observer { position: player.camera.position, yaw: player.yaw.to_radians() });
+        }
+        observers.extend(
+            self.other_players
+                .iter()
+                .filter(|p| p.is_alive && p.team == local_team)
+                .map(|p| Observer { position: p.position + Vector3::new(0.0, 1.6, 0.0), yaw: p.rotation.y }),
+        );
+
+        // Walls and active smoke both break line of sight
+        let line_blocked = |from: Vector3, to: Vector3| {
+            self.is_line_blocked(from, to) || self.smoke_blocks(from, to)
+        };
+
+        let mut seen = Vec::new();
+        let mut dead = Vec::new();
+        for enemy in self.other_players.iter().filter(|p| p.team != local_team) {
+            if !enemy.is_alive {
+                dead.push(enemy.authority.clone());
+                continue;
+            }
+
+            let chest = enemy.position + Vector3::new(0.0, 1.2, 0.0);
+            let spotted = observers
+                .iter()
+                .any(|observer| VisibilityTracker::can_spot(observer, chest, line_blocked));
+
+            // Enemies that just fired stay tracked while revealed
+            if spotted || self.enemy_visibility.is_revealed(&enemy.authority, now) {
+                seen.push((enemy.authority.clone(), enemy.position, enemy.rotation.y));
+            }
+        }
+
+        for (authority, position, yaw) in seen {
+            self.enemy_visibility.note_seen(&authority, position, yaw, now);
+        }
+        for authority in dead {
+            self.enemy_visibility.forget(&authority);
+        }
+        self.enemy_visibility.expire(now);
+    }
+
+    /// Other players the local player is allowed to see on the minimap, after fog-of-war:
+    /// every teammate, plus spotted enemies and fading "last seen" ghosts (spectators see everyone).
+    /// Each entry is (x, z, yaw radians, team, alive 0/1, visibility 0-1 where < 1 is a ghost).
+    pub fn minimap_entries(&self) -> Vec<[f32; 6]> {
+        // Spectators aren't on a team, so they see everyone
+        if self.is_spectating() {
+            return self.other_players
+                .iter()
+                .map(|p| [
+                    p.position.x,
+                    p.position.z,
+                    p.rotation.y,
+                    p.team.parse().unwrap_or(0.0),
+                    if p.is_alive { 1.0 } else { 0.0 },
+                    1.0,
+                ])
+                .collect();
+        }
+
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        let local_team = self.current_player_team.to_string();
+        let enemy_team = if self.current_player_team == 1 { 2.0 } else { 1.0 };
+
+        let teammates = self.other_players
+            .iter()
+            .filter(|p| p.team == local_team)
+            .map(|p| [
+                p.position.x,
+                p.position.z,
+                p.rotation.y,
+                self.current_player_team as f32,
+                if p.is_alive { 1.0 } else { 0.0 },
+                1.0,
+            ]);
+
+        let enemies = self.enemy_visibility.markers(now).into_iter().map(|marker| {
+            let visibility = match marker.kind {
+                MarkerKind::Visible => 1.0,
+                MarkerKind::Ghost { fade } => fade.min(0.99),
+            };
+            [marker.position.x, marker.position.z, marker.yaw, enemy_team, 1.0, visibility]
+        });
+
+        teammates.chain(enemies).collect()
+    }
+
+    /// Draw minimap at top right of screen
+    pub(super) fn draw_minimap(&self, d: &mut RaylibDrawHandle, player: &Player) {
+        let screen_width = d.get_screen_width();
+        let minimap_size = 150;
+        let minimap_x = screen_width - minimap_size - 20;
+        let minimap_y = 20;
+
+        // Draw minimap background (semi-transparent dark)
+        d.draw_rectangle(minimap_x, minimap_y, minimap_size, minimap_size, Color::new(20, 20, 30, 200));
+        d.draw_rectangle_lines(minimap_x, minimap_y, minimap_size, minimap_size, Color::new(100, 100, 120, 255));
+
+        // Map boundaries (50x50 world units)
+        let map_size = 50.0;
+        let scale = minimap_size as f32 / map_size;
+
+        // Draw map bounds
+        let bounds_color = Color::new(80, 80, 100, 255);
+        d.draw_rectangle_lines(minimap_x + 2, minimap_y + 2, minimap_size - 4, minimap_size - 4, bounds_color);
+
+        // Draw Solana corner walls on minimap
+        let wall_size = (15.0 * scale) as i32; // 15 units wall length
+        let corner_color = Color::new(156, 81, 255, 180); // Solana purple
+
+        // Convert world position to minimap position
+        let to_minimap = |world_x: f32, world_z: f32| -> (i32, i32) {
+            let norm_x = (world_x + 25.0) / map_size; // Normalize to 0-1
+            let norm_z = (world_z + 25.0) / map_size;
+            (
+                minimap_x + (norm_x * minimap_size as f32) as i32,
+                minimap_y + (norm_z * minimap_size as f32) as i32,
+            )
+        };
+
+        // Draw corner markers
+        let corners = [(25.0, 25.0), (-25.0, 25.0), (25.0, -25.0), (-25.0, -25.0)];
+        for corner in corners.iter() {
+            let (mx, my) = to_minimap(corner.0, corner.1);
+            d.draw_circle(mx, my, 3.0, corner_color);
+        }
+
+        // Teammates are always shown
+        let palette = palette::current();
+        let local_team = self.current_player_team.to_string();
+        for teammate in self.other_players.iter().filter(|p| p.is_alive && p.team == local_team) {
+            let (mx, my) = to_minimap(teammate.position.x, teammate.position.z);
+            d.draw_circle(mx, my, 4.0, palette.friendly);
+        }
+
+        // Enemies only when spotted (solid) or as a fading "last seen" ghost
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        for marker in self.enemy_visibility.markers(now) {
+            let (mx, my) = to_minimap(marker.position.x, marker.position.z);
+            match marker.kind {
+                MarkerKind::Visible => d.draw_circle(mx, my, 4.0, palette.enemy),
+                MarkerKind::Ghost { fade } => {
+                    d.draw_circle_lines(mx, my, 4.0, Color::new(palette.enemy.r, palette.enemy.g, palette.enemy.b, (200.0 * fade) as u8));
+                }
+            }
+        }
+
+        // Draw player position and direction
+        let (player_mx, player_my) = to_minimap(player.position.x, player.position.z);
+
+        // Player dot
+        d.draw_circle(player_mx, player_my, 5.0, palette.local_marker);
+
+        // Player direction indicator
+        let yaw_rad = player.yaw.to_radians();
+        let dir_length = 12.0;
+        let dir_end_x = player_mx + (yaw_rad.cos() * dir_length) as i32;
+        let dir_end_y = player_my + (yaw_rad.sin() * dir_length) as i32;
+        d.draw_line(player_mx, player_my, dir_end_x, dir_end_y, palette.local_marker);
+
+        // Draw "MINIMAP" label
+        draw_hud_text(d, t("hud.minimap"), minimap_x + 5, minimap_y - 18, 12, Color::new(200, 200, 220, 255));
+    }
+}
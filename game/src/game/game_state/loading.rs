@@ -0,0 +1,58 @@
+//! Map loading screen: a large map is decoded a step per frame (see `MapLoader`) while a
+//! progress bar keeps the page responsive, then it's loaded like any other map
+
+use raylib::prelude::*;
+use super::{GameState, GameMode};
+use crate::diagnostics;
+use crate::game::palette;
+use crate::game::fonts::{draw_hud_text, measure_hud_text};
+use crate::i18n::tr;
+use crate::map::{LoadStep, MapLoader};
+
+/// Progress bar size
+const BAR_WIDTH: i32 = 320;
+const BAR_HEIGHT: i32 = 8;
+
+impl GameState {
+    /// Decode a map over the next frames behind the loading screen; it replaces the current
+    /// one (as `load_map`) once done
+    pub fn begin_map_load(&mut self, loader: MapLoader) {
+        self.map_loader = Some(loader);
+        self.map_stream = None;
+        self.mode = GameMode::Playing;
+    }
+
+    /// Advance the pending map load by one step; true while it's still running
+    pub(super) fn step_map_load(&mut self) -> bool {
+        let Some(loader) = self.map_loader.as_mut() else {
+            return false;
+        };
+        match loader.step() {
+            LoadStep::Pending => return true,
+            LoadStep::Done(loaded) => {
+                log_info!(Game, "✅ Map deserialized successfully: '{}' with {} objects", loaded.map.name, loaded.map.objects.len());
+                // A stream announced with the head survives loading it
+                let stream = self.map_stream.take();
+                self.load_built_map(loaded);
+                self.map_stream = stream;
+            }
+            LoadStep::Failed(error) => diagnostics::fatal(&error),
+        }
+        self.map_loader = None;
+        false
+    }
+
+    /// Loading screen in place of the world while the map decodes
+    pub(super) fn draw_map_loading(&self, d: &mut RaylibDrawHandle, progress: f32) {
+        let (width, height) = (d.get_screen_width(), d.get_screen_height());
+        d.draw_rectangle(0, 0, width, height, Color::new(8, 6, 16, 255));
+
+        let label = tr!("hud.loading_map", percent = (progress * 100.0) as i32);
+        let label_width = measure_hud_text(d, &label, 24);
+        draw_hud_text(d, &label, (width - label_width) / 2, height / 2 - 40, 24, Color::new(200, 200, 220, 255));
+
+        let (x, y) = ((width - BAR_WIDTH) / 2, height / 2);
+        d.draw_rectangle(x, y, BAR_WIDTH, BAR_HEIGHT, Color::new(40, 36, 56, 255));
+        d.draw_rectangle(x, y, (BAR_WIDTH as f32 * progress.clamp(0.0, 1.0)) as i32, BAR_HEIGHT, palette::current().positive);
+    }
+}
@@ -0,0 +1,242 @@
+//! Map set pieces: trigger volumes the local player walks into (teleports, movers,
+//! weapon grants, sounds, objectives), the movers they open and the map script. Triggers fire
+//! on each client for its own player, so what they open or spawn is announced; timed script
+//! rules run on every client from match time and need no announcing
+
+use raylib::prelude::*;
+use crate::map::{Map, MapScript, ScriptCommand, SpatialIndex, TriggerAction};
+use crate::raycaster::{Map2D, Pvs, MAP_GRID_CELL_SIZE, PVS_ZONE_SIZE};
+use crate::game::{InputAccumulator, InputChain, TriggerVolume, WeaponKind, WeaponPickup, ScriptFire};
+use crate::audio::{mixer, AudioChannel};
+use crate::game::fonts::{draw_hud_text, measure_hud_text};
+use crate::i18n::t;
+use super::{GameState, emscripten_get_now};
+
+/// Feet are tested this far above the ground so volumes resting on it still catch them
+const TRIGGER_FOOT_OFFSET: f32 = 0.1;
+
+/// How long a new objective stays on screen (seconds), fading over the last one
+const OBJECTIVE_SHOW_TIME: f64 = 6.0;
+
+/// The map's script, or None if it has none or it doesn't parse (the editor shows why)
+pub(super) fn load_map_script(map: &Map) -> Option<MapScript> {
+    if map.script.trim().is_empty() {
+        return None;
+    }
+    match MapScript::parse(&map.script) {
+        Ok(script) => Some(script),
+        Err(error) => {
+            log_warn!(Game, "⚠️ Ignoring map script: {}", error.message());
+            None
+        }
+    }
+}
+
+impl GameState {
+    /// Fire the triggers the local player entered this frame, run the map script and slide open movers
+    pub(super) fn update_map_triggers(&mut self, delta: f32) {
+        let mut tags = Vec::new();
+        let actions: Vec<(TriggerAction, Vector3)> = match (&self.map, self.player.as_ref().filter(|p| !p.is_dead)) {
+            (Some(map), Some(player)) => {
+                let point = player.position + Vector3::new(0.0, TRIGGER_FOOT_OFFSET, 0.0);
+                let volumes = map.trigger_volumes().map(|(trigger, bounds)| {
+                    (trigger.object, TriggerVolume { min: bounds.min, max: bounds.max }, trigger.once)
+                });
+                let fired = self.map_triggers.update(volumes, point);
+                // The fired objects' tags start the script's `on <tag>` rules
+                for &object in &fired {
+                    if let Some(label) = map.label(object as usize) {
+                        tags.extend(label.tags.split(',').filter(|tag| !tag.is_empty()).map(str::to_string));
+                    }
+                }
+                map.trigger_volumes()
+                    .filter(|(trigger, _)| fired.contains(&trigger.object))
+                    .map(|(trigger, bounds)| (trigger.action.clone(), (bounds.min + bounds.max) * 0.5))
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        for (action, at) in actions {
+            self.run_trigger_action(action, Some(at), true);
+        }
+
+        self.update_map_script(&tags);
+        self.update_movers(delta);
+    }
+
+    /// Seconds since the match started (since the map was started offline)
+    fn script_time(&self) -> f32 {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        (now - self.match_started_at.unwrap_or(self.script_epoch)) as f32
+    }
+
+    /// Start the `on` rules for `tags` and run whatever script commands came due
+    fn update_map_script(&mut self, tags: &[String]) {
+        let Some(ref script) = self.script else {
+            return;
+        };
+        let time = self.script_time();
+        for tag in tags {
+            self.script_runner.fire_tag(script, tag, time);
+        }
+        let fires = self.script_runner.update(script, time);
+        for fire in fires {
+            self.run_script_command(fire);
+        }
+    }
+
+    fn run_script_command(&mut self, fire: ScriptFire) {
+        match fire.command {
+            // Timed rules run on every client, so only trigger-started ones are announced
+            ScriptCommand::Action(action) => self.run_trigger_action(action, None, !fire.timed),
+            ScriptCommand::Spawn { weapon, tag } => {
+                let anchor = self.map.as_ref().and_then(|map| map.tag_anchor(&tag));
+                let (Some(weapon), Some(position)) = (WeaponKind::from_id(&weapon), anchor) else {
+                    log_warn!(Game, "⚠️ Script can't spawn '{}' at '{}'", weapon, tag);
+                    return;
+                };
+                let started_at = self.match_started_at.unwrap_or(self.script_epoch);
+                // Every client derives the same id for a timed spawn, so a pickup removes it everywhere
+                let id = if fire.timed {
+                    format!("script-{}-{}", fire.rule, (fire.at * 1000.0) as u64)
+                } else {
+                    format!("{}-script-{}-{}", self.get_current_ephemeral_key(), fire.rule, (fire.at * 1000.0) as u64)
+                };
+                let pickup = WeaponPickup { id, weapon, position, dropped_at: started_at + fire.at as f64 };
+                self.spawn_pickup(pickup, !fire.timed);
+            }
+        }
+    }
+
+    /// Run a trigger or script action; `at` is where a trigger fired (scripts have no place),
+    /// `announce` tells the other clients about doors it opens
+    fn run_trigger_action(&mut self, action: TriggerAction, at: Option<Vector3>, announce: bool) {
+        log_info!(Game, "🚩 Trigger: {:?}", action);
+        match action {
+            TriggerAction::TeleportToTag(tag) => {
+                let Some(anchor) = self.map.as_ref().and_then(|map| map.tag_anchor(&tag)) else {
+                    log_warn!(Game, "⚠️ No object tagged '{}' to teleport to", tag);
+                    return;
+                };
+                if let Some(player) = self.player.as_mut() {
+                    player.set_position(anchor);
+                    // The jump isn't a step the movement chain could have made, so start a new one
+                    self.input_chain = InputChain::default();
+                    self.input_pending = InputAccumulator::default();
+                }
+            }
+            TriggerAction::OpenMover(tag) => {
+                self.open_movers(&tag);
+                if announce {
+                    self.send_game_event(serde_json::json!({ "type": "moverOpen", "tag": tag }));
+                }
+            }
+            TriggerAction::GrantPickup(weapon) => match WeaponKind::from_id(&weapon) {
+                Some(weapon) => self.grant_weapon(weapon),
+                None => log_warn!(Game, "⚠️ Trigger grants unknown weapon '{}'", weapon),
+            },
+            TriggerAction::PlaySound(sound) => match at {
+                Some(at) => self.play_world_sound(sound.path(), at, 0.8),
+                None => mixer::play_sound(sound.path(), AudioChannel::Effects, 0.8),
+            },
+            TriggerAction::StartObjective(text) => {
+                let now = unsafe { emscripten_get_now() / 1000.0 };
+                self.objective = Some((text, now));
+            }
+            // Only starts the script's `on` rules for the trigger's tags
+            TriggerAction::Script => {}
+        }
+    }
+
+    /// Sink every object tagged `tag` into the ground (triggers sharing the tag stay put)
+    fn open_movers(&mut self, tag: &str) {
+        let Some(ref map) = self.map else {
+            return;
+        };
+        for index in map.objects_with_tag(tag).filter(|&i| i < map.objects.len() && map.trigger(i).is_none()) {
+            let object = &map.objects[index];
+            let Some(bounds) = object.collision_bounds() else {
+                continue;
+            };
+            let closed = object.get_position();
+            let open = closed - Vector3::new(0.0, bounds.max.y - bounds.min.y, 0.0);
+            self.movers.open(index, closed, open);
+        }
+    }
+
+    /// Another player opened a mover
+    pub(super) fn apply_mover_event(&mut self, event: &serde_json::Value) {
+        match event.get("tag").and_then(|v| v.as_str()) {
+            Some(tag) => self.open_movers(tag),
+            None => log_warn!(Game, "⚠️ Ignoring malformed mover event: {}", event),
+        }
+    }
+
+    /// Move sliding objects, keeping collision in step with them
+    fn update_movers(&mut self, delta: f32) {
+        let moved = self.movers.update(delta);
+        if moved.is_empty() {
+            return;
+        }
+        self.place_map_objects(&moved);
+        // Sight lines and the PVS only need the final position
+        self.rebuild_map_queries(!self.movers.is_moving());
+    }
+
+    /// Close every mover, re-arm every trigger and restart the script (new match)
+    pub(super) fn reset_map_triggers(&mut self) {
+        self.map_triggers.reset();
+        self.objective = None;
+        self.script_runner.reset();
+        self.script_epoch = unsafe { emscripten_get_now() / 1000.0 };
+        let closed = self.movers.reset();
+        if !closed.is_empty() {
+            self.place_map_objects(&closed);
+            self.rebuild_map_queries(true);
+        }
+    }
+
+    fn place_map_objects(&mut self, positions: &[(usize, Vector3)]) {
+        let Some(ref mut map) = self.map else {
+            return;
+        };
+        for &(index, position) in positions {
+            if let Some(object) = map.objects.get_mut(index) {
+                object.set_position(position);
+            }
+        }
+    }
+
+    /// Rebuild the collision index after map objects moved, and the sight grid and PVS with `grid`
+    pub(super) fn rebuild_map_queries(&mut self, grid: bool) {
+        let Some(ref map) = self.map else {
+            return;
+        };
+        self.map_index = Some(SpatialIndex::build(map));
+        if grid {
+            let map_grid = Map2D::from_map(map, MAP_GRID_CELL_SIZE);
+            self.pvs = Some(Pvs::build(&map_grid, PVS_ZONE_SIZE));
+            self.map_grid = Some(map_grid);
+        }
+    }
+
+    /// The objective a trigger started, big under the top of the screen for a few seconds
+    pub(super) fn draw_objective(&self, d: &mut RaylibDrawHandle) {
+        let Some((ref text, started_at)) = self.objective else {
+            return;
+        };
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        let fade = (started_at + OBJECTIVE_SHOW_TIME - now).min(1.0) as f32;
+        if fade <= 0.0 || text.is_empty() {
+            return;
+        }
+
+        let screen_width = d.get_screen_width();
+        let top = d.get_screen_height() / 6;
+        let title = t("hud.objective");
+        let title_width = measure_hud_text(d, title, 20);
+        draw_hud_text(d, title, (screen_width - title_width) / 2, top, 20, Color::new(255, 190, 60, (255.0 * fade) as u8));
+        let text_width = measure_hud_text(d, text, 28);
+        draw_hud_text(d, text, (screen_width - text_width) / 2, top + 28, 28, Color::new(255, 255, 255, (240.0 * fade) as u8));
+    }
+}
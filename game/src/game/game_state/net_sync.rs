@@ -0,0 +1,792 @@
+//! Network synchronization: WebSocket subscriptions, input sending,
+//! remote player interpolation and local reconciliation
+
+use raylib::prelude::*;
+use crate::game::{is_bot_authority, ShakeSource, ThreatKind, WeaponKind};
+use super::{GameState, OtherPlayer, PlayerHit, emscripten_run_script, emscripten_run_script_string, emscripten_get_now};
+use crate::game::{Player, MovementInput, capsule_height};
+use crate::audio::{play_ui_sound, UiSound};
+use super::combat::GUNSHOT_SOUND;
+use super::effects::DAMAGE_FOR_FULL_SHAKE;
+
+/// Health at or below which the low-health warning plays
+const LOW_HEALTH_THRESHOLD: f32 = 25.0;
+
+/// Read an `[x, y, z]` array from a game event
+pub(super) fn event_vector(event: &serde_json::Value, key: &str) -> Option<Vector3> {
+    let values = event.get(key)?.as_array()?;
+    let component = |i: usize| values.get(i).and_then(|v| v.as_f64()).map(|v| v as f32);
+    Some(Vector3::new(component(0)?, component(1)?, component(2)?))
+}
+
+impl GameState {
+    /// Set the current game for blockchain synchronization
+    pub fn set_current_game(&mut self, game_pubkey: String) {
+        log_info!(Net, "🎮 Setting current game: {}", game_pubkey);
+        self.end_offline_session();
+        self.current_game_pubkey = Some(game_pubkey.clone());
+        self.damage_tracker.clear();
+        self.enemy_visibility.clear();
+        self.match_started_at = None;
+        self.votes.clear();
+        self.match_log.clear();
+        self.replay.clear();
+        // The leader turns backfill back on once the match is set up
+        self.backfill.clear();
+
+        // Initialize WebSocket connection and subscribe to player updates
+        self.setup_websocket_subscriptions(&game_pubkey);
+    }
+
+    /// Setup WebSocket subscriptions for real-time player updates
+    pub(super) fn setup_websocket_subscriptions(&mut self, game_pubkey: &str) {
+        use std::os::raw::c_char;
+        use std::ffi::CString;
+
+        if self.websocket_subscribed {
+            log_warn!(Net, "⚠️ Already subscribed to WebSocket updates");
+            return;
+        }
+
+        log_info!(Net, "🔌 ==========================================");
+        log_info!(Net, "🔌 SETTING UP WEBSOCKET SUBSCRIPTIONS");
+        log_info!(Net, "🔌 Game: {}", game_pubkey);
+        log_info!(Net, "🔌 This should only happen ONCE per game!");
+        log_info!(Net, "🔌 ==========================================" );
+
+        // Call JavaScript to connect WebSocket and subscribe to game players
+        let js_code = format!(
+            r#"
+            (async () => {{
+                try {{
+                    // Connect to WebSocket
+                    console.log('🔌 Connecting to WebSocket...');
+                    const connectResult = await window.gameBridge.connectWebSocket();
+                    if (!connectResult.success) {{
+                        console.error('❌ Failed to connect WebSocket:', connectResult.error);
+                        return;
+                    }}
+                    console.log('✅ WebSocket connected');
+
+                    // Subscribe to all players in the game
+                    console.log('📡 Subscribing to game players...');
+                    const subscribeResult = await window.gameBridge.subscribeToGamePlayers('{}');
+                    if (!subscribeResult.success) {{
+                        console.error('❌ Failed to subscribe to game players:', subscribeResult.error);
+                        return;
+                    }}
+                    console.log('✅ Subscribed to', subscribeResult.playerCount, 'players');
+                }} catch (error) {{
+                    console.error('❌ Error setting up WebSocket:', error);
+                }}
+            }})();
+            "#,
+            game_pubkey
+        );
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            emscripten_run_script(c_str.as_ptr());
+        }
+
+        self.websocket_subscribed = true;
+        log_info!(Net, "✅ ==========================================");
+        log_info!(Net, "✅ WEBSOCKET SUBSCRIPTIONS SETUP COMPLETE!");
+        log_info!(Net, "✅ From now on, player updates via WebSocket");
+        log_info!(Net, "✅ NO MORE HTTP POLLING should occur!");
+        log_info!(Net, "✅ ==========================================");
+    }
+
+    /// Set the current player authority for identifying the local player
+    pub fn set_player_authority(&mut self, authority: String) {
+        self.current_player_authority = Some(authority);
+    }
+
+    /// Cleanup WebSocket subscriptions when leaving the game
+    pub(super) fn cleanup_websocket_subscriptions(&mut self) {
+        use std::os::raw::c_char;
+        use std::ffi::CString;
+
+        if !self.websocket_subscribed {
+            return;
+        }
+
+        log_info!(Net, "🔌 Cleaning up WebSocket subscriptions");
+
+        if let Some(game_pubkey) = &self.current_game_pubkey {
+            let js_code = format!(
+                r#"
+                (async () => {{
+                    try {{
+                        if (window.gameBridge && window.gameBridge.unsubscribeFromGamePlayers) {{
+                            await window.gameBridge.unsubscribeFromGamePlayers('{}');
+                            console.log('✅ Unsubscribed from game players');
+                        }}
+                        if (window.gameBridge && window.gameBridge.disconnectWebSocket) {{
+                            window.gameBridge.disconnectWebSocket();
+                            console.log('✅ WebSocket disconnected');
+                        }}
+                    }} catch (error) {{
+                        console.error('❌ Error cleaning up WebSocket:', error);
+                    }}
+                }})();
+                "#,
+                game_pubkey
+            );
+
+            unsafe {
+                let c_str = CString::new(js_code).unwrap();
+                emscripten_run_script(c_str.as_ptr());
+            }
+        }
+
+        self.websocket_subscribed = false;
+        self.other_players.clear();
+        log_info!(Net, "✅ WebSocket cleanup complete");
+    }
+
+    /// Build this tick's movement input from the keyboard/joystick and the player's view
+    pub(super) fn movement_input(&self, rl: &RaylibHandle, player: &Player, delta: f32) -> MovementInput {
+        // Combine WASD and joystick input for blockchain
+        let joystick = self.get_joystick_input_from_js().unwrap_or((false, false, false, false));
+
+        // Movement is locked outside the live phase (freeze time, round end); rotation still goes out
+        let movable = self.round.is_live();
+
+        MovementInput {
+            forward: movable && (rl.is_key_down(KeyboardKey::KEY_W) || joystick.0),
+            backward: movable && (rl.is_key_down(KeyboardKey::KEY_S) || joystick.1),
+            left: movable && (rl.is_key_down(KeyboardKey::KEY_A) || joystick.2),
+            right: movable && (rl.is_key_down(KeyboardKey::KEY_D) || joystick.3),
+            running: player.is_running,
+            crouching: player.is_crouching,
+            jump: movable && rl.is_key_down(KeyboardKey::KEY_SPACE),
+            // Server expects rotation in radians
+            yaw: player.yaw.to_radians(),
+            pitch: player.pitch.to_radians(),
+            delta, // Use actual frame delta time
+        }
+    }
+
+    /// Send one tick of input (with its tick number and chained checksum) to the game program
+    pub(super) fn send_player_input(&self, input: &MovementInput) {
+        use std::ffi::CString;
+
+        // Get the game ID - return early if not set
+        let game_id = match &self.current_game_pubkey {
+            Some(id) => id,
+            None => {
+                // No game ID set, can't send input
+                return;
+            }
+        };
+
+        // Prepare input data as JSON - now sending rotation instead of mouse deltas
+        let input_json = format!(
+            r#"{{
+                "forward": {},
+                "backward": {},
+                "left": {},
+                "right": {},
+                "jump": {},
+                "rotationX": {},
+                "rotationY": {},
+                "rotationZ": {},
+                "deltaTime": {},
+                "gameId": "{}",
+                "tick": {},
+                "checksum": {}
+            }}"#,
+            input.forward,
+            input.backward,
+            input.left,
+            input.right,
+            input.jump,
+            input.pitch,    // rotationX (pitch)
+            input.yaw,      // rotationY (yaw) - main horizontal rotation
+            0.0,            // rotationZ (roll) - not used for FPS
+            input.delta,
+            game_id,        // Add the game ID (lobby public key)
+            self.input_chain.tick,
+            self.input_chain.checksum
+        );
+
+        // Call JavaScript function to send input
+        let js_code = format!(
+            r#"
+            (async () => {{
+                try {{
+                    if (window.gameBridge && window.gameBridge.sendPlayerInput) {{
+                        const input = {};
+                        await window.gameBridge.sendPlayerInput(input);
+                    }}
+                }} catch (error) {{
+                    console.error('Failed to send player input:', error);
+                }}
+            }})();
+            "#,
+            input_json
+        );
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            emscripten_run_script(c_str.as_ptr());
+        }
+    }
+
+    /// Broadcast a gameplay event (e.g. a grenade throw) to the other clients in the match
+    pub(super) fn send_game_event(&self, event: serde_json::Value) {
+        use std::ffi::CString;
+
+        let Some(ref game_id) = self.current_game_pubkey else {
+            return;
+        };
+
+        let js_code = format!(
+            r#"
+            (function() {{
+                if (window.gameBridge && window.gameBridge.sendGameEvent) {{
+                    window.gameBridge.sendGameEvent('{}', {});
+                }}
+            }})();
+            "#,
+            game_id, event
+        );
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            emscripten_run_script(c_str.as_ptr());
+        }
+    }
+
+    /// Publish updates for players with no GamePlayer account (backfill bots); other clients
+    /// get them alongside the chain's player updates (see process_websocket_updates_data)
+    pub(super) fn send_player_updates(&self, updates: &[serde_json::Value]) {
+        use std::ffi::CString;
+
+        let Some(ref game_id) = self.current_game_pubkey else {
+            return;
+        };
+
+        let js_code = format!(
+            r#"
+            (function() {{
+                if (window.gameBridge && window.gameBridge.sendPlayerUpdates) {{
+                    window.gameBridge.sendPlayerUpdates('{}', {});
+                }}
+            }})();
+            "#,
+            game_id, serde_json::Value::from(updates)
+        );
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            emscripten_run_script(c_str.as_ptr());
+        }
+    }
+
+    /// Apply gameplay events relayed from other clients (grenades, dropped weapons, votes, slides, hazard deaths)
+    pub(super) fn process_game_events(&mut self) {
+        for event in self.take_game_events() {
+            match event.get("type").and_then(|t| t.as_str()) {
+                Some("smoke") | Some("flash") => self.apply_grenade_event(&event),
+                Some("weaponDrop") | Some("weaponPickup") => self.apply_weapon_drop_event(&event),
+                Some("voteRequest") | Some("voteCall") | Some("voteCast") => self.apply_vote_event(&event),
+                Some("spray") => self.apply_spray_event(&event),
+                Some("emote") => self.apply_emote_event(&event),
+                Some("slide") => self.apply_slide_event(&event),
+                Some("hazardDeath") => self.apply_hazard_death_event(&event),
+                Some("moverOpen") => self.apply_mover_event(&event),
+                Some("botHit") => self.apply_bot_hit_event(&event),
+                Some("botKill") => self.apply_bot_kill_event(&event),
+                other => log_warn!(Net, "⚠️ Unknown game event type: {:?}", other),
+            }
+        }
+    }
+
+    /// Drain gameplay events received from other clients since the last call
+    fn take_game_events(&self) -> Vec<serde_json::Value> {
+        use std::ffi::CString;
+
+        let js_code = r#"
+            (function() {
+                if (window.gameBridge && window.gameBridge.getGameEvents) {
+                    return window.gameBridge.getGameEvents();
+                }
+                return '[]';
+            })()
+        "#;
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            let result_ptr = emscripten_run_script_string(c_str.as_ptr());
+            if result_ptr.is_null() {
+                return Vec::new();
+            }
+            let json = std::ffi::CStr::from_ptr(result_ptr).to_string_lossy();
+            serde_json::from_str(&json).unwrap_or_default()
+        }
+    }
+
+    /// Interpolate remote players towards their dead-reckoned positions
+    pub(super) fn interpolate_other_players(&mut self, delta: f32) {
+        // Smoothly interpolate other players with dead reckoning for latency compensation
+        // This runs every frame for buttery smooth movement
+        let current_time = unsafe { emscripten_get_now() / 1000.0 };
+        let interest: Vec<bool> = self.other_players.iter().map(|p| self.in_interest(p)).collect();
+        for (player, in_interest) in self.other_players.iter_mut().zip(interest) {
+            // Players behind walls (outside the PVS) skip smoothing and sit at their last known spot
+            if !in_interest {
+                player.position = player.target_position;
+                player.rotation = player.target_rotation;
+                continue;
+            }
+
+            // Dead reckoning: predict position based on velocity
+            // This compensates for network latency by extrapolating movement
+            let time_since_update = (current_time - player.last_update_time) as f32;
+
+            // Extrapolate position based on velocity (but limit to prevent overshooting)
+            let extrapolation_time = time_since_update.min(self.netcode.max_extrapolation_time);
+            let predicted_position = player.target_position + player.velocity * extrapolation_time;
+
+            // Interpolate towards predicted position (not just target)
+            // This makes remote players appear smooth even with latency
+            player.position = player.position.lerp(predicted_position, delta * self.netcode.position_interp_speed);
+
+            // Interpolate rotation with GENTLER speed to reduce gun jitter
+            // Rotation needs to be smoother than position for visual comfort
+            player.rotation = player.rotation.lerp(player.target_rotation, delta * self.netcode.rotation_interp_speed);
+        }
+    }
+
+    /// Reconcile the client-predicted local player with the server position
+    pub(super) fn reconcile_local_player(&mut self, delta: f32) {
+        // IMPROVED CLIENT-SIDE PREDICTION with Smart Reconciliation
+        // The local player movement is purely client-side for maximum responsiveness
+        // We reconcile with server position using a smart algorithm that reduces rubber-banding
+        if let Some(player) = &mut self.player {
+            // Calculate distance between client prediction and server position
+            let position_error = (player.position - player.target_position).length();
+
+            // Adaptive error threshold based on movement speed
+            // Moving players get more tolerance to reduce rubber-banding during lag
+            let velocity = (player.position - player.target_position).length() / delta;
+            let error_threshold = self.netcode.error_threshold(velocity);
+
+            if position_error > error_threshold {
+                // Large errors (> snap threshold) = teleportation or major desync
+                // Medium errors = gradual reconciliation
+                if position_error > self.netcode.snap_threshold {
+                    // Snap to server position for major desyncs
+                    player.position = player.target_position;
+                    log_warn!(Net, "⚠️ Large position error detected ({:.2}), snapping to server position", position_error);
+                } else {
+                    // Adaptive correction speed based on error magnitude
+                    // Larger errors = faster correction, smaller errors = smoother correction
+                    let correction_speed = self.netcode.correction_speed(position_error);
+                    
+                    // Smooth interpolation towards server position
+                    let correction_factor = (delta * correction_speed).min(1.0);
+                    player.position = player.position.lerp(player.target_position, correction_factor);
+                    
+                    // Log reconciliation for debugging
+                    if position_error > 1.0 {
+                        log_debug!(Net, "🔧 Reconciling position: error={:.2}, speed={:.1}, factor={:.3}", 
+                            position_error, correction_speed, correction_factor);
+                    }
+                }
+            }
+
+            // Rotation remains purely client-authoritative for responsiveness
+            // The server receives and broadcasts our rotation, no reconciliation needed
+            player.target_yaw = player.yaw;
+            player.target_pitch = player.pitch;
+        }
+    }
+
+    /// Process WebSocket player updates (replaces HTTP polling)
+    /// This is called every frame to check for new player position updates from WebSocket
+    pub(super) fn process_websocket_player_updates(&mut self) {
+        use std::os::raw::c_char;
+        use std::ffi::CString;
+
+        // Check if we have WebSocket subscriptions active
+        if !self.websocket_subscribed {
+            return;
+        }
+
+        // Call JavaScript to get any pending WebSocket updates
+        let js_code = r#"
+            (() => {
+                if (window.gameBridge && window.gameBridge.getWebSocketPlayerUpdates) {
+                    return window.gameBridge.getWebSocketPlayerUpdates();
+                }
+                return '{}';
+            })();
+        "#;
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            let result_ptr = emscripten_run_script_string(c_str.as_ptr());
+
+            if !result_ptr.is_null() {
+                let result_str = std::ffi::CStr::from_ptr(result_ptr)
+                    .to_string_lossy()
+                    .into_owned();
+
+                if !result_str.is_empty() && result_str != "{}" {
+                    self.process_websocket_updates_data(&result_str);
+                }
+            }
+        }
+    }
+
+    /// Process WebSocket update data
+    pub(super) fn process_websocket_updates_data(&mut self, json_str: &str) {
+        use serde_json::Value;
+
+        // Parse the JSON containing WebSocket updates
+        if let Ok(updates) = serde_json::from_str::<Value>(json_str) {
+            // Updates is a map of accountPubkey -> { timestamp, data, parsed }
+            if let Some(updates_obj) = updates.as_object() {
+                for (_account_pubkey, update) in updates_obj {
+                    // Backfill bots come from the lobby leader over the relay, not from the chain
+                    let parsed = update.get("parsed");
+                    let authority = parsed.and_then(|p| p.get("authority")).and_then(|a| a.as_str());
+                    if let (Some(parsed), Some(true)) = (parsed, authority.map(is_bot_authority)) {
+                        self.apply_bot_update(parsed, update.get("receivedAt").and_then(|v| v.as_f64()));
+                        continue;
+                    }
+                    // First try to get the parsed data (already decoded by JavaScript)
+                    if let Some(parsed) = update.get("parsed") {
+                        //println!("📡 Processing WebSocket update (pre-parsed)");
+                        self.process_single_player_update(parsed);
+                    }
+                    // Fallback: try to parse from raw account data
+                    else if let Some(account_data) = update.get("data") {
+                        if let Some(value) = account_data.get("value") {
+                            if let Some(data) = value.get("data") {
+                                if let Some(parsed) = data.get("parsed") {
+                                    //println!("📡 Processing WebSocket update (fallback parsing)");
+                                    self.process_single_player_update(parsed);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Process a single player update from WebSocket
+    pub(super) fn process_single_player_update(&mut self, player_data: &serde_json::Value) {
+        // Extract player information
+        let authority = player_data.get("authority")
+            .and_then(|v: &serde_json::Value| v.as_str())
+            .unwrap_or("");
+
+        // Get current player's ephemeral key for local player reconciliation
+        let current_ephemeral_key = self.get_current_ephemeral_key();
+        let is_local_player = authority == current_ephemeral_key;
+
+        // Parse position
+        let pos_x = player_data.get("positionX")
+            .and_then(|v: &serde_json::Value| v.as_f64())
+            .unwrap_or(0.0) as f32;
+        let pos_y = player_data.get("positionY")
+            .and_then(|v: &serde_json::Value| v.as_f64())
+            .unwrap_or(0.0) as f32;
+        let pos_z = player_data.get("positionZ")
+            .and_then(|v: &serde_json::Value| v.as_f64())
+            .unwrap_or(0.0) as f32;
+
+        // Parse rotation (WebSocket sends radians, use directly)
+        let rot_x = player_data.get("rotationX")
+            .and_then(|v: &serde_json::Value| v.as_f64())
+            .unwrap_or(0.0) as f32;
+        let rot_y = player_data.get("rotationY")
+            .and_then(|v: &serde_json::Value| v.as_f64())
+            .unwrap_or(0.0) as f32;
+        let rot_z = player_data.get("rotationZ")
+            .and_then(|v: &serde_json::Value| v.as_f64())
+            .unwrap_or(0.0) as f32;
+
+        // Parse other data
+        let username = player_data.get("username")
+            .and_then(|v: &serde_json::Value| v.as_str())
+            .unwrap_or("Unknown")
+            .to_string();
+
+        let team_num = player_data.get("team")
+            .and_then(|v: &serde_json::Value| v.as_u64())
+            .unwrap_or(1);
+        // Team 1 = Team A (Blue), Team 2 = Team B (Red)
+        // Store team number directly as "1" or "2" for consistent comparison
+        let team = team_num.to_string();
+
+        let is_alive = player_data.get("isAlive")
+            .and_then(|v: &serde_json::Value| v.as_bool())
+            .unwrap_or(true);
+
+        // Parse health
+        let health = player_data.get("health")
+            .and_then(|v: &serde_json::Value| v.as_u64())
+            .unwrap_or(100) as f32;
+
+        let bullet_count = player_data.get("bulletCount")
+            .and_then(|v: &serde_json::Value| v.as_u64())
+            .unwrap_or(10) as u8;
+
+        // Attribute damage and resolve kills/assists from the health change
+        let observed_at = unsafe { emscripten_get_now() / 1000.0 };
+        let observation = self.damage_tracker.observe_player(authority, health, bullet_count, observed_at);
+
+        let new_position = Vector3::new(pos_x, pos_y, pos_z);
+        let new_rotation = Vector3::new(rot_x, rot_y, rot_z);
+        self.match_log.note_player(authority, &username, &team);
+
+        if observation.fired {
+            self.match_log.record_shot(observed_at, authority, new_position, rot_y, rot_x);
+        }
+
+        // A remote player fired: replay the shot from their pose so the hit gets weapon/headshot metadata
+        if observation.fired && !is_local_player {
+            self.note_remote_shot(authority, new_position, rot_y, rot_x, observed_at);
+
+            // Firing reveals enemies on the minimap
+            if team != self.current_player_team.to_string() {
+                self.enemy_visibility.note_fired(authority, new_position, rot_y, observed_at);
+            }
+
+            // Remote gunshot, muffled if there's a wall in between
+            let muzzle = new_position + Vector3::new(0.0, 1.5, 0.0);
+            self.play_world_sound(GUNSHOT_SOUND, muzzle, 1.0);
+
+            // Nearby gunfire pushes the music towards the combat layer
+            self.combat_noise += self.world_sound_gain(muzzle);
+
+            // Enemy fire shows on the threat compass, fainter through walls
+            if team != self.current_player_team.to_string() {
+                self.note_threat(ThreatKind::Gunfire, muzzle, self.world_sound_gain(muzzle));
+            }
+        }
+
+        // We got hit: point the threat compass at whoever the damage was put on
+        if is_local_player {
+            if let Some(source) = observation.attacker.as_deref().and_then(|attacker| self.threat_source(attacker)) {
+                let amount = self.player.as_ref().map_or(0.0, |p| p.health - health);
+                self.note_threat(ThreatKind::Damage, source, amount);
+            }
+        }
+
+        if let Some(kill) = observation.kill {
+            self.last_kill_position = Some(new_position);
+            self.match_log.record_kill(observed_at, &kill);
+            self.publish_kill_event(&kill, &current_ephemeral_key);
+        }
+
+        // Handle local player reconciliation
+        if is_local_player {
+            // Store the player's team for use in respawn
+            // Team comes as 0 or 1 from blockchain
+            self.current_player_team = team_num as u8;
+
+            // Keeps the viewmodel's ammo counter in step between shots
+            self.current_bullet_count = bullet_count;
+            
+            // Variables to track state changes
+            let mut just_died = false;
+            let mut should_respawn = false;
+            let mut just_respawned = false;
+            let mut death_time = 0.0;
+
+            if let Some(player) = &mut self.player {
+                // IMPROVED CLIENT-SIDE PREDICTION:
+                // Only update target position, never directly set position from server
+                // This allows the client to predict movement freely
+                player.target_position = new_position;
+                
+                // Convert rotation from radians (server) to degrees (Player struct)
+                player.target_yaw = rot_y.to_degrees(); // rotationY is the yaw
+                player.target_pitch = rot_x.to_degrees(); // rotationX is the pitch
+
+                // Update health from blockchain (resets local regen on damage)
+                let current_time = unsafe { emscripten_get_now() / 1000.0 };
+                let previous_health = player.health;
+                player.apply_server_health(health, current_time);
+                self.shake.add(ShakeSource::Damage, (previous_health - player.health) / DAMAGE_FOR_FULL_SHAKE);
+
+                // Warn when health drops into the danger zone
+                if previous_health > LOW_HEALTH_THRESHOLD && player.health <= LOW_HEALTH_THRESHOLD && player.health > 0.0 {
+                    play_ui_sound(UiSound::LowHealth);
+                }
+
+                // Check for death
+                if player.health <= 0.0 && !player.is_dead {
+                    // Player just died
+                    player.is_dead = true;
+                    let current_time = unsafe { emscripten_get_now() / 1000.0 }; // Convert ms to seconds
+                    player.death_timestamp = current_time;
+                    log_info!(Net, "💀 Player died! Respawn available in 3 seconds...");
+
+                    just_died = true;
+                    death_time = current_time;
+                }
+
+                // Check for respawn (a hazard death is one the chain doesn't know about)
+                let alive = is_alive && !player.died_from_hazard();
+                if player.is_dead && !alive {
+                    let current_time = unsafe { emscripten_get_now() / 1000.0 };
+                    let time_since_death = current_time - player.death_timestamp;
+
+                    log_debug!(Net, "🔍 Respawn check: is_dead={}, is_alive={}, time_since_death={:.2}, death_timestamp={:.2}", 
+                        player.is_dead, is_alive, time_since_death, player.death_timestamp);
+
+                    // Only respawn if 3 seconds have passed AND we haven't already requested respawn
+                    // (death_timestamp < 0 means respawn already requested)
+                    if time_since_death >= 3.0 && player.death_timestamp >= 0.0 {
+                        log_info!(Net, "✅ Respawn conditions met! Triggering respawn...");
+                        should_respawn = true;
+                    }
+                } else if alive && player.is_dead {
+                    // Player respawned successfully
+                    player.is_dead = false;
+                    player.death_timestamp = 0.0;
+                    log_info!(Net, "✅ Player respawned!");
+
+                    just_respawned = true;
+                }
+            }
+
+            // Handle state changes after releasing the borrow
+            if just_died {
+                self.update_death_state_js(true, death_time);
+                self.drop_weapon_on_death();
+            }
+
+            if should_respawn {
+                log_info!(Net, "🚀 should_respawn=true, current_game_pubkey={:?}", self.current_game_pubkey);
+                if let Some(game_pubkey) = self.current_game_pubkey.clone() {
+                    log_info!(Net, "📞 Calling respawn with game_pubkey: {}", game_pubkey);
+                    self.call_respawn(&game_pubkey);
+                } else {
+                    log_error!(Net, "❌ Cannot respawn: No game_pubkey set!");
+                }
+            }
+
+            if just_respawned {
+                self.update_death_state_js(false, 0.0);
+                self.refill_grenades();
+                self.reset_loadout();
+            }
+
+            return; // Don't add local player to other_players list
+        }
+
+        // Get current time for dead reckoning
+        let current_time = unsafe { emscripten_get_now() / 1000.0 }; // Convert ms to seconds
+
+        // Update or create remote player
+        if let Some(existing) = self.other_players.iter_mut().find(|p| p.authority == authority) {
+            // Calculate velocity for dead reckoning (change in position / time)
+            let time_delta = current_time - existing.last_update_time;
+            if time_delta > 0.001 { // Avoid division by zero
+                existing.velocity = (new_position - existing.target_position) / time_delta as f32;
+            }
+
+            // Update target position and rotation for smooth interpolation
+            existing.target_position = new_position;
+            existing.target_rotation = new_rotation;
+            existing.username = username;
+            existing.team = team;
+            existing.is_alive = is_alive;
+            existing.sliding &= is_alive;
+            existing.health = health;
+            existing.last_update_time = current_time;
+        } else {
+            // New player - create with current position as both start and target
+            let other_player = OtherPlayer {
+                authority: authority.to_string(),
+                username: username.clone(),
+                team,
+                position: new_position,
+                rotation: new_rotation,
+                is_alive,
+                health,
+                sliding: false,
+                target_position: new_position,
+                target_rotation: new_rotation,
+                velocity: Vector3::zero(), // Start with no velocity
+                last_update_time: current_time,
+            };
+            log_info!(Net, "➕ Added new player: {} ({})", username, authority);
+            self.other_players.push(other_player);
+        }
+    }
+
+    /// Hit-test a remote player's shot (yaw/pitch in radians) against everyone else
+    pub(super) fn note_remote_shot(&mut self, shooter: &str, position: Vector3, yaw: f32, pitch: f32, now: f64) {
+        let eye = Vector3::new(position.x, position.y + 1.7, position.z);
+        let direction = Vector3::new(
+            yaw.cos() * pitch.cos(),
+            pitch.sin(),
+            yaw.sin() * pitch.cos(),
+        );
+
+        let targets: Vec<OtherPlayer> = self.other_players.iter()
+            .filter(|p| p.authority != shooter)
+            .cloned()
+            .collect();
+        let mut best = Self::hit_test_players(eye, direction, &targets);
+
+        // The local player isn't in other_players, test it separately
+        if let Some(player) = &self.player {
+            if !player.is_dead {
+                if let Some((distance, headshot)) = Self::hit_test_capsule(Ray::new(eye, direction), player.position, capsule_height(player.is_sliding())) {
+                    if best.as_ref().map_or(true, |b| distance < b.distance) {
+                        best = Some(PlayerHit { authority: self.get_current_ephemeral_key(), distance, headshot });
+                    }
+                }
+            }
+        }
+
+        if let Some(hit) = best {
+            // Hit feedback on other players (our own body isn't visible in first person)
+            if hit.authority != self.get_current_ephemeral_key() {
+                self.spawn_hit_effect(eye + direction * hit.distance, hit.headshot);
+            }
+
+            // Remote players only carry the default weapon on-chain for now
+            self.damage_tracker.note_hit(shooter, &hit.authority, WeaponKind::default(), hit.headshot, now);
+        }
+    }
+
+    /// Get current player's ephemeral key for comparison
+    pub(super) fn get_current_ephemeral_key(&self) -> String {
+        use std::os::raw::c_char;
+        use std::ffi::CString;
+
+        let js_code = r#"
+            (() => {
+                if (window.gameBridge && window.gameBridge.getCurrentPlayerEphemeralKey) {
+                    return window.gameBridge.getCurrentPlayerEphemeralKey();
+                }
+                return '';
+            })();
+        "#;
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            let result_ptr = emscripten_run_script_string(c_str.as_ptr());
+
+            if !result_ptr.is_null() {
+                return std::ffi::CStr::from_ptr(result_ptr)
+                    .to_string_lossy()
+                    .into_owned();
+            }
+        }
+
+        String::new()
+    }
+}
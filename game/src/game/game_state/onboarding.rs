@@ -0,0 +1,207 @@
+//! Onboarding mission: scripted tutorial map, step driver and instruction panel
+
+use raylib::prelude::*;
+use crate::game::{TrainingBot, TriggerVolume, Tutorial, TutorialInput, TutorialStep};
+use crate::game::fonts::{draw_hud_text, measure_hud_text};
+use crate::map::{Map, MapObject, ModelType};
+use crate::audio::{play_ui_sound, UiSound};
+use crate::i18n::{t, tr};
+use super::{GameState, emscripten_run_script};
+
+/// Ids of the three shooting-step targets (the objective bot comes after them)
+const TARGET_BOT_IDS: std::ops::RangeInclusive<u32> = 1..=3;
+const OBJECTIVE_BOT_ID: u32 = 10;
+
+/// Tutorial state living on the game state for the length of the mission
+pub(super) struct TutorialSession {
+    tutorial: Tutorial,
+    move_zone: TriggerVolume,
+    objective_zone: TriggerVolume,
+    /// Player yaw last frame (for the look step)
+    last_yaw: f32,
+    /// Whether a reload was running last frame (a reload finishing completes the reload step)
+    was_reloading: bool,
+}
+
+/// The tutorial arena: a walled lane with cover, target range and objective pad
+fn tutorial_map() -> Map {
+    let mut map = Map::new("Training Grounds".to_string());
+    // Player starts facing -Z, towards the target range
+    map.set_spawn_position(Vector3::new(0.0, 0.0, 18.0));
+
+    let mut add_block = |position: Vector3, scale: Vector3, color: Color| {
+        let mut object = MapObject::new(ModelType::Cube);
+        object.set_position(position);
+        object.set_scale(scale);
+        object.set_color(color);
+        map.add_object(object);
+    };
+
+    let wall = Color::new(60, 70, 90, 255);
+    let cover = Color::new(70, 130, 180, 255);
+
+    // Outer walls (side walls in two halves, object scale tops out at 25.5)
+    for (x, z) in [(-12.0, -11.0), (-12.0, 11.0), (12.0, -11.0), (12.0, 11.0)] {
+        add_block(Vector3::new(x, 1.5, z), Vector3::new(1.0, 3.0, 22.0), wall);
+    }
+    add_block(Vector3::new(0.0, 1.5, -22.0), Vector3::new(24.0, 3.0, 1.0), wall);
+    add_block(Vector3::new(0.0, 1.5, 22.0), Vector3::new(24.0, 3.0, 1.0), wall);
+
+    // Waist-high cover between the target range and the objective
+    add_block(Vector3::new(-5.0, 0.5, -6.0), Vector3::new(3.0, 1.0, 1.0), cover);
+    add_block(Vector3::new(5.0, 0.5, -6.0), Vector3::new(3.0, 1.0, 1.0), cover);
+
+    map
+}
+
+/// Static targets for the shooting step and the patrolling objective bot
+fn tutorial_bots() -> Vec<TrainingBot> {
+    let targets = [
+        Vector3::new(-4.0, 0.0, 0.0),
+        Vector3::new(0.0, 0.0, -2.0),
+        Vector3::new(4.0, 0.0, 0.0),
+    ];
+
+    let mut bots: Vec<TrainingBot> = TARGET_BOT_IDS
+        .zip(targets)
+        .map(|(id, position)| TrainingBot::stationary(id, position).without_respawn())
+        .collect();
+    bots.push(
+        TrainingBot::patrolling(OBJECTIVE_BOT_ID, Vector3::new(-8.0, 0.0, -12.0), Vector3::new(8.0, 0.0, -12.0), 2.5)
+            .without_respawn(),
+    );
+    bots
+}
+
+impl GameState {
+    /// Start the onboarding mission (leaves any match that was running)
+    pub fn start_tutorial(&mut self) {
+        log_info!(Game, "🎓 Starting tutorial");
+        self.begin_offline_session(tutorial_map(), tutorial_bots());
+        self.tutorial = Some(TutorialSession {
+            tutorial: Tutorial::new(),
+            move_zone: TriggerVolume::new(Vector3::new(0.0, 1.0, 10.0), Vector3::new(4.0, 2.0, 4.0)),
+            objective_zone: TriggerVolume::new(Vector3::new(0.0, 1.0, -16.0), Vector3::new(5.0, 2.0, 5.0)),
+            last_yaw: self.player.as_ref().map_or(0.0, |p| p.yaw),
+            was_reloading: false,
+        });
+    }
+
+    /// Feed this frame's player state into the tutorial and react to step changes
+    pub(super) fn update_tutorial(&mut self, rl: &RaylibHandle, delta: f32) {
+        let Some(ref mut session) = self.tutorial else {
+            return;
+        };
+        let Some(ref player) = self.player else {
+            return;
+        };
+
+        let bots_left = |ids: &dyn Fn(u32) -> bool| self.bots.iter().filter(|b| ids(b.id) && b.is_alive()).count();
+        let input = TutorialInput {
+            look_delta: player.yaw - session.last_yaw,
+            in_move_zone: session.move_zone.contains(player.position),
+            targets_left: bots_left(&|id| TARGET_BOT_IDS.contains(&id)),
+            reloaded: session.was_reloading && !self.reload_initiated,
+            confirm: rl.is_key_pressed(KeyboardKey::KEY_ENTER),
+            objective_bots_left: bots_left(&|id| id == OBJECTIVE_BOT_ID),
+            in_objective_zone: session.objective_zone.contains(player.position),
+        };
+        session.last_yaw = player.yaw;
+        session.was_reloading = self.reload_initiated;
+
+        // Finished: ENTER hands control back to the menu
+        if session.tutorial.is_complete() {
+            if input.confirm {
+                Self::notify_tutorial_complete();
+            }
+            return;
+        }
+
+        if let Some(step) = session.tutorial.update(input, delta) {
+            log_info!(Game, "🎓 Tutorial step: {:?}", step);
+            play_ui_sound(if step == TutorialStep::Complete { UiSound::MatchStart } else { UiSound::CountdownGo });
+        }
+    }
+
+    /// Let the web UI know the player finished the tutorial
+    fn notify_tutorial_complete() {
+        use std::ffi::CString;
+
+        let js_code = "window.gameBridge?.onTutorialComplete?.();";
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            emscripten_run_script(c_str.as_ptr());
+        }
+    }
+
+    /// Highlight the trigger zone the current step is about
+    pub(super) fn draw_tutorial_zones(&self, d3d: &mut RaylibMode3D<RaylibDrawHandle>) {
+        let Some(ref session) = self.tutorial else {
+            return;
+        };
+        let zone = match session.tutorial.step() {
+            TutorialStep::Move => session.move_zone,
+            TutorialStep::Objective => session.objective_zone,
+            _ => return,
+        };
+
+        let size = zone.size();
+        d3d.draw_cube(zone.center(), size.x, size.y, size.z, Color::new(0, 242, 148, 50));
+        d3d.draw_cube_wires(zone.center(), size.x, size.y, size.z, Color::new(0, 242, 148, 200));
+    }
+
+    /// Instruction panel at the top of the screen: step, heading, text and progress
+    pub(super) fn draw_tutorial_panel(&self, d: &mut RaylibDrawHandle) {
+        let Some(ref session) = self.tutorial else {
+            return;
+        };
+        let step = session.tutorial.step();
+        let (title, body) = step.instructions();
+
+        let screen_width = d.get_screen_width();
+        let panel_width = 520.min(screen_width - 40);
+        let x = (screen_width - panel_width) / 2;
+        let y = 20;
+        let body_lines = wrap_text(d, body, 16, panel_width - 30);
+        let panel_height = 78 + body_lines.len() as i32 * 20;
+
+        d.draw_rectangle(x, y, panel_width, panel_height, Color::new(10, 10, 20, 200));
+        d.draw_rectangle_lines(x, y, panel_width, panel_height, Color::new(0, 242, 148, 180));
+
+        let label = step.number().map_or(t("tutorial.training").to_string(), |n| tr!("tutorial.step", step = n, total = 6));
+        draw_hud_text(d, &label, x + 15, y + 12, 12, Color::new(200, 200, 220, 255));
+        let ammo = tr!("tutorial.ammo", count = self.current_bullet_count);
+        let ammo_width = measure_hud_text(d, &ammo, 12);
+        draw_hud_text(d, &ammo, x + panel_width - 15 - ammo_width, y + 12, 12, Color::new(200, 200, 220, 255));
+        draw_hud_text(d, title, x + 15, y + 28, 22, Color::new(0, 242, 148, 255));
+
+        for (i, line) in body_lines.iter().enumerate() {
+            draw_hud_text(d, line, x + 15, y + 56 + i as i32 * 20, 16, Color::WHITE);
+        }
+
+        let progress = session.tutorial.step_progress();
+        if progress > 0.0 {
+            let bar_width = panel_width - 30;
+            d.draw_rectangle(x + 15, y + panel_height - 12, bar_width, 4, Color::new(60, 60, 70, 255));
+            d.draw_rectangle(x + 15, y + panel_height - 12, (bar_width as f32 * progress) as i32, 4, Color::new(0, 242, 148, 255));
+        }
+    }
+}
+
+/// Greedy word wrap to `max_width` pixels at `font_size`
+fn wrap_text(d: &RaylibDrawHandle, text: &str, font_size: i32, max_width: i32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+        if !line.is_empty() && measure_hud_text(d, &candidate, font_size) > max_width {
+            lines.push(std::mem::replace(&mut line, word.to_string()));
+        } else {
+            line = candidate;
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
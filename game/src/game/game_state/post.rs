@@ -0,0 +1,105 @@
+//! Post-processing: full-screen passes over the drawn world (damage vignette, whiteout,
+//! film grain and chromatic aberration), composited under the HUD
+
+use raylib::prelude::*;
+use super::GameState;
+use super::effects::DAMAGE_FLASH_DURATION;
+use crate::game::comfort::{comfort_scale, ComfortEffect};
+use crate::game::graphics::film_effects;
+
+/// Share of health that can be missing before the red vignette creeps in
+const VIGNETTE_FREE_DAMAGE: f32 = 0.3;
+
+/// Edge alpha of the vignette at zero health
+const VIGNETTE_MAX_ALPHA: f32 = 110.0;
+
+/// Edge alpha right after a damage flash (falls, hazards)
+const DAMAGE_FLASH_ALPHA: f32 = 140.0;
+
+/// Screen alpha right after firing (the shot flash was a fixed 0.1s overlay before)
+const SHOT_WHITEOUT_ALPHA: f32 = 80.0;
+const SHOT_FLASH_DURATION: f32 = 0.1;
+
+/// How far red and blue are pulled apart (pixels at 720p)
+const CHROMATIC_OFFSET: f32 = 2.0;
+
+/// Grain specks drawn per frame and their strength
+const GRAIN_SPECKS: u32 = 600;
+const GRAIN_ALPHA: u8 = 22;
+
+impl GameState {
+    /// Draw the world rendered into `scene` back to the screen (inside the frame's drawing),
+    /// pulling the color channels apart. Render textures are stored upside down, hence the
+    /// negative source height
+    pub(super) fn draw_scene_texture(scene: &RenderTexture2D) {
+        let (width, height) = (scene.texture.width as f32, scene.texture.height as f32);
+        let offset = CHROMATIC_OFFSET * height / 720.0;
+        let source = raylib::ffi::Rectangle { x: 0.0, y: 0.0, width, height: -height };
+        let pass = |x: f32, color: raylib::ffi::Color| unsafe {
+            raylib::ffi::DrawTextureRec(scene.texture, source, raylib::ffi::Vector2 { x, y: 0.0 }, color);
+        };
+        // Green lands in place, then red and blue are added on either side of it
+        pass(0.0, raylib::ffi::Color { r: 0, g: 255, b: 0, a: 255 });
+        unsafe {
+            raylib::ffi::BeginBlendMode(raylib::ffi::BlendMode::BLEND_ADDITIVE as i32);
+        }
+        pass(offset, raylib::ffi::Color { r: 255, g: 0, b: 0, a: 255 });
+        pass(-offset, raylib::ffi::Color { r: 0, g: 0, b: 255, a: 255 });
+        unsafe {
+            raylib::ffi::EndBlendMode();
+        }
+    }
+
+    /// Full-screen passes over the world, under the HUD
+    pub(super) fn draw_post_effects(&self, d: &mut RaylibDrawHandle) {
+        let (width, height) = (d.get_screen_width(), d.get_screen_height());
+        let flash_scale = comfort_scale(ComfortEffect::Flash);
+
+        // Red vignette: creeps in as health drops, pulses when hurt by the environment
+        let missing = self.player.as_ref().filter(|p| !p.is_dead).map_or(0.0, |p| 1.0 - p.health / p.max_health);
+        let vignette = ((missing - VIGNETTE_FREE_DAMAGE) / (1.0 - VIGNETTE_FREE_DAMAGE)).clamp(0.0, 1.0) * VIGNETTE_MAX_ALPHA;
+        let pulse = (self.damage_flash_timer / DAMAGE_FLASH_DURATION).max(0.0) * DAMAGE_FLASH_ALPHA * flash_scale;
+        let alpha = vignette.max(pulse) as u8;
+        if alpha > 0 {
+            let edge = height / 5;
+            let (red, clear) = (Color::new(200, 0, 0, alpha), Color::new(200, 0, 0, 0));
+            d.draw_rectangle_gradient_v(0, 0, width, edge, red, clear);
+            d.draw_rectangle_gradient_v(0, height - edge, width, edge, clear, red);
+            d.draw_rectangle_gradient_h(0, 0, edge, height, red, clear);
+            d.draw_rectangle_gradient_h(width - edge, 0, edge, height, clear, red);
+        }
+
+        // Whiteout: the shot flash (eased by the comfort setting) or a flashbang's blinding,
+        // which stays at full strength since it's gameplay
+        let whiteout = (self.whiteout_amount() * flash_scale).max(self.flash_whiteout());
+        if whiteout > 0.0 {
+            d.draw_rectangle(0, 0, width, height, Color::new(255, 255, 255, (whiteout * 255.0) as u8));
+        }
+
+        if film_effects() {
+            Self::draw_film_grain(d, width, height);
+        }
+    }
+
+    /// How white the shot flash washes out the screen right now (0 - 1, before the comfort setting)
+    fn whiteout_amount(&self) -> f32 {
+        (self.screen_flash_timer / SHOT_FLASH_DURATION).clamp(0.0, 1.0) * SHOT_WHITEOUT_ALPHA / 255.0
+    }
+
+    /// Light and dark specks scattered differently every frame
+    fn draw_film_grain(d: &mut RaylibDrawHandle, width: i32, height: i32) {
+        let mut seed = (d.get_time() * 60.0) as u32 | 1;
+        let mut next = |range: i32| {
+            // xorshift32: plenty for noise and needs no dependency
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            (seed % range.max(1) as u32) as i32
+        };
+        for i in 0..GRAIN_SPECKS {
+            let shade = if i % 2 == 0 { 255 } else { 0 };
+            let (x, y) = (next(width), next(height));
+            d.draw_rectangle(x, y, 2, 2, Color::new(shade, shade, shade, GRAIN_ALPHA));
+        }
+    }
+}
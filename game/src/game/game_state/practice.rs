@@ -0,0 +1,172 @@
+//! Offline sessions (tutorial, shooting range, practice matches): training bots instead of players, local ammo, no chain calls
+
+use raylib::prelude::*;
+use crate::game::{BotRole, Loadout, TrainingBot};
+use crate::game::bots::BOT_MAX_HEALTH;
+use crate::game::fonts::{draw_hud_text, measure_hud_text};
+use crate::map::Map;
+use crate::audio::{play_ui_sound, UiSound};
+use super::GameState;
+
+/// Rounds per magazine (same as the on-chain reload refills)
+pub(super) const MAGAZINE_SIZE: u8 = 10;
+
+/// Bots share the player capsule proportions so hit tests line up
+pub(super) const BOT_HEIGHT: f32 = 1.8;
+pub(super) const BOT_RADIUS: f32 = 0.3;
+
+/// Seconds a damage number floats above a bot
+const DAMAGE_NUMBER_LIFETIME: f32 = 1.0;
+
+/// Floating damage readout over a bot that was hit
+#[derive(Debug, Clone)]
+pub(super) struct DamageNumber {
+    pub position: Vector3,
+    pub amount: f32,
+    pub headshot: bool,
+    /// Time remaining before the number disappears
+    pub timer: f32,
+}
+
+impl GameState {
+    /// Whether this is a local session with no match behind it
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Leave any match and start a local session on `map` with `bots`
+    pub(super) fn begin_offline_session(&mut self, map: Map, bots: Vec<TrainingBot>) {
+        self.cleanup_websocket_subscriptions();
+        self.current_game_pubkey = None;
+        self.other_players.clear();
+        self.damage_tracker.clear();
+        self.match_started_at = None;
+        self.offline = true;
+        // Practice always has the full arsenal (the match loadout is pushed again before the next match)
+        self.loadout = Loadout::default();
+
+        self.load_map(map);
+        self.start_playing();
+        // Nobody to wait for: skip the freeze countdown
+        self.round.start_freeze(0.0);
+
+        self.current_bullet_count = MAGAZINE_SIZE;
+        self.reload_initiated = false;
+        self.reload_progress = 0.0;
+        self.show_reload_prompt = false;
+        self.bots = bots;
+    }
+
+    /// Back to normal (networked) play
+    pub(super) fn end_offline_session(&mut self) {
+        self.offline = false;
+        self.bots.clear();
+        self.damage_numbers.clear();
+        self.tutorial = None;
+        self.range = None;
+        self.practice = None;
+    }
+
+    /// Closest standing bot hit by a ray: (bot index, distance, headshot)
+    pub(super) fn hit_test_bots(origin: Vector3, direction: Vector3, bots: &[TrainingBot]) -> Option<(usize, f32, bool)> {
+        let ray = Ray::new(origin, direction);
+        bots.iter()
+            .enumerate()
+            .filter(|(_, bot)| bot.is_alive())
+            .filter_map(|(index, bot)| Self::hit_test_capsule(ray, bot.position, BOT_HEIGHT).map(|(distance, headshot)| (index, distance, headshot)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Apply one shot's hits (bot index, damage, headshot) to the bots; an empty slice is a miss
+    pub(super) fn damage_bots(&mut self, hits: &[(usize, f32, bool)], now: f64) {
+        let mut dealt = 0.0;
+        for &(index, damage, headshot) in hits {
+            let Some(bot) = self.bots.get_mut(index) else {
+                continue;
+            };
+            if !bot.is_alive() {
+                continue;
+            }
+            dealt += damage.min(bot.health);
+            self.damage_numbers.push(DamageNumber {
+                position: bot.position + Vector3::new(0.0, BOT_HEIGHT + 0.5, 0.0),
+                amount: damage,
+                headshot,
+                timer: DAMAGE_NUMBER_LIFETIME,
+            });
+            if bot.apply_damage(damage) {
+                log_info!(Game, "🤖 Training bot {} down", bot.id);
+                play_ui_sound(UiSound::KillConfirm);
+                if let Some(ref mut practice) = self.practice {
+                    practice.kills += 1;
+                }
+            }
+        }
+
+        if let Some(ref mut range) = self.range {
+            range.stats.record_shot(dealt, now);
+        }
+    }
+
+    pub(super) fn update_bots(&mut self, delta: f32) {
+        for bot in &mut self.bots {
+            bot.update(delta);
+        }
+
+        // Damage numbers drift upwards and fade out
+        for number in &mut self.damage_numbers {
+            number.timer -= delta;
+            number.position.y += delta * 0.8;
+        }
+        self.damage_numbers.retain(|n| n.timer > 0.0);
+    }
+
+    /// Draw the floating damage numbers (2D pass, projected from the world)
+    pub(super) fn draw_damage_numbers(&self, d: &mut RaylibDrawHandle, camera: &Camera3D) {
+        let forward = (camera.target - camera.position).normalized();
+        for number in &self.damage_numbers {
+            if (number.position - camera.position).dot(forward) <= 0.0 {
+                continue;
+            }
+            let screen = d.get_world_to_screen(number.position, *camera);
+            let alpha = ((number.timer / DAMAGE_NUMBER_LIFETIME) * 255.0) as u8;
+            let color = if number.headshot {
+                Color::new(255, 80, 80, alpha)
+            } else {
+                Color::new(255, 230, 120, alpha)
+            };
+            let text = format!("{:.0}", number.amount);
+            let font_size = if number.headshot { 24 } else { 20 };
+            let x = screen.x as i32 - measure_hud_text(d, &text, font_size) / 2;
+            draw_hud_text(d, &text, x + 2, screen.y as i32 + 2, font_size, Color::new(0, 0, 0, alpha / 2));
+            draw_hud_text(d, &text, x, screen.y as i32, font_size, color);
+        }
+    }
+
+    /// Draw standing bots as capsules (orange targets, practice bots tinted by role) with a health bar overhead
+    pub(super) fn draw_bots(&self, d3d: &mut RaylibMode3D<RaylibDrawHandle>) {
+        for bot in self.bots.iter().filter(|b| b.is_alive()) {
+            let color = match bot.profile() {
+                None => Color::new(255, 170, 40, 255),
+                Some((_, BotRole::Rusher)) => Color::new(230, 70, 60, 255),
+                Some((_, BotRole::Anchor)) => Color::new(70, 130, 230, 255),
+                Some((_, BotRole::Objective)) => Color::new(170, 90, 230, 255),
+            };
+            d3d.draw_cylinder(bot.position, BOT_RADIUS, BOT_RADIUS, BOT_HEIGHT, 8, color);
+            let head = bot.position + Vector3::new(0.0, BOT_HEIGHT, 0.0);
+            d3d.draw_sphere(head, BOT_RADIUS * 0.8, color);
+
+            // Health bar: dark background with the remaining fraction in green on top
+            let bar_center = head + Vector3::new(0.0, 0.55, 0.0);
+            let fraction = bot.health / BOT_MAX_HEALTH;
+            d3d.draw_cube(bar_center, 0.8, 0.08, 0.08, Color::new(40, 40, 40, 200));
+            d3d.draw_cube(
+                bar_center - Vector3::new(0.4 * (1.0 - fraction), 0.0, 0.0),
+                0.8 * fraction,
+                0.1,
+                0.1,
+                Color::new(0, 242, 148, 255),
+            );
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Practice matches: an offline arena against bots that fight back, at a chosen difficulty
+//! and with chosen roles. Bot shots are resolved locally like the rest of an offline session
+
+use raylib::prelude::*;
+use crate::game::{BotDifficulty, BotRole, BotSenses, TrainingBot, InputAccumulator, InputChain, ShakeSource, ThreatKind, STEP_HEIGHT};
+use crate::game::bots::BOT_SHOT_DAMAGE;
+use crate::game::fonts::{draw_hud_text, measure_hud_text};
+use crate::map::{Map, MapObject, ModelType, SpatialIndex};
+use crate::i18n::t;
+use super::{GameState, BulletTrail, emscripten_get_now};
+use super::combat::GUNSHOT_SOUND;
+use super::effects::{DAMAGE_FLASH_DURATION, DAMAGE_FOR_FULL_SHAKE};
+use super::practice::{BOT_HEIGHT, BOT_RADIUS};
+
+/// Most bots a practice match can have
+const MAX_PRACTICE_BOTS: usize = 8;
+
+/// Where bots spawn, filled in order (the far half of the arena)
+const BOT_SPAWNS: [(f32, f32); MAX_PRACTICE_BOTS] = [
+    (0.0, -16.0), (-8.0, -15.0), (8.0, -15.0), (-15.0, -12.0),
+    (15.0, -12.0), (-4.0, -10.0), (4.0, -10.0), (0.0, -6.0),
+];
+
+/// Bot shots that miss still fly past this far off their target
+const NEAR_MISS_OFFSET: f32 = 0.6;
+
+/// Practice match state living on the game state while one runs
+pub(super) struct PracticeMatch {
+    difficulty: BotDifficulty,
+    pub kills: u32,
+    deaths: u32,
+}
+
+/// A walled square arena with cover on both halves and the objective pad in the middle
+fn practice_map() -> Map {
+    let mut map = Map::new("Practice Arena".to_string());
+    map.set_spawn_position(Vector3::new(0.0, 0.0, 16.0));
+
+    let mut add_block = |position: Vector3, scale: Vector3, color: Color| {
+        let mut object = MapObject::new(ModelType::Cube);
+        object.set_position(position);
+        object.set_scale(scale);
+        object.set_color(color);
+        map.add_object(object);
+    };
+
+    let wall = Color::new(60, 70, 90, 255);
+    let cover = Color::new(110, 100, 80, 255);
+
+    // Outer walls, two halves per side (object scale tops out at 25.5)
+    for half in [-10.0, 10.0] {
+        add_block(Vector3::new(half, 2.0, -20.0), Vector3::new(20.0, 4.0, 1.0), wall);
+        add_block(Vector3::new(half, 2.0, 20.0), Vector3::new(20.0, 4.0, 1.0), wall);
+        add_block(Vector3::new(-20.0, 2.0, half), Vector3::new(1.0, 4.0, 20.0), wall);
+        add_block(Vector3::new(20.0, 2.0, half), Vector3::new(1.0, 4.0, 20.0), wall);
+    }
+
+    // Mirrored cover: low crates to shoot over and taller pillars to hide behind
+    for side in [-1.0, 1.0] {
+        add_block(Vector3::new(-7.0, 0.6, 8.0 * side), Vector3::new(2.0, 1.2, 2.0), cover);
+        add_block(Vector3::new(7.0, 0.6, 8.0 * side), Vector3::new(2.0, 1.2, 2.0), cover);
+        add_block(Vector3::new(0.0, 1.5, 10.0 * side), Vector3::new(4.0, 3.0, 1.0), wall);
+        add_block(Vector3::new(-13.0, 1.5, 3.0 * side), Vector3::new(1.5, 3.0, 1.5), wall);
+        add_block(Vector3::new(13.0, 1.5, 3.0 * side), Vector3::new(1.5, 3.0, 1.5), wall);
+    }
+
+    // Objective pad: flat enough to walk over, tagged so objective bots find it
+    add_block(Vector3::new(0.0, 0.05, 0.0), Vector3::new(4.0, 0.1, 4.0), Color::new(0, 242, 148, 255));
+    let pad = map.objects.len() - 1;
+    map.set_object_label(pad, "objective", "objective");
+
+    map
+}
+
+/// Where a bot stepping from `from` towards `to` ends up: the full step, else whichever axis is free
+pub(super) fn slide_bot(index: &SpatialIndex, from: Vector3, to: Vector3) -> Vector3 {
+    let blocked = |p: Vector3| index.blocks_player(p, BOT_RADIUS, BOT_HEIGHT, STEP_HEIGHT);
+    [to, Vector3::new(to.x, from.y, from.z), Vector3::new(from.x, from.y, to.z)]
+        .into_iter()
+        .find(|&p| !blocked(p))
+        .unwrap_or(from)
+}
+
+/// Tracer for a bot shot at `target`: a hit ends past it, a miss flies by to one side
+pub(super) fn bot_tracer(muzzle: Vector3, target: Vector3, hit: bool) -> (Vector3, Vector3) {
+    let aim = if hit {
+        target
+    } else {
+        let side = (target - muzzle).cross(Vector3::new(0.0, 1.0, 0.0)).normalized();
+        target + side * NEAR_MISS_OFFSET
+    };
+    (muzzle, muzzle + (aim - muzzle) * 1.5)
+}
+
+/// `count` practice bots; `role` None mixes the roles
+fn practice_bots(difficulty: BotDifficulty, role: Option<BotRole>, count: usize) -> Vec<TrainingBot> {
+    BOT_SPAWNS
+        .iter()
+        .take(count.clamp(1, MAX_PRACTICE_BOTS))
+        .enumerate()
+        .map(|(i, &(x, z))| {
+            let role = role.unwrap_or(BotRole::ALL[i % BotRole::ALL.len()]);
+            TrainingBot::combatant(i as u32 + 1, Vector3::new(x, 0.0, z), difficulty, role)
+        })
+        .collect()
+}
+
+impl GameState {
+    /// Start an offline match against `count` bots (leaves any match that was running)
+    pub fn start_practice_match(&mut self, difficulty: BotDifficulty, role: Option<BotRole>, count: usize) {
+        log_info!(Game, "🤖 Starting practice match: {} {:?} bots ({:?})", count, role, difficulty);
+        self.begin_offline_session(practice_map(), practice_bots(difficulty, role, count));
+        self.practice = Some(PracticeMatch { difficulty, kills: 0, deaths: 0 });
+    }
+
+    /// Move the practice bots and resolve their shots at the player
+    pub(super) fn update_practice_match(&mut self, delta: f32) {
+        if self.practice.is_none() {
+            return;
+        }
+        let (Some(map), Some(index)) = (self.map.as_ref(), self.map_index.as_ref()) else {
+            return;
+        };
+        let objective = map.tag_anchor("objective").unwrap_or_else(Vector3::zero);
+        let player = self.player.as_ref().filter(|p| !p.is_dead).map(|p| (p.position, p.position + Vector3::new(0.0, p.eye_height(), 0.0)));
+
+        let mut hits = Vec::new();
+        let mut shots = Vec::new();
+        for bot in &mut self.bots {
+            let eye = bot.position + Vector3::new(0.0, BOT_HEIGHT * 0.9, 0.0);
+            let senses = BotSenses {
+                player: player.map(|(feet, _)| feet),
+                sees_player: player.is_some_and(|(_, head)| !index.is_line_blocked(eye, head)),
+                objective,
+            };
+            let Some(intent) = bot.think(delta, senses) else {
+                continue;
+            };
+
+            bot.position = slide_bot(index, bot.position, intent.move_to);
+
+            let (Some(hit), Some((_, head))) = (intent.shot, player) else {
+                continue;
+            };
+            if hit {
+                hits.push(bot.position);
+            }
+            let muzzle = bot.position + Vector3::new(0.0, BOT_HEIGHT * 0.8, 0.0);
+            shots.push(bot_tracer(muzzle, head - Vector3::new(0.0, 0.4, 0.0), hit));
+        }
+
+        for (start, end) in shots {
+            self.bullet_trails.push(BulletTrail { start, end, timer: 0.1 });
+            self.play_world_sound(GUNSHOT_SOUND, start, 0.6);
+            self.note_threat(ThreatKind::Gunfire, start, self.world_sound_gain(start));
+        }
+        for &source in &hits {
+            self.note_threat(ThreatKind::Damage, source, BOT_SHOT_DAMAGE);
+        }
+        if !hits.is_empty() {
+            self.take_bot_damage(hits.len() as f32 * BOT_SHOT_DAMAGE);
+        }
+    }
+
+    /// Hurt the player; going down counts a death and starts the next life at the spawn
+    fn take_bot_damage(&mut self, damage: f32) {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        let spawn = self.map.as_ref().map(|map| map.get_spawn_position());
+        let Some(player) = self.player.as_mut() else {
+            return;
+        };
+        player.apply_hazard_damage(damage, now);
+        self.damage_flash_timer = DAMAGE_FLASH_DURATION;
+        self.shake.add(ShakeSource::Damage, damage / DAMAGE_FOR_FULL_SHAKE);
+        if player.health > 0.0 {
+            return;
+        }
+
+        log_info!(Game, "🤖 Downed by a practice bot");
+        player.clear_hazard_damage();
+        if let Some(spawn) = spawn {
+            player.set_position(spawn);
+        }
+        self.input_chain = InputChain::default();
+        self.input_pending = InputAccumulator::default();
+        self.threats.clear();
+        // Everyone back to their spawn, so the next life doesn't start under fire
+        for bot in &mut self.bots {
+            bot.reset();
+        }
+        if let Some(ref mut practice) = self.practice {
+            practice.deaths += 1;
+        }
+    }
+
+    /// Difficulty and score on the left side of the screen
+    pub(super) fn draw_practice_panel(&self, d: &mut RaylibDrawHandle) {
+        let Some(ref practice) = self.practice else {
+            return;
+        };
+
+        let (x, y, width) = (20, 120, 220);
+        d.draw_rectangle(x, y, width, 110, Color::new(10, 10, 20, 200));
+        d.draw_rectangle_lines(x, y, width, 110, Color::new(0, 242, 148, 180));
+        draw_hud_text(d, t("practice.title"), x + 12, y + 10, 16, Color::new(0, 242, 148, 255));
+
+        let alive = self.bots.iter().filter(|b| b.is_alive()).count();
+        let rows = [
+            (t("practice.difficulty"), practice.difficulty.label().to_string()),
+            (t("practice.score"), format!("{} / {}", practice.kills, practice.deaths)),
+            (t("practice.bots_up"), format!("{}/{}", alive, self.bots.len())),
+        ];
+        for (i, (label, value)) in rows.iter().enumerate() {
+            let row_y = y + 38 + i as i32 * 20;
+            draw_hud_text(d, label, x + 12, row_y, 14, Color::new(200, 200, 220, 255));
+            let value_width = measure_hud_text(d, value, 14);
+            draw_hud_text(d, value, x + width - 12 - value_width, row_y, 14, Color::WHITE);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Round presentation (freeze countdown, round-end camera flourish, Victory/Defeat banner)
+
+use raylib::prelude::*;
+use super::{GameState, emscripten_run_script, emscripten_get_now};
+use crate::game::{Player, palette};
+use crate::game::fonts::{draw_hud_text, measure_hud_text};
+use crate::i18n::t;
+
+impl GameState {
+    /// Start the round-end presentation for the given winning team (1 = A, 2 = B)
+    pub fn end_round(&mut self, winning_team: u8) {
+        log_info!(Game, "🏁 Round ended, team {} wins", winning_team);
+        self.round.end_round(winning_team);
+
+        self.match_log.record_round_end(unsafe { emscripten_get_now() / 1000.0 }, winning_team);
+        if self.export_match_events {
+            self.export_match_log();
+        }
+    }
+
+    /// Record when clients entered the match (on-chain start + start countdown) so every client's freeze ends at the same moment
+    pub fn sync_freeze_time(&mut self, seconds_since_match_start: f32) {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        self.match_started_at = Some(now - seconds_since_match_start as f64);
+        self.apply_match_start();
+    }
+
+    /// Shorten (or skip, when rejoining) the freeze countdown to match the on-chain start
+    pub(super) fn apply_match_start(&mut self) {
+        let Some(started_at) = self.match_started_at else {
+            return;
+        };
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        let remaining = self.rules.freeze_time - (now - started_at) as f32;
+        log_info!(Game, "⏱️ Syncing freeze time: {:.1}s remaining", remaining.max(0.0));
+        self.round.sync_freeze(remaining);
+    }
+
+    /// Big 3-2-1 in the middle of the screen during freeze time
+    pub(super) fn draw_freeze_countdown(&self, d: &mut RaylibDrawHandle) {
+        let Some(seconds) = self.round.countdown() else {
+            return;
+        };
+
+        let screen_width = d.get_screen_width();
+        let screen_height = d.get_screen_height();
+
+        let label = t("round.starts_in");
+        let label_width = measure_hud_text(d, label, 24);
+        draw_hud_text(d, label, (screen_width - label_width) / 2, screen_height / 3 - 40, 24, Color::new(200, 200, 220, 255));
+
+        let text = seconds.to_string();
+        let font_size = 96;
+        let text_width = measure_hud_text(d, &text, font_size);
+        let x = (screen_width - text_width) / 2;
+        let y = screen_height / 3;
+        draw_hud_text(d, &text, x + 3, y + 3, font_size, Color::new(0, 0, 0, 200));
+        draw_hud_text(d, &text, x, y, font_size, palette::current().positive);
+    }
+
+    /// Tell the web UI the presentation is over so it can show the results screen
+    pub(super) fn notify_round_presentation_done(&self) {
+        use std::ffi::CString;
+
+        log_info!(Game, "🏁 Round-end presentation finished");
+        let js_code = "window.dispatchEvent(new CustomEvent('roundPresentationDone'));";
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            emscripten_run_script(c_str.as_ptr());
+        }
+    }
+
+    /// Camera to render with during the round-end presentation (None = normal first-person)
+    pub(super) fn presentation_camera(&self, player: &Player) -> Option<Camera3D> {
+        let progress = self.round.presentation_progress()?;
+
+        // Pan towards the final kill, or around ourselves if there wasn't one
+        let focus = self.last_kill_position.unwrap_or(player.position) + Vector3::new(0.0, 1.0, 0.0);
+
+        // Slowly orbit the focus point, starting from behind the player's view
+        let start_angle = player.yaw.to_radians() + std::f32::consts::PI;
+        let angle = start_angle + progress * 0.8;
+        let orbit_pos = focus + Vector3::new(angle.cos() * 6.0, 2.5, angle.sin() * 6.0);
+
+        // Ease from the first-person camera into the orbit over the first 40%
+        let blend = (progress / 0.4).min(1.0);
+        let eased = 1.0 - (1.0 - blend) * (1.0 - blend);
+        let position = player.camera.position.lerp(orbit_pos, eased);
+        let target = player.camera.target.lerp(focus, eased);
+
+        Some(Camera3D::perspective(position, target, Vector3::new(0.0, 1.0, 0.0), 70.0))
+    }
+
+    /// Draw the "VICTORY" / "DEFEAT" banner over the presentation
+    pub(super) fn draw_round_banner(&self, d: &mut RaylibDrawHandle) {
+        let (Some(progress), Some(winning_team)) = (self.round.presentation_progress(), self.round.winning_team()) else {
+            return;
+        };
+
+        let victory = winning_team == self.current_player_team;
+        let (text, color) = if victory {
+            (t("round.victory"), palette::current().positive)
+        } else {
+            (t("round.defeat"), palette::current().negative)
+        };
+
+        // Fade in after the slow-mo kicks in
+        let alpha = ((progress - 0.15) / 0.25).clamp(0.0, 1.0);
+        if alpha <= 0.0 {
+            return;
+        }
+
+        let screen_width = d.get_screen_width();
+        let screen_height = d.get_screen_height();
+
+        // Letterbox bars
+        let bar_height = (screen_height as f32 * 0.12 * alpha) as i32;
+        d.draw_rectangle(0, 0, screen_width, bar_height, Color::new(0, 0, 0, 220));
+        d.draw_rectangle(0, screen_height - bar_height, screen_width, bar_height, Color::new(0, 0, 0, 220));
+
+        let font_size = 80;
+        let text_width = measure_hud_text(d, text, font_size);
+        let x = (screen_width - text_width) / 2;
+        let y = screen_height / 2 - font_size / 2;
+
+        d.draw_rectangle(0, y - 20, screen_width, font_size + 40, Color::new(13, 13, 17, (180.0 * alpha) as u8));
+        draw_hud_text(d, text, x + 3, y + 3, font_size, Color::new(0, 0, 0, (200.0 * alpha) as u8));
+        draw_hud_text(d, text, x, y, font_size, Color::new(color.r, color.g, color.b, (255.0 * alpha) as u8));
+    }
+}
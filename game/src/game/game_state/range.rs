@@ -0,0 +1,153 @@
+//! Shooting range: target dummies at set distances, DPS/accuracy readout and quick weapon swaps
+
+use raylib::prelude::*;
+use crate::game::{RangeStats, TrainingBot, WeaponKind};
+use crate::game::fonts::{draw_hud_text, measure_hud_text};
+use crate::map::{Map, MapObject, ModelType};
+use crate::i18n::t;
+use super::{GameState, emscripten_get_now};
+use super::practice::MAGAZINE_SIZE;
+
+/// Shooting range state living on the game state while the range is open
+pub(super) struct RangeSession {
+    pub stats: RangeStats,
+    /// Weapon to swap back to with Q
+    previous_weapon: WeaponKind,
+    /// Weapon equipped last frame (to notice switches made with the number keys)
+    equipped: WeaponKind,
+}
+
+/// The range: a walled lane with distance markers every 10 units
+fn range_map() -> Map {
+    let mut map = Map::new("Shooting Range".to_string());
+    // Player starts at the firing line facing -Z, down the lane
+    map.set_spawn_position(Vector3::new(0.0, 0.0, 20.0));
+
+    let mut add_block = |position: Vector3, scale: Vector3, color: Color| {
+        let mut object = MapObject::new(ModelType::Cube);
+        object.set_position(position);
+        object.set_scale(scale);
+        object.set_color(color);
+        map.add_object(object);
+    };
+
+    let wall = Color::new(60, 70, 90, 255);
+    let marker = Color::new(0, 242, 148, 255);
+
+    // Side walls (two halves each, object scale tops out at 25.5) and the backstop
+    for (x, z) in [(-14.0, -9.0), (-14.0, 13.0), (14.0, -9.0), (14.0, 13.0)] {
+        add_block(Vector3::new(x, 2.0, z), Vector3::new(1.0, 4.0, 22.0), wall);
+    }
+    for x in [-7.0, 7.0] {
+        add_block(Vector3::new(x, 2.5, -20.0), Vector3::new(14.0, 5.0, 1.0), wall);
+    }
+
+    // Firing line and distance markers on both walls (10, 20, 30 units out)
+    add_block(Vector3::new(0.0, 0.5, 18.0), Vector3::new(12.0, 1.0, 0.4), Color::new(70, 130, 180, 255));
+    for z in [10.0, 0.0, -10.0] {
+        add_block(Vector3::new(-13.2, 1.5, z), Vector3::new(0.4, 3.0, 0.4), marker);
+        add_block(Vector3::new(13.2, 1.5, z), Vector3::new(0.4, 3.0, 0.4), marker);
+    }
+
+    map
+}
+
+/// Static dummies at each distance marker, plus two strafing dummies in between
+fn range_bots() -> Vec<TrainingBot> {
+    vec![
+        TrainingBot::stationary(1, Vector3::new(-3.0, 0.0, 10.0)),
+        TrainingBot::stationary(2, Vector3::new(3.0, 0.0, 0.0)),
+        TrainingBot::stationary(3, Vector3::new(0.0, 0.0, -10.0)),
+        TrainingBot::patrolling(4, Vector3::new(-9.0, 0.0, 5.0), Vector3::new(9.0, 0.0, 5.0), 3.0),
+        TrainingBot::patrolling(5, Vector3::new(9.0, 0.0, -5.0), Vector3::new(-9.0, 0.0, -5.0), 5.0),
+    ]
+}
+
+impl GameState {
+    /// Open the practice range (leaves any match that was running)
+    pub fn start_shooting_range(&mut self) {
+        log_info!(Game, "🎯 Starting shooting range");
+        self.begin_offline_session(range_map(), range_bots());
+        self.range = Some(RangeSession {
+            stats: RangeStats::new(),
+            previous_weapon: self.current_weapon,
+            equipped: self.current_weapon,
+        });
+    }
+
+    /// Range controls: Q swaps to the previous weapon, mouse wheel cycles, T resets dummies and stats
+    pub(super) fn update_range(&mut self, rl: &RaylibHandle) {
+        let Some(ref mut range) = self.range else {
+            return;
+        };
+        if self.current_weapon != range.equipped {
+            range.previous_weapon = range.equipped;
+            range.equipped = self.current_weapon;
+        }
+        let previous_weapon = range.previous_weapon;
+
+        if rl.is_key_pressed(KeyboardKey::KEY_Q) {
+            self.switch_weapon(previous_weapon);
+        }
+
+        let wheel = rl.get_mouse_wheel_move();
+        if wheel != 0.0 {
+            let count = self.carried_weapons.len();
+            if let Some(index) = self.carried_weapons.iter().position(|w| *w == self.current_weapon) {
+                let next = (if wheel > 0.0 { index + count - 1 } else { index + 1 }) % count;
+                self.switch_weapon(self.carried_weapons[next]);
+            }
+        }
+
+        if rl.is_key_pressed(KeyboardKey::KEY_T) {
+            self.reset_range();
+        }
+    }
+
+    /// Stand every dummy back up and clear the readout
+    fn reset_range(&mut self) {
+        log_info!(Game, "🎯 Resetting range");
+        for bot in &mut self.bots {
+            bot.reset();
+        }
+        self.damage_numbers.clear();
+        self.current_bullet_count = MAGAZINE_SIZE;
+        if let Some(ref mut range) = self.range {
+            range.stats.reset();
+        }
+    }
+
+    /// DPS / accuracy readout on the left side of the screen
+    pub(super) fn draw_range_panel(&self, d: &mut RaylibDrawHandle) {
+        let Some(ref range) = self.range else {
+            return;
+        };
+        let stats = &range.stats;
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+
+        let (x, y, width) = (20, 120, 220);
+        d.draw_rectangle(x, y, width, 230, Color::new(10, 10, 20, 200));
+        d.draw_rectangle_lines(x, y, width, 230, Color::new(0, 242, 148, 180));
+        draw_hud_text(d, t("range.title"), x + 12, y + 10, 16, Color::new(0, 242, 148, 255));
+
+        let weapon = self.current_weapon.label().to_string();
+        let rows = [
+            (t("range.weapon"), weapon),
+            (t("range.ammo"), format!("{}/{}", self.current_bullet_count, MAGAZINE_SIZE)),
+            (t("range.dps"), format!("{:.1}", stats.dps(now))),
+            (t("range.damage"), format!("{:.0}", stats.total_damage)),
+            (t("range.best_hit"), format!("{:.0}", stats.best_hit)),
+            (t("range.hits"), format!("{}/{}", stats.hits, stats.shots)),
+            (t("range.accuracy"), format!("{:.0}%", stats.accuracy() * 100.0)),
+        ];
+        for (i, (label, value)) in rows.iter().enumerate() {
+            let row_y = y + 38 + i as i32 * 20;
+            draw_hud_text(d, label, x + 12, row_y, 14, Color::new(200, 200, 220, 255));
+            let value_width = measure_hud_text(d, value, 14);
+            draw_hud_text(d, value, x + width - 12 - value_width, row_y, 14, Color::WHITE);
+        }
+
+        draw_hud_text(d, t("range.switch_keys"), x + 12, y + 186, 12, Color::new(150, 150, 170, 255));
+        draw_hud_text(d, t("range.reset_keys"), x + 12, y + 204, 12, Color::new(150, 150, 170, 255));
+    }
+}
@@ -0,0 +1,908 @@
+//! 3D world rendering (environment, other players, gun viewmodel)
+
+use raylib::prelude::*;
+use super::{GameState, GameMode, OtherPlayer};
+use crate::game::{Player, WeaponKind, BASE_FOV, palette, capsule_height};
+use crate::game::viewmodel::viewmodel;
+use crate::game::comfort::{comfort_scale, ComfortEffect};
+use crate::game::graphics::{render_mode, RenderMode};
+use crate::map::Ambiance;
+use super::combat::LOW_AMMO_THRESHOLD;
+use super::practice::MAGAZINE_SIZE;
+
+/// Ammo counter panel on the viewmodel: center in gun space (x right, y up, z forward) and size
+const AMMO_PANEL_CENTER: (f32, f32, f32) = (-0.1, 0.05, -0.1);
+const AMMO_PANEL_SIZE: (f32, f32) = (0.07, 0.065);
+
+/// Seven-segment digit cell on the ammo panel (width, height, gap between digits)
+const AMMO_DIGIT_SIZE: (f32, f32, f32) = (0.018, 0.034, 0.008);
+
+/// Lit segments per digit, bits a to g (top, top right, bottom right, bottom, bottom left, top left, middle)
+const DIGIT_SEGMENTS: [u8; 10] = [0x3F, 0x06, 0x5B, 0x4F, 0x66, 0x6D, 0x7D, 0x07, 0x7F, 0x6F];
+
+/// Segment strokes in a unit digit cell (0..1 across, 0..1 up), in DIGIT_SEGMENTS bit order
+const SEGMENT_STROKES: [((f32, f32), (f32, f32)); 7] = [
+    ((0.0, 1.0), (1.0, 1.0)),
+    ((1.0, 1.0), (1.0, 0.5)),
+    ((1.0, 0.5), (1.0, 0.0)),
+    ((0.0, 0.0), (1.0, 0.0)),
+    ((0.0, 0.0), (0.0, 0.5)),
+    ((0.0, 0.5), (0.0, 1.0)),
+    ((0.0, 0.5), (1.0, 0.5)),
+];
+
+impl GameState {
+    /// Sky, fog and light of the loaded map (the default night look before one loads)
+    pub fn ambiance(&self) -> Ambiance {
+        self.map.as_ref().map(|map| map.ambiance).unwrap_or_default()
+    }
+
+    /// Render the game world
+    pub fn render(&self, d: &mut RaylibDrawHandle, _thread: &RaylibThread, scene: Option<&RenderTexture2D>) {
+        if self.mode != GameMode::Playing {
+            return;
+        }
+        if let Some(ref loader) = self.map_loader {
+            self.draw_map_loading(d, loader.progress());
+            return;
+        }
+
+        // Get player camera (or the round-end flourish / spectator camera)
+        let camera = match (&self.spectator, &self.player) {
+            (Some(spectator), _) => Some(spectator.camera),
+            (None, Some(player)) => Some(self.shaken(self.presentation_camera(player).unwrap_or_else(|| self.scoped_camera(player)))),
+            (None, None) => None,
+        };
+
+        // Low-spec fallback replaces the 3D world in first person (spectators keep the free camera)
+        let retro = render_mode() == RenderMode::Retro && self.spectator.is_none() && self.map_grid.is_some();
+        if retro {
+            if let Some(ref player) = self.player {
+                self.draw_retro_world(d, player);
+            }
+        }
+
+        if let (Some(camera), false) = (camera, retro) {
+            // The scene goes through a render texture only when a pass needs its pixels
+            // (render textures aren't multisampled, so the default path keeps MSAA)
+            match scene {
+                Some(target) => {
+                    unsafe {
+                        raylib::ffi::BeginTextureMode(**target);
+                    }
+                    d.clear_background(self.ambiance().sky_color());
+                    self.draw_world(d, camera);
+                    unsafe {
+                        raylib::ffi::EndTextureMode();
+                    }
+                    Self::draw_scene_texture(target);
+                }
+                None => self.draw_world(d, camera),
+            }
+        }
+
+        // Vignette, whiteout and grain over the world, under the HUD
+        self.draw_post_effects(d);
+
+        if let Some(camera) = camera {
+            if self.spectator.as_ref().is_some_and(|s| s.show_overlays) {
+                self.draw_caster_overlays(d, &camera);
+            } else {
+                self.draw_nameplates(d, &camera);
+            }
+            self.draw_damage_numbers(d, &camera);
+        }
+
+        // Spectators get their own overlay instead of the player HUD
+        if self.is_spectating() {
+            self.draw_spectator_hud(d);
+            return;
+        }
+
+        // Round-end banner replaces the HUD
+        if !self.round.in_progress() {
+            self.draw_round_banner(d);
+            return;
+        }
+
+        // Freeze-time countdown over the HUD
+        self.draw_freeze_countdown(d);
+
+        // Voted timeout clock and the open vote
+        self.draw_timeout(d);
+        self.draw_vote(d);
+
+        // Draw 2D UI elements (crosshair, health bar) after 3D rendering
+        // Note: Minimap is now rendered in web UI for a modern look
+        if self.scope.is_scoped() {
+            self.draw_scope_overlay(d);
+        } else {
+            Self::draw_crosshair(d);
+            self.draw_threat_compass(d);
+        }
+
+        if let Some(ref player) = self.player {
+            // self.draw_minimap(d, player); // Disabled - now using web-based minimap
+            Self::draw_health_bar(d, player);
+            self.draw_grenade_count(d);
+            if self.show_reload_prompt {
+                Self::draw_reload_prompt(d);
+            }
+        }
+
+        // Dead zone warning and countdown, objectives from map triggers
+        self.draw_out_of_bounds_warning(d);
+        self.draw_objective(d);
+
+        // Spray / emote picker over the crosshair
+        self.draw_radial_menu(d);
+
+        // Top-down overview while V is held
+        self.draw_tactical_view(d);
+
+        // Tutorial instructions / range readout / practice score on top of the HUD
+        self.draw_tutorial_panel(d);
+        self.draw_range_panel(d);
+        self.draw_practice_panel(d);
+
+        // Touch controls disabled - using React VirtualJoystick instead
+        // if let Some(tc) = &self.touch_controls {
+        //     tc.draw(d);
+        // }
+
+        // No Rust-based settings hint or overlay; JS handles all settings UI.
+    }
+
+    /// 3D world from `camera`: environment, map, players, effects and the viewmodel
+    fn draw_world(&self, d: &mut RaylibDrawHandle, camera: Camera3D) {
+        let mut d3d = d.begin_mode3D(camera);
+
+        // Draw ground plane to match map size (50x50 units)
+        // Using a slightly lighter color for better visibility
+        d3d.draw_plane(
+            Vector3::new(0.0, -0.01, 0.0), // Slightly below Y=0 to avoid z-fighting
+            Vector2::new(50.0, 50.0),
+            self.ambiance().lit(Color::new(45, 45, 50, 255)), // Lighter gray ground for better contrast
+        );
+
+        // Draw grid on the ground (1x1 unit spacing for 50x50 map)
+        d3d.draw_grid(50, 1.0);
+
+        // Draw Solana logo in the sky (visible when looking down)
+        Self::draw_solana_logo(&mut d3d);
+
+        // Draw Solana-themed boundary walls at corners
+        Self::draw_boundary_walls(&mut d3d);
+
+        // Draw map if loaded, minus what the potentially-visible set hides
+        self.draw_map_culled(&mut d3d, camera.position);
+
+        // Draw other players from blockchain (minus the one a spectator is looking through, and those behind walls)
+        let spectator_pov = self.spectator.as_ref().and_then(|s| s.pov_authority(&self.spectator_targets()).map(str::to_string));
+        let visible = self.players_in_view(camera.position, spectator_pov.as_deref());
+        Self::draw_other_players(&mut d3d, &visible);
+        self.draw_emotes(&mut d3d, spectator_pov.as_deref());
+
+        // Draw team sprays on walls and floors
+        self.draw_sprays(&mut d3d);
+
+        // Draw training bots and the active tutorial zone (offline sessions)
+        self.draw_bots(&mut d3d);
+        self.draw_tutorial_zones(&mut d3d);
+
+        // Draw dropped weapons
+        self.draw_pickups(&mut d3d);
+
+        // Draw bullet trails
+        Self::draw_bullet_trails(&mut d3d, &self.bullet_trails);
+
+        // Draw hit feedback (blood or streamer-safe sparks)
+        Self::draw_hit_particles(&mut d3d, &self.hit_particles, self.streamer_safe);
+
+        // Draw glow halos and smoke clouds (translucent, so after opaque geometry)
+        self.draw_map_glow(&mut d3d, camera.position);
+        self.draw_smoke(&mut d3d, &camera);
+
+        // Draw some simple point lights as visual spheres (for ambient lighting effect)
+        // Top light
+        d3d.draw_sphere(
+            Vector3::new(0.0, 50.0, 0.0),
+            0.5,
+            Color::new(255, 255, 200, 100), // Semi-transparent warm light
+        );
+
+        // Draw gun model in front of camera (viewmodel), hidden during the round-end flourish and while scoped
+        if let (Some(player), None) = (&self.player, &self.spectator) {
+            if self.round.in_progress() && !self.scope.is_scoped() {
+                Self::draw_first_person_body(&mut d3d, player);
+                Self::draw_gun_viewmodel(&mut d3d, player, self.current_weapon, self.muzzle_flash_timer, self.reload_progress, self.current_bullet_count);
+            }
+        }
+    }
+
+    /// Draw the Solana logo in the sky (visible when looking down)
+    pub(super) fn draw_solana_logo(d3d: &mut RaylibMode3D<RaylibDrawHandle>) {
+        // Solana logo positioned high in the sky, facing downward
+        let logo_y = -100.0; // Below ground level (so it's visible when looking down)
+        let logo_center = Vector3::new(0.0, logo_y, 0.0);
+
+        // Solana colors (gradient from cyan to purple to magenta)
+        let color1 = Color::new(0, 255, 163, 255);   // Cyan/teal
+        let color2 = Color::new(156, 81, 255, 255);  // Purple
+        let color3 = Color::new(220, 31, 255, 255);  // Magenta
+
+        let bar_width = 20.0;
+        let bar_height = 2.0;
+        let bar_depth = 0.5;
+        let spacing = 3.5;
+
+        // Draw three diagonal bars with triangular ends (Solana logo style)
+        // Top bar (cyan) - with diagonal angle
+        let angle = 15.0_f32.to_radians();
+        let offset1 = Vector3::new(-5.0, spacing * 2.0, 0.0);
+
+        // Middle bar (purple)
+        let offset2 = Vector3::new(0.0, 0.0, 0.0);
+
+        // Bottom bar (magenta)
+        let offset3 = Vector3::new(5.0, -spacing * 2.0, 0.0);
+
+        // Draw bars as cubes with rotation to create diagonal effect
+        // Top bar
+        d3d.draw_cube(
+            logo_center + offset1,
+            bar_width, bar_height, bar_depth,
+            color1
+        );
+        // Add triangular end caps using triangles for top bar
+        Self::draw_triangle_cap(d3d, logo_center + offset1 + Vector3::new(bar_width / 2.0, 0.0, 0.0), color1, true);
+        Self::draw_triangle_cap(d3d, logo_center + offset1 - Vector3::new(bar_width / 2.0, 0.0, 0.0), color1, false);
+
+        // Middle bar
+        d3d.draw_cube(
+            logo_center + offset2,
+            bar_width, bar_height, bar_depth,
+            color2
+        );
+        Self::draw_triangle_cap(d3d, logo_center + offset2 + Vector3::new(bar_width / 2.0, 0.0, 0.0), color2, true);
+        Self::draw_triangle_cap(d3d, logo_center + offset2 - Vector3::new(bar_width / 2.0, 0.0, 0.0), color2, false);
+
+        // Bottom bar
+        d3d.draw_cube(
+            logo_center + offset3,
+            bar_width, bar_height, bar_depth,
+            color3
+        );
+        Self::draw_triangle_cap(d3d, logo_center + offset3 + Vector3::new(bar_width / 2.0, 0.0, 0.0), color3, true);
+        Self::draw_triangle_cap(d3d, logo_center + offset3 - Vector3::new(bar_width / 2.0, 0.0, 0.0), color3, false);
+    }
+
+    /// Draw triangular end cap for logo bars using small cubes
+    pub(super) fn draw_triangle_cap(d3d: &mut RaylibMode3D<RaylibDrawHandle>, position: Vector3, color: Color, facing_right: bool) {
+        let size = 1.5;
+        let direction = if facing_right { 1.0 } else { -1.0 };
+
+        // Draw a small cube/pyramid shape at the end of each bar
+        d3d.draw_cube(
+            position + Vector3::new(direction * size, 0.0, 0.0),
+            size * 2.0, size, 0.5,
+            color
+        );
+    }
+
+    /// Draw Solana-themed boundary walls at the corners of the map
+    pub(super) fn draw_boundary_walls(d3d: &mut RaylibMode3D<RaylibDrawHandle>) {
+        // Map size is 50x50, so boundaries are at +/- 25
+        let boundary = 25.0;
+        let wall_height = 10.0;
+        let wall_thickness = 1.0;
+        let wall_length = 15.0; // Length of each corner wall segment
+
+        // Solana colors (gradient from cyan to purple to magenta)
+        let color1 = Color::new(0, 255, 163, 255);   // Cyan/teal
+        let color2 = Color::new(156, 81, 255, 255);  // Purple
+        let color3 = Color::new(220, 31, 255, 255);  // Magenta
+
+        // Corner 1: +X, +Z (top-right) - Cyan walls
+        // Wall along X axis
+        d3d.draw_cube(
+            Vector3::new(boundary - wall_length / 2.0, wall_height / 2.0, boundary),
+            wall_length, wall_height, wall_thickness,
+            color1
+        );
+        d3d.draw_cube_wires(
+            Vector3::new(boundary - wall_length / 2.0, wall_height / 2.0, boundary),
+            wall_length, wall_height, wall_thickness,
+            Color::WHITE
+        );
+        // Wall along Z axis
+        d3d.draw_cube(
+            Vector3::new(boundary, wall_height / 2.0, boundary - wall_length / 2.0),
+            wall_thickness, wall_height, wall_length,
+            color1
+        );
+        d3d.draw_cube_wires(
+            Vector3::new(boundary, wall_height / 2.0, boundary - wall_length / 2.0),
+            wall_thickness, wall_height, wall_length,
+            Color::WHITE
+        );
+
+        // Corner 2: -X, +Z (top-left) - Purple walls
+        d3d.draw_cube(
+            Vector3::new(-boundary + wall_length / 2.0, wall_height / 2.0, boundary),
+            wall_length, wall_height, wall_thickness,
+            color2
+        );
+        d3d.draw_cube_wires(
+            Vector3::new(-boundary + wall_length / 2.0, wall_height / 2.0, boundary),
+            wall_length, wall_height, wall_thickness,
+            Color::WHITE
+        );
+        d3d.draw_cube(
+            Vector3::new(-boundary, wall_height / 2.0, boundary - wall_length / 2.0),
+            wall_thickness, wall_height, wall_length,
+            color2
+        );
+        d3d.draw_cube_wires(
+            Vector3::new(-boundary, wall_height / 2.0, boundary - wall_length / 2.0),
+            wall_thickness, wall_height, wall_length,
+            Color::WHITE
+        );
+
+        // Corner 3: +X, -Z (bottom-right) - Magenta walls
+        d3d.draw_cube(
+            Vector3::new(boundary - wall_length / 2.0, wall_height / 2.0, -boundary),
+            wall_length, wall_height, wall_thickness,
+            color3
+        );
+        d3d.draw_cube_wires(
+            Vector3::new(boundary - wall_length / 2.0, wall_height / 2.0, -boundary),
+            wall_length, wall_height, wall_thickness,
+            Color::WHITE
+        );
+        d3d.draw_cube(
+            Vector3::new(boundary, wall_height / 2.0, -boundary + wall_length / 2.0),
+            wall_thickness, wall_height, wall_length,
+            color3
+        );
+        d3d.draw_cube_wires(
+            Vector3::new(boundary, wall_height / 2.0, -boundary + wall_length / 2.0),
+            wall_thickness, wall_height, wall_length,
+            Color::WHITE
+        );
+
+        // Corner 4: -X, -Z (bottom-left) - Cyan again (completing the gradient loop)
+        d3d.draw_cube(
+            Vector3::new(-boundary + wall_length / 2.0, wall_height / 2.0, -boundary),
+            wall_length, wall_height, wall_thickness,
+            color1
+        );
+        d3d.draw_cube_wires(
+            Vector3::new(-boundary + wall_length / 2.0, wall_height / 2.0, -boundary),
+            wall_length, wall_height, wall_thickness,
+            Color::WHITE
+        );
+        d3d.draw_cube(
+            Vector3::new(-boundary, wall_height / 2.0, -boundary + wall_length / 2.0),
+            wall_thickness, wall_height, wall_length,
+            color1
+        );
+        d3d.draw_cube_wires(
+            Vector3::new(-boundary, wall_height / 2.0, -boundary + wall_length / 2.0),
+            wall_thickness, wall_height, wall_length,
+            Color::WHITE
+        );
+    }
+
+    /// Draw the local player's body (torso + legs) so looking down shows yourself
+    pub(super) fn draw_first_person_body(d3d: &mut RaylibMode3D<RaylibDrawHandle>, player: &Player) {
+        if player.is_dead {
+            return;
+        }
+
+        // Local frame: +X = forward, +Z = right, origin at the feet
+        // Crouching (and more so sliding) lowers the hips, which makes it obvious when looking down
+        let hip_height = if player.is_sliding() { 0.35 } else if player.is_crouching { 0.5 } else { 0.9 };
+        let leg_length = hip_height;
+        let swing = player.walk_cycle.sin() * 30.0 * player.stride_blend;
+
+        let torso_color = Color::new(40, 40, 55, 255);
+        let leg_color = Color::new(30, 30, 40, 255);
+        let boot_color = Color::new(20, 20, 25, 255);
+        let trim_color = Color::new(153, 69, 255, 255); // Solana purple belt
+
+        unsafe {
+            raylib::ffi::rlPushMatrix();
+            raylib::ffi::rlTranslatef(player.position.x, player.position.y, player.position.z);
+            raylib::ffi::rlRotatef(-player.yaw, 0.0, 1.0, 0.0);
+        }
+
+        // Torso sits slightly behind the eye so it doesn't clip the near plane
+        let torso_height = 0.55;
+        d3d.draw_cube(
+            Vector3::new(-0.18, hip_height + torso_height / 2.0, 0.0),
+            0.25, torso_height, 0.45,
+            torso_color,
+        );
+        d3d.draw_cube(Vector3::new(-0.18, hip_height + 0.03, 0.0), 0.27, 0.06, 0.47, trim_color);
+
+        // Legs swing in opposite phase around the hip joint
+        for (side, phase) in [(-0.12_f32, 1.0_f32), (0.12, -1.0)] {
+            unsafe {
+                raylib::ffi::rlPushMatrix();
+                raylib::ffi::rlTranslatef(-0.1, hip_height, side);
+                raylib::ffi::rlRotatef(swing * phase, 0.0, 0.0, 1.0);
+            }
+
+            d3d.draw_cube(Vector3::new(0.0, -leg_length / 2.0, 0.0), 0.15, leg_length, 0.15, leg_color);
+            d3d.draw_cube(Vector3::new(0.07, -leg_length + 0.05, 0.0), 0.28, 0.1, 0.16, boot_color);
+
+            unsafe {
+                raylib::ffi::rlPopMatrix();
+            }
+        }
+
+        unsafe {
+            raylib::ffi::rlPopMatrix();
+        }
+    }
+
+    /// Draw the gun viewmodel (first-person weapon view) - SIMPLIFIED VERSION
+    pub(super) fn draw_gun_viewmodel(d3d: &mut RaylibMode3D<RaylibDrawHandle>, player: &Player, weapon: WeaponKind, muzzle_flash_timer: f32, reload_progress: f32, ammo: u8) {
+        // 🎯 CRITICAL: Use the camera's actual position directly to avoid jitter
+        // The camera position is already smoothly interpolated by the reconciliation system
+        // This ensures the gun stays perfectly locked to the view, even during server corrections
+        let camera_pos = player.camera.position;
+
+        // Calculate gun position relative to camera
+        let yaw_rad = player.yaw.to_radians();
+        let pitch_rad = player.pitch.to_radians();
+
+        // Direction the camera is facing (forward)
+        let direction = Vector3::new(
+            yaw_rad.cos() * pitch_rad.cos(),
+            pitch_rad.sin(),
+            yaw_rad.sin() * pitch_rad.cos(),
+        );
+
+        // Right vector for positioning gun to the side
+        let right = Vector3::new(
+            (yaw_rad + 90.0_f32.to_radians()).cos(),
+            0.0,
+            (yaw_rad + 90.0_f32.to_radians()).sin(),
+        );
+
+        // Up vector (perpendicular to both forward and right)
+        let up = right.cross(direction).normalized();
+
+        // Player placement options: mirrored to the left hand and squeezed to the viewmodel FOV
+        // (mirroring flips triangle winding, so culling is off while the gun is drawn)
+        let settings = viewmodel();
+        let placement = settings.transform(camera_pos, right, up, direction, BASE_FOV);
+        unsafe {
+            raylib::ffi::rlDrawRenderBatchActive();
+            raylib::ffi::rlPushMatrix();
+            raylib::ffi::rlMultMatrixf(placement.as_ptr());
+            raylib::ffi::rlDisableBackfaceCulling();
+        }
+
+        // ENHANCED reload animation with multiple sophisticated stages
+        // Stage 1 (0.0-0.25): Gun tilts and moves down/left (inspect angle)
+        // Stage 2 (0.25-0.4): Magazine release + eject (drops down with rotation)
+        // Stage 3 (0.4-0.6): New magazine grab + insert (comes from side, inserts up)
+        // Stage 4 (0.6-0.75): Magazine lock + tap (small bounce)
+        // Stage 5 (0.75-0.9): Charging handle pull back and release
+        // Stage 6 (0.9-1.0): Gun returns to ready position
+        
+        let (reload_offset_y, reload_offset_x, reload_offset_z, reload_rotation_pitch, reload_rotation_roll, magazine_offset_y, magazine_offset_x, magazine_rotation) = if reload_progress > 0.0 {
+            if reload_progress < 0.25 {
+                // Stage 1: Tilt gun down and to the left for inspection
+                let stage_progress = reload_progress / 0.25;
+                let eased = stage_progress * stage_progress; // Ease-in
+                let y_offset = -eased * 0.5; // Move down more
+                let x_offset = eased * 0.2; // Move toward center/left
+                let z_offset = -eased * 0.15; // Pull back slightly
+                let rotation_pitch = eased * 60.0; // Tilt down 60 degrees
+                let rotation_roll = eased * -15.0; // Roll left 15 degrees
+                (y_offset, x_offset, z_offset, rotation_pitch, rotation_roll, 0.0, 0.0, 0.0)
+            } else if reload_progress < 0.4 {
+                // Stage 2: Magazine ejects - drops with spin
+                let stage_progress = (reload_progress - 0.25) / 0.15;
+                let eased = 1.0 - (1.0 - stage_progress).powi(2); // Ease-out (gravity)
+                let mag_drop = eased * 0.8; // Magazine falls faster
+                let mag_side = eased * 0.15; // Falls slightly to the side
+                let mag_spin = eased * 90.0; // Spins as it falls
+                (-0.5, 0.2, -0.15, 60.0, -15.0, -mag_drop, -mag_side, -mag_spin)
+            } else if reload_progress < 0.6 {
+                // Stage 3: New magazine appears from side and inserts
+                let stage_progress = (reload_progress - 0.4) / 0.2;
+                let eased = stage_progress * stage_progress; // Ease-in for controlled insertion
+                // Magazine comes from below-right and moves up-left
+                let mag_rise = -0.8 + eased * 0.8; // Start far below, rise to position
+                let mag_side = 0.3 - eased * 0.3; // Start to the right, move to center
+                let mag_tilt = 45.0 - eased * 45.0; // Start tilted, straighten
+                (-0.5, 0.2, -0.15, 60.0, -15.0, mag_rise, mag_side, mag_tilt)
+            } else if reload_progress < 0.75 {
+                // Stage 4: Magazine lock + tap (small bounce for emphasis)
+                let stage_progress = (reload_progress - 0.6) / 0.15;
+                let bounce = if stage_progress < 0.5 {
+                    stage_progress * 2.0 * 0.05 // Tap down
+                } else {
+                    (1.0 - (stage_progress - 0.5) * 2.0) * 0.05 // Bounce up
+                };
+                (-0.5, 0.2, -0.15, 60.0, -15.0, -bounce, 0.0, 0.0)
+            } else if reload_progress < 0.9 {
+                // Stage 5: Charging handle animation
+                let stage_progress = (reload_progress - 0.75) / 0.15;
+                // Gun stays in reload position while charging
+                (-0.5, 0.2, -0.15, 60.0, -15.0, 0.0, 0.0, 0.0)
+            } else {
+                // Stage 6: Return to ready position with smooth ease-out
+                let stage_progress = (reload_progress - 0.9) / 0.1;
+                let eased = 1.0 - (1.0 - stage_progress).powi(3); // Ease-out cubic
+                let y_offset = -0.5 + eased * 0.5; // Rise back up
+                let x_offset = 0.2 - eased * 0.2; // Move back to side
+                let z_offset = -0.15 + eased * 0.15; // Push forward
+                let rotation_pitch = 60.0 - eased * 60.0; // Straighten pitch
+                let rotation_roll = -15.0 + eased * 15.0; // Straighten roll
+                (y_offset, x_offset, z_offset, rotation_pitch, rotation_roll, 0.0, 0.0, 0.0)
+            }
+        } else {
+            (0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0)
+        };
+
+        // Walking bob: a figure-eight sway following the stride (scaled by the comfort setting)
+        let bob = player.stride_blend * comfort_scale(ComfortEffect::ViewBob);
+        let bob_x = player.walk_cycle.sin() * 0.015 * bob;
+        let bob_y = -(player.walk_cycle * 2.0).sin().abs() * 0.02 * bob;
+
+        // Landing impact pushes the gun down a little further than the camera
+        let landing_y = -player.landing_offset() * 0.06;
+
+        // Position gun base in front and to the right of camera using all three vectors
+        // Apply reload offsets for more dynamic movement
+        let gun_base = camera_pos 
+            + direction * (0.8 + reload_offset_z + settings.offset_z) // Forward/back
+            + right * (0.35 - reload_offset_x + bob_x + settings.offset_x) // Left/right
+            + up * (-0.3 + reload_offset_y + bob_y + landing_y + settings.offset_y); // Up/down
+
+        // Helper function to transform local gun coordinates to world space with advanced reload rotation
+        let to_world = |local_x: f32, local_y: f32, local_z: f32| -> Vector3 {
+            // Apply reload rotation around multiple axes for sophisticated animation
+            if reload_rotation_pitch.abs() > 0.01 || reload_rotation_roll.abs() > 0.01 {
+                let pitch_rad = reload_rotation_pitch.to_radians();
+                let roll_rad = reload_rotation_roll.to_radians();
+                
+                let cos_pitch = pitch_rad.cos();
+                let sin_pitch = pitch_rad.sin();
+                let cos_roll = roll_rad.cos();
+                let sin_roll = roll_rad.sin();
+                
+                // First apply pitch rotation (around right axis) - tilts gun up/down
+                let temp_y = local_y * cos_pitch - local_z * sin_pitch;
+                let temp_z = local_y * sin_pitch + local_z * cos_pitch;
+                
+                // Then apply roll rotation (around forward axis) - rolls gun left/right
+                let rotated_y = temp_y * cos_roll - local_x * sin_roll;
+                let rotated_x = temp_y * sin_roll + local_x * cos_roll;
+                let rotated_z = temp_z;
+                
+                gun_base + right * rotated_x + up * rotated_y + direction * rotated_z
+            } else {
+                gun_base + right * local_x + up * local_y + direction * local_z
+            }
+        };
+
+        // Draw gun as simple spheres with improved colors
+        let gun_body_color = Color::new(70, 70, 80, 255);
+        let gun_dark_color = Color::new(50, 50, 60, 255);
+        let magazine_color = Color::new(90, 90, 100, 255);
+
+        // Gun body - series of spheres along the forward axis
+        for i in 0..8 {
+            let z = (i as f32 - 4.0) * 0.08;
+            let pos = to_world(0.0, 0.0, z);
+            d3d.draw_sphere(pos, 0.06, gun_body_color);
+        }
+
+        // Barrel extension - forward from gun body
+        for i in 0..5 {
+            let z = 0.32 + i as f32 * 0.05;
+            let pos = to_world(0.0, 0.0, z);
+            d3d.draw_sphere(pos, 0.03, gun_dark_color);
+        }
+
+        // Magazine (sophisticated animation during reload) - positioned below gun body
+        // Magazine moves down when ejecting with spin, then new one appears from side
+        for i in 0..3 {
+            let base_y = -0.12 - i as f32 * 0.04;
+            let base_z = -0.05;
+            
+            // Apply magazine animation offsets and rotation
+            let mag_y = base_y + magazine_offset_y;
+            let mag_x = magazine_offset_x;
+            
+            // Transform magazine position with rotation
+            let mag_pos = if magazine_rotation.abs() > 0.01 {
+                let rot_rad = magazine_rotation.to_radians();
+                let cos_rot = rot_rad.cos();
+                let sin_rot = rot_rad.sin();
+                
+                // Rotate magazine around its center
+                let rotated_y = mag_y * cos_rot - base_z * sin_rot;
+                let rotated_z = mag_y * sin_rot + base_z * cos_rot;
+                
+                to_world(mag_x, rotated_y, rotated_z)
+            } else {
+                to_world(mag_x, mag_y, base_z)
+            };
+            
+            // Magazine visibility control during animation stages
+            let mag_alpha = if reload_progress > 0.25 && reload_progress < 0.4 {
+                // Stage 2: Ejecting old magazine - fade out quickly
+                let fade_progress = (reload_progress - 0.25) / 0.15;
+                (255.0 * (1.0 - fade_progress)) as u8
+            } else if reload_progress >= 0.4 && reload_progress < 0.6 {
+                // Stage 3: Inserting new magazine - fade in smoothly
+                let fade_progress = (reload_progress - 0.4) / 0.2;
+                (255.0 * fade_progress) as u8
+            } else if reload_progress > 0.0 && reload_progress <= 0.25 {
+                255 // Visible during initial stage
+            } else if reload_progress >= 0.6 {
+                255 // Fully visible after insertion
+            } else {
+                0 // Hidden between eject and insert
+            };
+            
+            d3d.draw_sphere(mag_pos, 0.04, Color::new(magazine_color.r, magazine_color.g, magazine_color.b, mag_alpha));
+        }
+
+        // Magazine release button (small detail)
+        let release_button = to_world(0.0, -0.08, -0.03);
+        d3d.draw_sphere(release_button, 0.015, Color::new(120, 120, 130, 255));
+
+        // Handle - downward and back from gun body (using up vector)
+        for i in 0..4 {
+            let y = -0.05 * i as f32;
+            let z = -0.2;
+            let pos = to_world(0.0, y, z);
+            d3d.draw_sphere(pos, 0.05, Color::new(70, 50, 40, 255));
+        }
+
+        // Trigger guard - downward from center (using up vector)
+        for i in 0..2 {
+            let y = -0.08 - i as f32 * 0.03;
+            let z = -0.1;
+            let pos = to_world(0.0, y, z);
+            d3d.draw_sphere(pos, 0.03, Color::new(156, 81, 255, 255)); // Solana purple
+        }
+        
+        // Charging handle (animated during stage 5 of reload)
+        // Pulls back and releases with smooth motion
+        let charging_handle_offset = if reload_progress > 0.75 && reload_progress < 0.9 {
+            let stage_progress = (reload_progress - 0.75) / 0.15;
+            if stage_progress < 0.5 {
+                // Pull back aggressively
+                let pull_progress = stage_progress * 2.0;
+                pull_progress * 0.15 // Pull back 15cm
+            } else {
+                // Release forward with spring motion
+                let release_progress = (stage_progress - 0.5) * 2.0;
+                let spring_release = 1.0 - release_progress + (release_progress * 0.2); // Small bounce
+                spring_release.max(0.0) * 0.15
+            }
+        } else {
+            0.0
+        };
+        
+        let charging_handle = to_world(0.02, 0.08, 0.15 - charging_handle_offset);
+        d3d.draw_sphere(charging_handle, 0.025, Color::new(100, 100, 110, 255));
+        
+        // Charging handle latch (detail piece)
+        let latch = to_world(0.02, 0.06, 0.13 - charging_handle_offset);
+        d3d.draw_sphere(latch, 0.015, Color::new(80, 80, 90, 255));
+
+        // Holographic magazine counter, dark while the magazine is out
+        if !(0.25..0.6).contains(&reload_progress) {
+            Self::draw_ammo_counter(d3d, &to_world, ammo, settings.left_handed);
+        }
+
+        // Muzzle flash effect when shooting
+        let flash_scale = comfort_scale(ComfortEffect::Flash);
+        if muzzle_flash_timer > 0.0 && flash_scale > 0.0 {
+            // Flash intensity fades with timer
+            let intensity = (muzzle_flash_timer / 0.05 * 255.0 * flash_scale) as u8;
+
+            // Bright yellow/orange flash at barrel tip
+            let flash_pos = to_world(0.0, 0.0, 0.6); // At the end of barrel
+            d3d.draw_sphere(flash_pos, 0.15, Color::new(255, 220, 100, intensity));
+
+            // Outer glow
+            d3d.draw_sphere(flash_pos, 0.25, Color::new(255, 180, 50, intensity / 2));
+
+            // Spread weapons get a wide, flat blast ring around the barrel
+            if weapon.pellet_count() > 1 {
+                for i in 0..6 {
+                    let angle = i as f32 / 6.0 * std::f32::consts::TAU;
+                    let petal = to_world(angle.cos() * 0.22, angle.sin() * 0.22, 0.7);
+                    d3d.draw_sphere(petal, 0.12, Color::new(255, 200, 80, intensity / 2));
+                }
+            }
+        }
+
+        unsafe {
+            raylib::ffi::rlDrawRenderBatchActive();
+            raylib::ffi::rlEnableBackfaceCulling();
+            raylib::ffi::rlPopMatrix();
+        }
+    }
+
+    /// Magazine count as a small hologram off the inner side of the receiver, in the Solana colors
+    fn draw_ammo_counter(d3d: &mut RaylibMode3D<RaylibDrawHandle>, to_world: &impl Fn(f32, f32, f32) -> Vector3, ammo: u8, left_handed: bool) {
+        // The left-handed mirror would print the digits back to front, so lay them out mirrored too
+        let flip = if left_handed { -1.0 } else { 1.0 };
+        let (center_x, center_y, center_z) = AMMO_PANEL_CENTER;
+        let at = |x: f32, y: f32| to_world(center_x + x * flip, center_y + y, center_z);
+
+        // Faint purple backing with a brighter frame
+        let (half_width, half_height) = (AMMO_PANEL_SIZE.0 / 2.0, AMMO_PANEL_SIZE.1 / 2.0);
+        let corners = [at(-half_width, -half_height), at(half_width, -half_height), at(half_width, half_height), at(-half_width, half_height)];
+        let backing = Color::new(156, 81, 255, 45);
+        d3d.draw_triangle3D(corners[0], corners[1], corners[2], backing);
+        d3d.draw_triangle3D(corners[0], corners[2], corners[3], backing);
+        for (i, corner) in corners.iter().enumerate() {
+            d3d.draw_line_3D(*corner, corners[(i + 1) % 4], Color::new(156, 81, 255, 160));
+        }
+
+        // Two seven-segment digits; unlit segments glow faintly like a real display
+        let lit = if ammo <= LOW_AMMO_THRESHOLD { palette::current().negative } else { Color::new(0, 255, 163, 255) };
+        let (digit_width, digit_height, gap) = AMMO_DIGIT_SIZE;
+        let tens = (ammo / 10 % 10) as usize;
+        let digits = [(ammo >= 10).then_some(tens), Some((ammo % 10) as usize)];
+        let bottom = -half_height + 0.018;
+        for (slot, digit) in digits.into_iter().enumerate() {
+            let left = -digit_width - gap / 2.0 + slot as f32 * (digit_width + gap);
+            let segments = digit.map_or(0, |digit| DIGIT_SEGMENTS[digit]);
+            for (bit, ((x0, y0), (x1, y1))) in SEGMENT_STROKES.iter().enumerate() {
+                let color = if segments & (1 << bit) != 0 { lit } else { Color::new(lit.r, lit.g, lit.b, 30) };
+                // Three parallel lines so the strokes read at viewmodel distance
+                let horizontal = y0 == y1;
+                for offset in [-0.0012, 0.0, 0.0012] {
+                    let (dx, dy) = if horizontal { (0.0, offset) } else { (offset, 0.0) };
+                    d3d.draw_line_3D(
+                        at(left + x0 * digit_width + dx, bottom + y0 * digit_height + dy),
+                        at(left + x1 * digit_width + dx, bottom + y1 * digit_height + dy),
+                        color,
+                    );
+                }
+            }
+        }
+
+        // Magazine fill bar along the bottom edge
+        let fill = (ammo as f32 / MAGAZINE_SIZE as f32).min(1.0);
+        let bar_y = -half_height + 0.008;
+        let bar_left = -half_width + 0.008;
+        let bar_right = bar_left + (AMMO_PANEL_SIZE.0 - 0.016) * fill;
+        for offset in [-0.001, 0.0, 0.001] {
+            d3d.draw_line_3D(at(bar_left, bar_y + offset), at(bar_right, bar_y + offset), Color::new(156, 81, 255, 230));
+        }
+    }
+
+    /// Draw other players in the game (from blockchain sync)
+    pub(super) fn draw_other_players(d3d: &mut RaylibMode3D<RaylibDrawHandle>, other_players: &[OtherPlayer]) {
+        let palette = palette::current();
+        for player in other_players {
+            // Skip dead players
+            if !player.is_alive {
+                continue;
+            }
+
+            // Choose color based on team (Team 1 = A, Team 2 = B; blue/red unless a color-blind preset is on)
+            let player_color = palette.team(&player.team);
+
+            // Draw player as a capsule (cylinder + spheres)
+            let height = capsule_height(player.sliding); // Player height (crouched down while sliding)
+            let radius = 0.3; // Player radius
+
+            // Draw body (cylinder)
+            d3d.draw_cylinder(
+                player.position,
+                radius,
+                radius,
+                height,
+                8,
+                player_color,
+            );
+
+            // Draw head (sphere on top)
+            let head_pos = Vector3::new(
+                player.position.x,
+                player.position.y + height,
+                player.position.z,
+            );
+            d3d.draw_sphere(head_pos, radius * 0.8, player_color);
+
+            // Usernames are drawn as 2D nameplates after the 3D pass (see draw_nameplates)
+
+            // Draw gun held by other player
+            Self::draw_other_player_gun(d3d, player, height);
+        }
+    }
+
+    /// Draw gun held by another player (third-person view)
+    pub(super) fn draw_other_player_gun(d3d: &mut RaylibMode3D<RaylibDrawHandle>, player: &OtherPlayer, player_height: f32) {
+        // rotation.y is yaw in radians from the contract
+        // rotation.x is pitch in radians
+        let yaw_rad = player.rotation.y;
+        let pitch_rad = player.rotation.x;
+
+        // Direction the player is facing (horizontal only for base direction)
+        let forward_horizontal = Vector3::new(
+            yaw_rad.cos(),
+            0.0,
+            yaw_rad.sin(),
+        );
+
+        // Right vector (perpendicular to forward, horizontal)
+        let right = Vector3::new(
+            (yaw_rad + std::f32::consts::PI / 2.0).cos(),
+            0.0,
+            (yaw_rad + std::f32::consts::PI / 2.0).sin(),
+        );
+
+        // Up vector (world up)
+        let up = Vector3::new(0.0, 1.0, 0.0);
+
+        // Calculate actual direction including pitch (same as first-person calculation)
+        let direction = Vector3::new(
+            yaw_rad.cos() * pitch_rad.cos(),
+            pitch_rad.sin(),
+            yaw_rad.sin() * pitch_rad.cos(),
+        ).normalized();
+
+        // Gun position (in front and to the right of player, at chest height)
+        let gun_base = Vector3::new(
+            player.position.x,
+            player.position.y + player_height * 0.55, // Chest height
+            player.position.z,
+        ) + direction * 0.4 + right * 0.2 + up * -0.1;
+
+        // Helper function to transform local gun coordinates to world space
+        let to_world = |local_x: f32, local_y: f32, local_z: f32| -> Vector3 {
+            gun_base + right * local_x + up * local_y + direction * local_z
+        };
+
+        // Gun color (same as first-person view)
+        let gun_color = Color::new(80, 80, 90, 255);
+
+        // Gun body - series of spheres along the forward axis
+        for i in 0..8 {
+            let z = (i as f32 - 4.0) * 0.08;
+            let pos = to_world(0.0, 0.0, z);
+            d3d.draw_sphere(pos, 0.06, gun_color);
+        }
+
+        // Barrel extension - forward from gun body
+        for i in 0..5 {
+            let z = 0.32 + i as f32 * 0.05;
+            let pos = to_world(0.0, 0.0, z);
+            d3d.draw_sphere(pos, 0.03, Color::new(60, 60, 70, 255));
+        }
+
+        // Handle - downward and back from gun body
+        for i in 0..4 {
+            let y = -0.05 * i as f32;
+            let z = -0.2;
+            let pos = to_world(0.0, y, z);
+            d3d.draw_sphere(pos, 0.05, Color::new(70, 50, 40, 255));
+        }
+
+        // Trigger guard - downward from center
+        for i in 0..2 {
+            let y = -0.08 - i as f32 * 0.03;
+            let z = -0.1;
+            let pos = to_world(0.0, y, z);
+            d3d.draw_sphere(pos, 0.03, Color::new(156, 81, 255, 255)); // Solana purple
+        }
+    }
+}
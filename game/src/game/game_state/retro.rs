@@ -0,0 +1,54 @@
+//! Low-spec retro renderer: the map grid drawn as raycast columns, players as billboards
+
+use raylib::prelude::*;
+use crate::game::{Player, BASE_FOV, palette};
+use crate::raycaster::Raycaster;
+use super::GameState;
+
+/// Billboard anchor above the feet (chest height) for the wall check
+const SPRITE_SIGHT_HEIGHT: f32 = 1.2;
+
+impl GameState {
+    /// First-person world without the 3D path: walls, visible players / bots and the gun
+    pub(super) fn draw_retro_world(&self, d: &mut RaylibDrawHandle, player: &Player) {
+        let Some(ref grid) = self.map_grid else {
+            return;
+        };
+        let (width, height) = (d.get_screen_width(), d.get_screen_height());
+        let (pos_x, pos_y) = grid.world_to_grid(player.position);
+        let yaw = player.yaw.to_radians();
+        let (dir_x, dir_y) = (yaw.cos(), yaw.sin());
+        // Camera plane points to the right; its length sets the horizontal FOV
+        let half_width = (BASE_FOV.to_radians() / 2.0).tan() * width as f32 / height as f32;
+        let (plane_x, plane_y) = (-dir_y * half_width, dir_x * half_width);
+
+        let mut raycaster = Raycaster::new(width, height);
+        raycaster.render(d, grid, pos_x, pos_y, dir_x, dir_y, plane_x, plane_y, player.pitch);
+
+        // Billboards have no depth test against the walls, so hidden ones are left out
+        let palette = palette::current();
+        let eye = player.camera.position;
+        let visible = |position: Vector3| !self.is_line_blocked(eye, position + Vector3::new(0.0, SPRITE_SIGHT_HEIGHT, 0.0));
+        let billboard = |position: Vector3, color: Color| {
+            let (x, y) = grid.world_to_grid(position);
+            (x, y, color)
+        };
+        let mut sprites: Vec<(f32, f32, Color)> = self
+            .other_players
+            .iter()
+            .filter(|p| p.is_alive && visible(p.position))
+            .map(|p| billboard(p.position, palette.team(&p.team)))
+            .collect();
+        sprites.extend(
+            self.bots
+                .iter()
+                .filter(|bot| bot.is_alive() && visible(bot.position))
+                .map(|bot| billboard(bot.position, palette.enemy)),
+        );
+        raycaster.render_sprites(d, pos_x, pos_y, dir_x, dir_y, plane_x, plane_y, &sprites);
+
+        if self.round.in_progress() && !self.scope.is_scoped() {
+            raycaster.render_gun(d, self.muzzle_flash_timer > 0.0);
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Crouch-slide sync: telling the other players when we slide so they draw us crouched down
+
+use super::GameState;
+
+impl GameState {
+    /// Send a slide event whenever the local player starts or stops sliding
+    pub(super) fn sync_slide_state(&mut self) {
+        let sliding = self.player.as_ref().is_some_and(|player| player.is_sliding());
+        if sliding == self.slide_sent {
+            return;
+        }
+        self.slide_sent = sliding;
+        let player = self.get_current_ephemeral_key();
+        self.send_game_event(serde_json::json!({ "type": "slide", "player": player, "active": sliding }));
+    }
+
+    /// Lower or raise another player's capsule
+    pub(super) fn apply_slide_event(&mut self, event: &serde_json::Value) {
+        let player = event.get("player").and_then(|v| v.as_str());
+        let active = event.get("active").and_then(|v| v.as_bool());
+        let (Some(player), Some(active)) = (player, active) else {
+            log_warn!(Game, "⚠️ Ignoring malformed slide event: {}", event);
+            return;
+        };
+        if let Some(other) = self.other_players.iter_mut().find(|p| p.authority == player) {
+            other.sliding = active && other.is_alive;
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Positional sound playback (distance falloff and occlusion behind walls)
+
+use raylib::prelude::*;
+use crate::audio::{mixer, AudioChannel};
+use super::GameState;
+
+/// Distance (world units) at which a world sound fades out completely
+const MAX_HEARING_DISTANCE: f32 = 45.0;
+
+/// Volume multiplier for sounds whose path to the listener is blocked by geometry
+/// (approximates the low-pass "muffled" effect)
+const OCCLUSION_VOLUME: f32 = 0.35;
+
+impl GameState {
+    /// Volume multiplier for a sound emitted at `source` as heard by the local player
+    pub(super) fn world_sound_gain(&self, source: Vector3) -> f32 {
+        let Some(listener) = self.listener_position() else {
+            return 0.0;
+        };
+
+        let falloff = (1.0 - listener.distance_to(source) / MAX_HEARING_DISTANCE).clamp(0.0, 1.0);
+        if falloff <= 0.0 {
+            return 0.0;
+        }
+
+        // Ringing ears after a flash drown out the world
+        let falloff = falloff * self.flash_muffle_gain();
+
+        let occluded = self.is_line_blocked(listener, source);
+        if occluded {
+            falloff * OCCLUSION_VOLUME
+        } else {
+            falloff
+        }
+    }
+
+    /// Where sounds are heard from: the spectator camera, or the local player's eyes
+    fn listener_position(&self) -> Option<Vector3> {
+        match (&self.spectator, &self.player) {
+            (Some(spectator), _) => Some(spectator.camera.position),
+            (None, Some(player)) => Some(player.camera.position),
+            (None, None) => None,
+        }
+    }
+
+    /// Keep the map's ambient loops at the right volume for where the listener is
+    pub(super) fn update_ambient_sounds(&mut self, delta: f32) {
+        if !self.ambient_sounds.due(delta) {
+            return;
+        }
+        let volumes = self.ambient_sound_volumes();
+        self.ambient_sounds.set_volumes(&volumes);
+    }
+
+    /// (loop, volume) for each of the map's ambient emitters, muffled behind walls like other world sounds
+    fn ambient_sound_volumes(&self) -> Vec<(&'static str, f32)> {
+        let (Some(map), Some(listener)) = (&self.map, self.listener_position()) else {
+            return Vec::new();
+        };
+        map.ambient_sounds
+            .iter()
+            .map(|emitter| {
+                let gain = emitter.gain(listener);
+                let occluded = gain > 0.0 && self.is_line_blocked(listener, emitter.position());
+                (emitter.sound.path(), if occluded { gain * OCCLUSION_VOLUME } else { gain })
+            })
+            .collect()
+    }
+
+    /// Play an effects-channel sound emitted somewhere in the world
+    pub(super) fn play_world_sound(&self, src: &str, source: Vector3, volume: f32) {
+        let gain = self.world_sound_gain(source);
+        if gain > 0.0 {
+            mixer::play_sound(src, AudioChannel::Effects, volume * gain);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Read-only spectator mode for watching live matches
+
+use raylib::prelude::*;
+use super::{GameState, GameMode};
+use crate::game::palette;
+use crate::game::fonts::{draw_hud_text, measure_hud_text};
+use crate::i18n::{t, tr};
+use crate::game::{SpectatorCamera, SpectatorMode, SpectatorTarget, split_clan_tag};
+
+impl GameState {
+    /// Enter spectator mode (no local player; other players come from the read-only subscription)
+    pub fn start_spectating(&mut self) {
+        log_info!(Game, "👁️ Switching to Spectator mode");
+        self.mode = GameMode::Playing;
+        self.mouse_captured = false;
+        self.player = None;
+        self.spectator = Some(SpectatorCamera::new());
+        self.round = crate::game::RoundState::new();
+        self.enemy_visibility.clear();
+    }
+
+    /// Whether we're watching a match rather than playing in it
+    pub fn is_spectating(&self) -> bool {
+        self.spectator.is_some()
+    }
+
+    /// Players the spectator camera can follow, in roster order (team A then team B, by name)
+    pub(super) fn spectator_targets(&self) -> Vec<SpectatorTarget> {
+        let mut targets: Vec<SpectatorTarget> = self.other_players
+            .iter()
+            .map(|p| SpectatorTarget {
+                authority: p.authority.clone(),
+                name: p.username.clone(),
+                team: p.team.clone(),
+                position: p.position,
+                yaw: p.rotation.y,
+                pitch: p.rotation.x,
+                health: p.health,
+                is_alive: p.is_alive,
+            })
+            .collect();
+        targets.sort_by(|a, b| a.team.cmp(&b.team).then_with(|| a.name.cmp(&b.name)));
+        targets
+    }
+
+    /// Advance the spectator camera and the remote player simulation
+    pub(super) fn update_spectator(&mut self, rl: &RaylibHandle, delta: f32) {
+        let targets = self.spectator_targets();
+        if let Some(ref mut spectator) = self.spectator {
+            if !self.show_settings {
+                spectator.update(rl, delta, &targets);
+            }
+        }
+
+        self.update_effects(delta);
+        self.interpolate_other_players(delta);
+        self.process_websocket_player_updates();
+        self.process_game_events();
+        self.update_votes();
+        self.update_grenades();
+        self.update_pickups();
+    }
+
+    /// Spectator overlay: who is being followed, the roster with POV keys and the controls
+    pub(super) fn draw_spectator_hud(&self, d: &mut RaylibDrawHandle) {
+        let Some(ref spectator) = self.spectator else {
+            return;
+        };
+        if !spectator.show_hud {
+            return;
+        }
+
+        let targets = self.spectator_targets();
+        let title = match (spectator.mode, spectator.followed_name(&targets)) {
+            (SpectatorMode::Pov { .. }, Some(name)) => tr!("spectator.pov", name = name),
+            (_, Some(name)) => tr!("spectator.following", name = name),
+            _ => tr!("spectator.free_camera", speed = format!("{:.0}", spectator.free_fly_speed)),
+        };
+
+        let screen_width = d.get_screen_width();
+        let screen_height = d.get_screen_height();
+        let font_size = 24;
+        let text_width = measure_hud_text(d, &title, font_size);
+        let x = (screen_width - text_width) / 2;
+
+        d.draw_rectangle(x - 16, 16, text_width + 32, font_size + 16, Color::new(13, 13, 17, 200));
+        draw_hud_text(d, &title, x, 24, font_size, Color::new(156, 81, 255, 255)); // Solana purple
+
+        self.draw_spectator_roster(d, &targets, spectator.target_index());
+
+        let hint = t("spectator.controls");
+        let hint_width = measure_hud_text(d, hint, 14);
+        draw_hud_text(d, hint, (screen_width - hint_width) / 2, screen_height - 30, 14, Color::new(200, 200, 220, 200));
+    }
+
+    /// Team rosters down the screen edges (team A left, team B right) with each player's POV key and health
+    fn draw_spectator_roster(&self, d: &mut RaylibDrawHandle, targets: &[SpectatorTarget], selected: Option<usize>) {
+        const ROW_HEIGHT: i32 = 26;
+        const WIDTH: i32 = 200;
+        let screen_width = d.get_screen_width();
+        let mut rows = [0, 0]; // Rows drawn per side
+        let palette = palette::current();
+
+        for (index, target) in targets.iter().enumerate() {
+            let side = if target.team == "1" { 0 } else { 1 };
+            let x = if side == 0 { 16 } else { screen_width - WIDTH - 16 };
+            let y = 90 + rows[side] * ROW_HEIGHT;
+            rows[side] += 1;
+
+            let background = if selected == Some(index) {
+                Color::new(156, 81, 255, 170)
+            } else {
+                Color::new(13, 13, 17, 170)
+            };
+            d.draw_rectangle(x, y, WIDTH, ROW_HEIGHT - 4, background);
+
+            // POV key (only the first ten players have one)
+            if index < 10 {
+                draw_hud_text(d, &((index + 1) % 10).to_string(), x + 6, y + 4, 14, Color::new(200, 200, 220, 255));
+            }
+            let name_color = if target.is_alive { palette.team_text(&target.team) } else { Color::new(110, 110, 120, 200) };
+            draw_hud_text(d, &target.name, x + 22, y + 4, 14, name_color);
+
+            // Health bar along the bottom of the row
+            let health = if target.is_alive { (target.health / 100.0).clamp(0.0, 1.0) } else { 0.0 };
+            d.draw_rectangle(x + 22, y + ROW_HEIGHT - 8, ((WIDTH - 28) as f32 * health) as i32, 3, name_color);
+        }
+    }
+
+    /// Caster overlay: name and health above every living player, walls and smoke included
+    pub(super) fn draw_caster_overlays(&self, d: &mut RaylibDrawHandle, camera: &Camera3D) {
+        let forward = (camera.target - camera.position).normalized();
+        let targets = self.spectator_targets();
+        let pov = self.spectator.as_ref().and_then(|s| s.pov_authority(&targets));
+        let palette = palette::current();
+
+        for target in targets.iter().filter(|t| t.is_alive && Some(t.authority.as_str()) != pov) {
+            let head = target.position + Vector3::new(0.0, 2.1, 0.0);
+            if (head - camera.position).dot(forward) <= 0.0 {
+                continue;
+            }
+
+            let screen = d.get_world_to_screen(head, *camera);
+            let color = palette.team_text(&target.team);
+            let (_, name) = split_clan_tag(&target.name);
+            let name_width = measure_hud_text(d, name, 14);
+            let (x, y) = (screen.x as i32, screen.y as i32);
+            draw_hud_text(d, name, x - name_width / 2, y - 10, 14, color);
+
+            let health = (target.health / 100.0).clamp(0.0, 1.0);
+            d.draw_rectangle(x - 20, y + 6, 40, 5, Color::new(0, 0, 0, 160));
+            d.draw_rectangle(x - 20, y + 6, (40.0 * health) as i32, 5, color);
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! Sprays and emotes: the hold-T radial menu, syncing them as game events and drawing decals / gestures
+
+use raylib::prelude::*;
+use crate::game::{Emote, RadialSlot, Spray, RADIAL_SLOTS, radial_slot_at, radial_slot_direction, palette};
+use crate::game::emote::{SPRAY_RANGE, SPRAY_SIZE};
+use crate::game::fonts::{draw_hud_text, measure_hud_text};
+use crate::i18n::t;
+use super::{GameState, emscripten_get_now};
+use super::net_sync::event_vector;
+
+/// Radial menu radius on screen (px); the cursor can't leave it
+const RADIAL_RADIUS: f32 = 130.0;
+
+/// Gap between a spray and the surface it's on (avoids z-fighting)
+const SPRAY_OFFSET: f32 = 0.02;
+
+impl GameState {
+    /// Hold T to open the radial menu, steer with the mouse, release to spray / emote
+    pub(super) fn update_radial_menu(&mut self, rl: &RaylibHandle, round_live: bool) {
+        let alive = self.player.as_ref().is_some_and(|p| !p.is_dead);
+        let held = round_live && alive && rl.is_key_down(KeyboardKey::KEY_T);
+
+        if held {
+            let cursor = self.radial_cursor.unwrap_or_else(Vector2::zero) + rl.get_mouse_delta();
+            let length = cursor.length();
+            self.radial_cursor = Some(if length > RADIAL_RADIUS { cursor * (RADIAL_RADIUS / length) } else { cursor });
+        } else if let Some(cursor) = self.radial_cursor.take() {
+            // Released (or interrupted by death / round end, which cancels)
+            if round_live && alive {
+                match radial_slot_at(cursor).map(|i| RADIAL_SLOTS[i]) {
+                    Some(RadialSlot::Spray) => self.place_spray(),
+                    Some(RadialSlot::Emote(emote)) => self.play_emote(emote),
+                    None => {}
+                }
+            }
+        }
+
+        if let Some(ref mut player) = self.player {
+            player.look_locked = self.radial_cursor.is_some();
+        }
+    }
+
+    /// Paint our team's spray on the surface under the crosshair
+    fn place_spray(&mut self) {
+        let Some(ref player) = self.player else {
+            return;
+        };
+        let owner = self.get_current_ephemeral_key();
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        if !self.sprays.can_spray(&owner, now) {
+            log_info!(Game, "🎨 Spray is on cooldown");
+            return;
+        }
+
+        let origin = player.camera.position;
+        let direction = (player.camera.target - origin).normalized();
+        let mut hit = self.map_index.as_ref().and_then(|index| index.raycast(Ray::new(origin, direction), SPRAY_RANGE));
+        // The ground isn't a map object
+        if direction.y < 0.0 {
+            let ground = -origin.y / direction.y;
+            let closer = hit.map_or(true, |(point, _)| ground < (point - origin).length());
+            if ground <= SPRAY_RANGE && closer {
+                hit = Some((origin + direction * ground, Vector3::new(0.0, 1.0, 0.0)));
+            }
+        }
+        let Some((position, normal)) = hit else {
+            log_info!(Game, "🎨 Nothing to spray on");
+            return;
+        };
+
+        self.send_game_event(serde_json::json!({
+            "type": "spray",
+            "owner": owner,
+            "team": self.current_player_team,
+            "position": [position.x, position.y, position.z],
+            "normal": [normal.x, normal.y, normal.z],
+        }));
+        self.sprays.add(Spray { owner, team: self.current_player_team, position, normal, placed_at: now });
+    }
+
+    /// Play a gesture on our model (seen by everyone else)
+    fn play_emote(&mut self, emote: Emote) {
+        let player = self.get_current_ephemeral_key();
+        log_info!(Game, "👋 Emote: {}", emote.id());
+        self.send_game_event(serde_json::json!({ "type": "emote", "player": player, "emote": emote.id() }));
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        self.emotes.play(player, emote, now);
+    }
+
+    /// Apply a spray placed by another player
+    pub(super) fn apply_spray_event(&mut self, event: &serde_json::Value) {
+        let owner = event.get("owner").and_then(|v| v.as_str());
+        let team = event.get("team").and_then(|v| v.as_u64());
+        let (Some(owner), Some(team), Some(position), Some(normal)) =
+            (owner, team, event_vector(event, "position"), event_vector(event, "normal"))
+        else {
+            log_warn!(Game, "⚠️ Ignoring malformed spray event: {}", event);
+            return;
+        };
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        self.sprays.add(Spray { owner: owner.to_string(), team: team as u8, position, normal: normal.normalized(), placed_at: now });
+    }
+
+    /// Start a gesture another player triggered (timed from when we hear about it)
+    pub(super) fn apply_emote_event(&mut self, event: &serde_json::Value) {
+        let player = event.get("player").and_then(|v| v.as_str());
+        let emote = event.get("emote").and_then(|v| v.as_str()).and_then(Emote::from_id);
+        let (Some(player), Some(emote)) = (player, emote) else {
+            log_warn!(Game, "⚠️ Ignoring malformed emote event: {}", event);
+            return;
+        };
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        self.emotes.play(player.to_string(), emote, now);
+    }
+
+    /// Drop finished gestures
+    pub(super) fn update_emotes(&mut self) {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        self.emotes.expire(now);
+    }
+
+    /// Draw sprays as team-colored discs flat on their surface
+    pub(super) fn draw_sprays(&self, d3d: &mut RaylibMode3D<RaylibDrawHandle>) {
+        let palette = palette::current();
+        for spray in self.sprays.sprays() {
+            let normal = spray.normal;
+            // Walls keep the decal upright, floors / ceilings orient it along X
+            let reference = if normal.y.abs() > 0.9 { Vector3::new(1.0, 0.0, 0.0) } else { Vector3::new(0.0, 1.0, 0.0) };
+            let right = reference.cross(normal).normalized();
+            let up = normal.cross(right).normalized();
+            let center = spray.position + normal * SPRAY_OFFSET;
+
+            let team = palette.team(&spray.team.to_string());
+            let rings = [
+                (SPRAY_SIZE / 2.0, Color::new(team.r, team.g, team.b, 200)),
+                (SPRAY_SIZE * 0.32, Color::new(20, 20, 25, 200)),
+                (SPRAY_SIZE * 0.18, Color::new(team.r, team.g, team.b, 230)),
+            ];
+            for (i, (radius, color)) in rings.into_iter().enumerate() {
+                // Each ring a hair further out so the inner ones draw over the outer ones
+                let center = center + normal * (i as f32 * 0.003);
+                const SIDES: usize = 12;
+                let corner = |k: usize| {
+                    let angle = k as f32 / SIDES as f32 * std::f32::consts::TAU;
+                    center + right * (angle.cos() * radius) + up * (angle.sin() * radius)
+                };
+                for k in 0..SIDES {
+                    // Both windings so the decal shows whichever side the surface faces
+                    d3d.draw_triangle3D(center, corner(k), corner(k + 1), color);
+                    d3d.draw_triangle3D(center, corner(k + 1), corner(k), color);
+                }
+            }
+        }
+    }
+
+    /// Draw gesture arms on other players who are emoting (skipping the spectated player)
+    pub(super) fn draw_emotes(&self, d3d: &mut RaylibMode3D<RaylibDrawHandle>, skip: Option<&str>) {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        let palette = palette::current();
+        for player in &self.other_players {
+            if !player.is_alive || skip == Some(player.authority.as_str()) {
+                continue;
+            }
+            let Some((emote, progress)) = self.emotes.progress(&player.authority, now) else {
+                continue;
+            };
+            let pose = emote.arm_pose(progress);
+
+            // Same yaw convention as draw_other_player_gun
+            let yaw = player.rotation.y;
+            let forward = Vector3::new(yaw.cos(), 0.0, yaw.sin());
+            let right = Vector3::new(-yaw.sin(), 0.0, yaw.cos());
+            let down = Vector3::new(0.0, -1.0, 0.0);
+            let color = palette.team(&player.team);
+
+            let sides: &[f32] = if pose.both { &[1.0, -1.0] } else { &[1.0] };
+            for &side in sides {
+                let shoulder = player.position + Vector3::new(0.0, 1.45, 0.0) + right * (0.32 * side);
+                let raised = down * pose.lift.cos() + forward * pose.lift.sin();
+                let arm = (raised * pose.spread.cos() + right * (side * pose.spread.sin())).normalized();
+                let hand = shoulder + arm * 0.65;
+                d3d.draw_cylinder_ex(shoulder, hand, 0.08, 0.07, 8, color);
+                d3d.draw_sphere(hand, 0.09, color);
+            }
+        }
+    }
+
+    /// Draw the radial menu while T is held
+    pub(super) fn draw_radial_menu(&self, d: &mut RaylibDrawHandle) {
+        let Some(cursor) = self.radial_cursor else {
+            return;
+        };
+        let center = Vector2::new(d.get_screen_width() as f32 / 2.0, d.get_screen_height() as f32 / 2.0);
+        let selected = radial_slot_at(cursor);
+
+        d.draw_circle_v(center, RADIAL_RADIUS + 30.0, Color::new(0, 0, 0, 150));
+        for (i, slot) in RADIAL_SLOTS.iter().enumerate() {
+            let position = center + radial_slot_direction(i) * RADIAL_RADIUS * 0.72;
+            let highlighted = selected == Some(i);
+            if highlighted {
+                d.draw_circle_v(position, 36.0, Color::new(153, 69, 255, 200));
+            }
+            let label = t(slot.label_key());
+            let size = 18;
+            let width = measure_hud_text(d, label, size);
+            let color = if highlighted { Color::WHITE } else { Color::new(200, 200, 210, 255) };
+            draw_hud_text(d, label, position.x as i32 - width / 2, position.y as i32 - size / 2, size, color);
+        }
+        d.draw_circle_v(center + cursor, 5.0, Color::WHITE);
+    }
+}
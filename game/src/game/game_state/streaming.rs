@@ -0,0 +1,78 @@
+//! Streamed maps: a map too large for one account starts on its head chunk and the bridge
+//! queues the rest (see `MapStream`); one chunk joins the running map per frame
+
+use std::ffi::CString;
+use base64::{Engine as _, engine::general_purpose};
+use super::{GameState, emscripten_run_script_string};
+use crate::map::{Map, MapStream};
+
+impl GameState {
+    /// Expect the rest of the map being loaded from the bridge's chunk queue
+    pub fn begin_map_stream(&mut self, stream: MapStream) {
+        log_info!(Solana, "📦 Map arrives in {} chunks; starting on the first", stream.total_chunks());
+        self.map_stream = Some(stream);
+    }
+
+    /// Share of a streamed map received, or None when no map is streaming
+    pub fn map_stream_progress(&self) -> Option<f32> {
+        self.map_stream.as_ref().map(MapStream::progress)
+    }
+
+    /// Add the next queued chunk, if any, to the running map
+    pub(super) fn poll_map_stream(&mut self) {
+        if self.map_stream.is_none() || self.map.is_none() {
+            return;
+        }
+        let Some(chunk) = take_queued_chunk() else {
+            return;
+        };
+        let (Some(map_id), Some(index)) = (
+            chunk.get("mapId").and_then(|v| v.as_str()),
+            chunk.get("index").and_then(|v| v.as_u64()),
+        ) else {
+            return;
+        };
+        let Some(stream) = self.map_stream.as_mut() else {
+            return;
+        };
+        if !stream.accept(map_id, index as usize) {
+            return;
+        }
+
+        let decoded = chunk.get("data").and_then(|v| v.as_str()).ok_or_else(|| "not fetched".to_string()).and_then(|data| {
+            let bytes = general_purpose::STANDARD.decode(data).map_err(|e| format!("{:?}", e))?;
+            Map::from_borsh_bytes(&bytes).map_err(|e| format!("{:?}", e))
+        });
+        let complete = stream.is_complete();
+        match (decoded, self.map.as_mut()) {
+            (Ok(part), Some(map)) => {
+                log_info!(Solana, "📦 Map chunk {} arrived: {} objects", index + 1, part.objects.len());
+                // Appended, so trigger and collision settings keyed by object index stay put
+                map.objects.extend(part.objects);
+            }
+            (Err(e), _) => log_error!(Solana, "❌ Map chunk {} of '{}' is missing: {}", index + 1, map_id, e),
+            _ => {}
+        }
+
+        // New objects collide right away; the sight grid and PVS are rebuilt once, when the map is whole
+        self.rebuild_map_queries(complete);
+        if complete {
+            log_info!(Solana, "✅ Streamed map complete: {} objects", self.map.as_ref().map_or(0, |m| m.objects.len()));
+            self.map_stream = None;
+        }
+    }
+}
+
+/// Oldest chunk in `Module.mapChunkQueue`, as queued by the bridge's `streamMapChunks`
+fn take_queued_chunk() -> Option<serde_json::Value> {
+    let js_code = "(function() { var q = Module.mapChunkQueue; return (q && q.length) ? q.shift() : ''; })()";
+    unsafe {
+        let c_str = CString::new(js_code).unwrap();
+        let result_ptr = emscripten_run_script_string(c_str.as_ptr());
+        if result_ptr.is_null() {
+            return None;
+        }
+        let json = std::ffi::CStr::from_ptr(result_ptr).to_string_lossy();
+        serde_json::from_str(&json).ok()
+    }
+}
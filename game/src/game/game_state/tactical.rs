@@ -0,0 +1,67 @@
+//! Top-down map grid: the hold-V tactical overview (drawn with the raycaster) and grid line of sight
+
+use raylib::prelude::*;
+use crate::game::palette;
+use crate::game::fonts::{draw_hud_text, measure_hud_text};
+use crate::i18n::t;
+use crate::raycaster::{Raycaster, Sight};
+use super::GameState;
+
+/// Overview panel size as a fraction of the shorter screen side
+const TACTICAL_PANEL_SCALE: f32 = 0.8;
+
+impl GameState {
+    /// Whether map geometry blocks the line between two points
+    ///
+    /// The grid settles most queries; only lines it can't decide (low cover,
+    /// odd shapes, high up) fall back to the exact check through the spatial index.
+    pub(super) fn is_line_blocked(&self, from: Vector3, to: Vector3) -> bool {
+        let Some(ref index) = self.map_index else {
+            return false;
+        };
+        match self.map_grid.as_ref().map(|grid| grid.line_of_sight(from, to)) {
+            Some(Sight::Clear) => false,
+            Some(Sight::Blocked) => true,
+            Some(Sight::Unsure) | None => index.is_line_blocked(from, to),
+        }
+    }
+
+    /// Open the overview while V is held (needs a loaded map)
+    pub(super) fn update_tactical_view(&mut self, rl: &RaylibHandle) {
+        self.show_tactical = self.map_grid.is_some() && rl.is_key_down(KeyboardKey::KEY_V);
+    }
+
+    /// Walls, our sightlines and living teammates, centered on screen
+    pub(super) fn draw_tactical_view(&self, d: &mut RaylibDrawHandle) {
+        if !self.show_tactical {
+            return;
+        }
+        let (Some(map2d), Some(player)) = (&self.map_grid, &self.player) else {
+            return;
+        };
+
+        let (screen_width, screen_height) = (d.get_screen_width(), d.get_screen_height());
+        let size = (screen_width.min(screen_height) as f32 * TACTICAL_PANEL_SCALE) as i32;
+        let (left, top) = ((screen_width - size) / 2, (screen_height - size) / 2);
+
+        let local_team = self.current_player_team.to_string();
+        let friendly = palette::current().friendly;
+        let teammates: Vec<(f32, f32, Color)> = self
+            .other_players
+            .iter()
+            .filter(|p| p.is_alive && p.team == local_team)
+            .map(|p| {
+                let (x, y) = map2d.world_to_grid(p.position);
+                (x, y, friendly)
+            })
+            .collect();
+
+        let (x, y) = map2d.world_to_grid(player.position);
+        let yaw = player.yaw.to_radians();
+        Raycaster::new(size, size).render_top_down(d, map2d, left, top, x, y, yaw.cos(), yaw.sin(), &teammates);
+
+        let title = t("hud.tactical");
+        let title_width = measure_hud_text(d, title, 20);
+        draw_hud_text(d, title, (screen_width - title_width) / 2, top + 8, 20, Color::new(0, 242, 148, 255));
+    }
+}
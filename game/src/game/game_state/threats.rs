@@ -0,0 +1,69 @@
+//! Threat compass: a ring under the crosshair that remembers where recent damage and enemy
+//! gunfire came from, fading over a couple of seconds (the red edge flash only says "hit")
+
+use raylib::prelude::*;
+use crate::game::{palette, threat_bearing, ThreatKind, THREAT_SECTORS};
+use super::GameState;
+use super::effects::DAMAGE_FOR_FULL_SHAKE;
+
+/// Weakest a hit lights its sector, so chip damage still shows
+const MIN_DAMAGE_WEIGHT: f32 = 0.3;
+
+/// How much an unmuffled enemy shot (world sound gain 1) lights its sector
+const GUNFIRE_WEIGHT: f32 = 0.35;
+
+/// How far under the crosshair the ring sits (pixels)
+const COMPASS_OFFSET: f32 = 70.0;
+
+/// Ring radii (pixels): gunfire on the inside, damage on the outside
+const GUNFIRE_RING: (f32, f32) = (20.0, 24.0);
+const DAMAGE_RING: (f32, f32) = (26.0, 32.0);
+
+/// Gap left between neighbouring sectors (degrees)
+const SECTOR_GAP: f32 = 2.0;
+
+impl GameState {
+    /// Remember a threat at `source`: `amount` is the damage taken, or the shot's sound gain for gunfire
+    pub(super) fn note_threat(&mut self, kind: ThreatKind, source: Vector3, amount: f32) {
+        let Some(player) = self.player.as_ref().filter(|p| !p.is_dead) else {
+            return;
+        };
+        let weight = match kind {
+            ThreatKind::Damage => (amount / DAMAGE_FOR_FULL_SHAKE).max(MIN_DAMAGE_WEIGHT),
+            ThreatKind::Gunfire => amount * GUNFIRE_WEIGHT,
+        };
+        self.threats.add(kind, threat_bearing(player.position, source), weight);
+    }
+
+    /// Where another player (or backfill bot) is, to pin a threat on them
+    pub(super) fn threat_source(&self, authority: &str) -> Option<Vector3> {
+        self.other_players.iter().find(|p| p.authority == authority).map(|p| p.position)
+    }
+
+    /// Recent threats around a ring under the crosshair; straight up is straight ahead
+    pub(super) fn draw_threat_compass(&self, d: &mut RaylibDrawHandle) {
+        let Some(player) = self.player.as_ref().filter(|p| !p.is_dead) else {
+            return;
+        };
+        if self.threats.is_empty() {
+            return;
+        }
+
+        let center = Vector2::new(d.get_screen_width() as f32 / 2.0, d.get_screen_height() as f32 / 2.0 + COMPASS_OFFSET);
+        d.draw_ring(center, GUNFIRE_RING.0, DAMAGE_RING.1, 0.0, 360.0, 48, Color::new(0, 0, 0, 60));
+
+        let negative = palette::current().negative;
+        let half_span = 180.0 / THREAT_SECTORS as f32 - SECTOR_GAP / 2.0;
+        for (angle, damage, gunfire) in self.threats.sectors(player.yaw.to_radians()) {
+            // Ring angles start at the right and run clockwise on screen, like yaw to the right
+            let middle = angle.to_degrees() - 90.0;
+            let (start, end) = (middle - half_span, middle + half_span);
+            if gunfire > 0.0 {
+                d.draw_ring(center, GUNFIRE_RING.0, GUNFIRE_RING.1, start, end, 4, Color::new(255, 190, 60, (gunfire * 220.0) as u8));
+            }
+            if damage > 0.0 {
+                d.draw_ring(center, DAMAGE_RING.0, DAMAGE_RING.1, start, end, 4, Color::new(negative.r, negative.g, negative.b, (damage * 230.0) as u8));
+            }
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! In-match votes (timeout, surrender, kick): called from the pause menu, tallied from every connected client
+
+use std::collections::HashSet;
+use raylib::prelude::*;
+use crate::game::{Vote, VoteKind, VoteOutcome, TIMEOUT_DURATION, is_bot_authority};
+use crate::game::fonts::{draw_hud_text, measure_hud_text};
+use crate::audio::{play_ui_sound, UiSound};
+use crate::i18n::{t, tr};
+use super::{GameState, emscripten_run_script, emscripten_get_now};
+
+impl GameState {
+    /// Apply a vote event: a request from our own pause menu, or a call / ballot relayed from another client.
+    /// Relayed events name their sender in `from` (stamped by the relay), which is who called or voted
+    pub(super) fn apply_vote_event(&mut self, event: &serde_json::Value) {
+        let kind = event.get("kind").and_then(|v| v.as_str());
+        let target = event.get("target").and_then(|v| v.as_str());
+        let from = event.get("from").and_then(|v| v.as_str());
+
+        match event.get("type").and_then(|t| t.as_str()) {
+            Some("voteRequest") => match kind.and_then(|k| VoteKind::from_id(k, target)) {
+                Some(kind) => self.call_vote(kind),
+                None => log_warn!(Game, "⚠️ Ignoring malformed vote request: {}", event),
+            },
+            Some("voteCall") => {
+                let id = event.get("id").and_then(|v| v.as_str());
+                let team = event.get("team").and_then(|v| v.as_u64());
+                let (Some(id), Some(kind), Some(caller), Some(team)) =
+                    (id, kind.and_then(|k| VoteKind::from_id(k, target)), from, team)
+                else {
+                    log_warn!(Game, "⚠️ Ignoring malformed vote call: {}", event);
+                    return;
+                };
+                // The caller's team as the chain has it, when we know them
+                let team = self.other_players.iter()
+                    .find(|p| p.authority == caller)
+                    .and_then(|p| p.team.parse().ok())
+                    .unwrap_or(team as u8);
+                // The caller's clock isn't ours: the vote runs for VOTE_DURATION from when it reaches us
+                let now = unsafe { emscripten_get_now() / 1000.0 };
+                let vote = Vote::new(id.to_string(), kind, caller.to_string(), team, now);
+                if self.votes.open(vote, now) {
+                    play_ui_sound(UiSound::CountdownTick);
+                }
+            }
+            Some("voteCast") => {
+                let id = event.get("id").and_then(|v| v.as_str());
+                let yes = event.get("yes").and_then(|v| v.as_bool());
+                if let (Some(id), Some(voter), Some(yes)) = (id, from, yes) {
+                    self.votes.cast(id, voter, yes);
+                }
+            }
+            _ => log_warn!(Game, "⚠️ Ignoring malformed vote event: {}", event),
+        }
+    }
+
+    /// Open a vote from the pause menu and announce it to the other clients
+    fn call_vote(&mut self, kind: VoteKind) {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        if self.is_spectating() || !self.round.in_progress() || !self.votes.can_call(now) {
+            log_info!(Game, "🗳️ Can't call a vote right now");
+            return;
+        }
+
+        let caller = self.get_current_ephemeral_key();
+        let id = format!("{}-{}", caller, (now * 1000.0) as u64);
+        log_info!(Game, "🗳️ Calling a {} vote", kind.id());
+
+        self.send_game_event(serde_json::json!({
+            "type": "voteCall",
+            "id": id,
+            "kind": kind.id(),
+            "target": kind.target(),
+            "team": self.current_player_team,
+        }));
+        let vote = Vote::new(id, kind, caller, self.current_player_team, now);
+        self.votes.open(vote, now);
+    }
+
+    /// F1 / F2 vote yes / no on the open vote (spectators don't vote)
+    pub(super) fn update_vote_input(&mut self, rl: &RaylibHandle) {
+        let yes = rl.is_key_pressed(KeyboardKey::KEY_F1);
+        if (!yes && !rl.is_key_pressed(KeyboardKey::KEY_F2)) || self.is_spectating() {
+            return;
+        }
+        let Some(vote) = self.votes.active() else {
+            return;
+        };
+
+        let voter = self.get_current_ephemeral_key();
+        if !vote.is_eligible(&voter, self.current_player_team) {
+            return;
+        }
+        let id = vote.id.clone();
+        if self.votes.cast(&id, &voter, yes) {
+            self.send_game_event(serde_json::json!({ "type": "voteCast", "id": id, "yes": yes }));
+        }
+    }
+
+    /// Close the open vote when it's decided and enforce the result
+    pub(super) fn update_votes(&mut self) {
+        let Some(vote) = self.votes.active() else {
+            return;
+        };
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        let electorate = self.vote_electorate(vote, now);
+
+        match self.votes.resolve(&electorate, now) {
+            Some(VoteOutcome::Passed(vote)) => self.enforce_vote(vote),
+            Some(VoteOutcome::Failed(vote)) => log_info!(Game, "🗳️ {} vote failed", vote.kind.id()),
+            None => {}
+        }
+    }
+
+    /// Connected players who get a say in `vote` (spectators never do): remote players
+    /// count while their account updates keep arriving, so a dropped client doesn't block the vote
+    fn vote_electorate(&self, vote: &Vote, now: f64) -> HashSet<String> {
+        let local_key = self.get_current_ephemeral_key();
+        let local = (!self.is_spectating()).then_some((local_key.as_str(), self.current_player_team, now));
+        let others = self.other_players.iter()
+            .filter(|p| !is_bot_authority(&p.authority))
+            .map(|p| (p.authority.as_str(), p.team.parse().unwrap_or(0), p.last_update_time));
+        vote.electorate(others.chain(local), now)
+    }
+
+    /// Carry out a passed vote: timeouts pause the round state machine, surrender and kick go through the web UI
+    fn enforce_vote(&mut self, vote: Vote) {
+        log_info!(Game, "🗳️ {} vote passed", vote.kind.id());
+        let js_code = match &vote.kind {
+            VoteKind::Timeout => {
+                if !self.round.start_timeout(TIMEOUT_DURATION) {
+                    return;
+                }
+                format!(
+                    "window.dispatchEvent(new CustomEvent('matchTimeout', {{ detail: {{ seconds: {} }} }}));",
+                    TIMEOUT_DURATION
+                )
+            }
+            VoteKind::Surrender => {
+                // MatchStatus ends the match, which plays the round-end presentation
+                let winning_team = if vote.team == 1 { "B" } else { "A" };
+                format!(
+                    "window.dispatchEvent(new CustomEvent('matchSurrendered', {{ detail: {{ winningTeam: '{}' }} }}));",
+                    winning_team
+                )
+            }
+            VoteKind::Kick { target } => {
+                if *target != self.get_current_ephemeral_key() {
+                    return;
+                }
+                "window.dispatchEvent(new CustomEvent('kickedFromMatch'));".to_string()
+            }
+        };
+
+        unsafe {
+            let c_str = std::ffi::CString::new(js_code).unwrap();
+            emscripten_run_script(c_str.as_ptr());
+        }
+    }
+
+    /// Open vote panel: what's being decided, the tally and how to vote
+    pub(super) fn draw_vote(&self, d: &mut RaylibDrawHandle) {
+        let Some(vote) = self.votes.active() else {
+            return;
+        };
+        let local = self.get_current_ephemeral_key();
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+
+        let question = match &vote.kind {
+            VoteKind::Timeout => t("vote.timeout").to_string(),
+            VoteKind::Surrender => t("vote.surrender").to_string(),
+            VoteKind::Kick { target } => tr!("vote.kick", name = self.display_name(target, &local)),
+        };
+        let (yes, no) = vote.tally(&self.vote_electorate(vote, now));
+        let footer = if vote.has_voted(&local) {
+            t("vote.cast")
+        } else if vote.is_eligible(&local, self.current_player_team) && !self.is_spectating() {
+            t("vote.keys")
+        } else {
+            t("vote.ineligible")
+        };
+
+        let (x, y, width, height) = (20, 120, 260, 96);
+        d.draw_rectangle(x, y, width, height, Color::new(13, 13, 17, 200));
+        d.draw_rectangle_lines(x, y, width, height, Color::new(156, 81, 255, 255));
+        draw_hud_text(
+            d,
+            &tr!("vote.header", name = self.display_name(&vote.caller, &local), seconds = format!("{:.0}", vote.time_left(now).ceil())),
+            x + 10, y + 8, 14, Color::new(200, 200, 220, 255),
+        );
+        draw_hud_text(d, &question, x + 10, y + 28, 20, Color::WHITE);
+        draw_hud_text(d, &tr!("vote.tally", yes = yes, no = no), x + 10, y + 52, 16, Color::new(0, 242, 148, 255));
+        draw_hud_text(d, footer, x + 10, y + 74, 14, Color::new(200, 200, 220, 255));
+    }
+
+    /// Big TIMEOUT clock while a voted timeout is running
+    pub(super) fn draw_timeout(&self, d: &mut RaylibDrawHandle) {
+        let Some(remaining) = self.round.timeout_remaining() else {
+            return;
+        };
+
+        let screen_width = d.get_screen_width();
+        let screen_height = d.get_screen_height();
+
+        let label = t("vote.timeout_banner");
+        let label_width = measure_hud_text(d, label, 48);
+        draw_hud_text(d, label, (screen_width - label_width) / 2, screen_height / 3 - 20, 48, Color::new(255, 170, 0, 255));
+
+        let seconds = remaining.ceil() as u32;
+        let text = format!("{}:{:02}", seconds / 60, seconds % 60);
+        let text_width = measure_hud_text(d, &text, 32);
+        draw_hud_text(d, &text, (screen_width - text_width) / 2, screen_height / 3 + 40, 32, Color::new(200, 200, 220, 255));
+    }
+}
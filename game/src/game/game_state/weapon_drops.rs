@@ -0,0 +1,170 @@
+//! Dropped weapons: dropping (Q / on death), walk-over pickup and drawing them
+
+use raylib::prelude::*;
+use crate::game::{WeaponKind, WeaponPickup};
+use crate::audio::{mixer, AudioChannel};
+use super::{GameState, emscripten_get_now};
+use super::net_sync::event_vector;
+
+/// Played when walking over a weapon
+const PICKUP_SOUND: &str = "/assets/gun/audio/weapon-pickup.wav";
+
+/// Manual drops land this far in front of the player (outside pickup reach)
+const DROP_DISTANCE: f32 = 1.6;
+
+/// Dropped weapons start blinking this long before despawning (seconds)
+const DESPAWN_WARNING: f64 = 5.0;
+
+impl GameState {
+    /// Drop the equipped weapon with Q (the last carried weapon can't be dropped)
+    pub(super) fn update_weapon_drop(&mut self, rl: &RaylibHandle) {
+        if !rl.is_key_pressed(KeyboardKey::KEY_Q) || self.carried_weapons.len() <= 1 || self.reload_initiated {
+            return;
+        }
+        let Some(player) = self.player.as_ref().filter(|p| !p.is_dead) else {
+            return;
+        };
+
+        let yaw = player.yaw.to_radians();
+        let position = player.position + Vector3::new(yaw.cos(), 0.0, yaw.sin()) * DROP_DISTANCE;
+        let weapon = self.current_weapon;
+
+        self.carried_weapons.retain(|w| *w != weapon);
+        self.switch_weapon(self.carried_weapons[0]);
+        self.drop_weapon(weapon, position);
+    }
+
+    /// Leave the equipped weapon where the player died
+    pub(super) fn drop_weapon_on_death(&mut self) {
+        if let Some(position) = self.player.as_ref().map(|p| p.position) {
+            self.drop_weapon(self.current_weapon, position);
+        }
+    }
+
+    /// Spawn a dropped weapon locally and announce it to the other clients
+    fn drop_weapon(&mut self, weapon: WeaponKind, position: Vector3) {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        let pickup = WeaponPickup {
+            id: format!("{}-{}", self.get_current_ephemeral_key(), (now * 1000.0) as u64),
+            weapon,
+            position: Vector3::new(position.x, 0.0, position.z),
+            dropped_at: now,
+        };
+        log_info!(Game, "🔫 Dropped {:?} at ({:.1}, {:.1})", weapon, pickup.position.x, pickup.position.z);
+        self.spawn_pickup(pickup, true);
+    }
+
+    /// Put a weapon in the world, announcing it unless every client spawns it on its own (map script timers)
+    pub(super) fn spawn_pickup(&mut self, pickup: WeaponPickup, announce: bool) {
+        if announce {
+            self.send_game_event(serde_json::json!({
+                "type": "weaponDrop",
+                "id": pickup.id,
+                "weapon": pickup.weapon.id(),
+                "position": [pickup.position.x, pickup.position.y, pickup.position.z],
+                "droppedAt": pickup.dropped_at,
+            }));
+        }
+        self.pickups.add(pickup);
+    }
+
+    /// Pick up weapons we aren't carrying yet by walking over them
+    ///
+    /// First come, first served: the pickup is claimed locally and the other
+    /// clients remove it when the event arrives.
+    pub(super) fn update_weapon_pickup(&mut self) {
+        let Some(player) = self.player.as_ref().filter(|p| !p.is_dead) else {
+            return;
+        };
+        let carried = &self.carried_weapons;
+        let Some(id) = self.pickups.in_reach(player.position, |w| !carried.contains(&w)).map(|p| p.id.clone()) else {
+            return;
+        };
+        let Some(pickup) = self.pickups.remove(&id) else {
+            return;
+        };
+
+        log_info!(Game, "🔫 Picked up {:?}", pickup.weapon);
+        self.send_game_event(serde_json::json!({ "type": "weaponPickup", "id": id }));
+        self.grant_weapon(pickup.weapon);
+    }
+
+    /// Put a weapon in hand (picked up, or handed out by a map trigger); one already carried is just drawn
+    pub(super) fn grant_weapon(&mut self, weapon: WeaponKind) {
+        mixer::play_sound(PICKUP_SOUND, AudioChannel::Effects, 0.8);
+        if !self.carried_weapons.contains(&weapon) {
+            self.carried_weapons.push(weapon);
+        }
+        self.switch_weapon(weapon);
+    }
+
+    /// Apply a drop / pickup announced by another player
+    pub(super) fn apply_weapon_drop_event(&mut self, event: &serde_json::Value) {
+        let id = event.get("id").and_then(|v| v.as_str());
+        match (event.get("type").and_then(|t| t.as_str()), id) {
+            (Some("weaponPickup"), Some(id)) => {
+                self.pickups.remove(id);
+            }
+            (Some("weaponDrop"), Some(id)) => {
+                let weapon = event.get("weapon").and_then(|v| v.as_str()).and_then(WeaponKind::from_id);
+                let dropped_at = event.get("droppedAt").and_then(|v| v.as_f64());
+                let (Some(weapon), Some(position), Some(dropped_at)) = (weapon, event_vector(event, "position"), dropped_at) else {
+                    log_warn!(Game, "⚠️ Ignoring malformed weapon drop: {}", event);
+                    return;
+                };
+                self.pickups.add(WeaponPickup { id: id.to_string(), weapon, position, dropped_at });
+            }
+            _ => log_warn!(Game, "⚠️ Ignoring malformed weapon event: {}", event),
+        }
+    }
+
+    /// Despawn weapons that have been lying around too long
+    pub(super) fn update_pickups(&mut self) {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+        self.pickups.expire(now);
+    }
+
+    /// Fresh copy of the selected loadout for a new life, spawning with its primary in hand
+    pub(super) fn reset_loadout(&mut self) {
+        self.carried_weapons = self.loadout.weapons.clone();
+        self.current_weapon = self.loadout.primary;
+        self.scope.reset();
+    }
+
+    /// Draw dropped weapons floating and spinning above the ground
+    pub(super) fn draw_pickups(&self, d3d: &mut RaylibMode3D<RaylibDrawHandle>) {
+        let now = unsafe { emscripten_get_now() / 1000.0 };
+
+        for pickup in self.pickups.pickups() {
+            // Blink for the last few seconds before despawning
+            let time_left = pickup.time_left(now);
+            if time_left < DESPAWN_WARNING && (time_left * 4.0) as u32 % 2 == 0 {
+                continue;
+            }
+
+            let bob = ((now * 2.0).sin() * 0.1) as f32;
+            let center = pickup.position + Vector3::new(0.0, 0.5 + bob, 0.0);
+            let angle = (now * 1.5) as f32;
+            let along = Vector3::new(angle.cos(), 0.0, angle.sin());
+
+            let (length, color) = match pickup.weapon {
+                WeaponKind::Rifle => (0.8, Color::new(60, 60, 70, 255)),
+                WeaponKind::Sniper => (1.2, Color::new(70, 80, 60, 255)),
+                WeaponKind::Shotgun => (0.9, Color::new(110, 70, 40, 255)),
+            };
+
+            // Barrel and stock
+            d3d.draw_cylinder_ex(center - along * (length * 0.1), center + along * (length * 0.5), 0.035, 0.035, 8, color);
+            d3d.draw_cylinder_ex(center - along * (length * 0.5), center - along * (length * 0.1), 0.07, 0.05, 8, color);
+
+            // Glow ring on the ground so drops are easy to spot
+            d3d.draw_circle_3D(
+                Vector3::new(pickup.position.x, 0.02, pickup.position.z),
+                0.6,
+                Vector3::new(1.0, 0.0, 0.0),
+                90.0,
+                Color::new(20, 241, 149, 160),
+            );
+        }
+    }
+}
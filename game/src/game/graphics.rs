@@ -0,0 +1,134 @@
+use raylib::prelude::*;
+use std::cell::Cell;
+
+/// Allowed draw distance (world units); the top end reaches across a whole 50x50 map
+pub const DRAW_DISTANCE_RANGE: (f32, f32) = (20.0, 100.0);
+
+/// Share of the draw distance that stays untouched before distance fog starts
+const FOG_START: f32 = 0.5;
+
+/// How steeply distance fog thickens toward the cutoff (higher = clearer for longer)
+const FOG_FALLOFF: f32 = 3.0;
+
+/// How the world is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderMode {
+    /// Full 3D scene (models, lighting, viewmodel)
+    #[default]
+    Full,
+    /// Low-spec fallback: Wolfenstein-style raycast columns over the map grid, players as billboards
+    Retro,
+}
+
+impl RenderMode {
+    /// Id used by the JS bridge (0 = full, 1 = retro)
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(RenderMode::Full),
+            1 => Some(RenderMode::Retro),
+            _ => None,
+        }
+    }
+}
+
+// Active render mode (Emscripten is single-threaded)
+thread_local! {
+    static RENDER_MODE: Cell<RenderMode> = Cell::new(RenderMode::Full);
+}
+
+pub fn set_render_mode(mode: RenderMode) {
+    RENDER_MODE.with(|current| current.set(mode));
+}
+
+pub fn render_mode() -> RenderMode {
+    RENDER_MODE.with(|current| current.get())
+}
+
+/// How far the world is drawn, and whether it fades into the sky before the cutoff instead of popping
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewDistance {
+    /// Map objects further away than this aren't drawn
+    pub draw_distance: f32,
+    /// Fade distant objects toward the sky color as they near the cutoff
+    pub fog: bool,
+}
+
+impl Default for ViewDistance {
+    fn default() -> Self {
+        Self { draw_distance: DRAW_DISTANCE_RANGE.1, fog: true }
+    }
+}
+
+impl ViewDistance {
+    /// Settings with the draw distance clamped to its allowed range
+    pub fn clamped(self) -> Self {
+        Self { draw_distance: self.draw_distance.clamp(DRAW_DISTANCE_RANGE.0, DRAW_DISTANCE_RANGE.1), fog: self.fog }
+    }
+
+    pub fn in_range(&self, distance: f32) -> bool {
+        distance <= self.draw_distance
+    }
+
+    /// How much of something `distance` away has faded into the sky (0 = untouched, 1 = gone at the cutoff)
+    /// Exponential from `FOG_START` onwards, so most of the view stays clear and the last stretch fades quickly
+    pub fn fog_amount(&self, distance: f32) -> f32 {
+        if !self.fog {
+            return 0.0;
+        }
+        let start = self.draw_distance * FOG_START;
+        let t = ((distance - start) / (self.draw_distance - start)).clamp(0.0, 1.0);
+        ((FOG_FALLOFF * t).exp() - 1.0) / (FOG_FALLOFF.exp() - 1.0)
+    }
+
+    /// `color` as seen from `distance` away, blended toward `sky`
+    pub fn apply(&self, color: Color, sky: Color, distance: f32) -> Color {
+        let amount = self.fog_amount(distance);
+        let blend = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * amount).round() as u8;
+        Color::new(blend(color.r, sky.r), blend(color.g, sky.g), blend(color.b, sky.b), color.a)
+    }
+}
+
+// Active view distance (Emscripten is single-threaded)
+thread_local! {
+    static VIEW_DISTANCE: Cell<ViewDistance> = Cell::new(ViewDistance::default());
+}
+
+pub fn set_view_distance(settings: ViewDistance) {
+    VIEW_DISTANCE.with(|current| current.set(settings.clamped()));
+}
+
+pub fn view_distance() -> ViewDistance {
+    VIEW_DISTANCE.with(|current| current.get())
+}
+
+// Film grain and chromatic aberration (off by default: the scene then skips the render texture)
+thread_local! {
+    static FILM_EFFECTS: Cell<bool> = Cell::new(false);
+}
+
+pub fn set_film_effects(enabled: bool) {
+    FILM_EFFECTS.with(|current| current.set(enabled));
+}
+
+pub fn film_effects() -> bool {
+    FILM_EFFECTS.with(|current| current.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fog_stays_clear_up_close_and_reaches_the_sky_at_the_cutoff() {
+        let view = ViewDistance { draw_distance: 40.0, fog: true };
+        assert_eq!(view.fog_amount(15.0), 0.0);
+        assert!(view.fog_amount(25.0) < view.fog_amount(35.0));
+        assert!((view.fog_amount(40.0) - 1.0).abs() < 1e-5);
+        assert!(view.in_range(40.0) && !view.in_range(40.5));
+
+        let (red, sky) = (Color::new(200, 40, 40, 255), Color::new(13, 13, 17, 255));
+        assert_eq!(view.apply(red, sky, 40.0), sky);
+        assert_eq!(ViewDistance { fog: false, ..view }.apply(red, sky, 40.0), red);
+        assert_eq!(ViewDistance { draw_distance: 500.0, fog: true }.clamped().draw_distance, DRAW_DISTANCE_RANGE.1);
+    }
+}
@@ -0,0 +1,158 @@
+use std::cell::Cell;
+
+/// Smallest and largest size a HUD element can be scaled to
+pub const HUD_SCALE_RANGE: (f32, f32) = (0.5, 2.0);
+
+/// HUD elements the player can move, resize and hide
+///
+/// The health bar is drawn by the game; the rest are web overlays that read the same layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HudElement {
+    Health,
+    Ammo,
+    KillFeed,
+    Minimap,
+    Timer,
+}
+
+impl HudElement {
+    pub const ALL: [HudElement; 5] = [HudElement::Health, HudElement::Ammo, HudElement::KillFeed, HudElement::Minimap, HudElement::Timer];
+
+    /// Key in the saved layout (same as HUD_ELEMENTS in app/src/hud-layout.js)
+    pub fn key(self) -> &'static str {
+        match self {
+            HudElement::Health => "health",
+            HudElement::Ammo => "ammo",
+            HudElement::KillFeed => "killFeed",
+            HudElement::Minimap => "minimap",
+            HudElement::Timer => "timer",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Where the element sits out of the box
+    fn default_placement(self) -> HudPlacement {
+        let (x, y) = match self {
+            HudElement::Health => (0.5, 0.94),
+            HudElement::Ammo => (0.92, 0.9),
+            HudElement::KillFeed => (0.13, 0.3),
+            HudElement::Minimap => (0.9, 0.33),
+            HudElement::Timer => (0.5, 0.08),
+        };
+        HudPlacement { x, y, scale: 1.0, visible: true }
+    }
+}
+
+/// Where and how big one HUD element is drawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HudPlacement {
+    /// Center of the element as a fraction of the screen width / height
+    pub x: f32,
+    pub y: f32,
+    /// Size relative to the element's default size
+    pub scale: f32,
+    pub visible: bool,
+}
+
+/// Player-arranged HUD (saved with the other settings on the web side)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HudLayout {
+    placements: [HudPlacement; 5],
+}
+
+impl Default for HudLayout {
+    fn default() -> Self {
+        Self { placements: HudElement::ALL.map(HudElement::default_placement) }
+    }
+}
+
+impl HudLayout {
+    /// Layout from the saved JSON (`{"health": {"x", "y", "scale", "visible"}, ...}`);
+    /// missing elements and fields keep their defaults and values are clamped
+    pub fn from_json(text: &str) -> Result<Self, serde_json::Error> {
+        let value: serde_json::Value = serde_json::from_str(text)?;
+        let mut layout = Self::default();
+        for element in HudElement::ALL {
+            let Some(saved) = value.get(element.key()) else {
+                continue;
+            };
+            let placement = &mut layout.placements[element.index()];
+            let number = |key: &str| saved.get(key).and_then(|v| v.as_f64()).map(|v| v as f32);
+            if let Some(x) = number("x") {
+                placement.x = x.clamp(0.0, 1.0);
+            }
+            if let Some(y) = number("y") {
+                placement.y = y.clamp(0.0, 1.0);
+            }
+            if let Some(scale) = number("scale") {
+                placement.scale = scale.clamp(HUD_SCALE_RANGE.0, HUD_SCALE_RANGE.1);
+            }
+            if let Some(visible) = saved.get("visible").and_then(|v| v.as_bool()) {
+                placement.visible = visible;
+            }
+        }
+        Ok(layout)
+    }
+
+    pub fn placement(&self, element: HudElement) -> HudPlacement {
+        self.placements[element.index()]
+    }
+
+    /// Screen rectangle (x, y, width, height) of an element whose default size is
+    /// `width` x `height`, kept fully on screen
+    pub fn rect(&self, element: HudElement, width: i32, height: i32, screen_width: i32, screen_height: i32) -> (i32, i32, i32, i32) {
+        let placement = self.placement(element);
+        let width = (width as f32 * placement.scale) as i32;
+        let height = (height as f32 * placement.scale) as i32;
+        let x = (placement.x * screen_width as f32) as i32 - width / 2;
+        let y = (placement.y * screen_height as f32) as i32 - height / 2;
+        (
+            x.clamp(0, (screen_width - width).max(0)),
+            y.clamp(0, (screen_height - height).max(0)),
+            width,
+            height,
+        )
+    }
+}
+
+// Active HUD layout (Emscripten is single-threaded)
+thread_local! {
+    static HUD_LAYOUT: Cell<HudLayout> = Cell::new(HudLayout::default());
+}
+
+pub fn set_hud_layout(layout: HudLayout) {
+    HUD_LAYOUT.with(|current| current.set(layout));
+}
+
+pub fn hud_layout() -> HudLayout {
+    HUD_LAYOUT.with(|current| current.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saved_layout_is_clamped_and_kept_on_screen() {
+        let layout = HudLayout::from_json(r#"{
+            "health": {"x": 1.4, "y": 0.0, "scale": 3.0, "visible": false},
+            "timer": {"x": 0.25},
+            "radar": {"x": 0.5}
+        }"#).unwrap();
+
+        let health = layout.placement(HudElement::Health);
+        assert_eq!((health.x, health.y, health.scale, health.visible), (1.0, 0.0, HUD_SCALE_RANGE.1, false));
+        let timer = layout.placement(HudElement::Timer);
+        assert_eq!((timer.x, timer.y, timer.scale, timer.visible), (0.25, 0.08, 1.0, true));
+        assert_eq!(layout.placement(HudElement::Ammo), HudLayout::default().placement(HudElement::Ammo));
+
+        // Pushed against the top right corner, at twice the size
+        assert_eq!(layout.rect(HudElement::Health, 300, 25, 1280, 720), (680, 0, 600, 50));
+        assert_eq!(HudLayout::default().rect(HudElement::Timer, 100, 20, 1000, 500), (450, 30, 100, 20));
+
+        assert!(HudLayout::from_json("not json").is_err());
+    }
+}
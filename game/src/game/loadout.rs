@@ -0,0 +1,48 @@
+use super::WeaponKind;
+
+/// Weapons a player spawns with and which one is in hand
+///
+/// Presets (names, per-map / per-mode auto-selection) live on the JS side,
+/// which pushes the resolved loadout before every match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loadout {
+    /// Carried weapons, in selection order
+    pub weapons: Vec<WeaponKind>,
+    /// Weapon equipped at spawn (always one of `weapons`)
+    pub primary: WeaponKind,
+}
+
+impl Default for Loadout {
+    fn default() -> Self {
+        Self { weapons: WeaponKind::ALL.to_vec(), primary: WeaponKind::default() }
+    }
+}
+
+impl Loadout {
+    /// Build from comma-separated weapon ids ("rifle,shotgun") and the spawn weapon's id
+    ///
+    /// Unknown ids are skipped; None if no weapon is left. A primary that isn't
+    /// carried falls back to the first carried weapon.
+    pub fn parse(weapons: &str, primary: &str) -> Option<Self> {
+        let ids: Vec<&str> = weapons.split(',').map(str::trim).collect();
+        let weapons: Vec<WeaponKind> = WeaponKind::ALL.into_iter().filter(|w| ids.contains(&w.id())).collect();
+        let first = *weapons.first()?;
+        let primary = WeaponKind::from_id(primary).filter(|w| weapons.contains(w)).unwrap_or(first);
+        Some(Self { weapons, primary })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keeps_selection_order_and_a_carried_primary() {
+        let loadout = Loadout::parse("shotgun, rifle, rocket", "shotgun").unwrap();
+        assert_eq!(loadout.weapons, vec![WeaponKind::Rifle, WeaponKind::Shotgun]);
+        assert_eq!(loadout.primary, WeaponKind::Shotgun);
+
+        assert_eq!(Loadout::parse("sniper", "rifle").unwrap().primary, WeaponKind::Sniper);
+        assert_eq!(Loadout::parse("", "rifle"), None);
+    }
+}
@@ -0,0 +1,217 @@
+use raylib::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use crate::game::KillEvent;
+
+/// Seconds between position samples
+pub const POSITION_SAMPLE_INTERVAL: f64 = 1.0;
+
+/// Format version of the exported log (bump when fields change)
+pub const MATCH_LOG_VERSION: u32 = 1;
+
+/// Columns of the CSV export (one row per event, unused columns left empty)
+const CSV_HEADER: &str = "t,type,player,target,team,x,y,z,yaw,pitch,weapon,headshot,winning_team";
+
+/// One entry of the match event log; `t` is seconds since the log started
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MatchEvent {
+    Kill {
+        t: f64,
+        killer: Option<String>,
+        victim: String,
+        assists: Vec<String>,
+        weapon: String,
+        headshot: bool,
+    },
+    Shot { t: f64, shooter: String, x: f32, y: f32, z: f32, yaw: f32, pitch: f32 },
+    Position { t: f64, player: String, team: String, x: f32, y: f32, z: f32, alive: bool },
+    RoundStart { t: f64 },
+    RoundEnd { t: f64, winning_team: u8 },
+}
+
+/// Player listed in the export header
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoggedPlayer {
+    pub username: String,
+    /// "1" = team A, "2" = team B
+    pub team: String,
+}
+
+/// Structured event log of one match (kills, shots, 1 Hz positions, round events)
+///
+/// Players are identified by authority; the export header maps them to names
+/// and teams. Exported as JSON or CSV for community stat tools.
+#[derive(Debug, Clone, Default)]
+pub struct MatchLog {
+    started_at: Option<f64>,
+    last_sample: Option<f64>,
+    players: BTreeMap<String, LoggedPlayer>,
+    events: Vec<MatchEvent>,
+}
+
+impl MatchLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Seconds since the first event (the first call starts the clock)
+    fn elapsed(&mut self, now: f64) -> f64 {
+        now - *self.started_at.get_or_insert(now)
+    }
+
+    pub fn note_player(&mut self, authority: &str, username: &str, team: &str) {
+        if self.players.get(authority).is_some_and(|p| p.username == username && p.team == team) {
+            return;
+        }
+        self.players.insert(authority.to_string(), LoggedPlayer { username: username.to_string(), team: team.to_string() });
+    }
+
+    /// Username reported for a player (None before their first update)
+    pub fn username(&self, authority: &str) -> Option<&str> {
+        self.players.get(authority).map(|p| p.username.as_str())
+    }
+
+    pub fn record_kill(&mut self, now: f64, kill: &KillEvent) {
+        let t = self.elapsed(now);
+        self.events.push(MatchEvent::Kill {
+            t,
+            killer: kill.killer.clone(),
+            victim: kill.victim.clone(),
+            assists: kill.assists.clone(),
+            weapon: kill.cause_id().to_string(),
+            headshot: kill.headshot,
+        });
+    }
+
+    pub fn record_shot(&mut self, now: f64, shooter: &str, position: Vector3, yaw: f32, pitch: f32) {
+        let t = self.elapsed(now);
+        self.events.push(MatchEvent::Shot { t, shooter: shooter.to_string(), x: position.x, y: position.y, z: position.z, yaw, pitch });
+    }
+
+    pub fn record_round_start(&mut self, now: f64) {
+        let t = self.elapsed(now);
+        self.events.push(MatchEvent::RoundStart { t });
+    }
+
+    pub fn record_round_end(&mut self, now: f64, winning_team: u8) {
+        let t = self.elapsed(now);
+        self.events.push(MatchEvent::RoundEnd { t, winning_team });
+    }
+
+    /// Whether the next 1 Hz position sample is due
+    pub fn position_sample_due(&self, now: f64) -> bool {
+        self.last_sample.map_or(true, |last| now - last >= POSITION_SAMPLE_INTERVAL)
+    }
+
+    /// Record every player's position (authority, team, position, alive)
+    pub fn record_positions<'a>(&mut self, now: f64, players: impl IntoIterator<Item = (&'a str, &'a str, Vector3, bool)>) {
+        let t = self.elapsed(now);
+        self.last_sample = Some(now);
+        for (player, team, position, alive) in players {
+            self.events.push(MatchEvent::Position {
+                t,
+                player: player.to_string(),
+                team: team.to_string(),
+                x: position.x,
+                y: position.y,
+                z: position.z,
+                alive,
+            });
+        }
+    }
+
+    /// Full log as JSON: `{ version, game, players: { authority: { username, team } }, events: [...] }`
+    pub fn to_json(&self, game: &str) -> String {
+        serde_json::json!({
+            "version": MATCH_LOG_VERSION,
+            "game": game,
+            "players": self.players,
+            "events": self.events,
+        })
+        .to_string()
+    }
+
+    /// Events as CSV (players by authority, see the JSON export for names)
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(CSV_HEADER);
+        csv.push('\n');
+        for event in &self.events {
+            let row: [String; 13] = match event {
+                MatchEvent::Kill { t, killer, victim, weapon, headshot, .. } => [
+                    format!("{:.2}", t), "kill".into(), csv_field(killer.as_deref().unwrap_or("")), csv_field(victim),
+                    String::new(), String::new(), String::new(), String::new(), String::new(), String::new(),
+                    weapon.clone(), headshot.to_string(), String::new(),
+                ],
+                MatchEvent::Shot { t, shooter, x, y, z, yaw, pitch } => [
+                    format!("{:.2}", t), "shot".into(), csv_field(shooter), String::new(), String::new(),
+                    format!("{:.2}", x), format!("{:.2}", y), format!("{:.2}", z), format!("{:.3}", yaw), format!("{:.3}", pitch),
+                    String::new(), String::new(), String::new(),
+                ],
+                MatchEvent::Position { t, player, team, x, y, z, alive } => [
+                    format!("{:.2}", t), if *alive { "position".into() } else { "position_dead".into() }, csv_field(player),
+                    String::new(), team.clone(), format!("{:.2}", x), format!("{:.2}", y), format!("{:.2}", z),
+                    String::new(), String::new(), String::new(), String::new(), String::new(),
+                ],
+                MatchEvent::RoundStart { t } => [
+                    format!("{:.2}", t), "round_start".into(), String::new(), String::new(), String::new(), String::new(),
+                    String::new(), String::new(), String::new(), String::new(), String::new(), String::new(), String::new(),
+                ],
+                MatchEvent::RoundEnd { t, winning_team } => [
+                    format!("{:.2}", t), "round_end".into(), String::new(), String::new(), String::new(), String::new(),
+                    String::new(), String::new(), String::new(), String::new(), String::new(), String::new(), winning_team.to_string(),
+                ],
+            };
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positions_are_sampled_at_one_hz() {
+        let mut log = MatchLog::new();
+        assert!(log.position_sample_due(10.0));
+        log.record_positions(10.0, [("A", "1", Vector3::new(1.0, 0.0, 2.0), true)]);
+        assert!(!log.position_sample_due(10.5));
+        assert!(log.position_sample_due(11.0));
+
+        log.record_round_end(12.5, 2);
+        assert_eq!(log.events.last(), Some(&MatchEvent::RoundEnd { t: 2.5, winning_team: 2 }));
+    }
+
+    #[test]
+    fn test_csv_rows_line_up_with_header() {
+        let mut log = MatchLog::new();
+        log.record_shot(0.0, "Shooter,1", Vector3::new(1.0, 2.0, 3.0), 0.5, -0.1);
+        log.record_round_start(1.0);
+
+        let csv = log.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines[1], "0.00,shot,\"Shooter,1\",,,1.00,2.00,3.00,0.500,-0.100,,,");
+        assert_eq!(lines[2].split(',').count(), CSV_HEADER.split(',').count());
+    }
+}
@@ -1,8 +1,70 @@
 mod game_state;
 mod player;
+mod movement;
 mod debug_menu;
+mod netcode;
+mod game_rules;
+mod damage_tracker;
+mod weapon;
+mod round;
+mod visibility;
+mod spectator;
+mod smoke;
+mod flash;
+mod scope;
+mod pickups;
+mod clan_tag;
+mod trigger;
+mod bots;
+mod backfill;
+mod tutorial;
+mod range_stats;
+mod vote;
+mod match_log;
+mod replay;
+mod loadout;
+mod emote;
+mod boundary;
+mod shake;
+mod mover;
+mod script;
+mod threat_compass;
+pub mod palette;
+pub mod comfort;
+pub mod viewmodel;
+pub mod hud_layout;
+pub mod graphics;
+pub mod fonts;
 pub mod touch_controls;
 
 pub use game_state::{GameState, GameMode};
 pub use player::Player;
+pub use movement::{MovementInput, MovementState, MovementClaim, MovementViolation, MovementWorld, MovementClock, MovementTuning, OpenGround, InputChain, InputAccumulator, step, verify_movement, capsule_height, BASE_MOVE_SPEED, PLAYER_RADIUS, PLAYER_HEIGHT, STEP_HEIGHT};
 pub use debug_menu::DebugMenu;
+pub use netcode::NetcodeConfig;
+pub use game_rules::GameRules;
+pub use damage_tracker::{DamageTracker, Hazard, KillEvent};
+pub use weapon::{WeaponKind, aggregate_pellet_damage};
+pub use loadout::Loadout;
+pub use scope::{ScopeState, BASE_FOV};
+pub use pickups::{PickupField, WeaponPickup};
+pub use clan_tag::split_clan_tag;
+pub use trigger::{TriggerVolume, TriggerTracker};
+pub use mover::MoverSet;
+pub use script::{ScriptFire, ScriptRunner};
+pub use bots::{TrainingBot, BotDifficulty, BotRole, BotSenses};
+pub use backfill::{BackfillBot, backfill_roster, is_bot_authority};
+pub use tutorial::{Tutorial, TutorialStep, TutorialInput};
+pub use range_stats::RangeStats;
+pub use round::{RoundState, RoundEvent};
+pub use match_log::{MatchLog, MatchEvent, MATCH_LOG_VERSION, POSITION_SAMPLE_INTERVAL};
+pub use replay::{ReplayRecorder, ReplayPlayer, REPLAY_VERSION};
+pub use vote::{VoteBox, Vote, VoteKind, VoteOutcome, TIMEOUT_DURATION};
+pub use spectator::{SpectatorCamera, SpectatorMode, SpectatorTarget};
+pub use smoke::{SmokeField, SmokeGrenade, ThrowEvent};
+pub use flash::{Blindness, FlashGrenade, flash_exposure};
+pub use emote::{Emote, EmoteBoard, RadialSlot, Spray, SprayField, RADIAL_SLOTS, radial_slot_at, radial_slot_direction};
+pub use visibility::{VisibilityTracker, Observer, EnemyMarker, MarkerKind};
+pub use boundary::{BoundaryStatus, BoundaryTimer, OutOfBoundsRules};
+pub use shake::{ScreenShake, ShakeSource};
+pub use threat_compass::{ThreatCompass, ThreatKind, threat_bearing, THREAT_SECTORS};
@@ -0,0 +1,817 @@
+//! Deterministic player movement shared by client prediction and validation
+//!
+//! This module deliberately has no raylib/JS dependencies so the exact same
+//! step function can be compiled into the rollup validator: given a starting
+//! state and the inputs a client sent, it re-simulates movement and flags
+//! anything a legitimate client could not have produced (speed hacks,
+//! tampered input streams).
+
+use serde::{Deserialize, Serialize};
+
+/// Base walking speed (units per second)
+pub const BASE_MOVE_SPEED: f32 = 5.0;
+
+/// Speed multiplier while running (Shift)
+pub const RUN_MULTIPLIER: f32 = 2.0;
+
+/// Speed multiplier while crouching (Ctrl)
+pub const CROUCH_MULTIPLIER: f32 = 0.5;
+
+/// Half-size of the playable area (50x50 map = -25 to +25)
+pub const MAP_BOUNDARY: f32 = 25.0;
+
+/// Longest tick a client may claim (seconds); anything larger is rejected
+pub const MAX_TICK_DELTA: f32 = 0.25;
+
+/// Fixed physics step (seconds): frames and replays run as many whole steps as fit
+pub const FIXED_DT: f32 = 1.0 / 60.0;
+
+/// Most steps one frame may run (a long hitch is dropped instead of catching up)
+const MAX_STEPS_PER_FRAME: u32 = 15;
+
+/// Downward acceleration while airborne (units/s²)
+pub const GRAVITY: f32 = 20.0;
+
+/// How quickly ground movement reaches the wished speed (wished speed per second)
+pub const GROUND_ACCELERATION: f32 = 10.0;
+
+/// Ground friction (fraction of speed lost per second)
+pub const FRICTION: f32 = 6.0;
+
+/// Friction acts as if moving at least this fast, so slow drift stops quickly
+const STOP_SPEED: f32 = 1.0;
+
+/// Player capsule the step collides with (same proportions the game draws players with)
+pub const PLAYER_RADIUS: f32 = 0.3;
+pub const PLAYER_HEIGHT: f32 = 1.8;
+
+/// Ledges lower than this are stepped onto instead of blocking
+pub const STEP_HEIGHT: f32 = 0.4;
+
+/// Highest obstacle top (above the feet) a jump can mantle onto
+pub const MANTLE_HEIGHT: f32 = 1.3;
+
+/// How far ahead of the capsule an obstacle is looked for when mantling
+const MANTLE_REACH: f32 = 0.3;
+
+/// Seconds a mantle takes to climb to the top
+pub const MANTLE_DURATION: f32 = 0.35;
+
+/// Speed a slide needs to start (a little under full sprint speed)
+const SLIDE_MIN_SPEED: f32 = BASE_MOVE_SPEED * RUN_MULTIPLIER * 0.8;
+
+/// Speed added when a slide starts (units per second)
+const SLIDE_BOOST: f32 = 3.0;
+
+/// Friction while sliding (fraction of speed lost per second), much lower than `FRICTION`
+const SLIDE_FRICTION: f32 = 1.2;
+
+/// Longest a slide lasts (seconds); it also ends early once it slows to a crouch walk
+pub const SLIDE_DURATION: f32 = 0.9;
+
+/// Seconds after a slide ends before the next one can start
+const SLIDE_COOLDOWN: f32 = 0.6;
+
+/// Player capsule height while sliding (hit tests and drawing)
+pub const SLIDE_HEIGHT: f32 = 1.0;
+
+/// One tick of player input, as sent to the game program
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MovementInput {
+    pub forward: bool,
+    pub backward: bool,
+    pub left: bool,
+    pub right: bool,
+    pub running: bool,
+    pub crouching: bool,
+    /// Jump held (hops again on landing while held)
+    pub jump: bool,
+    /// Yaw in radians
+    pub yaw: f32,
+    /// Pitch in radians (not used for movement, but covered by the checksum)
+    pub pitch: f32,
+    /// Tick length in seconds
+    pub delta: f32,
+}
+
+/// Simulated player: feet position, velocity and whether they stand on something
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MovementState {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub vx: f32,
+    pub vy: f32,
+    pub vz: f32,
+    pub grounded: bool,
+    /// Seconds left in which a jump still works after walking off a ledge
+    pub coyote: f32,
+    /// Seconds left of a mantle in progress (0 when not mantling), and the top being climbed to
+    pub mantle: f32,
+    pub mantle_top: f32,
+    /// Seconds left of a slide in progress (0 when not sliding)
+    pub slide: f32,
+    /// Seconds until another slide may start
+    pub slide_cooldown: f32,
+}
+
+impl MovementState {
+    /// Standing still at a position (lands on the next step if that's mid-air)
+    pub fn at(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z, ..Default::default() }
+    }
+
+    pub fn is_sliding(&self) -> bool {
+        self.slide > 0.0
+    }
+}
+
+/// Height of a player capsule, lowered while sliding
+pub fn capsule_height(sliding: bool) -> f32 {
+    if sliding { SLIDE_HEIGHT } else { PLAYER_HEIGHT }
+}
+
+/// Jump, air movement and fall damage tuning (part of the game rules, so every client agrees)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MovementTuning {
+    /// Upward speed a jump starts with (units per second)
+    pub jump_speed: f32,
+    /// Steering while airborne, as a fraction of ground acceleration (0 = none, 1 = full)
+    pub air_control: f32,
+    /// Seconds after walking off a ledge during which a jump still works
+    pub coyote_time: f32,
+    /// Landing speed (units per second) above which a fall hurts
+    pub fall_damage_speed: f32,
+    /// Damage per unit of landing speed above `fall_damage_speed`
+    pub fall_damage_scale: f32,
+    /// Fraction of fall damage still taken when landing crouched or sliding
+    pub fall_damage_cushion: f32,
+}
+
+impl Default for MovementTuning {
+    fn default() -> Self {
+        // Clears a 1-unit crate with room to spare (peak ~1.2 units); drops past
+        // ~3.6 units start to hurt and ~15 units is lethal from full health
+        Self {
+            jump_speed: 7.0,
+            air_control: 0.15,
+            coyote_time: 0.1,
+            fall_damage_speed: 12.0,
+            fall_damage_scale: 8.0,
+            fall_damage_cushion: 0.5,
+        }
+    }
+}
+
+/// Damage for landing at `impact_speed` (units per second, downwards); `cushioned`
+/// when landing crouched or mid-slide
+pub fn fall_damage(impact_speed: f32, cushioned: bool, tuning: &MovementTuning) -> f32 {
+    let damage = (impact_speed - tuning.fall_damage_speed).max(0.0) * tuning.fall_damage_scale;
+    if cushioned { damage * tuning.fall_damage_cushion } else { damage }
+}
+
+/// Static geometry the step collides with
+///
+/// Kept abstract so the validator can plug in its own copy of the map; the game
+/// uses the loaded map's spatial index, the editor walkthrough the map itself.
+pub trait MovementWorld {
+    /// Whether a player with feet at (x, y, z) overlaps geometry (ledges below `STEP_HEIGHT` don't count)
+    fn blocks(&self, x: f32, y: f32, z: f32) -> bool;
+    /// Height of the highest surface under a player at (x, z) that is no higher than `max_y` (0.0 is the ground)
+    fn floor_height(&self, x: f32, z: f32, max_y: f32) -> f32;
+}
+
+/// Flat ground and nothing else
+pub struct OpenGround;
+
+impl MovementWorld for OpenGround {
+    fn blocks(&self, _x: f32, _y: f32, _z: f32) -> bool {
+        false
+    }
+
+    fn floor_height(&self, _x: f32, _z: f32, _max_y: f32) -> f32 {
+        0.0
+    }
+}
+
+/// Turns variable frame times into whole fixed steps
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MovementClock {
+    /// Frame time not yet simulated (less than one step after `steps`)
+    accumulator: f32,
+}
+
+impl MovementClock {
+    /// Whole `FIXED_DT` steps to run for `delta` seconds of frame time; the rest carries over
+    pub fn steps(&mut self, delta: f32) -> u32 {
+        self.accumulator = (self.accumulator + delta).min(MAX_STEPS_PER_FRAME as f32 * FIXED_DT);
+        let steps = (self.accumulator / FIXED_DT) as u32;
+        self.accumulator -= steps as f32 * FIXED_DT;
+        steps
+    }
+
+    /// How far (0..1) frame time has got into the next step, for drawing between steps
+    pub fn alpha(&self) -> f32 {
+        self.accumulator / FIXED_DT
+    }
+}
+
+/// Why a movement claim was rejected
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MovementViolation {
+    /// Tick length is NaN, non-positive or longer than `MAX_TICK_DELTA`
+    InvalidDelta { tick: u32 },
+    /// The input stream doesn't hash to the checksum the client attached
+    ChecksumMismatch { expected: u32, claimed: u32 },
+    /// The claimed end position is further than the tolerance from the replay
+    PositionMismatch { error: f32 },
+}
+
+/// Advance the simulation by one fixed step of input
+///
+/// This is the single source of truth for player movement: `Player::update`
+/// uses it for prediction and `verify_movement` for replays. Everything is plain
+/// f32 arithmetic in a fixed order on a fixed `dt`, so the same inputs give the
+/// same bits on every client and on the validator.
+pub fn step(state: MovementState, input: &MovementInput, dt: f32, tuning: &MovementTuning, world: &dyn MovementWorld) -> MovementState {
+    let (wish_x, wish_z) = wish_direction(input);
+    let wish_speed = if wish_x == 0.0 && wish_z == 0.0 {
+        0.0
+    } else if input.running && !input.crouching {
+        BASE_MOVE_SPEED * RUN_MULTIPLIER
+    } else if input.crouching {
+        BASE_MOVE_SPEED * CROUCH_MULTIPLIER
+    } else {
+        BASE_MOVE_SPEED
+    };
+    let mut next = state;
+
+    // A mantle runs to completion, ignoring input
+    if next.mantle > 0.0 {
+        return continue_mantle(next, dt, tuning);
+    }
+
+    // Jumping (or already in the air) into a low obstacle climbs up onto it
+    if input.jump || !next.grounded {
+        if let Some(top) = mantle_top(&next, wish_x, wish_z, world) {
+            next.mantle = MANTLE_DURATION;
+            next.mantle_top = top;
+            next.vx = wish_x * wish_speed;
+            next.vz = wish_z * wish_speed;
+            next.vy = 0.0;
+            next.grounded = false;
+            next.coyote = 0.0;
+            next.slide = 0.0;
+            return next;
+        }
+    }
+
+    // Jump from the ground, or just after walking off it
+    if input.jump && (next.grounded || next.coyote > 0.0) {
+        next.vy = tuning.jump_speed;
+        next.grounded = false;
+        next.coyote = 0.0;
+    }
+
+    update_slide(&mut next, input, dt);
+
+    // Friction only grips on the ground (and barely while sliding)
+    if next.grounded {
+        let speed = (next.vx * next.vx + next.vz * next.vz).sqrt();
+        if speed > 0.0 {
+            let drop = if next.is_sliding() { speed * SLIDE_FRICTION * dt } else { speed.max(STOP_SPEED) * FRICTION * dt };
+            let scale = (speed - drop).max(0.0) / speed;
+            next.vx *= scale;
+            next.vz *= scale;
+        }
+    }
+
+    // Accelerate towards the wished velocity, never past the wished speed along it;
+    // a slide can't be steered
+    let acceleration = if next.grounded { GROUND_ACCELERATION } else { GROUND_ACCELERATION * tuning.air_control };
+    let missing = wish_speed - (next.vx * wish_x + next.vz * wish_z);
+    if missing > 0.0 && !next.is_sliding() {
+        let gain = (acceleration * wish_speed * dt).min(missing);
+        next.vx += gain * wish_x;
+        next.vz += gain * wish_z;
+    }
+
+    if !next.grounded {
+        next.vy -= GRAVITY * dt;
+    }
+
+    // One horizontal axis at a time, so walls are slid along instead of sticking;
+    // starting inside geometry lets the player walk out instead of getting stuck
+    let stuck = world.blocks(next.x, next.y, next.z);
+    let mut x = next.x + next.vx * dt;
+    if let Some(edge) = world_edge_stop(next.x, x) {
+        x = edge;
+        next.vx = 0.0;
+    }
+    if !stuck && world.blocks(x, next.y, next.z) {
+        next.vx = 0.0;
+    } else {
+        next.x = x;
+    }
+    let mut z = next.z + next.vz * dt;
+    if let Some(edge) = world_edge_stop(next.z, z) {
+        z = edge;
+        next.vz = 0.0;
+    }
+    if !stuck && world.blocks(next.x, next.y, z) {
+        next.vz = 0.0;
+    } else {
+        next.z = z;
+    }
+
+    if next.grounded {
+        // Follow the floor up and down ledges; walking off anything taller starts a fall
+        let floor = world.floor_height(next.x, next.z, next.y + STEP_HEIGHT);
+        if next.y - floor <= STEP_HEIGHT {
+            next.y = floor;
+        } else {
+            next.grounded = false;
+        }
+    } else {
+        // Ledges within a step of the feet catch the player too (hopping onto crates)
+        let floor = world.floor_height(next.x, next.z, next.y + STEP_HEIGHT);
+        let y = next.y + next.vy * dt;
+        if y <= floor {
+            next.y = floor;
+            next.vy = 0.0;
+            next.grounded = true;
+        } else if next.vy > 0.0 && world.blocks(next.x, y, next.z) {
+            // Head hit a ceiling
+            next.vy = 0.0;
+        } else {
+            next.y = y;
+        }
+    }
+
+    next.coyote = if next.grounded { tuning.coyote_time } else { (next.coyote - dt).max(0.0) };
+    next
+}
+
+/// Start, run down or end a crouch-slide
+///
+/// Crouching while grounded at sprint speed starts one with a burst of speed;
+/// it ends when crouch is let go, the player leaves the ground (a slide-jump
+/// keeps the speed), it slows to a crouch walk or times out. Then a cooldown
+/// stops it being chained.
+fn update_slide(state: &mut MovementState, input: &MovementInput, dt: f32) {
+    let speed = (state.vx * state.vx + state.vz * state.vz).sqrt();
+    state.slide_cooldown = (state.slide_cooldown - dt).max(0.0);
+
+    if state.is_sliding() {
+        state.slide = (state.slide - dt).max(0.0);
+        let slowed = speed < BASE_MOVE_SPEED * CROUCH_MULTIPLIER;
+        if !input.crouching || !state.grounded || slowed || state.slide == 0.0 {
+            state.slide = 0.0;
+            state.slide_cooldown = SLIDE_COOLDOWN;
+        }
+    } else if input.crouching && input.running && state.grounded && state.slide_cooldown == 0.0 && speed >= SLIDE_MIN_SPEED {
+        state.slide = SLIDE_DURATION;
+        let scale = (speed + SLIDE_BOOST) / speed;
+        state.vx *= scale;
+        state.vz *= scale;
+    }
+}
+
+/// Top of the obstacle the player is moving into, if it can be mantled: higher than
+/// a step, within `MANTLE_HEIGHT` of the feet and with room to stand on it
+fn mantle_top(state: &MovementState, wish_x: f32, wish_z: f32, world: &dyn MovementWorld) -> Option<f32> {
+    if wish_x == 0.0 && wish_z == 0.0 {
+        return None;
+    }
+    let (x, z) = (state.x + wish_x * MANTLE_REACH, state.z + wish_z * MANTLE_REACH);
+    if !world.blocks(x, state.y, z) {
+        return None;
+    }
+    let top = world.floor_height(x, z, state.y + MANTLE_HEIGHT);
+    (top > state.y + STEP_HEIGHT && !world.blocks(x, top, z)).then_some(top)
+}
+
+/// Rise towards the mantle top, then carry on over the edge with the kept horizontal speed
+fn continue_mantle(mut state: MovementState, dt: f32, tuning: &MovementTuning) -> MovementState {
+    let remaining = state.mantle;
+    state.mantle = (remaining - dt).max(0.0);
+    if state.mantle > 0.0 {
+        state.y += (state.mantle_top - state.y) * (dt / remaining);
+        return state;
+    }
+    state.y = state.mantle_top;
+    state.grounded = true;
+    state.coyote = tuning.coyote_time;
+    let (vx, vz) = (state.vx, state.vz);
+    let (x, z) = (state.x + vx * dt, state.z + vz * dt);
+    state.x = world_edge_stop(state.x, x).unwrap_or(x);
+    state.z = world_edge_stop(state.z, z).unwrap_or(z);
+    state
+}
+
+/// Where a move along one axis from `from` to `to` stops if it would push past the
+/// world edge (the edge is a wall the capsule touches); players already outside
+/// it, say after a server correction, can only head back in
+fn world_edge_stop(from: f32, to: f32) -> Option<f32> {
+    let edge = MAP_BOUNDARY - PLAYER_RADIUS;
+    if to.abs() <= edge || to.abs() <= from.abs() {
+        return None;
+    }
+    Some(if from.abs() <= edge { edge.copysign(to) } else { from })
+}
+
+/// Run the fixed steps `clock` grants for one input
+pub fn advance(state: MovementState, input: &MovementInput, clock: &mut MovementClock, tuning: &MovementTuning, world: &dyn MovementWorld) -> MovementState {
+    (0..clock.steps(input.delta)).fold(state, |state, _| step(state, input, FIXED_DT, tuning, world))
+}
+
+/// Normalized XZ direction the keys ask for (diagonals aren't faster)
+fn wish_direction(input: &MovementInput) -> (f32, f32) {
+    let forward = (input.yaw.cos(), input.yaw.sin());
+    // Right vector is 90 degrees from forward in the XZ plane
+    let right = (-input.yaw.sin(), input.yaw.cos());
+
+    let mut movement = (0.0_f32, 0.0_f32);
+    if input.forward {
+        movement = (movement.0 + forward.0, movement.1 + forward.1);
+    }
+    if input.backward {
+        movement = (movement.0 - forward.0, movement.1 - forward.1);
+    }
+    if input.left {
+        movement = (movement.0 - right.0, movement.1 - right.1);
+    }
+    if input.right {
+        movement = (movement.0 + right.0, movement.1 + right.1);
+    }
+
+    let length = (movement.0 * movement.0 + movement.1 * movement.1).sqrt();
+    if length > 0.0 {
+        (movement.0 / length, movement.1 / length)
+    } else {
+        (0.0, 0.0)
+    }
+}
+
+/// Chain a tick's input onto the running checksum (FNV-1a over the raw bits)
+pub fn input_checksum(previous: u32, tick: u32, input: &MovementInput) -> u32 {
+    let flags = input.forward as u32
+        | (input.backward as u32) << 1
+        | (input.left as u32) << 2
+        | (input.right as u32) << 3
+        | (input.running as u32) << 4
+        | (input.crouching as u32) << 5
+        | (input.jump as u32) << 6;
+
+    let mut hash: u32 = 0x811c_9dc5;
+    for word in [previous, tick, flags, input.yaw.to_bits(), input.pitch.to_bits(), input.delta.to_bits()] {
+        for byte in word.to_le_bytes() {
+            hash ^= byte as u32;
+            hash = hash.wrapping_mul(0x0100_0193);
+        }
+    }
+    hash
+}
+
+/// Client-side tick counter and running input checksum attached to every input
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InputChain {
+    pub tick: u32,
+    pub checksum: u32,
+}
+
+impl InputChain {
+    /// Advance to the next tick and fold the input into the checksum
+    pub fn record(&mut self, input: &MovementInput) {
+        self.tick = self.tick.wrapping_add(1);
+        self.checksum = input_checksum(self.checksum, self.tick, input);
+    }
+}
+
+/// Frame time the player has simulated since the last input went out
+///
+/// The player steps every frame but inputs only go out every input interval, so each
+/// sent input has to cover all the frames since the previous one for a replay to run
+/// the same fixed steps the player did.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InputAccumulator {
+    simulated: f32,
+}
+
+impl InputAccumulator {
+    /// Count one simulated frame (capped like `MovementClock`, which drops the rest of a long hitch)
+    pub fn add_frame(&mut self, delta: f32) {
+        self.simulated += delta.clamp(0.0, MAX_TICK_DELTA);
+    }
+
+    /// Tick lengths for the time simulated since the last call, each at most
+    /// `MAX_TICK_DELTA`; empty if the player hasn't moved on (freeze time)
+    pub fn take_ticks(&mut self) -> Vec<f32> {
+        let mut ticks = Vec::new();
+        while self.simulated > 1e-6 {
+            let tick = self.simulated.min(MAX_TICK_DELTA);
+            ticks.push(tick);
+            self.simulated -= tick;
+        }
+        self.simulated = 0.0;
+        ticks
+    }
+}
+
+/// A span of movement a client claims to have made
+#[derive(Debug, Clone)]
+pub struct MovementClaim<'a> {
+    /// Position (and chain head) the span starts from
+    pub start: MovementState,
+    pub start_chain: InputChain,
+    /// Inputs for ticks `start_chain.tick + 1 ..`
+    pub inputs: &'a [MovementInput],
+    /// Position and checksum the client reports at the end of the span
+    pub end: MovementState,
+    pub end_checksum: u32,
+}
+
+/// Re-simulate a claimed span of inputs against `world` and check it against what the client reported
+///
+/// The replay's step clock starts empty, so `tolerance` should cover one step
+/// of movement. Returns the replayed end state on success.
+pub fn verify_movement(claim: &MovementClaim, tolerance: f32, tuning: &MovementTuning, world: &dyn MovementWorld) -> Result<MovementState, MovementViolation> {
+    let mut chain = claim.start_chain;
+    let mut state = claim.start;
+    let mut clock = MovementClock::default();
+
+    for input in claim.inputs {
+        chain.record(input);
+        if !(input.delta > 0.0 && input.delta <= MAX_TICK_DELTA) {
+            return Err(MovementViolation::InvalidDelta { tick: chain.tick });
+        }
+        state = advance(state, input, &mut clock, tuning, world);
+    }
+
+    if chain.checksum != claim.end_checksum {
+        return Err(MovementViolation::ChecksumMismatch { expected: chain.checksum, claimed: claim.end_checksum });
+    }
+
+    let (dx, dz) = (claim.end.x - state.x, claim.end.z - state.z);
+    let error = (dx * dx + dz * dz).sqrt();
+    if error > tolerance {
+        return Err(MovementViolation::PositionMismatch { error });
+    }
+
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_forward(ticks: usize) -> (Vec<MovementInput>, InputChain, MovementState) {
+        let input = MovementInput { forward: true, running: true, delta: 0.05, ..Default::default() };
+        let inputs = vec![input; ticks];
+        let mut chain = InputChain::default();
+        let mut clock = MovementClock::default();
+        let mut state = MovementState::default();
+        for input in &inputs {
+            chain.record(input);
+            state = advance(state, input, &mut clock, &MovementTuning::default(), &OpenGround);
+        }
+        (inputs, chain, state)
+    }
+
+    #[test]
+    fn test_honest_replay_verifies() {
+        let (inputs, chain, end) = run_forward(20);
+        // 1s at up to 10 u/s, minus the first few steps spent speeding up
+        assert!(end.x > 9.0 && end.x < 10.0, "ran {}", end.x);
+        assert!(end.grounded && end.y == 0.0);
+
+        let claim = MovementClaim {
+            start: MovementState::default(),
+            start_chain: InputChain::default(),
+            inputs: &inputs,
+            end,
+            end_checksum: chain.checksum,
+        };
+        assert_eq!(verify_movement(&claim, 0.01, &MovementTuning::default(), &OpenGround), Ok(end));
+    }
+
+    #[test]
+    fn test_speed_hack_and_tampering_detected() {
+        let (inputs, chain, end) = run_forward(20);
+        let mut claim = MovementClaim {
+            start: MovementState::default(),
+            start_chain: InputChain::default(),
+            inputs: &inputs,
+            end: MovementState { x: end.x * 2.0, ..end },
+            end_checksum: chain.checksum,
+        };
+        assert!(matches!(verify_movement(&claim, 0.5, &MovementTuning::default(), &OpenGround), Err(MovementViolation::PositionMismatch { .. })));
+
+        claim.end = end;
+        claim.end_checksum ^= 1;
+        assert!(matches!(verify_movement(&claim, 0.5, &MovementTuning::default(), &OpenGround), Err(MovementViolation::ChecksumMismatch { .. })));
+
+        let slow_motion = [MovementInput { forward: true, delta: 1.0, ..Default::default() }];
+        claim.inputs = &slow_motion;
+        assert_eq!(verify_movement(&claim, 0.5, &MovementTuning::default(), &OpenGround), Err(MovementViolation::InvalidDelta { tick: 1 }));
+    }
+
+    #[test]
+    fn test_inputs_sent_at_the_input_rate_cover_every_frame() {
+        let tuning = MovementTuning::default();
+        // ~60 fps with jitter and an input sent every 50 ms, as the game loop does
+        let frames = [0.016, 0.017, 0.018, 0.015].repeat(30);
+        let input_interval = 0.05;
+
+        let mut player = MovementState::default();
+        let mut player_clock = MovementClock::default();
+        let mut timer = 0.0;
+        let mut pending = InputAccumulator::default();
+        let (mut chain, mut frame_chain) = (InputChain::default(), InputChain::default());
+        // Per send: start state and chain head, the inputs sent, end state and checksum
+        let mut spans = Vec::new();
+        let mut frame_delta_spans = Vec::new();
+        let (mut span_start, mut span_chain, mut frame_span_chain) = (player, chain, frame_chain);
+        for (i, delta) in frames.iter().copied().enumerate() {
+            // Turn throughout and start strafing partway, so inputs change between sends
+            let keys = MovementInput { forward: true, right: i >= 61, running: true, yaw: 0.3 + i as f32 * 0.02, ..Default::default() };
+            player = advance(player, &MovementInput { delta, ..keys }, &mut player_clock, &tuning, &OpenGround);
+            pending.add_frame(delta);
+
+            timer += delta;
+            if timer >= input_interval {
+                let inputs: Vec<MovementInput> = pending.take_ticks().into_iter().map(|tick| MovementInput { delta: tick, ..keys }).collect();
+                inputs.iter().for_each(|input| chain.record(input));
+                spans.push((span_start, span_chain, inputs, player, chain.checksum));
+
+                let frame_input = MovementInput { delta, ..keys };
+                frame_chain.record(&frame_input);
+                frame_delta_spans.push((span_start, frame_span_chain, vec![frame_input], player, frame_chain.checksum));
+
+                (span_start, span_chain, frame_span_chain) = (player, chain, frame_chain);
+                timer -= input_interval;
+            }
+        }
+        assert!(spans.len() > 30);
+
+        // Each span replays to where the player got, within the one step the replay clock may lag
+        let replays = |spans: &[(MovementState, InputChain, Vec<MovementInput>, MovementState, u32)]| {
+            spans.iter().all(|(start, start_chain, inputs, end, end_checksum)| {
+                let claim = MovementClaim { start: *start, start_chain: *start_chain, inputs, end: *end, end_checksum: *end_checksum };
+                verify_movement(&claim, 0.25, &tuning, &OpenGround).is_ok()
+            })
+        };
+        assert!(replays(&spans));
+        // An input carrying only its own frame's delta replays a third of the motion
+        assert!(!replays(&frame_delta_spans));
+
+        // A long hitch is split into ticks the validator accepts
+        let mut hitch = InputAccumulator::default();
+        hitch.add_frame(0.2);
+        hitch.add_frame(0.2);
+        assert_eq!(hitch.take_ticks(), vec![MAX_TICK_DELTA, 0.4 - MAX_TICK_DELTA]);
+        assert!(hitch.take_ticks().is_empty());
+    }
+
+    /// A wall along x = 3 and a 0.3 high step covering z >= 2
+    struct Room;
+
+    impl MovementWorld for Room {
+        fn blocks(&self, x: f32, _y: f32, _z: f32) -> bool {
+            x + PLAYER_RADIUS > 3.0
+        }
+
+        fn floor_height(&self, _x: f32, z: f32, max_y: f32) -> f32 {
+            if z + PLAYER_RADIUS > 2.0 && max_y >= 0.3 { 0.3 } else { 0.0 }
+        }
+    }
+
+    #[test]
+    fn test_step_slides_along_walls_climbs_ledges_and_jumps() {
+        // Running diagonally into the wall keeps the sideways part of the move
+        let input = MovementInput { forward: true, yaw: std::f32::consts::FRAC_PI_4, delta: FIXED_DT, ..Default::default() };
+        let mut state = MovementState::at(2.0, 0.0, 0.0);
+        for _ in 0..60 {
+            state = step(state, &input, FIXED_DT, &MovementTuning::default(), &Room);
+        }
+        assert!(state.x <= 3.0 - PLAYER_RADIUS && state.x > 2.5);
+        assert!(state.z > 2.0 && (state.y - 0.3).abs() < 1e-6 && state.grounded);
+
+        // Dropped from a height: falls under gravity and lands on the floor
+        let mut falling = MovementState::at(0.0, 2.0, 0.0);
+        falling = step(falling, &MovementInput::default(), FIXED_DT, &MovementTuning::default(), &Room);
+        assert!(falling.vy < 0.0 && falling.y < 2.0 && !falling.grounded);
+        for _ in 0..60 {
+            falling = step(falling, &MovementInput::default(), FIXED_DT, &MovementTuning::default(), &Room);
+        }
+        assert!(falling.grounded && falling.y == 0.0);
+
+        // Holding jump: one hop that clears a crate's height, and no second one mid-air
+        let tuning = MovementTuning::default();
+        let jump = MovementInput { jump: true, ..Default::default() };
+        let mut hop = step(falling, &jump, FIXED_DT, &tuning, &Room);
+        let mut peak: f32 = 0.0;
+        for _ in 0..30 {
+            hop = step(hop, &jump, FIXED_DT, &tuning, &Room);
+            peak = peak.max(hop.y);
+        }
+        assert!(peak > 1.0 && hop.vy < 0.0 && !hop.grounded);
+    }
+
+    #[test]
+    fn test_world_edge_stops_players_and_lets_strays_back_in() {
+        let tuning = MovementTuning::default();
+        let east = MovementInput { forward: true, running: true, delta: FIXED_DT, ..Default::default() };
+        let mut state = MovementState::at(20.0, 0.0, 0.0);
+        for _ in 0..60 {
+            state = step(state, &east, FIXED_DT, &tuning, &OpenGround);
+        }
+        assert_eq!(state.x, MAP_BOUNDARY - PLAYER_RADIUS);
+        assert_eq!(state.vx, 0.0);
+
+        // Pushed outside from elsewhere: can't go further out, can walk back
+        let outside = MovementState::at(MAP_BOUNDARY + 2.0, 0.0, 0.0);
+        assert_eq!(step(outside, &east, FIXED_DT, &tuning, &OpenGround).x, outside.x);
+        let west = MovementInput { yaw: std::f32::consts::PI, ..east };
+        assert!(step(outside, &west, FIXED_DT, &tuning, &OpenGround).x < outside.x);
+    }
+
+    #[test]
+    fn test_crouching_at_sprint_speed_slides_once() {
+        let tuning = MovementTuning::default();
+        let sprint = MovementInput { forward: true, running: true, delta: FIXED_DT, ..Default::default() };
+        let crouch = MovementInput { crouching: true, ..sprint };
+        let mut state = MovementState::at(-20.0, 0.0, 0.0);
+        for _ in 0..60 {
+            state = step(state, &sprint, FIXED_DT, &tuning, &OpenGround);
+        }
+        let sprint_speed = state.vx;
+
+        state = step(state, &crouch, FIXED_DT, &tuning, &OpenGround);
+        assert!(state.is_sliding() && state.vx > sprint_speed);
+        let start = state.x;
+        for _ in 0..30 {
+            state = step(state, &crouch, FIXED_DT, &tuning, &OpenGround);
+        }
+        // Half a second of sliding covers more ground than a crouch walk would
+        assert!(state.is_sliding() && state.x - start > BASE_MOVE_SPEED * CROUCH_MULTIPLIER * 0.5 * 2.0);
+
+        // Letting go ends it, and crouching again straight away doesn't start another
+        state = step(state, &sprint, FIXED_DT, &tuning, &OpenGround);
+        assert!(!state.is_sliding() && state.slide_cooldown > 0.0);
+        state = step(state, &crouch, FIXED_DT, &tuning, &OpenGround);
+        assert!(!state.is_sliding());
+    }
+
+    #[test]
+    fn test_fall_damage_starts_past_threshold_and_crouching_softens_it() {
+        let tuning = MovementTuning::default();
+        assert_eq!(fall_damage(tuning.jump_speed, false, &tuning), 0.0);
+        assert_eq!(fall_damage(tuning.fall_damage_speed, false, &tuning), 0.0);
+
+        // Dropped from 10 units: lands at sqrt(2 * g * h)
+        let impact = (2.0 * GRAVITY * 10.0).sqrt();
+        let hard = fall_damage(impact, false, &tuning);
+        assert!(hard > 40.0 && hard < 100.0, "took {}", hard);
+        assert_eq!(fall_damage(impact, true, &tuning), hard * tuning.fall_damage_cushion);
+    }
+
+    /// A waist-high crate (top at 1.0) spanning x 3..4
+    struct Crate;
+
+    impl MovementWorld for Crate {
+        fn blocks(&self, x: f32, y: f32, _z: f32) -> bool {
+            x + PLAYER_RADIUS > 3.0 && x - PLAYER_RADIUS < 4.0 && 1.0 > y + STEP_HEIGHT
+        }
+
+        fn floor_height(&self, x: f32, _z: f32, max_y: f32) -> f32 {
+            if x + PLAYER_RADIUS > 3.0 && x - PLAYER_RADIUS < 4.0 && max_y >= 1.0 { 1.0 } else { 0.0 }
+        }
+    }
+
+    #[test]
+    fn test_jumping_into_a_crate_mantles_onto_it() {
+        let tuning = MovementTuning::default();
+        let walk = MovementInput { forward: true, delta: FIXED_DT, ..Default::default() };
+        let mut state = MovementState::at(1.0, 0.0, 0.0);
+        for _ in 0..60 {
+            state = step(state, &walk, FIXED_DT, &tuning, &Crate);
+        }
+        assert!(state.x <= 3.0 - PLAYER_RADIUS && state.grounded && state.mantle == 0.0);
+
+        let climb = MovementInput { jump: true, ..walk };
+        state = step(state, &climb, FIXED_DT, &tuning, &Crate);
+        assert!(state.mantle > 0.0 && state.mantle_top == 1.0);
+        for _ in 0..(MANTLE_DURATION / FIXED_DT) as usize + 2 {
+            state = step(state, &walk, FIXED_DT, &tuning, &Crate);
+        }
+        assert!(state.mantle == 0.0 && state.grounded && state.y == 1.0);
+
+        // Carries on walking over the top
+        for _ in 0..10 {
+            state = step(state, &walk, FIXED_DT, &tuning, &Crate);
+        }
+        assert!(state.x > 3.0 && state.y == 1.0);
+    }
+}
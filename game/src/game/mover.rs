@@ -0,0 +1,75 @@
+use raylib::prelude::*;
+
+/// Seconds a mover takes to slide open
+pub const MOVER_OPEN_TIME: f32 = 1.2;
+
+/// A map object sliding from its closed to its open position
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Mover {
+    /// Index into `Map::objects`
+    object: usize,
+    closed: Vector3,
+    open: Vector3,
+    /// 0 = closed, 1 = open
+    progress: f32,
+}
+
+/// Doors and gates opened by map triggers; they stay open until the map is reset
+#[derive(Debug, Default)]
+pub struct MoverSet {
+    movers: Vec<Mover>,
+}
+
+impl MoverSet {
+    /// Start sliding `object` from `closed` to `open` (ignored if it's already moving or open)
+    pub fn open(&mut self, object: usize, closed: Vector3, open: Vector3) {
+        if !self.movers.iter().any(|m| m.object == object) {
+            self.movers.push(Mover { object, closed, open, progress: 0.0 });
+        }
+    }
+
+    /// Advance the sliding movers: (object, new position) for each one that moved
+    pub fn update(&mut self, delta: f32) -> Vec<(usize, Vector3)> {
+        let mut moved = Vec::new();
+        for mover in self.movers.iter_mut().filter(|m| m.progress < 1.0) {
+            mover.progress = (mover.progress + delta / MOVER_OPEN_TIME).min(1.0);
+            // Ease in and out so heavy doors start and stop gently
+            let t = mover.progress * mover.progress * (3.0 - 2.0 * mover.progress);
+            moved.push((mover.object, mover.closed + (mover.open - mover.closed) * t));
+        }
+        moved
+    }
+
+    /// Whether any mover is still on its way
+    pub fn is_moving(&self) -> bool {
+        self.movers.iter().any(|m| m.progress < 1.0)
+    }
+
+    /// Forget every mover: (object, closed position) to put the map back
+    pub fn reset(&mut self) -> Vec<(usize, Vector3)> {
+        self.movers.drain(..).map(|m| (m.object, m.closed)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_movers_ease_open_once_and_reset_to_closed() {
+        let closed = Vector3::new(0.0, 1.0, 0.0);
+        let open = Vector3::new(0.0, -1.0, 0.0);
+        let mut movers = MoverSet::default();
+        movers.open(3, closed, open);
+        movers.open(3, open, closed);
+
+        let halfway = movers.update(MOVER_OPEN_TIME / 2.0);
+        assert_eq!(halfway, vec![(3, Vector3::new(0.0, 0.0, 0.0))]);
+        assert_eq!(movers.update(MOVER_OPEN_TIME), vec![(3, open)]);
+        assert!(!movers.is_moving());
+        assert!(movers.update(0.1).is_empty());
+
+        assert_eq!(movers.reset(), vec![(3, closed)]);
+        assert!(movers.update(0.1).is_empty());
+    }
+}
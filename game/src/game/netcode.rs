@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+/// Runtime-tunable netcode settings (interpolation, extrapolation and reconciliation)
+///
+/// Defaults match the values that used to be hardcoded in `GameState::update`.
+/// The JS side can override any subset of these through `gameBridge.setNetcodeConfig`,
+/// which persists them to localStorage so they survive a reload.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NetcodeConfig {
+    /// Max time (seconds) a remote player is extrapolated past its last update
+    pub max_extrapolation_time: f32,
+
+    /// How fast remote player positions converge to the predicted position
+    pub position_interp_speed: f32,
+
+    /// How fast remote player rotations converge to the target rotation
+    pub rotation_interp_speed: f32,
+
+    /// Local reconciliation error (units) tolerated before correcting
+    pub base_error_threshold: f32,
+
+    /// Extra error tolerance gained per unit/second of movement
+    pub velocity_tolerance_factor: f32,
+
+    /// Cap on the extra velocity-based tolerance
+    pub max_velocity_tolerance: f32,
+
+    /// Errors above this (units) snap straight to the server position
+    pub snap_threshold: f32,
+
+    /// Correction speed used for small errors
+    pub min_correction_speed: f32,
+
+    /// Correction speed used for errors close to the snap threshold
+    pub max_correction_speed: f32,
+}
+
+impl Default for NetcodeConfig {
+    fn default() -> Self {
+        Self {
+            max_extrapolation_time: 0.2,
+            position_interp_speed: 15.0,
+            rotation_interp_speed: 8.0,
+            base_error_threshold: 0.3,
+            velocity_tolerance_factor: 0.1,
+            max_velocity_tolerance: 0.5,
+            snap_threshold: 3.0,
+            min_correction_speed: 5.0,
+            max_correction_speed: 15.0,
+        }
+    }
+}
+
+impl NetcodeConfig {
+    /// Parse a (possibly partial) JSON config; missing fields keep their defaults
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str::<Self>(json).map(|config| config.sanitized())
+    }
+
+    /// Serialize to JSON (used to mirror the active config back to JS)
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Clamp values into sane ranges so a bad console entry can't break movement
+    pub fn sanitized(mut self) -> Self {
+        self.max_extrapolation_time = self.max_extrapolation_time.clamp(0.0, 1.0);
+        self.position_interp_speed = self.position_interp_speed.clamp(0.1, 60.0);
+        self.rotation_interp_speed = self.rotation_interp_speed.clamp(0.1, 60.0);
+        self.base_error_threshold = self.base_error_threshold.clamp(0.0, 5.0);
+        self.velocity_tolerance_factor = self.velocity_tolerance_factor.clamp(0.0, 1.0);
+        self.max_velocity_tolerance = self.max_velocity_tolerance.clamp(0.0, 5.0);
+        self.snap_threshold = self.snap_threshold.clamp(0.5, 50.0);
+        self.min_correction_speed = self.min_correction_speed.clamp(0.1, 60.0);
+        self.max_correction_speed = self.max_correction_speed.clamp(self.min_correction_speed, 60.0);
+        self
+    }
+
+    /// Error threshold for local reconciliation at the given movement speed
+    pub fn error_threshold(&self, velocity: f32) -> f32 {
+        self.base_error_threshold + (velocity * self.velocity_tolerance_factor).min(self.max_velocity_tolerance)
+    }
+
+    /// Correction speed for a reconciliation error below the snap threshold
+    pub fn correction_speed(&self, position_error: f32) -> f32 {
+        let error_factor = (position_error / self.snap_threshold).min(1.0);
+        self.min_correction_speed + (self.max_correction_speed - self.min_correction_speed) * error_factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_json_keeps_defaults() {
+        let config = NetcodeConfig::from_json(r#"{"positionInterpSpeed": 20.0}"#).unwrap();
+        assert_eq!(config.position_interp_speed, 20.0);
+        assert_eq!(config.rotation_interp_speed, NetcodeConfig::default().rotation_interp_speed);
+    }
+
+    #[test]
+    fn test_sanitize_clamps_values() {
+        let config = NetcodeConfig::from_json(r#"{"maxExtrapolationTime": 10.0, "minCorrectionSpeed": 30.0, "maxCorrectionSpeed": 1.0}"#).unwrap();
+        assert_eq!(config.max_extrapolation_time, 1.0);
+        assert!(config.max_correction_speed >= config.min_correction_speed);
+    }
+}
@@ -0,0 +1,192 @@
+use raylib::prelude::*;
+use std::cell::Cell;
+
+/// Color-vision deficiency the palette is tuned for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorblindMode {
+    #[default]
+    Off,
+    /// Green-weak: red/green teams become blue/orange
+    Deuteranopia,
+    /// Red-weak: like deuteranopia, with brighter reds (reds look dark)
+    Protanopia,
+    /// Blue-weak: blue/yellow are confused, teams become teal/red
+    Tritanopia,
+}
+
+impl ColorblindMode {
+    pub const ALL: [ColorblindMode; 4] = [
+        ColorblindMode::Off,
+        ColorblindMode::Deuteranopia,
+        ColorblindMode::Protanopia,
+        ColorblindMode::Tritanopia,
+    ];
+
+    /// Id used by the JS bridge (0 = off, 1 = deuteranopia, 2 = protanopia, 3 = tritanopia)
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.get(id as usize).copied()
+    }
+
+    pub fn palette(&self) -> Palette {
+        match self {
+            ColorblindMode::Off => Palette {
+                team_a: Color::new(0, 150, 255, 255),
+                team_b: Color::new(255, 100, 100, 255),
+                team_a_text: Color::new(120, 200, 255, 230),
+                team_b_text: Color::new(255, 110, 110, 230),
+                friendly: Color::new(80, 160, 255, 255),
+                enemy: Color::new(255, 68, 68, 255),
+                local_marker: Color::new(0, 255, 163, 255),
+                blood: Color::new(180, 10, 10, 255),
+                blood_dark: Color::new(120, 0, 0, 255),
+                health_high: Color::new(0, 200, 80, 255),
+                health_mid: Color::new(220, 180, 0, 255),
+                health_low: Color::new(220, 50, 50, 255),
+                positive: Color::new(0, 242, 148, 255),
+                negative: Color::new(255, 68, 68, 255),
+            },
+            // Okabe-Ito blue / orange / vermillion stay apart for red-green deficiencies
+            ColorblindMode::Deuteranopia => Palette {
+                team_a: Color::new(0, 114, 178, 255),
+                team_b: Color::new(230, 159, 0, 255),
+                team_a_text: Color::new(86, 180, 233, 230),
+                team_b_text: Color::new(240, 180, 60, 230),
+                friendly: Color::new(40, 140, 220, 255),
+                enemy: Color::new(230, 159, 0, 255),
+                local_marker: Color::new(255, 255, 255, 255),
+                blood: Color::new(213, 94, 0, 255),
+                blood_dark: Color::new(150, 60, 0, 255),
+                health_high: Color::new(86, 180, 233, 255),
+                health_mid: Color::new(240, 228, 66, 255),
+                health_low: Color::new(213, 94, 0, 255),
+                positive: Color::new(86, 180, 233, 255),
+                negative: Color::new(230, 159, 0, 255),
+            },
+            ColorblindMode::Protanopia => Palette {
+                team_a: Color::new(0, 114, 178, 255),
+                team_b: Color::new(255, 190, 30, 255),
+                team_a_text: Color::new(86, 180, 233, 230),
+                team_b_text: Color::new(255, 210, 80, 230),
+                friendly: Color::new(40, 140, 220, 255),
+                enemy: Color::new(255, 190, 30, 255),
+                local_marker: Color::new(255, 255, 255, 255),
+                blood: Color::new(255, 140, 20, 255),
+                blood_dark: Color::new(190, 100, 0, 255),
+                health_high: Color::new(86, 180, 233, 255),
+                health_mid: Color::new(240, 228, 66, 255),
+                health_low: Color::new(255, 140, 20, 255),
+                positive: Color::new(86, 180, 233, 255),
+                negative: Color::new(255, 190, 30, 255),
+            },
+            ColorblindMode::Tritanopia => Palette {
+                team_a: Color::new(0, 170, 170, 255),
+                team_b: Color::new(180, 30, 20, 255),
+                team_a_text: Color::new(100, 220, 220, 230),
+                team_b_text: Color::new(255, 110, 90, 230),
+                friendly: Color::new(0, 200, 200, 255),
+                enemy: Color::new(230, 50, 32, 255),
+                local_marker: Color::new(255, 255, 255, 255),
+                blood: Color::new(200, 0, 40, 255),
+                blood_dark: Color::new(130, 0, 30, 255),
+                health_high: Color::new(0, 200, 200, 255),
+                health_mid: Color::new(204, 121, 167, 255),
+                health_low: Color::new(230, 50, 32, 255),
+                positive: Color::new(0, 200, 200, 255),
+                negative: Color::new(230, 50, 32, 255),
+            },
+        }
+    }
+}
+
+/// Gameplay-relevant colors (teams, hit effects, minimap markers, HUD accents)
+///
+/// Rendering code reads these from `current()` instead of hard-coding them so
+/// the color-blind presets apply everywhere. Decorative theme colors (Solana
+/// purple, map materials) are not part of the palette.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    /// Team A (1) player models
+    pub team_a: Color,
+    /// Team B (2) player models
+    pub team_b: Color,
+    /// Team A names and overlays
+    pub team_a_text: Color,
+    /// Team B names and overlays
+    pub team_b_text: Color,
+    /// Teammates relative to the local player (nameplates, minimap)
+    pub friendly: Color,
+    /// Enemies relative to the local player (nameplates, minimap)
+    pub enemy: Color,
+    /// The local player on the minimap
+    pub local_marker: Color,
+    pub blood: Color,
+    pub blood_dark: Color,
+    pub health_high: Color,
+    pub health_mid: Color,
+    pub health_low: Color,
+    /// Good-news accent (countdown, VICTORY, regen)
+    pub positive: Color,
+    /// Bad-news accent (DEFEAT, exhausted breath)
+    pub negative: Color,
+}
+
+impl Palette {
+    /// Player model color for a team ("1" = A, "2" = B)
+    pub fn team(&self, team: &str) -> Color {
+        if team == "1" { self.team_a } else { self.team_b }
+    }
+
+    /// Name / overlay color for a team ("1" = A, "2" = B)
+    pub fn team_text(&self, team: &str) -> Color {
+        if team == "1" { self.team_a_text } else { self.team_b_text }
+    }
+
+    /// Health bar fill for a health fraction (0.0 - 1.0)
+    pub fn health(&self, fraction: f32) -> Color {
+        if fraction > 0.6 {
+            self.health_high
+        } else if fraction > 0.3 {
+            self.health_mid
+        } else {
+            self.health_low
+        }
+    }
+}
+
+// Active color-blind mode (Emscripten is single-threaded)
+thread_local! {
+    static MODE: Cell<ColorblindMode> = Cell::new(ColorblindMode::Off);
+}
+
+/// Switch the palette every renderer reads
+pub fn set_colorblind_mode(mode: ColorblindMode) {
+    MODE.with(|current| current.set(mode));
+}
+
+/// Palette for the active color-blind mode
+pub fn current() -> Palette {
+    MODE.with(|current| current.get()).palette()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_keep_teams_apart() {
+        // Teams must differ in brightness too, not only hue
+        let luma = |c: Color| 0.299 * c.r as f32 + 0.587 * c.g as f32 + 0.114 * c.b as f32;
+        for mode in ColorblindMode::ALL.into_iter().skip(1) {
+            let palette = mode.palette();
+            assert!((luma(palette.team_a) - luma(palette.team_b)).abs() > 30.0, "{:?}", mode);
+            assert!((luma(palette.friendly) - luma(palette.enemy)).abs() > 30.0, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn test_mode_ids() {
+        assert_eq!(ColorblindMode::from_id(0), Some(ColorblindMode::Off));
+        assert_eq!(ColorblindMode::from_id(3), Some(ColorblindMode::Tritanopia));
+        assert_eq!(ColorblindMode::from_id(4), None);
+    }
+}
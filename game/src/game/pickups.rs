@@ -0,0 +1,103 @@
+use raylib::prelude::*;
+use super::WeaponKind;
+
+/// Horizontal distance within which walking over a weapon picks it up (units)
+pub const PICKUP_RADIUS: f32 = 1.2;
+
+/// Dropped weapons disappear after this long (seconds)
+pub const DESPAWN_TIME: f64 = 30.0;
+
+/// A weapon lying in the world
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeaponPickup {
+    /// Unique id shared by every client (dropper key + drop time)
+    pub id: String,
+    pub weapon: WeaponKind,
+    /// Ground position the weapon rests at
+    pub position: Vector3,
+    /// Local time (seconds) the weapon was dropped
+    pub dropped_at: f64,
+}
+
+impl WeaponPickup {
+    /// Seconds until the weapon despawns
+    pub fn time_left(&self, now: f64) -> f64 {
+        (self.dropped_at + DESPAWN_TIME - now).max(0.0)
+    }
+}
+
+/// All dropped weapons in the match
+#[derive(Debug, Default)]
+pub struct PickupField {
+    pickups: Vec<WeaponPickup>,
+}
+
+impl PickupField {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a dropped weapon (ignored if the id is already known)
+    pub fn add(&mut self, pickup: WeaponPickup) {
+        if !self.pickups.iter().any(|p| p.id == pickup.id) {
+            self.pickups.push(pickup);
+        }
+    }
+
+    /// Remove a weapon (picked up locally or by someone else)
+    pub fn remove(&mut self, id: &str) -> Option<WeaponPickup> {
+        let index = self.pickups.iter().position(|p| p.id == id)?;
+        Some(self.pickups.remove(index))
+    }
+
+    pub fn clear(&mut self) {
+        self.pickups.clear();
+    }
+
+    /// Drop weapons whose despawn timer ran out
+    pub fn expire(&mut self, now: f64) {
+        self.pickups.retain(|p| p.time_left(now) > 0.0);
+    }
+
+    /// Closest weapon within reach of `position` that `accept` allows
+    pub fn in_reach(&self, position: Vector3, accept: impl Fn(WeaponKind) -> bool) -> Option<&WeaponPickup> {
+        let horizontal_distance = |p: &WeaponPickup| {
+            let (dx, dz) = (p.position.x - position.x, p.position.z - position.z);
+            (dx * dx + dz * dz).sqrt()
+        };
+        self.pickups
+            .iter()
+            .filter(|p| accept(p.weapon) && horizontal_distance(p) <= PICKUP_RADIUS)
+            .min_by(|a, b| horizontal_distance(a).total_cmp(&horizontal_distance(b)))
+    }
+
+    pub fn pickups(&self) -> &[WeaponPickup] {
+        &self.pickups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pickup(id: &str, weapon: WeaponKind, x: f32) -> WeaponPickup {
+        WeaponPickup { id: id.to_string(), weapon, position: Vector3::new(x, 0.0, 0.0), dropped_at: 10.0 }
+    }
+
+    #[test]
+    fn test_reach_filter_and_despawn() {
+        let mut field = PickupField::new();
+        field.add(pickup("a", WeaponKind::Sniper, 1.0));
+        field.add(pickup("b", WeaponKind::Shotgun, 0.5));
+        field.add(pickup("b", WeaponKind::Shotgun, 0.5));
+        field.add(pickup("c", WeaponKind::Rifle, 5.0));
+        assert_eq!(field.pickups().len(), 3);
+
+        let feet = Vector3::new(0.0, 0.0, 0.0);
+        assert_eq!(field.in_reach(feet, |_| true).map(|p| p.id.as_str()), Some("b"));
+        assert_eq!(field.in_reach(feet, |w| w != WeaponKind::Shotgun).map(|p| p.id.as_str()), Some("a"));
+
+        field.expire(10.0 + DESPAWN_TIME);
+        assert!(field.pickups().is_empty());
+    }
+}
@@ -1,4 +1,29 @@
 use raylib::prelude::*;
+use super::movement::{fall_damage, step, MovementClock, MovementInput, MovementState, MovementTuning, MovementWorld, BASE_MOVE_SPEED, FIXED_DT};
+use super::comfort::{comfort_scale, ComfortEffect};
+use super::BASE_FOV;
+
+/// Fall speed (units per second) at which a landing gives the full dip
+const HARD_LANDING_SPEED: f32 = 12.0;
+
+/// Landings slower than this (small steps down) don't dip at all
+const SOFT_LANDING_SPEED: f32 = 3.0;
+
+/// Seconds a full landing dip takes to recover
+const LANDING_RECOVERY: f32 = 0.3;
+
+/// Camera drop (units) and FOV narrowing (degrees) at a full landing dip
+const LANDING_CAMERA_DIP: f32 = 0.12;
+const LANDING_FOV_DIP: f32 = 4.0;
+
+/// Camera pitch kick (degrees, downwards) at a full landing dip after a landing that hurt enough
+const LANDING_DAMAGE_KICK: f32 = 6.0;
+
+/// Fall damage that gives the full camera kick
+const FULL_KICK_DAMAGE: f32 = 40.0;
+
+/// Eye height while sliding, as a fraction of standing eye height
+const SLIDE_EYE_SCALE: f32 = 0.45;
 
 /// Player character with FPS camera and movement
 pub struct Player {
@@ -8,9 +33,6 @@ pub struct Player {
     /// Camera for first-person view
     pub camera: Camera3D,
 
-    /// Player movement speed (units per second)
-    pub move_speed: f32,
-
     /// Mouse sensitivity for looking around
     pub mouse_sensitivity: f32,
 
@@ -47,6 +69,51 @@ pub struct Player {
     /// Target rotation for server reconciliation
     pub target_yaw: f32,
     pub target_pitch: f32,
+
+    /// Last health value reported by the blockchain (authoritative)
+    pub server_health: f32,
+
+    /// Health regenerated locally on top of `server_health`
+    pub regen_amount: f32,
+
+    /// Timestamp (seconds) of the last damage taken, used for regen delay
+    pub last_damage_time: f64,
+
+    /// Whether passive regen is currently ticking (for HUD feedback)
+    pub is_regenerating: bool,
+
+    /// Walk cycle phase (radians), advanced by distance travelled (drives leg swing)
+    pub walk_cycle: f32,
+
+    /// How much the legs are swinging (0.0 standing still, 1.0 walking)
+    pub stride_blend: f32,
+
+    /// Look sensitivity multiplier while scoped (None when not scoped)
+    /// Shift holds breath instead of sprinting while this is set
+    pub scoped_look_scale: Option<f32>,
+
+    /// Mouse look is ignored (the mouse steers the spray / emote radial menu)
+    pub look_locked: bool,
+
+    /// Landing impact still showing (1.0 right after a hard landing, back to 0.0 as it recovers)
+    pub landing_dip: f32,
+
+    /// Downward camera kick (degrees at a full dip) from the last landing that hurt
+    landing_kick: f32,
+
+    /// Environmental damage (falls, the out-of-bounds zone) taken locally on top of
+    /// `server_health`: the game program has no instruction for it, so it's never reported on-chain
+    pub hazard_damage: f32,
+
+    /// Fall damage from landings since `take_landing_damage` was last called
+    landing_damage: f32,
+
+    /// Physics state after the latest fixed step, and the one before (drawn in between)
+    motion: MovementState,
+    previous_motion: MovementState,
+    clock: MovementClock,
+    /// `position` as last written by `update`; anything else means we were moved from outside
+    simulated_position: Vector3,
 }
 
 impl Player {
@@ -67,7 +134,6 @@ impl Player {
         Self {
             position,
             camera,
-            move_speed: 5.0, // 5 units per second
             mouse_sensitivity: 0.1,
             yaw: -90.0, // Start facing forward (negative Z)
             pitch: 0.0,
@@ -81,23 +147,40 @@ impl Player {
             target_position: position, // Initialize to current position
             target_yaw: -90.0,
             target_pitch: 0.0,
+            server_health: 100.0,
+            regen_amount: 0.0,
+            last_damage_time: 0.0,
+            is_regenerating: false,
+            walk_cycle: 0.0,
+            stride_blend: 0.0,
+            scoped_look_scale: None,
+            look_locked: false,
+            landing_dip: 0.0,
+            landing_kick: 0.0,
+            hazard_damage: 0.0,
+            landing_damage: 0.0,
+            motion: MovementState::at(position.x, position.y, position.z),
+            previous_motion: MovementState::at(position.x, position.y, position.z),
+            clock: MovementClock::default(),
+            simulated_position: position,
         }
     }
 
-    /// Update player movement and camera based on input
-    pub fn update(&mut self, rl: &RaylibHandle, delta: f32, joystick_input: Option<(bool, bool, bool, bool)>, mobile_camera_input: Option<(f32, f32)>) {
-        // Check for running (Shift key)
-        self.is_running = rl.is_key_down(KeyboardKey::KEY_LEFT_SHIFT) || rl.is_key_down(KeyboardKey::KEY_RIGHT_SHIFT);
-
-        // Check for crouching (Ctrl key)
-        self.is_crouching = rl.is_key_down(KeyboardKey::KEY_LEFT_CONTROL) || rl.is_key_down(KeyboardKey::KEY_RIGHT_CONTROL);
+    /// Look around without moving (round-start freeze)
+    pub fn update_look_only(&mut self, rl: &RaylibHandle, mobile_camera_input: Option<(f32, f32)>) {
+        self.apply_look(rl, mobile_camera_input);
+        self.update_camera();
+    }
 
+    /// Apply mouse / touch look to yaw and pitch
+    fn apply_look(&mut self, rl: &RaylibHandle, mobile_camera_input: Option<(f32, f32)>) {
         // Mouse look
-        let mouse_delta = rl.get_mouse_delta();
+        let mouse_delta = if self.look_locked { Vector2::zero() } else { rl.get_mouse_delta() };
 
         // Update yaw (horizontal) and pitch (vertical)
-        self.yaw += mouse_delta.x * self.mouse_sensitivity;
-        self.pitch -= mouse_delta.y * self.mouse_sensitivity;
+        let sensitivity = self.mouse_sensitivity * self.scoped_look_scale.unwrap_or(1.0);
+        self.yaw += mouse_delta.x * sensitivity;
+        self.pitch -= mouse_delta.y * sensitivity;
 
         // Mobile camera input (touch drag)
         if let Some((delta_x, delta_y)) = mobile_camera_input {
@@ -107,128 +190,210 @@ impl Player {
 
         // Clamp pitch to prevent camera flipping
         self.pitch = self.pitch.clamp(-89.0, 89.0);
+    }
+
+    /// Update player movement (fixed physics steps against `world`) and camera based on input
+    pub fn update(&mut self, rl: &RaylibHandle, delta: f32, joystick_input: Option<(bool, bool, bool, bool)>, mobile_camera_input: Option<(f32, f32)>, tuning: &MovementTuning, world: &dyn MovementWorld) {
+        // Check for running (Shift key)
+        self.is_running = self.scoped_look_scale.is_none()
+            && (rl.is_key_down(KeyboardKey::KEY_LEFT_SHIFT) || rl.is_key_down(KeyboardKey::KEY_RIGHT_SHIFT));
+
+        // Check for crouching (Ctrl key)
+        self.is_crouching = rl.is_key_down(KeyboardKey::KEY_LEFT_CONTROL) || rl.is_key_down(KeyboardKey::KEY_RIGHT_CONTROL);
+
+        self.apply_look(rl, mobile_camera_input);
 
         // Log rotation every frame for debugging
         /*println!("🎯 Rotation - Yaw: {:.2}°, Pitch: {:.2}° | Radians - Yaw: {:.4}, Pitch: {:.4}",
                  self.yaw, self.pitch,
                  self.yaw.to_radians(), self.pitch.to_radians());*/
 
-        // Calculate camera direction from yaw and pitch
         let yaw_rad = self.yaw.to_radians();
         let pitch_rad = self.pitch.to_radians();
 
-        let direction = Vector3::new(
-            yaw_rad.cos() * pitch_rad.cos(),
-            pitch_rad.sin(),
-            yaw_rad.sin() * pitch_rad.cos(),
-        );
+        // WASD movement + joystick input
+        let joystick = joystick_input.unwrap_or((false, false, false, false));
+        let input = MovementInput {
+            forward: rl.is_key_down(KeyboardKey::KEY_W) || joystick.0,
+            backward: rl.is_key_down(KeyboardKey::KEY_S) || joystick.1,
+            left: rl.is_key_down(KeyboardKey::KEY_A) || joystick.2,
+            right: rl.is_key_down(KeyboardKey::KEY_D) || joystick.3,
+            running: self.is_running,
+            crouching: self.is_crouching,
+            jump: rl.is_key_down(KeyboardKey::KEY_SPACE),
+            yaw: yaw_rad,
+            pitch: pitch_rad,
+            delta,
+        };
+
+        // Moved from outside since the last frame (server reconciliation): carry the
+        // physics along by the same amount so the step phase and velocity survive
+        let nudge = self.position - self.simulated_position;
+        if nudge != Vector3::zero() {
+            for state in [&mut self.motion, &mut self.previous_motion] {
+                state.x += nudge.x;
+                state.y += nudge.y;
+                state.z += nudge.z;
+            }
+        }
 
-        // Calculate right vector for strafing (perpendicular to forward)
-        // Right vector is 90 degrees to the left of forward in XZ plane
-        let right = Vector3::new(
-            (yaw_rad + 90.0_f32.to_radians()).cos(),
-            0.0,
-            (yaw_rad + 90.0_f32.to_radians()).sin(),
+        // Shared deterministic step at a fixed rate (the same code the validator replays)
+        let previous = self.position;
+        self.landing_dip = (self.landing_dip - delta / LANDING_RECOVERY).max(0.0);
+        for _ in 0..self.clock.steps(delta) {
+            self.previous_motion = self.motion;
+            self.motion = step(self.motion, &input, FIXED_DT, tuning, world);
+            if self.motion.grounded && !self.previous_motion.grounded {
+                let speed = -self.previous_motion.vy;
+                let impact = (speed - SOFT_LANDING_SPEED) / (HARD_LANDING_SPEED - SOFT_LANDING_SPEED);
+                self.landing_dip = self.landing_dip.max(impact.clamp(0.0, 1.0));
+
+                // Landing crouched (or into a slide) takes part of the hit
+                let damage = fall_damage(speed, input.crouching, tuning);
+                if damage > 0.0 {
+                    self.landing_damage += damage;
+                    self.landing_kick = LANDING_DAMAGE_KICK * (damage / FULL_KICK_DAMAGE).min(1.0);
+                }
+            }
+        }
+
+        // Draw between the last two steps so motion stays smooth at any frame rate
+        let alpha = self.clock.alpha();
+        let (from, to) = (self.previous_motion, self.motion);
+        self.position = Vector3::new(
+            from.x + (to.x - from.x) * alpha,
+            from.y + (to.y - from.y) * alpha,
+            from.z + (to.z - from.z) * alpha,
         );
+        self.simulated_position = self.position;
+        self.advance_walk_cycle((self.position - previous).length(), delta);
 
-        // WASD movement + joystick input
-        let mut movement = Vector3::zero();
+        // Log position every frame for debugging
+        //println!("📍 Position - X: {:.2}, Y: {:.2}, Z: {:.2}",
+        //         self.position.x, self.position.y, self.position.z);
 
-        // Check for forward movement (W key or joystick forward)
-        let forward_pressed = rl.is_key_down(KeyboardKey::KEY_W) || 
-            joystick_input.map_or(false, |(fwd, _, _, _)| fwd);
-        if forward_pressed {
-            // Move forward (ignore Y component for ground movement)
-            let forward = Vector3::new(direction.x, 0.0, direction.z).normalized();
-            movement = movement + forward;
-        }
-        
-        // Check for backward movement (S key or joystick backward)
-        let backward_pressed = rl.is_key_down(KeyboardKey::KEY_S) || 
-            joystick_input.map_or(false, |(_, back, _, _)| back);
-        if backward_pressed {
-            // Move backward
-            let forward = Vector3::new(direction.x, 0.0, direction.z).normalized();
-            movement = movement - forward;
+        self.update_camera();
+    }
+
+    /// Apply a health value reported by the blockchain
+    /// Any damage (or an on-chain heal/respawn) discards locally regenerated health;
+    /// a heal or respawn also clears hazard damage
+    pub fn apply_server_health(&mut self, health: f32, current_time: f64) {
+        if health < self.server_health {
+            self.last_damage_time = current_time;
         }
-        
-        // Check for left movement (A key or joystick left)
-        let left_pressed = rl.is_key_down(KeyboardKey::KEY_A) || 
-            joystick_input.map_or(false, |(_, _, left, _)| left);
-        if left_pressed {
-            // Strafe left
-            movement = movement - right;
+        if health > self.server_health {
+            self.hazard_damage = 0.0;
         }
-        
-        // Check for right movement (D key or joystick right)
-        let right_pressed = rl.is_key_down(KeyboardKey::KEY_D) || 
-            joystick_input.map_or(false, |(_, _, _, right)| right);
-        if right_pressed {
-            // Strafe right
-            movement = movement + right;
+        if health != self.server_health {
+            self.regen_amount = 0.0;
+            self.is_regenerating = false;
         }
 
-        // Normalize movement vector if moving diagonally
-        if movement.length() > 0.0 {
-            movement = movement.normalized();
-        }
+        self.server_health = health;
+        self.refresh_health();
+    }
 
-        // Calculate effective move speed based on running/crouching
-        let mut effective_speed = self.move_speed;
-        if self.is_running && !self.is_crouching {
-            effective_speed *= 2.0; // Running is 2x normal speed
-        } else if self.is_crouching {
-            effective_speed *= 0.5; // Crouching is 0.5x normal speed
+    /// Fall damage from landings since the last call (0 when nothing hurt)
+    pub fn take_landing_damage(&mut self) -> f32 {
+        std::mem::take(&mut self.landing_damage)
+    }
+
+    /// Take environmental damage locally (capped so health ends at 0); delays regen like any other damage
+    pub fn apply_hazard_damage(&mut self, amount: f32, current_time: f64) {
+        self.hazard_damage = (self.hazard_damage + amount).min(self.server_health + self.regen_amount);
+        self.last_damage_time = current_time;
+        self.is_regenerating = false;
+        self.refresh_health();
+    }
+
+    /// Forget hazard damage (respawning after a hazard death, which the chain never saw)
+    pub fn clear_hazard_damage(&mut self) {
+        self.hazard_damage = 0.0;
+        self.refresh_health();
+    }
+
+    /// Whether the player is down only because of hazard damage (the chain still has them alive)
+    pub fn died_from_hazard(&self) -> bool {
+        self.hazard_damage > 0.0 && self.health <= 0.0 && self.server_health > 0.0
+    }
+
+    /// Part of `health` that is local regen the chain hasn't confirmed (the program has no heal instruction)
+    pub fn predicted_regen(&self) -> f32 {
+        self.regen_amount.min(self.health)
+    }
+
+    /// Displayed health: on-chain health plus local regen, minus local hazard damage
+    fn refresh_health(&mut self) {
+        self.health = (self.server_health + self.regen_amount - self.hazard_damage).clamp(0.0, self.max_health);
+    }
+
+    /// Tick passive health regeneration
+    pub fn update_health_regen(&mut self, delay: f32, rate: f32, delta: f32, current_time: f64) {
+        let since_damage = (current_time - self.last_damage_time) as f32;
+
+        self.is_regenerating = !self.is_dead
+            && self.server_health > 0.0
+            && self.health < self.max_health
+            && since_damage >= delay;
+
+        if self.is_regenerating {
+            // Hazard damage heals first
+            let heal = rate * delta;
+            let recovered = heal.min(self.hazard_damage);
+            self.hazard_damage -= recovered;
+            self.regen_amount = (self.regen_amount + heal - recovered).min(self.max_health - self.server_health);
+            self.refresh_health();
         }
+    }
 
-        // Apply movement
-        let velocity = movement * effective_speed * delta;
-        self.position = self.position + velocity;
+    /// Advance the leg animation by the distance moved this frame
+    fn advance_walk_cycle(&mut self, distance: f32, delta: f32) {
+        // ~1.6 units per full stride
+        self.walk_cycle = (self.walk_cycle + distance * 4.0) % std::f32::consts::TAU;
 
-        // Clamp position to map boundaries (50x50 map = -25 to +25)
-        let boundary = 25.0;
-        self.position.x = self.position.x.clamp(-boundary, boundary);
-        self.position.z = self.position.z.clamp(-boundary, boundary);
+        let target_blend = if distance > 0.0 { 1.0 } else { 0.0 };
+        self.stride_blend += (target_blend - self.stride_blend) * (delta * 10.0).min(1.0);
+    }
 
-        // Log position every frame for debugging
-        //println!("📍 Position - X: {:.2}, Y: {:.2}, Z: {:.2}",
-        //         self.position.x, self.position.y, self.position.z);
+    /// Current landing dip eased for display and scaled by the screen-shake comfort setting
+    pub fn landing_offset(&self) -> f32 {
+        let dip = self.landing_dip * (2.0 - self.landing_dip); // ease-out: drops fast, recovers slowly
+        dip * comfort_scale(ComfortEffect::ScreenShake)
+    }
+
+    /// Whether a crouch-slide is in progress
+    pub fn is_sliding(&self) -> bool {
+        self.motion.is_sliding()
+    }
 
-        // Calculate effective height based on crouching
-        let effective_height = if self.is_crouching {
+    /// Eye height above the feet: lowered while crouching and further while sliding
+    pub fn eye_height(&self) -> f32 {
+        if self.is_sliding() {
+            self.height * SLIDE_EYE_SCALE
+        } else if self.is_crouching {
             self.height * 0.6 // Crouch to 60% of normal height
         } else {
             self.height
-        };
-
-        // Update camera position and target (rebuild camera to update immutable fields)
-        let camera_pos = Vector3::new(
-            self.position.x,
-            self.position.y + effective_height,
-            self.position.z,
-        );
-        let camera_target = camera_pos + direction;
-
-        self.camera = Camera3D::perspective(
-            camera_pos,
-            camera_target,
-            Vector3::new(0.0, 1.0, 0.0),
-            70.0,
-        );
+        }
     }
 
-    /// Set player position (useful for spawning)
+    /// Set player position (useful for spawning); stops any movement
     pub fn set_position(&mut self, position: Vector3) {
         self.position = position;
+        self.motion = MovementState::at(position.x, position.y, position.z);
+        self.previous_motion = self.motion;
+        self.simulated_position = position;
         self.update_camera();
     }
 
     /// Update camera based on current position and rotation (without processing input)
     /// This is useful for syncing camera with blockchain-authoritative state
     pub fn update_camera(&mut self) {
-        // Calculate look direction from yaw and pitch
+        // Calculate look direction from yaw and pitch (kicked down after a painful landing)
+        let dip = self.landing_offset();
         let yaw_rad = self.yaw.to_radians();
-        let pitch_rad = self.pitch.to_radians();
+        let pitch_rad = (self.pitch - self.landing_kick * dip).clamp(-89.0, 89.0).to_radians();
 
         let direction = Vector3::new(
             yaw_rad.cos() * pitch_rad.cos(),
@@ -236,16 +401,9 @@ impl Player {
             yaw_rad.sin() * pitch_rad.cos(),
         );
 
-        // Calculate effective height based on crouching state
-        let effective_height = if self.is_crouching {
-            self.height * 0.6
-        } else {
-            self.height
-        };
-
         let camera_pos = Vector3::new(
             self.position.x,
-            self.position.y + effective_height,
+            self.position.y + self.eye_height() - LANDING_CAMERA_DIP * dip,
             self.position.z,
         );
         let camera_target = camera_pos + direction;
@@ -254,7 +412,7 @@ impl Player {
             camera_pos,
             camera_target,
             Vector3::new(0.0, 1.0, 0.0),
-            70.0,
+            BASE_FOV - LANDING_FOV_DIP * dip,
         );
     }
 
@@ -280,9 +438,10 @@ impl Player {
             movement = movement.normalized();
         }
 
-        let effective_speed = self.move_speed;
+        let effective_speed = BASE_MOVE_SPEED;
         let velocity = movement * effective_speed * delta;
         self.position = self.position + velocity;
+        self.advance_walk_cycle(velocity.length(), delta);
 
         // Clamp to bounds
         let boundary = 25.0;
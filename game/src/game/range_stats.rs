@@ -0,0 +1,81 @@
+use std::collections::VecDeque;
+
+/// Window (seconds) the DPS readout averages over
+pub const DPS_WINDOW: f64 = 3.0;
+
+/// Shot/hit/damage bookkeeping for the shooting range readout
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RangeStats {
+    pub shots: u32,
+    pub hits: u32,
+    pub total_damage: f32,
+    /// Highest single-shot damage so far
+    pub best_hit: f32,
+    /// (time, damage) of recent hits, oldest first, for the rolling DPS
+    recent: VecDeque<(f64, f32)>,
+}
+
+impl RangeStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one trigger pull and the damage it dealt (0 for a miss; all pellets together)
+    pub fn record_shot(&mut self, damage: f32, now: f64) {
+        self.shots += 1;
+        if damage <= 0.0 {
+            return;
+        }
+        self.hits += 1;
+        self.total_damage += damage;
+        self.best_hit = self.best_hit.max(damage);
+        while self.recent.front().is_some_and(|(time, _)| now - time > DPS_WINDOW) {
+            self.recent.pop_front();
+        }
+        self.recent.push_back((now, damage));
+    }
+
+    /// Damage per second over the last `DPS_WINDOW` seconds
+    pub fn dps(&self, now: f64) -> f32 {
+        self.recent
+            .iter()
+            .filter(|(time, _)| now - time <= DPS_WINDOW)
+            .map(|(_, damage)| damage)
+            .sum::<f32>()
+            / DPS_WINDOW as f32
+    }
+
+    /// Share of shots that hit something (0.0 to 1.0)
+    pub fn accuracy(&self) -> f32 {
+        if self.shots == 0 {
+            return 0.0;
+        }
+        self.hits as f32 / self.shots as f32
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accuracy_and_rolling_dps() {
+        let mut stats = RangeStats::new();
+        stats.record_shot(30.0, 0.0);
+        stats.record_shot(0.0, 0.5);
+        stats.record_shot(60.0, 2.0);
+        stats.record_shot(30.0, 2.5);
+
+        assert_eq!(stats.accuracy(), 0.75);
+        assert_eq!(stats.total_damage, 120.0);
+        assert_eq!(stats.best_hit, 60.0);
+        assert_eq!(stats.dps(3.0), 40.0);
+
+        // The first hit drops out of the window
+        assert_eq!(stats.dps(3.5), 30.0);
+    }
+}
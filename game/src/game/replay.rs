@@ -0,0 +1,118 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+
+/// Snapshots recorded per second
+pub const REPLAY_TICK_RATE: f64 = 20.0;
+
+/// Seconds kept in the rolling buffer ("save last 15 seconds")
+pub const CLIP_DURATION: f64 = 15.0;
+
+/// Format version of exported replay files (bump when fields change)
+pub const REPLAY_VERSION: u32 = 1;
+
+/// One player in a replay snapshot
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplayPlayer {
+    pub authority: String,
+    pub username: String,
+    /// "1" = team A, "2" = team B
+    pub team: String,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub health: f32,
+    pub alive: bool,
+}
+
+/// Every player's pose at one moment
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplayFrame {
+    /// Local time in seconds (rebased to the clip start on export)
+    pub t: f64,
+    pub players: Vec<ReplayPlayer>,
+}
+
+/// Rolling replay buffer: keeps the last `CLIP_DURATION` seconds of snapshots
+/// so a highlight can be saved after it happened
+#[derive(Debug, Clone, Default)]
+pub struct ReplayRecorder {
+    frames: VecDeque<ReplayFrame>,
+}
+
+impl ReplayRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Whether the next snapshot is due
+    pub fn frame_due(&self, now: f64) -> bool {
+        self.frames.back().map_or(true, |last| now - last.t >= 1.0 / REPLAY_TICK_RATE)
+    }
+
+    /// Add a snapshot and drop the ones older than the clip window
+    pub fn record(&mut self, now: f64, players: Vec<ReplayPlayer>) {
+        self.frames.push_back(ReplayFrame { t: now, players });
+        while self.frames.front().is_some_and(|frame| now - frame.t > CLIP_DURATION) {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Seconds currently buffered
+    pub fn duration(&self) -> f64 {
+        match (self.frames.front(), self.frames.back()) {
+            (Some(first), Some(last)) => last.t - first.t,
+            _ => 0.0,
+        }
+    }
+
+    /// Buffered clip as a replay file: `{ version, game, map, tick_rate, duration, frames: [...] }`
+    pub fn export(&self, game: &str, map: &str) -> String {
+        let start = self.frames.front().map_or(0.0, |frame| frame.t);
+        let frames: Vec<ReplayFrame> = self.frames.iter()
+            .map(|frame| ReplayFrame { t: frame.t - start, players: frame.players.clone() })
+            .collect();
+        serde_json::json!({
+            "version": REPLAY_VERSION,
+            "game": game,
+            "map": map,
+            "tick_rate": REPLAY_TICK_RATE,
+            "duration": self.duration(),
+            "frames": frames,
+        })
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_keeps_only_the_clip_window() {
+        let mut recorder = ReplayRecorder::new();
+        let mut now = 0.0;
+        while now <= 40.0 {
+            if recorder.frame_due(now) {
+                recorder.record(now, Vec::new());
+            }
+            now += 0.01;
+        }
+
+        assert!(recorder.duration() <= CLIP_DURATION);
+        assert!(recorder.duration() > CLIP_DURATION - 0.1);
+        assert!(!recorder.frame_due(40.0));
+
+        let replay: serde_json::Value = serde_json::from_str(&recorder.export("game", "map")).unwrap();
+        assert_eq!(replay["frames"][0]["t"], 0.0);
+    }
+}
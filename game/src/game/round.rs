@@ -0,0 +1,213 @@
+/// Phase of the current round
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoundPhase {
+    /// Round-start freeze: movement and shooting locked while the countdown runs
+    Freeze {
+        /// Seconds until the round goes live
+        remaining: f32,
+    },
+    /// Normal gameplay
+    Live,
+    /// Voted timeout: everything frozen, then a short freeze before going live again
+    Timeout {
+        /// Seconds until the timeout is over
+        remaining: f32,
+    },
+    /// Round-end presentation (slow-mo, camera flourish, banner)
+    Ending {
+        /// Real (unscaled) seconds since the round ended
+        elapsed: f32,
+        /// Winning team (1 = Team A / Blue, 2 = Team B / Red)
+        winning_team: u8,
+    },
+    /// Presentation finished, waiting for the results screen
+    Ended {
+        winning_team: u8,
+    },
+}
+
+/// Something the round state machine wants announced
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoundEvent {
+    /// The freeze countdown reached a new whole second (3, 2, 1)
+    CountdownTick(u32),
+    /// Freeze time is over, the round is live
+    FreezeEnded,
+    /// A voted timeout ran out (the resume freeze starts)
+    TimeoutEnded,
+    /// The round-end presentation finished
+    PresentationDone,
+}
+
+/// Round state machine driving the freeze countdown and end-of-round presentation
+#[derive(Debug, Clone)]
+pub struct RoundState {
+    pub phase: RoundPhase,
+}
+
+/// Total length of the round-end presentation (real seconds)
+pub const ROUND_END_DURATION: f32 = 3.0;
+
+/// Length of the slow-motion part of the presentation (real seconds)
+const SLOW_MO_DURATION: f32 = 1.5;
+
+/// Time scale at the start of the slow-motion
+const SLOW_MO_SCALE: f32 = 0.2;
+
+/// Freeze countdown after a timeout so nobody is caught off guard (seconds)
+pub const TIMEOUT_RESUME_FREEZE: f32 = 3.0;
+
+impl RoundState {
+    pub fn new() -> Self {
+        Self { phase: RoundPhase::Live }
+    }
+
+    /// Begin the round with a freeze period (a zero duration goes straight to live)
+    pub fn start_freeze(&mut self, duration: f32) {
+        self.phase = if duration > 0.0 { RoundPhase::Freeze { remaining: duration } } else { RoundPhase::Live };
+    }
+
+    /// Correct the freeze countdown from the match state (ignored outside of freeze)
+    pub fn sync_freeze(&mut self, remaining: f32) {
+        if let RoundPhase::Freeze { .. } = self.phase {
+            self.phase = if remaining > 0.0 { RoundPhase::Freeze { remaining } } else { RoundPhase::Live };
+        }
+    }
+
+    /// Pause the round for a voted timeout (ignored once the round has ended)
+    pub fn start_timeout(&mut self, duration: f32) -> bool {
+        if !self.in_progress() {
+            return false;
+        }
+        self.phase = RoundPhase::Timeout { remaining: duration };
+        true
+    }
+
+    /// Start the round-end presentation (ignored if already ending)
+    pub fn end_round(&mut self, winning_team: u8) {
+        if self.in_progress() {
+            self.phase = RoundPhase::Ending { elapsed: 0.0, winning_team };
+        }
+    }
+
+    /// Advance with real (unscaled) delta, returning anything that should be announced
+    pub fn update(&mut self, real_delta: f32) -> Option<RoundEvent> {
+        match self.phase {
+            RoundPhase::Freeze { remaining } => {
+                let next = remaining - real_delta;
+                if next <= 0.0 {
+                    self.phase = RoundPhase::Live;
+                    return Some(RoundEvent::FreezeEnded);
+                }
+                self.phase = RoundPhase::Freeze { remaining: next };
+                (next.ceil() < remaining.ceil()).then(|| RoundEvent::CountdownTick(next.ceil() as u32))
+            }
+            RoundPhase::Timeout { remaining } => {
+                let next = remaining - real_delta;
+                if next <= 0.0 {
+                    self.phase = RoundPhase::Freeze { remaining: TIMEOUT_RESUME_FREEZE };
+                    return Some(RoundEvent::TimeoutEnded);
+                }
+                self.phase = RoundPhase::Timeout { remaining: next };
+                None
+            }
+            RoundPhase::Ending { elapsed, winning_team } => {
+                let elapsed = elapsed + real_delta;
+                if elapsed >= ROUND_END_DURATION {
+                    self.phase = RoundPhase::Ended { winning_team };
+                    return Some(RoundEvent::PresentationDone);
+                }
+                self.phase = RoundPhase::Ending { elapsed, winning_team };
+                None
+            }
+            RoundPhase::Live | RoundPhase::Ended { .. } => None,
+        }
+    }
+
+    /// Multiplier applied to the simulation delta (slow-mo ramps back up to 1.0)
+    pub fn time_scale(&self) -> f32 {
+        match self.phase {
+            RoundPhase::Ending { elapsed, .. } if elapsed < SLOW_MO_DURATION => {
+                let t = elapsed / SLOW_MO_DURATION;
+                SLOW_MO_SCALE + (1.0 - SLOW_MO_SCALE) * t * t
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// 0.0 to 1.0 progress through the presentation (None outside of it)
+    pub fn presentation_progress(&self) -> Option<f32> {
+        match self.phase {
+            RoundPhase::Ending { elapsed, .. } => Some((elapsed / ROUND_END_DURATION).min(1.0)),
+            RoundPhase::Ended { .. } => Some(1.0),
+            RoundPhase::Freeze { .. } | RoundPhase::Live | RoundPhase::Timeout { .. } => None,
+        }
+    }
+
+    /// Whether player input should be processed
+    pub fn is_live(&self) -> bool {
+        self.phase == RoundPhase::Live
+    }
+
+    /// Whether the round hasn't ended yet (freeze, live or timeout)
+    pub fn in_progress(&self) -> bool {
+        matches!(self.phase, RoundPhase::Freeze { .. } | RoundPhase::Live | RoundPhase::Timeout { .. })
+    }
+
+    /// Whole seconds left on the freeze countdown (None outside of freeze)
+    pub fn countdown(&self) -> Option<u32> {
+        match self.phase {
+            RoundPhase::Freeze { remaining } => Some(remaining.ceil() as u32),
+            _ => None,
+        }
+    }
+
+    /// Seconds left in a voted timeout (None outside of one)
+    pub fn timeout_remaining(&self) -> Option<f32> {
+        match self.phase {
+            RoundPhase::Timeout { remaining } => Some(remaining),
+            _ => None,
+        }
+    }
+
+    /// Winning team once the round has ended
+    pub fn winning_team(&self) -> Option<u8> {
+        match self.phase {
+            RoundPhase::Ending { winning_team, .. } | RoundPhase::Ended { winning_team } => Some(winning_team),
+            RoundPhase::Freeze { .. } | RoundPhase::Live | RoundPhase::Timeout { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_end_slow_mo_and_finish() {
+        let mut round = RoundState::new();
+        assert_eq!(round.time_scale(), 1.0);
+
+        round.end_round(1);
+        assert!(round.time_scale() < 0.5);
+        assert_eq!(round.update(1.0), None);
+        assert_eq!(round.update(ROUND_END_DURATION), Some(RoundEvent::PresentationDone));
+        assert_eq!(round.phase, RoundPhase::Ended { winning_team: 1 });
+        assert_eq!(round.time_scale(), 1.0);
+    }
+
+    #[test]
+    fn test_freeze_counts_down_then_goes_live() {
+        let mut round = RoundState::new();
+        round.start_freeze(3.0);
+        assert!(!round.is_live());
+        assert_eq!(round.countdown(), Some(3));
+
+        assert_eq!(round.update(0.5), None);
+        assert_eq!(round.update(0.6), Some(RoundEvent::CountdownTick(2)));
+        round.sync_freeze(0.4);
+        assert_eq!(round.countdown(), Some(1));
+        assert_eq!(round.update(0.5), Some(RoundEvent::FreezeEnded));
+        assert!(round.is_live());
+    }
+}
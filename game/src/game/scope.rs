@@ -0,0 +1,165 @@
+/// Field of view of the unscoped first-person camera (degrees)
+pub const BASE_FOV: f32 = 70.0;
+
+/// Time to go from hip to fully scoped (and back)
+const SCOPE_IN_TIME: f32 = 0.15;
+
+/// How long the player can hold their breath (seconds)
+const BREATH_CAPACITY: f32 = 4.0;
+
+/// Breath regained per second while breathing normally
+const BREATH_RECOVERY_RATE: f32 = 0.5;
+
+/// Breath needed before holding is allowed again after running out
+const BREATH_EXHAUSTED_THRESHOLD: f32 = 0.5;
+
+/// Peak scope sway while breathing normally (degrees)
+const SWAY_AMPLITUDE: f32 = 0.9;
+
+/// Sway multiplier while holding breath
+const STEADY_SWAY_MULTIPLIER: f32 = 0.1;
+
+/// Sway multiplier while out of breath
+const EXHAUSTED_SWAY_MULTIPLIER: f32 = 2.5;
+
+/// Scope zoom, sway and breath-hold state for scoped weapons
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopeState {
+    /// 0.0 = hip, 1.0 = fully scoped
+    zoom: f32,
+    /// Seconds of breath left
+    breath: f32,
+    /// Breath ran out; holding is disabled until it recovers
+    exhausted: bool,
+    holding_breath: bool,
+    /// Phase of the sway pattern (seconds)
+    sway_time: f32,
+}
+
+impl Default for ScopeState {
+    fn default() -> Self {
+        Self {
+            zoom: 0.0,
+            breath: BREATH_CAPACITY,
+            exhausted: false,
+            holding_breath: false,
+            sway_time: 0.0,
+        }
+    }
+}
+
+impl ScopeState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance zoom, breath and sway
+    pub fn update(&mut self, delta: f32, wants_scope: bool, wants_hold_breath: bool) {
+        let zoom_step = delta / SCOPE_IN_TIME;
+        self.zoom = if wants_scope { (self.zoom + zoom_step).min(1.0) } else { (self.zoom - zoom_step).max(0.0) };
+
+        self.holding_breath = wants_hold_breath && self.is_scoped() && !self.exhausted && self.breath > 0.0;
+        if self.holding_breath {
+            self.breath = (self.breath - delta).max(0.0);
+            if self.breath == 0.0 {
+                self.exhausted = true;
+                self.holding_breath = false;
+            }
+        } else {
+            self.breath = (self.breath + BREATH_RECOVERY_RATE * delta).min(BREATH_CAPACITY);
+            if self.exhausted && self.breath_fraction() >= BREATH_EXHAUSTED_THRESHOLD {
+                self.exhausted = false;
+            }
+        }
+
+        // The sway pattern slows down (but keeps its phase) while steadied
+        let sway_speed = if self.holding_breath { 0.2 } else { 1.0 };
+        self.sway_time += delta * sway_speed;
+    }
+
+    /// Drop out of the scope immediately (weapon switch, death)
+    pub fn reset(&mut self) {
+        self.zoom = 0.0;
+        self.holding_breath = false;
+    }
+
+    /// Whether the scope overlay should be shown
+    pub fn is_scoped(&self) -> bool {
+        self.zoom >= 1.0
+    }
+
+    /// Camera field of view for a weapon whose scope zooms to `scoped_fov`
+    pub fn fov(&self, scoped_fov: f32) -> f32 {
+        BASE_FOV + (scoped_fov - BASE_FOV) * self.zoom
+    }
+
+    /// Current aim offset from sway as (yaw, pitch) in degrees
+    pub fn sway(&self) -> (f32, f32) {
+        let multiplier = if self.holding_breath {
+            STEADY_SWAY_MULTIPLIER
+        } else if self.exhausted {
+            EXHAUSTED_SWAY_MULTIPLIER
+        } else {
+            1.0
+        };
+        let amplitude = SWAY_AMPLITUDE * multiplier * self.zoom;
+
+        // Slow figure-eight, like a rifle drifting with each breath
+        let t = self.sway_time;
+        ((t * 0.9).sin() * amplitude, (t * 1.8).sin() * amplitude * 0.5)
+    }
+
+    /// Breath left, 0.0 to 1.0 (for the HUD meter)
+    pub fn breath_fraction(&self) -> f32 {
+        self.breath / BREATH_CAPACITY
+    }
+
+    pub fn is_holding_breath(&self) -> bool {
+        self.holding_breath
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scoped() -> ScopeState {
+        let mut scope = ScopeState::new();
+        scope.update(1.0, true, false);
+        scope.sway_time = 1.0;
+        scope
+    }
+
+    #[test]
+    fn test_holding_breath_steadies_until_exhausted() {
+        let mut scope = scoped();
+        let (normal_yaw, _) = scope.sway();
+
+        scope.update(0.0, true, true);
+        assert!(scope.is_holding_breath());
+        assert!(scope.sway().0.abs() < normal_yaw.abs());
+
+        // Hold past the capacity: breath runs out and sway gets worse than normal
+        for _ in 0..50 {
+            scope.update(0.1, true, true);
+        }
+        assert!(scope.is_exhausted());
+        assert!(!scope.is_holding_breath());
+        scope.sway_time = 1.0;
+        assert!(scope.sway().0.abs() > normal_yaw.abs());
+    }
+
+    #[test]
+    fn test_fov_follows_zoom() {
+        let mut scope = ScopeState::new();
+        assert_eq!(scope.fov(15.0), BASE_FOV);
+        scope.update(1.0, true, false);
+        assert_eq!(scope.fov(15.0), 15.0);
+        scope.reset();
+        assert!(!scope.is_scoped());
+    }
+}
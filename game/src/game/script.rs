@@ -0,0 +1,108 @@
+use crate::map::{MapScript, ScriptCommand, ScriptStep, ScriptWhen};
+
+/// A script command that came due
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptFire {
+    pub command: ScriptCommand,
+    /// Match time (seconds) the command was due at
+    pub at: f32,
+    /// Index of the rule it came from
+    pub rule: usize,
+    /// Started by the match clock (the same on every client) rather than by the local player's trigger
+    pub timed: bool,
+}
+
+/// Runs a map script against match time
+///
+/// Timed rules depend only on the clock, so every client fires them at the same match time
+/// and a late joiner catches up on what it missed (the latest `every` instance, not all of them)
+#[derive(Debug, Default)]
+pub struct ScriptRunner {
+    /// Match time of the last update
+    clock: Option<f32>,
+    /// Commands waiting out a `wait`
+    pending: Vec<ScriptFire>,
+}
+
+impl ScriptRunner {
+    /// Advance to match time `now`: the commands that came due, in order
+    pub fn update(&mut self, script: &MapScript, now: f32) -> Vec<ScriptFire> {
+        let since = self.clock.unwrap_or(f32::NEG_INFINITY);
+        if now < since {
+            return Vec::new();
+        }
+        for (index, rule) in script.rules.iter().enumerate() {
+            let start = match rule.when {
+                ScriptWhen::After(delay) => delay,
+                // Only the latest round: the first one is a full period in
+                ScriptWhen::Every(period) if now >= period => (now / period).floor() * period,
+                ScriptWhen::Every(_) | ScriptWhen::On(_) => continue,
+            };
+            if start > since && start <= now {
+                self.schedule(script, index, start, true);
+            }
+        }
+        self.clock = Some(now);
+
+        self.pending.sort_by(|a, b| a.at.total_cmp(&b.at).then(a.rule.cmp(&b.rule)));
+        let due = self.pending.partition_point(|fire| fire.at <= now);
+        self.pending.drain(..due).collect()
+    }
+
+    /// The local player entered a trigger tagged `tag`: queue the `on <tag>` rules
+    /// (they come out of the next `update`)
+    pub fn fire_tag(&mut self, script: &MapScript, tag: &str, now: f32) {
+        for (index, rule) in script.rules.iter().enumerate() {
+            if matches!(&rule.when, ScriptWhen::On(on) if on == tag) {
+                self.schedule(script, index, now, false);
+            }
+        }
+    }
+
+    /// Forget the clock and everything queued (new match)
+    pub fn reset(&mut self) {
+        self.clock = None;
+        self.pending.clear();
+    }
+
+    fn schedule(&mut self, script: &MapScript, rule: usize, start: f32, timed: bool) {
+        let mut at = start;
+        for step in &script.rules[rule].steps {
+            match step {
+                ScriptStep::Wait(seconds) => at += seconds,
+                ScriptStep::Run(command) => self.pending.push(ScriptFire { command: command.clone(), at, rule, timed }),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::TriggerAction;
+
+    #[test]
+    fn test_rules_fire_from_match_time_and_late_joiners_catch_up() {
+        let script = MapScript::parse("after 5s open door\nevery 10s sound pop; wait 2s; objective go\non lever open gate").unwrap();
+        let open = |tag: &str| ScriptCommand::Action(TriggerAction::OpenMover(tag.to_string()));
+        let summary = |fires: Vec<ScriptFire>| fires.into_iter().map(|f| (f.at, f.rule)).collect::<Vec<_>>();
+
+        let mut runner = ScriptRunner::default();
+        assert!(runner.update(&script, 0.0).is_empty());
+        assert!(runner.update(&script, 4.9).is_empty());
+        let fires = runner.update(&script, 5.0);
+        assert_eq!(fires.len(), 1);
+        assert_eq!((fires[0].command.clone(), fires[0].timed), (open("door"), true));
+        assert_eq!(summary(runner.update(&script, 10.5)), vec![(10.0, 1)]);
+        assert_eq!(summary(runner.update(&script, 12.0)), vec![(12.0, 1)]);
+
+        runner.fire_tag(&script, "lever", 12.5);
+        runner.fire_tag(&script, "door", 12.5);
+        let fires = runner.update(&script, 12.5);
+        assert_eq!((fires.len(), fires[0].timed), (1, false));
+
+        // Joining at 25s: the door already opened, and only the 20s round of the repeating rule runs
+        let mut late = ScriptRunner::default();
+        assert_eq!(summary(late.update(&script, 25.0)), vec![(5.0, 0), (20.0, 1), (22.0, 1)]);
+    }
+}
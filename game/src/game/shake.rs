@@ -0,0 +1,111 @@
+/// Largest camera rotation at full trauma (degrees)
+const MAX_YAW: f32 = 4.0;
+const MAX_PITCH: f32 = 4.0;
+const MAX_ROLL: f32 = 5.0;
+
+/// Gameplay events that shake the camera
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShakeSource {
+    /// Firing a heavy weapon (see `WeaponKind::fire_shake`)
+    Fire,
+    /// A grenade bursting nearby
+    Blast,
+    /// Taking damage
+    Damage,
+}
+
+impl ShakeSource {
+    /// Trauma added at full strength
+    fn magnitude(&self) -> f32 {
+        match self {
+            ShakeSource::Fire => 0.5,
+            ShakeSource::Blast => 0.9,
+            ShakeSource::Damage => 0.6,
+        }
+    }
+
+    /// Trauma worn off per second: shots are a jolt, blasts rumble on
+    fn decay(&self) -> f32 {
+        match self {
+            ShakeSource::Fire => 2.5,
+            ShakeSource::Blast => 1.0,
+            ShakeSource::Damage => 1.8,
+        }
+    }
+}
+
+/// Trauma-based screen shake: events add trauma that wears off at their own rate,
+/// and the camera wobbles by trauma squared so small kicks stay subtle
+#[derive(Debug, Clone, Default)]
+pub struct ScreenShake {
+    /// Active kicks and the trauma each has left
+    kicks: Vec<(ShakeSource, f32)>,
+    /// Clock driving the wobble
+    time: f32,
+}
+
+impl ScreenShake {
+    /// Kick the camera; `strength` (0 - 1) scales the source's magnitude
+    pub fn add(&mut self, source: ShakeSource, strength: f32) {
+        let trauma = source.magnitude() * strength.clamp(0.0, 1.0);
+        if trauma > 0.0 {
+            self.kicks.push((source, trauma));
+        }
+    }
+
+    pub fn update(&mut self, delta: f32) {
+        self.time += delta;
+        for (source, trauma) in &mut self.kicks {
+            *trauma -= source.decay() * delta;
+        }
+        self.kicks.retain(|(_, trauma)| *trauma > 0.0);
+    }
+
+    /// Combined trauma of every active kick (0 - 1)
+    pub fn trauma(&self) -> f32 {
+        self.kicks.iter().map(|(_, trauma)| trauma).sum::<f32>().min(1.0)
+    }
+
+    /// Camera rotation this frame as (yaw, pitch, roll) degrees, scaled by `multiplier`
+    /// (the screen-shake comfort setting)
+    pub fn offset(&self, multiplier: f32) -> (f32, f32, f32) {
+        let shake = self.trauma().powi(2) * multiplier;
+        if shake <= 0.0 {
+            return (0.0, 0.0, 0.0);
+        }
+        // Two detuned sines per axis: smooth, but doesn't read as a regular rhythm
+        let wobble = |frequency: f32, phase: f32| {
+            ((self.time * frequency + phase).sin() + 0.5 * (self.time * frequency * 2.3 + phase * 1.7).sin()) / 1.5
+        };
+        (
+            MAX_YAW * shake * wobble(23.0, 0.0),
+            MAX_PITCH * shake * wobble(29.0, 1.3),
+            MAX_ROLL * shake * wobble(17.0, 2.6),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kicks_wear_off_at_their_own_rate_and_comfort_can_mute_them() {
+        let mut shake = ScreenShake::default();
+        shake.add(ShakeSource::Fire, 1.0);
+        shake.add(ShakeSource::Blast, 1.0);
+        assert_eq!(shake.trauma(), 1.0);
+
+        // The shot's jolt is gone after a quarter second, the blast still rumbles
+        shake.update(0.25);
+        assert_eq!(shake.kicks.len(), 1);
+        assert!((shake.trauma() - 0.65).abs() < 1e-5);
+        assert_ne!(shake.offset(1.0), (0.0, 0.0, 0.0));
+        assert_eq!(shake.offset(0.0), (0.0, 0.0, 0.0));
+
+        shake.update(1.0);
+        assert_eq!(shake.trauma(), 0.0);
+        shake.add(ShakeSource::Damage, 0.0);
+        assert!(shake.kicks.is_empty());
+    }
+}
@@ -0,0 +1,255 @@
+use raylib::prelude::*;
+
+/// Initial speed of a thrown grenade (units per second)
+pub const THROW_SPEED: f32 = 14.0;
+
+/// Downward acceleration applied to grenades in flight
+const GRAVITY: f32 = 9.81;
+
+/// Fixed simulation step for grenade flight (identical on every client)
+const FLIGHT_STEP: f32 = 1.0 / 60.0;
+
+/// Grenades detonate on landing or after this long in the air (seconds)
+pub const FUSE_TIME: f32 = 2.0;
+
+/// Fully grown smoke radius (units)
+pub const SMOKE_RADIUS: f32 = 4.5;
+
+/// Time for the cloud to reach full size after detonation (seconds)
+const SMOKE_EXPAND_TIME: f32 = 2.0;
+
+/// How long a cloud blocks vision after detonation (seconds)
+pub const SMOKE_DURATION: f32 = 15.0;
+
+/// Final part of the duration over which the cloud thins out (seconds)
+const SMOKE_FADE_TIME: f32 = 2.5;
+
+/// Billboards per cloud
+const PUFF_COUNT: usize = 28;
+
+/// A grenade throw as broadcast to every client
+///
+/// Everything else about the grenade (flight, detonation point, cloud growth)
+/// is simulated deterministically from these values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThrowEvent {
+    pub thrower: String,
+    pub origin: Vector3,
+    /// Normalized throw direction
+    pub direction: Vector3,
+    /// Local time (seconds, `emscripten_get_now` timebase) the grenade left the hand
+    pub thrown_at: f64,
+}
+
+/// Where and when a grenade thrown with `event` detonates
+///
+/// `blocked` is the map's line-of-sight test; the grenade stops at the last
+/// free point before hitting geometry.
+pub fn simulate_flight(event: &ThrowEvent, blocked: impl Fn(Vector3, Vector3) -> bool) -> (Vector3, f32) {
+    let mut position = event.origin;
+    let mut velocity = event.direction * THROW_SPEED;
+    let mut time = 0.0;
+
+    while time < FUSE_TIME {
+        velocity.y -= GRAVITY * FLIGHT_STEP;
+        let next = position + velocity * FLIGHT_STEP;
+        time += FLIGHT_STEP;
+
+        if next.y <= 0.0 {
+            return (Vector3::new(next.x, 0.0, next.z), time);
+        }
+        if blocked(position, next) {
+            return (position, time);
+        }
+        position = next;
+    }
+
+    (position, FUSE_TIME)
+}
+
+/// One camera-facing puff of a smoke cloud
+#[derive(Debug, Clone, Copy)]
+pub struct SmokePuff {
+    pub position: Vector3,
+    pub size: f32,
+    /// 0.0 to 1.0
+    pub opacity: f32,
+}
+
+/// A smoke grenade (in flight or detonated)
+#[derive(Debug, Clone)]
+pub struct SmokeGrenade {
+    pub thrower: String,
+    pub detonation_point: Vector3,
+    /// Local time the cloud starts growing
+    pub detonates_at: f64,
+    /// Fixed puff offsets (unit sphere), derived from the throw so every client draws the same cloud
+    puff_offsets: Vec<Vector3>,
+}
+
+impl SmokeGrenade {
+    pub fn from_throw(event: &ThrowEvent, blocked: impl Fn(Vector3, Vector3) -> bool) -> Self {
+        let (detonation_point, flight_time) = simulate_flight(event, blocked);
+
+        // Deterministic pseudo-random layout seeded from the throw
+        let mut seed = event.thrown_at.to_bits() ^ event.origin.x.to_bits() as u64;
+        for byte in event.thrower.bytes() {
+            seed = seed.wrapping_mul(31).wrapping_add(byte as u64);
+        }
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % 10_000) as f32 / 10_000.0
+        };
+        let puff_offsets = (0..PUFF_COUNT)
+            .map(|_| {
+                let angle = next() * std::f32::consts::TAU;
+                let distance = next().sqrt() * 0.75;
+                // Flattened so the cloud hugs the ground
+                Vector3::new(angle.cos() * distance, next() * 0.6, angle.sin() * distance)
+            })
+            .collect();
+
+        Self {
+            thrower: event.thrower.clone(),
+            detonation_point,
+            detonates_at: event.thrown_at + flight_time as f64,
+            puff_offsets,
+        }
+    }
+
+    /// Cloud radius at `now` (0 before detonation and after it has dissipated)
+    pub fn radius_at(&self, now: f64) -> f32 {
+        let age = (now - self.detonates_at) as f32;
+        if !(0.0..SMOKE_DURATION).contains(&age) {
+            return 0.0;
+        }
+        // Ease-out growth
+        let growth = (age / SMOKE_EXPAND_TIME).min(1.0);
+        SMOKE_RADIUS * (1.0 - (1.0 - growth).powi(2))
+    }
+
+    /// Overall density at `now` (fades out at the end of the duration)
+    pub fn density_at(&self, now: f64) -> f32 {
+        let remaining = SMOKE_DURATION - (now - self.detonates_at) as f32;
+        if self.radius_at(now) <= 0.0 {
+            return 0.0;
+        }
+        (remaining / SMOKE_FADE_TIME).clamp(0.0, 1.0)
+    }
+
+    /// Whether the cloud has fully dissipated
+    pub fn is_finished(&self, now: f64) -> bool {
+        (now - self.detonates_at) as f32 >= SMOKE_DURATION
+    }
+
+    /// Whether a sight line passes through the (dense enough) cloud
+    pub fn blocks_segment(&self, from: Vector3, to: Vector3, now: f64) -> bool {
+        if self.density_at(now) < 0.35 {
+            return false;
+        }
+        let radius = self.radius_at(now) * 0.9; // Thin edges don't block
+        let center = self.detonation_point + Vector3::new(0.0, radius * 0.4, 0.0);
+        segment_distance_to_point(from, to, center) < radius
+    }
+
+    /// Billboards to draw at `now`
+    pub fn puffs(&self, now: f64) -> Vec<SmokePuff> {
+        let radius = self.radius_at(now);
+        let density = self.density_at(now);
+        if radius <= 0.0 || density <= 0.0 {
+            return Vec::new();
+        }
+        self.puff_offsets
+            .iter()
+            .map(|offset| SmokePuff {
+                position: self.detonation_point + *offset * radius + Vector3::new(0.0, radius * 0.3, 0.0),
+                size: radius * 0.9,
+                opacity: density * 0.55,
+            })
+            .collect()
+    }
+}
+
+/// Shortest distance between a point and a line segment
+fn segment_distance_to_point(from: Vector3, to: Vector3, point: Vector3) -> f32 {
+    let segment = to - from;
+    let length_sq = segment.dot(segment);
+    let t = if length_sq > 0.0 { ((point - from).dot(segment) / length_sq).clamp(0.0, 1.0) } else { 0.0 };
+    (from + segment * t - point).length()
+}
+
+/// All smoke clouds in the match
+///
+/// `blocks_line` is the single vision check for smoke: minimap spotting,
+/// nameplates (and any aim assist / bot sight checks) should all go through it.
+#[derive(Debug, Default)]
+pub struct SmokeField {
+    grenades: Vec<SmokeGrenade>,
+}
+
+impl SmokeField {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, grenade: SmokeGrenade) {
+        self.grenades.push(grenade);
+    }
+
+    pub fn clear(&mut self) {
+        self.grenades.clear();
+    }
+
+    /// Drop clouds that have dissipated
+    pub fn expire(&mut self, now: f64) {
+        self.grenades.retain(|g| !g.is_finished(now));
+    }
+
+    /// Whether any cloud blocks the line between two points
+    pub fn blocks_line(&self, from: Vector3, to: Vector3, now: f64) -> bool {
+        self.grenades.iter().any(|g| g.blocks_segment(from, to, now))
+    }
+
+    pub fn grenades(&self) -> &[SmokeGrenade] {
+        &self.grenades
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn throw() -> ThrowEvent {
+        ThrowEvent {
+            thrower: "player".to_string(),
+            origin: Vector3::new(0.0, 1.6, 0.0),
+            direction: Vector3::new(1.0, 0.3, 0.0).normalized(),
+            thrown_at: 100.0,
+        }
+    }
+
+    #[test]
+    fn test_flight_is_deterministic_and_lands() {
+        let open = |_: Vector3, _: Vector3| false;
+        let a = SmokeGrenade::from_throw(&throw(), open);
+        let b = SmokeGrenade::from_throw(&throw(), open);
+        assert_eq!(a.detonation_point, b.detonation_point);
+        assert_eq!(a.detonates_at, b.detonates_at);
+        assert_eq!(a.detonation_point.y, 0.0);
+        assert!(a.detonation_point.x > 5.0);
+    }
+
+    #[test]
+    fn test_cloud_blocks_sight_only_while_active() {
+        let grenade = SmokeGrenade::from_throw(&throw(), |_: Vector3, _: Vector3| false);
+        let center = grenade.detonation_point;
+        let from = center + Vector3::new(-10.0, 1.5, 0.0);
+        let to = center + Vector3::new(10.0, 1.5, 0.0);
+
+        assert!(!grenade.blocks_segment(from, to, grenade.detonates_at - 0.5));
+        assert!(grenade.blocks_segment(from, to, grenade.detonates_at + 5.0));
+        assert!(!grenade.blocks_segment(from, to, grenade.detonates_at + SMOKE_DURATION as f64 + 1.0));
+    }
+}
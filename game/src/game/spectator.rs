@@ -0,0 +1,249 @@
+use raylib::prelude::*;
+
+/// Free-fly movement speed (units per second, doubled with Shift)
+const FREE_FLY_SPEED: f32 = 10.0;
+
+/// Range and step of the caster-adjustable free-fly speed (mouse wheel or +/-)
+const FREE_FLY_SPEED_MIN: f32 = 2.0;
+const FREE_FLY_SPEED_MAX: f32 = 60.0;
+const FREE_FLY_SPEED_STEP: f32 = 1.25;
+
+/// Eye height above a player's feet for the POV camera
+const POV_EYE_HEIGHT: f32 = 1.6;
+
+/// Number keys that jump to a player's POV (1 = first player in the roster, ..., 0 = tenth)
+const POV_KEYS: [KeyboardKey; 10] = [
+    KeyboardKey::KEY_ONE,
+    KeyboardKey::KEY_TWO,
+    KeyboardKey::KEY_THREE,
+    KeyboardKey::KEY_FOUR,
+    KeyboardKey::KEY_FIVE,
+    KeyboardKey::KEY_SIX,
+    KeyboardKey::KEY_SEVEN,
+    KeyboardKey::KEY_EIGHT,
+    KeyboardKey::KEY_NINE,
+    KeyboardKey::KEY_ZERO,
+];
+
+/// Mouse look sensitivity in free-fly mode (degrees per pixel)
+const LOOK_SENSITIVITY: f32 = 0.15;
+
+/// Follow camera offset behind/above the target
+const FOLLOW_DISTANCE: f32 = 4.0;
+const FOLLOW_HEIGHT: f32 = 2.2;
+
+/// How quickly the follow camera catches up with its target
+const FOLLOW_SMOOTHING: f32 = 8.0;
+
+/// What the spectator camera is doing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpectatorMode {
+    /// Third-person chase camera on a player (index into the target list)
+    Follow { target: usize },
+    /// First-person view through a player's eyes (index into the target list)
+    Pov { target: usize },
+    /// Noclip camera driven by WASD + mouse
+    FreeFly,
+}
+
+/// A player the spectator can follow
+///
+/// Targets are listed in roster order (team A then team B, by name) and
+/// include dead players so the POV number keys stay the same all match.
+#[derive(Debug, Clone)]
+pub struct SpectatorTarget {
+    pub authority: String,
+    pub name: String,
+    /// "1" = team A, "2" = team B
+    pub team: String,
+    pub position: Vector3,
+    /// Yaw in radians
+    pub yaw: f32,
+    /// Pitch in radians
+    pub pitch: f32,
+    pub health: f32,
+    pub is_alive: bool,
+}
+
+/// Read-only camera for watching a live match (no player account involved)
+///
+/// Controls: left/right click (or Q/E) cycle players, number keys jump to a
+/// player's POV, Space switches to free-fly and F back to following; in
+/// free-fly WASD + mouse to fly, Shift to go faster, Space/Ctrl to move
+/// up/down and the mouse wheel (or +/-) to change speed.
+///
+/// Caster tools: N toggles nameplates and health for every player, H hides the
+/// spectator HUD for a clean feed.
+#[derive(Debug, Clone)]
+pub struct SpectatorCamera {
+    pub mode: SpectatorMode,
+    pub camera: Camera3D,
+    /// Free-fly speed (units per second, before the Shift boost)
+    pub free_fly_speed: f32,
+    /// Nameplates and health bars for all players, through walls
+    pub show_overlays: bool,
+    /// Spectator HUD (title, roster, controls)
+    pub show_hud: bool,
+    yaw: f32,
+    pitch: f32,
+}
+
+impl SpectatorCamera {
+    pub fn new() -> Self {
+        let position = Vector3::new(0.0, 20.0, -20.0);
+        Self {
+            mode: SpectatorMode::Follow { target: 0 },
+            camera: Camera3D::perspective(position, Vector3::zero(), Vector3::new(0.0, 1.0, 0.0), 70.0),
+            free_fly_speed: FREE_FLY_SPEED,
+            show_overlays: false,
+            show_hud: true,
+            yaw: 90.0,
+            pitch: -40.0,
+        }
+    }
+
+    /// Index of the followed / POV player, if any
+    pub fn target_index(&self) -> Option<usize> {
+        match self.mode {
+            SpectatorMode::Follow { target } | SpectatorMode::Pov { target } => Some(target),
+            SpectatorMode::FreeFly => None,
+        }
+    }
+
+    /// Name of the followed player (None in free-fly or with nobody to follow)
+    pub fn followed_name<'a>(&self, targets: &'a [SpectatorTarget]) -> Option<&'a str> {
+        self.target_index().and_then(|target| targets.get(target)).map(|t| t.name.as_str())
+    }
+
+    /// Player whose eyes we're looking through (their model is hidden)
+    pub fn pov_authority<'a>(&self, targets: &'a [SpectatorTarget]) -> Option<&'a str> {
+        match self.mode {
+            SpectatorMode::Pov { target } => targets.get(target).map(|t| t.authority.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Handle input and move the camera
+    pub fn update(&mut self, rl: &RaylibHandle, delta: f32, targets: &[SpectatorTarget]) {
+        if rl.is_key_pressed(KeyboardKey::KEY_N) {
+            self.show_overlays = !self.show_overlays;
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_H) {
+            self.show_hud = !self.show_hud;
+        }
+
+        if rl.is_key_pressed(KeyboardKey::KEY_SPACE) && self.target_index().is_some() {
+            self.mode = SpectatorMode::FreeFly;
+        } else if rl.is_key_pressed(KeyboardKey::KEY_F) {
+            let target = self.target_index().unwrap_or(0);
+            self.mode = SpectatorMode::Follow { target };
+        }
+
+        if let Some(target) = POV_KEYS.iter().position(|key| rl.is_key_pressed(*key)) {
+            if target < targets.len() {
+                self.mode = SpectatorMode::Pov { target };
+            }
+        }
+
+        let next = rl.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT) || rl.is_key_pressed(KeyboardKey::KEY_E);
+        let previous = rl.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_RIGHT) || rl.is_key_pressed(KeyboardKey::KEY_Q);
+        if next || previous {
+            if let Some(target) = next_living(targets, self.target_index(), next) {
+                // Cycling keeps the current view style (chase or POV)
+                self.mode = match self.mode {
+                    SpectatorMode::Pov { .. } => SpectatorMode::Pov { target },
+                    _ => SpectatorMode::Follow { target },
+                };
+            }
+        }
+
+        match self.mode {
+            SpectatorMode::Follow { target } => match targets.get(target) {
+                Some(followed) => self.follow(followed, delta),
+                None => self.mode = SpectatorMode::FreeFly,
+            },
+            SpectatorMode::Pov { target } => match targets.get(target) {
+                Some(viewed) => self.pov(viewed),
+                None => self.mode = SpectatorMode::FreeFly,
+            },
+            SpectatorMode::FreeFly => self.free_fly(rl, delta),
+        }
+    }
+
+    /// Smoothly chase a player from behind
+    fn follow(&mut self, target: &SpectatorTarget, delta: f32) {
+        let behind = Vector3::new(-target.yaw.cos(), 0.0, -target.yaw.sin()) * FOLLOW_DISTANCE;
+        let desired = target.position + behind + Vector3::new(0.0, FOLLOW_HEIGHT, 0.0);
+        let look_at = target.position + Vector3::new(0.0, 1.5, 0.0);
+
+        let t = (FOLLOW_SMOOTHING * delta).min(1.0);
+        self.camera.position = self.camera.position.lerp(desired, t);
+        self.camera.target = look_at;
+
+        // Keep free-fly orientation in sync so toggling doesn't snap
+        let dir = (look_at - self.camera.position).normalized();
+        self.yaw = dir.z.atan2(dir.x).to_degrees();
+        self.pitch = dir.y.asin().to_degrees();
+    }
+
+    /// Look through a player's eyes
+    fn pov(&mut self, target: &SpectatorTarget) {
+        let forward = Vector3::new(
+            target.yaw.cos() * target.pitch.cos(),
+            target.pitch.sin(),
+            target.yaw.sin() * target.pitch.cos(),
+        );
+        self.camera.position = target.position + Vector3::new(0.0, POV_EYE_HEIGHT, 0.0);
+        self.camera.target = self.camera.position + forward;
+
+        // Free-fly continues from this view
+        self.yaw = target.yaw.to_degrees();
+        self.pitch = target.pitch.to_degrees();
+    }
+
+    /// Noclip movement with mouse look
+    fn free_fly(&mut self, rl: &RaylibHandle, delta: f32) {
+        let mut speed_steps = rl.get_mouse_wheel_move();
+        if rl.is_key_pressed(KeyboardKey::KEY_EQUAL) || rl.is_key_pressed(KeyboardKey::KEY_KP_ADD) { speed_steps += 1.0; }
+        if rl.is_key_pressed(KeyboardKey::KEY_MINUS) || rl.is_key_pressed(KeyboardKey::KEY_KP_SUBTRACT) { speed_steps -= 1.0; }
+        if speed_steps != 0.0 {
+            self.free_fly_speed = (self.free_fly_speed * FREE_FLY_SPEED_STEP.powf(speed_steps))
+                .clamp(FREE_FLY_SPEED_MIN, FREE_FLY_SPEED_MAX);
+        }
+
+        let mouse = rl.get_mouse_delta();
+        self.yaw += mouse.x * LOOK_SENSITIVITY;
+        self.pitch = (self.pitch - mouse.y * LOOK_SENSITIVITY).clamp(-89.0, 89.0);
+
+        let (yaw, pitch) = (self.yaw.to_radians(), self.pitch.to_radians());
+        let forward = Vector3::new(yaw.cos() * pitch.cos(), pitch.sin(), yaw.sin() * pitch.cos());
+        let right = Vector3::new(-yaw.sin(), 0.0, yaw.cos());
+
+        let mut movement = Vector3::zero();
+        if rl.is_key_down(KeyboardKey::KEY_W) { movement += forward; }
+        if rl.is_key_down(KeyboardKey::KEY_S) { movement -= forward; }
+        if rl.is_key_down(KeyboardKey::KEY_D) { movement += right; }
+        if rl.is_key_down(KeyboardKey::KEY_A) { movement -= right; }
+        if rl.is_key_down(KeyboardKey::KEY_SPACE) { movement.y += 1.0; }
+        if rl.is_key_down(KeyboardKey::KEY_LEFT_CONTROL) { movement.y -= 1.0; }
+
+        let speed = if rl.is_key_down(KeyboardKey::KEY_LEFT_SHIFT) { self.free_fly_speed * 2.0 } else { self.free_fly_speed };
+        if movement.length() > 0.0 {
+            self.camera.position += movement.normalized() * speed * delta;
+        }
+        self.camera.target = self.camera.position + forward;
+    }
+}
+
+/// Next (or previous) living player after `current`, wrapping around
+fn next_living(targets: &[SpectatorTarget], current: Option<usize>, forward: bool) -> Option<usize> {
+    let count = targets.len();
+    if count == 0 {
+        return None;
+    }
+    // From free-fly, "next" starts at the first player and "previous" at the last
+    let start = current.unwrap_or(if forward { count - 1 } else { 0 });
+    (1..=count)
+        .map(|step| if forward { (start + step) % count } else { (start + count * 2 - step) % count })
+        .find(|&index| targets[index].is_alive)
+}
@@ -0,0 +1,111 @@
+use std::f32::consts::{PI, TAU};
+use raylib::prelude::*;
+
+/// Compass sectors (22.5° each)
+pub const THREAT_SECTORS: usize = 16;
+
+/// Seconds for a threat to fade to about a third
+const THREAT_FADE_TIME: f32 = 2.0;
+
+/// Share of a threat the two neighbouring sectors get, so it reads as a soft wedge
+const NEIGHBOR_SHARE: f32 = 0.4;
+
+/// Levels below this count as faded out
+const THREAT_EPSILON: f32 = 0.01;
+
+/// What a compass entry was
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreatKind {
+    /// Something hurt the player from there
+    Damage,
+    /// An enemy fired from there
+    Gunfire,
+}
+
+/// Recent threat directions around the player, fading over a couple of seconds
+///
+/// Directions are kept as world bearings, so the ring turns with the player's view
+/// and an older threat still points where it came from
+#[derive(Debug, Clone, Default)]
+pub struct ThreatCompass {
+    damage: [f32; THREAT_SECTORS],
+    gunfire: [f32; THREAT_SECTORS],
+}
+
+/// World bearing (radians, same convention as yaw) from `from` towards `to` on the ground plane
+pub fn threat_bearing(from: Vector3, to: Vector3) -> f32 {
+    (to.z - from.z).atan2(to.x - from.x)
+}
+
+impl ThreatCompass {
+    /// Add a threat from world `bearing`; `weight` 1 lights its sector fully
+    pub fn add(&mut self, kind: ThreatKind, bearing: f32, weight: f32) {
+        let levels = match kind {
+            ThreatKind::Damage => &mut self.damage,
+            ThreatKind::Gunfire => &mut self.gunfire,
+        };
+        let step = TAU / THREAT_SECTORS as f32;
+        let sector = (bearing.rem_euclid(TAU) / step).round() as usize % THREAT_SECTORS;
+        for (offset, share) in [(0, 1.0), (1, NEIGHBOR_SHARE), (THREAT_SECTORS - 1, NEIGHBOR_SHARE)] {
+            let level = &mut levels[(sector + offset) % THREAT_SECTORS];
+            *level = (*level + weight.max(0.0) * share).min(1.0);
+        }
+    }
+
+    /// Fade every threat
+    pub fn update(&mut self, delta: f32) {
+        let fade = (-delta / THREAT_FADE_TIME).exp();
+        for level in self.damage.iter_mut().chain(self.gunfire.iter_mut()) {
+            *level = if *level * fade < THREAT_EPSILON { 0.0 } else { *level * fade };
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.damage.iter().chain(self.gunfire.iter()).all(|level| *level == 0.0)
+    }
+
+    /// (sector center relative to `yaw` in radians, damage level, gunfire level) for every sector;
+    /// positive angles are to the player's right, 0 straight ahead
+    pub fn sectors(&self, yaw: f32) -> impl Iterator<Item = (f32, f32, f32)> + '_ {
+        let step = TAU / THREAT_SECTORS as f32;
+        (0..THREAT_SECTORS).map(move |sector| {
+            let relative = (sector as f32 * step - yaw + PI).rem_euclid(TAU) - PI;
+            (relative, self.damage[sector], self.gunfire[sector])
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threats_point_relative_to_view_and_fade() {
+        let mut compass = ThreatCompass::default();
+        assert!(compass.is_empty());
+
+        // Facing +x, shot from +z: that's the player's right
+        let bearing = threat_bearing(Vector3::zero(), Vector3::new(0.0, 0.0, 10.0));
+        compass.add(ThreatKind::Damage, bearing, 0.8);
+        compass.add(ThreatKind::Gunfire, PI, 2.0);
+        let (angle, damage, gunfire) = compass.sectors(0.0).max_by(|a, b| a.1.total_cmp(&b.1)).unwrap();
+        assert!((angle - PI / 2.0).abs() < 1e-4);
+        assert_eq!((damage, gunfire), (0.8, 0.0));
+        assert_eq!(compass.sectors(0.0).filter(|s| s.1 > 0.0).count(), 3);
+
+        // Turning to face it puts it straight ahead; gunfire is capped
+        let (angle, _, _) = compass.sectors(PI / 2.0).max_by(|a, b| a.1.total_cmp(&b.1)).unwrap();
+        assert!(angle.abs() < 1e-4);
+        assert_eq!(compass.sectors(0.0).map(|s| s.2).fold(0.0, f32::max), 1.0);
+
+        compass.update(THREAT_FADE_TIME);
+        let strongest = compass.sectors(0.0).map(|s| s.1).fold(0.0, f32::max);
+        assert!((strongest - 0.8 / std::f32::consts::E).abs() < 1e-4);
+        compass.update(30.0);
+        assert!(compass.is_empty());
+    }
+}
@@ -0,0 +1,98 @@
+use raylib::prelude::*;
+
+/// An invisible axis-aligned box that fires when the player's feet are inside it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriggerVolume {
+    pub min: Vector3,
+    pub max: Vector3,
+}
+
+impl TriggerVolume {
+    /// Box of `size` centered on `center`
+    pub fn new(center: Vector3, size: Vector3) -> Self {
+        let half = size * 0.5;
+        Self { min: center - half, max: center + half }
+    }
+
+    pub fn contains(&self, point: Vector3) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    pub fn center(&self) -> Vector3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn size(&self) -> Vector3 {
+        self.max - self.min
+    }
+}
+
+/// Which trigger volumes the player is standing in, so each fires once per entry
+/// (one-shot triggers only the first time until `reset`)
+#[derive(Debug, Default)]
+pub struct TriggerTracker {
+    /// Triggers the player was inside last frame
+    inside: Vec<u16>,
+    /// One-shot triggers that already fired
+    spent: Vec<u16>,
+}
+
+impl TriggerTracker {
+    /// Triggers entered this frame out of every (id, volume, one-shot) with the player at `point`
+    pub fn update(&mut self, volumes: impl IntoIterator<Item = (u16, TriggerVolume, bool)>, point: Vector3) -> Vec<u16> {
+        let mut inside = Vec::new();
+        let mut entered = Vec::new();
+        for (id, volume, once) in volumes {
+            if !volume.contains(point) {
+                continue;
+            }
+            inside.push(id);
+            if self.inside.contains(&id) || self.spent.contains(&id) {
+                continue;
+            }
+            if once {
+                self.spent.push(id);
+            }
+            entered.push(id);
+        }
+        self.inside = inside;
+        entered
+    }
+
+    pub fn reset(&mut self) {
+        self.inside.clear();
+        self.spent.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains_is_inclusive() {
+        let volume = TriggerVolume::new(Vector3::new(5.0, 1.0, 0.0), Vector3::new(2.0, 2.0, 2.0));
+        assert!(volume.contains(Vector3::new(5.0, 0.0, 0.0)));
+        assert!(volume.contains(Vector3::new(6.0, 2.0, 1.0)));
+        assert!(!volume.contains(Vector3::new(6.1, 1.0, 0.0)));
+        assert_eq!(volume.center(), Vector3::new(5.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_triggers_fire_on_entry_and_one_shots_stay_spent() {
+        let volume = TriggerVolume::new(Vector3::zero(), Vector3::new(2.0, 2.0, 2.0));
+        let volumes = [(0, volume, false), (1, volume, true)];
+        let (inside, outside) = (Vector3::zero(), Vector3::new(5.0, 0.0, 0.0));
+        let mut tracker = TriggerTracker::default();
+
+        assert_eq!(tracker.update(volumes, inside), vec![0, 1]);
+        assert!(tracker.update(volumes, inside).is_empty());
+        assert!(tracker.update(volumes, outside).is_empty());
+        assert_eq!(tracker.update(volumes, inside), vec![0]);
+
+        tracker.reset();
+        assert_eq!(tracker.update(volumes, inside), vec![0, 1]);
+    }
+}
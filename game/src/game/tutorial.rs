@@ -0,0 +1,192 @@
+use crate::i18n::t;
+
+/// Total turning (degrees of yaw) needed to finish the look step
+const LOOK_REQUIRED: f32 = 270.0;
+
+/// Seconds the player has to hold the objective zone
+pub const OBJECTIVE_HOLD_TIME: f32 = 3.0;
+
+/// Steps of the onboarding mission, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialStep {
+    /// Look around with the mouse
+    Look,
+    /// Walk into the marked zone
+    Move,
+    /// Take down the target bots
+    Shoot,
+    /// Reload the weapon
+    Reload,
+    /// How wallets, session keys and transactions work in a real match
+    Session,
+    /// Clear the patrolling bot and hold the objective zone
+    Objective,
+    /// All done
+    Complete,
+}
+
+impl TutorialStep {
+    /// Step after this one (Complete stays Complete)
+    pub fn next(self) -> TutorialStep {
+        match self {
+            TutorialStep::Look => TutorialStep::Move,
+            TutorialStep::Move => TutorialStep::Shoot,
+            TutorialStep::Shoot => TutorialStep::Reload,
+            TutorialStep::Reload => TutorialStep::Session,
+            TutorialStep::Session => TutorialStep::Objective,
+            TutorialStep::Objective | TutorialStep::Complete => TutorialStep::Complete,
+        }
+    }
+
+    /// 1-based position for the "STEP 2/6" label (Complete has none)
+    pub fn number(self) -> Option<usize> {
+        match self {
+            TutorialStep::Look => Some(1),
+            TutorialStep::Move => Some(2),
+            TutorialStep::Shoot => Some(3),
+            TutorialStep::Reload => Some(4),
+            TutorialStep::Session => Some(5),
+            TutorialStep::Objective => Some(6),
+            TutorialStep::Complete => None,
+        }
+    }
+
+    /// Heading and instructions shown on the tutorial panel
+    pub fn instructions(self) -> (&'static str, &'static str) {
+        match self {
+            TutorialStep::Look => (t("tutorial.look.title"), t("tutorial.look.body")),
+            TutorialStep::Move => (t("tutorial.move.title"), t("tutorial.move.body")),
+            TutorialStep::Shoot => (t("tutorial.shoot.title"), t("tutorial.shoot.body")),
+            TutorialStep::Reload => (t("tutorial.reload.title"), t("tutorial.reload.body")),
+            TutorialStep::Session => (t("tutorial.session.title"), t("tutorial.session.body")),
+            TutorialStep::Objective => (t("tutorial.objective.title"), t("tutorial.objective.body")),
+            TutorialStep::Complete => (t("tutorial.complete.title"), t("tutorial.complete.body")),
+        }
+    }
+}
+
+/// What happened this frame, as far as the tutorial cares
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TutorialInput {
+    /// Absolute yaw change since last frame (degrees)
+    pub look_delta: f32,
+    /// Player's feet are inside the move zone
+    pub in_move_zone: bool,
+    /// Target bots for the shoot step still standing
+    pub targets_left: usize,
+    /// A reload finished this frame
+    pub reloaded: bool,
+    /// ENTER pressed this frame
+    pub confirm: bool,
+    /// Objective bots still standing
+    pub objective_bots_left: usize,
+    /// Player's feet are inside the objective zone
+    pub in_objective_zone: bool,
+}
+
+/// Scripted onboarding mission progress
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tutorial {
+    step: TutorialStep,
+    /// Degrees turned during the look step
+    looked: f32,
+    /// Seconds spent in the objective zone (resets on leaving it)
+    objective_held: f32,
+}
+
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self { step: TutorialStep::Look, looked: 0.0, objective_held: 0.0 }
+    }
+}
+
+impl Tutorial {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn step(&self) -> TutorialStep {
+        self.step
+    }
+
+    /// Advance with this frame's input, returning the step just entered (if any)
+    pub fn update(&mut self, input: TutorialInput, delta: f32) -> Option<TutorialStep> {
+        let done = match self.step {
+            TutorialStep::Look => {
+                self.looked += input.look_delta.abs();
+                self.looked >= LOOK_REQUIRED
+            }
+            TutorialStep::Move => input.in_move_zone,
+            TutorialStep::Shoot => input.targets_left == 0,
+            TutorialStep::Reload => input.reloaded,
+            TutorialStep::Session => input.confirm,
+            TutorialStep::Objective => {
+                if input.objective_bots_left == 0 && input.in_objective_zone {
+                    self.objective_held += delta;
+                } else {
+                    self.objective_held = 0.0;
+                }
+                self.objective_held >= OBJECTIVE_HOLD_TIME
+            }
+            TutorialStep::Complete => false,
+        };
+
+        if !done {
+            return None;
+        }
+        self.step = self.step.next();
+        Some(self.step)
+    }
+
+    /// 0.0 to 1.0 progress within the current step (for the panel's bar)
+    pub fn step_progress(&self) -> f32 {
+        match self.step {
+            TutorialStep::Look => (self.looked / LOOK_REQUIRED).min(1.0),
+            TutorialStep::Objective => (self.objective_held / OBJECTIVE_HOLD_TIME).min(1.0),
+            TutorialStep::Complete => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.step == TutorialStep::Complete
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_walks_through_every_step() {
+        let mut tutorial = Tutorial::new();
+        let idle = TutorialInput { targets_left: 3, objective_bots_left: 1, ..Default::default() };
+
+        assert_eq!(tutorial.update(TutorialInput { look_delta: -200.0, ..idle }, 0.1), None);
+        assert_eq!(tutorial.update(TutorialInput { look_delta: 100.0, ..idle }, 0.1), Some(TutorialStep::Move));
+        assert_eq!(tutorial.update(TutorialInput { in_move_zone: true, ..idle }, 0.1), Some(TutorialStep::Shoot));
+        assert_eq!(tutorial.update(idle, 0.1), None);
+        assert_eq!(tutorial.update(TutorialInput { targets_left: 0, ..idle }, 0.1), Some(TutorialStep::Reload));
+        assert_eq!(tutorial.update(TutorialInput { reloaded: true, ..idle }, 0.1), Some(TutorialStep::Session));
+        assert_eq!(tutorial.update(TutorialInput { confirm: true, ..idle }, 0.1), Some(TutorialStep::Objective));
+        assert_eq!(tutorial.step().number(), Some(6));
+    }
+
+    #[test]
+    fn test_objective_needs_cleared_bots_and_continuous_hold() {
+        let mut tutorial = Tutorial { step: TutorialStep::Objective, ..Tutorial::new() };
+        let holding = TutorialInput { in_objective_zone: true, ..Default::default() };
+
+        // Standing in the zone doesn't count while the bot is still up
+        tutorial.update(TutorialInput { objective_bots_left: 1, ..holding }, 2.0);
+        assert_eq!(tutorial.step_progress(), 0.0);
+
+        tutorial.update(holding, 2.0);
+        tutorial.update(TutorialInput { in_objective_zone: false, ..holding }, 0.1);
+        assert_eq!(tutorial.step_progress(), 0.0);
+
+        assert_eq!(tutorial.update(holding, 2.0), None);
+        assert_eq!(tutorial.update(holding, 1.0), Some(TutorialStep::Complete));
+        assert!(tutorial.is_complete());
+    }
+}
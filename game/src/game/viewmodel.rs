@@ -0,0 +1,118 @@
+use raylib::prelude::*;
+use std::cell::Cell;
+use super::BASE_FOV;
+
+/// Allowed viewmodel FOV (degrees, vertical like the camera's)
+pub const VIEWMODEL_FOV_RANGE: (f32, f32) = (50.0, 90.0);
+
+/// Furthest the gun can be nudged along each axis (units)
+pub const MAX_VIEWMODEL_OFFSET: f32 = 0.2;
+
+/// Player-adjustable gun placement (classic "viewmodel_fov" / "viewmodel_offset" / "cl_righthand")
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewmodelSettings {
+    /// Mirror the gun to the left side of the screen
+    pub left_handed: bool,
+    /// Nudge towards the outside of the screen (away from center, on the gun's side)
+    pub offset_x: f32,
+    pub offset_y: f32,
+    /// Nudge forward (away from the camera)
+    pub offset_z: f32,
+    /// FOV the gun looks like it's drawn with; higher makes it smaller and further off
+    pub fov: f32,
+}
+
+impl Default for ViewmodelSettings {
+    fn default() -> Self {
+        Self { left_handed: false, offset_x: 0.0, offset_y: 0.0, offset_z: 0.0, fov: BASE_FOV }
+    }
+}
+
+impl ViewmodelSettings {
+    /// Settings with offsets and FOV clamped to their allowed ranges
+    pub fn clamped(self) -> Self {
+        let offset = |value: f32| value.clamp(-MAX_VIEWMODEL_OFFSET, MAX_VIEWMODEL_OFFSET);
+        Self {
+            left_handed: self.left_handed,
+            offset_x: offset(self.offset_x),
+            offset_y: offset(self.offset_y),
+            offset_z: offset(self.offset_z),
+            fov: self.fov.clamp(VIEWMODEL_FOV_RANGE.0, VIEWMODEL_FOV_RANGE.1),
+        }
+    }
+
+    /// How much to squeeze the gun towards the view axis so it projects as if the
+    /// camera had `fov` instead of `camera_fov`
+    pub fn lateral_scale(&self, camera_fov: f32) -> f32 {
+        (camera_fov.to_radians() / 2.0).tan() / (self.fov.to_radians() / 2.0).tan()
+    }
+
+    /// Column-major model matrix (for `rlMultMatrixf`) applied to the gun drawn in world space
+    ///
+    /// Scales everything around the camera along its right / up axes (the viewmodel
+    /// FOV) and flips the right axis for left-handed mode. `right`, `up` and `forward`
+    /// must be orthonormal.
+    pub fn transform(&self, camera: Vector3, right: Vector3, up: Vector3, forward: Vector3, camera_fov: f32) -> [f32; 16] {
+        let lateral = self.lateral_scale(camera_fov);
+        let side = if self.left_handed { -lateral } else { lateral };
+        // L = side * r r^T + lateral * u u^T + f f^T
+        let column = |axis: Vector3| {
+            right * (side * right.dot(axis)) + up * (lateral * up.dot(axis)) + forward * forward.dot(axis)
+        };
+        let x = column(Vector3::new(1.0, 0.0, 0.0));
+        let y = column(Vector3::new(0.0, 1.0, 0.0));
+        let z = column(Vector3::new(0.0, 0.0, 1.0));
+        // Keep the camera itself fixed: t = c - L c
+        let t = camera - (x * camera.x + y * camera.y + z * camera.z);
+        [
+            x.x, x.y, x.z, 0.0,
+            y.x, y.y, y.z, 0.0,
+            z.x, z.y, z.z, 0.0,
+            t.x, t.y, t.z, 1.0,
+        ]
+    }
+}
+
+// Active viewmodel settings (Emscripten is single-threaded)
+thread_local! {
+    static VIEWMODEL: Cell<ViewmodelSettings> = Cell::new(ViewmodelSettings::default());
+}
+
+pub fn set_viewmodel(settings: ViewmodelSettings) {
+    VIEWMODEL.with(|viewmodel| viewmodel.set(settings.clamped()));
+}
+
+pub fn viewmodel() -> ViewmodelSettings {
+    VIEWMODEL.with(|viewmodel| viewmodel.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(m: &[f32; 16], p: Vector3) -> Vector3 {
+        Vector3::new(
+            m[0] * p.x + m[4] * p.y + m[8] * p.z + m[12],
+            m[1] * p.x + m[5] * p.y + m[9] * p.z + m[13],
+            m[2] * p.x + m[6] * p.y + m[10] * p.z + m[14],
+        )
+    }
+
+    #[test]
+    fn test_transform_mirrors_and_squeezes_around_the_camera() {
+        let camera = Vector3::new(3.0, 1.7, -2.0);
+        let (right, up, forward) = (Vector3::new(0.0, 0.0, 1.0), Vector3::new(0.0, 1.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+
+        let default = ViewmodelSettings::default();
+        assert!((default.lateral_scale(BASE_FOV) - 1.0).abs() < 1e-5);
+
+        let settings = ViewmodelSettings { left_handed: true, fov: 90.0, ..default };
+        let m = settings.transform(camera, right, up, forward, BASE_FOV);
+        assert!((apply(&m, camera) - camera).length() < 1e-5);
+
+        // A point right of and ahead of the camera ends up left, closer to the view axis, same depth
+        let moved = apply(&m, camera + right * 0.35 + forward * 0.8) - camera;
+        assert!(moved.dot(right) < 0.0 && moved.dot(right) > -0.35);
+        assert!((moved.dot(forward) - 0.8).abs() < 1e-5);
+    }
+}
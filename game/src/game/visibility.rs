@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use raylib::prelude::*;
+
+/// How far (world units) a player can spot an enemy
+pub const SPOT_DISTANCE: f32 = 30.0;
+
+/// Half of the spotting field of view (degrees either side of the view direction)
+pub const SPOT_HALF_FOV: f32 = 60.0;
+
+/// How long (seconds) an enemy stays revealed after firing
+pub const FIRE_REVEAL_TIME: f64 = 1.5;
+
+/// How long (seconds) a "last seen" ghost marker lingers before disappearing
+pub const GHOST_DURATION: f64 = 5.0;
+
+/// Grace period (seconds) before a lost sighting turns into a ghost (avoids flicker)
+const VISIBLE_GRACE: f64 = 0.25;
+
+/// Someone who can spot enemies (the local player or a teammate)
+#[derive(Debug, Clone, Copy)]
+pub struct Observer {
+    /// Eye position
+    pub position: Vector3,
+    /// View direction yaw in radians
+    pub yaw: f32,
+}
+
+/// How an enemy is shown on the minimap
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MarkerKind {
+    /// Currently spotted or revealed by firing
+    Visible,
+    /// Last seen position, fading from 1.0 to 0.0
+    Ghost { fade: f32 },
+}
+
+/// An enemy marker that passed the fog-of-war rules
+#[derive(Debug, Clone)]
+pub struct EnemyMarker {
+    pub authority: String,
+    pub position: Vector3,
+    /// Yaw in radians at the time of the sighting
+    pub yaw: f32,
+    pub kind: MarkerKind,
+}
+
+/// Last known information about an enemy
+#[derive(Debug, Clone, Copy)]
+struct Sighting {
+    position: Vector3,
+    yaw: f32,
+    last_seen: f64,
+    revealed_until: f64,
+}
+
+/// Minimap fog-of-war: enemies are only known when a teammate sees them or they fire
+#[derive(Debug, Default)]
+pub struct VisibilityTracker {
+    sightings: HashMap<String, Sighting>,
+}
+
+impl VisibilityTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether an observer can see a target (range, field of view, then line of sight)
+    pub fn can_spot(observer: &Observer, target: Vector3, line_blocked: impl Fn(Vector3, Vector3) -> bool) -> bool {
+        let to_target = target - observer.position;
+        let distance = to_target.length();
+        if distance > SPOT_DISTANCE {
+            return false;
+        }
+
+        if distance > 1.0 {
+            let facing = Vector2::new(observer.yaw.cos(), observer.yaw.sin());
+            let flat = Vector2::new(to_target.x, to_target.z).normalized();
+            if facing.dot(flat) < SPOT_HALF_FOV.to_radians().cos() {
+                return false;
+            }
+        }
+
+        !line_blocked(observer.position, target)
+    }
+
+    /// Record that an enemy is currently seen by the team
+    pub fn note_seen(&mut self, authority: &str, position: Vector3, yaw: f32, now: f64) {
+        let sighting = self.sightings.entry(authority.to_string()).or_insert(Sighting {
+            position,
+            yaw,
+            last_seen: now,
+            revealed_until: f64::MIN,
+        });
+        sighting.position = position;
+        sighting.yaw = yaw;
+        sighting.last_seen = now;
+    }
+
+    /// Record that an enemy fired (reveals them for a short time)
+    pub fn note_fired(&mut self, authority: &str, position: Vector3, yaw: f32, now: f64) {
+        self.note_seen(authority, position, yaw, now);
+        if let Some(sighting) = self.sightings.get_mut(authority) {
+            sighting.revealed_until = now + FIRE_REVEAL_TIME;
+        }
+    }
+
+    /// Whether an enemy is still revealed from firing
+    pub fn is_revealed(&self, authority: &str, now: f64) -> bool {
+        self.sightings.get(authority).is_some_and(|s| now < s.revealed_until)
+    }
+
+    /// Forget an enemy (e.g. they died)
+    pub fn forget(&mut self, authority: &str) {
+        self.sightings.remove(authority);
+    }
+
+    /// Forget everything (new match)
+    pub fn clear(&mut self) {
+        self.sightings.clear();
+    }
+
+    /// Drop ghosts that have fully faded
+    pub fn expire(&mut self, now: f64) {
+        self.sightings.retain(|_, s| now - s.last_seen <= VISIBLE_GRACE + GHOST_DURATION);
+    }
+
+    /// Markers to draw for the current time
+    pub fn markers(&self, now: f64) -> Vec<EnemyMarker> {
+        self.sightings
+            .iter()
+            .filter_map(|(authority, s)| {
+                let age = now - s.last_seen;
+                let kind = if age <= VISIBLE_GRACE {
+                    MarkerKind::Visible
+                } else if age <= VISIBLE_GRACE + GHOST_DURATION {
+                    MarkerKind::Ghost { fade: (1.0 - (age - VISIBLE_GRACE) / GHOST_DURATION) as f32 }
+                } else {
+                    return None;
+                };
+                Some(EnemyMarker { authority: authority.clone(), position: s.position, yaw: s.yaw, kind })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spotting_respects_fov_and_walls() {
+        let observer = Observer { position: Vector3::zero(), yaw: 0.0 }; // Facing +X
+        let open = |_: Vector3, _: Vector3| false;
+        let walled = |_: Vector3, _: Vector3| true;
+
+        assert!(VisibilityTracker::can_spot(&observer, Vector3::new(10.0, 0.0, 1.0), open));
+        assert!(!VisibilityTracker::can_spot(&observer, Vector3::new(-10.0, 0.0, 0.0), open));
+        assert!(!VisibilityTracker::can_spot(&observer, Vector3::new(10.0, 0.0, 0.0), walled));
+        assert!(!VisibilityTracker::can_spot(&observer, Vector3::new(SPOT_DISTANCE + 1.0, 0.0, 0.0), open));
+    }
+
+    #[test]
+    fn test_sighting_decays_to_ghost() {
+        let mut tracker = VisibilityTracker::new();
+        tracker.note_fired("enemy", Vector3::new(1.0, 0.0, 2.0), 0.0, 10.0);
+        assert!(tracker.is_revealed("enemy", 11.0));
+        assert_eq!(tracker.markers(10.1)[0].kind, MarkerKind::Visible);
+
+        match tracker.markers(12.0)[0].kind {
+            MarkerKind::Ghost { fade } => assert!(fade > 0.0 && fade < 1.0),
+            kind => panic!("expected ghost, got {:?}", kind),
+        }
+
+        tracker.expire(20.0);
+        assert!(tracker.markers(20.0).is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::HashSet;
+
+/// How long a vote stays open (seconds)
+pub const VOTE_DURATION: f64 = 20.0;
+
+/// Minimum time between two votes, counted from when the last one closed (seconds)
+pub const VOTE_COOLDOWN: f64 = 30.0;
+
+/// Length of a voted timeout (seconds)
+pub const TIMEOUT_DURATION: f32 = 30.0;
+
+/// Players heard from less recently than this are treated as disconnected and left
+/// out of the electorate, so a dropped client can't keep a vote from passing (seconds)
+pub const VOTER_TIMEOUT: f64 = 10.0;
+
+/// What a vote is about
+#[derive(Debug, Clone, PartialEq)]
+pub enum VoteKind {
+    /// Pause the round for TIMEOUT_DURATION
+    Timeout,
+    /// The caller's team gives up the match (only that team votes)
+    Surrender,
+    /// Remove a player from the match (they don't get a vote)
+    Kick { target: String },
+}
+
+impl VoteKind {
+    /// Id used in relayed vote events
+    pub fn id(&self) -> &'static str {
+        match self {
+            VoteKind::Timeout => "timeout",
+            VoteKind::Surrender => "surrender",
+            VoteKind::Kick { .. } => "kick",
+        }
+    }
+
+    pub fn from_id(id: &str, target: Option<&str>) -> Option<Self> {
+        match (id, target) {
+            ("timeout", _) => Some(VoteKind::Timeout),
+            ("surrender", _) => Some(VoteKind::Surrender),
+            ("kick", Some(target)) if !target.is_empty() => Some(VoteKind::Kick { target: target.to_string() }),
+            _ => None,
+        }
+    }
+
+    pub fn target(&self) -> Option<&str> {
+        match self {
+            VoteKind::Kick { target } => Some(target),
+            VoteKind::Timeout | VoteKind::Surrender => None,
+        }
+    }
+}
+
+/// An open vote
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vote {
+    /// Unique id shared by every client (caller key + call time)
+    pub id: String,
+    pub kind: VoteKind,
+    /// Player who called the vote
+    pub caller: String,
+    /// Caller's team (1 = Team A, 2 = Team B)
+    pub team: u8,
+    /// Local time (seconds) the vote was opened on this client
+    pub called_at: f64,
+    yes: HashSet<String>,
+    no: HashSet<String>,
+}
+
+impl Vote {
+    /// A new vote; the caller counts as a yes
+    pub fn new(id: String, kind: VoteKind, caller: String, team: u8, called_at: f64) -> Self {
+        let yes = HashSet::from([caller.clone()]);
+        Self { id, kind, caller, team, called_at, yes, no: HashSet::new() }
+    }
+
+    /// Whether a player on `team` gets a say (surrender is team-only, kick targets can't vote)
+    pub fn is_eligible(&self, voter: &str, team: u8) -> bool {
+        match &self.kind {
+            VoteKind::Timeout => true,
+            VoteKind::Surrender => team == self.team,
+            VoteKind::Kick { target } => voter != target,
+        }
+    }
+
+    /// Connected players who get a say: `(voter, team, last heard from)` per player, in local seconds
+    pub fn electorate<'a>(&self, players: impl IntoIterator<Item = (&'a str, u8, f64)>, now: f64) -> HashSet<String> {
+        players
+            .into_iter()
+            .filter(|(voter, team, last_seen)| now - last_seen < VOTER_TIMEOUT && self.is_eligible(voter, *team))
+            .map(|(voter, _, _)| voter.to_string())
+            .collect()
+    }
+
+    pub fn has_voted(&self, voter: &str) -> bool {
+        self.yes.contains(voter) || self.no.contains(voter)
+    }
+
+    /// (yes, no), counting only ballots from the `electorate`
+    pub fn tally(&self, electorate: &HashSet<String>) -> (usize, usize) {
+        (self.yes.intersection(electorate).count(), self.no.intersection(electorate).count())
+    }
+
+    pub fn time_left(&self, now: f64) -> f64 {
+        (self.called_at + VOTE_DURATION - now).max(0.0)
+    }
+}
+
+/// How a vote ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum VoteOutcome {
+    Passed(Vote),
+    Failed(Vote),
+}
+
+/// The match's vote: at most one open at a time, with a cooldown in between
+#[derive(Debug, Default)]
+pub struct VoteBox {
+    active: Option<Vote>,
+    cooldown_until: f64,
+}
+
+impl VoteBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn active(&self) -> Option<&Vote> {
+        self.active.as_ref()
+    }
+
+    /// Whether a new vote can be called right now
+    pub fn can_call(&self, now: f64) -> bool {
+        self.active.is_none() && now >= self.cooldown_until
+    }
+
+    /// Open a vote (ignored while another is open or during the cooldown)
+    pub fn open(&mut self, vote: Vote, now: f64) -> bool {
+        if !self.can_call(now) {
+            return false;
+        }
+        self.active = Some(vote);
+        true
+    }
+
+    /// Record a ballot for the open vote (ignores stale ids and second ballots)
+    pub fn cast(&mut self, vote_id: &str, voter: &str, yes: bool) -> bool {
+        let Some(vote) = self.active.as_mut().filter(|v| v.id == vote_id) else {
+            return false;
+        };
+        if vote.has_voted(voter) {
+            return false;
+        }
+        if yes {
+            vote.yes.insert(voter.to_string());
+        } else {
+            vote.no.insert(voter.to_string());
+        }
+        true
+    }
+
+    /// Close the vote once a strict majority of the `electorate` agrees,
+    /// once a yes majority becomes impossible, or when time runs out
+    /// (ballots from players outside the electorate don't count)
+    pub fn resolve(&mut self, electorate: &HashSet<String>, now: f64) -> Option<VoteOutcome> {
+        let vote = self.active.as_ref()?;
+        let (yes, no) = vote.tally(electorate);
+        let electorate = electorate.len().max(1);
+
+        let outcome = if yes * 2 > electorate {
+            VoteOutcome::Passed(vote.clone())
+        } else if no * 2 >= electorate || vote.time_left(now) <= 0.0 {
+            VoteOutcome::Failed(vote.clone())
+        } else {
+            return None;
+        };
+
+        self.active = None;
+        self.cooldown_until = now + VOTE_COOLDOWN;
+        Some(outcome)
+    }
+
+    pub fn clear(&mut self) {
+        self.active = None;
+        self.cooldown_until = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voters(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_vote_passes_on_majority_then_cools_down() {
+        let mut votes = VoteBox::new();
+        assert!(votes.open(Vote::new("v1".into(), VoteKind::Timeout, "alice".into(), 1, 0.0), 0.0));
+        assert!(!votes.open(Vote::new("v2".into(), VoteKind::Surrender, "bob".into(), 2, 1.0), 1.0));
+
+        // Caller's yes alone isn't a majority of four
+        let electorate = voters(&["alice", "bob", "carol", "dave"]);
+        assert_eq!(votes.resolve(&electorate, 1.0), None);
+        assert!(votes.cast("v1", "bob", true));
+        assert!(!votes.cast("v1", "bob", false));
+        assert_eq!(votes.resolve(&electorate, 2.0), None);
+        assert!(votes.cast("v1", "carol", true));
+
+        assert!(matches!(votes.resolve(&electorate, 3.0), Some(VoteOutcome::Passed(v)) if v.kind == VoteKind::Timeout));
+        assert!(!votes.can_call(3.0 + VOTE_COOLDOWN - 1.0));
+        assert!(votes.can_call(3.0 + VOTE_COOLDOWN));
+    }
+
+    #[test]
+    fn test_vote_fails_on_no_majority_or_timeout() {
+        let kick = VoteKind::from_id("kick", Some("mallory")).unwrap();
+        let vote = Vote::new("v1".into(), kick, "alice".into(), 1, 0.0);
+        assert!(!vote.is_eligible("mallory", 2));
+
+        let mut votes = VoteBox::new();
+        votes.open(vote, 0.0);
+        votes.cast("v1", "bob", false);
+        votes.cast("v1", "carol", false);
+        assert!(matches!(votes.resolve(&voters(&["alice", "bob", "carol", "dave"]), 1.0), Some(VoteOutcome::Failed(_))));
+
+        votes.clear();
+        votes.open(Vote::new("v2".into(), VoteKind::Surrender, "alice".into(), 1, 10.0), 10.0);
+        let electorate = voters(&["alice", "bob", "carol"]);
+        assert_eq!(votes.resolve(&electorate, 10.0 + VOTE_DURATION - 1.0), None);
+        assert!(matches!(votes.resolve(&electorate, 10.0 + VOTE_DURATION), Some(VoteOutcome::Failed(_))));
+    }
+
+    #[test]
+    fn test_electorate_leaves_out_disconnected_players() {
+        let vote = Vote::new("v1".into(), VoteKind::Surrender, "alice".into(), 1, 0.0);
+        let players = [("alice", 1, 50.0), ("bob", 1, 45.0), ("carol", 1, 50.0 - VOTER_TIMEOUT), ("dave", 2, 50.0)];
+        // carol dropped (no update in VOTER_TIMEOUT), dave is on the other team
+        assert_eq!(vote.electorate(players, 50.0), voters(&["alice", "bob"]));
+
+        // alice and bob are enough for a majority once carol is gone
+        let mut votes = VoteBox::new();
+        votes.open(vote.clone(), 0.0);
+        votes.cast("v1", "bob", true);
+        assert!(matches!(votes.resolve(&vote.electorate(players, 50.0), 50.0), Some(VoteOutcome::Passed(_))));
+    }
+
+    #[test]
+    fn test_ballots_outside_the_electorate_dont_count() {
+        let electorate = voters(&["alice", "bob", "carol"]);
+        let mut votes = VoteBox::new();
+        votes.open(Vote::new("v1".into(), VoteKind::Timeout, "alice".into(), 1, 0.0), 0.0);
+
+        // A spectator's and a dropped player's yes are recorded but not counted
+        votes.cast("v1", "spectator", true);
+        votes.cast("v1", "dropped", true);
+        assert_eq!(votes.active().unwrap().tally(&electorate), (1, 0));
+        assert_eq!(votes.resolve(&electorate, 1.0), None);
+
+        votes.cast("v1", "bob", true);
+        assert!(matches!(votes.resolve(&electorate, 2.0), Some(VoteOutcome::Passed(_))));
+    }
+}
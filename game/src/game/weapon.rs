@@ -0,0 +1,180 @@
+use raylib::prelude::*;
+use crate::i18n::t;
+
+/// Shotgun pellets deal full damage up to this distance (units)
+const SHOTGUN_FALLOFF_START: f32 = 6.0;
+
+/// Pellets deal their minimum damage from this distance on
+const SHOTGUN_FALLOFF_END: f32 = 20.0;
+
+/// Fraction of pellet damage left at (and beyond) the falloff end
+const SHOTGUN_MIN_DAMAGE_SCALE: f32 = 0.25;
+
+/// Weapons that can appear in the hit pipeline and kill feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WeaponKind {
+    /// Default automatic rifle
+    #[default]
+    Rifle,
+    /// Bolt-action sniper: scoped, heavy damage, slow fire rate
+    Sniper,
+    /// Pump shotgun: a cone of pellets, each raycast separately
+    Shotgun,
+}
+
+impl WeaponKind {
+    /// All weapons in selection order (number keys 1, 2, ...)
+    pub const ALL: [WeaponKind; 3] = [WeaponKind::Rifle, WeaponKind::Sniper, WeaponKind::Shotgun];
+
+    /// Stable identifier sent to the JS side (kill feed glyph lookup)
+    pub fn id(&self) -> &'static str {
+        match self {
+            WeaponKind::Rifle => "rifle",
+            WeaponKind::Sniper => "sniper",
+            WeaponKind::Shotgun => "shotgun",
+        }
+    }
+
+    /// Localized display name
+    pub fn label(&self) -> &'static str {
+        match self {
+            WeaponKind::Rifle => t("weapon.rifle"),
+            WeaponKind::Sniper => t("weapon.sniper"),
+            WeaponKind::Shotgun => t("weapon.shotgun"),
+        }
+    }
+
+    /// Inverse of `id` (for weapons received from the JS side)
+    pub fn from_id(id: &str) -> Option<WeaponKind> {
+        WeaponKind::ALL.into_iter().find(|w| w.id() == id)
+    }
+
+    /// Damage per hit (per pellet for the shotgun) at point-blank range
+    pub fn damage(&self) -> u8 {
+        match self {
+            WeaponKind::Rifle => 25,
+            WeaponKind::Sniper => 90,
+            WeaponKind::Shotgun => 12,
+        }
+    }
+
+    /// Damage of one hit at `distance` (only pellets fall off)
+    pub fn damage_at(&self, distance: f32) -> f32 {
+        let damage = self.damage() as f32;
+        match self {
+            WeaponKind::Shotgun => {
+                let t = ((distance - SHOTGUN_FALLOFF_START) / (SHOTGUN_FALLOFF_END - SHOTGUN_FALLOFF_START)).clamp(0.0, 1.0);
+                damage * (1.0 - t * (1.0 - SHOTGUN_MIN_DAMAGE_SCALE))
+            }
+            _ => damage,
+        }
+    }
+
+    /// Rays fired per shot
+    pub fn pellet_count(&self) -> usize {
+        match self {
+            WeaponKind::Shotgun => 8,
+            _ => 1,
+        }
+    }
+
+    /// Half-angle of the pellet cone (degrees)
+    pub fn spread(&self) -> f32 {
+        match self {
+            WeaponKind::Shotgun => 5.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Minimum time between shots (seconds)
+    pub fn fire_interval(&self) -> f64 {
+        match self {
+            WeaponKind::Rifle => 0.1,
+            WeaponKind::Sniper => 1.4,
+            WeaponKind::Shotgun => 0.9,
+        }
+    }
+
+    /// Screen shake per shot (0 - 1); the rifle's rapid fire would turn it into a constant rattle
+    pub fn fire_shake(&self) -> f32 {
+        match self {
+            WeaponKind::Rifle => 0.0,
+            WeaponKind::Sniper => 1.0,
+            WeaponKind::Shotgun => 0.8,
+        }
+    }
+
+    /// Camera field of view when fully scoped, None for weapons without a scope
+    pub fn scope_fov(&self) -> Option<f32> {
+        match self {
+            WeaponKind::Rifle => None,
+            WeaponKind::Sniper => Some(15.0),
+            WeaponKind::Shotgun => None,
+        }
+    }
+
+    /// Directions of every ray in one shot around `forward` (normalized)
+    ///
+    /// The first pellet always flies straight; the rest are spread over the cone
+    /// on a golden-angle spiral rotated by `seed`, so patterns vary per shot
+    /// without clumping.
+    pub fn pellet_directions(&self, forward: Vector3, seed: f32) -> Vec<Vector3> {
+        let count = self.pellet_count();
+        if count <= 1 {
+            return vec![forward];
+        }
+
+        let helper = if forward.y.abs() < 0.99 { Vector3::new(0.0, 1.0, 0.0) } else { Vector3::new(1.0, 0.0, 0.0) };
+        let right = forward.cross(helper).normalized();
+        let up = right.cross(forward).normalized();
+        let max_offset = self.spread().to_radians().tan();
+
+        (0..count)
+            .map(|i| {
+                let radius = max_offset * (i as f32 / (count - 1) as f32).sqrt();
+                let angle = seed + i as f32 * 2.399_963;
+                (forward + right * (angle.cos() * radius) + up * (angle.sin() * radius)).normalized()
+            })
+            .collect()
+    }
+}
+
+/// Sum per-pellet damage by target, most damaged target first
+pub fn aggregate_pellet_damage<'a>(hits: impl IntoIterator<Item = (&'a str, f32)>) -> Vec<(String, f32)> {
+    let mut totals: Vec<(String, f32)> = Vec::new();
+    for (target, damage) in hits {
+        match totals.iter_mut().find(|(t, _)| t == target) {
+            Some((_, total)) => *total += damage,
+            None => totals.push((target.to_string(), damage)),
+        }
+    }
+    totals.sort_by(|a, b| b.1.total_cmp(&a.1));
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pellets_stay_inside_cone() {
+        let forward = Vector3::new(1.0, 0.0, 0.0);
+        let pellets = WeaponKind::Shotgun.pellet_directions(forward, 0.7);
+        assert_eq!(pellets.len(), WeaponKind::Shotgun.pellet_count());
+
+        let min_cos = WeaponKind::Shotgun.spread().to_radians().cos() - 1e-4;
+        assert!(pellets.iter().all(|p| p.dot(forward) >= min_cos));
+        assert_eq!(WeaponKind::Rifle.pellet_directions(forward, 0.7), vec![forward]);
+    }
+
+    #[test]
+    fn test_pellet_damage_falls_off_and_aggregates() {
+        let shotgun = WeaponKind::Shotgun;
+        assert_eq!(shotgun.damage_at(2.0), 12.0);
+        assert_eq!(shotgun.damage_at(50.0), 3.0);
+        assert_eq!(WeaponKind::Rifle.damage_at(50.0), 25.0);
+
+        let totals = aggregate_pellet_damage([("a", 12.0), ("b", 12.0), ("a", 6.0)]);
+        assert_eq!(totals, vec![("a".to_string(), 18.0), ("b".to_string(), 12.0)]);
+    }
+}
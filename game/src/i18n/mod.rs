@@ -0,0 +1,114 @@
+//! Localization for in-engine text (HUD, announcements, map editor, menus)
+//!
+//! Each language is a flat `key -> string` JSON table embedded at build time.
+//! Lookups fall back to English, then to the key itself, so a missing
+//! translation never blanks out the UI. Placeholders are `{name}` and are
+//! filled by `tr!`.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Supported languages as (code, native name); the first one is the fallback
+pub const LANGUAGES: [(&str, &str); 2] = [("en", "English"), ("es", "Español")];
+
+/// Embedded string tables, same order as `LANGUAGES`
+const TABLES: [&str; 2] = [include_str!("en.json"), include_str!("es.json")];
+
+static STRINGS: OnceLock<Vec<HashMap<String, String>>> = OnceLock::new();
+
+// Active language index into LANGUAGES (Emscripten is single-threaded)
+thread_local! {
+    static LANGUAGE: Cell<usize> = const { Cell::new(0) };
+}
+
+fn strings() -> &'static [HashMap<String, String>] {
+    STRINGS.get_or_init(|| {
+        TABLES.iter()
+            .map(|table| serde_json::from_str(table).expect("invalid embedded string table"))
+            .collect()
+    })
+}
+
+/// Switch the language by code ("en", "es"); returns false for unknown codes
+pub fn set_language(code: &str) -> bool {
+    match LANGUAGES.iter().position(|(c, _)| *c == code) {
+        Some(index) => {
+            LANGUAGE.with(|language| language.set(index));
+            true
+        }
+        None => false,
+    }
+}
+
+/// Code of the active language
+pub fn language() -> &'static str {
+    LANGUAGES[LANGUAGE.with(|language| language.get())].0
+}
+
+/// Localized string for a key (English, then the key itself, when missing)
+pub fn t(key: &'static str) -> &'static str {
+    let strings = strings();
+    let index = LANGUAGE.with(|language| language.get());
+    strings[index].get(key)
+        .or_else(|| strings[0].get(key))
+        .map(String::as_str)
+        .unwrap_or(key)
+}
+
+/// Localized "ON" / "OFF" for toggle status messages
+pub fn on_off(enabled: bool) -> &'static str {
+    if enabled { t("common.on") } else { t("common.off") }
+}
+
+/// Fill `{name}` placeholders in a template
+pub fn fill(template: &str, args: &[(&str, String)]) -> String {
+    args.iter().fold(template.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+/// Localized string with placeholders: `tr!("editor.status.placed", count = n)`
+macro_rules! tr {
+    ($key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::fill($crate::i18n::t($key), &[$((stringify!($name), ($value).to_string())),*])
+    };
+}
+pub(crate) use tr;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `{name}` placeholders used by a string
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text.split('{').skip(1).filter_map(|part| part.split('}').next()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_translations_match_english_keys_and_placeholders() {
+        let strings = strings();
+        let english = &strings[0];
+        for (table, (code, _)) in strings.iter().zip(LANGUAGES.iter()).skip(1) {
+            for (key, text) in table {
+                let source = english.get(key).unwrap_or_else(|| panic!("{}: unknown key {}", code, key));
+                assert_eq!(placeholders(text), placeholders(source), "{}: {}", code, key);
+            }
+            for key in english.keys() {
+                assert!(table.contains_key(key), "{}: missing {}", code, key);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup_falls_back_and_fills_placeholders() {
+        assert_eq!(t("no.such.key"), "no.such.key");
+        assert!(set_language("es"));
+        assert!(!set_language("xx"));
+        assert_eq!(language(), "es");
+        assert_eq!(fill("{name} has {count}", &[("name", "A".into()), ("count", "3".into())]), "A has 3");
+        set_language("en");
+    }
+}
@@ -1,17 +1,25 @@
 use raylib::prelude::*;
 use raylib_imgui::RaylibGui;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::ffi::CString;
 use std::ffi::CStr;
 use serde_json::Value;
 
+#[macro_use]
+mod diagnostics;
 mod map;
 mod menu;
 mod game;
+mod audio;
+mod net;
+mod i18n;
+mod raycaster;
 
-use map::{MapBuilder, map::Map};
+use map::{MapBuilder, MapLoader, MapStream};
 use menu::{MenuState, MenuTab};
 use game::GameState;
+use audio::{MusicController, MusicContext, AudioChannel};
+use net::EndpointProbe;
 
 // Emscripten bindings for JavaScript interop
 extern "C" {
@@ -23,6 +31,8 @@ extern "C" {
 // Using thread_local since Emscripten is single-threaded
 thread_local! {
     static GAME_STATE: RefCell<Option<*mut GameState>> = RefCell::new(None);
+    /// Menu tab the web UI switched to, picked up by the main loop
+    static REQUESTED_MENU_TAB: Cell<Option<MenuTab>> = const { Cell::new(None) };
 }
 
 /// Set the game state pointer for JavaScript interop
@@ -38,13 +48,13 @@ pub extern "C" fn start_game() {
     use std::ffi::{CString, CStr};
     use serde_json::Value;
 
-    println!("📞 JavaScript called start_game()");
+    log_info!(Game, "📞 JavaScript called start_game()");
 
     GAME_STATE.with(|gs| {
         if let Some(state_ptr) = *gs.borrow() {
             unsafe {
                 // First, check if JavaScript has already fetched map data
-                println!("🗺️ Checking for pre-fetched map data in Module.mapDataResult...");
+                log_info!(Solana, "🗺️ Checking for pre-fetched map data in Module.mapDataResult...");
                 let check_js = CString::new("Module.mapDataResult || null").unwrap();
                 let result_ptr = emscripten_run_script_string(check_js.as_ptr());
 
@@ -52,60 +62,138 @@ pub extern "C" fn start_game() {
                     let result_str = CStr::from_ptr(result_ptr).to_str().unwrap_or("null");
 
                     if result_str != "null" {
-                        println!("🗺️ Map data found in Module.mapDataResult, attempting to load...");
+                        log_info!(Solana, "🗺️ Map data found in Module.mapDataResult, attempting to load...");
 
                         // Parse JSON and load map
                         match serde_json::from_str::<Value>(result_str) {
                             Ok(json_value) => {
                                 if let Some(base64_data) = json_value.get("data").and_then(|v| v.as_str()) {
-                                    println!("🗺️ Decoding base64 map data...");
-
-                                    use base64::{Engine as _, engine::general_purpose};
-                                    match general_purpose::STANDARD.decode(base64_data) {
-                                        Ok(bytes) => {
-                                            println!("🗺️ Decoded {} bytes, deserializing Borsh...", bytes.len());
-
-                                            match Map::from_borsh_bytes(&bytes) {
-                                                Ok(map) => {
-                                                    println!("✅ Map deserialized successfully: '{}' with {} objects", map.name, map.objects.len());
-                                                    (*state_ptr).load_map(map);
-                                                    println!("✅ Map loaded into game state!");
-                                                }
-                                                Err(e) => {
-                                                    println!("❌ Failed to deserialize map from Borsh: {:?}", e);
-                                                }
-                                            }
+                                    // Decoded a step per frame behind a loading screen so big maps don't stall the page
+                                    log_info!(Solana, "🗺️ Decoding {} characters of base64 map data over the next frames...", base64_data.len());
+                                    (*state_ptr).begin_map_load(MapLoader::from_base64(base64_data.to_string()));
+                                    if let Some(stream) = MapStream::from_result(&json_value) {
+                                        (*state_ptr).begin_map_stream(stream);
+                                    }
+                                } else if let Some(code) = json_value.get("shareCode").and_then(|v| v.as_str()) {
+                                    // Quick custom match: every player builds the same map from its share code
+                                    match map::generator::map_from_share_code(code) {
+                                        Some(map) => {
+                                            log_info!(Game, "🎲 Generated map '{}' from its share code: {} objects", map.name, map.objects.len());
+                                            (*state_ptr).load_map(map);
                                         }
-                                        Err(e) => {
-                                            println!("❌ Failed to decode base64: {:?}", e);
+                                        None => {
+                                            diagnostics::fatal(&format!("Invalid map share code: {}", code));
                                         }
                                     }
                                 } else {
-                                    println!("⚠️ No 'data' field in mapDataResult JSON");
+                                    log_warn!(Solana, "⚠️ No 'data' or 'shareCode' field in mapDataResult JSON");
                                 }
                             }
                             Err(e) => {
-                                println!("❌ Failed to parse mapDataResult JSON: {:?}", e);
+                                diagnostics::fatal(&format!("Failed to parse mapDataResult JSON: {:?}", e));
                             }
                         }
 
                         // Clear the result after processing
                         let clear_js = CString::new("Module.mapDataResult = null").unwrap();
                         emscripten_run_script(clear_js.as_ptr());
-                        println!("🧹 Cleared Module.mapDataResult");
+                        log_info!(Solana, "🧹 Cleared Module.mapDataResult");
                     } else {
-                        println!("⚠️ Module.mapDataResult is null - no map data available");
+                        log_warn!(Solana, "⚠️ Module.mapDataResult is null - no map data available");
                     }
                 } else {
-                    println!("⚠️ Module.mapDataResult is not set");
+                    log_warn!(Solana, "⚠️ Module.mapDataResult is not set");
                 }
 
                 // Start playing mode
                 (*state_ptr).start_playing();
-                println!("✅ Game mode set to Playing");
+                log_info!(Game, "✅ Game mode set to Playing");
+            }
+        } else {
+            log_warn!(Game, "⚠️ Game state not initialized");
+        }
+    });
+}
+
+/// JavaScript-callable function to watch the current game as a read-only spectator
+/// (map data is picked up from Module.mapDataResult like `start_game`)
+#[no_mangle]
+pub extern "C" fn start_spectating_js() {
+    log_info!(Game, "📞 JavaScript called start_spectating_js()");
+
+    // Reuse start_game for map loading, then switch the camera over to spectating
+    start_game();
+    GAME_STATE.with(|gs| {
+        if let Some(state_ptr) = *gs.borrow() {
+            unsafe {
+                (*state_ptr).start_spectating();
+            }
+        } else {
+            log_warn!(Game, "⚠️ Game state not initialized");
+        }
+    });
+}
+
+/// JavaScript-callable function to start the offline tutorial mission (no match or wallet needed)
+#[no_mangle]
+pub extern "C" fn start_tutorial_js() {
+    log_info!(Game, "📞 JavaScript called start_tutorial_js()");
+    GAME_STATE.with(|gs| {
+        if let Some(state_ptr) = *gs.borrow() {
+            unsafe {
+                (*state_ptr).start_tutorial();
             }
         } else {
-            println!("⚠️ Game state not initialized");
+            log_warn!(Game, "⚠️ Game state not initialized");
+        }
+    });
+}
+
+/// JavaScript-callable function to open the offline shooting range
+#[no_mangle]
+pub extern "C" fn start_shooting_range_js() {
+    log_info!(Game, "📞 JavaScript called start_shooting_range_js()");
+    GAME_STATE.with(|gs| {
+        if let Some(state_ptr) = *gs.borrow() {
+            unsafe {
+                (*state_ptr).start_shooting_range();
+            }
+        } else {
+            log_warn!(Game, "⚠️ Game state not initialized");
+        }
+    });
+}
+
+/// JavaScript-callable function to start an offline practice match: `difficulty` indexes
+/// `BotDifficulty::ALL`, `role` indexes `BotRole::ALL` (anything else mixes the roles)
+#[no_mangle]
+pub extern "C" fn start_practice_match_js(difficulty: i32, role: i32, bots: i32) {
+    log_info!(Game, "📞 JavaScript called start_practice_match_js({}, {}, {})", difficulty, role, bots);
+    let difficulty = game::BotDifficulty::ALL.get(difficulty.max(0) as usize).copied().unwrap_or(game::BotDifficulty::Normal);
+    let role = usize::try_from(role).ok().and_then(|role| game::BotRole::ALL.get(role).copied());
+    GAME_STATE.with(|gs| {
+        if let Some(state_ptr) = *gs.borrow() {
+            unsafe {
+                (*state_ptr).start_practice_match(difficulty, role, bots.max(1) as usize);
+            }
+        } else {
+            log_warn!(Game, "⚠️ Game state not initialized");
+        }
+    });
+}
+
+/// JavaScript-callable function for the lobby leader: fill `team1` / `team2` empty slots with
+/// bots this client runs (called after `set_current_game_js`, which clears any previous ones)
+#[no_mangle]
+pub extern "C" fn set_bot_backfill_js(team1: i32, team2: i32) {
+    log_info!(Game, "📞 JavaScript called set_bot_backfill_js({}, {})", team1, team2);
+    GAME_STATE.with(|gs| {
+        if let Some(state_ptr) = *gs.borrow() {
+            unsafe {
+                (*state_ptr).set_bot_backfill([team1.max(0) as usize, team2.max(0) as usize]);
+            }
+        } else {
+            log_warn!(Game, "⚠️ Game state not initialized");
         }
     });
 }
@@ -113,18 +201,57 @@ pub extern "C" fn start_game() {
 /// JavaScript-callable function to stop playing mode
 #[no_mangle]
 pub extern "C" fn stop_game() {
-    println!("📞 JavaScript called stop_game()");
+    log_info!(Game, "📞 JavaScript called stop_game()");
     GAME_STATE.with(|gs| {
         if let Some(state_ptr) = *gs.borrow() {
             unsafe {
                 (*state_ptr).stop_playing();
             }
         } else {
-            println!("⚠️ Game state not initialized");
+            log_warn!(Game, "⚠️ Game state not initialized");
         }
     });
 }
 
+/// JavaScript-callable function for the web UI's active tab (0 = map editor, 1 = lobby)
+#[no_mangle]
+pub extern "C" fn set_menu_tab_js(tab: u8) {
+    let tab = match tab {
+        0 => MenuTab::MapEditor,
+        1 => MenuTab::Lobby,
+        _ => return,
+    };
+    REQUESTED_MENU_TAB.with(|requested| requested.set(Some(tab)));
+}
+
+/// JavaScript-callable function for log verbosity: `category` indexes `LogCategory::ALL` (-1 = every
+/// category), `level` is a `LogLevel` id (0 = off ... 4 = debug)
+#[no_mangle]
+pub extern "C" fn set_log_level_js(category: i32, level: u8) {
+    let Some(level) = diagnostics::LogLevel::from_id(level) else {
+        log_warn!(Game, "⚠️ Unknown log level: {}", level);
+        return;
+    };
+    let category = match category {
+        -1 => None,
+        id => match u8::try_from(id).ok().and_then(diagnostics::LogCategory::from_id) {
+            Some(category) => Some(category),
+            None => {
+                log_warn!(Game, "⚠️ Unknown log category: {}", id);
+                return;
+            }
+        },
+    };
+    diagnostics::set_log_level(category, level);
+    log_info!(Game, "📝 Log level {:?} for {}", level, category.map_or("all categories", |c| c.name()));
+}
+
+/// JavaScript-callable function to mirror log lines into the in-game panel
+#[no_mangle]
+pub extern "C" fn set_log_panel_js(open: bool) {
+    diagnostics::set_log_panel(open);
+}
+
 /// JavaScript-callable function to set current game for sync
 #[no_mangle]
 pub extern "C" fn set_current_game_js(game_pubkey_ptr: *const std::os::raw::c_char) {
@@ -134,18 +261,226 @@ pub extern "C" fn set_current_game_js(game_pubkey_ptr: *const std::os::raw::c_ch
             .into_owned()
     };
 
-    println!("📞 JavaScript called set_current_game_js: {}", game_pubkey);
+    log_info!(Game, "📞 JavaScript called set_current_game_js: {}", game_pubkey);
     GAME_STATE.with(|gs| {
         if let Some(state_ptr) = *gs.borrow() {
             unsafe {
                 (*state_ptr).set_current_game(game_pubkey);
             }
         } else {
-            println!("⚠️ Game state not initialized");
+            log_warn!(Game, "⚠️ Game state not initialized");
+        }
+    });
+}
+
+/// JavaScript-callable function to select the game mode rules (health regen, etc.)
+#[no_mangle]
+pub extern "C" fn set_game_mode_js(mode_ptr: *const std::os::raw::c_char) {
+    let mode = unsafe {
+        std::ffi::CStr::from_ptr(mode_ptr)
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    log_info!(Game, "📞 JavaScript called set_game_mode_js: {}", mode);
+    GAME_STATE.with(|gs| {
+        if let Some(state_ptr) = *gs.borrow() {
+            unsafe {
+                (*state_ptr).rules = game::GameRules::for_mode(&mode);
+            }
+        } else {
+            log_warn!(Game, "⚠️ Game state not initialized");
         }
     });
 }
 
+/// JavaScript-callable function to set the spawn loadout (comma-separated weapon ids + the weapon in hand)
+#[no_mangle]
+pub extern "C" fn set_loadout_js(weapons_ptr: *const std::os::raw::c_char, primary_ptr: *const std::os::raw::c_char) {
+    let (weapons, primary) = unsafe {
+        (
+            std::ffi::CStr::from_ptr(weapons_ptr).to_string_lossy().into_owned(),
+            std::ffi::CStr::from_ptr(primary_ptr).to_string_lossy().into_owned(),
+        )
+    };
+
+    log_info!(Game, "📞 JavaScript called set_loadout_js: {} (primary {})", weapons, primary);
+    let Some(loadout) = game::Loadout::parse(&weapons, &primary) else {
+        log_warn!(Game, "⚠️ Ignoring empty loadout");
+        return;
+    };
+    GAME_STATE.with(|gs| {
+        if let Some(state_ptr) = *gs.borrow() {
+            unsafe {
+                (*state_ptr).loadout = loadout;
+            }
+        } else {
+            log_warn!(Game, "⚠️ Game state not initialized");
+        }
+    });
+}
+
+/// JavaScript-callable function to start the round-end presentation (1 = Team A, 2 = Team B)
+#[no_mangle]
+pub extern "C" fn end_round_js(winning_team: u8) {
+    log_info!(Game, "📞 JavaScript called end_round_js: {}", winning_team);
+    GAME_STATE.with(|gs| {
+        if let Some(state_ptr) = *gs.borrow() {
+            unsafe {
+                (*state_ptr).end_round(winning_team);
+            }
+        } else {
+            log_warn!(Game, "⚠️ Game state not initialized");
+        }
+    });
+}
+
+/// JavaScript-callable function to line the freeze countdown up with the on-chain match start
+#[no_mangle]
+pub extern "C" fn sync_freeze_time_js(seconds_since_match_start: f32) {
+    log_info!(Game, "📞 JavaScript called sync_freeze_time_js: {:.2}", seconds_since_match_start);
+    GAME_STATE.with(|gs| {
+        if let Some(state_ptr) = *gs.borrow() {
+            unsafe {
+                (*state_ptr).sync_freeze_time(seconds_since_match_start);
+            }
+        } else {
+            log_warn!(Game, "⚠️ Game state not initialized");
+        }
+    });
+}
+
+/// JavaScript-callable function to set a mixer channel volume
+/// (0 = master, 1 = effects, 2 = music, 3 = UI; volume 0.0 - 1.0)
+#[no_mangle]
+pub extern "C" fn set_audio_volume_js(channel: u8, volume: f32) {
+    match AudioChannel::from_id(channel) {
+        Some(channel) => {
+            log_info!(Game, "🔊 Mixer {:?} volume: {:.2}", channel, volume);
+            audio::mixer::set_channel_volume(channel, volume);
+        }
+        None => log_warn!(Game, "⚠️ Unknown audio channel: {}", channel),
+    }
+}
+
+/// JavaScript-callable function to toggle announcer/UI sounds
+#[no_mangle]
+pub extern "C" fn set_ui_sounds_enabled_js(enabled: bool) {
+    log_info!(Game, "🔊 UI sounds {}", if enabled { "enabled" } else { "disabled" });
+    audio::mixer::set_ui_sounds_enabled(enabled);
+}
+
+/// JavaScript-callable function to pick the color-blind palette
+/// (0 = off, 1 = deuteranopia, 2 = protanopia, 3 = tritanopia)
+#[no_mangle]
+pub extern "C" fn set_colorblind_mode_js(mode: u8) {
+    match game::palette::ColorblindMode::from_id(mode) {
+        Some(mode) => {
+            log_info!(Render, "🎨 Color-blind mode: {:?}", mode);
+            game::palette::set_colorblind_mode(mode);
+        }
+        None => log_warn!(Game, "⚠️ Unknown color-blind mode: {}", mode),
+    }
+}
+
+/// JavaScript-callable function to tone down a motion/flash effect
+/// (0 = screen shake, 1 = flash, 2 = view bob; intensity 0.0 - 1.0)
+#[no_mangle]
+pub extern "C" fn set_comfort_setting_js(effect: u8, enabled: bool, intensity: f32) {
+    match game::comfort::ComfortEffect::from_id(effect) {
+        Some(effect) => {
+            log_info!(Game, "🧘 Comfort {:?}: {} at {:.2}", effect, if enabled { "on" } else { "off" }, intensity);
+            game::comfort::set_comfort(effect, enabled, intensity);
+        }
+        None => log_warn!(Game, "⚠️ Unknown comfort effect: {}", effect),
+    }
+}
+
+/// JavaScript-callable function to set gun placement (left-handed mirroring, offsets in units, viewmodel FOV in degrees)
+#[no_mangle]
+pub extern "C" fn set_viewmodel_js(left_handed: bool, offset_x: f32, offset_y: f32, offset_z: f32, fov: f32) {
+    let settings = game::viewmodel::ViewmodelSettings { left_handed, offset_x, offset_y, offset_z, fov };
+    log_info!(Render, "🔫 Viewmodel: {:?}", settings);
+    game::viewmodel::set_viewmodel(settings);
+}
+
+/// JavaScript-callable function to pick how the world is drawn
+/// (0 = full 3D, 1 = retro raycaster for low-spec devices)
+#[no_mangle]
+pub extern "C" fn set_render_mode_js(mode: u8) {
+    match game::graphics::RenderMode::from_id(mode) {
+        Some(mode) => {
+            log_info!(Render, "🖥️ Render mode: {:?}", mode);
+            game::graphics::set_render_mode(mode);
+        }
+        None => log_warn!(Game, "⚠️ Unknown render mode: {}", mode),
+    }
+}
+
+/// JavaScript-callable function to set how far map geometry is drawn (units) and whether it fades into the sky
+#[no_mangle]
+pub extern "C" fn set_view_distance_js(draw_distance: f32, fog: bool) {
+    let settings = game::graphics::ViewDistance { draw_distance, fog };
+    log_info!(Render, "🌫️ View distance: {:?}", settings);
+    game::graphics::set_view_distance(settings);
+}
+
+/// JavaScript-callable function to toggle film grain and chromatic aberration
+#[no_mangle]
+pub extern "C" fn set_film_effects_js(enabled: bool) {
+    log_info!(Render, "🎞️ Film effects: {}", if enabled { "on" } else { "off" });
+    game::graphics::set_film_effects(enabled);
+}
+
+/// JavaScript-callable function to apply the player's HUD layout (JSON saved by the web settings)
+#[no_mangle]
+pub extern "C" fn set_hud_layout_js(layout_ptr: *const std::os::raw::c_char) {
+    let layout = unsafe {
+        std::ffi::CStr::from_ptr(layout_ptr)
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    match game::hud_layout::HudLayout::from_json(&layout) {
+        Ok(layout) => {
+            log_info!(Render, "🧩 HUD layout: {:?}", layout);
+            game::hud_layout::set_hud_layout(layout);
+        }
+        Err(e) => log_warn!(Render, "⚠️ Invalid HUD layout: {}", e),
+    }
+}
+
+/// JavaScript-callable function to switch the in-engine language ("en", "es")
+#[no_mangle]
+pub extern "C" fn set_language_js(code_ptr: *const std::os::raw::c_char) {
+    let code = unsafe {
+        std::ffi::CStr::from_ptr(code_ptr)
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    if i18n::set_language(&code) {
+        log_info!(Game, "🌐 Language: {}", code);
+    } else {
+        log_warn!(Game, "⚠️ Unknown language: {}", code);
+    }
+}
+
+/// JavaScript-callable function to play an announcer/UI sound by id (e.g. "click", "countdown_tick")
+#[no_mangle]
+pub extern "C" fn play_ui_sound_js(sound_ptr: *const std::os::raw::c_char) {
+    let sound_id = unsafe {
+        std::ffi::CStr::from_ptr(sound_ptr)
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    match audio::UiSound::from_id(&sound_id) {
+        Some(sound) => audio::play_ui_sound(sound),
+        None => log_warn!(Game, "⚠️ Unknown UI sound: {}", sound_id),
+    }
+}
+
 /// JavaScript-callable: set whether settings overlay is open (to pause input and show cursor)
 #[no_mangle]
 pub extern "C" fn set_settings_open(is_open: bool) {
@@ -217,6 +552,42 @@ pub extern "C" fn get_player_position(out_ptr: *mut f32) {
     });
 }
 
+/// JavaScript-callable function to get other players for the web minimap (fog-of-war applied)
+/// Writes up to `max` entries of 6 floats (x, z, yaw, team, alive, visibility) and returns the count
+#[no_mangle]
+pub extern "C" fn get_other_players(out_ptr: *mut f32, max: i32) -> i32 {
+    let mut count = 0;
+    GAME_STATE.with(|gs| {
+        if let Some(state_ptr) = *gs.borrow() {
+            unsafe {
+                let state = &*state_ptr;
+                for (i, entry) in state.minimap_entries().iter().take(max.max(0) as usize).enumerate() {
+                    for (j, value) in entry.iter().enumerate() {
+                        *out_ptr.add(i * 6 + j) = *value;
+                    }
+                    count += 1;
+                }
+            }
+        }
+    });
+    count
+}
+
+/// JavaScript-callable function for the web minimap's loading ring
+/// Returns the share of a streamed map received (0 to 1), or -1 when no map is streaming
+#[no_mangle]
+pub extern "C" fn get_map_stream_progress() -> f32 {
+    let mut progress = -1.0;
+    GAME_STATE.with(|gs| {
+        if let Some(state_ptr) = *gs.borrow() {
+            unsafe {
+                progress = (*state_ptr).map_stream_progress().unwrap_or(-1.0);
+            }
+        }
+    });
+    progress
+}
+
 /// Apply Solana-themed modern colors to ImGui
 pub fn apply_solana_ui_colors(_ui: &imgui::Ui) {
     // Note: Due to imgui 0.12 API limitations, we can't easily mutate the global style
@@ -260,6 +631,9 @@ fn draw_editor_ui(
 }
 
 fn main() {
+    // Panics go to the web UI with the breadcrumbs leading up to them
+    diagnostics::install_panic_hook();
+
     // Initialize the Raylib window with MSAA for better quality
     let (mut rl, thread) = raylib::init()
         .size(1280, 720)
@@ -269,6 +643,9 @@ fn main() {
 
     rl.set_target_fps(60);
 
+    // TTF atlases for HUD text (health numbers, prompts, nameplates, banners)
+    game::fonts::load_hud_fonts(&mut rl, &thread);
+
     // Set clipping planes to reduce z-fighting (depth precision issues)
     // Default is usually (0.01, 1000) which can cause z-fighting
     // Using (0.1, 200) gives better depth precision for close objects
@@ -288,6 +665,9 @@ fn main() {
     // Create game state
     let mut game_state = GameState::new();
 
+    // Render target for the 3D scene while film effects need its pixels (recreated on resize)
+    let mut scene_target: Option<RenderTexture2D> = None;
+
     // Initialize touch controls only on touch-enabled devices
     let screen_w = rl.get_screen_width() as f32;
     let screen_h = rl.get_screen_height() as f32;
@@ -311,11 +691,11 @@ fn main() {
     };
 
     // Disable built-in touch controls - we use React VirtualJoystick instead
-    println!("🎮 Using React VirtualJoystick - built-in touch controls disabled");
+    log_info!(Game, "🎮 Using React VirtualJoystick - built-in touch controls disabled");
 
     // Set the game state pointer for JavaScript interop
     set_game_state_ptr(&mut game_state as *mut GameState);
-    println!("✅ Game state pointer set for JavaScript interop");
+    log_info!(Game, "✅ Game state pointer set for JavaScript interop");
 
     // Create a new map builder
     let mut map_builder = MapBuilder::new("My Map".to_string());
@@ -329,25 +709,34 @@ fn main() {
     // Track if style has been applied
     let mut style_applied = false;
 
+    // Whether the editor walkthrough has the mouse captured
+    let mut editor_mouse_captured = false;
+
+    // Dynamic music (ambient in menus/editor, exploration + combat layers in-game)
+    let mut music = MusicController::new();
+
+    // RPC/rollup latency probe (runs at startup and when the connection settings ask for it)
+    let mut endpoint_probe = EndpointProbe::new();
+
+    // Last mode/tab and map seen, for crash breadcrumbs
+    let mut last_screen = None;
+    let mut last_map_name: Option<String> = None;
+
     // Main game loop
     while !rl.window_should_close() {
         let delta = rl.get_frame_time();
 
-        // Handle save/load
+        // Quick save to / open the local map library
         if rl.is_key_pressed(KeyboardKey::KEY_F5) {
-            match map_builder.save_map("map.json") {
-                Ok(_) => println!("Map saved successfully!"),
-                Err(e) => eprintln!("Failed to save map: {}", e),
-            }
+            map_builder.quick_save();
         }
         if rl.is_key_pressed(KeyboardKey::KEY_F9) {
-            match MapBuilder::load_map("map.json") {
-                Ok(loaded) => {
-                    map_builder = loaded;
-                    println!("Map loaded successfully!");
-                }
-                Err(e) => eprintln!("Failed to load map: {}", e),
-            }
+            map_builder.open_library();
+        }
+
+        // Follow the web UI's tab (the editor or the lobby backdrop)
+        if let Some(tab) = REQUESTED_MENU_TAB.with(|requested| requested.take()) {
+            menu_state.current_tab = tab;
         }
 
         // Start imgui frame
@@ -380,16 +769,16 @@ fn main() {
 
         // Check if game should start (when game state changes to 1)
         if menu_state.game_should_start {
-            println!("🎮 Starting game - transitioning to gameplay!");
+            log_info!(Game, "🎮 Starting game - transitioning to gameplay!");
 
             // Fetch the map from blockchain using JavaScript
             if let Some(map_id) = menu_state.current_map_name.clone() {
-                println!("🗺️ Fetching map data for ID: '{}'", map_id);
+                log_info!(Solana, "🗺️ Fetching map data for ID: '{}'", map_id);
                 menu_state.fetch_map_data(&map_id);
                 menu_state.game_should_start = false;
                 menu_state.waiting_for_map_data = true;
             } else {
-                println!("⚠️ No map ID in game data, cannot start game");
+                log_warn!(Solana, "⚠️ No map ID in game data, cannot start game");
                 menu_state.game_should_start = false;
             }
         }
@@ -444,6 +833,57 @@ fn main() {
                     }
                 }
             }
+
+            // Read streamer-safe effects toggle from window.__streamer_safe if present
+            let js_streamer = CString::new(
+                "(typeof window.__streamer_safe === 'boolean') ? String(window.__streamer_safe) : 'null'"
+            ).unwrap();
+            let streamer_ptr = emscripten_run_script_string(js_streamer.as_ptr());
+            if !streamer_ptr.is_null() {
+                if let Ok(streamer_str) = CStr::from_ptr(streamer_ptr).to_str() {
+                    if streamer_str != "null" {
+                        game_state.streamer_safe = streamer_str == "true";
+                    }
+                }
+            }
+
+            // Read match event export toggle from window.__export_match_events if present
+            let js_export = CString::new(
+                "(typeof window.__export_match_events === 'boolean') ? String(window.__export_match_events) : 'null'"
+            ).unwrap();
+            let export_ptr = emscripten_run_script_string(js_export.as_ptr());
+            if !export_ptr.is_null() {
+                if let Ok(export_str) = CStr::from_ptr(export_ptr).to_str() {
+                    if export_str != "null" {
+                        game_state.export_match_events = export_str == "true";
+                    }
+                }
+            }
+
+            // Read netcode tunables from window.__netcode_config when marked dirty
+            let js_netcode = CString::new(
+                r#"
+                (function(){
+                    if (!window.__netcode_config_dirty) return '';
+                    window.__netcode_config_dirty = false;
+                    return JSON.stringify(window.__netcode_config || {});
+                })()
+                "#
+            ).unwrap();
+            let netcode_ptr = emscripten_run_script_string(js_netcode.as_ptr());
+            if !netcode_ptr.is_null() {
+                if let Ok(netcode_str) = CStr::from_ptr(netcode_ptr).to_str() {
+                    if !netcode_str.is_empty() {
+                        match game::NetcodeConfig::from_json(netcode_str) {
+                            Ok(config) => {
+                                game_state.netcode = config;
+                                log_info!(Net, "🌐 Netcode config updated: {}", config.to_json());
+                            }
+                            Err(e) => log_warn!(Game, "⚠️ Invalid netcode config: {}", e),
+                        }
+                    }
+                }
+            }
         }
 
         // Update game state if playing
@@ -452,28 +892,87 @@ fn main() {
         // Capture mouse if in playing mode
         game_state.capture_mouse_if_playing(&mut rl);
 
+        // Update music for the current context and combat intensity
+        let music_context = match game_state.mode {
+            game::GameMode::Playing => MusicContext::InGame,
+            game::GameMode::DebugMenu if menu_state.current_tab == MenuTab::MapEditor => MusicContext::Editor,
+            game::GameMode::DebugMenu => MusicContext::Menu,
+        };
+        music.set_context(music_context);
+        music.add_combat_noise(game_state.take_combat_noise());
+        music.update(delta);
+
+        // Collect endpoint latency results
+        endpoint_probe.update(delta);
+
         // Show map editor UI when in editor mode
         if game_state.mode == game::GameMode::DebugMenu && menu_state.current_tab == MenuTab::MapEditor {
             mouse_over_ui = draw_editor_ui(ui, &mut map_builder, viewport_width as f32, &mut style_applied);
             map_builder.update(&rl, delta, mouse_over_ui);
+
+            // The walkthrough looks around with the mouse like a match does
+            if map_builder.is_walkthrough() != editor_mouse_captured {
+                editor_mouse_captured = map_builder.is_walkthrough();
+                if editor_mouse_captured {
+                    rl.disable_cursor();
+                } else {
+                    rl.enable_cursor();
+                }
+            }
+        }
+
+        // Keep the scene render target matched to the screen, and only while it's needed
+        let (screen_w, screen_h) = (rl.get_screen_width(), rl.get_screen_height());
+        let wants_scene_target = game_state.mode == game::GameMode::Playing && game::graphics::film_effects();
+        if !wants_scene_target || scene_target.as_ref().is_some_and(|t| t.texture.width != screen_w || t.texture.height != screen_h) {
+            scene_target = None;
+        }
+        if wants_scene_target && scene_target.is_none() {
+            scene_target = rl
+                .load_render_texture(&thread, screen_w as u32, screen_h as u32)
+                .map_err(|e| log_warn!(Render, "⚠️ Scene render target unavailable: {}", e))
+                .ok();
+        }
+
+        // Crash breadcrumbs for mode, tab and map changes
+        let screen = (game_state.mode, menu_state.current_tab);
+        if last_screen != Some(screen) {
+            diagnostics::transition(&format!("mode {:?}, tab {:?}", screen.0, screen.1));
+            last_screen = Some(screen);
+        }
+        let map_name = game_state.map.as_ref().map(|map| map.name.as_str());
+        if map_name != last_map_name.as_deref() {
+            diagnostics::transition(&format!("map: {}", map_name.unwrap_or("none")));
+            last_map_name = map_name.map(str::to_string);
         }
 
         // Render 3D scene
         let mut d = rl.begin_drawing(&thread);
-        d.clear_background(Color::new(13, 13, 17, 255)); // Dark purple-tinted background to match Solana theme
+        // Sky color comes from the map's ambiance (dark purple-tinted by default to match the Solana theme)
+        let sky = match game_state.mode {
+            game::GameMode::Playing => game_state.ambiance().sky_color(),
+            game::GameMode::DebugMenu if menu_state.current_tab == MenuTab::MapEditor => map_builder.map.ambiance.sky_color(),
+            game::GameMode::DebugMenu => game_state.backdrop_map().ambiance.sky_color(),
+        };
+        d.clear_background(sky);
 
         // Render based on mode
         match game_state.mode {
             game::GameMode::Playing => {
-                game_state.render(&mut d, &thread);
+                game_state.render(&mut d, &thread, scene_target.as_ref());
+                game_state.capture_pending_screenshot(&d, &thread);
             },
             game::GameMode::DebugMenu => {
-                if menu_state.current_tab == MenuTab::MapEditor {
-                    map_builder.render(&mut d, &thread, viewport_width);
+                match menu_state.current_tab {
+                    MenuTab::MapEditor => map_builder.render(&mut d, &thread, viewport_width),
+                    MenuTab::Lobby => game_state.render_lobby_backdrop(&mut d),
                 }
             }
         }
 
+        // Log lines mirrored in-game (toggled from the console)
+        diagnostics::draw_log_panel(&mut d);
+
         // End imgui frame - this draws the imgui overlay
         gui.end();
     }
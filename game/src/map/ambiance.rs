@@ -0,0 +1,136 @@
+use raylib::prelude::*;
+use serde::{Deserialize, Serialize};
+use borsh::{BorshSerialize, BorshDeserialize};
+use crate::i18n::t;
+
+/// Fog density is stored in thousandths per world unit (40 = 0.04, enough to swallow the far wall)
+const FOG_DENSITY_SCALE: f32 = 1000.0;
+
+/// Brightest ambient light the editor offers (percent)
+pub const MAX_LIGHT: u8 = 150;
+
+/// Mood of a map: the sky behind it, how hazy the air is and how brightly objects are lit
+/// Eight bytes on the wire, so every map can afford one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct Ambiance {
+    /// Clear color behind the world
+    pub sky: [u8; 3],
+    /// Color distant objects fade toward
+    pub fog: [u8; 3],
+    /// Fog density in thousandths per world unit (0 = clear air)
+    pub fog_density: u8,
+    /// Object brightness in percent (100 = colors as placed)
+    pub light: u8,
+}
+
+impl Default for Ambiance {
+    fn default() -> Self {
+        AmbiancePreset::Night.ambiance()
+    }
+}
+
+impl Ambiance {
+    pub fn sky_color(&self) -> Color {
+        Color::new(self.sky[0], self.sky[1], self.sky[2], 255)
+    }
+
+    pub fn fog_color(&self) -> Color {
+        Color::new(self.fog[0], self.fog[1], self.fog[2], 255)
+    }
+
+    /// How much of an object `distance` units away is hidden by fog (0 = none, 1 = all)
+    /// Exponential-squared, so nearby cover stays crisp and only the distance washes out
+    pub fn fog_amount(&self, distance: f32) -> f32 {
+        let depth = self.fog_density as f32 / FOG_DENSITY_SCALE * distance.max(0.0);
+        1.0 - (-depth * depth).exp()
+    }
+
+    /// `color` under this map's ambient light
+    pub fn lit(&self, color: Color) -> Color {
+        let scale = |channel: u8| (channel as f32 * self.light as f32 / 100.0).round().min(255.0) as u8;
+        Color::new(scale(color.r), scale(color.g), scale(color.b), color.a)
+    }
+
+    /// `color` as seen from `distance` units away: lit, then blended toward the fog
+    pub fn shade(&self, color: Color, distance: f32) -> Color {
+        let lit = self.lit(color);
+        let amount = self.fog_amount(distance);
+        let blend = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * amount).round() as u8;
+        Color::new(blend(lit.r, self.fog[0]), blend(lit.g, self.fog[1]), blend(lit.b, self.fog[2]), color.a)
+    }
+
+    /// The preset these settings came from, if they haven't been tweaked since
+    pub fn preset(&self) -> Option<AmbiancePreset> {
+        AmbiancePreset::ALL.into_iter().find(|preset| preset.ambiance() == *self)
+    }
+}
+
+/// Starting points offered in the editor; authors can tweak any value afterwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmbiancePreset {
+    /// The original dark purple-tinted look, clear air
+    Night,
+    Day,
+    Dusk,
+    Overcast,
+    Toxic,
+    Blackout,
+}
+
+impl AmbiancePreset {
+    pub const ALL: [AmbiancePreset; 6] = [
+        AmbiancePreset::Night,
+        AmbiancePreset::Day,
+        AmbiancePreset::Dusk,
+        AmbiancePreset::Overcast,
+        AmbiancePreset::Toxic,
+        AmbiancePreset::Blackout,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AmbiancePreset::Night => t("editor.ambiance.night"),
+            AmbiancePreset::Day => t("editor.ambiance.day"),
+            AmbiancePreset::Dusk => t("editor.ambiance.dusk"),
+            AmbiancePreset::Overcast => t("editor.ambiance.overcast"),
+            AmbiancePreset::Toxic => t("editor.ambiance.toxic"),
+            AmbiancePreset::Blackout => t("editor.ambiance.blackout"),
+        }
+    }
+
+    pub fn ambiance(&self) -> Ambiance {
+        let (sky, fog, fog_density, light) = match self {
+            AmbiancePreset::Night => ([13, 13, 17], [13, 13, 17], 0, 100),
+            AmbiancePreset::Day => ([120, 160, 210], [170, 195, 220], 8, 115),
+            AmbiancePreset::Dusk => ([70, 40, 80], [120, 70, 90], 15, 80),
+            AmbiancePreset::Overcast => ([90, 95, 100], [110, 112, 115], 25, 85),
+            AmbiancePreset::Toxic => ([20, 35, 15], [60, 110, 40], 40, 70),
+            AmbiancePreset::Blackout => ([3, 3, 5], [5, 5, 8], 50, 45),
+        };
+        Ambiance { sky, fog, fog_density, light }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_keeps_the_old_look_and_fog_fades_distant_colors() {
+        let night = Ambiance::default();
+        assert_eq!(night.sky_color(), Color::new(13, 13, 17, 255));
+        assert_eq!(night.preset(), Some(AmbiancePreset::Night));
+        let red = Color::new(200, 40, 40, 255);
+        assert_eq!(night.shade(red, 45.0), red);
+
+        let toxic = AmbiancePreset::Toxic.ambiance();
+        assert!(toxic.fog_amount(2.0) < 0.01);
+        assert!(toxic.fog_amount(10.0) < toxic.fog_amount(30.0));
+        assert!(toxic.fog_amount(60.0) > 0.99);
+        assert_eq!(toxic.shade(red, 60.0), toxic.fog_color());
+
+        let tweaked = Ambiance { light: 50, ..toxic };
+        assert_eq!(tweaked.preset(), None);
+        assert_eq!(tweaked.lit(red), Color::new(100, 20, 20, 255));
+    }
+}
@@ -0,0 +1,105 @@
+use raylib::prelude::*;
+use serde::{Deserialize, Serialize};
+use borsh::{BorshSerialize, BorshDeserialize};
+use crate::i18n::t;
+use super::map::WORLD_HALF_SIZE;
+
+/// Most ambient emitters one map can carry
+pub const MAX_AMBIENT_EMITTERS: usize = 16;
+
+/// Smallest and largest hearing radius an emitter can have (world units)
+pub const AMBIENT_RADIUS_RANGE: (u8, u8) = (2, 40);
+
+/// Built-in loops a map can place around its world: the game's own sounds, since map files can't carry audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub enum AmbientSound {
+    Wind,
+    Hum,
+    Machinery,
+}
+
+impl AmbientSound {
+    pub const ALL: [AmbientSound; 3] = [AmbientSound::Wind, AmbientSound::Hum, AmbientSound::Machinery];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AmbientSound::Wind => t("editor.ambient.wind"),
+            AmbientSound::Hum => t("editor.ambient.hum"),
+            AmbientSound::Machinery => t("editor.ambient.machinery"),
+        }
+    }
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            AmbientSound::Wind => "/assets/ambient/audio/wind.wav",
+            AmbientSound::Hum => "/assets/ambient/audio/hum.wav",
+            AmbientSound::Machinery => "/assets/ambient/audio/machinery.wav",
+        }
+    }
+}
+
+/// A looping sound placed in the world, heard within its radius and louder towards its center
+/// Nine bytes on the wire; emitters aren't map objects, so the game never draws or collides with them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct AmbientEmitter {
+    pub sound: AmbientSound,
+    /// Position in hundredths of a unit, like `MapObject`
+    pub pos_x: i16,
+    pub pos_y: i16,
+    pub pos_z: i16,
+    /// Hearing radius in world units
+    pub radius: u8,
+    /// Loudness at the center in percent
+    pub volume: u8,
+}
+
+impl AmbientEmitter {
+    pub fn new(sound: AmbientSound, position: Vector3) -> Self {
+        let mut emitter = Self { sound, pos_x: 0, pos_y: 0, pos_z: 0, radius: 12, volume: 60 };
+        emitter.set_position(position);
+        emitter
+    }
+
+    pub fn position(&self) -> Vector3 {
+        Vector3::new(self.pos_x as f32 / 100.0, self.pos_y as f32 / 100.0, self.pos_z as f32 / 100.0)
+    }
+
+    /// Set the position (clamped to world bounds)
+    pub fn set_position(&mut self, position: Vector3) {
+        self.pos_x = (position.x.clamp(-WORLD_HALF_SIZE, WORLD_HALF_SIZE) * 100.0) as i16;
+        self.pos_y = (position.y.clamp(-WORLD_HALF_SIZE, WORLD_HALF_SIZE) * 100.0) as i16;
+        self.pos_z = (position.z.clamp(-WORLD_HALF_SIZE, WORLD_HALF_SIZE) * 100.0) as i16;
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius.clamp(AMBIENT_RADIUS_RANGE.0, AMBIENT_RADIUS_RANGE.1) as f32
+    }
+
+    /// Loudness (0 to 1) for a listener at `listener`: full volume at the center, fading
+    /// smoothly to silence at the radius
+    pub fn gain(&self, listener: Vector3) -> f32 {
+        let falloff = (1.0 - listener.distance_to(self.position()) / self.radius()).clamp(0.0, 1.0);
+        falloff * falloff * self.volume.min(100) as f32 / 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emitters_fade_out_at_their_radius() {
+        let mut emitter = AmbientEmitter::new(AmbientSound::Hum, Vector3::new(2.0, 1.0, 0.0));
+        emitter.radius = 10;
+        emitter.volume = 80;
+        assert_eq!(emitter.gain(Vector3::new(2.0, 1.0, 0.0)), 0.8);
+        assert!((emitter.gain(Vector3::new(7.0, 1.0, 0.0)) - 0.2).abs() < 1e-6);
+        assert_eq!(emitter.gain(Vector3::new(12.0, 1.0, 0.0)), 0.0);
+
+        // Out-of-range values from a hand-edited file are clamped
+        emitter.radius = 0;
+        assert_eq!(emitter.radius(), AMBIENT_RADIUS_RANGE.0 as f32);
+        emitter.set_position(Vector3::new(100.0, 0.0, 0.0));
+        assert_eq!(emitter.position().x, WORLD_HALF_SIZE);
+    }
+}
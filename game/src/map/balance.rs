@@ -0,0 +1,187 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use raylib::prelude::*;
+
+use crate::game::BASE_MOVE_SPEED;
+use super::map::{Map, ModelType, WORLD_HALF_SIZE, WORLD_SIZE};
+use super::spatial::SpatialIndex;
+
+/// Walk grid resolution (world units per cell)
+pub const CELL_SIZE: f32 = 1.0;
+const GRID: usize = (WORLD_SIZE / CELL_SIZE) as usize;
+
+/// Player proportions for the walkability test (same as the editor walkthrough)
+const PLAYER_RADIUS: f32 = 0.3;
+const PLAYER_HEIGHT: f32 = 1.8;
+const STEP_HEIGHT: f32 = 0.4;
+const EYE_HEIGHT: f32 = 1.7;
+
+/// Difference in seconds to map center above which a map counts as lopsided
+pub const CENTER_ASYMMETRY_WARNING: f32 = 1.0;
+
+/// Team index into the per-team arrays (blue first)
+pub const BLUE: usize = 0;
+pub const RED: usize = 1;
+
+/// Spawn balance analysis: walk times from each team's spawns and spawn-to-spawn sightlines.
+/// Travel times come from a coarse walk grid (8-way, no corner cutting) at walking speed.
+#[derive(Debug, Clone, Default)]
+pub struct SpawnBalance {
+    /// Spawn point positions per team
+    pub spawns: [Vec<Vector3>; 2],
+    /// Seconds from the team's nearest spawn to each cell (row-major, x then z; None = unreachable)
+    pub travel_time: [Vec<Option<f32>>; 2],
+    /// Blue/red spawn pairs with a clear line of sight between them at eye height
+    pub sightlines: Vec<(Vector3, Vector3)>,
+    /// Seconds from each team's spawns to the walkable cell nearest the map center
+    pub center_time: [Option<f32>; 2],
+}
+
+impl SpawnBalance {
+    pub fn analyze(map: &Map) -> Self {
+        let index = SpatialIndex::build(map);
+        let walkable: Vec<bool> = (0..GRID * GRID)
+            .map(|cell| !index.blocks_player(cell_center(cell), PLAYER_RADIUS, PLAYER_HEIGHT, STEP_HEIGHT))
+            .collect();
+
+        let mut spawns: [Vec<Vector3>; 2] = Default::default();
+        for object in &map.objects {
+            match object.model_type {
+                ModelType::SpawnPointBlue => spawns[BLUE].push(object.get_position()),
+                ModelType::SpawnPointRed => spawns[RED].push(object.get_position()),
+                _ => {}
+            }
+        }
+
+        let travel_time = [
+            walk_times(&walkable, &spawns[BLUE]),
+            walk_times(&walkable, &spawns[RED]),
+        ];
+
+        let eye = Vector3::new(0.0, EYE_HEIGHT, 0.0);
+        let sightlines = spawns[BLUE]
+            .iter()
+            .flat_map(|&blue| spawns[RED].iter().map(move |&red| (blue, red)))
+            .filter(|&(blue, red)| !map.is_line_blocked(blue + eye, red + eye))
+            .collect();
+
+        let center = (0..GRID * GRID)
+            .filter(|&cell| walkable[cell])
+            .min_by(|&a, &b| cell_center(a).length().total_cmp(&cell_center(b).length()));
+        let center_time = [
+            center.and_then(|cell| travel_time[BLUE][cell]),
+            center.and_then(|cell| travel_time[RED][cell]),
+        ];
+
+        Self { spawns, travel_time, sightlines, center_time }
+    }
+
+    /// How much sooner one team reaches the center (seconds), when both can
+    pub fn center_asymmetry(&self) -> Option<f32> {
+        Some((self.center_time[BLUE]? - self.center_time[RED]?).abs())
+    }
+
+    /// (cell center, blue time, red time) for every cell at least one team can reach
+    pub fn cells(&self) -> impl Iterator<Item = (Vector3, Option<f32>, Option<f32>)> + '_ {
+        (0..GRID * GRID)
+            .map(|cell| (cell_center(cell), self.travel_time[BLUE][cell], self.travel_time[RED][cell]))
+            .filter(|(_, blue, red)| blue.is_some() || red.is_some())
+    }
+}
+
+/// Center of a grid cell on the ground
+fn cell_center(cell: usize) -> Vector3 {
+    let (x, z) = (cell % GRID, cell / GRID);
+    Vector3::new(
+        -WORLD_HALF_SIZE + (x as f32 + 0.5) * CELL_SIZE,
+        0.0,
+        -WORLD_HALF_SIZE + (z as f32 + 0.5) * CELL_SIZE,
+    )
+}
+
+/// Grid cell containing a world position
+fn cell_at(position: Vector3) -> usize {
+    let x = ((position.x + WORLD_HALF_SIZE) / CELL_SIZE).clamp(0.0, GRID as f32 - 1.0) as usize;
+    let z = ((position.z + WORLD_HALF_SIZE) / CELL_SIZE).clamp(0.0, GRID as f32 - 1.0) as usize;
+    z * GRID + x
+}
+
+/// Dijkstra from every start cell at once; returns seconds at walking speed per cell
+fn walk_times(walkable: &[bool], starts: &[Vector3]) -> Vec<Option<f32>> {
+    // Distances in hundredths of a cell so the heap can order integers
+    const STRAIGHT: u32 = 100;
+    const DIAGONAL: u32 = 141;
+
+    let mut distance: Vec<Option<u32>> = vec![None; GRID * GRID];
+    let mut queue = BinaryHeap::new();
+    for &start in starts {
+        let cell = cell_at(start);
+        if walkable[cell] && distance[cell].is_none() {
+            distance[cell] = Some(0);
+            queue.push(Reverse((0, cell)));
+        }
+    }
+
+    while let Some(Reverse((cost, cell))) = queue.pop() {
+        if distance[cell].is_some_and(|best| cost > best) {
+            continue;
+        }
+        let (x, z) = ((cell % GRID) as i32, (cell / GRID) as i32);
+        for (dx, dz) in [(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (1, -1), (-1, 1), (1, 1)] {
+            let (nx, nz) = (x + dx, z + dz);
+            if nx < 0 || nz < 0 || nx >= GRID as i32 || nz >= GRID as i32 {
+                continue;
+            }
+            let next = nz as usize * GRID + nx as usize;
+            let diagonal = dx != 0 && dz != 0;
+            // No cutting corners: both orthogonal neighbours must be open for a diagonal step
+            if !walkable[next]
+                || (diagonal && (!walkable[z as usize * GRID + nx as usize] || !walkable[nz as usize * GRID + x as usize]))
+            {
+                continue;
+            }
+            let next_cost = cost + if diagonal { DIAGONAL } else { STRAIGHT };
+            if distance[next].map_or(true, |best| next_cost < best) {
+                distance[next] = Some(next_cost);
+                queue.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+
+    distance
+        .into_iter()
+        .map(|d| d.map(|d| d as f32 / STRAIGHT as f32 * CELL_SIZE / BASE_MOVE_SPEED))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::MapObject;
+
+    #[test]
+    fn test_sightlines_and_center_times() {
+        let mut map = Map::new("Balance".to_string());
+        for (model_type, x) in [(ModelType::SpawnPointBlue, -19.5), (ModelType::SpawnPointRed, 19.5)] {
+            let mut spawn = MapObject::new(model_type);
+            spawn.set_position(Vector3::new(x, 0.0, 0.5));
+            map.add_object(spawn);
+        }
+
+        // Open ground: spawns see each other and reach the center at about the same time
+        let balance = SpawnBalance::analyze(&map);
+        assert_eq!(balance.sightlines.len(), 1);
+        assert!(balance.center_asymmetry().unwrap() < CENTER_ASYMMETRY_WARNING);
+
+        // A wall in front of red's spawn blocks the sightline and makes red walk around it
+        let mut wall = MapObject::new(ModelType::Cube);
+        wall.set_position(Vector3::new(16.0, 1.5, 0.5));
+        wall.set_scale(Vector3::new(1.0, 3.0, 20.0));
+        map.add_object(wall);
+
+        let balance = SpawnBalance::analyze(&map);
+        assert!(balance.sightlines.is_empty());
+        assert!(balance.center_time[RED].unwrap() > balance.center_time[BLUE].unwrap() + CENTER_ASYMMETRY_WARNING);
+    }
+}
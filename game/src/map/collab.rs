@@ -0,0 +1,384 @@
+use std::collections::{HashMap, VecDeque};
+
+use base64::{Engine as _, engine::general_purpose};
+use raylib::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::library;
+use super::map::{Map, MapObject};
+
+/// Seconds between pushes of local edits and cursor updates
+pub const SYNC_INTERVAL: f32 = 0.1;
+
+/// Seconds without any message before a collaborator is dropped from the list
+pub const COLLABORATOR_TIMEOUT: f64 = 5.0;
+
+/// Commits the host remembers for conflict checks (edits based on anything older always resync)
+const COMMIT_HISTORY: usize = 256;
+
+/// One change to the object list, as shared between collaborators
+///
+/// The editor has no command history, so these come from diffing the map
+/// against the last state everyone agreed on (see `diff_objects`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum EditOp {
+    /// New object inserted at `index`
+    Place { index: usize, object: MapObject },
+    /// Object at `index` moved, rotated, scaled or recolored
+    Update { index: usize, object: MapObject },
+    /// Object at `index` deleted
+    Delete { index: usize },
+}
+
+impl EditOp {
+    /// Apply to a map; false if the index doesn't exist there (the copies diverged)
+    pub fn apply(&self, map: &mut Map) -> bool {
+        match self {
+            EditOp::Place { index, object } if *index <= map.objects.len() => {
+                map.insert_object(*index, object.clone());
+                true
+            }
+            EditOp::Place { .. } => false,
+            EditOp::Update { index, object } => match map.objects.get_mut(*index) {
+                Some(slot) => {
+                    *slot = object.clone();
+                    true
+                }
+                None => false,
+            },
+            EditOp::Delete { index } => map.remove_object(*index).is_some(),
+        }
+    }
+}
+
+/// Ops turning `before` into `after`, applied in order
+///
+/// Same length: one update per changed object. Otherwise the common start and
+/// end are kept and the middle is deleted and placed again, which covers the
+/// editor's single place / delete / duplicate between two syncs exactly.
+pub fn diff_objects(before: &[MapObject], after: &[MapObject]) -> Vec<EditOp> {
+    if before.len() == after.len() {
+        return before
+            .iter()
+            .zip(after)
+            .enumerate()
+            .filter(|(_, (old, new))| old != new)
+            .map(|(index, (_, new))| EditOp::Update { index, object: new.clone() })
+            .collect();
+    }
+
+    let prefix = before.iter().zip(after).take_while(|(old, new)| old == new).count();
+    let suffix = before
+        .iter()
+        .rev()
+        .zip(after.iter().rev())
+        .take(before.len().min(after.len()) - prefix)
+        .take_while(|(old, new)| old == new)
+        .count();
+
+    let mut ops: Vec<EditOp> = (prefix..before.len() - suffix).map(|_| EditOp::Delete { index: prefix }).collect();
+    ops.extend(
+        after[prefix..after.len() - suffix]
+            .iter()
+            .enumerate()
+            .map(|(i, object)| EditOp::Place { index: prefix + i, object: object.clone() }),
+    );
+    ops
+}
+
+/// Messages on a session's bridge channel (the bridge stamps sender id and name)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum EditMessage {
+    /// Guest -> host: send me the whole map
+    Join,
+    /// Host -> all: the whole map (base64 Borsh) as of `revision`
+    Snapshot { revision: u64, map: String },
+    /// Guest -> host: edits made on top of `base`
+    Ops { base: u64, ops: Vec<EditOp> },
+    /// Host -> all: edits accepted as `revision`, made by `author`
+    Commit { revision: u64, author: String, ops: Vec<EditOp> },
+    /// Anyone: where their mouse points in the world
+    Cursor { position: [f32; 3] },
+    /// Anyone: closed the session
+    Leave,
+}
+
+/// An incoming message with the sender the bridge stamped on it
+#[derive(Debug, Clone, Deserialize)]
+pub struct EditEnvelope {
+    pub from: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(flatten)]
+    pub message: EditMessage,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollabRole {
+    Host,
+    Guest,
+}
+
+/// Someone else in the session
+#[derive(Debug, Clone)]
+pub struct Collaborator {
+    pub name: String,
+    pub cursor: Option<Vector3>,
+    /// Editor time of their last message
+    pub seen_at: f64,
+}
+
+/// Collaborative editing session: the host's map is the truth
+///
+/// Everyone diffs their map against `shared` every `SYNC_INTERVAL`. The host
+/// commits its own edits right away; guests send theirs to the host, which
+/// accepts them unless someone else committed since the guest's base revision.
+/// Rejected edits are answered with a full snapshot, so the guest's map snaps
+/// back to the host's.
+pub struct CollabSession {
+    /// Code others type to join
+    pub code: String,
+    pub role: CollabRole,
+    /// Our sender id on the channel
+    pub peer_id: String,
+    /// Last host commit this map includes
+    pub revision: u64,
+    /// Objects as of `revision` plus our own edits already sent (None until a guest's first snapshot)
+    shared: Option<Vec<MapObject>>,
+    /// Host only: revision and author of recent commits
+    commits: VecDeque<(u64, String)>,
+    /// Sender id of the host, once heard from (guests only)
+    host_id: Option<String>,
+    /// Set when the host closes the session under a guest
+    pub host_left: bool,
+    pub collaborators: HashMap<String, Collaborator>,
+}
+
+impl CollabSession {
+    /// Start sharing `map`
+    pub fn host(code: String, peer_id: String, map: &Map) -> Self {
+        Self::new(code, CollabRole::Host, peer_id, Some(map.objects.clone()))
+    }
+
+    /// Join someone else's session; send `EditMessage::Join` to get the map
+    pub fn join(code: String, peer_id: String) -> Self {
+        Self::new(code, CollabRole::Guest, peer_id, None)
+    }
+
+    fn new(code: String, role: CollabRole, peer_id: String, shared: Option<Vec<MapObject>>) -> Self {
+        Self {
+            code,
+            role,
+            peer_id,
+            revision: 0,
+            shared,
+            commits: VecDeque::new(),
+            host_id: None,
+            host_left: false,
+            collaborators: HashMap::new(),
+        }
+    }
+
+    pub fn is_host(&self) -> bool {
+        self.role == CollabRole::Host
+    }
+
+    /// Whether this map follows the session yet (a guest waits for the host's snapshot)
+    pub fn is_synced(&self) -> bool {
+        self.shared.is_some()
+    }
+
+    /// Message carrying everything edited locally since the last call, if anything was
+    pub fn take_local_edits(&mut self, map: &Map) -> Option<EditMessage> {
+        let shared = self.shared.as_mut()?;
+        let ops = diff_objects(shared, &map.objects);
+        if ops.is_empty() {
+            return None;
+        }
+        *shared = map.objects.clone();
+
+        match self.role {
+            CollabRole::Host => {
+                let revision = self.record_commit(self.peer_id.clone());
+                Some(EditMessage::Commit { revision, author: self.peer_id.clone(), ops })
+            }
+            CollabRole::Guest => Some(EditMessage::Ops { base: self.revision, ops }),
+        }
+    }
+
+    /// Full map for a (re)joining guest
+    pub fn snapshot(&self, map: &Map) -> Option<EditMessage> {
+        let bytes = map.to_borsh_bytes().ok()?;
+        Some(EditMessage::Snapshot { revision: self.revision, map: general_purpose::STANDARD.encode(bytes) })
+    }
+
+    /// Host: adopt a map that was replaced wholesale (load, import, new) and share it
+    pub fn reset_shared(&mut self, map: &Map) -> Option<EditMessage> {
+        self.revision += 1;
+        self.commits.clear();
+        self.shared = Some(map.objects.clone());
+        self.snapshot(map)
+    }
+
+    /// Handle one message from the channel, updating `map`; returns what to send back
+    ///
+    /// Pending local edits are flushed first, so they're never mixed up with remote ones.
+    pub fn receive(&mut self, envelope: EditEnvelope, map: &mut Map, now: f64) -> Vec<EditMessage> {
+        let mut replies: Vec<EditMessage> = self.take_local_edits(map).into_iter().collect();
+        let EditEnvelope { from, name, message } = envelope;
+        if from == self.peer_id {
+            return replies;
+        }
+
+        if matches!(message, EditMessage::Leave) {
+            self.collaborators.remove(&from);
+            if self.host_id.as_deref() == Some(from.as_str()) {
+                self.host_left = true;
+            }
+            return replies;
+        }
+        let collaborator = self.collaborators.entry(from.clone()).or_insert(Collaborator { name: String::new(), cursor: None, seen_at: now });
+        collaborator.name = name;
+        collaborator.seen_at = now;
+
+        match (self.role, message) {
+            (_, EditMessage::Cursor { position }) => {
+                collaborator.cursor = Some(Vector3::new(position[0], position[1], position[2]));
+            }
+            (CollabRole::Host, EditMessage::Join) => replies.extend(self.snapshot(map)),
+            (CollabRole::Host, EditMessage::Ops { base, ops }) => {
+                if self.conflicts(&from, base) || !ops.iter().all(|op| op.apply(map)) {
+                    // Host wins: roll everyone back to the host's map (minus any half-applied ops)
+                    if let Some(shared) = &self.shared {
+                        map.objects = shared.clone();
+                    }
+                    replies.extend(self.snapshot(map));
+                } else {
+                    self.shared = Some(map.objects.clone());
+                    let revision = self.record_commit(from.clone());
+                    replies.push(EditMessage::Commit { revision, author: from, ops });
+                }
+            }
+            (CollabRole::Guest, EditMessage::Snapshot { revision, map: encoded }) => {
+                let decoded = general_purpose::STANDARD
+                    .decode(encoded)
+                    .map_err(|e| e.to_string())
+                    .and_then(|bytes| library::parse_map(&bytes));
+                if let Ok(snapshot) = decoded {
+                    *map = snapshot;
+                    self.shared = Some(map.objects.clone());
+                    self.revision = revision;
+                    self.host_id = Some(from);
+                }
+            }
+            (CollabRole::Guest, EditMessage::Commit { revision, author, ops }) if self.is_synced() && revision > self.revision => {
+                if author == self.peer_id {
+                    // Our own edits coming back: already in the map
+                    self.revision = revision;
+                } else if revision == self.revision + 1 && ops.iter().all(|op| op.apply(map)) {
+                    self.shared = Some(map.objects.clone());
+                    self.revision = revision;
+                } else {
+                    // Missed a commit or the copies diverged: ask for the whole map again
+                    replies.push(EditMessage::Join);
+                }
+            }
+            _ => {}
+        }
+        replies
+    }
+
+    /// Drop collaborators that went quiet (closed the tab without a Leave)
+    pub fn expire_collaborators(&mut self, now: f64) {
+        self.collaborators.retain(|_, c| now - c.seen_at < COLLABORATOR_TIMEOUT);
+    }
+
+    /// Whether edits `author` based on `base` would overwrite someone else's
+    fn conflicts(&self, author: &str, base: u64) -> bool {
+        let oldest = self.commits.front().map_or(self.revision, |(revision, _)| revision - 1);
+        base < oldest || base > self.revision || self.commits.iter().any(|(revision, by)| *revision > base && by != author)
+    }
+
+    fn record_commit(&mut self, author: String) -> u64 {
+        self.revision += 1;
+        self.commits.push_back((self.revision, author));
+        if self.commits.len() > COMMIT_HISTORY {
+            self.commits.pop_front();
+        }
+        self.revision
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::ModelType;
+
+    fn object_at(x: f32) -> MapObject {
+        let mut object = MapObject::new(ModelType::Cube);
+        object.set_position(Vector3::new(x, 0.0, 0.0));
+        object
+    }
+
+    fn map_with(xs: &[f32]) -> Map {
+        let mut map = Map::new("Test".to_string());
+        map.objects = xs.iter().map(|&x| object_at(x)).collect();
+        map
+    }
+
+    #[test]
+    fn test_diff_ops_replay_the_edit() {
+        let cases: [(&[f32], &[f32]); 4] = [
+            (&[1.0, 2.0, 3.0], &[1.0, 5.0, 3.0]),
+            (&[1.0, 2.0, 3.0], &[1.0, 3.0]),
+            (&[1.0, 2.0], &[1.0, 2.0, 4.0]),
+            (&[1.0, 2.0, 3.0], &[7.0]),
+        ];
+        for (before, after) in cases {
+            let mut map = map_with(before);
+            let target = map_with(after);
+            for op in diff_objects(&map.objects.clone(), &target.objects) {
+                assert!(op.apply(&mut map));
+            }
+            assert_eq!(map.objects, target.objects);
+        }
+        assert_eq!(diff_objects(&[object_at(1.0)], &[object_at(1.0), object_at(2.0)]).len(), 1);
+    }
+
+    #[test]
+    fn test_host_accepts_guest_edits_and_resyncs_conflicts() {
+        let envelope = |from: &str, message| EditEnvelope { from: from.to_string(), name: from.to_string(), message };
+        let mut host_map = map_with(&[1.0, 2.0]);
+        let mut host = CollabSession::host("CODE".to_string(), "host".to_string(), &host_map);
+
+        // Guest joins and gets the map
+        let mut guest_map = Map::new("Empty".to_string());
+        let mut guest = CollabSession::join("CODE".to_string(), "guest".to_string());
+        let snapshot = host.receive(envelope("guest", EditMessage::Join), &mut host_map, 0.0).remove(0);
+        guest.receive(envelope("host", snapshot), &mut guest_map, 0.0);
+        assert_eq!(guest_map.objects, host_map.objects);
+
+        // A guest edit on the current revision is committed for everyone
+        guest_map.objects[0] = object_at(9.0);
+        let ops = guest.take_local_edits(&guest_map).unwrap();
+        let commit = host.receive(envelope("guest", ops), &mut host_map, 0.1);
+        assert!(matches!(commit[..], [EditMessage::Commit { revision: 1, .. }]));
+        assert_eq!(host_map.objects, guest_map.objects);
+
+        // The host edits the same object before the guest's next edit arrives: host wins
+        host_map.objects[1] = object_at(4.0);
+        let host_commit = host.take_local_edits(&host_map).unwrap();
+        guest_map.objects[1] = object_at(6.0);
+        let stale = guest.take_local_edits(&guest_map).unwrap();
+        let replies = host.receive(envelope("guest", stale), &mut host_map, 0.2);
+        assert!(matches!(replies[..], [EditMessage::Snapshot { revision: 2, .. }]));
+        assert_eq!(host_map.objects[1], object_at(4.0));
+
+        guest.receive(envelope("host", host_commit), &mut guest_map, 0.2);
+        guest.receive(envelope("host", replies[0].clone()), &mut guest_map, 0.2);
+        assert_eq!(guest_map.objects, host_map.objects);
+        assert_eq!(guest.revision, host.revision);
+    }
+}
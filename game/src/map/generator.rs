@@ -0,0 +1,521 @@
+use raylib::prelude::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::map::{Map, MapObject, ModelType, WORLD_HALF_SIZE};
+use crate::i18n::t;
+
+/// Longest a generated block may be along one axis (object scale limit)
+const MAX_BLOCK_LENGTH: f32 = 25.0;
+
+/// Color of maps regenerated from a share code, so every player sees the same thing
+pub const SHARED_MAP_COLOR: Color = Color::new(150, 150, 160, 255);
+
+/// A number a generator exposes as a slider in the editor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeneratorParam {
+    /// i18n key of the slider label
+    pub label: &'static str,
+    pub min: f32,
+    pub max: f32,
+    pub default: f32,
+    /// Whole numbers only (counts)
+    pub integer: bool,
+}
+
+impl GeneratorParam {
+    /// `value` clamped to the range and rounded (to whole numbers for integer parameters,
+    /// tenths otherwise, so a share code reproduces the exact value)
+    pub fn clamp(&self, value: f32) -> f32 {
+        let value = value.clamp(self.min, self.max);
+        if self.integer { value.round() } else { (value * 10.0).round() / 10.0 }
+    }
+}
+
+/// A procedural layout offered under the editor's Generate header
+///
+/// Register extra ones with `MapBuilder::register_generator`. Generators must be
+/// deterministic: the same seed and parameter values always give the same objects,
+/// so a layout can be shared as its seed.
+pub trait MapGenerator {
+    /// Stable short name used in share codes (lowercase letters only)
+    fn id(&self) -> &'static str;
+
+    /// Localized name for the generator list
+    fn label(&self) -> &'static str;
+
+    /// Parameters in slider order; `generate` gets their values in the same order
+    fn params(&self) -> &'static [GeneratorParam] {
+        &[]
+    }
+
+    /// Objects for the layout in the author's current color (spawn points included if it places its own)
+    fn generate(&self, seed: u32, values: &[f32], color: Color) -> Vec<MapObject>;
+}
+
+/// Generators every editor session starts with
+pub fn builtin_generators() -> Vec<Box<dyn MapGenerator>> {
+    vec![Box::new(SymmetricArena), Box::new(CoverScatter), Box::new(SymmetricCorridors)]
+}
+
+/// Default values for a generator's parameters
+pub fn default_values(generator: &dyn MapGenerator) -> Vec<f32> {
+    generator.params().iter().map(|param| param.default).collect()
+}
+
+/// Value of parameter `index`, clamped to its range (the default when missing)
+pub fn param_value(generator: &dyn MapGenerator, values: &[f32], index: usize) -> f32 {
+    let param = generator.params()[index];
+    param.clamp(values.get(index).copied().unwrap_or(param.default))
+}
+
+/// Text that regenerates a layout anywhere: `<generator id>:<seed in hex>[:<value>,<value>...]`,
+/// e.g. `arena:1f3a9c:3,6,3.5`
+pub fn share_code(generator: &dyn MapGenerator, seed: u32, values: &[f32]) -> String {
+    let values: Vec<String> = (0..generator.params().len()).map(|i| param_value(generator, values, i).to_string()).collect();
+    if values.is_empty() {
+        format!("{}:{:x}", generator.id(), seed)
+    } else {
+        format!("{}:{:x}:{}", generator.id(), seed, values.join(","))
+    }
+}
+
+/// A layout decoded from a share code
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedLayout {
+    /// Index into the generator list the code was parsed against
+    pub generator: usize,
+    pub seed: u32,
+    /// One value per parameter (defaults fill in any the code leaves out)
+    pub values: Vec<f32>,
+}
+
+/// Decode a share code against `generators`; None if it's malformed or names an unknown generator
+pub fn parse_share_code(generators: &[Box<dyn MapGenerator>], code: &str) -> Option<SharedLayout> {
+    let mut parts = code.trim().splitn(3, ':');
+    let id = parts.next()?;
+    let seed = u32::from_str_radix(parts.next()?, 16).ok()?;
+    let index = generators.iter().position(|g| g.id() == id)?;
+    let generator = generators[index].as_ref();
+
+    let mut values = default_values(generator);
+    if let Some(list) = parts.next().filter(|list| !list.is_empty()) {
+        for (i, value) in list.split(',').enumerate() {
+            let value: f32 = value.parse().ok()?;
+            if let Some(slot) = values.get_mut(i) {
+                *slot = generator.params()[i].clamp(value);
+            }
+        }
+    }
+    Some(SharedLayout { generator: index, seed, values })
+}
+
+/// Regenerate the map a share code describes with the built-in generators
+/// (for quick custom matches, where every player builds the map locally)
+pub fn map_from_share_code(code: &str) -> Option<Map> {
+    let generators = builtin_generators();
+    let layout = parse_share_code(&generators, code)?;
+    let mut map = Map::new(code.trim().to_string());
+    for object in generators[layout.generator].generate(layout.seed, &layout.values, SHARED_MAP_COLOR) {
+        map.add_object(object);
+    }
+    Some(map)
+}
+
+/// A fresh seed for the "random" button
+pub fn random_seed() -> u32 {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64);
+    SeededRng::new(nanos as u32 ^ (nanos >> 32) as u32).next_u64() as u32
+}
+
+/// Deterministic random numbers for generators (SplitMix64), identical on every platform
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u32) -> Self {
+        Self { state: seed as u64 }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Uniform in [min, max)
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+}
+
+/// Cube standing on the ground centered on (`center_x`, `center_z`), `size` = (width, height, depth)
+pub fn block(center_x: f32, center_z: f32, size: Vector3, color: Color) -> MapObject {
+    let mut object = MapObject::new(ModelType::Cube);
+    object.set_position(Vector3::new(center_x, size.y / 2.0, center_z));
+    object.set_scale(size);
+    object.set_color(color);
+    object
+}
+
+/// Wall along x at `z` from `x_start` to `x_end`, split so no piece passes the scale limit
+pub fn wall_along_x(z: f32, x_start: f32, x_end: f32, thickness: f32, height: f32, color: Color) -> Vec<MapObject> {
+    let length = x_end - x_start;
+    if length <= 0.05 {
+        return Vec::new();
+    }
+    let pieces = (length / MAX_BLOCK_LENGTH).ceil().max(1.0) as usize;
+    let piece = length / pieces as f32;
+    (0..pieces)
+        .map(|i| block(x_start + piece * (i as f32 + 0.5), z, Vector3::new(piece, height, thickness), color))
+        .collect()
+}
+
+/// Wall along z at `x` from `z_start` to `z_end`, split so no piece passes the scale limit
+pub fn wall_along_z(x: f32, z_start: f32, z_end: f32, thickness: f32, height: f32, color: Color) -> Vec<MapObject> {
+    let length = z_end - z_start;
+    if length <= 0.05 {
+        return Vec::new();
+    }
+    let pieces = (length / MAX_BLOCK_LENGTH).ceil().max(1.0) as usize;
+    let piece = length / pieces as f32;
+    (0..pieces)
+        .map(|i| block(x, z_start + piece * (i as f32 + 0.5), Vector3::new(thickness, height, piece), color))
+        .collect()
+}
+
+/// Crates and pillars dropped around the map with some breathing room between them
+pub struct CoverScatter;
+
+const COVER_PARAMS: [GeneratorParam; 3] = [
+    GeneratorParam { label: "editor.generate.density", min: 4.0, max: 60.0, default: 20.0, integer: true },
+    GeneratorParam { label: "editor.generate.max_height", min: 0.8, max: 4.0, default: 2.0, integer: false },
+    GeneratorParam { label: "editor.generate.spacing", min: 1.0, max: 8.0, default: 3.0, integer: false },
+];
+
+impl MapGenerator for CoverScatter {
+    fn id(&self) -> &'static str {
+        "scatter"
+    }
+
+    fn label(&self) -> &'static str {
+        t("editor.generate.cover_scatter")
+    }
+
+    fn params(&self) -> &'static [GeneratorParam] {
+        &COVER_PARAMS
+    }
+
+    fn generate(&self, seed: u32, values: &[f32], color: Color) -> Vec<MapObject> {
+        let count = param_value(self, values, 0) as usize;
+        let max_height = param_value(self, values, 1);
+        let spacing = param_value(self, values, 2);
+        let mut rng = SeededRng::new(seed);
+
+        let limit = WORLD_HALF_SIZE - 3.0;
+        let mut placed: Vec<(f32, f32, f32)> = Vec::new();
+        let mut objects = Vec::new();
+        // Give up on a crowded map instead of looping forever
+        for _ in 0..count * 20 {
+            if objects.len() >= count {
+                break;
+            }
+            let width = rng.range(1.0, 2.5);
+            let depth = if rng.next_f32() < 0.3 { width } else { rng.range(1.0, 2.5) };
+            let height = rng.range(0.8_f32.min(max_height), max_height);
+            let (x, z) = (rng.range(-limit, limit), rng.range(-limit, limit));
+            let radius = width.max(depth) / 2.0;
+            if placed.iter().any(|&(px, pz, pr)| ((px - x).powi(2) + (pz - z).powi(2)).sqrt() < pr + radius + spacing) {
+                continue;
+            }
+            placed.push((x, z, radius));
+            objects.push(block(x, z, Vector3::new(width, height, depth), color));
+        }
+        objects
+    }
+}
+
+/// Parallel lanes separated by walls with doorways, the same from both ends of the map
+/// (every wall has a twin rotated half a turn around the center)
+pub struct SymmetricCorridors;
+
+const CORRIDOR_PARAMS: [GeneratorParam; 3] = [
+    GeneratorParam { label: "editor.generate.lanes", min: 2.0, max: 6.0, default: 3.0, integer: true },
+    GeneratorParam { label: "editor.generate.doors", min: 1.0, max: 3.0, default: 2.0, integer: true },
+    GeneratorParam { label: "editor.generate.wall_height", min: 1.5, max: 6.0, default: 3.0, integer: false },
+];
+
+/// Walls stop short of the map edge so the lanes connect at the ends
+const CORRIDOR_LENGTH: f32 = 18.0;
+const DOOR_WIDTH: f32 = 3.0;
+
+impl MapGenerator for SymmetricCorridors {
+    fn id(&self) -> &'static str {
+        "corridors"
+    }
+
+    fn label(&self) -> &'static str {
+        t("editor.generate.symmetric_corridors")
+    }
+
+    fn params(&self) -> &'static [GeneratorParam] {
+        &CORRIDOR_PARAMS
+    }
+
+    fn generate(&self, seed: u32, values: &[f32], color: Color) -> Vec<MapObject> {
+        let lanes = param_value(self, values, 0) as usize;
+        let doors = param_value(self, values, 1) as usize;
+        let height = param_value(self, values, 2);
+        let mut rng = SeededRng::new(seed);
+
+        let lane_width = (WORLD_HALF_SIZE * 2.0 - 6.0) / lanes as f32;
+        let wall_x = |i: usize| -WORLD_HALF_SIZE + 3.0 + lane_width * i as f32;
+
+        let mut objects = Vec::new();
+        for i in 1..lanes {
+            let twin = lanes - i;
+            if twin < i {
+                break;
+            }
+            // Doorway centers for wall i; its twin gets them mirrored. A wall on the
+            // center line is its own twin, so its doorways are mirrored onto itself.
+            let mut centers: Vec<f32> = if twin == i {
+                let half: Vec<f32> = (0..doors.div_ceil(2)).map(|_| rng.range(DOOR_WIDTH, CORRIDOR_LENGTH - DOOR_WIDTH)).collect();
+                half.iter().flat_map(|&z| [z, -z]).collect()
+            } else {
+                (0..doors).map(|_| rng.range(-CORRIDOR_LENGTH + DOOR_WIDTH, CORRIDOR_LENGTH - DOOR_WIDTH)).collect()
+            };
+            centers.sort_by(f32::total_cmp);
+
+            let segments = wall_segments(&centers, -CORRIDOR_LENGTH, CORRIDOR_LENGTH);
+            for &(start, end) in &segments {
+                objects.extend(wall_along_z(wall_x(i), start, end, 1.0, height, color));
+            }
+            if twin != i {
+                for &(start, end) in &segments {
+                    objects.extend(wall_along_z(wall_x(twin), -end, -start, 1.0, height, color));
+                }
+            }
+        }
+        objects
+    }
+}
+
+/// Solid stretches of a wall from `from` to `to` around doorways centered at `centers` (sorted)
+fn wall_segments(centers: &[f32], from: f32, to: f32) -> Vec<(f32, f32)> {
+    let mut segments = Vec::new();
+    let mut start = from;
+    for &center in centers {
+        let door_start = center - DOOR_WIDTH / 2.0;
+        if door_start > start {
+            segments.push((start, door_start));
+        }
+        start = start.max(center + DOOR_WIDTH / 2.0);
+    }
+    if start < to {
+        segments.push((start, to));
+    }
+    segments
+}
+
+/// Team arena: a spawn room at each end (blue at -x, red at +x), lanes between them split by
+/// walls with doorways, and scattered mid cover. Only the blue half is rolled; the red half is
+/// that half rotated a half turn around the center, so neither team has a better side.
+pub struct SymmetricArena;
+
+const ARENA_PARAMS: [GeneratorParam; 3] = [
+    GeneratorParam { label: "editor.generate.lanes", min: 2.0, max: 4.0, default: 3.0, integer: true },
+    GeneratorParam { label: "editor.generate.cover_pairs", min: 0.0, max: 12.0, default: 6.0, integer: true },
+    GeneratorParam { label: "editor.generate.wall_height", min: 2.0, max: 5.0, default: 3.0, integer: false },
+];
+
+/// Spawn rooms run from the map edge to this distance from the center
+const ROOM_FRONT: f32 = 18.0;
+const ROOM_HALF_WIDTH: f32 = 6.0;
+/// Lane walls end this far from the center, leaving open ground in front of the rooms
+const LANE_END: f32 = 14.0;
+/// Lanes fill the map minus this margin on both sides
+const LANE_MARGIN: f32 = 2.0;
+const SPAWNS_PER_TEAM: usize = 3;
+
+impl MapGenerator for SymmetricArena {
+    fn id(&self) -> &'static str {
+        "arena"
+    }
+
+    fn label(&self) -> &'static str {
+        t("editor.generate.symmetric_arena")
+    }
+
+    fn params(&self) -> &'static [GeneratorParam] {
+        &ARENA_PARAMS
+    }
+
+    fn generate(&self, seed: u32, values: &[f32], color: Color) -> Vec<MapObject> {
+        let lanes = param_value(self, values, 0) as usize;
+        let cover_pairs = param_value(self, values, 1) as usize;
+        let height = param_value(self, values, 2);
+        let mut rng = SeededRng::new(seed);
+        let mut half = Vec::new();
+
+        // Blue spawn room: front wall with a doorway in the middle, side walls back to the edge
+        for (start, end) in wall_segments(&[0.0], -ROOM_HALF_WIDTH, ROOM_HALF_WIDTH) {
+            half.extend(wall_along_z(-ROOM_FRONT, start, end, 1.0, height, color));
+        }
+        for z in [-ROOM_HALF_WIDTH, ROOM_HALF_WIDTH] {
+            half.extend(wall_along_x(z, -WORLD_HALF_SIZE, -ROOM_FRONT, 1.0, height, color));
+        }
+        let spawn_gap = ROOM_HALF_WIDTH * 2.0 / (SPAWNS_PER_TEAM + 1) as f32;
+        for i in 0..SPAWNS_PER_TEAM {
+            let mut spawn = MapObject::new(ModelType::SpawnPointBlue);
+            spawn.set_position(Vector3::new(
+                (-WORLD_HALF_SIZE - ROOM_FRONT) / 2.0,
+                1.0,
+                -ROOM_HALF_WIDTH + spawn_gap * (i + 1) as f32,
+            ));
+            half.push(spawn);
+        }
+
+        // Lane walls: one on each side of the center keeps the wall at +z and leaves the one at
+        // -z to the turn; a wall on the center line only gets its blue (-x) half here
+        let lane_width = (WORLD_HALF_SIZE - LANE_MARGIN) * 2.0 / lanes as f32;
+        let wall_zs: Vec<f32> = (1..lanes).map(|i| -WORLD_HALF_SIZE + LANE_MARGIN + lane_width * i as f32).collect();
+        for &z in &wall_zs {
+            if z.abs() < 0.01 {
+                let center = rng.range(-LANE_END + DOOR_WIDTH, -DOOR_WIDTH);
+                for (start, end) in wall_segments(&[center], -LANE_END, 0.0) {
+                    half.extend(wall_along_x(0.0, start, end, 1.0, height, color));
+                }
+            } else if z > 0.0 {
+                let mut centers: Vec<f32> = (0..2).map(|_| rng.range(-LANE_END + DOOR_WIDTH, LANE_END - DOOR_WIDTH)).collect();
+                centers.sort_by(f32::total_cmp);
+                for (start, end) in wall_segments(&centers, -LANE_END, LANE_END) {
+                    half.extend(wall_along_x(z, start, end, 1.0, height, color));
+                }
+            }
+        }
+
+        // Mid cover on the blue side of the center, clear of the lane walls and of each other
+        let limit = WORLD_HALF_SIZE - LANE_MARGIN - 1.0;
+        let mut placed: Vec<(f32, f32, f32)> = Vec::new();
+        for _ in 0..cover_pairs * 20 {
+            if placed.len() >= cover_pairs {
+                break;
+            }
+            let width = rng.range(1.0, 2.0);
+            let depth = rng.range(1.0, 2.0);
+            let cover_height = rng.range(1.0, 1.6_f32.min(height));
+            let (x, z) = (rng.range(-LANE_END + 1.0, -1.5), rng.range(-limit, limit));
+            let radius = width.max(depth) / 2.0;
+            let blocks_lane = wall_zs.iter().any(|&wall_z| (wall_z - z).abs() < depth / 2.0 + 1.5);
+            let crowded = placed.iter().any(|&(px, pz, pr)| ((px - x).powi(2) + (pz - z).powi(2)).sqrt() < pr + radius + 2.0);
+            if blocks_lane || crowded {
+                continue;
+            }
+            placed.push((x, z, radius));
+            half.push(block(x, z, Vector3::new(width, cover_height, depth), color));
+        }
+
+        let red: Vec<MapObject> = half.iter().map(half_turn).collect();
+        half.extend(red);
+        half
+    }
+}
+
+/// `object` rotated half a turn around the map center (blue spawn points become red)
+fn half_turn(object: &MapObject) -> MapObject {
+    let mut twin = object.clone();
+    let position = object.get_position();
+    twin.set_position(Vector3::new(-position.x, position.y, -position.z));
+    if object.model_type == ModelType::SpawnPointBlue {
+        twin.model_type = ModelType::SpawnPointRed;
+    }
+    twin
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::stats::{MAP_BYTE_BUDGET, MAP_OBJECT_BUDGET};
+
+    #[test]
+    fn test_generators_are_deterministic_and_corridors_symmetric() {
+        for generator in builtin_generators() {
+            let values = default_values(generator.as_ref());
+            let a = generator.generate(42, &values, Color::GRAY);
+            let b = generator.generate(42, &values, Color::GRAY);
+            assert!(!a.is_empty());
+            assert_eq!(a.len(), b.len());
+            assert!(a.iter().zip(&b).all(|(a, b)| a.get_position() == b.get_position() && a.get_scale() == b.get_scale()));
+        }
+
+        // Every corridor wall piece has a twin half a turn around the center
+        for lanes in 2..=6 {
+            let objects = SymmetricCorridors.generate(7, &[lanes as f32, 2.0, 3.0], Color::GRAY);
+            for object in &objects {
+                let p = object.get_position();
+                assert!(
+                    objects.iter().any(|o| {
+                        let q = o.get_position();
+                        (q.x + p.x).abs() < 0.02 && (q.z + p.z).abs() < 0.02 && (o.get_scale().z - object.get_scale().z).abs() < 0.02
+                    }),
+                    "no twin for the piece at ({}, {}) with {} lanes", p.x, p.z, lanes
+                );
+            }
+        }
+
+        // Out-of-range values from older editor sessions are clamped
+        assert_eq!(param_value(&CoverScatter, &[1000.0], 0), 60.0);
+        assert_eq!(param_value(&CoverScatter, &[], 1), 2.0);
+    }
+
+    #[test]
+    fn test_arena_is_fair_and_share_codes_round_trip() {
+        for seed in [1, 99, 0xdead_beef] {
+            for lanes in 2..=4 {
+                let objects = SymmetricArena.generate(seed, &[lanes as f32, 12.0, 5.0], Color::GRAY);
+                let count = |model_type| objects.iter().filter(|o| o.model_type == model_type).count();
+                assert_eq!(count(ModelType::SpawnPointBlue), SPAWNS_PER_TEAM);
+                assert_eq!(count(ModelType::SpawnPointRed), SPAWNS_PER_TEAM);
+                assert!(objects.len() <= MAP_OBJECT_BUDGET && objects.len() * 16 < MAP_BYTE_BUDGET);
+
+                // Each object has a twin half a turn around the center, spawns on the other team
+                for object in &objects {
+                    let p = object.get_position();
+                    let twin_type = match object.model_type {
+                        ModelType::SpawnPointBlue => ModelType::SpawnPointRed,
+                        ModelType::SpawnPointRed => ModelType::SpawnPointBlue,
+                        other => other,
+                    };
+                    assert!(objects.iter().any(|o| {
+                        let q = o.get_position();
+                        o.model_type == twin_type && (q.x + p.x).abs() < 0.02 && (q.z + p.z).abs() < 0.02 && o.get_scale() == object.get_scale()
+                    }));
+                }
+            }
+        }
+
+        let generators = builtin_generators();
+        let code = share_code(&SymmetricArena, 0x1f3a9c, &[4.0, 5.0, 3.47]);
+        assert_eq!(code, "arena:1f3a9c:4,5,3.5");
+        let layout = parse_share_code(&generators, &code).unwrap();
+        assert_eq!(layout, SharedLayout { generator: 0, seed: 0x1f3a9c, values: vec![4.0, 5.0, 3.5] });
+
+        // Missing values fall back to defaults; junk is rejected
+        assert_eq!(parse_share_code(&generators, "corridors:7").unwrap().values, default_values(&SymmetricCorridors));
+        assert!(parse_share_code(&generators, "arena:zz:3").is_none());
+        assert!(parse_share_code(&generators, "maze:1f:3").is_none());
+
+        let a = map_from_share_code(&code).unwrap();
+        let b = map_from_share_code(&code).unwrap();
+        assert_eq!(a.objects, b.objects);
+    }
+}
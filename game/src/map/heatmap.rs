@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use raylib::prelude::*;
+
+use crate::game::{MATCH_LOG_VERSION, POSITION_SAMPLE_INTERVAL, REPLAY_VERSION};
+use crate::i18n::{t, tr};
+use super::map::{WORLD_HALF_SIZE, WORLD_SIZE};
+
+/// Heatmap resolution (world units per cell)
+pub const HEATMAP_CELL_SIZE: f32 = 2.0;
+const GRID: usize = (WORLD_SIZE / HEATMAP_CELL_SIZE) as usize;
+
+/// A kill is placed where the players were last sampled, if that's at most this long before it (seconds)
+const KILL_SAMPLE_WINDOW: f64 = 3.0;
+
+/// What a heatmap counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeatmapKind {
+    /// Where killers stood
+    Kills,
+    /// Where victims went down
+    Deaths,
+    /// Where players spent their time (cold cells are dead zones)
+    Presence,
+}
+
+impl HeatmapKind {
+    pub const ALL: [HeatmapKind; 3] = [HeatmapKind::Kills, HeatmapKind::Deaths, HeatmapKind::Presence];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HeatmapKind::Kills => t("editor.heatmap.kills"),
+            HeatmapKind::Deaths => t("editor.heatmap.deaths"),
+            HeatmapKind::Presence => t("editor.heatmap.presence"),
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Why a file couldn't be added
+#[derive(Debug, Clone, PartialEq)]
+pub enum HeatmapError {
+    NotJson,
+    /// Neither a match event log nor a replay
+    UnknownFormat,
+    /// Exported by a newer version of the game
+    UnsupportedVersion(u64),
+}
+
+impl HeatmapError {
+    /// Localized description for the editor
+    pub fn message(&self) -> String {
+        match self {
+            HeatmapError::NotJson => t("editor.heatmap.not_json").to_string(),
+            HeatmapError::UnknownFormat => t("editor.heatmap.unknown_format").to_string(),
+            HeatmapError::UnsupportedVersion(version) => tr!("editor.heatmap.unsupported_version", version = version),
+        }
+    }
+}
+
+/// Kill, death and position heatmaps accumulated from exported match logs and replays
+///
+/// Match logs give kills, deaths and 1 Hz positions; replays have no killers, so they only
+/// add deaths (a player going down) and positions. Kills and deaths are placed at the
+/// players' last position sample, so they're as coarse as the file's sampling.
+#[derive(Debug, Clone)]
+pub struct MatchHeatmap {
+    /// Per kind, row-major (x then z)
+    cells: [Vec<f32>; 3],
+    pub files: usize,
+    pub kills: usize,
+    pub deaths: usize,
+    /// Player-seconds of position samples
+    pub seconds: f32,
+}
+
+impl Default for MatchHeatmap {
+    fn default() -> Self {
+        Self { cells: std::array::from_fn(|_| vec![0.0; GRID * GRID]), files: 0, kills: 0, deaths: 0, seconds: 0.0 }
+    }
+}
+
+impl MatchHeatmap {
+    /// Add an exported match log (JSON) or replay file
+    pub fn ingest(&mut self, text: &str) -> Result<(), HeatmapError> {
+        let file: serde_json::Value = serde_json::from_str(text).map_err(|_| HeatmapError::NotJson)?;
+        let version = file.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+        if let Some(events) = file.get("events").and_then(|v| v.as_array()) {
+            check_version(version, MATCH_LOG_VERSION)?;
+            self.ingest_match_log(events);
+        } else if let Some(frames) = file.get("frames").and_then(|v| v.as_array()) {
+            check_version(version, REPLAY_VERSION)?;
+            let tick_rate = file.get("tick_rate").and_then(|v| v.as_f64()).filter(|rate| *rate > 0.0).unwrap_or(1.0);
+            self.ingest_replay(frames, tick_rate);
+        } else {
+            return Err(HeatmapError::UnknownFormat);
+        }
+        self.files += 1;
+        Ok(())
+    }
+
+    fn ingest_match_log(&mut self, events: &[serde_json::Value]) {
+        // Each player's latest living position sample: (t, position)
+        let mut last_seen: HashMap<&str, (f64, Vector3)> = HashMap::new();
+        for event in events {
+            let time = event.get("t").and_then(|v| v.as_f64()).unwrap_or(0.0);
+            match event.get("type").and_then(|v| v.as_str()) {
+                Some("position") => {
+                    let (Some(player), Some(position)) = (event.get("player").and_then(|v| v.as_str()), event_position(event)) else {
+                        continue;
+                    };
+                    if event.get("alive").and_then(|v| v.as_bool()).unwrap_or(true) {
+                        last_seen.insert(player, (time, position));
+                        self.add(HeatmapKind::Presence, position, POSITION_SAMPLE_INTERVAL as f32);
+                    }
+                }
+                Some("kill") => {
+                    let recent = |player: Option<&str>| {
+                        let (seen, position) = *last_seen.get(player?)?;
+                        (time - seen <= KILL_SAMPLE_WINDOW).then_some(position)
+                    };
+                    if let Some(position) = recent(event.get("victim").and_then(|v| v.as_str())) {
+                        self.add(HeatmapKind::Deaths, position, 1.0);
+                    }
+                    if let Some(position) = recent(event.get("killer").and_then(|v| v.as_str())) {
+                        self.add(HeatmapKind::Kills, position, 1.0);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn ingest_replay(&mut self, frames: &[serde_json::Value], tick_rate: f64) {
+        // Each player's position in the previous frame, if they were alive in it
+        let mut previous: HashMap<&str, Vector3> = HashMap::new();
+        for frame in frames {
+            let players = frame.get("players").and_then(|v| v.as_array()).map(Vec::as_slice).unwrap_or_default();
+            for player in players {
+                let (Some(authority), Some(position)) = (player.get("authority").and_then(|v| v.as_str()), event_position(player)) else {
+                    continue;
+                };
+                if player.get("alive").and_then(|v| v.as_bool()).unwrap_or(true) {
+                    previous.insert(authority, position);
+                    self.add(HeatmapKind::Presence, position, (1.0 / tick_rate) as f32);
+                } else if let Some(position) = previous.remove(authority) {
+                    self.add(HeatmapKind::Deaths, position, 1.0);
+                }
+            }
+        }
+    }
+
+    fn add(&mut self, kind: HeatmapKind, position: Vector3, amount: f32) {
+        match kind {
+            HeatmapKind::Kills => self.kills += 1,
+            HeatmapKind::Deaths => self.deaths += 1,
+            HeatmapKind::Presence => self.seconds += amount,
+        }
+        self.cells[kind.index()][cell_at(position)] += amount;
+    }
+
+    /// (cell center, intensity 0..=1 relative to the hottest cell) for every cell with any heat
+    pub fn cells(&self, kind: HeatmapKind) -> impl Iterator<Item = (Vector3, f32)> + '_ {
+        let cells = &self.cells[kind.index()];
+        let max = cells.iter().copied().fold(0.0, f32::max);
+        cells
+            .iter()
+            .enumerate()
+            .filter(|(_, &value)| value > 0.0)
+            .map(move |(cell, &value)| (cell_center(cell), value / max))
+    }
+
+    /// The hottest cell and its raw value (kills, deaths or player-seconds)
+    pub fn hottest(&self, kind: HeatmapKind) -> Option<(Vector3, f32)> {
+        self.cells[kind.index()]
+            .iter()
+            .enumerate()
+            .filter(|(_, &value)| value > 0.0)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(cell, &value)| (cell_center(cell), value))
+    }
+}
+
+fn check_version(version: u64, supported: u32) -> Result<(), HeatmapError> {
+    if version > supported as u64 {
+        return Err(HeatmapError::UnsupportedVersion(version));
+    }
+    Ok(())
+}
+
+/// `x`, `y`, `z` fields of a logged event or replay player
+fn event_position(value: &serde_json::Value) -> Option<Vector3> {
+    let component = |key: &str| value.get(key).and_then(|v| v.as_f64()).map(|v| v as f32);
+    Some(Vector3::new(component("x")?, component("y")?, component("z")?))
+}
+
+/// Center of a heatmap cell on the ground
+fn cell_center(cell: usize) -> Vector3 {
+    let (x, z) = (cell % GRID, cell / GRID);
+    Vector3::new(
+        -WORLD_HALF_SIZE + (x as f32 + 0.5) * HEATMAP_CELL_SIZE,
+        0.0,
+        -WORLD_HALF_SIZE + (z as f32 + 0.5) * HEATMAP_CELL_SIZE,
+    )
+}
+
+/// Heatmap cell containing a world position
+fn cell_at(position: Vector3) -> usize {
+    let x = ((position.x + WORLD_HALF_SIZE) / HEATMAP_CELL_SIZE).clamp(0.0, GRID as f32 - 1.0) as usize;
+    let z = ((position.z + WORLD_HALF_SIZE) / HEATMAP_CELL_SIZE).clamp(0.0, GRID as f32 - 1.0) as usize;
+    z * GRID + x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ingest_match_logs_and_replays() {
+        let log = r#"{"version":1,"game":"g","players":{},"events":[
+            {"type":"position","t":0.0,"player":"a","team":"1","x":10.0,"y":0.0,"z":10.0,"alive":true},
+            {"type":"position","t":0.0,"player":"b","team":"2","x":-10.0,"y":0.0,"z":-10.0,"alive":true},
+            {"type":"position","t":1.0,"player":"b","team":"2","x":-11.0,"y":0.0,"z":-10.0,"alive":true},
+            {"type":"kill","t":1.5,"killer":"a","victim":"b","assists":[],"weapon":"rifle","headshot":false},
+            {"type":"kill","t":9.0,"killer":null,"victim":"a","assists":[],"weapon":"fall","headshot":false}
+        ]}"#;
+        let mut heatmap = MatchHeatmap::default();
+        heatmap.ingest(log).unwrap();
+        assert_eq!((heatmap.files, heatmap.kills, heatmap.deaths, heatmap.seconds), (1, 1, 1, 3.0));
+        assert_eq!(heatmap.hottest(HeatmapKind::Kills), Some((Vector3::new(10.0, 0.0, 10.0), 1.0)));
+        assert_eq!(heatmap.hottest(HeatmapKind::Deaths), Some((Vector3::new(-10.0, 0.0, -10.0), 1.0)));
+        assert!(heatmap.cells(HeatmapKind::Presence).all(|(_, heat)| heat > 0.0 && heat <= 1.0));
+
+        // Replays: someone going down counts as a death where they last stood
+        let replay = r#"{"version":1,"tick_rate":2.0,"frames":[
+            {"t":0.0,"players":[{"authority":"a","x":0.5,"y":0.0,"z":0.5,"alive":true}]},
+            {"t":0.5,"players":[{"authority":"a","x":0.5,"y":0.0,"z":0.5,"alive":false}]}
+        ]}"#;
+        heatmap.ingest(replay).unwrap();
+        assert_eq!((heatmap.files, heatmap.deaths, heatmap.seconds), (2, 2, 3.5));
+
+        assert_eq!(heatmap.ingest("not json"), Err(HeatmapError::NotJson));
+        assert_eq!(heatmap.ingest(r#"{"objects":[]}"#), Err(HeatmapError::UnknownFormat));
+        assert_eq!(heatmap.ingest(r#"{"version":9,"events":[]}"#), Err(HeatmapError::UnsupportedVersion(9)));
+    }
+}
@@ -0,0 +1,157 @@
+use raylib::prelude::*;
+
+use super::map::{MapObject, ModelType, WORLD_SIZE, WORLD_HALF_SIZE};
+use crate::i18n::t;
+
+/// Grid sizes offered for image import (cells per side)
+pub const HEIGHTMAP_RESOLUTIONS: [u32; 4] = [12, 16, 24, 32];
+
+/// Heights are rounded to this step so neighbouring cells merge into longer blocks
+const HEIGHT_STEP: f32 = 0.5;
+
+/// What an imported image turns into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeightmapStyle {
+    /// Cubes only where the image is brighter than the threshold (walls and cover from a sketch)
+    #[default]
+    Blocks,
+    /// A column under every cell, colored by height (rolling ground)
+    Terrain,
+}
+
+impl HeightmapStyle {
+    pub const ALL: [HeightmapStyle; 2] = [HeightmapStyle::Blocks, HeightmapStyle::Terrain];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HeightmapStyle::Blocks => t("editor.heightmap.blocks"),
+            HeightmapStyle::Terrain => t("editor.heightmap.terrain"),
+        }
+    }
+}
+
+/// Settings for turning a grayscale image into geometry
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeightmapOptions {
+    pub style: HeightmapStyle,
+    /// Index into `HEIGHTMAP_RESOLUTIONS`
+    pub resolution_index: usize,
+    /// Height of a pure white pixel
+    pub max_height: f32,
+    /// Pixels at or below this brightness are left empty (Blocks only)
+    pub threshold: u8,
+    /// Dark pixels are high instead of bright ones
+    pub invert: bool,
+    /// Remove existing geometry (spawn points are kept) before adding the import
+    pub replace: bool,
+}
+
+impl Default for HeightmapOptions {
+    fn default() -> Self {
+        Self {
+            style: HeightmapStyle::Blocks,
+            resolution_index: 2,
+            max_height: 4.0,
+            threshold: 40,
+            invert: false,
+            replace: true,
+        }
+    }
+}
+
+impl HeightmapOptions {
+    pub fn resolution(&self) -> u32 {
+        HEIGHTMAP_RESOLUTIONS[self.resolution_index % HEIGHTMAP_RESOLUTIONS.len()]
+    }
+}
+
+/// Turn a `width` x `height` grayscale image (row-major, one byte per pixel) into cubes covering the world.
+/// Runs of equal height along a row become a single stretched cube to keep the object count down.
+pub fn heightmap_to_objects(pixels: &[u8], width: usize, height: usize, options: &HeightmapOptions, color: Color) -> Vec<MapObject> {
+    if width == 0 || height == 0 || pixels.len() < width * height {
+        return Vec::new();
+    }
+
+    let cell_x = WORLD_SIZE / width as f32;
+    let cell_z = WORLD_SIZE / height as f32;
+
+    // Quantized height of one pixel (None = leave empty)
+    let cell_height = |x: usize, z: usize| -> Option<f32> {
+        let value = pixels[z * width + x];
+        let value = if options.invert { 255 - value } else { value };
+        match options.style {
+            HeightmapStyle::Blocks if value <= options.threshold => None,
+            _ => {
+                let raw = value as f32 / 255.0 * options.max_height;
+                Some(((raw / HEIGHT_STEP).round() * HEIGHT_STEP).max(HEIGHT_STEP))
+            }
+        }
+    };
+
+    let mut objects = Vec::new();
+    for z in 0..height {
+        let mut x = 0;
+        while x < width {
+            let Some(column_height) = cell_height(x, z) else {
+                x += 1;
+                continue;
+            };
+
+            // Extend the run while the height matches and the cube stays under the scale limit
+            let mut run = 1;
+            while x + run < width
+                && cell_height(x + run, z) == Some(column_height)
+                && (run + 1) as f32 * cell_x <= 25.5
+            {
+                run += 1;
+            }
+
+            let mut object = MapObject::new(ModelType::Cube);
+            object.set_position(Vector3::new(
+                -WORLD_HALF_SIZE + (x as f32 + run as f32 / 2.0) * cell_x,
+                column_height / 2.0,
+                -WORLD_HALF_SIZE + (z as f32 + 0.5) * cell_z,
+            ));
+            object.set_scale(Vector3::new(run as f32 * cell_x, column_height, cell_z));
+            object.set_color(match options.style {
+                HeightmapStyle::Blocks => color,
+                HeightmapStyle::Terrain => terrain_color(column_height / options.max_height),
+            });
+            objects.push(object);
+
+            x += run;
+        }
+    }
+    objects
+}
+
+/// Green lowlands, brown slopes, pale peaks
+fn terrain_color(level: f32) -> Color {
+    match level {
+        l if l < 0.35 => Color::new(90, 140, 70, 255),
+        l if l < 0.7 => Color::new(140, 110, 70, 255),
+        _ => Color::new(200, 200, 190, 255),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows_merge_into_stretched_cubes() {
+        // One row, 8 cells of 6.25 units: a bright run of three, a gap, then a mid-gray pair
+        let pixels = [255, 255, 255, 0, 0, 128, 128, 0];
+        let options = HeightmapOptions::default();
+        let objects = heightmap_to_objects(&pixels, 8, 1, &options, Color::GRAY);
+
+        assert_eq!(objects.len(), 2);
+        assert!((objects[0].get_scale().x - 18.75).abs() < 0.1);
+        assert_eq!(objects[0].get_scale().y, 4.0);
+        assert_eq!(objects[1].get_scale().y, 2.0);
+
+        // Terrain covers the dark cells too (at the minimum height)
+        let terrain = HeightmapOptions { style: HeightmapStyle::Terrain, ..options };
+        assert_eq!(heightmap_to_objects(&pixels, 8, 1, &terrain, Color::GRAY).len(), 4);
+    }
+}
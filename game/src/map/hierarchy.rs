@@ -0,0 +1,148 @@
+use raylib::prelude::*;
+
+use super::map::{MapObject, ModelType, ObjectLabel};
+use crate::i18n::t;
+
+/// Groups of objects the Hierarchy can be narrowed down to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HierarchyCategory {
+    #[default]
+    All,
+    /// Blue and red spawn points
+    SpawnPoints,
+    /// Solid geometry players can hide behind
+    Cover,
+    /// Flat planes (floors, ramps, platforms)
+    Floors,
+}
+
+impl HierarchyCategory {
+    /// All categories in the order shown in the filter combo
+    pub const ALL: [HierarchyCategory; 4] = [
+        HierarchyCategory::All,
+        HierarchyCategory::SpawnPoints,
+        HierarchyCategory::Cover,
+        HierarchyCategory::Floors,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HierarchyCategory::All => t("editor.hierarchy.all"),
+            HierarchyCategory::SpawnPoints => t("editor.hierarchy.spawn_points"),
+            HierarchyCategory::Cover => t("editor.hierarchy.cover"),
+            HierarchyCategory::Floors => t("editor.hierarchy.floors"),
+        }
+    }
+
+    pub fn includes(&self, model_type: ModelType) -> bool {
+        match self {
+            HierarchyCategory::All => true,
+            HierarchyCategory::SpawnPoints => matches!(model_type, ModelType::SpawnPointBlue | ModelType::SpawnPointRed),
+            HierarchyCategory::Floors => model_type == ModelType::Plane,
+            HierarchyCategory::Cover => matches!(
+                model_type,
+                ModelType::Cube | ModelType::Rectangle | ModelType::Triangle | ModelType::Sphere | ModelType::Cylinder
+            ),
+        }
+    }
+}
+
+/// Rough color name for searching ("blue", "red", ...)
+pub fn color_name(color: Color) -> &'static str {
+    let (r, g, b) = (color.r as f32 / 255.0, color.g as f32 / 255.0, color.b as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+
+    if chroma < 0.12 {
+        return if max > 0.85 { "white" } else if max < 0.15 { "black" } else { "gray" };
+    }
+
+    let hue = if max == r {
+        60.0 * ((g - b) / chroma).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / chroma + 2.0)
+    } else {
+        60.0 * ((r - g) / chroma + 4.0)
+    };
+
+    match hue {
+        h if h < 20.0 => "red",
+        h if h < 45.0 => "orange",
+        h if h < 70.0 => "yellow",
+        h if h < 160.0 => "green",
+        h if h < 195.0 => "cyan",
+        h if h < 260.0 => "blue",
+        h if h < 330.0 => "purple",
+        _ => "red",
+    }
+}
+
+/// Search box + category filter for the Hierarchy panel
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HierarchyFilter {
+    /// Space-separated terms, all of which must match (type, name, tag, color name, hex color or index)
+    pub query: String,
+    pub category: HierarchyCategory,
+}
+
+impl HierarchyFilter {
+    /// Whether anything is narrowing the list
+    pub fn is_active(&self) -> bool {
+        !self.query.trim().is_empty() || self.category != HierarchyCategory::All
+    }
+
+    pub fn matches(&self, index: usize, object: &MapObject, label: Option<&ObjectLabel>) -> bool {
+        if !self.category.includes(object.model_type) {
+            return false;
+        }
+
+        let type_name = format!("{:?}", object.model_type).to_lowercase();
+        let color = object.get_color();
+        let hex = format!("{:02x}{:02x}{:02x}", color.r, color.g, color.b);
+        let color_name = color_name(color);
+        let name = label.map(|l| l.name.to_lowercase()).unwrap_or_default();
+
+        self.query.split_whitespace().all(|term| {
+            let term = term.to_lowercase();
+            type_name.contains(&term)
+                || name.contains(&term)
+                || label.is_some_and(|l| l.has_tag(&term))
+                || color_name == term
+                || hex.starts_with(term.trim_start_matches('#'))
+                || term.trim_start_matches('#').parse::<usize>() == Ok(index)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_by_type_color_and_category() {
+        let mut crate_box = MapObject::new(ModelType::Cube);
+        crate_box.set_color(Color::new(200, 40, 40, 255));
+        let spawn = MapObject::new(ModelType::SpawnPointBlue);
+
+        let mut filter = HierarchyFilter { query: "cube red".to_string(), ..Default::default() };
+        assert!(filter.matches(0, &crate_box, None));
+        assert!(!filter.matches(1, &spawn, None));
+
+        filter.query = "#c82828".to_string();
+        assert!(filter.matches(0, &crate_box, None));
+
+        let label = ObjectLabel { object: 0, name: "Mid Box".to_string(), tags: "a-site,cover".to_string() };
+        filter.query = "mid a-site".to_string();
+        assert!(filter.matches(0, &crate_box, Some(&label)));
+        assert!(!filter.matches(0, &crate_box, None));
+
+        filter.query = "spawn".to_string();
+        assert!(filter.matches(1, &spawn, None));
+
+        filter.query.clear();
+        filter.category = HierarchyCategory::SpawnPoints;
+        assert!(!filter.matches(0, &crate_box, None));
+        assert!(filter.matches(1, &spawn, None));
+    }
+}
@@ -0,0 +1,156 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_os = "emscripten"))]
+use std::{fs, path::Path};
+
+use super::map::Map;
+use crate::i18n::{t, tr};
+
+/// Folder native builds keep library saves in; any .fpssomap file dropped here shows up in the Load dialog
+#[cfg(not(target_os = "emscripten"))]
+pub const LIBRARY_DIR: &str = "maps";
+
+/// Extension of library saves and editor exports
+pub const MAP_FILE_EXTENSION: &str = "fpssomap";
+
+/// One save in the local map library (a file on native, an IndexedDB record on web)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LibraryEntry {
+    /// Save name (file stem on native, record key on web)
+    pub name: String,
+    /// Last modified, seconds since the Unix epoch
+    pub modified: u64,
+    pub object_count: usize,
+    /// Serialized size
+    pub bytes: usize,
+}
+
+/// Save name for a map name: letters, digits, '-' and '_' only, so it is a valid file stem everywhere
+pub fn slot_name(name: &str) -> String {
+    let slot: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if slot.is_empty() {
+        "untitled".to_string()
+    } else {
+        slot
+    }
+}
+
+/// Most recently modified first
+pub fn sort_entries(entries: &mut [LibraryEntry]) {
+    entries.sort_by(|a, b| b.modified.cmp(&a.modified).then_with(|| a.name.cmp(&b.name)));
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// "just now", "12 min ago", "3 h ago", "5 days ago"
+pub fn format_age(modified: u64, now: u64) -> String {
+    let seconds = now.saturating_sub(modified);
+    match seconds {
+        0..=59 => t("editor.library.just_now").to_string(),
+        60..=3599 => tr!("editor.library.minutes_ago", count = seconds / 60),
+        3600..=86399 => tr!("editor.library.hours_ago", count = seconds / 3600),
+        _ => tr!("editor.library.days_ago", count = seconds / 86400),
+    }
+}
+
+/// Parse library bytes (Borsh, falling back to JSON like file import does)
+pub fn parse_map(bytes: &[u8]) -> Result<Map, String> {
+    Map::from_borsh_bytes(bytes).or_else(|_| Map::from_json_bytes(bytes).map_err(|e| format!("{}", e)))
+}
+
+/// Every readable save in `dir`, newest first (missing folder = empty library)
+#[cfg(not(target_os = "emscripten"))]
+pub fn list(dir: &Path) -> Vec<LibraryEntry> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut entries: Vec<LibraryEntry> = read_dir
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == MAP_FILE_EXTENSION))
+        .filter_map(|path| {
+            let bytes = fs::read(&path).ok()?;
+            let map = parse_map(&bytes).ok()?;
+            let modified = fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_secs());
+            Some(LibraryEntry {
+                name: path.file_stem()?.to_string_lossy().into_owned(),
+                modified,
+                object_count: map.objects.len(),
+                bytes: bytes.len(),
+            })
+        })
+        .collect();
+    sort_entries(&mut entries);
+    entries
+}
+
+/// Write `map` to `dir` as `name` (overwriting an existing save of that name)
+#[cfg(not(target_os = "emscripten"))]
+pub fn save(dir: &Path, name: &str, map: &Map) -> Result<LibraryEntry, String> {
+    let bytes = map.to_borsh_bytes().map_err(|e| format!("Failed to serialize map: {}", e))?;
+    let name = slot_name(name);
+
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    fs::write(dir.join(format!("{}.{}", name, MAP_FILE_EXTENSION)), &bytes)
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+
+    Ok(LibraryEntry { name, modified: unix_now(), object_count: map.objects.len(), bytes: bytes.len() })
+}
+
+#[cfg(not(target_os = "emscripten"))]
+pub fn load(dir: &Path, name: &str) -> Result<Map, String> {
+    let bytes = fs::read(dir.join(format!("{}.{}", name, MAP_FILE_EXTENSION)))
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    parse_map(&bytes)
+}
+
+#[cfg(not(target_os = "emscripten"))]
+pub fn delete(dir: &Path, name: &str) -> Result<(), String> {
+    fs::remove_file(dir.join(format!("{}.{}", name, MAP_FILE_EXTENSION)))
+        .map_err(|e| format!("Failed to delete file: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::{MapObject, ModelType};
+
+    #[test]
+    fn test_save_list_load_roundtrip() {
+        assert_eq!(slot_name("  My Arena v2! "), "My_Arena_v2_");
+        assert_eq!(slot_name(""), "untitled");
+        assert_eq!(format_age(1000, 1030), "just now");
+        assert_eq!(format_age(1000, 1000 + 7200), "2 h ago");
+
+        let dir = std::env::temp_dir().join(format!("fpsdotso-library-test-{}", std::process::id()));
+        let mut map = Map::new("Arena".to_string());
+        map.add_object(MapObject::new(ModelType::Cube));
+        map.add_object(MapObject::new(ModelType::SpawnPointRed));
+
+        let saved = save(&dir, "Arena One", &map).unwrap();
+        assert_eq!(saved.name, "Arena_One");
+
+        let entries = list(&dir);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].object_count, 2);
+        assert_eq!(entries[0].bytes, saved.bytes);
+        assert_eq!(load(&dir, "Arena_One").unwrap().objects.len(), 2);
+
+        delete(&dir, "Arena_One").unwrap();
+        assert!(list(&dir).is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
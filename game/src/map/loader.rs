@@ -0,0 +1,203 @@
+//! Map loading spread over frames: base64 decoding and Borsh parsing of a large map
+//! happen a slice per frame, then the sight grid, PVS and collision index are built a
+//! frame each, so match start doesn't freeze on one long frame
+
+use base64::{Engine as _, engine::general_purpose};
+use borsh::BorshDeserialize;
+
+use crate::raycaster::{Map2D, Pvs, MAP_GRID_CELL_SIZE, PVS_ZONE_SIZE};
+use super::map::Map;
+use super::spatial::SpatialIndex;
+
+/// Base64 characters decoded per step (a multiple of 4, so every slice decodes on its own)
+pub const DECODE_CHARS_PER_STEP: usize = 64 * 1024;
+
+/// Objects parsed per step
+pub const OBJECTS_PER_STEP: usize = 2048;
+
+enum Stage {
+    /// Decoding base64 text into bytes
+    Decode { text: String, position: usize, bytes: Vec<u8> },
+    /// Name, version and object count
+    Header { bytes: Vec<u8> },
+    /// Objects, `offset` bytes into the data
+    Objects { bytes: Vec<u8>, offset: usize, map: Map, remaining: u32 },
+    /// Rasterizing the sight grid
+    Grid { map: Map },
+    /// Building the potentially-visible set from the grid
+    Visibility { map: Map, grid: Map2D },
+    /// Bucketing collision bounds
+    Index { map: Map, grid: Map2D, pvs: Pvs },
+    /// Handed out or failed
+    Finished,
+}
+
+/// A map plus the queries the game builds from it at load
+pub struct LoadedMap {
+    pub map: Map,
+    /// Sight grid for the tactical overview and line-of-sight queries
+    pub grid: Map2D,
+    /// Potentially-visible set built from `grid`
+    pub pvs: Pvs,
+    pub index: SpatialIndex,
+}
+
+impl LoadedMap {
+    /// Build everything in one go, for maps small enough not to need the loading screen
+    pub fn build(map: Map) -> Self {
+        let grid = Map2D::from_map(&map, MAP_GRID_CELL_SIZE);
+        let pvs = Pvs::build(&grid, PVS_ZONE_SIZE);
+        let index = SpatialIndex::build(&map);
+        Self { map, grid, pvs, index }
+    }
+}
+
+/// Outcome of one `MapLoader::step`
+pub enum LoadStep {
+    /// More work left; the loader's `progress` says how much
+    Pending,
+    Done(LoadedMap),
+    Failed(String),
+}
+
+/// A map being decoded a step at a time (call `step` once per frame until it's done)
+pub struct MapLoader {
+    stage: Stage,
+    /// Objects in the map once the header is read
+    total_objects: u32,
+}
+
+impl MapLoader {
+    /// Load from the base64 text the bridge hands over in `Module.mapDataResult`
+    pub fn from_base64(text: String) -> Self {
+        let capacity = text.len() / 4 * 3;
+        Self { stage: Stage::Decode { text, position: 0, bytes: Vec::with_capacity(capacity) }, total_objects: 0 }
+    }
+
+    /// Load from Borsh bytes that are already decoded
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self { stage: Stage::Header { bytes }, total_objects: 0 }
+    }
+
+    /// Do one frame's worth of work
+    pub fn step(&mut self) -> LoadStep {
+        match std::mem::replace(&mut self.stage, Stage::Finished) {
+            Stage::Decode { text, position, mut bytes } => {
+                let end = (position + DECODE_CHARS_PER_STEP).min(text.len());
+                if let Err(e) = general_purpose::STANDARD.decode_vec(&text.as_bytes()[position..end], &mut bytes) {
+                    return LoadStep::Failed(format!("Failed to decode base64: {:?}", e));
+                }
+                self.stage = if end == text.len() { Stage::Header { bytes } } else { Stage::Decode { text, position: end, bytes } };
+                LoadStep::Pending
+            }
+            Stage::Header { bytes } => {
+                let mut buf = bytes.as_slice();
+                match Map::read_borsh_header(&mut buf) {
+                    Ok((map, count)) => {
+                        let offset = bytes.len() - buf.len();
+                        self.total_objects = count;
+                        self.stage = Stage::Objects { bytes, offset, map, remaining: count };
+                        LoadStep::Pending
+                    }
+                    Err(e) => LoadStep::Failed(format!("Failed to deserialize map from Borsh: {:?}", e)),
+                }
+            }
+            Stage::Objects { bytes, offset, mut map, remaining } => {
+                let mut buf = &bytes[offset..];
+                let batch = remaining.min(OBJECTS_PER_STEP as u32);
+                for _ in 0..batch {
+                    match BorshDeserialize::deserialize(&mut buf) {
+                        Ok(object) => map.objects.push(object),
+                        Err(e) => return LoadStep::Failed(format!("Failed to deserialize map from Borsh: {:?}", e)),
+                    }
+                }
+                if remaining > batch {
+                    let offset = bytes.len() - buf.len();
+                    self.stage = Stage::Objects { bytes, offset, map, remaining: remaining - batch };
+                    return LoadStep::Pending;
+                }
+                match map.read_borsh_tail(&mut buf) {
+                    Ok(()) => {
+                        self.stage = Stage::Grid { map };
+                        LoadStep::Pending
+                    }
+                    Err(e) => LoadStep::Failed(format!("Failed to deserialize map from Borsh: {:?}", e)),
+                }
+            }
+            Stage::Grid { map } => {
+                let grid = Map2D::from_map(&map, MAP_GRID_CELL_SIZE);
+                self.stage = Stage::Visibility { map, grid };
+                LoadStep::Pending
+            }
+            Stage::Visibility { map, grid } => {
+                let pvs = Pvs::build(&grid, PVS_ZONE_SIZE);
+                self.stage = Stage::Index { map, grid, pvs };
+                LoadStep::Pending
+            }
+            Stage::Index { map, grid, pvs } => {
+                let index = SpatialIndex::build(&map);
+                LoadStep::Done(LoadedMap { map, grid, pvs, index })
+            }
+            Stage::Finished => LoadStep::Failed("Map load already finished".to_string()),
+        }
+    }
+
+    /// Share of the work done, 0 to 1 (decoding counts for the first 40%, parsing the next
+    /// 40%, building the grid, PVS and index the rest)
+    pub fn progress(&self) -> f32 {
+        match &self.stage {
+            Stage::Decode { text, position, .. } => 0.4 * *position as f32 / text.len().max(1) as f32,
+            Stage::Header { .. } => 0.4,
+            Stage::Objects { remaining, .. } => {
+                0.4 + 0.4 * (self.total_objects - remaining) as f32 / self.total_objects.max(1) as f32
+            }
+            Stage::Grid { .. } => 0.8,
+            Stage::Visibility { .. } => 0.85,
+            Stage::Index { .. } => 0.95,
+            Stage::Finished => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::{MapObject, ModelType};
+
+    #[test]
+    fn test_stepped_load_matches_one_shot_decode() {
+        let mut map = Map::new("big".to_string());
+        for i in 0..OBJECTS_PER_STEP * 3 + 7 {
+            let mut object = MapObject::new(ModelType::Cube);
+            object.pos_x = i as i16;
+            map.add_object(object);
+        }
+        map.script = "every 5s: message hi".to_string();
+        let bytes = map.to_borsh_bytes().unwrap();
+        let text = general_purpose::STANDARD.encode(&bytes);
+        // Decode slices, the header, the object batches, then the grid, PVS and index
+        let expected_steps = text.len().div_ceil(DECODE_CHARS_PER_STEP) + 1 + map.objects.len().div_ceil(OBJECTS_PER_STEP) + 3;
+        assert!(expected_steps > 5);
+
+        let mut loader = MapLoader::from_base64(text);
+        let mut steps = 0;
+        let mut last_progress = 0.0;
+        let loaded = loop {
+            steps += 1;
+            match loader.step() {
+                LoadStep::Pending => {
+                    assert!(loader.progress() >= last_progress && loader.progress() < 1.0);
+                    last_progress = loader.progress();
+                }
+                LoadStep::Done(loaded) => break loaded.map,
+                LoadStep::Failed(e) => panic!("{}", e),
+            }
+        };
+        assert_eq!(steps, expected_steps);
+        assert_eq!(loaded.to_borsh_bytes().unwrap(), bytes);
+
+        // Truncated data fails instead of hanging
+        let mut loader = MapLoader::from_bytes(bytes[..bytes.len() / 2].to_vec());
+        assert!((0..10).any(|_| matches!(loader.step(), LoadStep::Failed(_))));
+    }
+}
@@ -1,11 +1,35 @@
 use raylib::prelude::*;
 use serde::{Deserialize, Serialize};
 use borsh::{BorshSerialize, BorshDeserialize};
+use crate::i18n::t;
+use crate::game::{MovementWorld, PLAYER_HEIGHT, PLAYER_RADIUS, STEP_HEIGHT};
+use super::ambiance::Ambiance;
+use super::triggers::{ObjectTrigger, TriggerAction};
+use super::ambient_sound::{AmbientEmitter, AmbientSound, MAX_AMBIENT_EMITTERS};
 
 /// Maximum world size (50x50 units)
 pub const WORLD_SIZE: f32 = 50.0;
 pub const WORLD_HALF_SIZE: f32 = WORLD_SIZE / 2.0;
 
+/// Current map format version (2 added object labels, 3 added ambiance, 4 added glowing objects,
+/// 5 added collision flags, 6 added trigger volumes, 7 added the map script, 8 added ambient sound emitters)
+pub const MAP_VERSION: u8 = 8;
+
+/// Longest object name ("mid box", "A site wall")
+pub const OBJECT_NAME_MAX_LEN: usize = 24;
+
+/// Longest comma-separated tag list on one object
+pub const OBJECT_TAGS_MAX_LEN: usize = 32;
+
+/// Glow an object starts with when it's made emissive (Solana purple, fairly strong)
+pub const DEFAULT_GLOW: ([u8; 3], u8) = ([153, 69, 255], 70);
+
+/// Opacity trigger volumes are drawn with in the editor (the game hides them)
+const TRIGGER_ALPHA: u8 = 70;
+
+/// Halo shells drawn around glowing objects: (size relative to the object, share of the glow)
+const GLOW_HALO_LAYERS: [(f32, f32); 3] = [(1.06, 0.35), (1.15, 0.18), (1.3, 0.08)];
+
 /// Types of 3D models that can be placed in the map
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
 pub enum ModelType {
@@ -19,10 +43,26 @@ pub enum ModelType {
     SpawnPointRed,
 }
 
+impl ModelType {
+    /// Localized display name
+    pub fn label(&self) -> &'static str {
+        match self {
+            ModelType::Cube => t("model.cube"),
+            ModelType::Rectangle => t("model.rectangle"),
+            ModelType::Triangle => t("model.triangle"),
+            ModelType::Sphere => t("model.sphere"),
+            ModelType::Cylinder => t("model.cylinder"),
+            ModelType::Plane => t("model.plane"),
+            ModelType::SpawnPointBlue => t("model.spawn_blue"),
+            ModelType::SpawnPointRed => t("model.spawn_red"),
+        }
+    }
+}
+
 /// Compact representation of a 3D object in the map
 /// Uses 16-bit integers for positions and rotations to save space
 /// Borsh-serialized for Solana/Anchor compatibility
-#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct MapObject {
     /// Model type
     pub model_type: ModelType,
@@ -138,19 +178,89 @@ impl MapObject {
         self.color_b = color.b;
     }
 
+    /// World-space axis-aligned bounds used for collision queries
+    /// (None for objects that don't block, e.g. spawn points)
+    pub fn collision_bounds(&self) -> Option<BoundingBox> {
+        let scale = self.get_scale();
+        let (half_x, half_z) = (scale.x / 2.0, scale.z / 2.0);
+
+        // Local extents, matching how `draw` places each model around its origin
+        let (local_min, local_max) = match self.model_type {
+            ModelType::Cube | ModelType::Rectangle => (
+                Vector3::new(-half_x, -scale.y / 2.0, -half_z),
+                Vector3::new(half_x, scale.y / 2.0, half_z),
+            ),
+            ModelType::Sphere => {
+                let radius = scale.x.max(scale.y).max(scale.z) / 2.0;
+                (Vector3::new(-radius, -radius, -radius), Vector3::new(radius, radius, radius))
+            }
+            ModelType::Cylinder => {
+                let radius = half_x.max(half_z);
+                (Vector3::new(-radius, 0.0, -radius), Vector3::new(radius, scale.y, radius))
+            }
+            ModelType::Triangle => (
+                Vector3::new(-half_x, 0.0, -0.05),
+                Vector3::new(half_x, scale.y, 0.05),
+            ),
+            ModelType::Plane => (
+                Vector3::new(-half_x, -0.05, -half_z),
+                Vector3::new(half_x, 0.05, half_z),
+            ),
+            ModelType::SpawnPointBlue | ModelType::SpawnPointRed => return None,
+        };
+
+        // Rotate the corners (Z, then X, then Y - same order as `draw`) and take their bounds
+        let rotation = self.get_rotation();
+        let position = self.get_position();
+        let mut min = Vector3::new(f32::MAX, f32::MAX, f32::MAX);
+        let mut max = Vector3::new(f32::MIN, f32::MIN, f32::MIN);
+        for i in 0..8 {
+            let corner = Vector3::new(
+                if i & 1 == 0 { local_min.x } else { local_max.x },
+                if i & 2 == 0 { local_min.y } else { local_max.y },
+                if i & 4 == 0 { local_min.z } else { local_max.z },
+            );
+            let world = position + rotate_euler_degrees(corner, rotation);
+            min = Vector3::new(min.x.min(world.x), min.y.min(world.y), min.z.min(world.z));
+            max = Vector3::new(max.x.max(world.x), max.y.max(world.y), max.z.max(world.z));
+        }
+
+        Some(BoundingBox::new(min, max))
+    }
+
+    /// Distance from `point` to the nearest part of this object (its bounds, or its position without any)
+    /// so big floors and walls count as close while standing on or beside them
+    pub fn distance_to(&self, point: Vector3) -> f32 {
+        match self.collision_bounds() {
+            Some(bounds) => {
+                let nearest = Vector3::new(
+                    point.x.clamp(bounds.min.x, bounds.max.x),
+                    point.y.clamp(bounds.min.y, bounds.max.y),
+                    point.z.clamp(bounds.min.z, bounds.max.z),
+                );
+                nearest.distance_to(point)
+            }
+            None => self.get_position().distance_to(point),
+        }
+    }
+
     /// Draw this object using Raylib with shading
     pub fn draw(&self, d: &mut RaylibMode3D<RaylibDrawHandle>) {
+        self.draw_tinted(d, self.get_color());
+    }
+
+    /// Draw this object in `color` instead of its own (lit and fogged by the map's ambiance)
+    pub fn draw_tinted(&self, d: &mut RaylibMode3D<RaylibDrawHandle>, color: Color) {
         let position = self.get_position();
         let rotation = self.get_rotation();
         let scale = self.get_scale();
-        let color = self.get_color();
 
         // Create lighter/brighter color for wireframe (light blue for prototype look)
         let wire_color = Color::new(
             color.r.saturating_add(80).min(255),
             color.g.saturating_add(80).min(255),
             color.b.saturating_add(50).min(255),
-            255,
+            color.a,
         );
 
         // Apply rotation using push/pop matrix
@@ -314,6 +424,252 @@ impl MapObject {
             raylib::ffi::rlPopMatrix();
         }
     }
+
+    /// Fake bloom: additive shells a little larger than the object, fading outwards
+    /// Drawn after opaque geometry without writing depth so they never hide what's behind them
+    pub fn draw_glow_halo(&self, d: &mut RaylibMode3D<RaylibDrawHandle>, glow: Color) {
+        let position = self.get_position();
+        unsafe {
+            raylib::ffi::rlDrawRenderBatchActive();
+            raylib::ffi::rlDisableDepthMask();
+            raylib::ffi::BeginBlendMode(raylib::ffi::BlendMode::BLEND_ADDITIVE as i32);
+        }
+        for (size, share) in GLOW_HALO_LAYERS {
+            let alpha = (glow.a as f32 * share).round() as u8;
+            unsafe {
+                raylib::ffi::rlPushMatrix();
+                raylib::ffi::rlTranslatef(position.x, position.y, position.z);
+                raylib::ffi::rlScalef(size, size, size);
+                raylib::ffi::rlTranslatef(-position.x, -position.y, -position.z);
+            }
+            self.draw_tinted(d, Color::new(glow.r, glow.g, glow.b, alpha));
+            unsafe {
+                raylib::ffi::rlPopMatrix();
+            }
+        }
+        unsafe {
+            raylib::ffi::EndBlendMode();
+            raylib::ffi::rlEnableDepthMask();
+        }
+    }
+}
+
+/// Rotate a point by Euler angles in degrees (applied Z, then X, then Y)
+fn rotate_euler_degrees(point: Vector3, rotation: Vector3) -> Vector3 {
+    let (sin_z, cos_z) = rotation.z.to_radians().sin_cos();
+    let p = Vector3::new(point.x * cos_z - point.y * sin_z, point.x * sin_z + point.y * cos_z, point.z);
+
+    let (sin_x, cos_x) = rotation.x.to_radians().sin_cos();
+    let p = Vector3::new(p.x, p.y * cos_x - p.z * sin_x, p.y * sin_x + p.z * cos_x);
+
+    let (sin_y, cos_y) = rotation.y.to_radians().sin_cos();
+    Vector3::new(p.x * cos_y + p.z * sin_y, p.y, -p.x * sin_y + p.z * cos_y)
+}
+
+/// `Map::blocks_player` against precomputed collision bounds (for callers testing many positions)
+pub fn player_blocked_by(bounds: &[BoundingBox], position: Vector3, radius: f32, height: f32, step_height: f32) -> bool {
+    let min = Vector3::new(position.x - radius, position.y + step_height, position.z - radius);
+    let max = Vector3::new(position.x + radius, position.y + height, position.z + radius);
+    bounds.iter().any(|b| {
+        min.x < b.max.x && max.x > b.min.x
+            && min.y < b.max.y && max.y > b.min.y
+            && min.z < b.max.z && max.z > b.min.z
+    })
+}
+
+/// Top of the highest box under a player's footprint at (x, z) that is no higher than `max_y` (0.0 without one)
+pub fn player_floor_on(bounds: &[BoundingBox], x: f32, z: f32, radius: f32, max_y: f32) -> f32 {
+    bounds
+        .iter()
+        .filter(|b| x - radius < b.max.x && x + radius > b.min.x && z - radius < b.max.z && z + radius > b.min.z)
+        .map(|b| b.max.y)
+        .filter(|&top| top <= max_y)
+        .fold(0.0, f32::max)
+}
+
+/// Slab test: does the segment from `from` to `to` touch the box?
+pub(super) fn segment_intersects_box(from: Vector3, to: Vector3, bounds: &BoundingBox) -> bool {
+    let direction = to - from;
+    let mut t_min = 0.0f32;
+    let mut t_max = 1.0f32;
+
+    for (origin, dir, min, max) in [
+        (from.x, direction.x, bounds.min.x, bounds.max.x),
+        (from.y, direction.y, bounds.min.y, bounds.max.y),
+        (from.z, direction.z, bounds.min.z, bounds.max.z),
+    ] {
+        if dir.abs() < f32::EPSILON {
+            // Parallel to this slab: must already be inside it
+            if origin < min || origin > max {
+                return false;
+            }
+        } else {
+            let t1 = (min - origin) / dir;
+            let t2 = (max - origin) / dir;
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+            if t_min > t_max {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Optional author label for one map object
+/// Stored sparsely on the map so unlabeled objects cost nothing in the serialized size
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct ObjectLabel {
+    /// Index into `Map::objects`
+    pub object: u16,
+    /// Display name shown in the editor ("mid box")
+    pub name: String,
+    /// Comma-separated tags gameplay systems look objects up by ("a-site,objective")
+    pub tags: String,
+}
+
+impl ObjectLabel {
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        self.tags.split(',').filter(|t| !t.is_empty())
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags().any(|t| t == tag)
+    }
+}
+
+/// Optional emissive material for one map object (neon strips, glowing signs)
+/// Stored sparsely like labels; glowing surfaces ignore the map's light level and get a halo
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct ObjectGlow {
+    /// Index into `Map::objects`
+    pub object: u16,
+    pub color: [u8; 3],
+    /// Strength in percent: how far the surface is pulled toward the glow color, and how bright the halo is
+    pub intensity: u8,
+}
+
+impl ObjectGlow {
+    pub fn color(&self) -> Color {
+        Color::new(self.color[0], self.color[1], self.color[2], 255)
+    }
+
+    fn strength(&self) -> f32 {
+        self.intensity.min(100) as f32 / 100.0
+    }
+
+    /// Surface color: `shaded` pulled toward the glow color by the intensity
+    pub fn emit(&self, shaded: Color) -> Color {
+        let amount = self.strength();
+        let blend = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * amount).round() as u8;
+        Color::new(blend(shaded.r, self.color[0]), blend(shaded.g, self.color[1]), blend(shaded.b, self.color[2]), shaded.a)
+    }
+
+    /// Halo color, its alpha dimmed by the intensity and `fade` (distance fog, 1 = gone)
+    pub fn halo(&self, fade: f32) -> Color {
+        let amount = self.strength() * (1.0 - fade.clamp(0.0, 1.0));
+        Color::new(self.color[0], self.color[1], self.color[2], (amount * 255.0).round() as u8)
+    }
+}
+
+/// What an object stops: players, bullets (and sight), both or neither
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub enum Collision {
+    /// Blocks players and bullets
+    #[default]
+    Solid,
+    /// Stops bullets and sight, players walk through (foliage, curtains)
+    BulletOnly,
+    /// Pure decoration, nothing collides with it
+    NonSolid,
+    /// Keeps players out, bullets and sight pass (railings, invisible walls)
+    PlayerClip,
+}
+
+impl Collision {
+    pub const ALL: [Collision; 4] = [Collision::Solid, Collision::BulletOnly, Collision::NonSolid, Collision::PlayerClip];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Collision::Solid => t("editor.collision.solid"),
+            Collision::BulletOnly => t("editor.collision.bullet_only"),
+            Collision::NonSolid => t("editor.collision.non_solid"),
+            Collision::PlayerClip => t("editor.collision.player_clip"),
+        }
+    }
+
+    pub fn blocks_players(&self) -> bool {
+        matches!(self, Collision::Solid | Collision::PlayerClip)
+    }
+
+    pub fn blocks_bullets(&self) -> bool {
+        matches!(self, Collision::Solid | Collision::BulletOnly)
+    }
+}
+
+/// Collision of one map object that isn't plain solid
+/// Stored sparsely like labels and published with them in side-table accounts, so the on-chain object layout stays the same
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct ObjectCollision {
+    /// Index into `Map::objects`
+    pub object: u16,
+    pub collision: Collision,
+}
+
+/// Per-object data stored beside `Map::objects` (labels, glows, ...), pointing at its object by index
+trait ObjectEntry {
+    fn object_index(&mut self) -> &mut u16;
+}
+
+impl ObjectEntry for ObjectLabel {
+    fn object_index(&mut self) -> &mut u16 {
+        &mut self.object
+    }
+}
+
+impl ObjectEntry for ObjectGlow {
+    fn object_index(&mut self) -> &mut u16 {
+        &mut self.object
+    }
+}
+
+impl ObjectEntry for ObjectCollision {
+    fn object_index(&mut self) -> &mut u16 {
+        &mut self.object
+    }
+}
+
+impl ObjectEntry for ObjectTrigger {
+    fn object_index(&mut self) -> &mut u16 {
+        &mut self.object
+    }
+}
+
+/// Renumber `entries` after an object was inserted at `index`, or drop the removed object's
+/// entry and renumber the ones after it
+fn shift_entries<T: ObjectEntry>(entries: &mut Vec<T>, index: usize, inserted: bool) {
+    if !inserted {
+        entries.retain_mut(|entry| *entry.object_index() as usize != index);
+    }
+    for entry in entries.iter_mut() {
+        let object = entry.object_index();
+        match inserted {
+            true if *object as usize >= index => *object += 1,
+            false if *object as usize > index => *object -= 1,
+            _ => {}
+        }
+    }
+}
+
+/// Tidy a tag list as typed: lowercase, spaces become dashes, only [a-z0-9_-,] kept
+pub(super) fn normalize_tags(tags: &str) -> String {
+    tags.trim()
+        .chars()
+        .map(|c| if c == ' ' { '-' } else { c.to_ascii_lowercase() })
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ','))
+        .take(OBJECT_TAGS_MAX_LEN)
+        .collect()
 }
 
 /// Map data structure - designed to fit in ~10KB
@@ -332,6 +688,35 @@ pub struct Map {
     pub spawn_x: i16,
     pub spawn_y: i16,
     pub spawn_z: i16,
+
+    /// Names/tags for labeled objects, sorted by object index
+    /// Published maps keep them, like every table after the spawn point, in side-table accounts
+    #[serde(default)]
+    pub labels: Vec<ObjectLabel>,
+
+    /// Sky, fog and light level
+    #[serde(default)]
+    pub ambiance: Ambiance,
+
+    /// Emissive materials for glowing objects, sorted by object index
+    #[serde(default)]
+    pub glows: Vec<ObjectGlow>,
+
+    /// Objects that aren't plain solid, sorted by object index
+    #[serde(default)]
+    pub collisions: Vec<ObjectCollision>,
+
+    /// Objects turned into trigger volumes, sorted by object index
+    #[serde(default)]
+    pub triggers: Vec<ObjectTrigger>,
+
+    /// Source of the map script (see `MapScript`), empty for none
+    #[serde(default)]
+    pub script: String,
+
+    /// Looping wind/hum/machinery sounds placed around the map
+    #[serde(default)]
+    pub ambient_sounds: Vec<AmbientEmitter>,
 }
 
 impl Map {
@@ -339,11 +724,18 @@ impl Map {
     pub fn new(name: String) -> Self {
         Self {
             name,
-            version: 1,
+            version: MAP_VERSION,
             objects: Vec::new(),
             spawn_x: 0,
             spawn_y: 1000, // 10.0 units up
             spawn_z: 0,
+            labels: Vec::new(),
+            ambiance: Ambiance::default(),
+            glows: Vec::new(),
+            collisions: Vec::new(),
+            triggers: Vec::new(),
+            script: String::new(),
+            ambient_sounds: Vec::new(),
         }
     }
 
@@ -352,15 +744,74 @@ impl Map {
         self.objects.push(object);
     }
 
+    /// Insert an object at `index` (clamped to the end), keeping labels, glows, collision flags
+    /// and triggers on their objects
+    pub fn insert_object(&mut self, index: usize, object: MapObject) {
+        let index = index.min(self.objects.len());
+        self.shift_entries(index, true);
+        self.objects.insert(index, object);
+    }
+
     /// Remove an object by index
     pub fn remove_object(&mut self, index: usize) -> Option<MapObject> {
         if index < self.objects.len() {
+            // Drop its side-table entries and shift those of later objects down
+            self.shift_entries(index, false);
             Some(self.objects.remove(index))
         } else {
             None
         }
     }
 
+    /// Keep every side table pointing at the right objects around an insert or removal at `index`
+    fn shift_entries(&mut self, index: usize, inserted: bool) {
+        shift_entries(&mut self.labels, index, inserted);
+        shift_entries(&mut self.glows, index, inserted);
+        shift_entries(&mut self.collisions, index, inserted);
+        shift_entries(&mut self.triggers, index, inserted);
+    }
+
+    /// Label of an object, if it has one
+    pub fn label(&self, index: usize) -> Option<&ObjectLabel> {
+        self.labels.iter().find(|label| label.object as usize == index)
+    }
+
+    /// Name of an object, if it has one
+    pub fn object_name(&self, index: usize) -> Option<&str> {
+        self.label(index).map(|label| label.name.as_str()).filter(|name| !name.is_empty())
+    }
+
+    /// Set an object's name and tags (both empty removes the label)
+    pub fn set_object_label(&mut self, index: usize, name: &str, tags: &str) {
+        if index >= self.objects.len() {
+            return;
+        }
+        let name: String = name.chars().take(OBJECT_NAME_MAX_LEN).collect();
+        let tags = normalize_tags(tags);
+
+        self.labels.retain(|label| label.object as usize != index);
+        if name.trim().is_empty() && tags.is_empty() {
+            return;
+        }
+        let label = ObjectLabel { object: index as u16, name, tags };
+        let position = self.labels.partition_point(|l| l.object < label.object);
+        self.labels.insert(position, label);
+    }
+
+    /// Indices of every object carrying `tag` (for objectives, movers, triggers...)
+    pub fn objects_with_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = usize> + 'a {
+        self.labels.iter().filter(move |label| label.has_tag(tag)).map(|label| label.object as usize)
+    }
+
+    /// Place a new ambient sound emitter, returning its index (None once the map has `MAX_AMBIENT_EMITTERS`)
+    pub fn add_ambient_sound(&mut self, sound: AmbientSound, position: Vector3) -> Option<usize> {
+        if self.ambient_sounds.len() >= MAX_AMBIENT_EMITTERS {
+            return None;
+        }
+        self.ambient_sounds.push(AmbientEmitter::new(sound, position));
+        Some(self.ambient_sounds.len() - 1)
+    }
+
     /// Get spawn position as Vector3
     pub fn get_spawn_position(&self) -> Vector3 {
         Vector3::new(
@@ -370,6 +821,102 @@ impl Map {
         )
     }
 
+    /// Emissive material of an object, if it glows
+    pub fn glow(&self, index: usize) -> Option<&ObjectGlow> {
+        self.glows.iter().find(|glow| glow.object as usize == index)
+    }
+
+    /// Make an object glow (intensity 0 turns it back into a plain surface)
+    pub fn set_object_glow(&mut self, index: usize, color: [u8; 3], intensity: u8) {
+        if index >= self.objects.len() {
+            return;
+        }
+        self.glows.retain(|glow| glow.object as usize != index);
+        if intensity == 0 {
+            return;
+        }
+        let glow = ObjectGlow { object: index as u16, color, intensity: intensity.min(100) };
+        let position = self.glows.partition_point(|g| g.object < glow.object);
+        self.glows.insert(position, glow);
+    }
+
+    /// What object `index` collides with (nothing, for trigger volumes)
+    pub fn collision(&self, index: usize) -> Collision {
+        if self.trigger(index).is_some() {
+            return Collision::NonSolid;
+        }
+        self.collisions.iter().find(|c| c.object as usize == index).map_or(Collision::Solid, |c| c.collision)
+    }
+
+    /// Trigger of an object, if it's a trigger volume
+    pub fn trigger(&self, index: usize) -> Option<&ObjectTrigger> {
+        self.triggers.iter().find(|trigger| trigger.object as usize == index)
+    }
+
+    /// Turn an object into a trigger volume (None turns it back into a plain object)
+    /// Objects without collision bounds (spawn points) have no volume and can't be triggers
+    pub fn set_object_trigger(&mut self, index: usize, trigger: Option<(TriggerAction, bool)>) {
+        if self.objects.get(index).and_then(|object| object.collision_bounds()).is_none() {
+            return;
+        }
+        self.triggers.retain(|trigger| trigger.object as usize != index);
+        let Some((action, once)) = trigger else {
+            return;
+        };
+        let trigger = ObjectTrigger { object: index as u16, action, once };
+        let position = self.triggers.partition_point(|t| t.object < trigger.object);
+        self.triggers.insert(position, trigger);
+    }
+
+    /// Every trigger with the volume it watches
+    pub fn trigger_volumes(&self) -> impl Iterator<Item = (&ObjectTrigger, BoundingBox)> + '_ {
+        self.triggers.iter().filter_map(|trigger| {
+            let bounds = self.objects.get(trigger.object as usize)?.collision_bounds()?;
+            Some((trigger, bounds))
+        })
+    }
+
+    /// Where a teleport to `tag` lands: on top of the first tagged object that isn't a trigger
+    /// (at the marker itself for spawn points and other objects without bounds)
+    pub fn tag_anchor(&self, tag: &str) -> Option<Vector3> {
+        let index = self.objects_with_tag(tag).find(|&index| index < self.objects.len() && self.trigger(index).is_none())?;
+        let object = &self.objects[index];
+        Some(match object.collision_bounds() {
+            Some(bounds) => Vector3::new((bounds.min.x + bounds.max.x) / 2.0, bounds.max.y, (bounds.min.z + bounds.max.z) / 2.0),
+            None => object.get_position(),
+        })
+    }
+
+    /// Change what an object collides with (solid is the default and isn't stored)
+    pub fn set_object_collision(&mut self, index: usize, collision: Collision) {
+        if index >= self.objects.len() {
+            return;
+        }
+        self.collisions.retain(|c| c.object as usize != index);
+        if collision == Collision::Solid {
+            return;
+        }
+        let entry = ObjectCollision { object: index as u16, collision };
+        let position = self.collisions.partition_point(|c| c.object < entry.object);
+        self.collisions.insert(position, entry);
+    }
+
+    /// Collision bounds of every object whose collision passes `keep`
+    pub(super) fn collision_bounds_where(&self, keep: fn(Collision) -> bool) -> impl Iterator<Item = (BoundingBox, Collision)> + '_ {
+        self.objects
+            .iter()
+            .enumerate()
+            .map(|(index, object)| (object, self.collision(index)))
+            .filter(move |(_, collision)| keep(*collision))
+            .filter_map(|(object, collision)| object.collision_bounds().map(|bounds| (bounds, collision)))
+    }
+
+    /// Color to draw object `index` in from `distance` away: ambiance light and fog, then its glow
+    pub fn shade_object(&self, index: usize, distance: f32) -> Color {
+        let shaded = self.ambiance.shade(self.objects[index].get_color(), distance);
+        self.glow(index).map_or(shaded, |glow| glow.emit(shaded))
+    }
+
     /// Set spawn position
     pub fn set_spawn_position(&mut self, pos: Vector3) {
         self.spawn_x = (pos.x.clamp(-WORLD_HALF_SIZE, WORLD_HALF_SIZE) * 100.0) as i16;
@@ -377,21 +924,109 @@ impl Map {
         self.spawn_z = (pos.z.clamp(-WORLD_HALF_SIZE, WORLD_HALF_SIZE) * 100.0) as i16;
     }
 
-    /// Render all objects in the map
-    pub fn render(&self, d: &mut RaylibMode3D<RaylibDrawHandle>) {
-        for object in &self.objects {
-            object.draw(d);
+    /// Render all objects in the map, shaded by its ambiance as seen from `eye`, then the glow halos
+    /// (the editor view: trigger volumes are drawn too)
+    pub fn render(&self, d: &mut RaylibMode3D<RaylibDrawHandle>, eye: Vector3) {
+        for (index, object) in self.objects.iter().enumerate() {
+            let color = self.shade_object(index, object.distance_to(eye));
+            // Trigger volumes are only seen here, as see-through boxes
+            let color = if self.trigger(index).is_some() { Color::new(color.r, color.g, color.b, TRIGGER_ALPHA) } else { color };
+            object.draw_tinted(d, color);
+        }
+        for glow in &self.glows {
+            if let Some(object) = self.objects.get(glow.object as usize) {
+                object.draw_glow_halo(d, glow.halo(0.0));
+            }
         }
     }
 
+    /// Whether the straight line between two points passes through any map geometry
+    /// that stops bullets (cheap line-of-sight check against each object's collision bounds)
+    pub fn is_line_blocked(&self, from: Vector3, to: Vector3) -> bool {
+        self.collision_bounds_where(|c| c.blocks_bullets())
+            .any(|(bounds, _)| segment_intersects_box(from, to, &bounds))
+    }
+
+    /// Nearest bullet-stopping surface hit by a ray within `max_distance`, as (point, surface normal)
+    pub fn raycast(&self, ray: Ray, max_distance: f32) -> Option<(Vector3, Vector3)> {
+        self.collision_bounds_where(|c| c.blocks_bullets())
+            .map(|(bounds, _)| get_ray_collision_box(ray, bounds))
+            .filter(|hit| hit.hit && hit.distance <= max_distance)
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+            .map(|hit| (hit.point, hit.normal))
+    }
+
+    /// Whether a player standing at `position` (feet) would overlap map geometry.
+    /// Geometry below `step_height` above the feet is ignored so low ledges can be walked over.
+    pub fn blocks_player(&self, position: Vector3, radius: f32, height: f32, step_height: f32) -> bool {
+        player_blocked_by(&self.solid_bounds(), position, radius, height, step_height)
+    }
+
+    /// Collision bounds of every object that blocks players
+    fn solid_bounds(&self) -> Vec<BoundingBox> {
+        self.collision_bounds_where(|c| c.blocks_players()).map(|(bounds, _)| bounds).collect()
+    }
+
     /// Save map to Borsh bytes (compact binary format for Solana)
     pub fn to_borsh_bytes(&self) -> Result<Vec<u8>, std::io::Error> {
         borsh::to_vec(self)
     }
 
-    /// Load map from Borsh bytes
+    /// Load map from Borsh bytes: the version 1 fields, then whichever later sections
+    /// (labels, ambiance, glows, collision flags, triggers, script, ambient sounds) the file has. Older clients still
+    /// produce version 1, and maps published without side tables load from chain as version 1
     pub fn from_borsh_bytes(bytes: &[u8]) -> Result<Self, std::io::Error> {
-        borsh::from_slice(bytes)
+        let mut buf = bytes;
+        let (mut map, count) = Map::read_borsh_header(&mut buf)?;
+        for _ in 0..count {
+            map.objects.push(BorshDeserialize::deserialize(&mut buf)?);
+        }
+        map.read_borsh_tail(&mut buf)?;
+        Ok(map)
+    }
+
+    /// Start of the Borsh layout: name and version (as an otherwise empty map) and the object count.
+    /// The objects follow, then `read_borsh_tail`; `MapLoader` reads them a batch per frame
+    pub fn read_borsh_header(buf: &mut &[u8]) -> Result<(Self, u32), std::io::Error> {
+        let mut map = Map::new(BorshDeserialize::deserialize(buf)?);
+        map.version = BorshDeserialize::deserialize(buf)?;
+        let count: u32 = BorshDeserialize::deserialize(buf)?;
+        // Every object takes at least 16 bytes, so a corrupt count can't reserve more than the data holds
+        map.objects.reserve((count as usize).min(buf.len() / 16));
+        Ok((map, count))
+    }
+
+    /// Everything after the objects: the spawn point and the sections later versions appended
+    pub fn read_borsh_tail(&mut self, buf: &mut &[u8]) -> Result<(), std::io::Error> {
+        self.spawn_x = BorshDeserialize::deserialize(buf)?;
+        self.spawn_y = BorshDeserialize::deserialize(buf)?;
+        self.spawn_z = BorshDeserialize::deserialize(buf)?;
+        // Each version appended one section, so older files simply end early
+        if !buf.is_empty() {
+            self.labels = BorshDeserialize::deserialize(buf)?;
+        }
+        if !buf.is_empty() {
+            self.ambiance = BorshDeserialize::deserialize(buf)?;
+        }
+        if !buf.is_empty() {
+            self.glows = BorshDeserialize::deserialize(buf)?;
+        }
+        if !buf.is_empty() {
+            self.collisions = BorshDeserialize::deserialize(buf)?;
+        }
+        if !buf.is_empty() {
+            self.triggers = BorshDeserialize::deserialize(buf)?;
+        }
+        if !buf.is_empty() {
+            self.script = BorshDeserialize::deserialize(buf)?;
+        }
+        if !buf.is_empty() {
+            self.ambient_sounds = BorshDeserialize::deserialize(buf)?;
+        }
+        if !buf.is_empty() {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Unexpected bytes after map data"));
+        }
+        Ok(())
     }
 
     /// Save map to JSON bytes (legacy format, for backwards compatibility)
@@ -427,7 +1062,17 @@ impl Map {
         //   - color: 3 * 1 byte = 3 bytes
         //   Total per object: ~16 bytes
         // - spawn: 3 * 2 bytes = 6 bytes
-        4 + self.name.len() + 1 + 4 + (self.objects.len() * 16) + 6
+        // - labels: 4 bytes (length) + 2 (index) + 4 + name.len() + 4 + tags.len() each
+        // - ambiance: 3 (sky) + 3 (fog) + 1 (density) + 1 (light) = 8 bytes
+        // - glows: 4 bytes (length) + 2 (index) + 3 (color) + 1 (intensity) each
+        // - collisions: 4 bytes (length) + 2 (index) + 1 (flag) each
+        // - triggers: 4 bytes (length) + 2 (index) + 1 (action) + 4 + text.len() + 1 (once) each
+        // - script: 4 bytes (length) + script.len()
+        // - ambient sounds: 4 bytes (length) + 1 (sound) + 6 (position) + 1 (radius) + 1 (volume) each
+        let labels: usize = self.labels.iter().map(|l| 10 + l.name.len() + l.tags.len()).sum();
+        let triggers: usize = self.triggers.iter().map(|trigger| 8 + trigger.action.text().map_or(1, str::len)).sum();
+        4 + self.name.len() + 1 + 4 + (self.objects.len() * 16) + 6 + 4 + labels + 8 + 4 + self.glows.len() * 6
+            + 4 + self.collisions.len() * 3 + 4 + triggers + 4 + self.script.len() + 4 + self.ambient_sounds.len() * 9
     }
 
     /// Get estimated size in bytes (legacy, for backwards compatibility)
@@ -437,9 +1082,21 @@ impl Map {
     }
 }
 
+/// Brute-force collision for callers whose map changes between frames (the editor walkthrough)
+impl MovementWorld for Map {
+    fn blocks(&self, x: f32, y: f32, z: f32) -> bool {
+        self.blocks_player(Vector3::new(x, y, z), PLAYER_RADIUS, PLAYER_HEIGHT, STEP_HEIGHT)
+    }
+
+    fn floor_height(&self, x: f32, z: f32, max_y: f32) -> f32 {
+        player_floor_on(&self.solid_bounds(), x, z, PLAYER_RADIUS, max_y)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::map::AmbiancePreset;
 
     #[test]
     fn test_map_object_position() {
@@ -451,6 +1108,19 @@ mod tests {
         assert!((pos.z - 3.7).abs() < 0.1);
     }
 
+    #[test]
+    fn test_line_blocked_by_wall() {
+        let mut map = Map::new("Test Map".to_string());
+        let mut wall = MapObject::new(ModelType::Cube);
+        wall.set_position(Vector3::new(0.0, 1.0, 0.0));
+        wall.set_scale(Vector3::new(1.0, 4.0, 10.0));
+        map.add_object(wall);
+
+        assert!(map.is_line_blocked(Vector3::new(-5.0, 1.5, 0.0), Vector3::new(5.0, 1.5, 0.0)));
+        assert!(!map.is_line_blocked(Vector3::new(-5.0, 1.5, 0.0), Vector3::new(-2.0, 1.5, 0.0)));
+        assert!(!map.is_line_blocked(Vector3::new(-5.0, 4.0, 0.0), Vector3::new(5.0, 4.0, 0.0)));
+    }
+
     #[test]
     fn test_map_json_serialization() {
         let mut map = Map::new("Test Map".to_string());
@@ -486,6 +1156,139 @@ mod tests {
         assert_eq!(loaded_map.objects[1].model_type, ModelType::Sphere);
     }
 
+    #[test]
+    fn test_ambient_sounds_round_trip() {
+        let mut map = Map::new("Ambient".to_string());
+        map.add_object(MapObject::new(ModelType::Cube));
+        assert_eq!(map.add_ambient_sound(AmbientSound::Wind, Vector3::new(3.0, 2.0, -4.0)), Some(0));
+        assert_eq!(map.add_ambient_sound(AmbientSound::Machinery, Vector3::zero()), Some(1));
+
+        let loaded_map = Map::from_borsh_bytes(&map.to_borsh_bytes().unwrap()).unwrap();
+        assert_eq!(loaded_map.ambient_sounds, map.ambient_sounds);
+        assert_eq!(loaded_map.ambient_sounds[0].position(), Vector3::new(3.0, 2.0, -4.0));
+
+        while map.ambient_sounds.len() < MAX_AMBIENT_EMITTERS {
+            map.add_ambient_sound(AmbientSound::Hum, Vector3::zero());
+        }
+        assert_eq!(map.add_ambient_sound(AmbientSound::Hum, Vector3::zero()), None);
+    }
+
+    #[test]
+    fn test_labels_survive_removal_and_load_legacy_maps() {
+        let mut map = Map::new("Labels".to_string());
+        for _ in 0..3 {
+            map.add_object(MapObject::new(ModelType::Cube));
+        }
+        map.set_object_label(0, "spawn wall", "");
+        map.set_object_label(2, "mid box", "Mid Cover,objective");
+        assert_eq!(map.label(2).unwrap().tags, "mid-cover,objective");
+
+        // Removing object 0 drops its label and shifts "mid box" to index 1
+        map.remove_object(0);
+        assert_eq!(map.object_name(1), Some("mid box"));
+        assert_eq!(map.objects_with_tag("objective").collect::<Vec<_>>(), vec![1]);
+
+        let loaded = Map::from_borsh_bytes(&map.to_borsh_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.labels, map.labels);
+
+        // Version 2 bytes are the current layout minus the trailing ambiance, glows, collisions, triggers, script and
+        // ambient sounds, version 1 also drops the labels vec
+        let mut legacy = map.clone();
+        legacy.ambiance = AmbiancePreset::Toxic.ambiance();
+        let mut bytes = legacy.to_borsh_bytes().unwrap();
+        bytes.truncate(bytes.len() - 28);
+        let loaded = Map::from_borsh_bytes(&bytes).unwrap();
+        assert_eq!(loaded.labels, map.labels);
+        assert_eq!(loaded.ambiance, Ambiance::default());
+
+        legacy.labels.clear();
+        let mut bytes = legacy.to_borsh_bytes().unwrap();
+        bytes.truncate(bytes.len() - 32);
+        let loaded = Map::from_borsh_bytes(&bytes).unwrap();
+        assert_eq!(loaded.objects.len(), 2);
+        assert!(loaded.labels.is_empty());
+    }
+
+    #[test]
+    fn test_glows_follow_their_objects_and_override_the_light_level() {
+        let mut map = Map::new("Neon".to_string());
+        for _ in 0..3 {
+            map.add_object(MapObject::new(ModelType::Cube));
+        }
+        map.ambiance = AmbiancePreset::Blackout.ambiance();
+        map.set_object_glow(2, DEFAULT_GLOW.0, 100);
+
+        map.insert_object(0, MapObject::new(ModelType::Sphere));
+        assert!(map.glow(2).is_none());
+        assert_eq!(map.shade_object(3, 0.0), Color::new(153, 69, 255, 255));
+        assert_ne!(map.shade_object(2, 0.0), map.shade_object(3, 0.0));
+
+        let loaded = Map::from_borsh_bytes(&map.to_borsh_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.glows, map.glows);
+
+        map.set_object_glow(3, DEFAULT_GLOW.0, 0);
+        assert!(map.glows.is_empty());
+    }
+
+    #[test]
+    fn test_collision_flags_split_what_players_and_bullets_hit() {
+        let mut map = Map::new("Clip".to_string());
+        for x in [-2.0, 0.0, 2.0] {
+            let mut wall = MapObject::new(ModelType::Cube);
+            wall.set_position(Vector3::new(x, 1.0, 0.0));
+            wall.set_scale(Vector3::new(1.0, 2.0, 1.0));
+            map.add_object(wall);
+        }
+        map.set_object_collision(0, Collision::PlayerClip);
+        map.set_object_collision(1, Collision::BulletOnly);
+        map.set_object_collision(2, Collision::NonSolid);
+
+        let (left, middle, right) = (Vector3::new(-2.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0));
+        assert!(map.blocks_player(left, 0.3, 1.8, 0.4));
+        assert!(!map.blocks_player(middle, 0.3, 1.8, 0.4));
+        assert!(!map.blocks_player(right, 0.3, 1.8, 0.4));
+        let across = |x: f32| (Vector3::new(x, 1.0, -3.0), Vector3::new(x, 1.0, 3.0));
+        assert!(!map.is_line_blocked(across(-2.0).0, across(-2.0).1));
+        assert!(map.is_line_blocked(across(0.0).0, across(0.0).1));
+        assert!(!map.is_line_blocked(across(2.0).0, across(2.0).1));
+
+        // Flags follow their objects and round-trip; solid is the unstored default
+        map.remove_object(0);
+        assert_eq!(map.collision(0), Collision::BulletOnly);
+        let loaded = Map::from_borsh_bytes(&map.to_borsh_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.collisions, map.collisions);
+        map.set_object_collision(0, Collision::Solid);
+        assert_eq!(map.collisions.len(), 1);
+    }
+
+    #[test]
+    fn test_triggers_are_hidden_volumes_that_follow_their_objects() {
+        let mut map = Map::new("Set piece".to_string());
+        let mut pad = MapObject::new(ModelType::Cube);
+        pad.set_position(Vector3::new(4.0, 1.0, 0.0));
+        map.add_object(MapObject::new(ModelType::SpawnPointRed));
+        map.add_object(MapObject::new(ModelType::Cube));
+        map.add_object(pad);
+        map.set_object_label(2, "", "landing");
+
+        let mut teleport = TriggerAction::TeleportToTag(String::new());
+        teleport.set_text("Landing Pad");
+        assert_eq!(teleport, TriggerAction::TeleportToTag("landing-pad".to_string()));
+        map.set_object_trigger(0, Some((teleport.clone(), false)));
+        assert!(map.triggers.is_empty(), "spawn points have no volume");
+        map.set_object_trigger(1, Some((TriggerAction::TeleportToTag("landing".to_string()), true)));
+        assert_eq!(map.collision(1), Collision::NonSolid);
+        assert_eq!(map.tag_anchor("landing"), Some(Vector3::new(4.0, 1.5, 0.0)));
+
+        map.remove_object(0);
+        assert_eq!(map.trigger_volumes().map(|(trigger, _)| trigger.object).collect::<Vec<_>>(), vec![0]);
+        let loaded = Map::from_borsh_bytes(&map.to_borsh_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.triggers, map.triggers);
+
+        map.set_object_trigger(0, None);
+        assert_eq!(map.collision(0), Collision::Solid);
+    }
+
     #[test]
     fn test_borsh_size_estimation() {
         let mut map = Map::new("My Map".to_string());
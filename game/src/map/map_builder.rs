@@ -1,7 +1,38 @@
 use raylib::prelude::*;
-use std::fs;
 
-use super::map::{Map, MapObject, ModelType, WORLD_SIZE, WORLD_HALF_SIZE};
+use super::map::{Collision, Map, MapObject, ModelType, WORLD_SIZE, WORLD_HALF_SIZE, DEFAULT_GLOW};
+use super::triggers::{TriggerAction, TriggerSound};
+use super::script::{MapScript, SCRIPT_MAX_LEN};
+use super::ambiance::{AmbiancePreset, MAX_LIGHT};
+use super::ambient_sound::{AmbientSound, AMBIENT_RADIUS_RANGE, MAX_AMBIENT_EMITTERS};
+use super::hierarchy::{HierarchyFilter, HierarchyCategory, color_name};
+use super::stats::{MapStats, BudgetLevel, MAP_BYTE_BUDGET, MAP_OBJECT_BUDGET, MODEL_TYPES};
+use super::stream::MAP_CHUNK_OBJECTS;
+use super::walkthrough::Walkthrough;
+use super::heightmap::{heightmap_to_objects, HeightmapOptions, HeightmapStyle, HEIGHTMAP_RESOLUTIONS};
+use super::generator::{builtin_generators, default_values, parse_share_code, random_seed, share_code, MapGenerator};
+use super::balance::{SpawnBalance, BLUE, RED, CENTER_ASYMMETRY_WARNING};
+use super::heatmap::{HeatmapKind, MatchHeatmap, HEATMAP_CELL_SIZE};
+use super::library::{self, LibraryEntry, MAP_FILE_EXTENSION};
+use super::collab::{CollabSession, EditMessage};
+use super::snap::{SnapSettings, snap_value, TRANSLATION_STEPS, ROTATION_STEPS, SCALE_STEPS};
+use crate::i18n::{t, tr, on_off};
+use crate::game::WeaponKind;
+
+/// Swatches every new editor session starts with
+const DEFAULT_PALETTE: [Color; 8] = [
+    Color { r: 70, g: 130, b: 180, a: 255 },  // Blueprint blue (default)
+    Color { r: 60, g: 70, b: 90, a: 255 },    // Slate walls
+    Color { r: 150, g: 150, b: 150, a: 255 }, // Concrete
+    Color { r: 140, g: 100, b: 60, a: 255 },  // Crate brown
+    Color { r: 90, g: 140, b: 70, a: 255 },   // Foliage green
+    Color { r: 200, g: 170, b: 110, a: 255 }, // Sand
+    Color { r: 200, g: 60, b: 50, a: 255 },   // Danger red
+    Color { r: 0, g: 242, b: 148, a: 255 },   // Solana teal
+];
+
+/// Most swatches the palette holds
+const MAX_PALETTE_SIZE: usize = 16;
 
 /// Editor mode states
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -13,6 +44,18 @@ pub enum EditorMode {
     Scaling,
 }
 
+impl EditorMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EditorMode::Placing => t("editor.mode.placing"),
+            EditorMode::Selecting => t("editor.mode.selecting"),
+            EditorMode::Moving => t("editor.mode.moving"),
+            EditorMode::Rotating => t("editor.mode.rotating"),
+            EditorMode::Scaling => t("editor.mode.scaling"),
+        }
+    }
+}
+
 /// Axis for manipulation
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Axis {
@@ -33,12 +76,18 @@ pub struct MapBuilder {
     /// Currently selected object index
     pub selected_object: Option<usize>,
 
+    /// Additional objects selected together with `selected_object` ("select all of type")
+    pub multi_selection: Vec<usize>,
+
     /// Current model type to place
     pub current_model_type: ModelType,
 
     /// Current color for new objects
     pub current_color: Color,
 
+    /// Saved color swatches
+    pub palette: Vec<Color>,
+
     /// Camera for 3D view
     pub camera: Camera3D,
 
@@ -51,9 +100,38 @@ pub struct MapBuilder {
     /// Manipulation speed multiplier
     pub manipulation_speed: f32,
 
-    /// Grid snap enabled
-    pub grid_snap: bool,
-    pub grid_size: f32,
+    /// Snapping for move (grid), rotate and scale
+    pub snap: SnapSettings,
+
+    /// First-person walkthrough (None while editing with the free camera)
+    pub walkthrough: Option<Walkthrough>,
+    /// Whether the walkthrough collides with map geometry
+    pub walkthrough_collision: bool,
+
+    /// Image/heightmap import settings
+    pub heightmap_options: HeightmapOptions,
+
+    /// Procedural layouts under the Generate header, with each one's parameter values
+    generators: Vec<Box<dyn MapGenerator>>,
+    generator_values: Vec<Vec<f32>>,
+    pub generator_index: usize,
+    pub generator_seed: u32,
+    /// Remove existing geometry before adding the generated layout
+    pub generator_replace: bool,
+    /// Share code typed or pasted in to regenerate someone else's layout
+    pub generator_code_input: String,
+
+    /// Spawn balance overlay (travel-time contours and cross-spawn sightlines)
+    pub show_balance: bool,
+    pub balance: Option<SpawnBalance>,
+    /// Serialized map the current analysis was made from, and time until the next staleness check
+    balance_source: Vec<u8>,
+    balance_refresh_timer: f32,
+
+    /// Heatmaps from exported match logs and replays, drawn over the ground
+    pub heatmap: MatchHeatmap,
+    pub heatmap_kind: HeatmapKind,
+    pub show_heatmap: bool,
 
     /// Show grid
     pub show_grid: bool,
@@ -61,6 +139,7 @@ pub struct MapBuilder {
     /// UI state
     pub show_help: bool,
     pub show_hierarchy: bool,
+    pub hierarchy_filter: HierarchyFilter,
     pub status_message: String,
     pub status_timer: f32,
 
@@ -69,10 +148,28 @@ pub struct MapBuilder {
     pub upload_map_id: String,
     pub upload_map_name: String,
     pub upload_map_description: String,
+    /// Republish over a map the wallet already owns instead of creating a new one
+    pub upload_update_existing: bool,
+    /// On-chain ID the current map was loaded from (offered as the update target)
+    pub published_map_id: Option<String>,
 
     /// My Maps view state
     pub show_my_maps: bool,
     pub user_map_ids: Vec<String>,
+    /// Map waiting for delete confirmation
+    pub pending_map_delete: Option<String>,
+
+    /// Local map library (Library window) state
+    pub show_library: bool,
+    pub library_entries: Vec<LibraryEntry>,
+    pub library_save_name: String,
+
+    /// Collaborative editing (Collaborate window) state
+    pub show_collab: bool,
+    pub collab: Option<CollabSession>,
+    pub collab_name: String,
+    pub collab_join_code: String,
+    pub collab_sync_timer: f32,
 
     /// Drag-and-drop state
     pub is_dragging_model: bool,
@@ -89,60 +186,71 @@ impl MapBuilder {
             60.0,
         );
 
-        Self {
+        let mut builder = Self {
             map: Map::new(map_name),
             mode: EditorMode::Placing,
             selected_object: None,
+            multi_selection: Vec::new(),
             current_model_type: ModelType::Cube,
             current_color: Color::new(70, 130, 180, 255), // Prototype/blueprint style: dark blue
+            palette: DEFAULT_PALETTE.to_vec(),
             camera,
             preview_position: Vector3::new(0.0, 1.0, 0.0), // Start at 1 unit above ground
             current_axis: Axis::All,
             manipulation_speed: 1.0,
-            grid_snap: true,
-            grid_size: 1.0,
+            snap: SnapSettings::default(),
+            walkthrough: None,
+            walkthrough_collision: true,
+            heightmap_options: HeightmapOptions::default(),
+            generators: Vec::new(),
+            generator_values: Vec::new(),
+            generator_index: 0,
+            generator_seed: random_seed(),
+            generator_replace: true,
+            generator_code_input: String::new(),
+            show_balance: false,
+            balance: None,
+            balance_source: Vec::new(),
+            balance_refresh_timer: 0.0,
+            heatmap: MatchHeatmap::default(),
+            heatmap_kind: HeatmapKind::Deaths,
+            show_heatmap: true,
             show_grid: true,
             show_help: true, // Show help by default
             show_hierarchy: true, // Show hierarchy by default
-            status_message: "Welcome! Press H for help, U for hierarchy".to_string(),
+            hierarchy_filter: HierarchyFilter::default(),
+            status_message: t("editor.status.welcome").to_string(),
             status_timer: 5.0,
             show_upload_popup: false,
             upload_map_id: String::new(),
             upload_map_name: String::new(),
             upload_map_description: String::new(),
+            upload_update_existing: false,
+            published_map_id: None,
             show_my_maps: false,
             user_map_ids: Vec::new(),
+            pending_map_delete: None,
+            show_library: false,
+            library_entries: Vec::new(),
+            library_save_name: String::new(),
+            show_collab: false,
+            collab: None,
+            collab_name: String::new(),
+            collab_join_code: String::new(),
+            collab_sync_timer: 0.0,
             is_dragging_model: false,
             dragged_model_type: None,
+        };
+        for generator in builtin_generators() {
+            builder.register_generator(generator);
         }
+        builder
     }
 
-    /// Load a map from file (supports both Borsh and JSON formats)
-    pub fn load_map(path: &str) -> Result<Self, String> {
-        let bytes = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
-
-        // Try Borsh first, fall back to JSON for backwards compatibility
-        let map = Map::from_borsh_bytes(&bytes)
-            .or_else(|_| Map::from_json_bytes(&bytes).map_err(|e| format!("{}", e)))
-            .map_err(|e| format!("Failed to parse map (tried both Borsh and JSON): {}", e))?;
-
-        let mut builder = Self::new(map.name.clone());
-        builder.map = map;
-        builder.set_status("Map loaded successfully");
-
-        Ok(builder)
-    }
-
-    /// Save the map to file (uses Borsh format for compactness)
-    pub fn save_map(&self, path: &str) -> Result<(), String> {
-        let bytes = self.map.to_borsh_bytes().map_err(|e| format!("Failed to serialize map: {}", e))?;
-
-        if bytes.len() > 10240 {
-            return Err(format!("Map size ({} bytes) exceeds 10KB limit!", bytes.len()));
-        }
-
-        fs::write(path, bytes).map_err(|e| format!("Failed to write file: {}", e))?;
-        Ok(())
+    /// Offer a procedural generator under the Generate header
+    pub fn register_generator(&mut self, generator: Box<dyn MapGenerator>) {
+        self.generator_values.push(default_values(generator.as_ref()));
+        self.generators.push(generator);
     }
 
     /// Update the map builder state
@@ -160,6 +268,26 @@ impl MapBuilder {
         #[cfg(target_os = "emscripten")]
         self.check_loaded_map_from_solana();
 
+        // Check for a deleted map (Emscripten only)
+        #[cfg(target_os = "emscripten")]
+        self.check_removed_map();
+
+        // Check for library listings and loads from IndexedDB (Emscripten only)
+        #[cfg(target_os = "emscripten")]
+        self.check_library();
+
+        // Check for an uploaded heightmap image (Emscripten only)
+        #[cfg(target_os = "emscripten")]
+        self.check_uploaded_heightmap();
+
+        // Check for match logs/replays picked for the heatmap (Emscripten only)
+        #[cfg(target_os = "emscripten")]
+        self.check_uploaded_heatmap_files();
+
+        // Exchange edits and cursors with collaborators (Emscripten only)
+        #[cfg(target_os = "emscripten")]
+        self.update_collab(rl, delta);
+
         // Handle drag-and-drop completion
         if self.is_dragging_model {
             // If mouse is released, complete the drag
@@ -169,7 +297,7 @@ impl MapBuilder {
                     if !mouse_over_ui {
                         self.current_model_type = dragged_type;
                         // The placing mode will handle actual placement
-                        self.set_status(&format!("Dropped {:?} - click to place", dragged_type));
+                        self.set_status(&tr!("editor.status.dropped", model = dragged_type.label()));
                     }
                 }
                 // Clear drag state
@@ -178,16 +306,36 @@ impl MapBuilder {
             }
         }
 
+        if self.show_balance {
+            self.refresh_balance(delta);
+        }
+
+        // Walkthrough takes over the camera and keyboard until F/ESC
+        if self.walkthrough.is_some() {
+            self.update_walkthrough(rl, delta);
+            return;
+        }
+        if !mouse_over_ui && rl.is_key_pressed(KeyboardKey::KEY_F) {
+            self.toggle_walkthrough();
+            return;
+        }
+
         // Camera controls
         self.update_camera(rl, delta);
 
-        // Handle input based on mode
-        match self.mode {
-            EditorMode::Placing => self.handle_placing_mode(rl, mouse_over_ui),
-            EditorMode::Selecting => self.handle_selecting_mode(rl),
-            EditorMode::Moving => self.handle_moving_mode(rl, delta),
-            EditorMode::Rotating => self.handle_rotating_mode(rl, delta),
-            EditorMode::Scaling => self.handle_scaling_mode(rl, delta),
+        // Eyedropper takes the click instead of the current mode
+        let alt_down = rl.is_key_down(KeyboardKey::KEY_LEFT_ALT) || rl.is_key_down(KeyboardKey::KEY_RIGHT_ALT);
+        if alt_down && !mouse_over_ui && rl.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT) {
+            self.sample_color(rl);
+        } else {
+            // Handle input based on mode
+            match self.mode {
+                EditorMode::Placing => self.handle_placing_mode(rl, mouse_over_ui),
+                EditorMode::Selecting => self.handle_selecting_mode(rl),
+                EditorMode::Moving => self.handle_moving_mode(rl, delta),
+                EditorMode::Rotating => self.handle_rotating_mode(rl, delta),
+                EditorMode::Scaling => self.handle_scaling_mode(rl, delta),
+            }
         }
 
         // Only process keyboard shortcuts when not hovering over UI
@@ -195,72 +343,57 @@ impl MapBuilder {
             // Mode switching
             if rl.is_key_pressed(KeyboardKey::KEY_ONE) {
                 self.mode = EditorMode::Placing;
-                self.set_status("Mode: Placing");
+                self.set_status(&tr!("editor.status.mode", mode = EditorMode::Placing.label()));
             } else if rl.is_key_pressed(KeyboardKey::KEY_TWO) {
                 self.mode = EditorMode::Selecting;
-                self.set_status("Mode: Selecting");
+                self.set_status(&tr!("editor.status.mode", mode = EditorMode::Selecting.label()));
             } else if rl.is_key_pressed(KeyboardKey::KEY_THREE) && self.selected_object.is_some() {
                 self.mode = EditorMode::Moving;
-                self.set_status("Mode: Moving");
+                self.set_status(&tr!("editor.status.mode", mode = EditorMode::Moving.label()));
             } else if rl.is_key_pressed(KeyboardKey::KEY_FOUR) && self.selected_object.is_some() {
                 self.mode = EditorMode::Rotating;
-                self.set_status("Mode: Rotating");
+                self.set_status(&tr!("editor.status.mode", mode = EditorMode::Rotating.label()));
             } else if rl.is_key_pressed(KeyboardKey::KEY_FIVE) && self.selected_object.is_some() {
                 self.mode = EditorMode::Scaling;
-                self.set_status("Mode: Scaling");
+                self.set_status(&tr!("editor.status.mode", mode = EditorMode::Scaling.label()));
             }
 
             // Axis switching (for manipulation modes)
             if rl.is_key_pressed(KeyboardKey::KEY_X) {
                 self.current_axis = Axis::X;
-                self.set_status("Axis: X");
+                self.set_status(&tr!("editor.status.axis", axis = "X"));
             } else if rl.is_key_pressed(KeyboardKey::KEY_Y) {
                 self.current_axis = Axis::Y;
-                self.set_status("Axis: Y");
+                self.set_status(&tr!("editor.status.axis", axis = "Y"));
             } else if rl.is_key_pressed(KeyboardKey::KEY_Z) {
                 self.current_axis = Axis::Z;
-                self.set_status("Axis: Z");
+                self.set_status(&tr!("editor.status.axis", axis = "Z"));
             } else if rl.is_key_pressed(KeyboardKey::KEY_A) {
                 self.current_axis = Axis::All;
-                self.set_status("Axis: All");
+                self.set_status(&tr!("editor.status.axis", axis = t("editor.axis.all")));
             }
 
             // Model type switching (in placing mode)
             if self.mode == EditorMode::Placing {
-                if rl.is_key_pressed(KeyboardKey::KEY_C) {
-                    self.current_model_type = ModelType::Cube;
-                    self.set_status("Model: Cube");
-                } else if rl.is_key_pressed(KeyboardKey::KEY_R) {
-                    self.current_model_type = ModelType::Rectangle;
-                    self.set_status("Model: Rectangle");
-                } else if rl.is_key_pressed(KeyboardKey::KEY_T) {
-                    self.current_model_type = ModelType::Triangle;
-                    self.set_status("Model: Triangle");
-                } else if rl.is_key_pressed(KeyboardKey::KEY_S) {
-                    self.current_model_type = ModelType::Sphere;
-                    self.set_status("Model: Sphere");
-                } else if rl.is_key_pressed(KeyboardKey::KEY_L) {
-                    self.current_model_type = ModelType::Cylinder;
-                    self.set_status("Model: Cylinder");
-                } else if rl.is_key_pressed(KeyboardKey::KEY_P) {
-                    self.current_model_type = ModelType::Plane;
-                    self.set_status("Model: Plane");
-                } else if rl.is_key_pressed(KeyboardKey::KEY_B) {
-                    self.current_model_type = ModelType::SpawnPointBlue;
-                    self.set_status("Model: Blue Spawn Point");
-                } else if rl.is_key_pressed(KeyboardKey::KEY_D) {
-                    self.current_model_type = ModelType::SpawnPointRed;
-                    self.set_status("Model: Red Spawn Point");
+                let model_keys = [
+                    (KeyboardKey::KEY_C, ModelType::Cube),
+                    (KeyboardKey::KEY_R, ModelType::Rectangle),
+                    (KeyboardKey::KEY_T, ModelType::Triangle),
+                    (KeyboardKey::KEY_S, ModelType::Sphere),
+                    (KeyboardKey::KEY_L, ModelType::Cylinder),
+                    (KeyboardKey::KEY_P, ModelType::Plane),
+                    (KeyboardKey::KEY_B, ModelType::SpawnPointBlue),
+                    (KeyboardKey::KEY_D, ModelType::SpawnPointRed),
+                ];
+                if let Some(&(_, model_type)) = model_keys.iter().find(|(key, _)| rl.is_key_pressed(*key)) {
+                    self.current_model_type = model_type;
+                    self.set_status(&tr!("editor.status.model", model = model_type.label()));
                 }
             }
 
-            // Delete selected object
+            // Delete selected object(s)
             if rl.is_key_pressed(KeyboardKey::KEY_DELETE) || rl.is_key_pressed(KeyboardKey::KEY_BACKSPACE) {
-                if let Some(index) = self.selected_object {
-                    self.map.remove_object(index);
-                    self.selected_object = None;
-                    self.set_status("Object deleted");
-                }
+                self.delete_selection();
             }
         }
 
@@ -269,10 +402,19 @@ impl MapBuilder {
             self.show_grid = !self.show_grid;
         }
 
-        // Toggle grid snap
+        // Toggle / cycle snapping for the active tool
         if rl.is_key_pressed(KeyboardKey::KEY_N) {
-            self.grid_snap = !self.grid_snap;
-            self.set_status(&format!("Grid snap: {}", if self.grid_snap { "ON" } else { "OFF" }));
+            self.toggle_snap();
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_LEFT_BRACKET) {
+            self.cycle_snap_step(false);
+        } else if rl.is_key_pressed(KeyboardKey::KEY_RIGHT_BRACKET) {
+            self.cycle_snap_step(true);
+        }
+
+        // Toggle spawn balance overlay
+        if !mouse_over_ui && rl.is_key_pressed(KeyboardKey::KEY_V) {
+            self.toggle_balance();
         }
 
         // Toggle help
@@ -347,6 +489,158 @@ impl MapBuilder {
         self.camera = Camera3D::perspective(new_pos, new_target, Vector3::new(0.0, 1.0, 0.0), 60.0);
     }
 
+    /// Whether the first-person walkthrough is running (the mouse is captured for looking)
+    pub fn is_walkthrough(&self) -> bool {
+        self.walkthrough.is_some()
+    }
+
+    /// Enter or leave the first-person walkthrough
+    fn toggle_walkthrough(&mut self) {
+        match self.walkthrough.take() {
+            Some(walkthrough) => {
+                self.camera = walkthrough.editor_camera;
+                self.set_status(t("editor.status.walkthrough_ended"));
+            }
+            None => {
+                self.walkthrough = Some(Walkthrough::new(self.camera, self.walkthrough_collision));
+                self.set_status(t("editor.status.walkthrough_controls"));
+            }
+        }
+    }
+
+    /// Walk the map at eye height
+    fn update_walkthrough(&mut self, rl: &RaylibHandle, delta: f32) {
+        if rl.is_key_pressed(KeyboardKey::KEY_F) || rl.is_key_pressed(KeyboardKey::KEY_ESCAPE) {
+            self.toggle_walkthrough();
+            return;
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_C) {
+            self.walkthrough_collision = !self.walkthrough_collision;
+            self.set_status(&tr!("editor.status.walkthrough_collision", state = on_off(self.walkthrough_collision)));
+        }
+
+        let Some(ref mut walkthrough) = self.walkthrough else {
+            return;
+        };
+        walkthrough.collision = self.walkthrough_collision;
+        walkthrough.update(rl, delta, &self.map);
+        self.camera = walkthrough.player.camera;
+    }
+
+    fn toggle_balance(&mut self) {
+        self.show_balance = !self.show_balance;
+        self.balance_refresh_timer = 0.0;
+        if !self.show_balance {
+            self.balance = None;
+            self.balance_source.clear();
+        }
+        self.set_status(&tr!("editor.status.balance_overlay", state = on_off(self.show_balance)));
+    }
+
+    /// Re-run the spawn balance analysis when the map has changed (checked twice a second)
+    fn refresh_balance(&mut self, delta: f32) {
+        self.balance_refresh_timer -= delta;
+        if self.balance_refresh_timer > 0.0 {
+            return;
+        }
+        self.balance_refresh_timer = 0.5;
+
+        let bytes = self.map.to_borsh_bytes().unwrap_or_default();
+        if self.balance.is_none() || bytes != self.balance_source {
+            self.balance = Some(SpawnBalance::analyze(&self.map));
+            self.balance_source = bytes;
+        }
+    }
+
+    /// Travel-time contours (2 second bands, tinted by the team that gets there first;
+    /// yellow where both arrive within half a second) and red lines between spawns that see each other
+    fn draw_balance_overlay(&self, d: &mut RaylibMode3D<RaylibDrawHandle>) {
+        let Some(ref balance) = self.balance else {
+            return;
+        };
+
+        for (center, blue, red) in balance.cells() {
+            let (time, color) = match (blue, red) {
+                (Some(b), Some(r)) if (b - r).abs() < 0.5 => (b.min(r), Color::new(255, 220, 0, 0)),
+                (Some(b), Some(r)) if b < r => (b, Color::new(60, 120, 255, 0)),
+                (Some(b), None) => (b, Color::new(60, 120, 255, 0)),
+                (_, Some(r)) => (r, Color::new(255, 70, 60, 0)),
+                _ => continue,
+            };
+            let band = (time / 2.0) as i32;
+            let alpha = if band % 2 == 0 { 70 } else { 120 };
+            d.draw_plane(
+                center + Vector3::new(0.0, 0.02, 0.0),
+                Vector2::new(0.95, 0.95),
+                Color::new(color.r, color.g, color.b, alpha),
+            );
+        }
+
+        for &(blue, red) in &balance.sightlines {
+            let eye = Vector3::new(0.0, 1.7, 0.0);
+            d.draw_line_3D(blue + eye, red + eye, Color::RED);
+            d.draw_sphere(blue + eye, 0.15, Color::RED);
+            d.draw_sphere(red + eye, 0.15, Color::RED);
+        }
+    }
+
+    /// Ambient sound emitters as small amber markers inside a wireframe of their hearing radius
+    /// (editor only, the game never draws them)
+    fn draw_ambient_emitters(&self, d: &mut RaylibMode3D<RaylibDrawHandle>) {
+        for emitter in &self.map.ambient_sounds {
+            let position = emitter.position();
+            d.draw_sphere(position, 0.25, Color::new(255, 190, 60, 255));
+            d.draw_sphere_wires(position, emitter.radius(), 8, 12, Color::new(255, 190, 60, 70));
+        }
+    }
+
+    /// Heatmap cells, cold blue through yellow to hot red
+    fn draw_heatmap_overlay(&self, d: &mut RaylibMode3D<RaylibDrawHandle>) {
+        for (center, heat) in self.heatmap.cells(self.heatmap_kind) {
+            let (from, to, blend) = if heat < 0.5 {
+                ((40.0, 90.0, 255.0), (255.0, 220.0, 0.0), heat * 2.0)
+            } else {
+                ((255.0, 220.0, 0.0), (255.0, 40.0, 30.0), heat * 2.0 - 1.0)
+            };
+            let mix = |a: f32, b: f32| (a + (b - a) * blend) as u8;
+            d.draw_plane(
+                center + Vector3::new(0.0, 0.03, 0.0),
+                Vector2::new(HEATMAP_CELL_SIZE * 0.95, HEATMAP_CELL_SIZE * 0.95),
+                Color::new(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2), (60.0 + heat * 120.0) as u8),
+            );
+        }
+    }
+
+    /// Summary of the spawn balance analysis
+    fn draw_balance_panel(&self, d: &mut RaylibDrawHandle) {
+        let Some(ref balance) = self.balance else {
+            return;
+        };
+
+        let format_time = |time: Option<f32>| time.map_or(t("editor.balance.unreachable").to_string(), |time| format!("{:.1}s", time));
+        let mut lines = vec![
+            (
+                tr!("editor.balance.spawns", blue = balance.spawns[BLUE].len(), red = balance.spawns[RED].len()),
+                Color::WHITE,
+            ),
+            (tr!("editor.balance.blue_center", time = format_time(balance.center_time[BLUE])), Color::new(120, 170, 255, 255)),
+            (tr!("editor.balance.red_center", time = format_time(balance.center_time[RED])), Color::new(255, 120, 110, 255)),
+        ];
+        if let Some(asymmetry) = balance.center_asymmetry() {
+            let color = if asymmetry > CENTER_ASYMMETRY_WARNING { Color::ORANGE } else { Color::GREEN };
+            lines.push((tr!("editor.balance.center_difference", seconds = format!("{:.1}", asymmetry)), color));
+        }
+        let sightline_color = if balance.sightlines.is_empty() { Color::GREEN } else { Color::RED };
+        lines.push((tr!("editor.balance.sightlines", count = balance.sightlines.len()), sightline_color));
+
+        let (x, y) = (10, 560);
+        d.draw_rectangle(x, y, 300, 20 + lines.len() as i32 * 18, Color::new(0, 0, 0, 180));
+        d.draw_text(t("editor.balance.title"), x + 10, y + 6, 14, Color::new(0, 242, 148, 255));
+        for (i, (text, color)) in lines.iter().enumerate() {
+            d.draw_text(text, x + 10, y + 24 + i as i32 * 18, 14, *color);
+        }
+    }
+
     /// Handle placing mode
     fn handle_placing_mode(&mut self, rl: &RaylibHandle, mouse_over_ui: bool) {
         // Use mouse raycast to determine placement position
@@ -360,53 +654,8 @@ impl MapBuilder {
             let should_calculate = self.is_dragging_model || mouse_pos.x < viewport_width;
 
             if should_calculate {
-                // Manual raycast calculation
-                // The viewport is the full height but only 70% of the width
-                let screen_width = 1280.0;
-                let screen_height = 720.0;
-
-                // Normalize to -1 to 1 range, but consider the full screen width for proper aspect ratio
-                let ndc_x = (2.0 * mouse_pos.x / screen_width) - 1.0;
-                let ndc_y = 1.0 - (2.0 * mouse_pos.y / screen_height);
-
-                // Calculate ray direction from camera
-                let camera_pos = self.camera.position;
-                let camera_target = self.camera.target;
-                let camera_up = self.camera.up;
-
-                // Camera forward vector
-                let forward = Vector3::new(
-                    camera_target.x - camera_pos.x,
-                    camera_target.y - camera_pos.y,
-                    camera_target.z - camera_pos.z,
-                ).normalized();
-
-                // Camera right vector (cross product: forward x up)
-                let right = Vector3::new(
-                    forward.y * camera_up.z - forward.z * camera_up.y,
-                    forward.z * camera_up.x - forward.x * camera_up.z,
-                    forward.x * camera_up.y - forward.y * camera_up.x,
-                ).normalized();
-
-                // Camera actual up vector (cross product: right x forward)
-                let up = Vector3::new(
-                    right.y * forward.z - right.z * forward.y,
-                    right.z * forward.x - right.x * forward.z,
-                    right.x * forward.y - right.y * forward.x,
-                ).normalized();
-
-                // FOV and aspect ratio
-                let fov_rad = 60.0_f32.to_radians();
-                let aspect = screen_width / screen_height;
-                let half_height = (fov_rad / 2.0).tan();
-                let half_width = half_height * aspect;
-
-                // Calculate ray direction
-                let ray_dir = Vector3::new(
-                    forward.x + right.x * ndc_x * half_width + up.x * ndc_y * half_height,
-                    forward.y + right.y * ndc_x * half_width + up.y * ndc_y * half_height,
-                    forward.z + right.z * ndc_x * half_width + up.z * ndc_y * half_height,
-                ).normalized();
+                let ray = self.mouse_ray(mouse_pos);
+                let (camera_pos, ray_dir) = (ray.position, ray.direction);
 
                 // Raycast to ground plane (y = 0)
                 if ray_dir.y != 0.0 {
@@ -436,8 +685,136 @@ impl MapBuilder {
             obj.set_position(self.snap_to_grid(self.preview_position));
             obj.set_color(self.current_color);
             self.map.add_object(obj);
-            self.set_status(&format!("Object placed ({} total)", self.map.objects.len()));
+            self.set_status(&tr!("editor.status.placed", count = self.map.objects.len()));
+        }
+    }
+
+    /// Ray from the editor camera through a point on screen
+    fn mouse_ray(&self, mouse_pos: Vector2) -> Ray {
+        // Manual raycast calculation
+        // The viewport is the full height but only 70% of the width
+        let screen_width = 1280.0;
+        let screen_height = 720.0;
+
+        // Normalize to -1 to 1 range, but consider the full screen width for proper aspect ratio
+        let ndc_x = (2.0 * mouse_pos.x / screen_width) - 1.0;
+        let ndc_y = 1.0 - (2.0 * mouse_pos.y / screen_height);
+
+        // Calculate ray direction from camera
+        let camera_pos = self.camera.position;
+        let camera_target = self.camera.target;
+        let camera_up = self.camera.up;
+
+        // Camera forward vector
+        let forward = Vector3::new(
+            camera_target.x - camera_pos.x,
+            camera_target.y - camera_pos.y,
+            camera_target.z - camera_pos.z,
+        ).normalized();
+
+        // Camera right vector (cross product: forward x up)
+        let right = Vector3::new(
+            forward.y * camera_up.z - forward.z * camera_up.y,
+            forward.z * camera_up.x - forward.x * camera_up.z,
+            forward.x * camera_up.y - forward.y * camera_up.x,
+        ).normalized();
+
+        // Camera actual up vector (cross product: right x forward)
+        let up = Vector3::new(
+            right.y * forward.z - right.z * forward.y,
+            right.z * forward.x - right.x * forward.z,
+            right.x * forward.y - right.y * forward.x,
+        ).normalized();
+
+        // FOV and aspect ratio
+        let fov_rad = 60.0_f32.to_radians();
+        let aspect = screen_width / screen_height;
+        let half_height = (fov_rad / 2.0).tan();
+        let half_width = half_height * aspect;
+
+        // Calculate ray direction
+        let ray_dir = Vector3::new(
+            forward.x + right.x * ndc_x * half_width + up.x * ndc_y * half_height,
+            forward.y + right.y * ndc_x * half_width + up.y * ndc_y * half_height,
+            forward.z + right.z * ndc_x * half_width + up.z * ndc_y * half_height,
+        ).normalized();
+
+        Ray::new(camera_pos, ray_dir)
+    }
+
+    /// Closest object under a ray (spawn points are picked by a small box around their marker)
+    fn pick_object(&self, ray: Ray) -> Option<usize> {
+        self.map.objects
+            .iter()
+            .enumerate()
+            .filter_map(|(i, obj)| {
+                let bounds = obj.collision_bounds().unwrap_or_else(|| {
+                    let pos = obj.get_position();
+                    BoundingBox::new(pos - Vector3::new(0.5, 0.0, 0.5), pos + Vector3::new(0.5, 2.0, 0.5))
+                });
+                let hit = get_ray_collision_box(ray, bounds);
+                hit.hit.then_some((i, hit.distance))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    /// Alt+click: take the paint color from the object under the cursor
+    fn sample_color(&mut self, rl: &RaylibHandle) {
+        let ray = self.mouse_ray(rl.get_mouse_position());
+        match self.pick_object(ray) {
+            Some(index) => {
+                let color = self.map.objects[index].get_color();
+                self.current_color = color;
+                self.set_status(&tr!(
+                    "editor.status.picked_color",
+                    hex = format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b),
+                    color = color_name(color),
+                    index = index,
+                ));
+            }
+            None => self.set_status(t("editor.status.eyedropper_miss")),
+        }
+    }
+
+    /// Recolor the selected object(s)
+    fn set_selection_color(&mut self, color: Color) {
+        let indices = self.multi_selection.iter().copied().chain(self.selected_object);
+        for index in indices.collect::<Vec<_>>() {
+            if let Some(obj) = self.map.objects.get_mut(index) {
+                obj.set_color(color);
+            }
+        }
+    }
+
+    /// Change what every selected object collides with
+    fn set_selection_collision(&mut self, collision: Collision) {
+        let indices = self.multi_selection.iter().copied().chain(self.selected_object);
+        for index in indices.collect::<Vec<_>>() {
+            self.map.set_object_collision(index, collision);
+        }
+    }
+
+    /// Row of palette swatches: returns the clicked color (right-click removes a swatch)
+    fn draw_palette(&mut self, ui: &imgui::Ui, id: &str) -> Option<Color> {
+        let mut clicked = None;
+        let mut removed = None;
+        for (i, color) in self.palette.iter().enumerate() {
+            if i % 8 != 0 {
+                ui.same_line();
+            }
+            let rgba = [color.r as f32 / 255.0, color.g as f32 / 255.0, color.b as f32 / 255.0, 1.0];
+            if ui.color_button_config(format!("##{}_swatch{}", id, i), rgba).size([20.0, 20.0]).build() {
+                clicked = Some(*color);
+            }
+            if ui.is_item_clicked_with_button(imgui::MouseButton::Right) {
+                removed = Some(i);
+            }
+        }
+        if let Some(i) = removed {
+            self.palette.remove(i);
         }
+        clicked
     }
 
     /// Handle selecting mode
@@ -452,8 +829,8 @@ impl MapBuilder {
 
         for (i, key) in number_keys.iter().enumerate() {
             if rl.is_key_pressed(*key) && i < self.map.objects.len() {
-                self.selected_object = Some(i);
-                self.set_status(&format!("Selected object {}: {:?}", i, self.map.objects[i].model_type));
+                self.select_single(i);
+                self.set_status(&tr!("editor.status.selected_type", index = i, model = self.map.objects[i].model_type.label()));
                 return;
             }
         }
@@ -461,29 +838,26 @@ impl MapBuilder {
         // Cycle through objects with < and >
         if rl.is_key_pressed(KeyboardKey::KEY_COMMA) {
             if !self.map.objects.is_empty() {
-                if let Some(idx) = self.selected_object {
-                    self.selected_object = Some(if idx == 0 { self.map.objects.len() - 1 } else { idx - 1 });
-                } else {
-                    self.selected_object = Some(self.map.objects.len() - 1);
-                }
-                self.set_status(&format!("Selected object {}", self.selected_object.unwrap()));
+                let previous = match self.selected_object {
+                    Some(idx) if idx > 0 => idx - 1,
+                    _ => self.map.objects.len() - 1,
+                };
+                self.select_single(previous);
+                self.set_status(&tr!("editor.status.selected", index = self.selected_object.unwrap()));
             }
         }
         if rl.is_key_pressed(KeyboardKey::KEY_PERIOD) {
             if !self.map.objects.is_empty() {
-                if let Some(idx) = self.selected_object {
-                    self.selected_object = Some((idx + 1) % self.map.objects.len());
-                } else {
-                    self.selected_object = Some(0);
-                }
-                self.set_status(&format!("Selected object {}", self.selected_object.unwrap()));
+                let next = self.selected_object.map_or(0, |idx| (idx + 1) % self.map.objects.len());
+                self.select_single(next);
+                self.set_status(&tr!("editor.status.selected", index = self.selected_object.unwrap()));
             }
         }
 
         // Deselect with Escape
         if rl.is_key_pressed(KeyboardKey::KEY_ESCAPE) {
-            self.selected_object = None;
-            self.set_status("Deselected");
+            self.clear_selection();
+            self.set_status(t("editor.status.deselected"));
         }
     }
 
@@ -492,33 +866,40 @@ impl MapBuilder {
         if let Some(index) = self.selected_object {
             if index < self.map.objects.len() {
                 let move_speed = self.manipulation_speed * delta * 10.0;
+                let step = self.snap.translation_step();
                 let mut pos = self.map.objects[index].get_position();
 
                 match self.current_axis {
                     Axis::X => {
-                        if rl.is_key_down(KeyboardKey::KEY_LEFT) { pos.x -= move_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_RIGHT) { pos.x += move_speed; }
+                        pos.x += key_axis(rl, KeyboardKey::KEY_LEFT, KeyboardKey::KEY_RIGHT, move_speed, step);
                     }
                     Axis::Y => {
-                        if rl.is_key_down(KeyboardKey::KEY_DOWN) { pos.y -= move_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_UP) { pos.y += move_speed; }
+                        pos.y += key_axis(rl, KeyboardKey::KEY_DOWN, KeyboardKey::KEY_UP, move_speed, step);
                     }
                     Axis::Z => {
-                        if rl.is_key_down(KeyboardKey::KEY_DOWN) { pos.z -= move_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_UP) { pos.z += move_speed; }
+                        pos.z += key_axis(rl, KeyboardKey::KEY_DOWN, KeyboardKey::KEY_UP, move_speed, step);
                     }
                     Axis::All => {
-                        if rl.is_key_down(KeyboardKey::KEY_LEFT) { pos.x -= move_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_RIGHT) { pos.x += move_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_UP) { pos.z -= move_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_DOWN) { pos.z += move_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_PAGE_UP) { pos.y += move_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_PAGE_DOWN) { pos.y -= move_speed; }
+                        pos.x += key_axis(rl, KeyboardKey::KEY_LEFT, KeyboardKey::KEY_RIGHT, move_speed, step);
+                        pos.z += key_axis(rl, KeyboardKey::KEY_UP, KeyboardKey::KEY_DOWN, move_speed, step);
+                        pos.y += key_axis(rl, KeyboardKey::KEY_PAGE_DOWN, KeyboardKey::KEY_PAGE_UP, move_speed, step);
                     }
                 }
 
                 let snapped_pos = self.snap_to_grid(self.clamp_to_world(pos));
+                let offset = snapped_pos - self.map.objects[index].get_position();
+                if offset.length() == 0.0 {
+                    return;
+                }
                 self.map.objects[index].set_position(snapped_pos);
+
+                // The rest of a multi-selection follows the primary object
+                for &other in &self.multi_selection {
+                    if other != index && other < self.map.objects.len() {
+                        let moved = self.clamp_to_world(self.map.objects[other].get_position() + offset);
+                        self.map.objects[other].set_position(moved);
+                    }
+                }
             }
         }
     }
@@ -528,25 +909,19 @@ impl MapBuilder {
         if let Some(index) = self.selected_object {
             if index < self.map.objects.len() {
                 let rot_speed = self.manipulation_speed * delta * 90.0;
+                let step = self.snap.rotation_step();
                 let mut rot = self.map.objects[index].get_rotation();
+                let turn = key_axis(rl, KeyboardKey::KEY_LEFT, KeyboardKey::KEY_RIGHT, rot_speed, step);
+                if turn == 0.0 {
+                    return;
+                }
 
+                // Only the axis being turned lands on the snap angle, the others keep their value
+                let snap = |angle: f32| step.map_or(angle, |step| snap_value(angle, step));
                 match self.current_axis {
-                    Axis::X => {
-                        if rl.is_key_down(KeyboardKey::KEY_LEFT) { rot.x -= rot_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_RIGHT) { rot.x += rot_speed; }
-                    }
-                    Axis::Y => {
-                        if rl.is_key_down(KeyboardKey::KEY_LEFT) { rot.y -= rot_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_RIGHT) { rot.y += rot_speed; }
-                    }
-                    Axis::Z => {
-                        if rl.is_key_down(KeyboardKey::KEY_LEFT) { rot.z -= rot_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_RIGHT) { rot.z += rot_speed; }
-                    }
-                    Axis::All => {
-                        if rl.is_key_down(KeyboardKey::KEY_LEFT) { rot.y -= rot_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_RIGHT) { rot.y += rot_speed; }
-                    }
+                    Axis::X => rot.x = snap(rot.x + turn),
+                    Axis::Y | Axis::All => rot.y = snap(rot.y + turn),
+                    Axis::Z => rot.z = snap(rot.z + turn),
                 }
 
                 self.map.objects[index].set_rotation(rot);
@@ -559,25 +934,30 @@ impl MapBuilder {
         if let Some(index) = self.selected_object {
             if index < self.map.objects.len() {
                 let scale_speed = self.manipulation_speed * delta * 2.0;
+                let step = self.snap.scale_step();
                 let mut scale = self.map.objects[index].get_scale();
+                let snap = |value: f32| step.map_or(value, |step| snap_value(value, step).max(step));
 
                 match self.current_axis {
                     Axis::X => {
-                        if rl.is_key_down(KeyboardKey::KEY_LEFT) { scale.x -= scale_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_RIGHT) { scale.x += scale_speed; }
+                        let change = key_axis(rl, KeyboardKey::KEY_LEFT, KeyboardKey::KEY_RIGHT, scale_speed, step);
+                        if change == 0.0 { return; }
+                        scale.x = snap(scale.x + change);
                     }
                     Axis::Y => {
-                        if rl.is_key_down(KeyboardKey::KEY_DOWN) { scale.y -= scale_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_UP) { scale.y += scale_speed; }
+                        let change = key_axis(rl, KeyboardKey::KEY_DOWN, KeyboardKey::KEY_UP, scale_speed, step);
+                        if change == 0.0 { return; }
+                        scale.y = snap(scale.y + change);
                     }
                     Axis::Z => {
-                        if rl.is_key_down(KeyboardKey::KEY_DOWN) { scale.z -= scale_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_UP) { scale.z += scale_speed; }
+                        let change = key_axis(rl, KeyboardKey::KEY_DOWN, KeyboardKey::KEY_UP, scale_speed, step);
+                        if change == 0.0 { return; }
+                        scale.z = snap(scale.z + change);
                     }
                     Axis::All => {
-                        let mut uniform_scale = (scale.x + scale.y + scale.z) / 3.0;
-                        if rl.is_key_down(KeyboardKey::KEY_UP) { uniform_scale += scale_speed; }
-                        if rl.is_key_down(KeyboardKey::KEY_DOWN) { uniform_scale -= scale_speed; }
+                        let change = key_axis(rl, KeyboardKey::KEY_DOWN, KeyboardKey::KEY_UP, scale_speed, step);
+                        if change == 0.0 { return; }
+                        let uniform_scale = snap((scale.x + scale.y + scale.z) / 3.0 + change);
                         scale = Vector3::new(uniform_scale, uniform_scale, uniform_scale);
                     }
                 }
@@ -587,6 +967,46 @@ impl MapBuilder {
         }
     }
 
+    /// Cycle the snap increment of the active tool ([ and ])
+    fn cycle_snap_step(&mut self, forward: bool) {
+        match self.mode {
+            EditorMode::Rotating => {
+                self.snap.cycle_rotation(forward);
+                self.snap.rotate = true;
+                self.set_status(&tr!("editor.status.rotation_snap_step", step = ROTATION_STEPS[self.snap.rotate_index]));
+            }
+            EditorMode::Scaling => {
+                self.snap.cycle_scale(forward);
+                self.snap.scale = true;
+                self.set_status(&tr!("editor.status.scale_snap_step", step = SCALE_STEPS[self.snap.scale_index]));
+            }
+            _ => {
+                self.snap.cycle_translation(forward);
+                self.snap.translate = true;
+                self.set_status(&tr!("editor.status.grid_snap_step", step = TRANSLATION_STEPS[self.snap.translate_index]));
+            }
+        }
+    }
+
+    /// Toggle snapping for the active tool (N)
+    fn toggle_snap(&mut self) {
+        let (name, enabled) = match self.mode {
+            EditorMode::Rotating => {
+                self.snap.rotate = !self.snap.rotate;
+                (t("editor.snap.rotation"), self.snap.rotate)
+            }
+            EditorMode::Scaling => {
+                self.snap.scale = !self.snap.scale;
+                (t("editor.snap.scale"), self.snap.scale)
+            }
+            _ => {
+                self.snap.translate = !self.snap.translate;
+                (t("editor.snap.grid"), self.snap.translate)
+            }
+        };
+        self.set_status(&format!("{}: {}", name, on_off(enabled)));
+    }
+
     /// Render the map builder
     pub fn render(&self, d: &mut RaylibDrawHandle, _thread: &RaylibThread, viewport_width: i32) {
         let mut d3d = d.begin_mode3D(self.camera);
@@ -595,27 +1015,46 @@ impl MapBuilder {
         self.draw_world_environment(&mut d3d);
 
         // Render map objects
-        self.map.render(&mut d3d);
+        self.map.render(&mut d3d, self.camera.position);
 
         // Draw preview in placing mode or when dragging
-        if self.mode == EditorMode::Placing || self.is_dragging_model {
+        if self.walkthrough.is_none() && (self.mode == EditorMode::Placing || self.is_dragging_model) {
             self.draw_preview(&mut d3d);
         }
 
-        // Highlight selected object
+        // Highlight selected object (plus the rest of a multi-selection)
         if let Some(index) = self.selected_object {
             if index < self.map.objects.len() {
                 self.draw_selection_highlight(&mut d3d, &self.map.objects[index]);
             }
         }
+        for &index in self.multi_selection.iter().filter(|&&i| Some(i) != self.selected_object) {
+            if let Some(obj) = self.map.objects.get(index) {
+                let scale = obj.get_scale();
+                d3d.draw_sphere_wires(obj.get_position(), scale.x.max(scale.y).max(scale.z) * 0.7, 8, 8, Color::ORANGE);
+            }
+        }
 
         // Draw spawn point
         self.draw_spawn_point(&mut d3d);
 
+        if self.show_balance {
+            self.draw_balance_overlay(&mut d3d);
+        }
+
+        if self.show_heatmap {
+            self.draw_heatmap_overlay(&mut d3d);
+        }
+
+        self.draw_ambient_emitters(&mut d3d);
+
+        self.draw_collab_cursors(&mut d3d);
+
         drop(d3d);
 
         // Draw minimal UI
         self.draw_ui(d, viewport_width);
+        self.draw_collab_names(d);
     }
 
     /// Draw world environment (ground, walls, grid)
@@ -628,7 +1067,7 @@ impl MapBuilder {
         d.draw_plane(
             Vector3::new(0.0, 0.0, 0.0),
             Vector2::new(WORLD_SIZE, WORLD_SIZE),
-            Color::DARKGRAY,
+            self.map.ambiance.lit(Color::DARKGRAY),
         );
 
         // Draw grid on top of ground (50x50 units)
@@ -736,10 +1175,25 @@ impl MapBuilder {
         // Draw viewport border
         d.draw_line(viewport_width, 0, viewport_width, 720, Color::DARKGRAY);
 
+        if self.show_balance {
+            self.draw_balance_panel(d);
+        }
+
+        // Walkthrough: crosshair in the middle of the viewport and a controls reminder
+        if let Some(ref walkthrough) = self.walkthrough {
+            let (cx, cy) = (viewport_width / 2, 360);
+            d.draw_line(cx - 8, cy, cx + 8, cy, Color::WHITE);
+            d.draw_line(cx, cy - 8, cx, cy + 8, Color::WHITE);
+
+            let hint = tr!("editor.walkthrough_hint", state = on_off(walkthrough.collision));
+            d.draw_rectangle(10, 10, d.measure_text(&hint, 16) + 20, 30, Color::new(10, 10, 20, 200));
+            d.draw_text(&hint, 20, 17, 16, Color::new(0, 242, 148, 255));
+        }
+
         // Show drag indicator when dragging
         if self.is_dragging_model {
             if let Some(model_type) = self.dragged_model_type {
-                let text = format!("Dragging: {:?}", model_type);
+                let text = tr!("editor.dragging", model = model_type.label());
                 let text_width = text.len() as i32 * 10;
                 let x = (viewport_width - text_width) / 2;
                 let y = 100;
@@ -767,15 +1221,15 @@ impl MapBuilder {
         d.draw_rectangle_lines(panel_x, panel_y, panel_width, panel_height, Color::WHITE);
 
         // Title
-        d.draw_text("=== OBJECT HIERARCHY ===", panel_x + 10, panel_y + 10, 18, Color::WHITE);
-        d.draw_text("Press number keys to select:", panel_x + 10, panel_y + 30, 14, Color::LIGHTGRAY);
+        d.draw_text(t("editor.panel.hierarchy_title"), panel_x + 10, panel_y + 10, 18, Color::WHITE);
+        d.draw_text(t("editor.panel.hierarchy_hint"), panel_x + 10, panel_y + 30, 14, Color::LIGHTGRAY);
 
         // List objects
         let start_y = panel_y + 55;
         let visible_objects = 30;
 
         if self.map.objects.is_empty() {
-            d.draw_text("(No objects yet)", panel_x + 10, start_y, 16, Color::GRAY);
+            d.draw_text(t("editor.panel.no_objects"), panel_x + 10, start_y, 16, Color::GRAY);
         } else {
             for (i, obj) in self.map.objects.iter().enumerate().take(visible_objects) {
                 let y = start_y + i as i32 * line_height;
@@ -792,9 +1246,9 @@ impl MapBuilder {
                 let color = obj.get_color();
 
                 let text = format!(
-                    "{}: {:?} @ ({:.1},{:.1},{:.1}) S:{:.1}",
+                    "{}: {} @ ({:.1},{:.1},{:.1}) S:{:.1}",
                     i,
-                    obj.model_type,
+                    obj.model_type.label(),
                     pos.x,
                     pos.y,
                     pos.z,
@@ -809,7 +1263,7 @@ impl MapBuilder {
 
             if self.map.objects.len() > visible_objects {
                 d.draw_text(
-                    &format!("... and {} more", self.map.objects.len() - visible_objects),
+                    &tr!("editor.panel.more_objects", count = self.map.objects.len() - visible_objects),
                     panel_x + 10,
                     start_y + visible_objects as i32 * line_height,
                     14,
@@ -820,57 +1274,16 @@ impl MapBuilder {
 
         // Instructions
         let instructions_y = panel_y + panel_height - 80;
-        d.draw_text("--- SELECTION ---", panel_x + 10, instructions_y, 14, Color::LIGHTGRAY);
-        d.draw_text("0-9: Quick select (0-9)", panel_x + 10, instructions_y + 18, 13, Color::WHITE);
-        d.draw_text(",/. : Prev/Next", panel_x + 10, instructions_y + 34, 13, Color::WHITE);
-        d.draw_text("ESC: Deselect", panel_x + 10, instructions_y + 50, 13, Color::WHITE);
-        d.draw_text("DEL: Delete selected", panel_x + 10, instructions_y + 66, 13, Color::WHITE);
+        d.draw_text(t("editor.panel.selection"), panel_x + 10, instructions_y, 14, Color::LIGHTGRAY);
+        d.draw_text(t("editor.panel.quick_select"), panel_x + 10, instructions_y + 18, 13, Color::WHITE);
+        d.draw_text(t("editor.panel.prev_next"), panel_x + 10, instructions_y + 34, 13, Color::WHITE);
+        d.draw_text(t("editor.panel.deselect"), panel_x + 10, instructions_y + 50, 13, Color::WHITE);
+        d.draw_text(t("editor.panel.delete"), panel_x + 10, instructions_y + 66, 13, Color::WHITE);
     }
 
     /// Draw help overlay
     fn draw_help(&self, d: &mut RaylibDrawHandle) {
-        let help_text = vec![
-            "=== FPS.so MAP BUILDER ===",
-            "",
-            "=== CAMERA ===",
-            "WASD: Move camera",
-            "Q/E: Move up/down",
-            "",
-            "=== MODES ===",
-            "1: Placing mode",
-            "2: Selecting mode",
-            "3: Moving mode (need selection)",
-            "4: Rotating mode (need selection)",
-            "5: Scaling mode (need selection)",
-            "",
-            "=== PLACING MODE ===",
-            "C: Cube   T: Triangle",
-            "S: Sphere L: Cylinder",
-            "P: Plane",
-            "Preview follows camera",
-            "Arrow/NumPad: Fine adjust",
-            "PgUp/PgDn: Adjust Y",
-            "Space/Click: Place object",
-            "",
-            "=== SELECTING MODE ===",
-            ",/.: Prev/Next object",
-            "ESC: Deselect",
-            "DEL/Backspace: Delete",
-            "",
-            "=== MANIPULATION ===",
-            "X/Y/Z: Lock to axis",
-            "A: All axes",
-            "Arrow Keys: Adjust values",
-            "PgUp/PgDn: Y-axis adjust",
-            "",
-            "=== OTHER ===",
-            "G: Toggle grid",
-            "N: Toggle grid snap",
-            "F5: Save map",
-            "F9: Load map",
-            "U: Toggle hierarchy",
-            "H/F1: Toggle help",
-        ];
+        let help_text: Vec<&str> = t("editor.help").lines().collect();
 
         let bg_width = 340;
         let bg_height = help_text.len() as i32 * 18 + 20;
@@ -883,11 +1296,11 @@ impl MapBuilder {
 
     /// Snap position to grid
     fn snap_to_grid(&self, pos: Vector3) -> Vector3 {
-        if self.grid_snap {
+        if let Some(step) = self.snap.translation_step() {
             Vector3::new(
-                (pos.x / self.grid_size).round() * self.grid_size,
-                (pos.y / self.grid_size).round() * self.grid_size,
-                (pos.z / self.grid_size).round() * self.grid_size,
+                snap_value(pos.x, step),
+                snap_value(pos.y, step),
+                snap_value(pos.z, step),
             )
         } else {
             pos
@@ -903,6 +1316,48 @@ impl MapBuilder {
         )
     }
 
+    /// Select one object, dropping any group selection
+    fn select_single(&mut self, index: usize) {
+        self.selected_object = Some(index);
+        self.multi_selection.clear();
+    }
+
+    fn clear_selection(&mut self) {
+        self.selected_object = None;
+        self.multi_selection.clear();
+    }
+
+    /// Select every object in `indices` (the first becomes the primary selection)
+    fn select_group(&mut self, indices: Vec<usize>) {
+        self.selected_object = indices.first().copied();
+        self.multi_selection = if indices.len() > 1 { indices } else { Vec::new() };
+        if self.selected_object.is_some() {
+            self.mode = EditorMode::Selecting;
+        }
+    }
+
+    /// Remove the selected object(s)
+    fn delete_selection(&mut self) {
+        let mut indices = self.multi_selection.clone();
+        indices.extend(self.selected_object);
+        indices.sort_unstable();
+        indices.dedup();
+        if indices.is_empty() {
+            return;
+        }
+
+        // Highest index first so the remaining indices stay valid
+        for &index in indices.iter().rev() {
+            self.map.remove_object(index);
+        }
+        self.clear_selection();
+        self.set_status(&if indices.len() == 1 {
+            t("editor.status.deleted_one").to_string()
+        } else {
+            tr!("editor.status.deleted_many", count = indices.len())
+        });
+    }
+
     /// Set status message
     fn set_status(&mut self, message: &str) {
         self.status_message = message.to_string();
@@ -972,16 +1427,18 @@ impl MapBuilder {
                 match map_result {
                     Ok(map) => {
                         self.map = map;
-                        self.selected_object = None;
-                        self.set_status(&format!("Map loaded successfully ({} objects)", self.map.objects.len()));
+                        self.published_map_id = None;
+                        self.clear_selection();
+                        self.share_replaced_map();
+                        self.set_status(&tr!("editor.status.map_loaded", count = self.map.objects.len()));
                     }
                     Err(e) => {
-                        self.set_status(&format!("Failed to load map: {}", e));
+                        self.set_status(&tr!("editor.status.load_failed", error = e));
                     }
                 }
             }
             Err(e) => {
-                self.set_status(&format!("Failed to decode map data: {}", e));
+                self.set_status(&tr!("editor.status.decode_failed", error = e));
             }
         }
     }
@@ -1006,7 +1463,7 @@ impl MapBuilder {
         // Tools Panel (left side) - now includes File and Help menus
         // Calculate height to fit from top offset to near bottom of screen
         let tools_height = 720.0 - y_offset - 20.0;  // Screen height - offset - bottom margin
-        ui.window("Tools")
+        ui.window(&format!("{}###tools", t("editor.window.tools")))
             .position([10.0, y_offset], imgui::Condition::Always)
             .size([220.0, tools_height], imgui::Condition::Always)
             .bg_alpha(0.9)
@@ -1014,91 +1471,65 @@ impl MapBuilder {
             .build(|| {
                 // Solana teal header color
                 let _header_color = ui.push_style_color(imgui::StyleColor::Text, [0.08, 0.95, 0.58, 1.0]);
-                ui.text("MAP EDITOR TOOLS");
+                ui.text(t("editor.tools.header"));
                 drop(_header_color);
                 ui.separator();
 
                 // FILE MENU SECTION
-                ui.text_colored([0.60, 0.27, 1.0, 1.0], "File");
+                ui.text_colored([0.60, 0.27, 1.0, 1.0], t("editor.tools.file"));
                 ui.separator();
                 ui.dummy([0.0, 5.0]);
 
-                if ui.button_with_size("New Map", [180.0, 25.0]) {
+                if ui.button_with_size(t("editor.tools.new_map"), [180.0, 25.0]) {
                     self.map = Map::new("Untitled Map".to_string());
-                    self.selected_object = None;
-                    self.set_status("Created new map");
+                    self.published_map_id = None;
+                    self.clear_selection();
+                    self.share_replaced_map();
+                    self.set_status(t("editor.status.new_map"));
                 }
 
-                if ui.button_with_size("Save Map", [180.0, 25.0]) {
+                if ui.button_with_size(t("editor.tools.library"), [180.0, 25.0]) {
+                    self.open_library();
+                }
+
+                if ui.button_with_size(t("editor.tools.collaborate"), [180.0, 25.0]) {
+                    self.show_collab = !self.show_collab;
+                }
+
+                if ui.button_with_size(t("editor.tools.save_map"), [180.0, 25.0]) {
                     match self.map.to_borsh_bytes() {
                         Ok(bytes) => {
-                            use base64::{Engine as _, engine::general_purpose};
-                            let base64_string = general_purpose::STANDARD.encode(&bytes);
-                            let filename = format!("{}.fpssomap", self.map.name.replace(" ", "_"));
+                            let filename = format!("{}.{}", library::slot_name(&self.map.name), MAP_FILE_EXTENSION);
 
-                            // Trigger browser download via Emscripten JavaScript interop
+                            // Trigger a browser download through the blob-download bridge
                             #[cfg(target_os = "emscripten")]
                             {
-                                use std::ffi::CString;
-
-                                extern "C" {
-                                    pub fn emscripten_run_script(script: *const i8);
-                                }
-
-                                let js_code = format!(
-                                    r#"
-                                    (function() {{
-                                        var base64Data = '{}';
-                                        var filename = '{}';
-
-                                        // Decode base64 to binary
-                                        var byteCharacters = atob(base64Data);
-                                        var byteNumbers = new Array(byteCharacters.length);
-                                        for (var i = 0; i < byteCharacters.length; i++) {{
-                                            byteNumbers[i] = byteCharacters.charCodeAt(i);
-                                        }}
-                                        var byteArray = new Uint8Array(byteNumbers);
-
-                                        // Create blob and download
-                                        var blob = new Blob([byteArray], {{type: 'application/octet-stream'}});
-                                        var url = URL.createObjectURL(blob);
-                                        var a = document.createElement('a');
-                                        a.href = url;
-                                        a.download = filename;
-                                        document.body.appendChild(a);
-                                        a.click();
-                                        document.body.removeChild(a);
-                                        URL.revokeObjectURL(url);
-                                    }})();
-                                    "#,
-                                    base64_string, filename
-                                );
-
-                                let c_str = CString::new(js_code).unwrap();
-                                unsafe {
-                                    emscripten_run_script(c_str.as_ptr());
+                                match crate::net::download_file(&filename, "application/octet-stream", &bytes) {
+                                    Ok(()) => self.set_status(&tr!("editor.status.map_downloaded", file = filename, bytes = bytes.len())),
+                                    Err(e) => self.set_status(&tr!("editor.status.save_failed", error = e)),
                                 }
-
-                                self.set_status(&format!("Map downloaded: {} ({} bytes)", filename, bytes.len()));
                             }
 
                             #[cfg(not(target_os = "emscripten"))]
                             {
-                                // For native builds, save to file
-                                if let Err(e) = std::fs::write(&filename, bytes) {
-                                    self.set_status(&format!("Failed to save: {}", e));
+                                // For native builds, export into the library folder
+                                let path = std::path::Path::new(library::LIBRARY_DIR).join(&filename);
+                                let result = std::fs::create_dir_all(library::LIBRARY_DIR).and_then(|_| std::fs::write(&path, bytes));
+                                if let Err(e) = result {
+                                    self.set_status(&tr!("editor.status.save_failed", error = e));
                                 } else {
-                                    self.set_status(&format!("Map saved: {}", filename));
+                                    self.set_status(&tr!("editor.status.map_saved", path = path.display()));
+                                    self.refresh_library();
                                 }
                             }
                         }
                         Err(e) => {
-                            self.set_status(&format!("Failed to save: {}", e));
+                            self.set_status(&tr!("editor.status.save_failed", error = e));
                         }
                     }
                 }
 
-                if ui.button_with_size("Import Map", [180.0, 25.0]) {
+                if ui.button_with_size(t("editor.tools.import_map"), [180.0, 25.0]) {
                     // Trigger file picker via Emscripten JavaScript interop
                     #[cfg(target_os = "emscripten")]
                     {
@@ -1143,24 +1574,39 @@ impl MapBuilder {
                             emscripten_run_script(c_str.as_ptr());
                         }
 
-                        self.set_status("Select .fpssomap file to import...");
+                        self.set_status(t("editor.status.select_import"));
                     }
 
                     #[cfg(not(target_os = "emscripten"))]
                     {
-                        self.set_status("Import from .fpssomap - feature only available in browser");
+                        // Native builds import by dropping files into the library folder
+                        self.open_library();
+                        self.set_status(&tr!("editor.status.copy_to_import", extension = MAP_FILE_EXTENSION, dir = library::LIBRARY_DIR));
                     }
                 }
 
+                if ui.collapsing_header(&format!("{}###heightmap", t("editor.tools.import_heightmap")), imgui::TreeNodeFlags::empty()) {
+                    self.draw_heightmap_import(ui);
+                }
+
+                if ui.collapsing_header(&format!("{}###generate", t("editor.generate.title")), imgui::TreeNodeFlags::empty()) {
+                    self.draw_generator_tools(ui);
+                }
+
+                if ui.collapsing_header(&format!("{}###heatmap", t("editor.heatmap.title")), imgui::TreeNodeFlags::empty()) {
+                    self.draw_heatmap_tools(ui);
+                }
+
                 ui.dummy([0.0, 10.0]);
 
-                if ui.button_with_size("My Maps", [180.0, 25.0]) {
+                if ui.button_with_size(t("editor.tools.my_maps"), [180.0, 25.0]) {
                     self.show_my_maps = !self.show_my_maps;
                 }
 
-                if ui.button_with_size("Upload to Solana", [180.0, 25.0]) {
+                if ui.button_with_size(t("editor.tools.upload"), [180.0, 25.0]) {
                     self.show_upload_popup = true;
-                    self.upload_map_id = String::new();
+                    self.upload_update_existing = self.published_map_id.is_some();
+                    self.upload_map_id = self.published_map_id.clone().unwrap_or_default();
                     self.upload_map_name = self.map.name.clone();
                     self.upload_map_description = String::new();
                 }
@@ -1170,164 +1616,301 @@ impl MapBuilder {
                 ui.dummy([0.0, 10.0]);
 
                 // TOOLS SECTION
-                ui.text_colored([0.60, 0.27, 1.0, 1.0], "Tools");
+                ui.text_colored([0.60, 0.27, 1.0, 1.0], t("editor.tools.tools"));
                 ui.separator();
                 ui.dummy([0.0, 5.0]);
 
-                if ui.button_with_size("1. Placing Mode", [180.0, 25.0]) {
+                if ui.button_with_size(t("editor.tools.placing_mode"), [180.0, 25.0]) {
                     self.mode = EditorMode::Placing;
                 }
-                if ui.button_with_size("2. Selecting Mode", [180.0, 25.0]) {
+                if ui.button_with_size(t("editor.tools.selecting_mode"), [180.0, 25.0]) {
                     self.mode = EditorMode::Selecting;
                 }
 
                 ui.dummy([0.0, 10.0]);
-                ui.text("Place Model:");
-                ui.text_colored([0.7, 0.7, 0.7, 1.0], "(Click or drag to viewport)");
+                ui.text(t("editor.tools.place_model"));
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], t("editor.tools.drag_hint_short"));
                 ui.dummy([0.0, 5.0]);
 
                 // Draggable model buttons with visual representation
-                self.draw_draggable_model_button(ui, ModelType::Cube, "🟦 Cube (C)", "##cube");
-                self.draw_draggable_model_button(ui, ModelType::Rectangle, "▬ Rectangle (R)", "##rect");
-                self.draw_draggable_model_button(ui, ModelType::Triangle, "🔺 Triangle (T)", "##tri");
-                self.draw_draggable_model_button(ui, ModelType::Sphere, "⚫ Sphere (S)", "##sphere");
-                self.draw_draggable_model_button(ui, ModelType::Cylinder, "🛢 Cylinder (L)", "##cyl");
-                self.draw_draggable_model_button(ui, ModelType::Plane, "▭ Plane (P)", "##plane");
+                self.draw_draggable_model_button(ui, ModelType::Cube, &format!("🟦 {} (C)", ModelType::Cube.label()), "##cube");
+                self.draw_draggable_model_button(ui, ModelType::Rectangle, &format!("▬ {} (R)", ModelType::Rectangle.label()), "##rect");
+                self.draw_draggable_model_button(ui, ModelType::Triangle, &format!("🔺 {} (T)", ModelType::Triangle.label()), "##tri");
+                self.draw_draggable_model_button(ui, ModelType::Sphere, &format!("⚫ {} (S)", ModelType::Sphere.label()), "##sphere");
+                self.draw_draggable_model_button(ui, ModelType::Cylinder, &format!("🛢 {} (L)", ModelType::Cylinder.label()), "##cyl");
+                self.draw_draggable_model_button(ui, ModelType::Plane, &format!("▭ {} (P)", ModelType::Plane.label()), "##plane");
 
                 ui.dummy([0.0, 10.0]);
-                ui.text("Spawn Points:");
-                ui.text_colored([0.7, 0.7, 0.7, 1.0], "(Click or drag to viewport)");
+                ui.text(t("editor.tools.spawn_points"));
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], t("editor.tools.drag_hint_short"));
                 ui.dummy([0.0, 5.0]);
 
-                self.draw_draggable_model_button(ui, ModelType::SpawnPointBlue, "🔵 Blue Spawn (B)", "##bluespawn");
-                self.draw_draggable_model_button(ui, ModelType::SpawnPointRed, "🔴 Red Spawn (D)", "##redspawn");
+                self.draw_draggable_model_button(ui, ModelType::SpawnPointBlue, &format!("🔵 {} (B)", t("editor.tools.blue_spawn")), "##bluespawn");
+                self.draw_draggable_model_button(ui, ModelType::SpawnPointRed, &format!("🔴 {} (D)", t("editor.tools.red_spawn")), "##redspawn");
+
+                ui.dummy([0.0, 10.0]);
+                ui.text(t("editor.tools.paint_color"));
+                ui.text_colored([0.7, 0.7, 0.7, 1.0], t("editor.tools.sample_hint"));
+                ui.dummy([0.0, 5.0]);
+                let mut paint = color_to_rgb(self.current_color);
+                ui.set_next_item_width(180.0);
+                if ui.color_edit3("##paint_color", &mut paint) {
+                    self.current_color = rgb_to_color(paint);
+                }
+                if let Some(color) = self.draw_palette(ui, "tools") {
+                    self.current_color = color;
+                }
+                if ui.button_with_size(t("editor.tools.save_swatch"), [180.0, 22.0]) {
+                    let current = self.current_color;
+                    if self.palette.iter().any(|c| (c.r, c.g, c.b) == (current.r, current.g, current.b)) {
+                        self.set_status(t("editor.status.swatch_exists"));
+                    } else if self.palette.len() >= MAX_PALETTE_SIZE {
+                        self.set_status(t("editor.status.palette_full"));
+                    } else {
+                        self.palette.push(self.current_color);
+                    }
+                }
 
                 if self.selected_object.is_some() {
                     ui.dummy([0.0, 10.0]);
-                    ui.text("Transform:");
+                    ui.text(t("editor.tools.transform"));
                     ui.dummy([0.0, 5.0]);
-                    if ui.button_with_size("3. Move (G)", [180.0, 22.0]) {
+                    if ui.button_with_size(t("editor.tools.move"), [180.0, 22.0]) {
                         self.mode = EditorMode::Moving;
                     }
-                    if ui.button_with_size("4. Rotate (R)", [180.0, 22.0]) {
+                    if ui.button_with_size(t("editor.tools.rotate"), [180.0, 22.0]) {
                         self.mode = EditorMode::Rotating;
                     }
-                    if ui.button_with_size("5. Scale (S)", [180.0, 22.0]) {
+                    if ui.button_with_size(t("editor.tools.scale"), [180.0, 22.0]) {
                         self.mode = EditorMode::Scaling;
                     }
                 }
 
-                ui.dummy([0.0, 15.0]);
-                ui.separator();
                 ui.dummy([0.0, 10.0]);
+                if ui.button_with_size(
+                    if self.walkthrough.is_some() { t("editor.tools.exit_walkthrough") } else { t("editor.tools.walkthrough") },
+                    [180.0, 25.0],
+                ) {
+                    self.toggle_walkthrough();
+                }
+                ui.checkbox(&format!("{}##walkthrough", t("editor.tools.collision")), &mut self.walkthrough_collision);
+                if ui.button_with_size(
+                    if self.show_balance { t("editor.tools.hide_balance") } else { t("editor.tools.balance") },
+                    [180.0, 25.0],
+                ) {
+                    self.toggle_balance();
+                }
 
-                // HELP SECTION
-                ui.text_colored([0.60, 0.27, 1.0, 1.0], "Controls");
-                ui.separator();
+                ui.dummy([0.0, 10.0]);
+                ui.text(t("editor.tools.snapping"));
                 ui.dummy([0.0, 5.0]);
+                let translation_labels = TRANSLATION_STEPS.map(|s| format!("{} u", s));
+                let rotation_labels = ROTATION_STEPS.map(|s| format!("{}°", s));
+                let scale_labels = SCALE_STEPS.map(|s| format!("{}", s));
+                ui.checkbox(&format!("{}##snap", t("editor.tools.snap_move")), &mut self.snap.translate);
+                ui.same_line_with_pos(80.0);
+                ui.set_next_item_width(100.0);
+                ui.combo_simple_string("##snap_move", &mut self.snap.translate_index, &translation_labels);
+                ui.checkbox(&format!("{}##snap", t("editor.tools.snap_rotate")), &mut self.snap.rotate);
+                ui.same_line_with_pos(80.0);
+                ui.set_next_item_width(100.0);
+                ui.combo_simple_string("##snap_rotate", &mut self.snap.rotate_index, &rotation_labels);
+                ui.checkbox(&format!("{}##snap", t("editor.tools.snap_scale")), &mut self.snap.scale);
+                ui.same_line_with_pos(80.0);
+                ui.set_next_item_width(100.0);
+                ui.combo_simple_string("##snap_scale", &mut self.snap.scale_index, &scale_labels);
 
-                ui.text("Camera:");
-                ui.text("  WASD - Move camera");
-                ui.text("  Arrow Keys - Rotate camera");
-                ui.text("  Q/E - Move up/down");
-
-                ui.separator();
-                ui.text("Modes:");
-                ui.text("  1 - Placing Mode");
-                ui.text("  2 - Selecting Mode");
-                ui.text("  3 - Moving Mode");
-                ui.text("  4 - Rotating Mode");
-                ui.text("  5 - Scaling Mode");
-
+                ui.dummy([0.0, 15.0]);
                 ui.separator();
-                ui.text("Models (Placing Mode):");
-                ui.text("  C - Cube");
-                ui.text("  R - Rectangle");
-                ui.text("  T - Triangle");
-                ui.text("  S - Sphere");
-                ui.text("  L - Cylinder");
-                ui.text("  P - Plane");
-                ui.text("  B - Blue Spawn Point");
-                ui.text("  D - Red Spawn Point");
+                ui.dummy([0.0, 10.0]);
 
+                // HELP SECTION
+                ui.text_colored([0.60, 0.27, 1.0, 1.0], t("editor.tools.controls"));
                 ui.separator();
-                ui.text("Actions:");
-                ui.text("  Click - Place/Select object");
-                ui.text("  Delete/Backspace - Remove object");
-                ui.text("  N - Toggle grid snap");
-                ui.text("  G - Toggle grid");
+                ui.dummy([0.0, 5.0]);
 
-                ui.separator();
-                ui.text("Save/Load:");
-                ui.text("  F5 - Quick save");
-                ui.text("  F9 - Quick load");
+                // "---" lines are separators
+                for line in t("editor.controls").lines() {
+                    if line == "---" {
+                        ui.separator();
+                    } else {
+                        ui.text(line);
+                    }
+                }
             });
 
         // Inspector Panel (right side, top)
-        ui.window("Inspector")
+        ui.window(&format!("{}###inspector", t("editor.window.inspector")))
             .position([viewport_width + 10.0, y_offset], imgui::Condition::Always)
             .size([390.0, 330.0], imgui::Condition::Always)
             .collapsible(false)
             .bg_alpha(0.85)
             .build(|| {
                 // Solana teal header color
-                ui.text_colored([0.08, 0.95, 0.58, 1.0], "INSPECTOR");
+                ui.text_colored([0.08, 0.95, 0.58, 1.0], t("editor.inspector.header"));
                 ui.separator();
 
-                ui.text(format!("Mode: {:?}", self.mode));
-                ui.text(format!("Objects: {}/600", self.map.objects.len())); // Updated capacity with Borsh
-
-                // Calculate actual size using Borsh (more compact than JSON)
-                let actual_size = match self.map.to_borsh_bytes() {
-                    Ok(bytes) => bytes.len(),
-                    Err(_) => 0,
-                };
-                let max_size = 10240; // 10 KB in bytes
-                let size_percent = (actual_size as f32 / max_size as f32 * 100.0) as u32;
+                ui.text(tr!("editor.status.mode", mode = self.mode.label()));
 
-                let size_color = if actual_size > max_size {
-                    [1.0, 0.0, 0.0, 1.0] // Red if over limit
-                } else if size_percent > 80 {
-                    [1.0, 0.8, 0.0, 1.0] // Orange/yellow if getting close
-                } else {
-                    [0.0, 1.0, 0.0, 1.0] // Green if within limit
+                // Map stats and budgets (10 KB on chain, 400 objects)
+                let stats = MapStats::of(&self.map);
+                let level_color = |level: BudgetLevel| match level {
+                    BudgetLevel::Over => [1.0, 0.0, 0.0, 1.0],    // Red if over limit
+                    BudgetLevel::Warning => [1.0, 0.8, 0.0, 1.0], // Orange/yellow if getting close
+                    BudgetLevel::Ok => [0.0, 1.0, 0.0, 1.0],      // Green if within limit
                 };
-
                 ui.text_colored(
-                    size_color,
-                    format!("Size: {} / {} bytes ({}%)", actual_size, max_size, size_percent)
+                    level_color(stats.object_level()),
+                    tr!("editor.inspector.objects", count = stats.total_objects, budget = MAP_OBJECT_BUDGET),
+                );
+                ui.same_line();
+                ui.text_colored(
+                    level_color(stats.byte_level()),
+                    tr!(
+                        "editor.inspector.size",
+                        bytes = stats.bytes,
+                        budget = MAP_BYTE_BUDGET,
+                        percent = stats.bytes * 100 / MAP_BYTE_BUDGET,
+                    ),
                 );
+                // Past one account's worth of objects the map uploads in chunks that stream in during matches
+                let chunks = stats.total_objects.div_ceil(MAP_CHUNK_OBJECTS);
+                if chunks > 1 {
+                    ui.text_colored([0.6, 0.8, 1.0, 1.0], tr!("editor.inspector.streamed", chunks = chunks));
+                }
+                if stats.byte_level() == BudgetLevel::Over && chunks <= 1 {
+                    ui.text_colored([1.0, 0.0, 0.0, 1.0], t("editor.inspector.too_large"));
+                } else if stats.object_level() == BudgetLevel::Over {
+                    ui.text_colored([1.0, 0.0, 0.0, 1.0], t("editor.inspector.over_objects"));
+                } else if stats.object_level() != BudgetLevel::Ok || stats.byte_level() != BudgetLevel::Ok {
+                    ui.text_colored([1.0, 0.8, 0.0, 1.0], t("editor.inspector.near_budget"));
+                }
+
+                if ui.collapsing_header(&format!("{}###map_stats", t("editor.inspector.stats")), imgui::TreeNodeFlags::empty()) {
+                    for (model_type, count) in MODEL_TYPES.iter().zip(stats.counts) {
+                        if count > 0 {
+                            ui.text(format!("  {}: {}", model_type.label(), count));
+                        }
+                    }
+                    ui.text(tr!("editor.inspector.draw_calls", count = stats.draw_calls));
+                    ui.text(tr!("editor.inspector.collision_boxes", count = stats.collision_boxes));
+                    ui.text(tr!("editor.inspector.bytes", raw = stats.bytes, compressed = stats.compressed_bytes));
+                }
+
+                if ui.collapsing_header(&format!("{}###ambiance", t("editor.ambiance.title")), imgui::TreeNodeFlags::empty()) {
+                    self.draw_ambiance_editor(ui);
+                }
+
+                if ui.collapsing_header(&format!("{}###script", t("editor.script.title")), imgui::TreeNodeFlags::empty()) {
+                    self.draw_script_editor(ui);
+                }
+
+                if ui.collapsing_header(&format!("{}###ambient_sounds", t("editor.ambient.title")), imgui::TreeNodeFlags::empty()) {
+                    self.draw_ambient_sound_editor(ui);
+                }
 
                 ui.separator();
 
                 if let Some(index) = self.selected_object {
                     if index < self.map.objects.len() {
-                        ui.text_colored([1.0, 1.0, 0.0, 1.0], format!("Selected: Object {}", index));
-                        ui.text(format!("Type: {:?}", self.map.objects[index].model_type));
+                        ui.text_colored([1.0, 1.0, 0.0, 1.0], tr!("editor.inspector.selected", index = index));
+                        if self.multi_selection.len() > 1 {
+                            ui.same_line();
+                            ui.text_colored([1.0, 0.6, 0.0, 1.0], tr!("editor.inspector.group", count = self.multi_selection.len() - 1));
+                        }
+                        ui.text(tr!("editor.inspector.type", model = self.map.objects[index].model_type.label()));
+
+                        // Optional label: name for authors, tags for gameplay lookups
+                        let (mut name, mut tags) = self.map.label(index)
+                            .map(|l| (l.name.clone(), l.tags.clone()))
+                            .unwrap_or_default();
+                        let mut label_changed = false;
+                        ui.set_next_item_width(250.0);
+                        label_changed |= ui.input_text(&format!("{}##label", t("editor.inspector.name")), &mut name)
+                            .hint(t("editor.inspector.name_hint"))
+                            .build();
+                        ui.set_next_item_width(250.0);
+                        label_changed |= ui.input_text(&format!("{}##label", t("editor.inspector.tags")), &mut tags)
+                            .hint(t("editor.inspector.tags_hint"))
+                            .build();
+                        if label_changed {
+                            self.map.set_object_label(index, &name, &tags);
+                        }
+
+                        // Color (applies to the whole selection)
+                        let mut rgb = color_to_rgb(self.map.objects[index].get_color());
+                        ui.set_next_item_width(250.0);
+                        if ui.color_edit3(&format!("{}##object", t("editor.inspector.color")), &mut rgb) {
+                            self.set_selection_color(rgb_to_color(rgb));
+                        }
+                        if let Some(color) = self.draw_palette(ui, "inspector") {
+                            self.set_selection_color(color);
+                        }
+
+                        // Emissive material (neon accents get a halo in-game)
+                        let glow = self.map.glow(index).copied();
+                        let mut glowing = glow.is_some();
+                        if ui.checkbox(&format!("{}##object", t("editor.inspector.glow")), &mut glowing) {
+                            let (color, intensity) = if glowing { DEFAULT_GLOW } else { (DEFAULT_GLOW.0, 0) };
+                            self.map.set_object_glow(index, color, intensity);
+                        }
+                        if let Some(glow) = glow.filter(|_| glowing) {
+                            let mut rgb = color_to_rgb(glow.color());
+                            let mut intensity = glow.intensity;
+                            ui.set_next_item_width(250.0);
+                            let mut changed = ui.color_edit3(&format!("{}##glow", t("editor.inspector.glow_color")), &mut rgb);
+                            ui.set_next_item_width(150.0);
+                            changed |= ui.slider(&format!("{}##glow", t("editor.inspector.glow_intensity")), 1, 100, &mut intensity);
+                            if changed {
+                                let color = rgb_to_color(rgb);
+                                self.map.set_object_glow(index, [color.r, color.g, color.b], intensity);
+                            }
+                        }
+
+                        // What stops at this object: players, bullets, both or neither (applies to the whole selection)
+                        // Trigger volumes never collide, so they don't get the choice
+                        if self.map.trigger(index).is_none() {
+                            let labels: Vec<&str> = Collision::ALL.iter().map(|c| c.label()).collect();
+                            let mut collision_index = Collision::ALL.iter().position(|c| *c == self.map.collision(index)).unwrap_or(0);
+                            ui.set_next_item_width(150.0);
+                            if ui.combo_simple_string(&format!("{}##object", t("editor.inspector.collision")), &mut collision_index, &labels) {
+                                self.set_selection_collision(Collision::ALL[collision_index]);
+                            }
+                        }
+
+                        // Trigger volume: hidden in-game, its bounds fire an action when walked into
+                        if self.map.objects[index].collision_bounds().is_some() {
+                            let mut is_trigger = self.map.trigger(index).is_some();
+                            if ui.checkbox(&format!("{}##object", t("editor.inspector.trigger")), &mut is_trigger) {
+                                let trigger = is_trigger.then(|| (TriggerAction::kinds()[0].clone(), false));
+                                self.map.set_object_trigger(index, trigger);
+                            }
+                            self.draw_trigger_editor(ui, index);
+                        }
 
                         ui.separator();
 
                         // Position controls
-                        ui.text("Position:");
+                        ui.text(t("editor.inspector.position"));
                         let mut pos = self.map.objects[index].get_position();
                         let mut pos_changed = false;
 
                         ui.set_next_item_width(120.0);
                         pos_changed |= ui
                             .input_float("X##pos", &mut pos.x)
-                            .step(0.1)
+                            .step(self.snap.translation_step().unwrap_or(0.1))
                             .step_fast(1.0)
                             .build();
                         ui.set_next_item_width(120.0);
                         pos_changed |= ui
                             .input_float("Y##pos", &mut pos.y)
-                            .step(0.1)
+                            .step(self.snap.translation_step().unwrap_or(0.1))
                             .step_fast(1.0)
                             .build();
                         ui.set_next_item_width(120.0);
                         pos_changed |= ui
                             .input_float("Z##pos", &mut pos.z)
-                            .step(0.1)
+                            .step(self.snap.translation_step().unwrap_or(0.1))
                             .step_fast(1.0)
                             .build();
 
@@ -1342,26 +1925,26 @@ impl MapBuilder {
                         ui.separator();
 
                         // Rotation controls
-                        ui.text("Rotation:");
+                        ui.text(t("editor.inspector.rotation"));
                         let mut rot = self.map.objects[index].get_rotation();
                         let mut rot_changed = false;
 
                         ui.set_next_item_width(120.0);
                         rot_changed |= ui
                             .input_float("X##rot", &mut rot.x)
-                            .step(1.0)
+                            .step(self.snap.rotation_step().unwrap_or(1.0))
                             .step_fast(15.0)
                             .build();
                         ui.set_next_item_width(120.0);
                         rot_changed |= ui
                             .input_float("Y##rot", &mut rot.y)
-                            .step(1.0)
+                            .step(self.snap.rotation_step().unwrap_or(1.0))
                             .step_fast(15.0)
                             .build();
                         ui.set_next_item_width(120.0);
                         rot_changed |= ui
                             .input_float("Z##rot", &mut rot.z)
-                            .step(1.0)
+                            .step(self.snap.rotation_step().unwrap_or(1.0))
                             .step_fast(15.0)
                             .build();
 
@@ -1376,26 +1959,26 @@ impl MapBuilder {
                         ui.separator();
 
                         // Scale controls
-                        ui.text("Scale:");
+                        ui.text(t("editor.inspector.scale"));
                         let mut scale = self.map.objects[index].get_scale();
                         let mut scale_changed = false;
 
                         ui.set_next_item_width(120.0);
                         scale_changed |= ui
                             .input_float("X##scale", &mut scale.x)
-                            .step(0.1)
+                            .step(self.snap.scale_step().unwrap_or(0.1))
                             .step_fast(0.5)
                             .build();
                         ui.set_next_item_width(120.0);
                         scale_changed |= ui
                             .input_float("Y##scale", &mut scale.y)
-                            .step(0.1)
+                            .step(self.snap.scale_step().unwrap_or(0.1))
                             .step_fast(0.5)
                             .build();
                         ui.set_next_item_width(120.0);
                         scale_changed |= ui
                             .input_float("Z##scale", &mut scale.z)
-                            .step(0.1)
+                            .step(self.snap.scale_step().unwrap_or(0.1))
                             .step_fast(0.5)
                             .build();
 
@@ -1410,36 +1993,93 @@ impl MapBuilder {
                         ui.separator();
 
                         // Delete button
-                        if ui.button("Delete Object") {
-                            self.map.remove_object(index);
-                            self.selected_object = None;
-                            self.set_status("Object deleted");
+                        let delete_label = if self.multi_selection.len() > 1 {
+                            tr!("editor.inspector.delete_many", count = self.multi_selection.len())
+                        } else {
+                            t("editor.inspector.delete_one").to_string()
+                        };
+                        if ui.button(&delete_label) {
+                            self.delete_selection();
                         }
                     }
                 } else {
-                    ui.text_colored([0.5, 0.5, 0.5, 1.0], "No object selected");
+                    ui.text_colored([0.5, 0.5, 0.5, 1.0], t("editor.inspector.nothing_selected"));
                 }
             });
 
         // Hierarchy Panel (right side, bottom - no gap with Inspector)
-        ui.window("Hierarchy")
+        ui.window(&format!("{}###hierarchy", t("editor.window.hierarchy")))
             .position([viewport_width + 10.0, y_offset + 330.0], imgui::Condition::Always)
             .size([390.0, 365.0], imgui::Condition::Always)
             .collapsible(false)
             .bg_alpha(0.85)
             .build(|| {
                 // Solana teal header color
-                ui.text_colored([0.08, 0.95, 0.58, 1.0], "HIERARCHY");
+                ui.text_colored([0.08, 0.95, 0.58, 1.0], t("editor.hierarchy.header"));
                 ui.separator();
 
-                if self.map.objects.is_empty() {
-                    ui.text_colored([0.5, 0.5, 0.5, 1.0], "(No objects yet)");
-                    ui.text("Press Space/Click to place objects");
+                // Search (type, name, tag, color name, hex color or index) and category filter
+                ui.set_next_item_width(200.0);
+                ui.input_text("##hierarchy_search", &mut self.hierarchy_filter.query)
+                    .hint(t("editor.hierarchy.search_hint"))
+                    .build();
+                ui.same_line();
+                ui.set_next_item_width(130.0);
+                let mut category_index = HierarchyCategory::ALL
+                    .iter()
+                    .position(|c| *c == self.hierarchy_filter.category)
+                    .unwrap_or(0);
+                let category_labels: Vec<&str> = HierarchyCategory::ALL.iter().map(|c| c.label()).collect();
+                if ui.combo_simple_string("##hierarchy_category", &mut category_index, &category_labels) {
+                    self.hierarchy_filter.category = HierarchyCategory::ALL[category_index];
+                }
+
+                let visible: Vec<usize> = self.map.objects
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, obj)| self.hierarchy_filter.matches(*i, obj, self.map.label(*i)))
+                    .map(|(i, _)| i)
+                    .collect();
+
+                // Bulk selection
+                let selected_type = self.selected_object
+                    .and_then(|i| self.map.objects.get(i))
+                    .map(|obj| obj.model_type);
+                if let Some(model_type) = selected_type {
+                    if ui.button(tr!("editor.hierarchy.select_all_type", model = model_type.label())) {
+                        let same_type: Vec<usize> = self.map.objects
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, obj)| obj.model_type == model_type)
+                            .map(|(i, _)| i)
+                            .collect();
+                        let count = same_type.len();
+                        self.select_group(same_type);
+                        self.set_status(&tr!("editor.status.selected_all_type", count = count, model = model_type.label()));
+                    }
+                    ui.same_line();
+                }
+                if self.hierarchy_filter.is_active() && !visible.is_empty() && ui.button(t("editor.hierarchy.select_shown")) {
+                    let count = visible.len();
+                    self.select_group(visible.clone());
+                    self.set_status(&tr!("editor.status.selected_many", count = count));
+                }
+                if self.hierarchy_filter.is_active() {
+                    ui.text_colored([0.7, 0.7, 0.7, 1.0], tr!("editor.hierarchy.showing", shown = visible.len(), total = self.map.objects.len()));
+                }
+                ui.separator();
+
+                if self.map.objects.is_empty() {
+                    ui.text_colored([0.5, 0.5, 0.5, 1.0], t("editor.panel.no_objects"));
+                    ui.text(t("editor.hierarchy.place_hint"));
+                } else if visible.is_empty() {
+                    ui.text_colored([0.5, 0.5, 0.5, 1.0], t("editor.hierarchy.no_match"));
                 } else {
                     let mut new_selection = None;
 
-                    for (i, obj) in self.map.objects.iter().enumerate() {
-                        let is_selected = self.selected_object == Some(i);
+                    for &i in &visible {
+                        let obj = &self.map.objects[i];
+                        let is_selected = self.selected_object == Some(i) || self.multi_selection.contains(&i);
 
                         let _header_token = if is_selected {
                             Some(ui.push_style_color(imgui::StyleColor::Header, [0.3, 0.6, 0.8, 0.6]))
@@ -1447,7 +2087,10 @@ impl MapBuilder {
                             None
                         };
 
-                        let label = format!("[{}] {:?}##obj{}", i, obj.model_type, i);
+                        let label = match self.map.object_name(i) {
+                            Some(name) => format!("[{}] {} ({})##obj{}", i, name, obj.model_type.label(), i),
+                            None => format!("[{}] {} ({})##obj{}", i, obj.model_type.label(), color_name(obj.get_color()), i),
+                        };
 
                         if ui.selectable_config(&label)
                             .selected(is_selected)
@@ -1458,9 +2101,9 @@ impl MapBuilder {
                     }
 
                     if let Some(i) = new_selection {
-                        self.selected_object = Some(i);
+                        self.select_single(i);
                         self.mode = EditorMode::Selecting;
-                        self.set_status(&format!("Selected object {}", i));
+                        self.set_status(&tr!("editor.status.selected", index = i));
                     }
                 }
             });
@@ -1479,26 +2122,64 @@ impl MapBuilder {
 
         // Upload to Solana Window
         if self.show_upload_popup {
-            ui.window("Upload to Solana")
+            // Owned map IDs for the update target list
+            #[cfg(target_os = "emscripten")]
+            self.check_user_map_ids();
+
+            ui.window(&format!("{}###upload", t("editor.window.upload")))
                 .position([400.0, 200.0], imgui::Condition::Appearing)
                 .size([400.0, 300.0], imgui::Condition::Always)
                 .collapsible(false)
                 .build(|| {
-                    ui.text("Upload Map to Solana Blockchain");
+                    ui.text(t("editor.upload.header"));
                     ui.separator();
 
-                    ui.text("Map ID (unique identifier):");
-                    ui.input_text("##mapid", &mut self.upload_map_id).build();
+                    if ui.checkbox(&format!("{}##update_existing", t("editor.upload.update_existing")), &mut self.upload_update_existing) && self.upload_update_existing {
+                        self.upload_map_id = self.published_map_id.clone().unwrap_or_default();
+                    }
+
+                    if self.upload_update_existing {
+                        ui.text(t("editor.upload.update_target"));
+                        let mut owned = self.user_map_ids.clone();
+                        if let Some(id) = &self.published_map_id {
+                            if !owned.contains(id) {
+                                owned.insert(0, id.clone());
+                            }
+                        }
+                        if owned.is_empty() {
+                            ui.text_disabled(t("editor.upload.no_maps"));
+                        } else {
+                            let mut index = owned.iter().position(|id| *id == self.upload_map_id).unwrap_or(0);
+                            ui.combo_simple_string("##update_map_id", &mut index, &owned);
+                            self.upload_map_id = owned[index].clone();
+                        }
+                        ui.same_line();
+                        if ui.button(&format!("{}##update_maps", t("editor.upload.refresh"))) {
+                            self.request_user_maps();
+                        }
+
+                        ui.text_wrapped(t("editor.upload.update_info"));
+                    } else {
+                        ui.text(t("editor.upload.map_id"));
+                        ui.input_text("##mapid", &mut self.upload_map_id).build();
 
-                    ui.text("Map Name:");
-                    ui.input_text("##mapname", &mut self.upload_map_name).build();
+                        ui.text(t("editor.upload.map_name"));
+                        ui.input_text("##mapname", &mut self.upload_map_name).build();
 
-                    ui.text("Description:");
-                    ui.input_text_multiline("##mapdesc", &mut self.upload_map_description, [350.0, 80.0]).build();
+                        ui.text(t("editor.upload.description"));
+                        ui.input_text_multiline("##mapdesc", &mut self.upload_map_description, [350.0, 80.0]).build();
+                    }
 
                     ui.separator();
 
-                    if ui.button("Upload") {
+                    if self.upload_update_existing {
+                        if self.upload_map_id.is_empty() {
+                            ui.text_disabled(t("editor.upload.update"));
+                        } else if ui.button(&format!("{}##update", t("editor.upload.update"))) {
+                            self.update_map_on_solana();
+                            self.show_upload_popup = false;
+                        }
+                    } else if ui.button(t("editor.upload.upload")) {
                         // Call JavaScript to upload map
                         self.upload_map_to_solana();
                         self.show_upload_popup = false;
@@ -1506,7 +2187,7 @@ impl MapBuilder {
 
                     ui.same_line();
 
-                    if ui.button("Cancel") {
+                    if ui.button(&format!("{}##upload", t("common.cancel"))) {
                         self.show_upload_popup = false;
                     }
                 });
@@ -1518,43 +2199,44 @@ impl MapBuilder {
             #[cfg(target_os = "emscripten")]
             self.check_user_map_ids();
 
-            ui.window("My Maps")
+            ui.window(&format!("{}###my_maps", t("editor.window.my_maps")))
                 .position([400.0, 100.0], imgui::Condition::FirstUseEver)
                 .size([500.0, 400.0], imgui::Condition::FirstUseEver)
                 .build(|| {
                     // Solana teal header color
-                    ui.text_colored([0.08, 0.95, 0.58, 1.0], "MY MAPS");
+                    ui.text_colored([0.08, 0.95, 0.58, 1.0], t("editor.my_maps.header"));
                     ui.separator();
 
-                    ui.text("Your maps stored on Solana:");
+                    ui.text(t("editor.my_maps.intro"));
                     ui.separator();
 
                     // Request user maps from JavaScript
                     #[cfg(target_os = "emscripten")]
                     {
-                        if ui.button("Refresh Maps") {
+                        if ui.button(&format!("{}##my_maps", t("editor.my_maps.refresh"))) {
                             self.request_user_maps();
                         }
 
                         ui.same_line();
-                        ui.text_colored([0.7, 0.7, 0.7, 1.0], &format!("({} maps)", self.user_map_ids.len()));
+                        ui.text_colored([0.7, 0.7, 0.7, 1.0], &tr!("editor.my_maps.count", count = self.user_map_ids.len()));
                     }
 
                     #[cfg(not(target_os = "emscripten"))]
                     {
-                        ui.text_colored([1.0, 0.5, 0.0, 1.0], "Solana features only available in browser");
+                        ui.text_colored([1.0, 0.5, 0.0, 1.0], t("editor.status.solana_browser_only"));
                     }
 
                     ui.separator();
 
                     // Display list of user's maps
                     if self.user_map_ids.is_empty() {
-                        ui.text_colored([0.7, 0.7, 0.7, 1.0], "No maps found. Create one to get started!");
+                        ui.text_colored([0.7, 0.7, 0.7, 1.0], t("editor.my_maps.empty"));
                     } else {
-                        ui.text("Click a map to load it:");
+                        ui.text(t("editor.my_maps.click_to_load"));
                         ui.separator();
 
                         let mut map_to_load: Option<String> = None;
+                        let mut map_to_delete: Option<String> = None;
 
                         for (i, map_id) in self.user_map_ids.iter().enumerate() {
                             // Display map ID
@@ -1562,26 +2244,187 @@ impl MapBuilder {
                             ui.same_line();
 
                             // Load button with unique ID
-                            let button_label = format!("Load##{}", i);
+                            let button_label = format!("{}##{}", t("common.load"), i);
                             if ui.button(&button_label) {
                                 map_to_load = Some(map_id.clone());
                             }
+
+                            ui.same_line();
+                            if ui.button(format!("{}##{}", t("editor.my_maps.delete"), i)) {
+                                map_to_delete = Some(map_id.clone());
+                            }
                         }
 
                         // Load map after iteration to avoid borrow issues
                         if let Some(map_id) = map_to_load {
                             self.load_map_from_solana(&map_id);
                         }
+                        if map_to_delete.is_some() {
+                            self.pending_map_delete = map_to_delete;
+                        }
                     }
 
                     ui.separator();
 
-                    if ui.button("Close") {
+                    if ui.button(&format!("{}##my_maps", t("common.close"))) {
                         self.show_my_maps = false;
                     }
                 });
         }
 
+        // Local Map Library Window
+        if self.show_library {
+            let mut opened = true;
+            let mut to_load: Option<String> = None;
+            let mut to_delete: Option<String> = None;
+            let mut save_requested = false;
+
+            ui.window(&format!("{}###library", t("editor.window.library")))
+                .position([400.0, 120.0], imgui::Condition::FirstUseEver)
+                .size([460.0, 380.0], imgui::Condition::FirstUseEver)
+                .opened(&mut opened)
+                .build(|| {
+                    ui.text_colored([0.60, 0.27, 1.0, 1.0], t("editor.library.save_current"));
+                    ui.input_text("##library_save_name", &mut self.library_save_name).build();
+                    ui.same_line();
+                    if ui.button(&format!("{}##library", t("editor.library.save"))) {
+                        save_requested = true;
+                    }
+                    if self.library_entries.iter().any(|e| e.name == library::slot_name(&self.library_save_name)) {
+                        ui.text_colored([1.0, 0.8, 0.2, 1.0], t("editor.library.overwrites"));
+                    }
+
+                    ui.separator();
+                    #[cfg(not(target_os = "emscripten"))]
+                    ui.text_disabled(tr!("editor.library.saved_in_dir", dir = library::LIBRARY_DIR, extension = MAP_FILE_EXTENSION));
+                    #[cfg(target_os = "emscripten")]
+                    ui.text_disabled(t("editor.library.saved_in_browser"));
+
+                    if self.library_entries.is_empty() {
+                        ui.text_colored([0.7, 0.7, 0.7, 1.0], t("editor.library.empty"));
+                    }
+
+                    let now = library::unix_now();
+                    for (i, entry) in self.library_entries.iter().enumerate() {
+                        ui.separator();
+                        ui.text(&entry.name);
+                        ui.text_disabled(tr!(
+                            "editor.library.entry",
+                            count = entry.object_count,
+                            size = format!("{:.1}", entry.bytes as f32 / 1024.0),
+                            age = library::format_age(entry.modified, now),
+                        ));
+                        ui.same_line();
+                        if ui.small_button(format!("{}##library{}", t("common.load"), i)) {
+                            to_load = Some(entry.name.clone());
+                        }
+                        ui.same_line();
+                        if ui.small_button(format!("{}##library{}", t("common.delete"), i)) {
+                            to_delete = Some(entry.name.clone());
+                        }
+                    }
+                });
+
+            if save_requested {
+                self.save_to_library(library::slot_name(&self.library_save_name));
+            }
+            if let Some(name) = to_load {
+                self.load_from_library(&name);
+            }
+            if let Some(name) = to_delete {
+                self.delete_from_library(&name);
+            }
+            if !opened {
+                self.show_library = false;
+            }
+        }
+
+        // Collaborative Editing Window
+        if self.show_collab {
+            let mut opened = true;
+            let mut host_requested = false;
+            let mut join_requested = false;
+            let mut leave_requested = false;
+
+            ui.window(&format!("{}###collab", t("editor.window.collab")))
+                .position([400.0, 160.0], imgui::Condition::FirstUseEver)
+                .size([360.0, 300.0], imgui::Condition::FirstUseEver)
+                .opened(&mut opened)
+                .build(|| {
+                    match &self.collab {
+                        None => {
+                            ui.text(t("editor.collab.your_name"));
+                            ui.input_text("##collab_name", &mut self.collab_name).build();
+                            ui.separator();
+                            if ui.button(t("editor.collab.host")) {
+                                host_requested = true;
+                            }
+                            ui.text_disabled(t("editor.collab.host_info"));
+                            ui.separator();
+                            ui.input_text("##collab_code", &mut self.collab_join_code).build();
+                            ui.same_line();
+                            if ui.button(t("editor.collab.join")) {
+                                join_requested = true;
+                            }
+                        }
+                        Some(session) => {
+                            ui.text_colored([0.08, 0.95, 0.58, 1.0], tr!("editor.collab.code", code = session.code));
+                            ui.text_disabled(if session.is_host() { t("editor.collab.hosting") } else { t("editor.collab.guest") });
+                            if !session.is_synced() {
+                                ui.text_colored([1.0, 0.8, 0.2, 1.0], t("editor.collab.waiting"));
+                            }
+                            ui.separator();
+                            ui.text(tr!("editor.collab.collaborators", count = session.collaborators.len()));
+                            for (id, collaborator) in &session.collaborators {
+                                let [r, g, b] = color_to_rgb(collab_color(id));
+                                ui.text_colored([r, g, b, 1.0], &collaborator.name);
+                            }
+                            ui.separator();
+                            if ui.button(t("editor.collab.leave")) {
+                                leave_requested = true;
+                            }
+                        }
+                    }
+                });
+
+            if host_requested {
+                self.host_collab();
+            }
+            if join_requested {
+                self.join_collab();
+            }
+            if leave_requested {
+                self.leave_collab();
+            }
+            if !opened {
+                self.show_collab = false;
+            }
+        }
+
+        // Delete confirmation
+        if let Some(map_id) = self.pending_map_delete.clone() {
+            ui.window(&format!("{}###my_map_delete", t("editor.window.confirm")))
+                .position([450.0, 250.0], imgui::Condition::Appearing)
+                .size([380.0, 160.0], imgui::Condition::Always)
+                .collapsible(false)
+                .build(|| {
+                    ui.text(tr!("editor.confirm.question", action = t("editor.my_maps.delete"), id = map_id));
+                    ui.separator();
+                    ui.text_colored([1.0, 0.4, 0.4, 1.0], t("editor.confirm.delete_refund"));
+                    ui.text_colored([1.0, 0.4, 0.4, 1.0], t("editor.confirm.delete_warning"));
+                    ui.dummy([0.0, 5.0]);
+
+                    if ui.button(format!("{}##confirm", t("editor.my_maps.delete"))) {
+                        self.delete_solana_map(&map_id);
+                        self.pending_map_delete = None;
+                    }
+                    ui.same_line();
+                    if ui.button(&format!("{}##my_map_delete", t("common.cancel"))) {
+                        self.pending_map_delete = None;
+                    }
+                });
+        }
+
         // Update mouse_over_ui after drawing all UI
         mouse_over_ui = mouse_over_ui || ui.is_any_item_hovered() || ui.is_window_hovered();
 
@@ -1623,14 +2466,14 @@ impl MapBuilder {
         if button_clicked {
             self.current_model_type = model_type;
             self.mode = EditorMode::Placing;
-            self.set_status(&format!("Selected {:?} for placing", model_type));
+            self.set_status(&tr!("editor.status.selected_for_placing", model = model_type.label()));
         }
 
         // Show tooltip on hover
         if ui.is_item_hovered() {
             ui.tooltip(|| {
-                ui.text(format!("Click to select or drag to viewport"));
-                ui.text(format!("Model: {:?}", model_type));
+                ui.text(t("editor.tools.drag_hint"));
+                ui.text(tr!("editor.status.model", model = model_type.label()));
             });
         }
 
@@ -1707,111 +2550,149 @@ impl MapBuilder {
                     emscripten_run_script(c_str.as_ptr());
                 }
 
-                self.set_status("Uploading map to Solana...");
+                self.set_status(t("editor.status.uploading"));
             }
             Err(e) => {
-                self.set_status(&format!("Failed to serialize map: {}", e));
+                self.set_status(&tr!("editor.status.serialize_failed", error = e));
             }
         }
     }
 
     #[cfg(not(target_os = "emscripten"))]
     fn upload_map_to_solana(&mut self) {
-        self.set_status("Solana upload only available in browser");
+        self.set_status(t("editor.status.upload_browser_only"));
     }
 
-    /// Request user's maps from Solana
+    /// Republish the map over an existing on-chain map the wallet owns
     #[cfg(target_os = "emscripten")]
-    fn request_user_maps(&mut self) {
+    fn update_map_on_solana(&mut self) {
         use std::ffi::CString;
+        use base64::{Engine as _, engine::general_purpose};
 
         extern "C" {
             pub fn emscripten_run_script(script: *const i8);
         }
 
-        let js_code = r#"
-        (async function() {
-            try {
-                // Check if Solana bridge is available
-                if (!window.solanaMapBridge) {
-                    throw new Error('Solana bridge not initialized. Please connect your wallet first.');
-                }
+        match self.map.to_borsh_bytes() {
+            Ok(bytes) => {
+                let base64_string = general_purpose::STANDARD.encode(&bytes);
 
-                const userMaps = await window.solanaMapBridge.getUserMaps();
-                console.log('User maps:', userMaps);
+                let js_code = format!(
+                    r#"
+                    (async function() {{
+                        try {{
+                            if (!window.solanaMapBridge) {{
+                                throw new Error('Solana bridge not initialized. Please connect your wallet first.');
+                            }}
 
-                // Store map IDs as JSON string for Rust to access
-                if (userMaps && userMaps.mapIds) {
-                    Module.userMapIdsJson = JSON.stringify(userMaps.mapIds);
-                    alert('Found ' + userMaps.mapIds.length + ' maps!');
-                } else {
-                    Module.userMapIdsJson = '[]';
-                    alert('No maps found');
+                            const mapId = '{}';
+                            const mapData = Uint8Array.from(atob('{}'), c => c.charCodeAt(0));
+
+                            const result = await window.solanaMapBridge.updateMapData(mapId, mapData);
+                            if (!result) {{
+                                alert('Failed to update map. Check console for details.');
+                                return;
+                            }}
+
+                            // Lobbies that were set up before this update will load the new version
+                            const lobbies = await window.solanaMapBridge.getLobbiesUsingMap(mapId);
+                            let message = 'Map updated!\nTransaction: ' + result.transaction;
+                            if (lobbies.length > 0) {{
+                                message += '\n\nWarning: ' + lobbies.length + ' lobby(s) waiting to start are set up with the previous version ('
+                                    + lobbies.map(l => l.lobbyName).join(', ') + '). They will load the new one.';
+                            }}
+                            alert(message);
+                        }} catch (error) {{
+                            console.error('Error updating map:', error);
+                            alert('Error: ' + error.message);
+                        }}
+                    }})();
+                    "#,
+                    self.upload_map_id.replace("'", "\\'"),
+                    base64_string
+                );
+
+                let c_str = CString::new(js_code).unwrap();
+                unsafe {
+                    emscripten_run_script(c_str.as_ptr());
                 }
-            } catch (error) {
-                console.error('Error fetching user maps:', error);
-                Module.userMapIdsJson = '[]';
-                alert('Error: ' + error.message);
-            }
-        })();
-        "#;
 
-        let c_str = CString::new(js_code).unwrap();
-        unsafe {
-            emscripten_run_script(c_str.as_ptr());
+                self.published_map_id = Some(self.upload_map_id.clone());
+                self.set_status(&tr!("editor.status.updating", id = self.upload_map_id));
+            }
+            Err(e) => {
+                self.set_status(&tr!("editor.status.serialize_failed", error = e));
+            }
         }
-
-        self.set_status("Fetching maps from Solana...");
     }
 
     #[cfg(not(target_os = "emscripten"))]
-    fn request_user_maps(&mut self) {
-        self.set_status("Solana features only available in browser");
+    fn update_map_on_solana(&mut self) {
+        self.set_status(t("editor.status.upload_browser_only"));
     }
 
-    /// Check if map IDs have been loaded from JavaScript
-    #[cfg(target_os = "emscripten")]
-    fn check_user_map_ids(&mut self) {
-        use std::ffi::CString;
+    /// Show the Library window with a fresh listing
+    pub fn open_library(&mut self) {
+        self.show_library = true;
+        self.library_save_name = library::slot_name(&self.map.name);
+        self.refresh_library();
+    }
 
-        extern "C" {
-            pub fn emscripten_run_script_string(script: *const i8) -> *const i8;
-            pub fn emscripten_run_script(script: *const i8);
-        }
+    /// F5: save the map to the library under its own name
+    pub fn quick_save(&mut self) {
+        self.save_to_library(library::slot_name(&self.map.name));
+    }
 
-        // Check if map IDs JSON exists
-        let js_check = CString::new("typeof Module.userMapIdsJson !== 'undefined' ? Module.userMapIdsJson : ''").unwrap();
+    #[cfg(not(target_os = "emscripten"))]
+    fn refresh_library(&mut self) {
+        self.library_entries = library::list(std::path::Path::new(library::LIBRARY_DIR));
+    }
 
-        unsafe {
-            let result_ptr = emscripten_run_script_string(js_check.as_ptr());
-            if result_ptr.is_null() {
-                return;
+    #[cfg(not(target_os = "emscripten"))]
+    fn save_to_library(&mut self, name: String) {
+        match library::save(std::path::Path::new(library::LIBRARY_DIR), &name, &self.map) {
+            Ok(entry) => {
+                self.set_status(&tr!("editor.status.library_saved", name = entry.name, count = entry.object_count));
+                self.refresh_library();
             }
+            Err(e) => self.set_status(&tr!("editor.status.save_failed", error = e)),
+        }
+    }
 
-            let c_str = std::ffi::CStr::from_ptr(result_ptr);
-            if let Ok(json_str) = c_str.to_str() {
-                if !json_str.is_empty() {
-                    // Parse JSON array
-                    if let Ok(map_ids) = serde_json::from_str::<Vec<String>>(json_str) {
-                        self.user_map_ids = map_ids;
+    #[cfg(not(target_os = "emscripten"))]
+    fn load_from_library(&mut self, name: &str) {
+        match library::load(std::path::Path::new(library::LIBRARY_DIR), name) {
+            Ok(map) => self.apply_library_map(name, map),
+            Err(e) => self.set_status(&tr!("editor.status.load_failed", error = e)),
+        }
+    }
 
-                        // Clear the JavaScript variable so we don't keep re-parsing
-                        let clear_js = CString::new("delete Module.userMapIdsJson;").unwrap();
-                        emscripten_run_script(clear_js.as_ptr());
-                    }
-                }
+    #[cfg(not(target_os = "emscripten"))]
+    fn delete_from_library(&mut self, name: &str) {
+        match library::delete(std::path::Path::new(library::LIBRARY_DIR), name) {
+            Ok(()) => {
+                self.set_status(&tr!("editor.status.library_deleted", name = name));
+                self.refresh_library();
             }
+            Err(e) => self.set_status(&tr!("editor.status.delete_failed", error = e)),
         }
     }
 
-    #[cfg(not(target_os = "emscripten"))]
-    fn check_user_map_ids(&mut self) {
-        // No-op on non-Emscripten platforms
+    /// Replace the edited map with one from the library
+    fn apply_library_map(&mut self, name: &str, map: Map) {
+        self.map = map;
+        self.published_map_id = None;
+        self.clear_selection();
+        self.share_replaced_map();
+        self.mode = EditorMode::Placing;
+        self.show_library = false;
+        self.set_status(&tr!("editor.status.library_loaded", name = name, count = self.map.objects.len()));
     }
 
-    /// Load a map from Solana by ID
+    /// Run a library call in the browser; `script` may await `window.mapLibrary`,
+    /// and the listing is refreshed into `Module.mapLibraryList` afterwards
     #[cfg(target_os = "emscripten")]
-    fn load_map_from_solana(&mut self, map_id: &str) {
+    fn run_library_script(&mut self, script: &str) {
         use std::ffi::CString;
 
         extern "C" {
@@ -1822,59 +2703,72 @@ impl MapBuilder {
             r#"
             (async function() {{
                 try {{
-                    if (!window.solanaMapBridge) {{
-                        throw new Error('Solana bridge not initialized.');
-                    }}
-
-                    const mapId = '{}';
-                    console.log('Loading map:', mapId);
-
-                    // Get map data from Solana in Borsh format (for Rust compatibility)
-                    const mapData = await window.solanaMapBridge.getMapData(mapId, 'borsh');
-
-                    if (!mapData) {{
-                        throw new Error('Map data not found');
-                    }}
-
-                    // mapData is already a Uint8Array in Borsh format
-                    // Convert to base64 for passing to Rust
-                    const bytes = mapData;
-                    let binary = '';
-                    for (let i = 0; i < bytes.length; i++) {{
-                        binary += String.fromCharCode(bytes[i]);
+                    if (!window.mapLibrary) {{
+                        throw new Error('Map library not available');
                     }}
-                    const base64Data = btoa(binary);
-
-                    // Store for Rust to access
-                    Module.loadedMapData = base64Data;
-                    Module.loadedMapId = mapId;
-
-                    console.log('Map data loaded successfully');
+                    {}
+                    Module.mapLibraryList = JSON.stringify(await window.mapLibrary.listMaps());
                 }} catch (error) {{
-                    console.error('Error loading map:', error);
-                    alert('Error loading map: ' + error.message);
+                    console.error('Map library error:', error);
+                    alert('Map library error: ' + error.message);
                 }}
             }})();
             "#,
-            map_id.replace("'", "\\'")
+            script
         );
 
         let c_str = CString::new(js_code).unwrap();
         unsafe {
             emscripten_run_script(c_str.as_ptr());
         }
+    }
 
-        self.set_status(&format!("Loading map {}...", map_id));
+    #[cfg(target_os = "emscripten")]
+    fn refresh_library(&mut self) {
+        self.run_library_script("");
     }
 
-    #[cfg(not(target_os = "emscripten"))]
-    fn load_map_from_solana(&mut self, _map_id: &str) {
-        self.set_status("Solana features only available in browser");
+    #[cfg(target_os = "emscripten")]
+    fn save_to_library(&mut self, name: String) {
+        use base64::{Engine as _, engine::general_purpose};
+
+        match self.map.to_borsh_bytes() {
+            Ok(bytes) => {
+                self.run_library_script(&format!(
+                    "await window.mapLibrary.saveMap('{}', Uint8Array.from(atob('{}'), c => c.charCodeAt(0)), {});",
+                    name,
+                    general_purpose::STANDARD.encode(&bytes),
+                    self.map.objects.len()
+                ));
+                self.set_status(&tr!("editor.status.library_saved", name = name, count = self.map.objects.len()));
+            }
+            Err(e) => self.set_status(&tr!("editor.status.serialize_failed", error = e)),
+        }
     }
 
-    /// Check if map data has been loaded from Solana and apply it
     #[cfg(target_os = "emscripten")]
-    fn check_loaded_map_from_solana(&mut self) {
+    fn load_from_library(&mut self, name: &str) {
+        self.run_library_script(&format!(
+            r#"const bytes = await window.mapLibrary.loadMap('{0}');
+                    if (!bytes) throw new Error('No saved map named {0}');
+                    let binary = '';
+                    for (let i = 0; i < bytes.length; i++) binary += String.fromCharCode(bytes[i]);
+                    Module.libraryMapName = '{0}';
+                    Module.libraryMapData = btoa(binary);"#,
+            name
+        ));
+        self.set_status(&tr!("editor.status.library_loading", name = name));
+    }
+
+    #[cfg(target_os = "emscripten")]
+    fn delete_from_library(&mut self, name: &str) {
+        self.run_library_script(&format!("await window.mapLibrary.deleteMap('{}');", name));
+        self.set_status(&tr!("editor.status.library_deleted", name = name));
+    }
+
+    /// Pick up library listings and loaded maps from JavaScript
+    #[cfg(target_os = "emscripten")]
+    fn check_library(&mut self) {
         use std::ffi::CString;
         use base64::{Engine as _, engine::general_purpose};
 
@@ -1883,23 +2777,491 @@ impl MapBuilder {
             pub fn emscripten_run_script(script: *const i8);
         }
 
-        // Check if map data exists
-        let js_check = CString::new("typeof Module.loadedMapData !== 'undefined' ? Module.loadedMapData : ''").unwrap();
-
         unsafe {
-            let result_ptr = emscripten_run_script_string(js_check.as_ptr());
-            if result_ptr.is_null() {
+            let js_list = CString::new("typeof Module.mapLibraryList !== 'undefined' ? Module.mapLibraryList : ''").unwrap();
+            let list_ptr = emscripten_run_script_string(js_list.as_ptr());
+            if !list_ptr.is_null() {
+                let json_str = std::ffi::CStr::from_ptr(list_ptr).to_str().unwrap_or("");
+                if !json_str.is_empty() {
+                    if let Ok(mut entries) = serde_json::from_str::<Vec<LibraryEntry>>(json_str) {
+                        library::sort_entries(&mut entries);
+                        self.library_entries = entries;
+                    }
+                    let clear_js = CString::new("delete Module.mapLibraryList;").unwrap();
+                    emscripten_run_script(clear_js.as_ptr());
+                }
+            }
+
+            let js_data = CString::new("typeof Module.libraryMapData !== 'undefined' ? Module.libraryMapName + ':' + Module.libraryMapData : ''").unwrap();
+            let data_ptr = emscripten_run_script_string(js_data.as_ptr());
+            if data_ptr.is_null() {
                 return;
             }
+            let payload = std::ffi::CStr::from_ptr(data_ptr).to_str().unwrap_or("").to_string();
+            let Some((name, base64_str)) = payload.split_once(':') else {
+                return;
+            };
 
-            let c_str = std::ffi::CStr::from_ptr(result_ptr);
-            if let Ok(base64_str) = c_str.to_str() {
-                if !base64_str.is_empty() {
-                    // Decode base64
-                    if let Ok(bytes) = general_purpose::STANDARD.decode(base64_str) {
-                        // Parse map from bytes (try Borsh first, fall back to JSON)
-                        let map_result = Map::from_borsh_bytes(&bytes)
-                            .or_else(|_| Map::from_json_bytes(&bytes).map_err(|e| format!("{}", e)));
+            let clear_js = CString::new("delete Module.libraryMapData; delete Module.libraryMapName;").unwrap();
+            emscripten_run_script(clear_js.as_ptr());
+
+            match general_purpose::STANDARD.decode(base64_str).map_err(|e| e.to_string()).and_then(|bytes| library::parse_map(&bytes)) {
+                Ok(map) => self.apply_library_map(name, map),
+                Err(e) => self.set_status(&tr!("editor.status.load_failed", error = e)),
+            }
+        }
+    }
+
+    /// Start sharing the current map; the session code is shown in the Collaborate window
+    #[cfg(target_os = "emscripten")]
+    fn host_collab(&mut self) {
+        let name = serde_json::to_string(&self.collab_display_name()).unwrap_or_default();
+        let reply = run_bridge_script(&format!("window.gameBridge?.hostEditSession ? window.gameBridge.hostEditSession({}) : ''", name));
+        let Some((code, peer_id)) = reply.split_once(':') else {
+            self.set_status(t("editor.status.collab_unavailable"));
+            return;
+        };
+        self.collab = Some(CollabSession::host(code.to_string(), peer_id.to_string(), &self.map));
+        self.collab_sync_timer = 0.0;
+        self.set_status(&tr!("editor.status.collab_hosting", code = code));
+    }
+
+    /// Join the session whose code was typed in; the host answers with its map
+    #[cfg(target_os = "emscripten")]
+    fn join_collab(&mut self) {
+        let code = self.collab_join_code.trim().to_uppercase();
+        if code.is_empty() {
+            return;
+        }
+        let args = serde_json::to_string(&(code.as_str(), self.collab_display_name())).unwrap_or_default();
+        let peer_id = run_bridge_script(&format!(
+            "window.gameBridge?.joinEditSession ? window.gameBridge.joinEditSession(...{}) : ''",
+            args
+        ));
+        if peer_id.is_empty() {
+            self.set_status(t("editor.status.collab_unavailable"));
+            return;
+        }
+        self.collab = Some(CollabSession::join(code.clone(), peer_id));
+        self.collab_sync_timer = 0.0;
+        self.send_collab_message(&EditMessage::Join);
+        self.set_status(&tr!("editor.status.collab_joining", code = code));
+    }
+
+    /// Close the session (the map stays as it is)
+    #[cfg(target_os = "emscripten")]
+    fn leave_collab(&mut self) {
+        if self.collab.is_none() {
+            return;
+        }
+        // Flush the last edits so nothing made just before leaving is lost
+        let pending = self.collab.as_mut().and_then(|session| session.take_local_edits(&self.map));
+        if let Some(message) = pending {
+            self.send_collab_message(&message);
+        }
+        self.send_collab_message(&EditMessage::Leave);
+        run_bridge_script("window.gameBridge?.leaveEditSession && window.gameBridge.leaveEditSession(), ''");
+        self.collab = None;
+        self.set_status(t("editor.status.collab_left"));
+    }
+
+    #[cfg(not(target_os = "emscripten"))]
+    fn host_collab(&mut self) {
+        self.set_status(t("editor.status.collab_unavailable"));
+    }
+
+    #[cfg(not(target_os = "emscripten"))]
+    fn join_collab(&mut self) {
+        self.set_status(t("editor.status.collab_unavailable"));
+    }
+
+    #[cfg(not(target_os = "emscripten"))]
+    fn leave_collab(&mut self) {
+        self.collab = None;
+    }
+
+    fn collab_display_name(&self) -> String {
+        let name = self.collab_name.trim();
+        if name.is_empty() { t("editor.collab.default_name").to_string() } else { name.to_string() }
+    }
+
+    /// Post a message on the session channel (the bridge stamps our id and name)
+    #[cfg(target_os = "emscripten")]
+    fn send_collab_message(&self, message: &EditMessage) {
+        let Ok(json) = serde_json::to_string(message) else {
+            return;
+        };
+        // Encoding the JSON again gives a valid JS string literal
+        let literal = serde_json::to_string(&json).unwrap_or_default();
+        run_bridge_script(&format!("window.gameBridge?.sendEditMessage && window.gameBridge.sendEditMessage({}), ''", literal));
+    }
+
+    #[cfg(not(target_os = "emscripten"))]
+    fn send_collab_message(&self, _message: &EditMessage) {}
+
+    /// Host: a load / import / new map replaced everything, so send the whole map instead of a diff
+    fn share_replaced_map(&mut self) {
+        let snapshot = match self.collab.as_mut() {
+            Some(session) if session.is_host() => session.reset_shared(&self.map),
+            _ => None,
+        };
+        if let Some(message) = snapshot {
+            self.send_collab_message(&message);
+        }
+    }
+
+    /// Apply collaborators' edits, push ours and our cursor
+    #[cfg(target_os = "emscripten")]
+    fn update_collab(&mut self, rl: &RaylibHandle, delta: f32) {
+        use super::collab::{EditEnvelope, SYNC_INTERVAL};
+
+        let Some(mut session) = self.collab.take() else {
+            return;
+        };
+        let now = rl.get_time();
+        let was_synced = session.is_synced();
+        let mut outgoing = Vec::new();
+
+        let incoming = run_bridge_script("window.gameBridge?.getEditMessages ? window.gameBridge.getEditMessages() : '[]'");
+        let messages: Vec<serde_json::Value> = serde_json::from_str(&incoming).unwrap_or_default();
+        for envelope in messages.into_iter().filter_map(|value| serde_json::from_value::<EditEnvelope>(value).ok()) {
+            outgoing.extend(session.receive(envelope, &mut self.map, now));
+        }
+
+        self.collab_sync_timer -= delta;
+        if self.collab_sync_timer <= 0.0 {
+            self.collab_sync_timer = SYNC_INTERVAL;
+            outgoing.extend(session.take_local_edits(&self.map));
+            // Sent even when the mouse is still: it doubles as the presence beat
+            let cursor = self.mouse_world_point(rl);
+            outgoing.push(EditMessage::Cursor { position: [cursor.x, cursor.y, cursor.z] });
+            session.expire_collaborators(now);
+        }
+
+        for message in &outgoing {
+            self.send_collab_message(message);
+        }
+
+        // Remote edits shift and delete objects under our selection
+        let count = self.map.objects.len();
+        if self.selected_object.is_some_and(|index| index >= count) {
+            self.clear_selection();
+        }
+        self.multi_selection.retain(|&index| index < count);
+
+        if !was_synced && session.is_synced() {
+            self.clear_selection();
+            self.set_status(&tr!("editor.status.collab_joined", code = session.code, count = count));
+        }
+        if session.host_left {
+            run_bridge_script("window.gameBridge?.leaveEditSession && window.gameBridge.leaveEditSession(), ''");
+            self.set_status(t("editor.status.collab_host_left"));
+            return;
+        }
+        self.collab = Some(session);
+    }
+
+    /// Point under the mouse: the first object hit, else the ground, else the camera target
+    #[cfg(target_os = "emscripten")]
+    fn mouse_world_point(&self, rl: &RaylibHandle) -> Vector3 {
+        let ray = self.mouse_ray(rl.get_mouse_position());
+        if let Some((point, _)) = self.map.raycast(ray, WORLD_SIZE * 2.0) {
+            return point;
+        }
+        if ray.direction.y < 0.0 {
+            return ray.position + ray.direction * (-ray.position.y / ray.direction.y);
+        }
+        self.camera.target
+    }
+
+    /// Collaborators' cursors as colored markers
+    fn draw_collab_cursors(&self, d: &mut RaylibMode3D<RaylibDrawHandle>) {
+        let Some(session) = &self.collab else {
+            return;
+        };
+        for (id, collaborator) in &session.collaborators {
+            if let Some(cursor) = collaborator.cursor {
+                let color = collab_color(id);
+                d.draw_sphere(cursor, 0.25, color);
+                d.draw_line_3D(cursor, cursor + Vector3::new(0.0, 1.5, 0.0), color);
+            }
+        }
+    }
+
+    /// Collaborators' names above their cursors
+    fn draw_collab_names(&self, d: &mut RaylibDrawHandle) {
+        let Some(session) = &self.collab else {
+            return;
+        };
+        for (id, collaborator) in &session.collaborators {
+            if let Some(cursor) = collaborator.cursor {
+                let screen = d.get_world_to_screen(cursor + Vector3::new(0.0, 1.7, 0.0), self.camera);
+                let width = d.measure_text(&collaborator.name, 14);
+                d.draw_text(&collaborator.name, screen.x as i32 - width / 2, screen.y as i32 - 14, 14, collab_color(id));
+            }
+        }
+    }
+
+    /// Delete an on-chain map; the entry leaves `user_map_ids` once the transaction succeeds
+    #[cfg(target_os = "emscripten")]
+    fn delete_solana_map(&mut self, map_id: &str) {
+        use std::ffi::CString;
+
+        extern "C" {
+            pub fn emscripten_run_script(script: *const i8);
+        }
+
+        let js_code = format!(
+            r#"
+            (async function() {{
+                try {{
+                    if (!window.solanaMapBridge) {{
+                        throw new Error('Solana bridge not initialized. Please connect your wallet first.');
+                    }}
+
+                    const mapId = '{}';
+                    const result = await window.solanaMapBridge.deleteMap(mapId);
+                    if (result) {{
+                        Module.removedMapId = mapId;
+                    }} else {{
+                        alert('Failed to delete map. Check console for details.');
+                    }}
+                }} catch (error) {{
+                    console.error('Error removing map:', error);
+                    alert('Error: ' + error.message);
+                }}
+            }})();
+            "#,
+            map_id.replace("'", "\\'")
+        );
+
+        let c_str = CString::new(js_code).unwrap();
+        unsafe {
+            emscripten_run_script(c_str.as_ptr());
+        }
+
+        self.set_status(&tr!("editor.status.deleting_map", id = map_id));
+    }
+
+    #[cfg(not(target_os = "emscripten"))]
+    fn delete_solana_map(&mut self, _map_id: &str) {
+        self.set_status(t("editor.status.solana_browser_only"));
+    }
+
+    /// Check if a delete finished and drop the map from the list
+    #[cfg(target_os = "emscripten")]
+    fn check_removed_map(&mut self) {
+        use std::ffi::CString;
+
+        extern "C" {
+            pub fn emscripten_run_script_string(script: *const i8) -> *const i8;
+            pub fn emscripten_run_script(script: *const i8);
+        }
+
+        let js_check = CString::new("typeof Module.removedMapId !== 'undefined' ? Module.removedMapId : ''").unwrap();
+
+        unsafe {
+            let result_ptr = emscripten_run_script_string(js_check.as_ptr());
+            if result_ptr.is_null() {
+                return;
+            }
+
+            let map_id = std::ffi::CStr::from_ptr(result_ptr).to_str().unwrap_or("").to_string();
+            if map_id.is_empty() {
+                return;
+            }
+
+            self.user_map_ids.retain(|id| *id != map_id);
+            if self.published_map_id.as_deref() == Some(map_id.as_str()) {
+                self.published_map_id = None;
+            }
+            self.set_status(&tr!("editor.status.map_removed", id = map_id));
+
+            let clear_js = CString::new("delete Module.removedMapId;").unwrap();
+            emscripten_run_script(clear_js.as_ptr());
+        }
+    }
+
+    /// Request user's maps from Solana
+    #[cfg(target_os = "emscripten")]
+    fn request_user_maps(&mut self) {
+        use std::ffi::CString;
+
+        extern "C" {
+            pub fn emscripten_run_script(script: *const i8);
+        }
+
+        let js_code = r#"
+        (async function() {
+            try {
+                // Check if Solana bridge is available
+                if (!window.solanaMapBridge) {
+                    throw new Error('Solana bridge not initialized. Please connect your wallet first.');
+                }
+
+                const userMaps = await window.solanaMapBridge.getUserMaps();
+                console.log('User maps:', userMaps);
+
+                // Store map IDs as JSON string for Rust to access
+                if (userMaps && userMaps.mapIds) {
+                    Module.userMapIdsJson = JSON.stringify(userMaps.mapIds);
+                    alert('Found ' + userMaps.mapIds.length + ' maps!');
+                } else {
+                    Module.userMapIdsJson = '[]';
+                    alert('No maps found');
+                }
+            } catch (error) {
+                console.error('Error fetching user maps:', error);
+                Module.userMapIdsJson = '[]';
+                alert('Error: ' + error.message);
+            }
+        })();
+        "#;
+
+        let c_str = CString::new(js_code).unwrap();
+        unsafe {
+            emscripten_run_script(c_str.as_ptr());
+        }
+
+        self.set_status(t("editor.status.fetching_maps"));
+    }
+
+    #[cfg(not(target_os = "emscripten"))]
+    fn request_user_maps(&mut self) {
+        self.set_status(t("editor.status.solana_browser_only"));
+    }
+
+    /// Check if map IDs have been loaded from JavaScript
+    #[cfg(target_os = "emscripten")]
+    fn check_user_map_ids(&mut self) {
+        use std::ffi::CString;
+
+        extern "C" {
+            pub fn emscripten_run_script_string(script: *const i8) -> *const i8;
+            pub fn emscripten_run_script(script: *const i8);
+        }
+
+        // Check if map IDs JSON exists
+        let js_check = CString::new("typeof Module.userMapIdsJson !== 'undefined' ? Module.userMapIdsJson : ''").unwrap();
+
+        unsafe {
+            let result_ptr = emscripten_run_script_string(js_check.as_ptr());
+            if result_ptr.is_null() {
+                return;
+            }
+
+            let c_str = std::ffi::CStr::from_ptr(result_ptr);
+            if let Ok(json_str) = c_str.to_str() {
+                if !json_str.is_empty() {
+                    // Parse JSON array
+                    if let Ok(map_ids) = serde_json::from_str::<Vec<String>>(json_str) {
+                        self.user_map_ids = map_ids;
+
+                        // Clear the JavaScript variable so we don't keep re-parsing
+                        let clear_js = CString::new("delete Module.userMapIdsJson;").unwrap();
+                        emscripten_run_script(clear_js.as_ptr());
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "emscripten"))]
+    fn check_user_map_ids(&mut self) {
+        // No-op on non-Emscripten platforms
+    }
+
+    /// Load a map from Solana by ID
+    #[cfg(target_os = "emscripten")]
+    fn load_map_from_solana(&mut self, map_id: &str) {
+        use std::ffi::CString;
+
+        extern "C" {
+            pub fn emscripten_run_script(script: *const i8);
+        }
+
+        let js_code = format!(
+            r#"
+            (async function() {{
+                try {{
+                    if (!window.solanaMapBridge) {{
+                        throw new Error('Solana bridge not initialized.');
+                    }}
+
+                    const mapId = '{}';
+                    console.log('Loading map:', mapId);
+
+                    // Get map data from Solana in Borsh format (for Rust compatibility)
+                    const mapData = await window.solanaMapBridge.getMapData(mapId, 'borsh');
+
+                    if (!mapData) {{
+                        throw new Error('Map data not found');
+                    }}
+
+                    // mapData is already a Uint8Array in Borsh format
+                    // Convert to base64 for passing to Rust
+                    const bytes = mapData;
+                    let binary = '';
+                    for (let i = 0; i < bytes.length; i++) {{
+                        binary += String.fromCharCode(bytes[i]);
+                    }}
+                    const base64Data = btoa(binary);
+
+                    // Store for Rust to access
+                    Module.loadedMapData = base64Data;
+                    Module.loadedMapId = mapId;
+
+                    console.log('Map data loaded successfully');
+                }} catch (error) {{
+                    console.error('Error loading map:', error);
+                    alert('Error loading map: ' + error.message);
+                }}
+            }})();
+            "#,
+            map_id.replace("'", "\\'")
+        );
+
+        let c_str = CString::new(js_code).unwrap();
+        unsafe {
+            emscripten_run_script(c_str.as_ptr());
+        }
+
+        self.set_status(&tr!("editor.status.loading_map", id = map_id));
+    }
+
+    #[cfg(not(target_os = "emscripten"))]
+    fn load_map_from_solana(&mut self, _map_id: &str) {
+        self.set_status(t("editor.status.solana_browser_only"));
+    }
+
+    /// Check if map data has been loaded from Solana and apply it
+    #[cfg(target_os = "emscripten")]
+    fn check_loaded_map_from_solana(&mut self) {
+        use std::ffi::CString;
+        use base64::{Engine as _, engine::general_purpose};
+
+        extern "C" {
+            pub fn emscripten_run_script_string(script: *const i8) -> *const i8;
+            pub fn emscripten_run_script(script: *const i8);
+        }
+
+        // Check if map data exists
+        let js_check = CString::new("typeof Module.loadedMapData !== 'undefined' ? Module.loadedMapData : ''").unwrap();
+
+        unsafe {
+            let result_ptr = emscripten_run_script_string(js_check.as_ptr());
+            if result_ptr.is_null() {
+                return;
+            }
+
+            let c_str = std::ffi::CStr::from_ptr(result_ptr);
+            if let Ok(base64_str) = c_str.to_str() {
+                if !base64_str.is_empty() {
+                    // Decode base64
+                    if let Ok(bytes) = general_purpose::STANDARD.decode(base64_str) {
+                        // Parse map from bytes (try Borsh first, fall back to JSON)
+                        let map_result = Map::from_borsh_bytes(&bytes)
+                            .or_else(|_| Map::from_json_bytes(&bytes).map_err(|e| format!("{}", e)));
 
                         match map_result {
                             Ok(loaded_map) => {
@@ -1913,17 +3275,19 @@ impl MapBuilder {
                                 };
 
                                 self.map = loaded_map;
-                                self.selected_object = None;
+                                self.published_map_id = Some(map_id.clone());
+                                self.clear_selection();
+                                self.share_replaced_map();
                                 self.mode = EditorMode::Placing;
                                 self.show_my_maps = false; // Close the My Maps window
-                                self.set_status(&format!("Loaded map '{}' from Solana - Ready to edit!", map_id));
+                                self.set_status(&tr!("editor.status.loaded_from_solana", id = map_id));
 
                                 // Clear the JavaScript variables
                                 let clear_js = CString::new("delete Module.loadedMapData; delete Module.loadedMapId;").unwrap();
                                 emscripten_run_script(clear_js.as_ptr());
                             }
                             Err(e) => {
-                                self.set_status(&format!("Failed to parse map: {}", e));
+                                self.set_status(&tr!("editor.status.parse_failed", error = e));
 
                                 // Clear the JavaScript variables even on error
                                 let clear_js = CString::new("delete Module.loadedMapData; delete Module.loadedMapId;").unwrap();
@@ -1940,4 +3304,629 @@ impl MapBuilder {
     fn check_loaded_map_from_solana(&mut self) {
         // No-op on non-Emscripten platforms
     }
+
+    /// Heightmap import options and the image picker (Tools panel)
+    /// Action and parameters of the selected object's trigger, if it is one
+    fn draw_trigger_editor(&mut self, ui: &imgui::Ui, index: usize) {
+        let Some(trigger) = self.map.trigger(index).cloned() else {
+            return;
+        };
+        let (mut action, mut once) = (trigger.action, trigger.once);
+
+        let kinds = TriggerAction::kinds();
+        let labels: Vec<&str> = kinds.iter().map(|kind| kind.label()).collect();
+        let mut kind = action.kind_index();
+        ui.set_next_item_width(150.0);
+        let mut changed = ui.combo_simple_string(&format!("{}##trigger", t("editor.trigger.action")), &mut kind, &labels);
+        if changed {
+            action = kinds[kind].clone();
+        }
+
+        match action {
+            TriggerAction::PlaySound(ref mut sound) => {
+                let labels: Vec<&str> = TriggerSound::ALL.iter().map(|s| s.label()).collect();
+                let mut selected = TriggerSound::ALL.iter().position(|s| *s == *sound).unwrap_or(0);
+                ui.set_next_item_width(150.0);
+                if ui.combo_simple_string(&format!("{}##trigger", t("editor.trigger.sound")), &mut selected, &labels) {
+                    *sound = TriggerSound::ALL[selected];
+                    changed = true;
+                }
+            }
+            TriggerAction::GrantPickup(ref mut weapon) => {
+                let labels: Vec<&str> = WeaponKind::ALL.iter().map(|w| w.label()).collect();
+                let mut selected = WeaponKind::ALL.iter().position(|w| w.id() == weapon.as_str()).unwrap_or(0);
+                ui.set_next_item_width(150.0);
+                if ui.combo_simple_string(&format!("{}##trigger", t("editor.trigger.weapon")), &mut selected, &labels) {
+                    *weapon = WeaponKind::ALL[selected].id().to_string();
+                    changed = true;
+                }
+            }
+            TriggerAction::Script => ui.text_disabled(t("editor.trigger.script_hint")),
+            _ => {
+                let objective = matches!(action, TriggerAction::StartObjective(_));
+                let (label, hint) = if objective {
+                    (t("editor.trigger.objective_text"), t("editor.trigger.objective_hint"))
+                } else {
+                    (t("editor.trigger.tag"), t("editor.trigger.tag_hint"))
+                };
+                let mut text = action.text().unwrap_or_default().to_string();
+                ui.set_next_item_width(250.0);
+                if ui.input_text(&format!("{}##trigger", label), &mut text).hint(hint).build() {
+                    action.set_text(&text);
+                    changed = true;
+                }
+            }
+        }
+
+        changed |= ui.checkbox(&format!("{}##trigger", t("editor.trigger.once")), &mut once);
+        if changed {
+            self.map.set_object_trigger(index, Some((action, once)));
+        }
+    }
+
+    /// Preset picker plus sky, fog and light controls; the viewport previews the result
+    fn draw_ambiance_editor(&mut self, ui: &imgui::Ui) {
+        let ambiance = &mut self.map.ambiance;
+
+        // Tweaked settings show as "Custom" until a preset is picked again
+        let mut labels: Vec<&str> = AmbiancePreset::ALL.iter().map(|p| p.label()).collect();
+        labels.push(t("editor.ambiance.custom"));
+        let mut preset_index = ambiance.preset()
+            .and_then(|preset| AmbiancePreset::ALL.iter().position(|p| *p == preset))
+            .unwrap_or(AmbiancePreset::ALL.len());
+        ui.set_next_item_width(150.0);
+        if ui.combo_simple_string(&format!("{}##ambiance", t("editor.ambiance.preset")), &mut preset_index, &labels) {
+            if let Some(preset) = AmbiancePreset::ALL.get(preset_index) {
+                *ambiance = preset.ambiance();
+            }
+        }
+
+        let mut sky = color_to_rgb(ambiance.sky_color());
+        ui.set_next_item_width(250.0);
+        if ui.color_edit3(&format!("{}##ambiance", t("editor.ambiance.sky")), &mut sky) {
+            let color = rgb_to_color(sky);
+            ambiance.sky = [color.r, color.g, color.b];
+        }
+        let mut fog = color_to_rgb(ambiance.fog_color());
+        ui.set_next_item_width(250.0);
+        if ui.color_edit3(&format!("{}##ambiance", t("editor.ambiance.fog")), &mut fog) {
+            let color = rgb_to_color(fog);
+            ambiance.fog = [color.r, color.g, color.b];
+        }
+        ui.set_next_item_width(150.0);
+        ui.slider(&format!("{}##ambiance", t("editor.ambiance.fog_density")), 0, 100, &mut ambiance.fog_density);
+        ui.set_next_item_width(150.0);
+        ui.slider(&format!("{}##ambiance", t("editor.ambiance.light")), 20, MAX_LIGHT, &mut ambiance.light);
+    }
+
+    /// Ambient sound emitters: add one at the view center, then pick its loop, radius and volume
+    fn draw_ambient_sound_editor(&mut self, ui: &imgui::Ui) {
+        ui.text_wrapped(t("editor.ambient.help"));
+        let target = self.clamp_to_world(Vector3::new(self.camera.target.x, self.camera.target.y.max(1.0), self.camera.target.z));
+        if ui.button_with_size(t("editor.ambient.add"), [180.0, 25.0]) {
+            match self.map.add_ambient_sound(AmbientSound::Wind, target) {
+                Some(index) => self.set_status(&tr!("editor.status.ambient_added", index = index + 1)),
+                None => self.set_status(&tr!("editor.status.ambient_full", max = MAX_AMBIENT_EMITTERS)),
+            }
+        }
+
+        let labels: Vec<&str> = AmbientSound::ALL.iter().map(|s| s.label()).collect();
+        let mut to_remove = None;
+        for (i, emitter) in self.map.ambient_sounds.iter_mut().enumerate() {
+            ui.separator();
+            let position = emitter.position();
+            ui.text(tr!("editor.ambient.emitter", index = i + 1, x = format!("{:.1}", position.x), y = format!("{:.1}", position.y), z = format!("{:.1}", position.z)));
+
+            let mut selected = AmbientSound::ALL.iter().position(|s| *s == emitter.sound).unwrap_or(0);
+            ui.set_next_item_width(150.0);
+            if ui.combo_simple_string(&format!("{}##ambient{}", t("editor.ambient.sound"), i), &mut selected, &labels) {
+                emitter.sound = AmbientSound::ALL[selected];
+            }
+            ui.set_next_item_width(150.0);
+            ui.slider(&format!("{}##ambient{}", t("editor.ambient.radius"), i), AMBIENT_RADIUS_RANGE.0, AMBIENT_RADIUS_RANGE.1, &mut emitter.radius);
+            ui.set_next_item_width(150.0);
+            ui.slider(&format!("{}##ambient{}", t("editor.ambient.volume"), i), 0, 100, &mut emitter.volume);
+
+            if ui.small_button(format!("{}##ambient{}", t("editor.ambient.move_here"), i)) {
+                emitter.set_position(target);
+            }
+            ui.same_line();
+            if ui.small_button(format!("{}##ambient{}", t("common.delete"), i)) {
+                to_remove = Some(i);
+            }
+        }
+        if let Some(index) = to_remove {
+            self.map.ambient_sounds.remove(index);
+        }
+    }
+
+    /// Map script source, checked as it's typed
+    fn draw_script_editor(&mut self, ui: &imgui::Ui) {
+        ui.input_text_multiline("##script", &mut self.map.script, [280.0, 120.0]).build();
+
+        let size = tr!("editor.script.size", bytes = self.map.script.len(), max = SCRIPT_MAX_LEN);
+        match MapScript::parse(&self.map.script) {
+            Ok(script) => ui.text_colored([0.0, 0.95, 0.58, 1.0], format!("{} - {}", tr!("editor.script.rules", count = script.rules.len()), size)),
+            Err(error) => ui.text_colored([1.0, 0.3, 0.3, 1.0], format!("{} - {}", error.message(), size)),
+        }
+        ui.text_wrapped(t("editor.script.help"));
+    }
+
+    fn draw_heightmap_import(&mut self, ui: &imgui::Ui) {
+        let options = &mut self.heightmap_options;
+
+        let mut style_index = HeightmapStyle::ALL.iter().position(|s| *s == options.style).unwrap_or(0);
+        let style_labels: Vec<&str> = HeightmapStyle::ALL.iter().map(|s| s.label()).collect();
+        ui.set_next_item_width(110.0);
+        if ui.combo_simple_string(&format!("{}##heightmap", t("editor.heightmap.style")), &mut style_index, &style_labels) {
+            options.style = HeightmapStyle::ALL[style_index];
+        }
+
+        let resolution_labels = HEIGHTMAP_RESOLUTIONS.map(|r| format!("{0}x{0}", r));
+        ui.set_next_item_width(110.0);
+        ui.combo_simple_string(&format!("{}##heightmap", t("editor.heightmap.grid")), &mut options.resolution_index, &resolution_labels);
+
+        ui.set_next_item_width(110.0);
+        ui.slider(&format!("{}##heightmap", t("editor.heightmap.height")), 0.5, 20.0, &mut options.max_height);
+        if options.style == HeightmapStyle::Blocks {
+            ui.set_next_item_width(110.0);
+            ui.slider(&format!("{}##heightmap", t("editor.heightmap.threshold")), 0, 254, &mut options.threshold);
+        }
+        ui.checkbox(&format!("{}##heightmap_invert", t("editor.heightmap.invert")), &mut options.invert);
+        ui.checkbox(&format!("{}##heightmap_replace", t("editor.heightmap.replace")), &mut options.replace);
+
+        if ui.button_with_size(t("editor.heightmap.choose_image"), [180.0, 25.0]) {
+            self.pick_heightmap_image();
+        }
+    }
+
+    fn draw_generator_tools(&mut self, ui: &imgui::Ui) {
+        let labels: Vec<&str> = self.generators.iter().map(|g| g.label()).collect();
+        ui.set_next_item_width(180.0);
+        ui.combo_simple_string("##generator", &mut self.generator_index, &labels);
+        let Some(generator) = self.generators.get(self.generator_index) else {
+            return;
+        };
+
+        let values = &mut self.generator_values[self.generator_index];
+        for (i, param) in generator.params().iter().enumerate() {
+            let label = format!("{}##generator_{}", t(param.label), i);
+            ui.set_next_item_width(110.0);
+            if param.integer {
+                let mut value = values[i] as i32;
+                if ui.slider(&label, param.min as i32, param.max as i32, &mut value) {
+                    values[i] = value as f32;
+                }
+            } else {
+                ui.slider(&label, param.min, param.max, &mut values[i]);
+            }
+        }
+
+        ui.set_next_item_width(110.0);
+        ui.input_scalar(&format!("{}##generator", t("editor.generate.seed")), &mut self.generator_seed).build();
+        ui.same_line();
+        if ui.small_button(t("editor.generate.random_seed")) {
+            self.generator_seed = random_seed();
+        }
+        ui.checkbox(&format!("{}##generator_replace", t("editor.heightmap.replace")), &mut self.generator_replace);
+        let code = share_code(generator.as_ref(), self.generator_seed, &self.generator_values[self.generator_index]);
+
+        if ui.button_with_size(t("editor.generate.run"), [180.0, 25.0]) {
+            self.run_generator();
+        }
+
+        // Share codes: anyone with the code regenerates the identical layout
+        ui.text_disabled(format!("{}: {}", t("editor.generate.share_code"), code));
+        ui.same_line();
+        if ui.small_button(t("editor.generate.copy_code")) {
+            #[cfg(target_os = "emscripten")]
+            run_bridge_script(&format!(
+                "navigator.clipboard && navigator.clipboard.writeText({}), ''",
+                serde_json::to_string(&code).unwrap_or_default()
+            ));
+            #[cfg(not(target_os = "emscripten"))]
+            ui.set_clipboard_text(&code);
+            self.set_status(&tr!("editor.status.share_code_copied", code = code));
+        }
+        ui.set_next_item_width(110.0);
+        ui.input_text("##generator_code", &mut self.generator_code_input)
+            .hint(t("editor.generate.paste_code"))
+            .build();
+        ui.same_line();
+        if ui.small_button(t("editor.generate.load_code")) {
+            self.load_share_code();
+        }
+    }
+
+    /// Select the generator, values and seed from the pasted share code and generate its layout
+    fn load_share_code(&mut self) {
+        let Some(layout) = parse_share_code(&self.generators, &self.generator_code_input) else {
+            self.set_status(t("editor.status.share_code_invalid"));
+            return;
+        };
+        self.generator_index = layout.generator;
+        self.generator_values[layout.generator] = layout.values;
+        self.generator_seed = layout.seed;
+        self.generator_code_input.clear();
+        self.run_generator();
+    }
+
+    /// Block out the map with the selected generator's layout
+    fn run_generator(&mut self) {
+        let Some(generator) = self.generators.get(self.generator_index) else {
+            return;
+        };
+        let objects = generator.generate(self.generator_seed, &self.generator_values[self.generator_index], self.current_color);
+        if objects.is_empty() {
+            self.set_status(t("editor.status.generate_empty"));
+            return;
+        }
+
+        let count = self.place_objects(objects, self.generator_replace);
+        if self.over_budget() {
+            self.set_status(&tr!("editor.status.generate_over_budget", count = count));
+        } else {
+            self.set_status(&tr!("editor.status.generated", count = count, seed = self.generator_seed));
+        }
+    }
+
+    /// Add imported or generated objects; `replace` removes the existing geometry first
+    /// (spawn points are kept unless the new objects bring their own)
+    fn place_objects(&mut self, objects: Vec<MapObject>, replace: bool) -> usize {
+        if replace {
+            let is_spawn = |object: &MapObject| matches!(object.model_type, ModelType::SpawnPointBlue | ModelType::SpawnPointRed);
+            let keep_spawns = !objects.iter().any(is_spawn);
+            // Highest index first so indices stay valid
+            for index in (0..self.map.objects.len()).rev() {
+                if !(keep_spawns && is_spawn(&self.map.objects[index])) {
+                    self.map.remove_object(index);
+                }
+            }
+        }
+
+        let count = objects.len();
+        for object in objects {
+            self.map.add_object(object);
+        }
+        self.clear_selection();
+        count
+    }
+
+    /// Whether the map is past its byte or object budget
+    fn over_budget(&self) -> bool {
+        let stats = MapStats::of(&self.map);
+        stats.byte_level() == BudgetLevel::Over || stats.object_level() == BudgetLevel::Over
+    }
+
+    fn draw_heatmap_tools(&mut self, ui: &imgui::Ui) {
+        if ui.button_with_size(t("editor.heatmap.load"), [180.0, 25.0]) {
+            self.pick_heatmap_files();
+        }
+
+        let mut kind_index = HeatmapKind::ALL.iter().position(|k| *k == self.heatmap_kind).unwrap_or(0);
+        let kind_labels: Vec<&str> = HeatmapKind::ALL.iter().map(|k| k.label()).collect();
+        ui.set_next_item_width(110.0);
+        if ui.combo_simple_string(&format!("{}##heatmap_kind", t("editor.heatmap.show")), &mut kind_index, &kind_labels) {
+            self.heatmap_kind = HeatmapKind::ALL[kind_index];
+        }
+        ui.checkbox(&format!("{}##heatmap_overlay", t("editor.heatmap.overlay")), &mut self.show_heatmap);
+
+        if ui.button_with_size(t("editor.heatmap.top_down"), [88.0, 25.0]) {
+            // Straight down on the middle of the world, high enough to see all of it
+            self.camera = Camera3D::perspective(
+                Vector3::new(0.0, WORLD_SIZE * 1.2, 1.0),
+                Vector3::zero(),
+                Vector3::new(0.0, 1.0, 0.0),
+                60.0,
+            );
+        }
+        ui.same_line();
+        if ui.button_with_size(t("editor.heatmap.clear"), [88.0, 25.0]) {
+            self.heatmap = MatchHeatmap::default();
+            self.set_status(t("editor.status.heatmap_cleared"));
+        }
+
+        let heatmap = &self.heatmap;
+        if heatmap.files == 0 {
+            ui.text_wrapped(t("editor.heatmap.help"));
+            return;
+        }
+        ui.text(tr!(
+            "editor.heatmap.summary",
+            files = heatmap.files,
+            kills = heatmap.kills,
+            deaths = heatmap.deaths,
+            minutes = format!("{:.1}", heatmap.seconds / 60.0)
+        ));
+        match heatmap.hottest(self.heatmap_kind) {
+            Some((center, value)) => {
+                let value = match self.heatmap_kind {
+                    HeatmapKind::Presence => format!("{:.0}s", value),
+                    _ => format!("{:.0}", value),
+                };
+                ui.text(tr!("editor.heatmap.hottest", x = center.x, z = center.z, value = value));
+            }
+            None => ui.text(t("editor.heatmap.no_data")),
+        }
+    }
+
+    /// Open a multi-file picker for exported match logs and replays; the browser reads them as text
+    #[cfg(target_os = "emscripten")]
+    fn pick_heatmap_files(&mut self) {
+        use std::ffi::CString;
+
+        extern "C" {
+            pub fn emscripten_run_script(script: *const i8);
+        }
+
+        let js_code = r#"
+        (function() {
+            var input = document.createElement('input');
+            input.type = 'file';
+            input.accept = '.json,application/json';
+            input.multiple = true;
+
+            input.onchange = function(e) {
+                var files = Array.prototype.slice.call(e.target.files);
+                if (files.length === 0) return;
+
+                Promise.all(files.map(function(file) {
+                    return file.text().then(function(text) {
+                        return { name: file.name, text: text };
+                    });
+                })).then(function(loaded) {
+                    Module.uploadedHeatmapFiles = JSON.stringify(loaded);
+                    console.log('Heatmap files loaded: ' + loaded.length);
+                });
+            };
+
+            input.click();
+        })();
+        "#;
+
+        let c_str = CString::new(js_code).unwrap();
+        unsafe {
+            emscripten_run_script(c_str.as_ptr());
+        }
+
+        self.set_status(t("editor.status.select_heatmap_files"));
+    }
+
+    #[cfg(not(target_os = "emscripten"))]
+    fn pick_heatmap_files(&mut self) {
+        self.set_status(t("editor.status.heatmap_browser_only"));
+    }
+
+    /// Check for match logs/replays read by the browser (Emscripten only)
+    #[cfg(target_os = "emscripten")]
+    fn check_uploaded_heatmap_files(&mut self) {
+        use std::ffi::CString;
+
+        extern "C" {
+            pub fn emscripten_run_script_string(script: *const i8) -> *const i8;
+        }
+
+        // JSON [{name, text}] once the files are read, empty otherwise
+        let js_get_data = CString::new(r#"
+            (function() {
+                if (!Module.uploadedHeatmapFiles) return '';
+                var result = Module.uploadedHeatmapFiles;
+                delete Module.uploadedHeatmapFiles;
+                return result;
+            })()
+        "#).unwrap();
+
+        let data = unsafe {
+            let result_ptr = emscripten_run_script_string(js_get_data.as_ptr());
+            if result_ptr.is_null() {
+                return;
+            }
+            std::ffi::CStr::from_ptr(result_ptr).to_str().unwrap_or("").to_string()
+        };
+        if data.is_empty() {
+            return;
+        }
+
+        let files: Vec<serde_json::Value> = serde_json::from_str(&data).unwrap_or_default();
+        self.add_heatmap_files(files.iter().map(|file| {
+            let field = |key: &str| file.get(key).and_then(|v| v.as_str()).unwrap_or("");
+            (field("name"), field("text"))
+        }));
+    }
+
+    /// Add (name, contents) files to the heatmap; the status names the first one that didn't load
+    fn add_heatmap_files<'a>(&mut self, files: impl Iterator<Item = (&'a str, &'a str)>) {
+        let mut added = 0;
+        let mut failed = None;
+        for (name, text) in files {
+            match self.heatmap.ingest(text) {
+                Ok(()) => added += 1,
+                Err(e) => {
+                    log_warn!(Editor, "⚠️ Skipping heatmap file {}: {}", name, e.message());
+                    failed.get_or_insert((name.to_string(), e));
+                }
+            }
+        }
+        match failed {
+            Some((name, e)) => self.set_status(&tr!("editor.status.heatmap_file_failed", added = added, file = name, error = e.message())),
+            None => self.set_status(&tr!("editor.status.heatmap_loaded", added = added)),
+        }
+    }
+
+    /// Open a file picker; the browser scales the image to the import grid and hands back grayscale bytes
+    #[cfg(target_os = "emscripten")]
+    fn pick_heightmap_image(&mut self) {
+        use std::ffi::CString;
+
+        extern "C" {
+            pub fn emscripten_run_script(script: *const i8);
+        }
+
+        let js_code = format!(r#"
+        (function() {{
+            var size = {};
+            var input = document.createElement('input');
+            input.type = 'file';
+            input.accept = 'image/*';
+
+            input.onchange = function(e) {{
+                var file = e.target.files[0];
+                if (!file) return;
+
+                var image = new Image();
+                image.onload = function() {{
+                    // Downscale onto a size x size canvas and convert to grayscale (transparent = black)
+                    var canvas = document.createElement('canvas');
+                    canvas.width = size;
+                    canvas.height = size;
+                    var ctx = canvas.getContext('2d');
+                    ctx.drawImage(image, 0, 0, size, size);
+                    var rgba = ctx.getImageData(0, 0, size, size).data;
+
+                    var gray = new Uint8Array(size * size);
+                    for (var i = 0; i < gray.length; i++) {{
+                        var luma = 0.299 * rgba[i * 4] + 0.587 * rgba[i * 4 + 1] + 0.114 * rgba[i * 4 + 2];
+                        gray[i] = Math.round(luma * rgba[i * 4 + 3] / 255);
+                    }}
+
+                    Module.uploadedHeightmap = gray;
+                    Module.uploadedHeightmapSize = size;
+                    URL.revokeObjectURL(image.src);
+                    console.log('Heightmap image loaded: ' + file.name + ' (' + size + 'x' + size + ')');
+                }};
+                image.src = URL.createObjectURL(file);
+            }};
+
+            input.click();
+        }})();
+        "#, self.heightmap_options.resolution());
+
+        let c_str = CString::new(js_code).unwrap();
+        unsafe {
+            emscripten_run_script(c_str.as_ptr());
+        }
+
+        self.set_status(t("editor.status.select_image"));
+    }
+
+    #[cfg(not(target_os = "emscripten"))]
+    fn pick_heightmap_image(&mut self) {
+        self.set_status(t("editor.status.heightmap_browser_only"));
+    }
+
+    /// Check for a heightmap image decoded by the browser (Emscripten only)
+    #[cfg(target_os = "emscripten")]
+    fn check_uploaded_heightmap(&mut self) {
+        use std::ffi::CString;
+        use base64::{Engine as _, engine::general_purpose};
+
+        extern "C" {
+            pub fn emscripten_run_script_string(script: *const i8) -> *const i8;
+        }
+
+        // "size:base64" once an image is ready, empty otherwise
+        let js_get_data = CString::new(r#"
+            (function() {
+                if (!Module.uploadedHeightmap) return '';
+                var bytes = Module.uploadedHeightmap;
+                var binary = '';
+                for (var i = 0; i < bytes.length; i++) {
+                    binary += String.fromCharCode(bytes[i]);
+                }
+                var result = Module.uploadedHeightmapSize + ':' + btoa(binary);
+                delete Module.uploadedHeightmap;
+                delete Module.uploadedHeightmapSize;
+                return result;
+            })()
+        "#).unwrap();
+
+        let data = unsafe {
+            let result_ptr = emscripten_run_script_string(js_get_data.as_ptr());
+            if result_ptr.is_null() {
+                return;
+            }
+            std::ffi::CStr::from_ptr(result_ptr).to_str().unwrap_or("").to_string()
+        };
+
+        let Some((size, base64_data)) = data.split_once(':') else {
+            return;
+        };
+        let Ok(size) = size.parse::<usize>() else {
+            self.set_status(t("editor.status.heightmap_size_failed"));
+            return;
+        };
+
+        match general_purpose::STANDARD.decode(base64_data) {
+            Ok(pixels) => self.apply_heightmap(&pixels, size),
+            Err(e) => self.set_status(&tr!("editor.status.heightmap_decode_failed", error = e)),
+        }
+    }
+
+    /// Turn a square grayscale image into blockout geometry
+    fn apply_heightmap(&mut self, pixels: &[u8], size: usize) {
+        let objects = heightmap_to_objects(pixels, size, size, &self.heightmap_options, self.current_color);
+        if objects.is_empty() {
+            self.set_status(t("editor.status.heightmap_empty"));
+            return;
+        }
+
+        let count = self.place_objects(objects, self.heightmap_options.replace);
+        if self.over_budget() {
+            self.set_status(&tr!("editor.status.heightmap_over_budget", count = count));
+        } else {
+            self.set_status(&tr!("editor.status.heightmap_imported", count = count));
+        }
+    }
+}
+
+/// How far a key pair moves a value this frame: held keys move smoothly by `amount`,
+/// with snapping each press moves exactly one `step`
+fn key_axis(rl: &RaylibHandle, negative: KeyboardKey, positive: KeyboardKey, amount: f32, step: Option<f32>) -> f32 {
+    let (distance, positive_held, negative_held) = match step {
+        Some(step) => (step, rl.is_key_pressed(positive), rl.is_key_pressed(negative)),
+        None => (amount, rl.is_key_down(positive), rl.is_key_down(negative)),
+    };
+    match (positive_held, negative_held) {
+        (true, false) => distance,
+        (false, true) => -distance,
+        _ => 0.0,
+    }
+}
+
+fn color_to_rgb(color: Color) -> [f32; 3] {
+    [color.r as f32 / 255.0, color.g as f32 / 255.0, color.b as f32 / 255.0]
+}
+
+fn rgb_to_color(rgb: [f32; 3]) -> Color {
+    Color::new((rgb[0] * 255.0).round() as u8, (rgb[1] * 255.0).round() as u8, (rgb[2] * 255.0).round() as u8, 255)
+}
+
+/// Colors collaborators' cursors and names are picked from
+const COLLAB_COLORS: [Color; 6] = [Color::ORANGE, Color::SKYBLUE, Color::PINK, Color::GOLD, Color::LIME, Color::VIOLET];
+
+/// Stable per-collaborator color for cursors and names
+fn collab_color(peer_id: &str) -> Color {
+    let hash = peer_id.bytes().fold(0usize, |hash, byte| hash.wrapping_mul(31).wrapping_add(byte as usize));
+    COLLAB_COLORS[hash % COLLAB_COLORS.len()]
+}
+
+/// Run a bridge call in the browser and return its result as a string
+#[cfg(target_os = "emscripten")]
+fn run_bridge_script(script: &str) -> String {
+    use std::ffi::CString;
+
+    extern "C" {
+        pub fn emscripten_run_script_string(script: *const i8) -> *const i8;
+    }
+
+    let Ok(c_script) = CString::new(script) else {
+        return String::new();
+    };
+    unsafe {
+        let result_ptr = emscripten_run_script_string(c_script.as_ptr());
+        if result_ptr.is_null() {
+            return String::new();
+        }
+        std::ffi::CStr::from_ptr(result_ptr).to_str().unwrap_or("").to_string()
+    }
 }
@@ -1,5 +1,32 @@
 pub mod map;
 pub mod map_builder;
+pub mod hierarchy;
+pub mod snap;
+pub mod stats;
+pub mod walkthrough;
+pub mod balance;
+pub mod heightmap;
+pub mod library;
+pub mod collab;
+pub mod spatial;
+pub mod ambiance;
+pub mod triggers;
+pub mod script;
+pub mod heatmap;
+pub mod ambient_sound;
+pub mod generator;
+pub mod loader;
+pub mod stream;
 
-pub use map::{Map, MapObject, ModelType, WORLD_SIZE, WORLD_HALF_SIZE};
+pub use map::{Collision, Map, MapObject, ModelType, ObjectLabel, WORLD_SIZE, WORLD_HALF_SIZE};
 pub use map_builder::{MapBuilder, EditorMode, Axis};
+pub use hierarchy::{HierarchyFilter, HierarchyCategory};
+pub use snap::SnapSettings;
+pub use stats::MapStats;
+pub use spatial::SpatialIndex;
+pub use ambiance::{Ambiance, AmbiancePreset};
+pub use triggers::{ObjectTrigger, TriggerAction, TriggerSound};
+pub use ambient_sound::{AmbientEmitter, AmbientSound};
+pub use script::{MapScript, ScriptCommand, ScriptStep, ScriptWhen};
+pub use loader::{LoadStep, LoadedMap, MapLoader};
+pub use stream::{MapStream, MAP_CHUNK_OBJECTS};
@@ -0,0 +1,233 @@
+use crate::game::WeaponKind;
+use crate::i18n::tr;
+use super::map::normalize_tags;
+use super::triggers::{TriggerAction, TriggerSound};
+
+/// Longest script a map may carry (bytes); it counts toward the map budget like everything else
+pub const SCRIPT_MAX_LEN: usize = 1024;
+
+/// Shortest `every` period, so a script can't flood the match with spawns
+pub const MIN_SCRIPT_PERIOD: f32 = 1.0;
+
+/// When a rule starts its sequence
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptWhen {
+    /// `after 60s`: once, this many seconds into the match
+    After(f32),
+    /// `every 30s`: at each multiple of the period
+    Every(f32),
+    /// `on lever`: when the local player enters a trigger volume tagged with this
+    On(String),
+}
+
+/// Something a script does
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptCommand {
+    /// `open`, `teleport`, `give`, `sound` and `objective` do what the trigger actions do
+    Action(TriggerAction),
+    /// `spawn shotgun at crate-x`: a weapon pickup on top of the tagged object
+    Spawn { weapon: String, tag: String },
+}
+
+/// One entry of a rule's sequence
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptStep {
+    /// `wait 5s`: delay the commands after it
+    Wait(f32),
+    Run(ScriptCommand),
+}
+
+/// One script line: a schedule and the `;`-separated sequence it starts
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptRule {
+    pub when: ScriptWhen,
+    pub steps: Vec<ScriptStep>,
+}
+
+/// A map's parsed script
+///
+/// One rule per line, `#` starts a comment:
+/// ```text
+/// after 60s open door-a
+/// every 30s spawn shotgun at crate-x
+/// on lever open gate; wait 3s; objective Hold the gate
+/// ```
+/// Timed rules run from match time, so every client runs them at the same moment
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MapScript {
+    pub rules: Vec<ScriptRule>,
+}
+
+/// What's wrong with a script
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptProblem {
+    TooLong,
+    UnknownSchedule(String),
+    BadDuration(String),
+    PeriodTooShort,
+    UnknownCommand(String),
+    /// A command or schedule is missing its argument (the word it belongs to)
+    MissingArgument(String),
+    UnknownWeapon(String),
+    UnknownSound(String),
+}
+
+/// First problem found in a script, with its line (1-based, 0 for the whole script)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptError {
+    pub line: usize,
+    pub problem: ScriptProblem,
+}
+
+impl ScriptError {
+    /// Localized description for the editor
+    pub fn message(&self) -> String {
+        let problem = match &self.problem {
+            ScriptProblem::TooLong => tr!("editor.script.too_long", max = SCRIPT_MAX_LEN),
+            ScriptProblem::UnknownSchedule(word) => tr!("editor.script.unknown_schedule", word = word),
+            ScriptProblem::BadDuration(word) => tr!("editor.script.bad_duration", word = word),
+            ScriptProblem::PeriodTooShort => tr!("editor.script.period_too_short", min = MIN_SCRIPT_PERIOD),
+            ScriptProblem::UnknownCommand(word) => tr!("editor.script.unknown_command", word = word),
+            ScriptProblem::MissingArgument(word) => tr!("editor.script.missing_argument", word = word),
+            ScriptProblem::UnknownWeapon(word) => tr!("editor.script.unknown_weapon", word = word),
+            ScriptProblem::UnknownSound(word) => tr!("editor.script.unknown_sound", word = word),
+        };
+        if self.line == 0 {
+            problem
+        } else {
+            tr!("editor.script.line", line = self.line, problem = problem)
+        }
+    }
+}
+
+impl MapScript {
+    pub fn parse(source: &str) -> Result<Self, ScriptError> {
+        if source.len() > SCRIPT_MAX_LEN {
+            return Err(ScriptError { line: 0, problem: ScriptProblem::TooLong });
+        }
+        let mut rules = Vec::new();
+        for (i, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            rules.push(parse_rule(line).map_err(|problem| ScriptError { line: i + 1, problem })?);
+        }
+        Ok(Self { rules })
+    }
+}
+
+/// First word of `text` and the rest, trimmed
+fn split_word(text: &str) -> (&str, &str) {
+    let text = text.trim();
+    text.split_once(char::is_whitespace).map_or((text, ""), |(word, rest)| (word, rest.trim()))
+}
+
+fn parse_rule(line: &str) -> Result<ScriptRule, ScriptProblem> {
+    let (keyword, rest) = split_word(line);
+    let (argument, sequence) = split_word(rest);
+    if argument.is_empty() {
+        return Err(ScriptProblem::MissingArgument(keyword.to_string()));
+    }
+    let when = match keyword {
+        "after" => ScriptWhen::After(parse_duration(argument)?),
+        "every" => {
+            let period = parse_duration(argument)?;
+            if period < MIN_SCRIPT_PERIOD {
+                return Err(ScriptProblem::PeriodTooShort);
+            }
+            ScriptWhen::Every(period)
+        }
+        "on" => ScriptWhen::On(parse_tag(argument)),
+        other => return Err(ScriptProblem::UnknownSchedule(other.to_string())),
+    };
+    let steps = sequence
+        .split(';')
+        .map(str::trim)
+        .filter(|step| !step.is_empty())
+        .map(parse_step)
+        .collect::<Result<Vec<_>, _>>()?;
+    if steps.is_empty() {
+        return Err(ScriptProblem::MissingArgument(keyword.to_string()));
+    }
+    Ok(ScriptRule { when, steps })
+}
+
+fn parse_step(step: &str) -> Result<ScriptStep, ScriptProblem> {
+    let (command, argument) = split_word(step);
+    if argument.is_empty() {
+        return Err(ScriptProblem::MissingArgument(command.to_string()));
+    }
+    let command = match command {
+        "wait" => return Ok(ScriptStep::Wait(parse_duration(argument)?)),
+        "open" => ScriptCommand::Action(TriggerAction::OpenMover(parse_tag(argument))),
+        "teleport" => ScriptCommand::Action(TriggerAction::TeleportToTag(parse_tag(argument))),
+        "give" => ScriptCommand::Action(TriggerAction::GrantPickup(parse_weapon(argument)?)),
+        "sound" => {
+            let sound = TriggerSound::from_id(argument).ok_or_else(|| ScriptProblem::UnknownSound(argument.to_string()))?;
+            ScriptCommand::Action(TriggerAction::PlaySound(sound))
+        }
+        "objective" => ScriptCommand::Action(TriggerAction::StartObjective(argument.trim_matches('"').to_string())),
+        "spawn" => {
+            let (weapon, rest) = split_word(argument);
+            let tag = rest.strip_prefix("at").map(str::trim).filter(|tag| !tag.is_empty());
+            let tag = tag.ok_or_else(|| ScriptProblem::MissingArgument("at".to_string()))?;
+            ScriptCommand::Spawn { weapon: parse_weapon(weapon)?, tag: parse_tag(tag) }
+        }
+        other => return Err(ScriptProblem::UnknownCommand(other.to_string())),
+    };
+    Ok(ScriptStep::Run(command))
+}
+
+/// "60s", "1.5s" or plain "60" seconds
+fn parse_duration(word: &str) -> Result<f32, ScriptProblem> {
+    word.strip_suffix('s')
+        .unwrap_or(word)
+        .parse::<f32>()
+        .ok()
+        .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+        .ok_or_else(|| ScriptProblem::BadDuration(word.to_string()))
+}
+
+/// Tags are matched the way object tags are stored
+fn parse_tag(word: &str) -> String {
+    normalize_tags(word).replace(',', "")
+}
+
+fn parse_weapon(word: &str) -> Result<String, ScriptProblem> {
+    WeaponKind::from_id(word).map(|weapon| weapon.id().to_string()).ok_or_else(|| ScriptProblem::UnknownWeapon(word.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rules_sequences_and_errors() {
+        let script = MapScript::parse(
+            "# doors\nafter 60s open Door-A\nevery 30 spawn shotgun at crate-x\non lever open gate; wait 2.5s; objective \"Hold the gate\"\n",
+        )
+        .unwrap();
+        assert_eq!(script.rules.len(), 3);
+        assert_eq!(script.rules[0].when, ScriptWhen::After(60.0));
+        assert_eq!(script.rules[0].steps, vec![ScriptStep::Run(ScriptCommand::Action(TriggerAction::OpenMover("door-a".to_string())))]);
+        assert_eq!(
+            script.rules[1].steps,
+            vec![ScriptStep::Run(ScriptCommand::Spawn { weapon: "shotgun".to_string(), tag: "crate-x".to_string() })]
+        );
+        assert_eq!(script.rules[2].when, ScriptWhen::On("lever".to_string()));
+        assert_eq!(script.rules[2].steps[1], ScriptStep::Wait(2.5));
+        assert_eq!(
+            script.rules[2].steps[2],
+            ScriptStep::Run(ScriptCommand::Action(TriggerAction::StartObjective("Hold the gate".to_string())))
+        );
+
+        let error = |source: &str| MapScript::parse(source).unwrap_err();
+        assert_eq!(error("after 5s open a\nsoon 5s open b").line, 2);
+        assert_eq!(error("every 0.5s sound pop").problem, ScriptProblem::PeriodTooShort);
+        assert_eq!(error("after ten open a").problem, ScriptProblem::BadDuration("ten".to_string()));
+        assert_eq!(error("after 1s give bazooka").problem, ScriptProblem::UnknownWeapon("bazooka".to_string()));
+        assert_eq!(error("after 1s spawn rifle crate").problem, ScriptProblem::MissingArgument("at".to_string()));
+        assert_eq!(error(&"#".repeat(SCRIPT_MAX_LEN + 1)), ScriptError { line: 0, problem: ScriptProblem::TooLong });
+    }
+}
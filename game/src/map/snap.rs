@@ -0,0 +1,97 @@
+/// Translation snap sizes (world units)
+pub const TRANSLATION_STEPS: [f32; 4] = [0.25, 0.5, 1.0, 2.0];
+
+/// Rotation snap angles (degrees)
+pub const ROTATION_STEPS: [f32; 3] = [15.0, 45.0, 90.0];
+
+/// Scale snap sizes (scale is stored in tenths, so 0.1 is the finest useful step)
+pub const SCALE_STEPS: [f32; 3] = [0.1, 0.5, 1.0];
+
+/// Round `value` to the nearest multiple of `step`
+pub fn snap_value(value: f32, step: f32) -> f32 {
+    (value / step).round() * step
+}
+
+/// Per-tool snapping: an on/off switch and a selected increment for move, rotate and scale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapSettings {
+    pub translate: bool,
+    pub translate_index: usize,
+    pub rotate: bool,
+    pub rotate_index: usize,
+    pub scale: bool,
+    pub scale_index: usize,
+}
+
+impl Default for SnapSettings {
+    fn default() -> Self {
+        Self {
+            translate: true,
+            translate_index: 2, // 1.0 units, the old fixed grid
+            rotate: true,
+            rotate_index: 0,
+            scale: false,
+            scale_index: 1,
+        }
+    }
+}
+
+impl SnapSettings {
+    /// Translation increment when snapping is on
+    pub fn translation_step(&self) -> Option<f32> {
+        self.translate.then(|| TRANSLATION_STEPS[self.translate_index % TRANSLATION_STEPS.len()])
+    }
+
+    /// Rotation increment in degrees when snapping is on
+    pub fn rotation_step(&self) -> Option<f32> {
+        self.rotate.then(|| ROTATION_STEPS[self.rotate_index % ROTATION_STEPS.len()])
+    }
+
+    /// Scale increment when snapping is on
+    pub fn scale_step(&self) -> Option<f32> {
+        self.scale.then(|| SCALE_STEPS[self.scale_index % SCALE_STEPS.len()])
+    }
+
+    pub fn cycle_translation(&mut self, forward: bool) {
+        self.translate_index = cycle(self.translate_index, TRANSLATION_STEPS.len(), forward);
+    }
+
+    pub fn cycle_rotation(&mut self, forward: bool) {
+        self.rotate_index = cycle(self.rotate_index, ROTATION_STEPS.len(), forward);
+    }
+
+    pub fn cycle_scale(&mut self, forward: bool) {
+        self.scale_index = cycle(self.scale_index, SCALE_STEPS.len(), forward);
+    }
+}
+
+fn cycle(index: usize, len: usize, forward: bool) -> usize {
+    if forward {
+        (index + 1) % len
+    } else {
+        (index + len - 1) % len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_cycle_and_snap() {
+        let mut snap = SnapSettings::default();
+        assert_eq!(snap.translation_step(), Some(1.0));
+        assert_eq!(snap.scale_step(), None);
+
+        snap.cycle_translation(true);
+        assert_eq!(snap.translation_step(), Some(2.0));
+        snap.cycle_translation(true);
+        assert_eq!(snap.translation_step(), Some(0.25));
+        snap.cycle_rotation(false);
+        assert_eq!(snap.rotation_step(), Some(90.0));
+
+        assert_eq!(snap_value(1.3, 0.25), 1.25);
+        assert_eq!(snap_value(50.0, 45.0), 45.0);
+        assert_eq!(snap_value(-0.7, 0.5), -0.5);
+    }
+}
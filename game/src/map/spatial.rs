@@ -0,0 +1,229 @@
+use raylib::prelude::*;
+
+use crate::game::{MovementWorld, PLAYER_HEIGHT, PLAYER_RADIUS, STEP_HEIGHT};
+use super::map::{player_blocked_by, player_floor_on, segment_intersects_box, Collision, Map, WORLD_HALF_SIZE, WORLD_SIZE};
+
+/// Side of one broad-phase cell (world units)
+pub const SPATIAL_CELL_SIZE: f32 = 4.0;
+
+/// Broad phase for collision queries: object bounds bucketed into a uniform grid
+///
+/// Built once per loaded map. Each box is listed in every cell its XZ footprint
+/// touches; boxes reaching outside the world go in a list every query checks.
+/// Answers match the brute-force `Map` queries; only fewer boxes get tested.
+/// Also the world the match's movement step collides with.
+pub struct SpatialIndex {
+    /// Collision bounds of every object something collides with
+    bounds: Vec<BoundingBox>,
+    /// What each of `bounds` blocks
+    collisions: Vec<Collision>,
+    /// Cells along each side of the world
+    cells_per_side: usize,
+    /// Indices into `bounds` per cell (row-major, x then z)
+    cells: Vec<Vec<usize>>,
+    /// Boxes not fully inside the world
+    outside: Vec<usize>,
+}
+
+impl SpatialIndex {
+    /// Bucket a map's collision bounds
+    pub fn build(map: &Map) -> Self {
+        let (bounds, collisions): (Vec<BoundingBox>, Vec<Collision>) =
+            map.collision_bounds_where(|c| c != Collision::NonSolid).unzip();
+        let cells_per_side = (WORLD_SIZE / SPATIAL_CELL_SIZE).ceil() as usize;
+        let mut index = Self { bounds: Vec::new(), collisions, cells_per_side, cells: vec![Vec::new(); cells_per_side * cells_per_side], outside: Vec::new() };
+
+        for (i, b) in bounds.iter().enumerate() {
+            let inside = [b.min.x, b.min.z, b.max.x, b.max.z].iter().all(|v| v.abs() <= WORLD_HALF_SIZE);
+            if !inside {
+                index.outside.push(i);
+                continue;
+            }
+            let (min_col, min_row) = index.cell_of(b.min.x, b.min.z);
+            let (max_col, max_row) = index.cell_of(b.max.x, b.max.z);
+            for row in min_row..=max_row {
+                for col in min_col..=max_col {
+                    index.cells[row * cells_per_side + col].push(i);
+                }
+            }
+        }
+        index.bounds = bounds;
+        index
+    }
+
+    /// Cell (column, row) containing a world (x, z), clamped to the grid
+    fn cell_of(&self, x: f32, z: f32) -> (usize, usize) {
+        let cell = |value: f32| (((value + WORLD_HALF_SIZE) / SPATIAL_CELL_SIZE).max(0.0) as usize).min(self.cells_per_side - 1);
+        (cell(x), cell(z))
+    }
+
+    /// Boxes whose cells overlap an XZ rectangle, each once
+    fn candidates_in_rect(&self, min_x: f32, min_z: f32, max_x: f32, max_z: f32) -> Vec<usize> {
+        let (min_col, min_row) = self.cell_of(min_x, min_z);
+        let (max_col, max_row) = self.cell_of(max_x, max_z);
+        let mut candidates: Vec<usize> = (min_row..=max_row)
+            .flat_map(|row| (min_col..=max_col).flat_map(move |col| self.cells[row * self.cells_per_side + col].iter().copied()))
+            .chain(self.outside.iter().copied())
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates
+    }
+
+    /// Boxes in the cells a segment crosses (XZ), each once, roughly nearest first
+    fn candidates_along(&self, from: Vector3, to: Vector3) -> Vec<usize> {
+        let mut seen = vec![false; self.bounds.len()];
+        let mut candidates = Vec::new();
+        let mut take = |indices: &[usize]| {
+            for &i in indices {
+                if !std::mem::replace(&mut seen[i], true) {
+                    candidates.push(i);
+                }
+            }
+        };
+        take(&self.outside);
+
+        // Clip to the world square (Liang-Barsky); only `outside` boxes live beyond it
+        let (dx, dz) = (to.x - from.x, to.z - from.z);
+        let (mut t0, mut t1) = (0.0f32, 1.0f32);
+        for (p, q) in [
+            (-dx, from.x + WORLD_HALF_SIZE),
+            (dx, WORLD_HALF_SIZE - from.x),
+            (-dz, from.z + WORLD_HALF_SIZE),
+            (dz, WORLD_HALF_SIZE - from.z),
+        ] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return candidates;
+                }
+            } else if p < 0.0 {
+                t0 = t0.max(q / p);
+            } else {
+                t1 = t1.min(q / p);
+            }
+        }
+        if t0 > t1 {
+            return candidates;
+        }
+
+        // Walk the grid cells between the clipped end points (DDA)
+        let to_grid = |t: f32| ((from.x + dx * t + WORLD_HALF_SIZE) / SPATIAL_CELL_SIZE, (from.z + dz * t + WORLD_HALF_SIZE) / SPATIAL_CELL_SIZE);
+        let ((x0, z0), (x1, z1)) = (to_grid(t0), to_grid(t1));
+        let last = self.cells_per_side as i32 - 1;
+        let (mut col, mut row) = ((x0.floor() as i32).clamp(0, last), (z0.floor() as i32).clamp(0, last));
+        let (end_col, end_row) = ((x1.floor() as i32).clamp(0, last), (z1.floor() as i32).clamp(0, last));
+        let (step_col, step_row) = (if x1 < x0 { -1 } else { 1 }, if z1 < z0 { -1 } else { 1 });
+        let delta_x = if x1 == x0 { f32::INFINITY } else { 1.0 / (x1 - x0).abs() };
+        let delta_z = if z1 == z0 { f32::INFINITY } else { 1.0 / (z1 - z0).abs() };
+        let first_boundary = |start: f32, cell: i32, step: i32| if step > 0 { cell as f32 + 1.0 - start } else { start - cell as f32 };
+        // Segment fraction (0..1 of the clipped part) at the next column / row boundary
+        let mut next_x = first_boundary(x0, col, step_col) * delta_x;
+        let mut next_z = first_boundary(z0, row, step_row) * delta_z;
+
+        loop {
+            take(&self.cells[row as usize * self.cells_per_side + col as usize]);
+            if (col == end_col && row == end_row) || next_x.min(next_z) > 1.0 {
+                return candidates;
+            }
+            if next_x < next_z {
+                col += step_col;
+                next_x += delta_x;
+            } else {
+                row += step_row;
+                next_z += delta_z;
+            }
+            if !(0..=last).contains(&col) || !(0..=last).contains(&row) {
+                return candidates;
+            }
+        }
+    }
+
+    /// Same as `Map::is_line_blocked`
+    pub fn is_line_blocked(&self, from: Vector3, to: Vector3) -> bool {
+        self.candidates_along(from, to)
+            .into_iter()
+            .filter(|&i| self.collisions[i].blocks_bullets())
+            .any(|i| segment_intersects_box(from, to, &self.bounds[i]))
+    }
+
+    /// Same as `Map::raycast`
+    pub fn raycast(&self, ray: Ray, max_distance: f32) -> Option<(Vector3, Vector3)> {
+        let end = ray.position + ray.direction.normalized() * max_distance;
+        self.candidates_along(ray.position, end)
+            .into_iter()
+            .filter(|&i| self.collisions[i].blocks_bullets())
+            .map(|i| get_ray_collision_box(ray, self.bounds[i]))
+            .filter(|hit| hit.hit && hit.distance <= max_distance)
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+            .map(|hit| (hit.point, hit.normal))
+    }
+
+    /// Player-blocking boxes that may overlap a player footprint of `radius` around (x, z)
+    fn under_player(&self, x: f32, z: f32, radius: f32) -> Vec<BoundingBox> {
+        self.candidates_in_rect(x - radius, z - radius, x + radius, z + radius)
+            .into_iter()
+            .filter(|&i| self.collisions[i].blocks_players())
+            .map(|i| self.bounds[i])
+            .collect()
+    }
+
+    /// Same as `Map::blocks_player`
+    pub fn blocks_player(&self, position: Vector3, radius: f32, height: f32, step_height: f32) -> bool {
+        player_blocked_by(&self.under_player(position.x, position.z, radius), position, radius, height, step_height)
+    }
+}
+
+impl MovementWorld for SpatialIndex {
+    fn blocks(&self, x: f32, y: f32, z: f32) -> bool {
+        self.blocks_player(Vector3::new(x, y, z), PLAYER_RADIUS, PLAYER_HEIGHT, STEP_HEIGHT)
+    }
+
+    fn floor_height(&self, x: f32, z: f32, max_y: f32) -> f32 {
+        player_floor_on(&self.under_player(x, z, PLAYER_RADIUS), x, z, PLAYER_RADIUS, max_y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::{MapObject, ModelType};
+
+    #[test]
+    fn test_index_agrees_with_brute_force_queries() {
+        let mut map = Map::new("Test".to_string());
+        for i in 0..40 {
+            let mut object = MapObject::new(ModelType::Cube);
+            let (x, z) = ((i * 7 % 45) as f32 - 22.0, (i * 11 % 45) as f32 - 22.0);
+            object.set_scale(Vector3::new(1.0 + (i % 3) as f32, 1.0 + (i % 4) as f32, 1.0));
+            object.set_position(Vector3::new(x, 1.0, z));
+            map.objects.push(object);
+        }
+        // One wall poking out of the world
+        let mut edge = MapObject::new(ModelType::Cube);
+        edge.set_scale(Vector3::new(2.0, 3.0, 10.0));
+        edge.set_position(Vector3::new(WORLD_HALF_SIZE, 1.5, 0.0));
+        map.objects.push(edge);
+        for i in (0..40).step_by(5) {
+            map.set_object_collision(i, Collision::ALL[i / 5 % 4]);
+        }
+
+        let index = SpatialIndex::build(&map);
+        let eye = |x: f32, z: f32| Vector3::new(x, 1.5, z);
+        let points = [eye(-24.0, -24.0), eye(24.0, 20.0), eye(0.0, 0.0), eye(-30.0, 3.0), eye(30.0, -2.0), eye(10.0, -24.5)];
+        for &from in &points {
+            for &to in &points {
+                assert_eq!(index.is_line_blocked(from, to), map.is_line_blocked(from, to));
+                if from == to {
+                    continue;
+                }
+                let ray = Ray::new(from, (to - from).normalized());
+                let exact = map.raycast(ray, 40.0).map(|(point, _)| point);
+                assert_eq!(index.raycast(ray, 40.0).map(|(point, _)| point), exact);
+            }
+            for z in -25..25 {
+                let feet = Vector3::new(from.x.clamp(-24.0, 24.0), 0.0, z as f32);
+                assert_eq!(index.blocks_player(feet, 0.3, 1.8, 0.4), map.blocks_player(feet, 0.3, 1.8, 0.4));
+            }
+        }
+    }
+}
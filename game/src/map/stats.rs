@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use super::map::{Collision, Map, ModelType};
+
+/// Largest serialized map the chain account accepts
+pub const MAP_BYTE_BUDGET: usize = 10240;
+
+/// Object count past which frame time on low-end machines suffers
+pub const MAP_OBJECT_BUDGET: usize = 400;
+
+/// Share of a budget at which the stats panel starts warning
+pub const BUDGET_WARNING_RATIO: f32 = 0.8;
+
+/// Every model type, in the order the stats panel lists them
+pub const MODEL_TYPES: [ModelType; 8] = [
+    ModelType::Cube,
+    ModelType::Rectangle,
+    ModelType::Triangle,
+    ModelType::Sphere,
+    ModelType::Cylinder,
+    ModelType::Plane,
+    ModelType::SpawnPointBlue,
+    ModelType::SpawnPointRed,
+];
+
+/// Draw calls `MapObject::draw` issues for one object of this type (solid + wireframe pieces)
+fn draw_calls(model_type: ModelType) -> usize {
+    match model_type {
+        ModelType::Cube | ModelType::Rectangle | ModelType::Sphere | ModelType::Cylinder => 2,
+        ModelType::Triangle => 4,
+        ModelType::Plane => 5,
+        ModelType::SpawnPointBlue | ModelType::SpawnPointRed => 4,
+    }
+}
+
+/// How close a value is to its budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetLevel {
+    Ok,
+    Warning,
+    Over,
+}
+
+impl BudgetLevel {
+    pub fn of(value: usize, budget: usize) -> Self {
+        if value > budget {
+            BudgetLevel::Over
+        } else if value as f32 >= budget as f32 * BUDGET_WARNING_RATIO {
+            BudgetLevel::Warning
+        } else {
+            BudgetLevel::Ok
+        }
+    }
+}
+
+/// Snapshot of a map's size and rendering/collision cost for the editor stats panel
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MapStats {
+    /// Object count per type, in `MODEL_TYPES` order
+    pub counts: [usize; MODEL_TYPES.len()],
+    pub total_objects: usize,
+    /// Estimated draw calls per frame for the map geometry
+    pub draw_calls: usize,
+    /// Objects that produce a collision box (everything but spawn points and non-solid decoration)
+    pub collision_boxes: usize,
+    /// Borsh-serialized size
+    pub bytes: usize,
+    /// Rough compressed size of the serialized bytes
+    pub compressed_bytes: usize,
+}
+
+impl MapStats {
+    pub fn of(map: &Map) -> Self {
+        let mut stats = MapStats { total_objects: map.objects.len(), ..Default::default() };
+        for (index, object) in map.objects.iter().enumerate() {
+            if let Some(slot) = MODEL_TYPES.iter().position(|t| *t == object.model_type) {
+                stats.counts[slot] += 1;
+            }
+            stats.draw_calls += draw_calls(object.model_type);
+            if object.collision_bounds().is_some() && map.collision(index) != Collision::NonSolid {
+                stats.collision_boxes += 1;
+            }
+        }
+
+        let bytes = map.to_borsh_bytes().unwrap_or_default();
+        stats.bytes = bytes.len();
+        stats.compressed_bytes = estimate_compressed_size(&bytes);
+        stats
+    }
+
+    pub fn byte_level(&self) -> BudgetLevel {
+        BudgetLevel::of(self.bytes, MAP_BYTE_BUDGET)
+    }
+
+    pub fn object_level(&self) -> BudgetLevel {
+        BudgetLevel::of(self.total_objects, MAP_OBJECT_BUDGET)
+    }
+}
+
+/// Size after a simple LZ77 pass (greedy matches of 3+ bytes within 4 KB, flag bit per token).
+/// Close enough to gzip on map data to show how much repetition a map has.
+pub fn estimate_compressed_size(bytes: &[u8]) -> usize {
+    const WINDOW: usize = 4096;
+    const MIN_MATCH: usize = 3;
+    const MAX_MATCH: usize = 18;
+
+    // Last position each 3-byte prefix was seen at (single-entry hash chain keeps this linear)
+    let mut last_seen: HashMap<[u8; MIN_MATCH], usize> = HashMap::new();
+    let mut bits = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let prefix = bytes.get(i..i + MIN_MATCH).map(|p| [p[0], p[1], p[2]]);
+        let length = prefix
+            .and_then(|prefix| last_seen.insert(prefix, i))
+            .filter(|&j| i - j <= WINDOW)
+            .map_or(0, |j| {
+                bytes[j..].iter().zip(&bytes[i..]).take(MAX_MATCH).take_while(|(a, b)| a == b).count()
+            });
+
+        if length >= MIN_MATCH {
+            bits += 1 + 16; // flag + 12-bit offset and 4-bit length
+            i += length;
+        } else {
+            bits += 1 + 8; // flag + literal
+            i += 1;
+        }
+    }
+    bits.div_ceil(8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::MapObject;
+
+    #[test]
+    fn test_counts_and_budgets() {
+        let mut map = Map::new("Stats".to_string());
+        for _ in 0..3 {
+            map.add_object(MapObject::new(ModelType::Cube));
+        }
+        map.add_object(MapObject::new(ModelType::SpawnPointBlue));
+
+        let stats = MapStats::of(&map);
+        assert_eq!(stats.counts[0], 3);
+        assert_eq!(stats.counts[6], 1);
+        assert_eq!(stats.collision_boxes, 3);
+        assert_eq!(stats.draw_calls, 10);
+        // Identical objects compress well
+        assert!(stats.compressed_bytes < stats.bytes);
+
+        assert_eq!(BudgetLevel::of(100, 400), BudgetLevel::Ok);
+        assert_eq!(BudgetLevel::of(350, 400), BudgetLevel::Warning);
+        assert_eq!(BudgetLevel::of(401, 400), BudgetLevel::Over);
+    }
+}
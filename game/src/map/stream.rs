@@ -0,0 +1,92 @@
+//! Streaming for maps too large for one on-chain account
+//!
+//! A published map past `MAP_CHUNK_OBJECTS` objects is split across several map accounts:
+//! the head keeps the map id and the first chunk (spawn points first, then the objects
+//! nearest the center) and its description carries a manifest with the chunk count. The
+//! match starts on the head alone; the bridge fetches the other chunks in the background and
+//! queues them, and `GameState` adds one per frame to the running map.
+
+/// Objects stored per map account (19 Borsh bytes each, so a chunk stays inside `MAP_BYTE_BUDGET`)
+pub const MAP_CHUNK_OBJECTS: usize = 500;
+
+/// Which chunks of a streamed map have arrived
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapStream {
+    map_id: String,
+    received: Vec<bool>,
+}
+
+impl MapStream {
+    /// Stream for `map_id` split into `chunks` chunks; the head (chunk 0) is already loaded
+    pub fn new(map_id: String, chunks: usize) -> Self {
+        let mut received = vec![false; chunks.max(1)];
+        received[0] = true;
+        Self { map_id, received }
+    }
+
+    /// Stream announced by the bridge's map data result (`mapId` and `chunks`), if the map
+    /// has chunks beyond the head
+    pub fn from_result(result: &serde_json::Value) -> Option<Self> {
+        let chunks = result.get("chunks")?.as_u64()? as usize;
+        let map_id = result.get("mapId")?.as_str()?;
+        (chunks > 1).then(|| Self::new(map_id.to_string(), chunks))
+    }
+
+    /// Mark chunk `index` of `map_id` received; false for another map's chunk, a repeat or an
+    /// index outside the manifest (the caller drops those)
+    pub fn accept(&mut self, map_id: &str, index: usize) -> bool {
+        if map_id != self.map_id {
+            return false;
+        }
+        match self.received.get_mut(index) {
+            Some(received) if !*received => {
+                *received = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn received_chunks(&self) -> usize {
+        self.received.iter().filter(|r| **r).count()
+    }
+
+    pub fn total_chunks(&self) -> usize {
+        self.received.len()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.received.iter().all(|r| *r)
+    }
+
+    /// Share of the chunks received, 0 to 1
+    pub fn progress(&self) -> f32 {
+        self.received_chunks() as f32 / self.total_chunks() as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_accepts_each_chunk_once() {
+        let mut stream = MapStream::new("arena".to_string(), 3);
+        assert_eq!(stream.received_chunks(), 1);
+        assert!(!stream.is_complete());
+
+        assert!(!stream.accept("other", 1), "chunks of a previous map are ignored");
+        assert!(!stream.accept("arena", 0), "the head is already loaded");
+        assert!(!stream.accept("arena", 3), "past the manifest");
+        assert!(stream.accept("arena", 2));
+        assert!(!stream.accept("arena", 2), "repeats are ignored");
+        assert!((stream.progress() - 2.0 / 3.0).abs() < 1e-6);
+
+        assert!(stream.accept("arena", 1));
+        assert!(stream.is_complete());
+        assert_eq!(stream.progress(), 1.0);
+
+        // A map that fits one account is complete from the start
+        assert!(MapStream::new("small".to_string(), 1).is_complete());
+    }
+}
@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use borsh::{BorshSerialize, BorshDeserialize};
+use crate::i18n::t;
+
+/// Longest tag, weapon id or objective text a trigger stores
+pub const TRIGGER_TEXT_MAX_LEN: usize = 48;
+
+/// Sounds a trigger can play: the game's own effects, since map files can't carry audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub enum TriggerSound {
+    Pop,
+    Click,
+    Pickup,
+    DryFire,
+}
+
+impl TriggerSound {
+    pub const ALL: [TriggerSound; 4] = [TriggerSound::Pop, TriggerSound::Click, TriggerSound::Pickup, TriggerSound::DryFire];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TriggerSound::Pop => t("editor.trigger.sound_pop"),
+            TriggerSound::Click => t("editor.trigger.sound_click"),
+            TriggerSound::Pickup => t("editor.trigger.sound_pickup"),
+            TriggerSound::DryFire => t("editor.trigger.sound_dry_fire"),
+        }
+    }
+
+    /// Name scripts use (`sound dry-fire`)
+    pub fn id(&self) -> &'static str {
+        match self {
+            TriggerSound::Pop => "pop",
+            TriggerSound::Click => "click",
+            TriggerSound::Pickup => "pickup",
+            TriggerSound::DryFire => "dry-fire",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<TriggerSound> {
+        TriggerSound::ALL.into_iter().find(|s| s.id() == id)
+    }
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            TriggerSound::Pop => "/assets/grenade/audio/smoke-pop.wav",
+            TriggerSound::Click => "/assets/gun/audio/weapon-switch.wav",
+            TriggerSound::Pickup => "/assets/gun/audio/weapon-pickup.wav",
+            TriggerSound::DryFire => "/assets/gun/audio/dry-fire.wav",
+        }
+    }
+}
+
+/// What a trigger does when the local player walks into it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub enum TriggerAction {
+    /// Move the player on top of the first object with this tag
+    TeleportToTag(String),
+    /// Slide every object with this tag open (doors, gates)
+    OpenMover(String),
+    /// Hand the player a weapon (`WeaponKind::id`)
+    GrantPickup(String),
+    /// Play a sound at the trigger
+    PlaySound(TriggerSound),
+    /// Show the player an objective
+    StartObjective(String),
+    /// Nothing by itself: runs the map script's `on <tag>` rules for the object's tags
+    Script,
+}
+
+impl TriggerAction {
+    /// One action of each kind with empty parameters, in the editor's order
+    pub fn kinds() -> [TriggerAction; 6] {
+        [
+            TriggerAction::TeleportToTag(String::new()),
+            TriggerAction::OpenMover(String::new()),
+            TriggerAction::GrantPickup("shotgun".to_string()),
+            TriggerAction::PlaySound(TriggerSound::Pop),
+            TriggerAction::StartObjective(String::new()),
+            TriggerAction::Script,
+        ]
+    }
+
+    /// Position of this action's kind in `kinds`
+    pub fn kind_index(&self) -> usize {
+        match self {
+            TriggerAction::TeleportToTag(_) => 0,
+            TriggerAction::OpenMover(_) => 1,
+            TriggerAction::GrantPickup(_) => 2,
+            TriggerAction::PlaySound(_) => 3,
+            TriggerAction::StartObjective(_) => 4,
+            TriggerAction::Script => 5,
+        }
+    }
+
+    /// Localized name of the action's kind
+    pub fn label(&self) -> &'static str {
+        match self {
+            TriggerAction::TeleportToTag(_) => t("editor.trigger.teleport"),
+            TriggerAction::OpenMover(_) => t("editor.trigger.open_mover"),
+            TriggerAction::GrantPickup(_) => t("editor.trigger.grant_pickup"),
+            TriggerAction::PlaySound(_) => t("editor.trigger.play_sound"),
+            TriggerAction::StartObjective(_) => t("editor.trigger.objective"),
+            TriggerAction::Script => t("editor.trigger.script"),
+        }
+    }
+
+    /// Tag, weapon id or objective text (None for sounds and script triggers)
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            TriggerAction::TeleportToTag(text)
+            | TriggerAction::OpenMover(text)
+            | TriggerAction::GrantPickup(text)
+            | TriggerAction::StartObjective(text) => Some(text),
+            TriggerAction::PlaySound(_) | TriggerAction::Script => None,
+        }
+    }
+
+    /// Tags are tidied like object tags, objective text is kept as typed
+    pub fn set_text(&mut self, text: &str) {
+        let text: String = text.chars().take(TRIGGER_TEXT_MAX_LEN).collect();
+        match self {
+            TriggerAction::TeleportToTag(tag) | TriggerAction::OpenMover(tag) => {
+                *tag = super::map::normalize_tags(&text).replace(',', "");
+            }
+            TriggerAction::GrantPickup(weapon) => *weapon = text,
+            TriggerAction::StartObjective(objective) => *objective = text,
+            TriggerAction::PlaySound(_) | TriggerAction::Script => {}
+        }
+    }
+}
+
+/// A map object turned into a trigger volume: its bounds fire `action` when the local
+/// player's feet enter them. Trigger objects are hidden in-game and nothing collides with them
+/// Stored sparsely like labels
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct ObjectTrigger {
+    /// Index into `Map::objects`
+    pub object: u16,
+    pub action: TriggerAction,
+    /// Fire only the first time per round instead of on every entry
+    pub once: bool,
+}
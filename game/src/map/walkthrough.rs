@@ -0,0 +1,39 @@
+use raylib::prelude::*;
+
+use crate::game::{MovementTuning, MovementWorld, OpenGround, Player};
+use super::map::{Map, WORLD_HALF_SIZE};
+
+/// First-person preview of the map at player eye height, with the game's movement controls
+pub struct Walkthrough {
+    pub player: Player,
+    /// Whether map geometry blocks movement
+    pub collision: bool,
+    /// Editor camera to go back to when the walkthrough ends
+    pub editor_camera: Camera3D,
+}
+
+impl Walkthrough {
+    /// Drop onto the ground below the editor camera, facing the same way
+    pub fn new(editor_camera: Camera3D, collision: bool) -> Self {
+        let forward = editor_camera.target - editor_camera.position;
+        let start = Vector3::new(
+            editor_camera.position.x.clamp(-WORLD_HALF_SIZE + 1.0, WORLD_HALF_SIZE - 1.0),
+            0.0,
+            editor_camera.position.z.clamp(-WORLD_HALF_SIZE + 1.0, WORLD_HALF_SIZE - 1.0),
+        );
+
+        let mut player = Player::new(start);
+        if forward.x != 0.0 || forward.z != 0.0 {
+            player.yaw = forward.z.atan2(forward.x).to_degrees();
+        }
+        player.set_position(start);
+
+        Self { player, collision, editor_camera }
+    }
+
+    /// WASD / mouse look / Shift / Ctrl / Space like in a match, sliding along walls when collision is on
+    pub fn update(&mut self, rl: &RaylibHandle, delta: f32, map: &Map) {
+        let world: &dyn MovementWorld = if self.collision { map } else { &OpenGround };
+        self.player.update(rl, delta, None, None, &MovementTuning::default(), world);
+    }
+}
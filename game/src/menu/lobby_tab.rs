@@ -1,4 +1,5 @@
 use super::menu_state::MenuState;
+use crate::i18n::{t, tr};
 
 pub struct LobbyTab;
 
@@ -13,7 +14,7 @@ impl LobbyTab {
         // Title
         let _title_color = ui.push_style_color(imgui::StyleColor::Text, [0.08, 0.95, 0.58, 1.0]);
         ui.set_window_font_scale(1.5);
-        ui.text("FIND OR CREATE A MATCH");
+        ui.text(t("lobby.browser.title"));
         ui.set_window_font_scale(1.0);
         drop(_title_color);
 
@@ -24,7 +25,7 @@ impl LobbyTab {
         let _button_hover = ui.push_style_color(imgui::StyleColor::ButtonHovered, [0.48, 0.25, 0.75, 1.0]);
         let _button_active = ui.push_style_color(imgui::StyleColor::ButtonActive, [0.58, 0.35, 0.85, 1.0]);
 
-        if ui.button_with_size("+ CREATE ROOM", [200.0, 40.0]) {
+        if ui.button_with_size(t("lobby.browser.create_room"), [200.0, 40.0]) {
             menu_state.show_create_room_popup = true;
         }
 
@@ -37,7 +38,7 @@ impl LobbyTab {
         ui.same_line();
 
         // Refresh button
-        if ui.button_with_size("REFRESH", [120.0, 40.0]) {
+        if ui.button_with_size(t("lobby.browser.refresh"), [120.0, 40.0]) {
             menu_state.load_games_from_blockchain();
         }
 
@@ -46,7 +47,7 @@ impl LobbyTab {
         ui.same_line();
 
         // Test button
-        if ui.button_with_size("TEST", [80.0, 40.0]) {
+        if ui.button_with_size(t("lobby.browser.test"), [80.0, 40.0]) {
             menu_state.test_blockchain_connection();
         }
 
@@ -55,16 +56,22 @@ impl LobbyTab {
         ui.same_line();
 
         // Leave Game button
-        if ui.button_with_size("LEAVE GAME", [120.0, 40.0]) {
+        if ui.button_with_size(t("lobby.browser.leave_game"), [120.0, 40.0]) {
             menu_state.leave_current_game();
         }
 
+        // Last bridge error (already in a game, out of SOL, RPC down, ...)
+        if let Some(error) = &menu_state.last_error {
+            ui.dummy([0.0, 10.0]);
+            ui.text_colored([1.0, 0.27, 0.27, 1.0], error.user_message());
+        }
+
         ui.dummy([0.0, 20.0]);
         ui.separator();
         ui.dummy([0.0, 10.0]);
 
         // Room list header
-        ui.text("AVAILABLE ROOMS");
+        ui.text(t("lobby.browser.available_rooms"));
         ui.dummy([0.0, 5.0]);
 
         // Room list
@@ -74,11 +81,11 @@ impl LobbyTab {
             .build(|| {
                 if menu_state.available_rooms.is_empty() {
                     ui.dummy([0.0, 50.0]);
-                    ui.text_colored([0.8, 0.8, 0.8, 1.0], "No games loaded from blockchain");
+                    ui.text_colored([0.8, 0.8, 0.8, 1.0], t("lobby.browser.no_games"));
                     ui.dummy([0.0, 10.0]);
-                    ui.text_colored([0.6, 0.6, 0.6, 1.0], "1. Connect your wallet in the web interface");
-                    ui.text_colored([0.6, 0.6, 0.6, 1.0], "2. Click 'REFRESH' button to load games");
-                    ui.text_colored([0.6, 0.6, 0.6, 1.0], "3. Or create your own room to get started!");
+                    ui.text_colored([0.6, 0.6, 0.6, 1.0], t("lobby.browser.hint_wallet"));
+                    ui.text_colored([0.6, 0.6, 0.6, 1.0], t("lobby.browser.hint_refresh"));
+                    ui.text_colored([0.6, 0.6, 0.6, 1.0], t("lobby.browser.hint_create"));
                 } else {
                     let mut join_room_id: Option<String> = None;
                     
@@ -112,8 +119,8 @@ impl LobbyTab {
                                 ui.dummy([0.0, 5.0]);
 
                                 // Room info
-                                ui.text_colored([0.7, 0.7, 0.7, 1.0], format!("Map: {}", room.map));
-                                ui.text_colored([0.7, 0.7, 0.7, 1.0], format!("Host: {}", room.host));
+                                ui.text_colored([0.7, 0.7, 0.7, 1.0], tr!("lobby.browser.room_map", map = room.map));
+                                ui.text_colored([0.7, 0.7, 0.7, 1.0], tr!("lobby.browser.room_host", host = room.host));
 
                                 ui.same_line();
                                 ui.dummy([200.0, 0.0]);
@@ -125,7 +132,7 @@ impl LobbyTab {
                                 } else {
                                     [0.08, 0.95, 0.58, 1.0] // Solana teal
                                 };
-                                ui.text_colored(player_color, format!("{}/{} Players", room.current_players, room.max_players));
+                                ui.text_colored(player_color, tr!("lobby.browser.room_players", count = room.current_players, max = room.max_players));
 
                                 ui.same_line();
                                 ui.dummy([50.0, 0.0]);
@@ -133,11 +140,11 @@ impl LobbyTab {
 
                                 // Join button
                                 if is_full {
-                                    ui.text_disabled("FULL");
+                                    ui.text_disabled(t("lobby.browser.full"));
                                 } else {
                                     let _join_btn = ui.push_style_color(imgui::StyleColor::Button, [0.08, 0.95, 0.58, 0.8]);
                                     let _join_hover = ui.push_style_color(imgui::StyleColor::ButtonHovered, [0.10, 1.0, 0.65, 1.0]);
-                                    if ui.button_with_size("JOIN##".to_string() + &i.to_string(), [80.0, 30.0]) {
+                                    if ui.button_with_size(format!("{}##{}", t("lobby.browser.join"), i), [80.0, 30.0]) {
                                         join_room_id = Some(room.id.clone());
                                     }
                                     drop(_join_btn);
@@ -174,31 +181,31 @@ impl LobbyTab {
         menu_state.check_loaded_maps();
 
         ui.popup("Create Room", || {
-                ui.text("CREATE NEW ROOM");
+                ui.text(t("lobby.create.title"));
                 ui.separator();
                 ui.dummy([0.0, 10.0]);
 
-                ui.text("Room Name:");
+                ui.text(t("lobby.create.room_name"));
                 ui.input_text("##room_name", &mut menu_state.new_room_name)
                     .build();
 
                 ui.dummy([0.0, 10.0]);
 
-                ui.text("Max Players:");
+                ui.text(t("lobby.create.max_players"));
                 ui.slider("##max_players", 2, 16, &mut menu_state.new_room_max_players);
 
                 ui.dummy([0.0, 10.0]);
 
-                ui.text("Select Map:");
+                ui.text(t("lobby.create.select_map"));
                 ui.same_line();
 
                 // Refresh button
                 if menu_state.maps_loading {
-                    ui.text_disabled("⟳ Refresh");
+                    ui.text_disabled(t("lobby.create.refresh_maps"));
                 } else {
                     let _refresh_color = ui.push_style_color(imgui::StyleColor::Button, [0.3, 0.5, 0.8, 0.8]);
                     let _refresh_hover = ui.push_style_color(imgui::StyleColor::ButtonHovered, [0.4, 0.6, 0.9, 1.0]);
-                    if ui.button("⟳ Refresh") {
+                    if ui.button(t("lobby.create.refresh_maps")) {
                         // Reset state and fetch again
                         menu_state.maps_loaded = false;
                         menu_state.maps_loading = false;
@@ -213,16 +220,20 @@ impl LobbyTab {
 
                 // Display maps from Solana
                 if menu_state.maps_loading {
-                    ui.text_colored([0.7, 0.7, 0.0, 1.0], "Loading maps from Solana...");
+                    ui.text_colored([0.7, 0.7, 0.0, 1.0], t("lobby.create.loading_maps"));
                 } else if menu_state.available_maps.is_empty() {
-                    ui.text_colored([0.9, 0.5, 0.0, 1.0], "No maps found!");
-                    ui.text_colored([0.7, 0.7, 0.7, 1.0], "Create a map in the Map Editor first");
+                    ui.text_colored([0.9, 0.5, 0.0, 1.0], t("lobby.create.no_maps"));
+                    ui.text_colored([0.7, 0.7, 0.7, 1.0], t("lobby.create.no_maps_hint"));
                 } else {
                     for map in &menu_state.available_maps {
                         let label = format!("{} - {}", map.name, map.description);
                         if ui.radio_button(&label, &mut &menu_state.selected_map_for_room, &map.id) {
                             menu_state.selected_map_for_room = map.id.clone();
                         }
+                        if map.play_count > 0 {
+                            ui.same_line();
+                            ui.text_disabled(format!("{} plays", map.play_count));
+                        }
                     }
                 }
 
@@ -233,18 +244,18 @@ impl LobbyTab {
                 let can_create = !menu_state.new_room_name.is_empty() && !menu_state.selected_map_for_room.is_empty();
 
                 if !can_create {
-                    ui.text_disabled("CREATE");
+                    ui.text_disabled(t("lobby.create.create"));
                     if menu_state.new_room_name.is_empty() {
                         ui.same_line();
-                        ui.text_colored([0.7, 0.3, 0.3, 1.0], "(Enter a room name)");
+                        ui.text_colored([0.7, 0.3, 0.3, 1.0], t("lobby.create.need_name"));
                     } else if menu_state.selected_map_for_room.is_empty() {
                         ui.same_line();
-                        ui.text_colored([0.7, 0.3, 0.3, 1.0], "(Select a map)");
+                        ui.text_colored([0.7, 0.3, 0.3, 1.0], t("lobby.create.need_map"));
                     }
                 } else {
                     let _create_btn = ui.push_style_color(imgui::StyleColor::Button, [0.08, 0.95, 0.58, 0.8]);
-                    if ui.button("CREATE") {
-                        println!("🔘 CREATE button clicked!");
+                    if ui.button(t("lobby.create.create")) {
+                        log_info!(Solana, "🔘 CREATE button clicked!");
                         menu_state.create_room();
                         ui.close_current_popup();
                     }
@@ -253,7 +264,7 @@ impl LobbyTab {
 
                 ui.same_line();
 
-                if ui.button("CANCEL") {
+                if ui.button(t("lobby.create.cancel")) {
                     menu_state.show_create_room_popup = false;
                     ui.close_current_popup();
                 }
@@ -1,4 +1,5 @@
 use raylib::prelude::*;
+use crate::i18n::t;
 
 /// Modern color palette with vibrant accents
 pub struct UIColors {
@@ -437,7 +438,7 @@ impl TeamRoster {
                 2.0,
                 colors.accent_green,
             );
-            d.draw_text("✓ READY", (badge_x + 8.0) as i32, (badge_y + 5.0) as i32, 16, colors.accent_green);
+            d.draw_text(t("lobby.room.badge_ready"), (badge_x + 8.0) as i32, (badge_y + 5.0) as i32, 16, colors.accent_green);
         } else {
             // Gray not ready badge
             d.draw_rectangle_rec(
@@ -449,7 +450,7 @@ impl TeamRoster {
                 1.0,
                 colors.text_dim,
             );
-            d.draw_text("WAITING", (badge_x + 8.0) as i32, (badge_y + 5.0) as i32, 15, colors.text_dim);
+            d.draw_text(t("lobby.room.badge_waiting"), (badge_x + 8.0) as i32, (badge_y + 5.0) as i32, 15, colors.text_dim);
         }
     }
 
@@ -460,7 +461,7 @@ impl TeamRoster {
             1.0,
             Color::new(60, 70, 80, 150),
         );
-        d.draw_text("Empty Slot", x as i32 + 10, y as i32 + 8, 16, colors.text_secondary);
+        d.draw_text(t("lobby.room.empty"), x as i32 + 10, y as i32 + 8, 16, colors.text_secondary);
     }
 }
 
@@ -484,7 +485,7 @@ pub struct GamesList {
 impl GamesList {
     pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
         Self {
-            panel: UIPanel::new(x, y, width, height, "AVAILABLE GAMES".to_string(), Color::new(0, 255, 150, 255)),
+            panel: UIPanel::new(x, y, width, height, t("lobby.browser.available_games").to_string(), Color::new(0, 255, 150, 255)),
             games: Vec::new(),
             selected_index: None,
             scroll_offset: 0.0,
@@ -525,8 +526,8 @@ impl GamesList {
 
         // Draw "No games available" if list is empty
         if self.games.is_empty() {
-            let text = "No games available";
-            let subtext = "Click REFRESH or CREATE GAME to get started";
+            let text = t("lobby.browser.no_games_available");
+            let subtext = t("lobby.browser.no_games_hint");
 
             let text_width = d.measure_text(text, 24);
             let subtext_width = d.measure_text(subtext, 14);
@@ -633,7 +634,7 @@ impl GamesList {
         d.draw_text(&count_text, count_text_x, (badge_y + 7.0) as i32, 18, colors.text_primary);
 
         // Players label
-        d.draw_text("PLAYERS", (badge_x + 8.0) as i32, (y + 8.0) as i32, 10, colors.text_dim);
+        d.draw_text(t("lobby.browser.players_label"), (badge_x + 8.0) as i32, (y + 8.0) as i32, 10, colors.text_dim);
     }
 
     pub fn update(&mut self, mouse_pos: Vector2, mouse_wheel: f32) -> Option<usize> {
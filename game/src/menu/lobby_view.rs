@@ -1,4 +1,5 @@
 use super::menu_state::MenuState;
+use crate::i18n::{t, tr};
 
 pub struct LobbyView;
 
@@ -15,7 +16,7 @@ impl LobbyView {
         // Title
         let _title_color = ui.push_style_color(imgui::StyleColor::Text, [0.08, 0.95, 0.58, 1.0]);
         ui.set_window_font_scale(1.5);
-        ui.text("GAME LOBBY");
+        ui.text(t("lobby.room.title"));
         ui.set_window_font_scale(1.0);
         drop(_title_color);
 
@@ -23,9 +24,9 @@ impl LobbyView {
 
         // Lobby info header
         if let Some(lobby_id) = &menu_state.current_lobby_id {
-            ui.text_colored([0.8, 0.8, 0.8, 1.0], &format!("Lobby ID: {}...{}", 
-                &lobby_id[0..8], 
-                &lobby_id[lobby_id.len()-8..]
+            ui.text_colored([0.8, 0.8, 0.8, 1.0], tr!("lobby.room.id",
+                start = &lobby_id[0..8],
+                end = &lobby_id[lobby_id.len()-8..]
             ));
         }
 
@@ -41,7 +42,7 @@ impl LobbyView {
             .build(|| {
                 let _team_color = ui.push_style_color(imgui::StyleColor::Text, [0.2, 0.8, 1.0, 1.0]);
                 ui.set_window_font_scale(1.2);
-                ui.text("TEAM A");
+                ui.text(t("lobby.room.team_a"));
                 ui.set_window_font_scale(1.0);
                 drop(_team_color);
 
@@ -58,7 +59,7 @@ impl LobbyView {
                 // Show empty slots
                 let max_players = 5; // Default max players per team
                 for i in menu_state.lobby_team_a.len()..max_players {
-                    ui.text_colored([0.5, 0.5, 0.5, 1.0], &format!("{}. Empty Slot", i + 1));
+                    ui.text_colored([0.5, 0.5, 0.5, 1.0], tr!("lobby.room.empty_slot", number = i + 1));
                 }
             });
 
@@ -72,7 +73,7 @@ impl LobbyView {
             .build(|| {
                 let _team_color = ui.push_style_color(imgui::StyleColor::Text, [1.0, 0.4, 0.4, 1.0]);
                 ui.set_window_font_scale(1.2);
-                ui.text("TEAM B");
+                ui.text(t("lobby.room.team_b"));
                 ui.set_window_font_scale(1.0);
                 drop(_team_color);
 
@@ -89,7 +90,7 @@ impl LobbyView {
                 // Show empty slots
                 let max_players = 5; // Default max players per team
                 for i in menu_state.lobby_team_b.len()..max_players {
-                    ui.text_colored([0.5, 0.5, 0.5, 1.0], &format!("{}. Empty Slot", i + 1));
+                    ui.text_colored([0.5, 0.5, 0.5, 1.0], tr!("lobby.room.empty_slot", number = i + 1));
                 }
             });
 
@@ -101,7 +102,7 @@ impl LobbyView {
         let _button_active = ui.push_style_color(imgui::StyleColor::ButtonActive, [0.58, 0.35, 0.85, 1.0]);
 
         // Leave Lobby button (all players)
-        if ui.button_with_size("LEAVE LOBBY", [150.0, 40.0]) {
+        if ui.button_with_size(t("lobby.room.leave"), [150.0, 40.0]) {
             menu_state.leave_lobby();
             menu_state.in_lobby = false;
             menu_state.current_lobby_id = None;
@@ -117,7 +118,7 @@ impl LobbyView {
         ui.same_line();
 
         // Ready/Unready button (all players)
-        let ready_text = if menu_state.player_ready_state { "UNREADY" } else { "READY" };
+        let ready_text = if menu_state.player_ready_state { t("lobby.room.unready") } else { t("lobby.room.ready") };
         let ready_color = if menu_state.player_ready_state {
             [0.8, 0.4, 0.0, 1.0] // Orange for unready
         } else {
@@ -139,7 +140,7 @@ impl LobbyView {
         ]);
 
         if ui.button_with_size(ready_text, [150.0, 40.0]) {
-            println!("🖱️ READY button clicked!");
+            log_info!(Solana, "🖱️ READY button clicked!");
             menu_state.toggle_ready_state();
         }
 
@@ -157,7 +158,7 @@ impl LobbyView {
             let _start_hover = ui.push_style_color(imgui::StyleColor::ButtonHovered, [0.3, 0.9, 0.3, 1.0]);
             let _start_active = ui.push_style_color(imgui::StyleColor::ButtonActive, [0.4, 1.0, 0.4, 1.0]);
 
-            if ui.button_with_size("START GAME", [150.0, 40.0]) {
+            if ui.button_with_size(t("lobby.room.start"), [150.0, 40.0]) {
                 menu_state.start_lobby_game();
             }
 
@@ -171,7 +172,7 @@ impl LobbyView {
         ui.same_line();
 
         // Refresh button
-        if ui.button_with_size("REFRESH", [120.0, 40.0]) {
+        if ui.button_with_size(t("lobby.browser.refresh"), [120.0, 40.0]) {
             menu_state.fetch_lobby_data();
         }
 
@@ -183,20 +184,20 @@ impl LobbyView {
 
         // Status messages
         if menu_state.joining_lobby_pending {
-            ui.text_colored([0.8, 0.8, 0.0, 1.0], "Joining lobby...");
+            ui.text_colored([0.8, 0.8, 0.0, 1.0], t("lobby.room.joining"));
         }
 
         if menu_state.starting_game_pending {
-            ui.text_colored([0.8, 0.8, 0.0, 1.0], "Starting game...");
+            ui.text_colored([0.8, 0.8, 0.0, 1.0], t("lobby.room.starting"));
         }
 
         // Show lobby leader info
         if menu_state.is_lobby_leader {
-            ui.text_colored([0.0, 1.0, 0.0, 1.0], "You are the lobby leader");
+            ui.text_colored([0.0, 1.0, 0.0, 1.0], t("lobby.room.you_lead"));
         } else if let Some(leader) = &menu_state.lobby_leader {
-            ui.text_colored([0.8, 0.8, 0.8, 1.0], &format!("Lobby leader: {}...{}", 
-                &leader[0..8], 
-                &leader[leader.len()-8..]
+            ui.text_colored([0.8, 0.8, 0.8, 1.0], tr!("lobby.room.leader",
+                start = &leader[0..8],
+                end = &leader[leader.len()-8..]
             ));
         }
     }
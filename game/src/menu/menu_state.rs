@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
+use crate::net::BridgeError;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MenuTab {
     MapEditor,
+    /// The web lobby is up; the canvas shows the backdrop scene behind it
+    Lobby,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +23,14 @@ pub struct AvailableMap {
     pub id: String,
     pub name: String,
     pub description: String,
+    /// Matches started on this map (counted from the matchmaking program's games)
+    #[serde(default)]
+    pub play_count: u64,
+}
+
+/// Most played maps first, then alphabetical; same order as the web map list
+pub fn sort_maps_by_plays(maps: &mut [AvailableMap]) {
+    maps.sort_by(|a, b| b.play_count.cmp(&a.play_count).then_with(|| a.name.cmp(&b.name)));
 }
 
 pub struct MenuState {
@@ -74,6 +85,9 @@ pub struct MenuState {
 
     /// Player state polling
     pub check_player_game_pending: bool, // Flag to indicate we're checking player's current game
+
+    /// Last bridge error, shown to the player until the next one (or a success)
+    pub last_error: Option<BridgeError>,
 }
 
 impl MenuState {
@@ -113,6 +127,7 @@ impl MenuState {
             current_game_pubkey: None,
             waiting_for_map_data: false,
             check_player_game_pending: false,
+            last_error: None,
         };
         
         // Games will be loaded manually via the REFRESH button
@@ -122,13 +137,13 @@ impl MenuState {
     }
 
     pub fn create_room(&mut self) {
-        println!("🔍 Debug: create_room function called");
-        println!("🔍 Debug: Room name: '{}'", self.new_room_name);
+        log_debug!(Solana, "🔍 Debug: create_room function called");
+        log_debug!(Solana, "🔍 Debug: Room name: '{}'", self.new_room_name);
         if !self.new_room_name.is_empty() {
-            println!("🔍 Debug: Starting create_room function");
+            log_debug!(Solana, "🔍 Debug: Starting create_room function");
             #[cfg(target_os = "emscripten")]
             {
-                println!("🔍 Debug: Using Emscripten path (web)");
+                log_debug!(Solana, "🔍 Debug: Using Emscripten path (web)");
                 use std::ffi::CString;
 
                 extern "C" {
@@ -166,12 +181,12 @@ impl MenuState {
                                 console.log('🔍 Module.createGameResult type:', typeof Module.createGameResult);
                             }} else {{
                                 console.error('❌ Failed to create game - result is null');
-                                Module.createGameResult = JSON.stringify({{ error: 'Failed to create game' }});
+                                Module.createGameResult = JSON.stringify({{ error: {{ kind: 'Unknown', message: 'Failed to create game' }} }});
                                 console.log('❌ Set error result:', Module.createGameResult);
                             }}
                         }} catch (error) {{
                             console.error('❌ Error creating game:', error);
-                            Module.createGameResult = JSON.stringify({{ error: error.message }});
+                            Module.createGameResult = JSON.stringify({{ error: window.toBridgeError(error) }});
                         }}
                     }})();
                     "#,
@@ -179,9 +194,9 @@ impl MenuState {
                     self.selected_map_for_room.replace("'", "\\'")
                 );
 
-                println!("🎮 Calling JavaScript to create game...");
-                println!("📝 Room name: {}", self.new_room_name);
-                println!("🗺️ Map: {}", self.selected_map_for_room);
+                log_info!(Solana, "🎮 Calling JavaScript to create game...");
+                log_info!(Solana, "📝 Room name: {}", self.new_room_name);
+                log_info!(Solana, "🗺️ Map: {}", self.selected_map_for_room);
                 
                 let c_str = CString::new(js_code).unwrap();
                 unsafe {
@@ -195,12 +210,12 @@ impl MenuState {
                 
                 // Set pending state
                 self.create_game_pending = true;
-                println!("⏳ Game creation pending...");
+                log_info!(Solana, "⏳ Game creation pending...");
             }
 
             #[cfg(not(target_os = "emscripten"))]
             {
-                println!("🔍 Debug: Using native path (not web)");
+                log_debug!(Solana, "🔍 Debug: Using native path (not web)");
                 // For native builds, just add to local rooms
                 let new_room = Room {
                     id: format!("room_{}", self.available_rooms.len() + 1),
@@ -218,15 +233,15 @@ impl MenuState {
             self.new_room_max_players = 10;
             self.show_create_room_popup = false;
             
-            println!("🔍 Debug: create_room function completed");
+            log_debug!(Solana, "🔍 Debug: create_room function completed");
         }
     }
 
     /// Load available games from the blockchain
     #[cfg(target_os = "emscripten")]
     pub fn load_games_from_blockchain(&mut self) {
-        println!("🔍 Loading games from blockchain...");
-        println!("🔍 Current rooms count: {}", self.available_rooms.len());
+        log_debug!(Solana, "🔍 Loading games from blockchain...");
+        log_debug!(Solana, "🔍 Current rooms count: {}", self.available_rooms.len());
         
         extern "C" {
             pub fn emscripten_run_script(script: *const i8);
@@ -242,7 +257,7 @@ impl MenuState {
                 // Check if game bridge is available
                 if (!window.gameBridge) {
                     console.error('❌ Game bridge not available');
-                    Module.loadGamesResult = JSON.stringify({ error: 'Game bridge not initialized' });
+                    Module.loadGamesResult = JSON.stringify({ error: { kind: 'BridgeUnavailable' } });
                     return;
                 }
 
@@ -256,11 +271,11 @@ impl MenuState {
                     Module.loadGamesResult = JSON.stringify({ success: true, games: games });
                 } else {
                     console.error('❌ Failed to load games - invalid response');
-                    Module.loadGamesResult = JSON.stringify({ error: 'Failed to load games' });
+                    Module.loadGamesResult = JSON.stringify({ error: { kind: 'Unknown', message: 'Failed to load games' } });
                 }
             } catch (error) {
                 console.error('❌ Error loading games:', error);
-                Module.loadGamesResult = JSON.stringify({ error: error.message });
+                Module.loadGamesResult = JSON.stringify({ error: window.toBridgeError(error) });
             }
         })();
         "#;
@@ -316,26 +331,27 @@ impl MenuState {
         }
 
         // Parse and handle result
-        println!("🔍 Load games result JSON: {}", result_json);
+        log_debug!(Solana, "🔍 Load games result JSON: {}", result_json);
         if let Ok(result) = serde_json::from_str::<serde_json::Value>(result_json) {
-            println!("🔍 Parsed result successfully: {:?}", result);
-            if let Some(error) = result.get("error") {
-                println!("❌ Failed to load games: {}", error);
+            log_debug!(Solana, "🔍 Parsed result successfully: {:?}", result);
+            if let Some(error) = BridgeError::from_result(&result) {
+                log_error!(Solana, "❌ Failed to load games: {}", error);
+                self.last_error = Some(error);
                 // Add fallback rooms if blockchain loading fails
                 self.add_fallback_rooms();
             } else if let Some(games) = result.get("games") {
                 if let Some(games_array) = games.as_array() {
-                    println!("🔍 Found {} games in blockchain response", games_array.len());
+                    log_debug!(Solana, "🔍 Found {} games in blockchain response", games_array.len());
                     // Clear existing rooms
                     self.available_rooms.clear();
                     
                     // Convert blockchain games to Room structs
                     for (i, game) in games_array.iter().enumerate() {
-                        println!("🔍 Processing game {}: {:?}", i, game);
+                        log_debug!(Solana, "🔍 Processing game {}: {:?}", i, game);
                         
                         // Debug: Show all available fields
                         if let Some(game_obj) = game.as_object() {
-                            println!("🔍 Available fields in game {}: {:?}", i, game_obj.keys().collect::<Vec<_>>());
+                            log_debug!(Solana, "🔍 Available fields in game {}: {:?}", i, game_obj.keys().collect::<Vec<_>>());
                         }
                         
                         if let (Some(public_key), Some(lobby_name), Some(map_name), Some(total_players), Some(max_players), Some(created_by)) = (
@@ -360,7 +376,7 @@ impl MenuState {
                             self.available_rooms.push(room);
                         }
                     }
-                    println!("✅ Loaded {} games from blockchain", self.available_rooms.len());
+                    log_info!(Solana, "✅ Loaded {} games from blockchain", self.available_rooms.len());
                 }
             }
         }
@@ -368,7 +384,7 @@ impl MenuState {
 
     #[cfg(not(target_os = "emscripten"))]
     pub fn load_games_from_blockchain(&mut self) {
-        println!("🔍 Debug: load_games_from_blockchain called but not in emscripten mode");
+        log_debug!(Solana, "🔍 Debug: load_games_from_blockchain called but not in emscripten mode");
         // For native builds, add some dummy data
         self.available_rooms = vec![
             Room {
@@ -389,7 +405,7 @@ impl MenuState {
 
     /// Add fallback rooms when blockchain loading fails
     fn add_fallback_rooms(&mut self) {
-        println!("🔍 Adding fallback rooms due to blockchain loading failure");
+        log_debug!(Solana, "🔍 Adding fallback rooms due to blockchain loading failure");
         self.available_rooms = vec![
             Room {
                 id: "fallback_1".to_string(),
@@ -413,7 +429,7 @@ impl MenuState {
     /// Test blockchain connection
     #[cfg(target_os = "emscripten")]
     pub fn test_blockchain_connection(&mut self) {
-        println!("🧪 Testing blockchain connection...");
+        log_info!(Solana, "🧪 Testing blockchain connection...");
         
         extern "C" {
             pub fn emscripten_run_script(script: *const i8);
@@ -429,7 +445,7 @@ impl MenuState {
                 // Check if game bridge is available
                 if (!window.gameBridge) {
                     console.error('❌ Game bridge not available');
-                    Module.testResult = JSON.stringify({ error: 'Game bridge not available' });
+                    Module.testResult = JSON.stringify({ error: { kind: 'BridgeUnavailable' } });
                     return;
                 }
 
@@ -458,7 +474,7 @@ impl MenuState {
 
             } catch (error) {
                 console.error('❌ Blockchain connection test failed:', error);
-                Module.testResult = JSON.stringify({ error: error.message });
+                Module.testResult = JSON.stringify({ error: window.toBridgeError(error) });
             }
         })();
         "#;
@@ -471,13 +487,13 @@ impl MenuState {
 
     #[cfg(not(target_os = "emscripten"))]
     pub fn test_blockchain_connection(&mut self) {
-        println!("🧪 Blockchain connection test not available in native build");
+        log_info!(Solana, "🧪 Blockchain connection test not available in native build");
     }
 
     /// Leave current game
     #[cfg(target_os = "emscripten")]
     pub fn leave_current_game(&mut self) {
-        println!("🚪 Leaving current game...");
+        log_info!(Solana, "🚪 Leaving current game...");
         
         extern "C" {
             pub fn emscripten_run_script(script: *const i8);
@@ -493,7 +509,7 @@ impl MenuState {
                 // Check if game bridge is available
                 if (!window.gameBridge) {
                     console.error('❌ Game bridge not available');
-                    Module.leaveGameResult = JSON.stringify({ error: 'Game bridge not available' });
+                    Module.leaveGameResult = JSON.stringify({ error: { kind: 'BridgeUnavailable' } });
                     return;
                 }
 
@@ -507,11 +523,11 @@ impl MenuState {
                     Module.leaveGameResult = JSON.stringify(result);
                 } else {
                     console.error('❌ Failed to leave game - result is null');
-                    Module.leaveGameResult = JSON.stringify({ error: 'Failed to leave game' });
+                    Module.leaveGameResult = JSON.stringify({ error: { kind: 'Unknown', message: 'Failed to leave game' } });
                 }
             } catch (error) {
                 console.error('❌ Error leaving game:', error);
-                Module.leaveGameResult = JSON.stringify({ error: error.message });
+                Module.leaveGameResult = JSON.stringify({ error: window.toBridgeError(error) });
             }
         })();
         "#;
@@ -524,7 +540,7 @@ impl MenuState {
 
     #[cfg(not(target_os = "emscripten"))]
     pub fn leave_current_game(&mut self) {
-        println!("🚪 Leave current game not available in native build");
+        log_info!(Solana, "🚪 Leave current game not available in native build");
     }
 
     /// Check for create game response (web only)
@@ -534,7 +550,7 @@ impl MenuState {
             return;
         }
         
-        println!("🔍 Checking for create game response... (pending: {})", self.create_game_pending);
+        log_debug!(Solana, "🔍 Checking for create game response... (pending: {})", self.create_game_pending);
 
         extern "C" {
             pub fn emscripten_run_script(script: *const i8);
@@ -548,31 +564,31 @@ impl MenuState {
         let has_result = unsafe {
             let result_ptr = emscripten_run_script_string(js_check.as_ptr());
             if result_ptr.is_null() {
-                println!("🔍 JavaScript check returned null");
+                log_debug!(Solana, "🔍 JavaScript check returned null");
                 return;
             }
             let result = std::ffi::CStr::from_ptr(result_ptr).to_str().unwrap_or("false");
-            println!("🔍 JavaScript check result: {}", result);
+            log_debug!(Solana, "🔍 JavaScript check result: {}", result);
             result == "true"
         };
 
         if !has_result {
-            println!("🔍 No result yet, continuing to wait...");
+            log_debug!(Solana, "🔍 No result yet, continuing to wait...");
             return;
         }
         
-        println!("🔍 Result found! Processing...");
+        log_debug!(Solana, "🔍 Result found! Processing...");
 
         // Get the result
         let js_get_result = CString::new("Module.createGameResult || '{}'").unwrap();
         let result_json = unsafe {
             let result_ptr = emscripten_run_script_string(js_get_result.as_ptr());
             if result_ptr.is_null() {
-                println!("🔍 JavaScript get result returned null");
+                log_debug!(Solana, "🔍 JavaScript get result returned null");
                 return;
             }
             let result = std::ffi::CStr::from_ptr(result_ptr).to_str().unwrap_or("{}");
-            println!("🔍 JavaScript get result: {}", result);
+            log_debug!(Solana, "🔍 JavaScript get result: {}", result);
             result
         };
 
@@ -586,37 +602,37 @@ impl MenuState {
         }
 
         // Parse and handle result
-        println!("🔍 Result JSON: {}", result_json);
+        log_debug!(Solana, "🔍 Result JSON: {}", result_json);
         if let Ok(result) = serde_json::from_str::<serde_json::Value>(result_json) {
-            println!("🔍 Parsed result: {:?}", result);
-            if let Some(error) = result.get("error") {
-                if let Some(error_str) = error.as_str() {
-                    if error_str == "PlayerAlreadyInGame" {
-                        println!("⚠️ Player is already in a game - cannot create new game");
-                        // Add a helpful room to show the error
-                        let error_room = Room {
+            log_debug!(Solana, "🔍 Parsed result: {:?}", result);
+            if let Some(error) = BridgeError::from_result(&result) {
+                // Add a room entry so the error shows up in the list
+                let error_room = match &error {
+                    BridgeError::PlayerAlreadyInGame { .. } => {
+                        log_warn!(Solana, "⚠️ Player is already in a game - cannot create new game");
+                        Room {
                             id: "error_already_in_game".to_string(),
                             name: "⚠️ Already in a game".to_string(),
                             map: "Leave current game first".to_string(),
                             current_players: 0,
                             max_players: 0,
                             host: "System".to_string(),
-                        };
-                        self.available_rooms.push(error_room);
-                    } else {
-                        println!("❌ Failed to create game: {}", error_str);
-                        // Add error room
-                        let error_room = Room {
+                        }
+                    }
+                    _ => {
+                        log_error!(Solana, "❌ Failed to create game: {:?}", error);
+                        Room {
                             id: "error_create_failed".to_string(),
-                            name: format!("❌ Create failed: {}", error_str),
+                            name: format!("❌ Create failed: {}", error.user_message()),
                             map: "Check console for details".to_string(),
                             current_players: 0,
                             max_players: 0,
                             host: "System".to_string(),
-                        };
-                        self.available_rooms.push(error_room);
+                        }
                     }
-                }
+                };
+                self.available_rooms.push(error_room);
+                self.last_error = Some(error);
             } else if let Some(game_pda) = result.get("gamePda") {
                 if let Some(pda_str) = game_pda.as_str() {
                     // Create room with on-chain data using stored pending data
@@ -629,10 +645,11 @@ impl MenuState {
                         host: "You".to_string(),
                     };
                     self.available_rooms.push(new_room);
-                    println!("✅ Game created successfully on-chain!");
+                    self.last_error = None;
+                    log_info!(Solana, "✅ Game created successfully on-chain!");
 
                     // Automatically join the created lobby (open lobby view)
-                    println!("🚪 Automatically entering the created lobby...");
+                    log_info!(Solana, "🚪 Automatically entering the created lobby...");
                     self.in_lobby = true;
                     self.current_lobby_id = Some(pda_str.to_string());
                     self.current_game_pubkey = Some(pda_str.to_string()); // Store for blockchain sync
@@ -662,7 +679,7 @@ impl MenuState {
 
     #[cfg(not(target_os = "emscripten"))]
     pub fn check_create_game_response(&mut self) {
-        println!("🔍 Debug: check_create_game_response called but not in emscripten mode");
+        log_debug!(Solana, "🔍 Debug: check_create_game_response called but not in emscripten mode");
         // No-op for native builds
     }
 
@@ -716,6 +733,14 @@ impl MenuState {
                     }
                 }
 
+                // Play counts decide the list order
+                const playCounts = await window.solanaMapBridge.getMapPlayCounts(
+                    mapsWithMetadata.map(map => map.id)
+                );
+                for (const map of mapsWithMetadata) {
+                    map.play_count = playCounts[map.id];
+                }
+
                 Module.userMapsData = JSON.stringify(mapsWithMetadata);
                 console.log('Loaded', mapsWithMetadata.length, 'user maps');
             } catch (error) {
@@ -757,7 +782,8 @@ impl MenuState {
             if let Ok(json_str) = c_str.to_str() {
                 if !json_str.is_empty() {
                     // Parse the JSON
-                    if let Ok(maps) = serde_json::from_str::<Vec<AvailableMap>>(json_str) {
+                    if let Ok(mut maps) = serde_json::from_str::<Vec<AvailableMap>>(json_str) {
+                        sort_maps_by_plays(&mut maps);
                         self.available_maps = maps;
                         self.maps_loaded = true;
                         self.maps_loading = false;
@@ -791,7 +817,7 @@ impl MenuState {
     /// Join a lobby by calling joinGame
     #[cfg(target_os = "emscripten")]
     pub fn join_lobby(&mut self, game_id: String) {
-        println!("🎮 Joining lobby: {}", game_id);
+        log_info!(Solana, "🎮 Joining lobby: {}", game_id);
         self.joining_lobby_pending = true;
 
         extern "C" {
@@ -810,10 +836,10 @@ impl MenuState {
                     }} else if (result && result.error) {{
                         Module.joinGameResult = JSON.stringify({{ error: result.error, message: result.message }});
                     }} else {{
-                        Module.joinGameResult = JSON.stringify({{ error: 'Unknown error' }});
+                        Module.joinGameResult = JSON.stringify({{ error: {{ kind: 'Unknown', message: 'Unknown error' }} }});
                     }}
                 }} catch (error) {{
-                    Module.joinGameResult = JSON.stringify({{ error: error.message }});
+                    Module.joinGameResult = JSON.stringify({{ error: window.toBridgeError(error) }});
                 }}
             }})();
             "#,
@@ -828,13 +854,13 @@ impl MenuState {
 
     #[cfg(not(target_os = "emscripten"))]
     pub fn join_lobby(&mut self, _game_id: String) {
-        println!("🎮 Join lobby not available in native build");
+        log_info!(Solana, "🎮 Join lobby not available in native build");
     }
 
     /// Leave the current lobby
     #[cfg(target_os = "emscripten")]
     pub fn leave_lobby(&mut self) {
-        println!("🚪 Leaving lobby...");
+        log_info!(Solana, "🚪 Leaving lobby...");
         
         extern "C" {
             pub fn emscripten_run_script(script: *const i8);
@@ -851,10 +877,10 @@ impl MenuState {
                 } else if (result && result.error) {
                     Module.leaveGameResult = JSON.stringify({ error: result.error, message: result.message });
                 } else {
-                    Module.leaveGameResult = JSON.stringify({ error: 'Unknown error' });
+                    Module.leaveGameResult = JSON.stringify({ error: { kind: 'Unknown', message: 'Unknown error' } });
                 }
             } catch (error) {
-                Module.leaveGameResult = JSON.stringify({ error: error.message });
+                Module.leaveGameResult = JSON.stringify({ error: window.toBridgeError(error) });
             }
         })();
         "#;
@@ -867,14 +893,14 @@ impl MenuState {
 
     #[cfg(not(target_os = "emscripten"))]
     pub fn leave_lobby(&mut self) {
-        println!("🚪 Leave lobby not available in native build");
+        log_info!(Solana, "🚪 Leave lobby not available in native build");
     }
 
     /// Start the lobby game (leader only)
     #[cfg(target_os = "emscripten")]
     pub fn start_lobby_game(&mut self) {
         if let Some(lobby_id) = &self.current_lobby_id {
-            println!("🎮 Starting game: {}", lobby_id);
+            log_info!(Solana, "🎮 Starting game: {}", lobby_id);
             self.starting_game_pending = true;
 
             extern "C" {
@@ -893,10 +919,10 @@ impl MenuState {
                         }} else if (result && result.error) {{
                             Module.startGameResult = JSON.stringify({{ error: result.error, message: result.message }});
                         }} else {{
-                            Module.startGameResult = JSON.stringify({{ error: 'Unknown error' }});
+                            Module.startGameResult = JSON.stringify({{ error: {{ kind: 'Unknown', message: 'Unknown error' }} }});
                         }}
                     }} catch (error) {{
-                        Module.startGameResult = JSON.stringify({{ error: error.message }});
+                        Module.startGameResult = JSON.stringify({{ error: window.toBridgeError(error) }});
                     }}
                 }})();
                 "#,
@@ -912,7 +938,7 @@ impl MenuState {
 
     #[cfg(not(target_os = "emscripten"))]
     pub fn start_lobby_game(&mut self) {
-        println!("🎮 Start lobby game not available in native build");
+        log_info!(Solana, "🎮 Start lobby game not available in native build");
     }
 
     /// Fetch lobby data to update team rosters
@@ -933,10 +959,10 @@ impl MenuState {
                         if (result) {{
                             Module.lobbyDataResult = JSON.stringify({{ success: true, game: result }});
                         }} else {{
-                            Module.lobbyDataResult = JSON.stringify({{ error: 'Failed to fetch game data' }});
+                            Module.lobbyDataResult = JSON.stringify({{ error: {{ kind: 'Unknown', message: 'Failed to fetch game data' }} }});
                         }}
                     }} catch (error) {{
-                        Module.lobbyDataResult = JSON.stringify({{ error: error.message }});
+                        Module.lobbyDataResult = JSON.stringify({{ error: window.toBridgeError(error) }});
                     }}
                 }})();
                 "#,
@@ -972,26 +998,26 @@ impl MenuState {
             let result_str = result_cstr.to_string_lossy();
 
             if result_str != "null" && !result_str.is_empty() {
-                println!("🔍 Lobby data result received: {}", &result_str[..result_str.len().min(200)]);
+                log_debug!(Solana, "🔍 Lobby data result received: {}", &result_str[..result_str.len().min(200)]);
 
                 if let Ok(result) = serde_json::from_str::<serde_json::Value>(&result_str) {
                     if let Some(success) = result.get("success") {
                         if success.as_bool().unwrap_or(false) {
                             if let Some(game) = result.get("game") {
-                                println!("📦 Processing game data from blockchain");
-                                println!("📦 Game data keys: {:?}", game.as_object().map(|o| o.keys().collect::<Vec<_>>()));
+                                log_info!(Solana, "📦 Processing game data from blockchain");
+                                log_info!(Solana, "📦 Game data keys: {:?}", game.as_object().map(|o| o.keys().collect::<Vec<_>>()));
                                 self.populate_team_rosters(game);
                             } else {
-                                println!("⚠️ No game data in response");
+                                log_warn!(Solana, "⚠️ No game data in response");
                             }
                         } else {
-                            println!("⚠️ Response success was false");
+                            log_warn!(Solana, "⚠️ Response success was false");
                         }
                     } else {
-                        println!("⚠️ No success field in response");
+                        log_warn!(Solana, "⚠️ No success field in response");
                     }
                 } else {
-                    println!("⚠️ Failed to parse JSON response");
+                    log_warn!(Solana, "⚠️ Failed to parse JSON response");
                 }
 
                 // Clear the result
@@ -1010,7 +1036,7 @@ impl MenuState {
 
     /// Populate team rosters from game data
     fn populate_team_rosters(&mut self, game: &serde_json::Value) {
-        println!("📋 populate_team_rosters called");
+        log_info!(Solana, "📋 populate_team_rosters called");
 
         // Clear existing rosters
         self.lobby_team_a.clear();
@@ -1032,27 +1058,27 @@ impl MenuState {
         let old_game_state = self.current_game_state;
         self.current_game_state = game_state;
 
-        println!("🎲 Game state: old={}, new={}", old_game_state, game_state);
+        log_info!(Solana, "🎲 Game state: old={}, new={}", old_game_state, game_state);
 
         // Get map ID from game data (it's a string)
         if let Some(map_id) = game.get("mapId").and_then(|v| v.as_str()) {
             self.current_map_name = Some(map_id.to_string());
-            println!("🗺️ Current map ID from blockchain: {}", map_id);
+            log_info!(Solana, "🗺️ Current map ID from blockchain: {}", map_id);
         } else {
-            println!("⚠️ No map ID found in game data");
+            log_warn!(Solana, "⚠️ No map ID found in game data");
             // Debug: print all keys in game data
             if let Some(obj) = game.as_object() {
-                println!("📋 Available keys in game data: {:?}", obj.keys().collect::<Vec<_>>());
+                log_info!(Solana, "📋 Available keys in game data: {:?}", obj.keys().collect::<Vec<_>>());
             }
         }
 
         // If game state changed from 0 (waiting) to 1 (active), signal game should start
         if old_game_state == 0 && game_state == 1 {
-            println!("🎮 GAME STATE CHANGED TO ACTIVE! Signaling game start...");
-            println!("🚀 Setting game_should_start = true");
+            log_info!(Solana, "🎮 GAME STATE CHANGED TO ACTIVE! Signaling game start...");
+            log_info!(Solana, "🚀 Setting game_should_start = true");
             self.game_should_start = true;
         } else if game_state == 1 {
-            println!("ℹ️ Game state is already active (state=1), but not transitioning from waiting");
+            log_info!(Solana, "ℹ️ Game state is already active (state=1), but not transitioning from waiting");
         }
 
         // Get lobby leader info
@@ -1076,7 +1102,7 @@ impl MenuState {
             self.lobby_team_b.push(format!("Player {}", i));
         }
 
-        println!("📊 Updated team rosters - Team A: {} players, Team B: {} players, Game State: {}",
+        log_info!(Solana, "📊 Updated team rosters - Team A: {} players, Team B: {} players, Game State: {}",
                  team_a_count, team_b_count, game_state);
 
         // After populating with placeholder players, fetch real player data
@@ -1101,10 +1127,10 @@ impl MenuState {
                         if (players) {{
                             Module.teamPlayersResult = JSON.stringify({{ success: true, players: players }});
                         }} else {{
-                            Module.teamPlayersResult = JSON.stringify({{ error: 'Failed to fetch players' }});
+                            Module.teamPlayersResult = JSON.stringify({{ error: {{ kind: 'Unknown', message: 'Failed to fetch players' }} }});
                         }}
                     }} catch (error) {{
-                        Module.teamPlayersResult = JSON.stringify({{ error: error.message }});
+                        Module.teamPlayersResult = JSON.stringify({{ error: window.toBridgeError(error) }});
                     }}
                 }})();
                 "#,
@@ -1153,7 +1179,7 @@ impl MenuState {
             
             if current_pubkey != "null" && !current_pubkey.is_empty() {
                 self.is_lobby_leader = current_pubkey == leader_pubkey;
-                println!("🔍 Current player: {}, Leader: {}, Is leader: {}", 
+                log_debug!(Solana, "🔍 Current player: {}, Leader: {}, Is leader: {}", 
                          current_pubkey, leader_pubkey, self.is_lobby_leader);
             }
         }
@@ -1186,24 +1212,25 @@ impl MenuState {
             let result_str = result_cstr.to_string_lossy();
             
             if result_str != "null" && !result_str.is_empty() {
-                println!("🔍 Join game result: {}", result_str);
+                log_debug!(Solana, "🔍 Join game result: {}", result_str);
                 
                 if let Ok(result) = serde_json::from_str::<serde_json::Value>(&result_str) {
                     if let Some(success) = result.get("success") {
                         if success.as_bool().unwrap_or(false) {
-                            println!("✅ Successfully joined game!");
+                            log_info!(Solana, "✅ Successfully joined game!");
                             self.in_lobby = true;
                             self.joining_lobby_pending = false;
                             // Set the lobby ID if not already set
                             if self.current_lobby_id.is_none() {
                                 // This should have been set when join_lobby was called
-                                println!("⚠️ Warning: current_lobby_id not set when joining game");
+                                log_warn!(Solana, "⚠️ Warning: current_lobby_id not set when joining game");
                             }
                             // Fetch lobby data to populate teams
                             self.fetch_lobby_data();
-                        } else if let Some(error) = result.get("error") {
-                            println!("❌ Failed to join game: {}", error);
+                        } else if let Some(error) = BridgeError::from_result(&result) {
+                            log_error!(Solana, "❌ Failed to join game: {:?}", error);
                             self.joining_lobby_pending = false;
+                            self.last_error = Some(error);
                         }
                     }
                 }
@@ -1243,19 +1270,20 @@ impl MenuState {
             let result_str = result_cstr.to_string_lossy();
             
             if result_str != "null" && !result_str.is_empty() {
-                println!("🔍 Start game result: {}", result_str);
+                log_debug!(Solana, "🔍 Start game result: {}", result_str);
                 
                 if let Ok(result) = serde_json::from_str::<serde_json::Value>(&result_str) {
                     if let Some(success) = result.get("success") {
                         if success.as_bool().unwrap_or(false) {
-                            println!("✅ Game started successfully!");
+                            log_info!(Solana, "✅ Game started successfully!");
                             if let Some(transaction) = result.get("transaction") {
-                                println!("Transaction: {}", transaction);
+                                log_info!(Solana, "Transaction: {}", transaction);
                             }
                             self.starting_game_pending = false;
-                        } else if let Some(error) = result.get("error") {
-                            println!("❌ Failed to start game: {}", error);
+                        } else if let Some(error) = BridgeError::from_result(&result) {
+                            log_error!(Solana, "❌ Failed to start game: {:?}", error);
                             self.starting_game_pending = false;
+                            self.last_error = Some(error);
                         }
                     }
                 }
@@ -1291,7 +1319,7 @@ impl MenuState {
             let result_str = result_cstr.to_string_lossy();
             
             if result_str != "null" && !result_str.is_empty() {
-                println!("🔍 Team players result: {}", result_str);
+                log_debug!(Solana, "🔍 Team players result: {}", result_str);
                 
                 if let Ok(result) = serde_json::from_str::<serde_json::Value>(&result_str) {
                     if let Some(success) = result.get("success") {
@@ -1347,9 +1375,9 @@ impl MenuState {
             }
         }
 
-        println!("📊 Updated rosters with real usernames - Team A: {:?}, Team B: {:?}",
+        log_info!(Solana, "📊 Updated rosters with real usernames - Team A: {:?}, Team B: {:?}",
                  self.lobby_team_a, self.lobby_team_b);
-        println!("📊 Ready states - Team A: {:?}, Team B: {:?}",
+        log_info!(Solana, "📊 Ready states - Team A: {:?}, Team B: {:?}",
                  self.lobby_team_a_ready, self.lobby_team_b_ready);
     }
 
@@ -1379,7 +1407,7 @@ impl MenuState {
                         Module.playerCurrentGameResult = JSON.stringify({ success: true, gameId: null });
                     }
                 } catch (error) {
-                    Module.playerCurrentGameResult = JSON.stringify({ error: error.message });
+                    Module.playerCurrentGameResult = JSON.stringify({ error: window.toBridgeError(error) });
                 }
             })();
         "#;
@@ -1416,7 +1444,7 @@ impl MenuState {
             let result_str = result_cstr.to_string_lossy();
 
             if result_str != "null" && !result_str.is_empty() {
-                println!("🔍 Player current game result: {}", result_str);
+                log_debug!(Solana, "🔍 Player current game result: {}", result_str);
 
                 if let Ok(result) = serde_json::from_str::<serde_json::Value>(&result_str) {
                     if let Some(success) = result.get("success") {
@@ -1424,7 +1452,7 @@ impl MenuState {
                             if let Some(game_id) = result.get("gameId") {
                                 if !game_id.is_null() {
                                     if let Some(game_id_str) = game_id.as_str() {
-                                        println!("🎮 Player is already in game: {}", game_id_str);
+                                        log_info!(Solana, "🎮 Player is already in game: {}", game_id_str);
 
                                         // Auto-enter lobby
                                         self.in_lobby = true;
@@ -1434,10 +1462,10 @@ impl MenuState {
                                         // Fetch lobby data to populate teams and check if leader
                                         self.fetch_lobby_data();
 
-                                        println!("✅ Auto-reconnected to lobby!");
+                                        log_info!(Solana, "✅ Auto-reconnected to lobby!");
                                     }
                                 } else {
-                                    println!("✅ Player is not in any game");
+                                    log_info!(Solana, "✅ Player is not in any game");
                                 }
                             }
                         }
@@ -1463,9 +1491,9 @@ impl MenuState {
     /// Toggle player's ready state
     #[cfg(target_os = "emscripten")]
     pub fn toggle_ready_state(&mut self) {
-        println!("🔄 Toggle ready state called! Current state: {}", self.player_ready_state);
+        log_info!(Solana, "🔄 Toggle ready state called! Current state: {}", self.player_ready_state);
         let new_ready_state = !self.player_ready_state;
-        println!("🔄 New state will be: {}", new_ready_state);
+        log_info!(Solana, "🔄 New state will be: {}", new_ready_state);
         self.set_ready_state(new_ready_state);
     }
 
@@ -1485,7 +1513,7 @@ impl MenuState {
         let lobby_id = match &self.current_lobby_id {
             Some(id) => id.clone(),
             None => {
-                println!("❌ Cannot set ready state: not in a lobby");
+                log_error!(Solana, "❌ Cannot set ready state: not in a lobby");
                 return;
             }
         };
@@ -1506,10 +1534,10 @@ impl MenuState {
                     if (result && result.transaction) {{
                         Module.setReadyResult = JSON.stringify({{ success: true, isReady: {} }});
                     }} else {{
-                        Module.setReadyResult = JSON.stringify({{ error: 'Failed to set ready state' }});
+                        Module.setReadyResult = JSON.stringify({{ error: {{ kind: 'Unknown', message: 'Failed to set ready state' }} }});
                     }}
                 }} catch (error) {{
-                    Module.setReadyResult = JSON.stringify({{ error: error.message }});
+                    Module.setReadyResult = JSON.stringify({{ error: window.toBridgeError(error) }});
                 }}
             }})();
             "#,
@@ -1548,7 +1576,7 @@ impl MenuState {
             let result_str = result_cstr.to_string_lossy();
 
             if result_str != "null" && !result_str.is_empty() {
-                println!("🔍 Set ready result: {}", result_str);
+                log_debug!(Solana, "🔍 Set ready result: {}", result_str);
 
                 if let Ok(result) = serde_json::from_str::<serde_json::Value>(&result_str) {
                     if let Some(success) = result.get("success") {
@@ -1556,14 +1584,15 @@ impl MenuState {
                             if let Some(is_ready) = result.get("isReady") {
                                 if let Some(ready_bool) = is_ready.as_bool() {
                                     self.player_ready_state = ready_bool;
-                                    println!("✅ Ready state updated to: {}", ready_bool);
+                                    log_info!(Solana, "✅ Ready state updated to: {}", ready_bool);
 
                                     // Refresh lobby data to update all players' ready states
                                     self.fetch_lobby_data();
                                 }
                             }
-                        } else if let Some(error) = result.get("error") {
-                            println!("❌ Failed to set ready state: {}", error);
+                        } else if let Some(error) = BridgeError::from_result(&result) {
+                            log_error!(Solana, "❌ Failed to set ready state: {:?}", error);
+                            self.last_error = Some(error);
                         }
                     }
                 }
@@ -1597,17 +1626,14 @@ impl MenuState {
             (async function() {{
                 try {{
                     console.log('🗺️ Fetching map data for ID: {}');
+                    // The bridge leaves the base64 data (or a generated arena's share code) in Module.mapDataResult
                     const mapData = await window.gameBridge.getMapDataById('{}');
-                    if (mapData) {{
-                        // Store as base64 since we're passing binary data
-                        const base64 = btoa(String.fromCharCode(...new Uint8Array(mapData)));
-                        Module.mapDataResult = JSON.stringify({{ success: true, data: base64 }});
-                    }} else {{
-                        Module.mapDataResult = JSON.stringify({{ error: 'Failed to fetch map data' }});
+                    if (!mapData && !Module.mapDataResult) {{
+                        Module.mapDataResult = JSON.stringify({{ error: {{ kind: 'Unknown', message: 'Failed to fetch map data' }} }});
                     }}
                 }} catch (error) {{
                     console.error('❌ Error fetching map data:', error);
-                    Module.mapDataResult = JSON.stringify({{ error: error.message }});
+                    Module.mapDataResult = JSON.stringify({{ error: window.toBridgeError(error) }});
                 }}
             }})();
             "#,
@@ -1642,63 +1668,43 @@ impl MenuState {
             let result_str = result_cstr.to_string_lossy();
 
             if result_str != "null" && !result_str.is_empty() {
-                println!("🗺️ Map data result received");
+                log_info!(Solana, "🗺️ Map data result received");
 
                 if let Ok(result) = serde_json::from_str::<serde_json::Value>(&result_str) {
                     if let Some(success) = result.get("success") {
                         if success.as_bool().unwrap_or(false) {
                             if let Some(base64_data) = result.get("data").and_then(|v| v.as_str()) {
-                                println!("📦 Processing map data from blockchain");
-
-                                // Decode base64 to bytes
-                                use base64::{Engine as _, engine::general_purpose};
-                                match general_purpose::STANDARD.decode(base64_data) {
-                                    Ok(bytes) => {
-                                        println!("🗺️ Decoded {} bytes of map data", bytes.len());
-
-                                        // Deserialize map from Borsh bytes
-                                        use crate::map::Map;
-                                        match Map::from_borsh_bytes(&bytes) {
-                                            Ok(map) => {
-                                                println!("✅ Successfully loaded map: '{}' with {} objects", map.name, map.objects.len());
-                                                game_state.load_map(map);
-
-                                                // Set the current game pubkey for blockchain sync
-                                                if let Some(game_pubkey) = &self.current_game_pubkey {
-                                                    println!("🎮 Setting current game pubkey for sync: {}", game_pubkey);
-                                                    game_state.set_current_game(game_pubkey.clone());
-                                                } else {
-                                                    println!("⚠️ No game pubkey available for blockchain sync");
-                                                }
-
-                                                game_state.capture_mouse_if_playing(rl);
-
-                                                // Reset flags
-                                                self.waiting_for_map_data = false;
-                                                self.in_lobby = false;
-                                            },
-                                            Err(e) => {
-                                                println!("❌ Failed to deserialize map data: {}", e);
-                                                self.waiting_for_map_data = false;
-                                            }
-                                        }
-                                    },
-                                    Err(e) => {
-                                        println!("❌ Failed to decode base64 map data: {}", e);
+                                // Decoded a step per frame behind the loading screen
+                                log_info!(Solana, "📦 Processing map data from blockchain");
+                                game_state.begin_map_load(crate::map::MapLoader::from_base64(base64_data.to_string()));
+                                if let Some(stream) = crate::map::MapStream::from_result(&result) {
+                                    game_state.begin_map_stream(stream);
+                                }
+                                self.enter_match(game_state, rl);
+                            } else if let Some(code) = result.get("shareCode").and_then(|v| v.as_str()) {
+                                match crate::map::generator::map_from_share_code(code) {
+                                    Some(map) => {
+                                        log_info!(Solana, "🎲 Generated map '{}' from its share code: {} objects", map.name, map.objects.len());
+                                        game_state.load_map(map);
+                                        self.enter_match(game_state, rl);
+                                    }
+                                    None => {
+                                        log_error!(Solana, "❌ Invalid map share code: {}", code);
                                         self.waiting_for_map_data = false;
                                     }
                                 }
                             } else {
-                                println!("⚠️ No data in map response");
+                                log_warn!(Solana, "⚠️ No data in map response");
                                 self.waiting_for_map_data = false;
                             }
                         } else {
-                            println!("⚠️ Map fetch was not successful");
+                            log_warn!(Solana, "⚠️ Map fetch was not successful");
                             self.waiting_for_map_data = false;
                         }
-                    } else if let Some(error) = result.get("error") {
-                        println!("❌ Error fetching map: {}", error.as_str().unwrap_or("unknown"));
+                    } else if let Some(error) = BridgeError::from_result(&result) {
+                        log_error!(Solana, "❌ Error fetching map: {:?}", error);
                         self.waiting_for_map_data = false;
+                        self.last_error = Some(error);
                     }
                 }
 
@@ -1711,6 +1717,24 @@ impl MenuState {
         }
     }
 
+    /// The map is on its way: hook the match up for blockchain sync and leave the lobby
+    #[cfg(target_os = "emscripten")]
+    fn enter_match(&mut self, game_state: &mut crate::game::GameState, rl: &mut crate::RaylibHandle) {
+        // Set the current game pubkey for blockchain sync
+        if let Some(game_pubkey) = &self.current_game_pubkey {
+            log_info!(Solana, "🎮 Setting current game pubkey for sync: {}", game_pubkey);
+            game_state.set_current_game(game_pubkey.clone());
+        } else {
+            log_warn!(Solana, "⚠️ No game pubkey available for blockchain sync");
+        }
+
+        game_state.capture_mouse_if_playing(rl);
+
+        // Reset flags
+        self.waiting_for_map_data = false;
+        self.in_lobby = false;
+    }
+
     #[cfg(not(target_os = "emscripten"))]
     pub fn check_map_data_response(&mut self, _game_state: &mut crate::game::GameState, _rl: &mut crate::RaylibHandle) {
         // Not available outside of browser
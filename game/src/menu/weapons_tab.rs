@@ -1,4 +1,5 @@
 use super::menu_state::MenuState;
+use crate::i18n::{t, tr};
 
 #[derive(Debug, Clone)]
 pub struct Weapon {
@@ -111,13 +112,24 @@ impl WeaponsTab {
         ]
     }
 
+    /// Localized name of a weapon type from `get_weapons`
+    fn type_label(weapon_type: &str) -> &'static str {
+        match weapon_type {
+            "Rifle" => t("weapons.type.rifle"),
+            "SMG" => t("weapons.type.smg"),
+            "Sniper" => t("weapons.type.sniper"),
+            "Shotgun" => t("weapons.type.shotgun"),
+            _ => t("weapons.type.pistol"),
+        }
+    }
+
     pub fn draw(menu_state: &mut MenuState, ui: &imgui::Ui) {
         ui.dummy([0.0, 20.0]);
 
         // Title
         let _title_color = ui.push_style_color(imgui::StyleColor::Text, [0.08, 0.95, 0.58, 1.0]);
         ui.set_window_font_scale(1.5);
-        ui.text("ARSENAL");
+        ui.text(t("weapons.title"));
         ui.set_window_font_scale(1.0);
         drop(_title_color);
 
@@ -135,7 +147,7 @@ impl WeaponsTab {
             .size([0.0, 0.0])
             .border(true)
             .build(|| {
-                ui.text("WEAPON LIST");
+                ui.text(t("weapons.list"));
                 ui.separator();
                 ui.dummy([0.0, 5.0]);
 
@@ -148,7 +160,7 @@ impl WeaponsTab {
                         current_type = &weapon.weapon_type;
                         ui.dummy([0.0, 10.0]);
                         let _type_color = ui.push_style_color(imgui::StyleColor::Text, [0.60, 0.27, 1.0, 1.0]);
-                        ui.text(format!("▼ {}", current_type.to_uppercase()));
+                        ui.text(format!("▼ {}", Self::type_label(current_type).to_uppercase()));
                         drop(_type_color);
                         ui.separator();
                         ui.dummy([0.0, 5.0]);
@@ -188,7 +200,7 @@ impl WeaponsTab {
                             ui.same_line();
 
                             // Select button
-                            if ui.button_with_size(format!("VIEW##{}", i), [80.0, 25.0]) {
+                            if ui.button_with_size(format!("{}##{}", t("weapons.view"), i), [80.0, 25.0]) {
                                 menu_state.selected_weapon = Some(i);
                             }
                         });
@@ -220,7 +232,7 @@ impl WeaponsTab {
 
                         // Type
                         let _type_color = ui.push_style_color(imgui::StyleColor::Text, [0.60, 0.27, 1.0, 1.0]);
-                        ui.text(&weapon.weapon_type);
+                        ui.text(Self::type_label(&weapon.weapon_type));
                         drop(_type_color);
 
                         ui.dummy([0.0, 20.0]);
@@ -228,11 +240,11 @@ impl WeaponsTab {
                         ui.dummy([0.0, 20.0]);
 
                         // Stats
-                        ui.text("STATISTICS");
+                        ui.text(t("weapons.statistics"));
                         ui.dummy([0.0, 10.0]);
 
                         // Damage
-                        ui.text_colored([0.7, 0.7, 0.7, 1.0], "Damage");
+                        ui.text_colored([0.7, 0.7, 0.7, 1.0], t("weapons.stat.damage"));
                         ui.same_line();
                         ui.dummy([150.0, 0.0]);
                         ui.same_line();
@@ -241,27 +253,27 @@ impl WeaponsTab {
                         ui.dummy([0.0, 5.0]);
 
                         // Fire Rate
-                        ui.text_colored([0.7, 0.7, 0.7, 1.0], "Fire Rate");
+                        ui.text_colored([0.7, 0.7, 0.7, 1.0], t("weapons.stat.fire_rate"));
                         ui.same_line();
                         ui.dummy([150.0, 0.0]);
                         ui.same_line();
-                        ui.text(format!("{} rounds/sec", weapon.fire_rate));
+                        ui.text(tr!("weapons.stat.fire_rate_value", rate = weapon.fire_rate));
 
                         ui.dummy([0.0, 5.0]);
 
                         // Magazine Size
-                        ui.text_colored([0.7, 0.7, 0.7, 1.0], "Magazine Size");
+                        ui.text_colored([0.7, 0.7, 0.7, 1.0], t("weapons.stat.magazine"));
                         ui.same_line();
                         ui.dummy([150.0, 0.0]);
                         ui.same_line();
-                        ui.text(format!("{} rounds", weapon.magazine_size));
+                        ui.text(tr!("weapons.stat.magazine_value", rounds = weapon.magazine_size));
 
                         ui.dummy([0.0, 20.0]);
                         ui.separator();
                         ui.dummy([0.0, 20.0]);
 
                         // Price
-                        ui.text("COST");
+                        ui.text(t("weapons.cost"));
                         ui.dummy([0.0, 5.0]);
                         let _price_color = ui.push_style_color(imgui::StyleColor::Text, [0.08, 0.95, 0.58, 1.0]);
                         ui.set_window_font_scale(1.5);
@@ -274,7 +286,7 @@ impl WeaponsTab {
                         // Equip button
                         let _equip_btn = ui.push_style_color(imgui::StyleColor::Button, [0.38, 0.17, 0.60, 1.0]);
                         let _equip_hover = ui.push_style_color(imgui::StyleColor::ButtonHovered, [0.48, 0.25, 0.75, 1.0]);
-                        if ui.button_with_size("EQUIP", [150.0, 40.0]) {
+                        if ui.button_with_size(t("weapons.equip"), [150.0, 40.0]) {
                             // TODO: Equip weapon logic
                         }
                         drop(_equip_btn);
@@ -283,7 +295,7 @@ impl WeaponsTab {
                 } else {
                     ui.dummy([0.0, 200.0]);
                     let _hint_color = ui.push_style_color(imgui::StyleColor::Text, [0.5, 0.5, 0.5, 1.0]);
-                    ui.text("Select a weapon to view details");
+                    ui.text(t("weapons.select_hint"));
                     drop(_hint_color);
                 }
             });
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Error reported by the JS bridge (the `error` field of every `Module.xxxResult`)
+///
+/// The bridge tags errors with `kind` (see `app/src/utils/bridge-error.js`),
+/// e.g. `{ "kind": "PlayerAlreadyInGame", "currentGame": "..." }`, so callers
+/// match on variants instead of comparing message strings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum BridgeError {
+    /// Game or Solana bridge not initialized yet
+    BridgeUnavailable,
+    /// No wallet connected
+    WalletNotConnected,
+    /// Creating or joining a game while still in another one
+    #[serde(rename_all = "camelCase")]
+    PlayerAlreadyInGame { current_game: Option<String> },
+    /// Leaving when not in a game
+    NotInGame,
+    /// Not enough SOL to pay for the transaction
+    InsufficientFunds,
+    /// Custom error returned by one of our programs
+    Program {
+        code: u32,
+        #[serde(default)]
+        name: String,
+    },
+    /// RPC unreachable, rate limited or timed out
+    Rpc { message: String },
+    /// Anything the bridge couldn't classify
+    Unknown { message: String },
+}
+
+impl BridgeError {
+    /// Error carried by a bridge result, if any
+    pub fn from_result(result: &serde_json::Value) -> Option<Self> {
+        result.get("error").filter(|e| !e.is_null()).map(Self::from_value)
+    }
+
+    /// Parse a tagged error; bare strings from older bridge code map to the variant of that name
+    pub fn from_value(value: &serde_json::Value) -> Self {
+        let tagged = match value.as_str() {
+            Some(kind) => serde_json::json!({ "kind": kind }),
+            None => value.clone(),
+        };
+        serde_json::from_value(tagged).unwrap_or_else(|_| BridgeError::Unknown {
+            message: value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string()),
+        })
+    }
+
+    /// What to tell the player
+    pub fn user_message(&self) -> String {
+        match self {
+            BridgeError::BridgeUnavailable => "Still connecting to Solana - try again in a moment".to_string(),
+            BridgeError::WalletNotConnected => "Connect your wallet first".to_string(),
+            BridgeError::PlayerAlreadyInGame { .. } => "You're already in a game - leave it first".to_string(),
+            BridgeError::NotInGame => "You're not in a game".to_string(),
+            BridgeError::InsufficientFunds => "Not enough SOL to pay for the transaction".to_string(),
+            BridgeError::Program { code, name } if name.is_empty() => {
+                format!("Transaction rejected by the program (error {})", code)
+            }
+            BridgeError::Program { name, .. } => format!("Transaction rejected: {}", name),
+            BridgeError::Rpc { .. } => "Can't reach the Solana RPC - check your connection settings".to_string(),
+            BridgeError::Unknown { message } => message.clone(),
+        }
+    }
+}
+
+impl fmt::Display for BridgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BridgeError::Rpc { message } => write!(f, "{} ({})", self.user_message(), message),
+            _ => f.write_str(&self.user_message()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_tagged_errors() {
+        let result = serde_json::json!({ "error": { "kind": "PlayerAlreadyInGame", "currentGame": "Game111" } });
+        assert_eq!(
+            BridgeError::from_result(&result),
+            Some(BridgeError::PlayerAlreadyInGame { current_game: Some("Game111".to_string()) })
+        );
+
+        let result = serde_json::json!({ "error": { "kind": "Program", "code": 6004, "name": "LeaderStillInLobby" } });
+        assert_eq!(
+            BridgeError::from_result(&result),
+            Some(BridgeError::Program { code: 6004, name: "LeaderStillInLobby".to_string() })
+        );
+        assert_eq!(BridgeError::from_result(&serde_json::json!({ "success": true })), None);
+    }
+
+    #[test]
+    fn test_bare_strings_map_to_variants_or_unknown() {
+        assert_eq!(BridgeError::from_value(&serde_json::json!("NotInGame")), BridgeError::NotInGame);
+        assert_eq!(
+            BridgeError::from_value(&serde_json::json!("PlayerAlreadyInGame")),
+            BridgeError::PlayerAlreadyInGame { current_game: None }
+        );
+        assert_eq!(
+            BridgeError::from_value(&serde_json::json!("Failed to fetch players")),
+            BridgeError::Unknown { message: "Failed to fetch players".to_string() }
+        );
+    }
+}
@@ -0,0 +1,54 @@
+/// Folder native builds write "downloads" into
+pub const DOWNLOAD_DIR: &str = "downloads";
+
+/// Hand a file to the player: a browser download on the web (base64 -> Blob ->
+/// anchor click), a file in `DOWNLOAD_DIR` on native builds
+pub fn download_file(filename: &str, mime: &str, bytes: &[u8]) -> std::io::Result<()> {
+    #[cfg(target_os = "emscripten")]
+    {
+        use std::ffi::CString;
+        use base64::{Engine as _, engine::general_purpose};
+
+        extern "C" {
+            fn emscripten_run_script(script: *const std::os::raw::c_char);
+        }
+
+        let js_code = format!(
+            r#"
+            (function() {{
+                var byteCharacters = atob('{}');
+                var byteArray = new Uint8Array(byteCharacters.length);
+                for (var i = 0; i < byteCharacters.length; i++) {{
+                    byteArray[i] = byteCharacters.charCodeAt(i);
+                }}
+
+                var blob = new Blob([byteArray], {{type: {}}});
+                var url = URL.createObjectURL(blob);
+                var a = document.createElement('a');
+                a.href = url;
+                a.download = {};
+                document.body.appendChild(a);
+                a.click();
+                document.body.removeChild(a);
+                URL.revokeObjectURL(url);
+            }})();
+            "#,
+            general_purpose::STANDARD.encode(bytes),
+            serde_json::to_string(mime).unwrap_or_default(),
+            serde_json::to_string(filename).unwrap_or_default()
+        );
+
+        let c_str = CString::new(js_code).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        unsafe {
+            emscripten_run_script(c_str.as_ptr());
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "emscripten"))]
+    {
+        let _ = mime;
+        std::fs::create_dir_all(DOWNLOAD_DIR)?;
+        std::fs::write(std::path::Path::new(DOWNLOAD_DIR).join(filename), bytes)
+    }
+}
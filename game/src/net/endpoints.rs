@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use serde::{Deserialize, Serialize};
+
+// Emscripten bindings for JavaScript interop
+extern "C" {
+    fn emscripten_run_script(script: *const std::os::raw::c_char);
+    fn emscripten_run_script_string(script: *const std::os::raw::c_char) -> *const std::os::raw::c_char;
+}
+
+/// Pings sent to each endpoint (the best round trip is kept)
+const PINGS_PER_ENDPOINT: u32 = 3;
+
+/// Seconds before endpoints that haven't answered are treated as unreachable
+const PROBE_TIMEOUT: f32 = 6.0;
+
+/// Seconds to wait for the bridge to publish its endpoint list before giving up
+const CANDIDATE_WAIT_TIMEOUT: f32 = 10.0;
+
+/// How often (seconds) JS is polled for probe results / re-probe requests
+const POLL_INTERVAL: f32 = 0.25;
+
+/// What an endpoint serves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EndpointKind {
+    /// Base-layer Solana RPC (lobbies, maps, wallets)
+    Rpc,
+    /// Ephemeral rollup RPC (gameplay transactions)
+    Rollup,
+}
+
+/// An endpoint the player can connect to (published by the bridge in `window.__fpsEndpoints`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Endpoint {
+    pub id: String,
+    /// Cluster served ("localnet", "devnet", "mainnet-beta")
+    #[serde(default)]
+    pub cluster: String,
+    pub kind: EndpointKind,
+    pub region: String,
+    pub url: String,
+}
+
+/// Probe outcome handed back to the bridge
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProbeReport {
+    /// Best round trip (ms) per endpoint id, None when unreachable
+    pub latencies: HashMap<String, Option<f64>>,
+    pub best_rpc: Option<String>,
+    pub best_rollup: Option<String>,
+    /// Cluster the probed endpoints serve, so a report that lands after a cluster switch can be discarded
+    pub cluster: Option<String>,
+}
+
+impl ProbeReport {
+    /// Pick the lowest-latency reachable endpoint of each kind
+    pub fn from_latencies(endpoints: &[Endpoint], latencies: HashMap<String, Option<f64>>) -> Self {
+        let fastest = |kind: EndpointKind| {
+            endpoints
+                .iter()
+                .filter(|e| e.kind == kind)
+                .filter_map(|e| latencies.get(&e.id).copied().flatten().map(|ms| (e, ms)))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(e, _)| e.id.clone())
+        };
+
+        Self {
+            best_rpc: fastest(EndpointKind::Rpc),
+            best_rollup: fastest(EndpointKind::Rollup),
+            cluster: endpoints.first().map(|e| e.cluster.clone()).filter(|c| !c.is_empty()),
+            latencies,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ProbeState {
+    /// Waiting for the bridge to publish the endpoint list
+    WaitingForCandidates { waited: f32 },
+    /// Pings in flight
+    Probing { endpoints: Vec<Endpoint>, elapsed: f32 },
+    /// Finished (until a re-probe is requested from the settings panel)
+    Idle,
+}
+
+/// Startup latency probe for RPC and rollup endpoints
+///
+/// The pings themselves are `fetch` calls (JSON-RPC `getHealth`) started from
+/// Rust; results land in `window.__fpsEndpointLatency` and are collected here.
+/// When done, the report is passed to `gameBridge.onEndpointProbeResults`,
+/// which applies the fastest endpoints if the player chose auto-select.
+pub struct EndpointProbe {
+    state: ProbeState,
+    poll_timer: f32,
+}
+
+impl EndpointProbe {
+    /// Create a probe that starts as soon as the bridge publishes its endpoints
+    pub fn new() -> Self {
+        Self {
+            state: ProbeState::WaitingForCandidates { waited: 0.0 },
+            poll_timer: 0.0,
+        }
+    }
+
+    /// Advance the probe (call once per frame)
+    pub fn update(&mut self, delta: f32) {
+        self.poll_timer -= delta;
+        let poll = self.poll_timer <= 0.0;
+        if poll {
+            self.poll_timer = POLL_INTERVAL;
+        }
+
+        match &mut self.state {
+            ProbeState::WaitingForCandidates { waited } => {
+                *waited += delta;
+                if !poll {
+                    return;
+                }
+                match read_candidates() {
+                    Some(endpoints) if !endpoints.is_empty() => self.start(endpoints),
+                    _ if *waited > CANDIDATE_WAIT_TIMEOUT => {
+                        log_warn!(Net, "⚠️ No endpoint list published, skipping latency probe");
+                        self.state = ProbeState::Idle;
+                    }
+                    _ => {}
+                }
+            }
+            ProbeState::Probing { endpoints, elapsed } => {
+                *elapsed += delta;
+                if !poll {
+                    return;
+                }
+                let latencies = read_latencies();
+                let all_answered = endpoints.iter().all(|e| latencies.contains_key(&e.id));
+                if all_answered || *elapsed > PROBE_TIMEOUT {
+                    let latencies = endpoints
+                        .iter()
+                        .map(|e| (e.id.clone(), latencies.get(&e.id).copied().flatten()))
+                        .collect();
+                    let report = ProbeReport::from_latencies(endpoints, latencies);
+                    log_info!(Net, "📶 Endpoint probe done: rpc={:?} rollup={:?}", report.best_rpc, report.best_rollup);
+                    publish_report(&report);
+                    self.state = ProbeState::Idle;
+                }
+            }
+            ProbeState::Idle => {
+                if poll && take_reprobe_request() {
+                    self.state = ProbeState::WaitingForCandidates { waited: 0.0 };
+                }
+            }
+        }
+    }
+
+    /// Fire off the pings for every endpoint
+    fn start(&mut self, endpoints: Vec<Endpoint>) {
+        log_info!(Net, "📶 Probing {} endpoints...", endpoints.len());
+
+        // Ids and URLs come from the bridge, so they're quoted as JSON rather than pasted in
+        let targets: Vec<String> = endpoints
+            .iter()
+            .map(|e| serde_json::json!([e.id, e.url]).to_string())
+            .collect();
+
+        let js_code = format!(
+            r#"
+            (function() {{
+                window.__fpsEndpointLatency = {{}};
+                var body = JSON.stringify({{ jsonrpc: '2.0', id: 1, method: 'getHealth' }});
+                [{}].forEach(function(target) {{
+                    var id = target[0], url = target[1], best = null, remaining = {};
+                    var ping = function() {{
+                        var start = performance.now();
+                        fetch(url, {{ method: 'POST', headers: {{ 'Content-Type': 'application/json' }}, body: body }})
+                            .then(function(response) {{
+                                // Errors and rate limits don't count as a reachable endpoint
+                                if (!response.ok) return;
+                                var ms = performance.now() - start;
+                                best = best === null ? ms : Math.min(best, ms);
+                            }})
+                            .catch(function() {{}})
+                            .finally(function() {{
+                                remaining--;
+                                if (remaining > 0) {{
+                                    ping();
+                                }} else {{
+                                    window.__fpsEndpointLatency[id] = best;
+                                }}
+                            }});
+                    }};
+                    ping();
+                }});
+            }})();
+            "#,
+            targets.join(", "),
+            PINGS_PER_ENDPOINT
+        );
+
+        unsafe {
+            let c_str = CString::new(js_code).unwrap();
+            emscripten_run_script(c_str.as_ptr());
+        }
+
+        self.state = ProbeState::Probing { endpoints, elapsed: 0.0 };
+    }
+}
+
+/// Run a JS expression that returns a string
+fn run_script_string(js: &str) -> Option<String> {
+    let js_code = CString::new(js).ok()?;
+    unsafe {
+        let result_ptr = emscripten_run_script_string(js_code.as_ptr());
+        if result_ptr.is_null() {
+            return None;
+        }
+        CStr::from_ptr(result_ptr).to_str().ok().map(|s| s.to_string())
+    }
+}
+
+/// Endpoint list published by the bridge
+fn read_candidates() -> Option<Vec<Endpoint>> {
+    let json = run_script_string("JSON.stringify(window.__fpsEndpoints || [])")?;
+    match serde_json::from_str(&json) {
+        Ok(endpoints) => Some(endpoints),
+        Err(e) => {
+            log_warn!(Net, "⚠️ Invalid endpoint list: {}", e);
+            None
+        }
+    }
+}
+
+/// Latencies reported so far (null = every ping failed)
+fn read_latencies() -> HashMap<String, Option<f64>> {
+    run_script_string("JSON.stringify(window.__fpsEndpointLatency || {})")
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Consume a re-probe request from the settings panel
+fn take_reprobe_request() -> bool {
+    run_script_string(
+        r#"
+        (function(){
+            if (!window.__endpoint_probe_requested) return 'false';
+            window.__endpoint_probe_requested = false;
+            return 'true';
+        })()
+        "#,
+    )
+    .is_some_and(|flag| flag == "true")
+}
+
+/// Hand the report to the bridge (which applies it in auto mode)
+fn publish_report(report: &ProbeReport) {
+    let json = serde_json::to_string(report).unwrap_or_else(|_| "{}".to_string());
+    let js_code = format!(
+        r#"
+        (function() {{
+            window.endpointProbeReport = {};
+            if (window.gameBridge && window.gameBridge.onEndpointProbeResults) {{
+                window.gameBridge.onEndpointProbeResults(window.endpointProbeReport);
+            }}
+        }})();
+        "#,
+        json
+    );
+
+    unsafe {
+        let c_str = CString::new(js_code).unwrap();
+        emscripten_run_script(c_str.as_ptr());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint(id: &str, kind: EndpointKind) -> Endpoint {
+        Endpoint {
+            id: id.to_string(),
+            cluster: "devnet".to_string(),
+            kind,
+            region: "test".to_string(),
+            url: format!("https://{}", id),
+        }
+    }
+
+    #[test]
+    fn test_report_picks_fastest_reachable_per_kind() {
+        let endpoints = vec![
+            endpoint("rpc-eu", EndpointKind::Rpc),
+            endpoint("rpc-us", EndpointKind::Rpc),
+            endpoint("er-eu", EndpointKind::Rollup),
+            endpoint("er-us", EndpointKind::Rollup),
+        ];
+        let latencies = HashMap::from([
+            ("rpc-eu".to_string(), Some(80.0)),
+            ("rpc-us".to_string(), Some(40.0)),
+            ("er-eu".to_string(), None),
+            ("er-us".to_string(), Some(120.0)),
+        ]);
+
+        let report = ProbeReport::from_latencies(&endpoints, latencies);
+        assert_eq!(report.best_rpc.as_deref(), Some("rpc-us"));
+        assert_eq!(report.best_rollup.as_deref(), Some("er-us"));
+        assert_eq!(report.cluster.as_deref(), Some("devnet"));
+    }
+}
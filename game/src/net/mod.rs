@@ -0,0 +1,7 @@
+mod bridge_error;
+mod endpoints;
+mod download;
+
+pub use bridge_error::BridgeError;
+pub use endpoints::EndpointProbe;
+pub use download::download_file;
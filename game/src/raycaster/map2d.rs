@@ -1,5 +1,26 @@
 use raylib::prelude::*;
-use crate::map::{Map, MapObject};
+use crate::map::{Map, MapObject, ModelType, WORLD_HALF_SIZE, WORLD_SIZE};
+
+/// Lines of sight between the ground and this height can be answered from the grid
+/// (a standing player's head plus margin); wall cells must reach at least this high
+pub const SIGHT_HEIGHT: f32 = 2.2;
+
+/// Cell size the game rasterizes loaded maps at (world units)
+pub const MAP_GRID_CELL_SIZE: f32 = 0.5;
+
+/// Geometry starting below this counts as standing on the ground
+const GROUND_TOLERANCE: f32 = 0.05;
+
+/// Answer of a grid line-of-sight query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sight {
+    /// Nothing in the way
+    Clear,
+    /// A wall cell is in the way
+    Blocked,
+    /// The line crosses partly covered cells (or leaves the height band): do the exact 3D check
+    Unsure,
+}
 
 /// 2D grid-based map for raycasting
 /// Each cell contains a wall type (0 = empty, >0 = wall with different textures)
@@ -8,8 +29,13 @@ pub struct Map2D {
     pub width: usize,
     /// Height of the map in cells
     pub height: usize,
+    /// World units per cell
+    pub cell_size: f32,
     /// Grid data (0 = empty, 1+ = wall type)
     pub grid: Vec<Vec<i32>>,
+    /// Cells with geometry that doesn't fill them from the ground to `SIGHT_HEIGHT`
+    /// (low cover, thin or rotated walls, floating platforms)
+    pub partial: Vec<Vec<bool>>,
 }
 
 impl Map2D {
@@ -18,10 +44,53 @@ impl Map2D {
         Self {
             width,
             height,
+            cell_size: 1.0,
             grid: vec![vec![0; height]; width],
+            partial: vec![vec![false; height]; width],
         }
     }
 
+    /// Rasterize a map's geometry at `cell_size` world units per cell
+    ///
+    /// Cells completely covered by an upright box standing on the ground and at
+    /// least `SIGHT_HEIGHT` tall become walls (colored after the object); every
+    /// other cell touched by geometry is marked `partial`. Objects sight passes
+    /// through (non-solid, player clip) are left out.
+    pub fn from_map(map: &Map, cell_size: f32) -> Self {
+        let cells = (WORLD_SIZE / cell_size).ceil() as usize;
+        let mut map2d = Self::new(cells, cells);
+        map2d.cell_size = cell_size;
+
+        for (index, obj) in map.objects.iter().enumerate() {
+            let Some(bounds) = obj.collision_bounds().filter(|_| map.collision(index).blocks_bullets()) else {
+                continue;
+            };
+            let full_height = bounds.min.y <= GROUND_TOLERANCE && bounds.max.y >= SIGHT_HEIGHT && is_upright_box(obj);
+            let (min_x, min_y) = map2d.world_to_grid(bounds.min);
+            let (max_x, max_y) = map2d.world_to_grid(bounds.max);
+            let last = cells as i32 - 1;
+
+            for x in (min_x.floor() as i32).max(0)..=(max_x.ceil() as i32 - 1).min(last) {
+                for y in (min_y.floor() as i32).max(0)..=(max_y.ceil() as i32 - 1).min(last) {
+                    let (x, y) = (x as usize, y as usize);
+                    let covered = full_height
+                        && x as f32 >= min_x
+                        && (x + 1) as f32 <= max_x
+                        && y as f32 >= min_y
+                        && (y + 1) as f32 <= max_y;
+                    if covered {
+                        map2d.grid[x][y] = wall_type_for(obj);
+                        map2d.partial[x][y] = false;
+                    } else if map2d.grid[x][y] == 0 {
+                        map2d.partial[x][y] = true;
+                    }
+                }
+            }
+        }
+
+        map2d
+    }
+
     /// Create a demo map for testing (like Wolfenstein 3D)
     pub fn create_demo() -> Self {
         let width = 24;
@@ -77,6 +146,113 @@ impl Map2D {
         self.get_wall(x as i32, y as i32) > 0
     }
 
+    /// Grid coordinates (fractional) of a world position
+    /// World X maps to grid x and world Z to grid y
+    pub fn world_to_grid(&self, position: Vector3) -> (f32, f32) {
+        ((position.x + WORLD_HALF_SIZE) / self.cell_size, (position.z + WORLD_HALF_SIZE) / self.cell_size)
+    }
+
+    fn is_partial(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height && self.partial[x as usize][y as usize]
+    }
+
+    /// Line of sight between two world points from the grid alone
+    ///
+    /// Walls fill their cells from the ground to `SIGHT_HEIGHT`, so any line that
+    /// stays in that band and crosses one is blocked. Lines outside the band, or
+    /// crossing partial cells without hitting a wall, are `Unsure`.
+    pub fn line_of_sight(&self, from: Vector3, to: Vector3) -> Sight {
+        let band = 0.0..=SIGHT_HEIGHT;
+        if !band.contains(&from.y) || !band.contains(&to.y) {
+            return Sight::Unsure;
+        }
+
+        let (x0, y0) = self.world_to_grid(from);
+        let (x1, y1) = self.world_to_grid(to);
+        let (dx, dy) = (x1 - x0, y1 - y0);
+        let length = (dx * dx + dy * dy).sqrt();
+        let (dir_x, dir_y) = if length > 0.0 { (dx / length, dy / length) } else { (0.0, 0.0) };
+
+        let mut map_x = x0.floor() as i32;
+        let mut map_y = y0.floor() as i32;
+        let (end_x, end_y) = (x1.floor() as i32, y1.floor() as i32);
+        let delta_dist_x = if dir_x == 0.0 { 1e30 } else { (1.0 / dir_x).abs() };
+        let delta_dist_y = if dir_y == 0.0 { 1e30 } else { (1.0 / dir_y).abs() };
+        let (step_x, mut side_dist_x) = if dir_x < 0.0 {
+            (-1, (x0 - map_x as f32) * delta_dist_x)
+        } else {
+            (1, (map_x as f32 + 1.0 - x0) * delta_dist_x)
+        };
+        let (step_y, mut side_dist_y) = if dir_y < 0.0 {
+            (-1, (y0 - map_y as f32) * delta_dist_y)
+        } else {
+            (1, (map_y as f32 + 1.0 - y0) * delta_dist_y)
+        };
+
+        // Visit every cell the segment passes through, start and end included
+        let mut sight = Sight::Clear;
+        loop {
+            if self.get_wall(map_x, map_y) > 0 {
+                return Sight::Blocked;
+            }
+            if self.is_partial(map_x, map_y) {
+                sight = Sight::Unsure;
+            }
+            if (map_x == end_x && map_y == end_y) || side_dist_x.min(side_dist_y) > length {
+                return sight;
+            }
+            if side_dist_x < side_dist_y {
+                map_x += step_x;
+                side_dist_x += delta_dist_x;
+            } else {
+                map_y += step_y;
+                side_dist_y += delta_dist_y;
+            }
+        }
+    }
+
+    /// Distance from (x, y) along a normalized direction to the first wall (DDA),
+    /// or `max_distance` if nothing is hit before that
+    pub fn cast_ray(&self, x: f32, y: f32, dir_x: f32, dir_y: f32, max_distance: f32) -> f32 {
+        let mut map_x = x.floor() as i32;
+        let mut map_y = y.floor() as i32;
+        if self.get_wall(map_x, map_y) > 0 {
+            return 0.0;
+        }
+
+        let delta_dist_x = if dir_x == 0.0 { 1e30 } else { (1.0 / dir_x).abs() };
+        let delta_dist_y = if dir_y == 0.0 { 1e30 } else { (1.0 / dir_y).abs() };
+        let (step_x, mut side_dist_x) = if dir_x < 0.0 {
+            (-1, (x - map_x as f32) * delta_dist_x)
+        } else {
+            (1, (map_x as f32 + 1.0 - x) * delta_dist_x)
+        };
+        let (step_y, mut side_dist_y) = if dir_y < 0.0 {
+            (-1, (y - map_y as f32) * delta_dist_y)
+        } else {
+            (1, (map_y as f32 + 1.0 - y) * delta_dist_y)
+        };
+
+        // Out of bounds counts as a wall, so this always ends
+        loop {
+            let distance = if side_dist_x < side_dist_y {
+                map_x += step_x;
+                side_dist_x += delta_dist_x;
+                side_dist_x - delta_dist_x
+            } else {
+                map_y += step_y;
+                side_dist_y += delta_dist_y;
+                side_dist_y - delta_dist_y
+            };
+            if distance >= max_distance {
+                return max_distance;
+            }
+            if self.get_wall(map_x, map_y) > 0 {
+                return distance;
+            }
+        }
+    }
+
     /// Get wall color based on wall type
     pub fn get_wall_color(&self, wall_type: i32) -> Color {
         match wall_type {
@@ -156,24 +332,7 @@ impl Map2D {
                         let grid_z = center_z + dz;
 
                         if grid_x >= 0 && grid_x < width as i32 && grid_z >= 0 && grid_z < height as i32 {
-                            // Determine wall type based on object color
-                            let color_r = obj.color_r;
-                            let color_g = obj.color_g;
-                            let color_b = obj.color_b;
-
-                            let wall_type = if color_r > 200 && color_g < 100 && color_b < 100 {
-                                2 // Red
-                            } else if color_r < 100 && color_g > 200 && color_b < 100 {
-                                3 // Green
-                            } else if color_r < 100 && color_g < 100 && color_b > 200 {
-                                4 // Blue
-                            } else if color_r > 200 && color_g > 200 && color_b < 100 {
-                                5 // Yellow
-                            } else {
-                                1 // Default gray
-                            };
-
-                            map2d.grid[grid_x as usize][grid_z as usize] = wall_type;
+                            map2d.grid[grid_x as usize][grid_z as usize] = wall_type_for(obj);
                         }
                     }
                 }
@@ -183,3 +342,68 @@ impl Map2D {
         map2d
     }
 }
+
+/// Wall type (color) for a map object, from its color
+fn wall_type_for(obj: &MapObject) -> i32 {
+    let (color_r, color_g, color_b) = (obj.color_r, obj.color_g, obj.color_b);
+    if color_r > 200 && color_g < 100 && color_b < 100 {
+        2 // Red
+    } else if color_r < 100 && color_g > 200 && color_b < 100 {
+        3 // Green
+    } else if color_r < 100 && color_g < 100 && color_b > 200 {
+        4 // Blue
+    } else if color_r > 200 && color_g > 200 && color_b < 100 {
+        5 // Yellow
+    } else {
+        1 // Default gray
+    }
+}
+
+/// Whether an object's collision bounds are exactly its shape: a box only turned in
+/// quarter turns around the vertical axis
+fn is_upright_box(obj: &MapObject) -> bool {
+    matches!(obj.model_type, ModelType::Cube | ModelType::Rectangle)
+        && obj.rot_x % 360 == 0
+        && obj.rot_z % 360 == 0
+        && obj.rot_y % 90 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cast_ray_stops_at_the_first_wall() {
+        let map = Map2D::create_demo();
+        // From (4.5, 8.5) facing +x, the vertical wall at x = 8 starts 3.5 cells away
+        assert!((map.cast_ray(4.5, 8.5, 1.0, 0.0, 50.0) - 3.5).abs() < 1e-4);
+        assert_eq!(map.cast_ray(4.5, 8.5, 1.0, 0.0, 2.0), 2.0);
+        // Diagonal towards the border at x = 0 / y = 0
+        let d = std::f32::consts::FRAC_1_SQRT_2;
+        assert!((map.cast_ray(3.0, 3.0, -d, -d, 50.0) - 2.0 * std::f32::consts::SQRT_2).abs() < 1e-3);
+        assert_eq!(map.cast_ray(0.5, 0.5, 1.0, 0.0, 50.0), 0.0);
+    }
+
+    #[test]
+    fn test_line_of_sight_from_rasterized_map() {
+        let mut map = Map::new("Test".to_string());
+        // Tall wall across x = 0 (z from -3 to 3), and a low crate further along +z
+        let mut wall = MapObject::new(ModelType::Cube);
+        wall.set_scale(Vector3::new(1.0, 4.0, 6.0));
+        wall.set_position(Vector3::new(0.0, 2.0, 0.0));
+        let mut crate_box = MapObject::new(ModelType::Cube);
+        crate_box.set_scale(Vector3::new(1.0, 1.0, 1.0));
+        crate_box.set_position(Vector3::new(0.0, 0.5, 8.0));
+        map.objects = vec![wall, crate_box];
+
+        let grid = Map2D::from_map(&map, 0.5);
+        let eye = |x: f32, z: f32| Vector3::new(x, 1.7, z);
+        assert_eq!(grid.line_of_sight(eye(-5.0, 0.0), eye(5.0, 0.0)), Sight::Blocked);
+        assert_eq!(grid.line_of_sight(eye(-5.0, -5.0), eye(-5.0, 5.0)), Sight::Clear);
+        assert_eq!(grid.line_of_sight(eye(-5.0, 8.0), eye(5.0, 8.0)), Sight::Unsure);
+        assert_eq!(grid.line_of_sight(eye(-5.0, 0.0), Vector3::new(5.0, 6.0, 0.0)), Sight::Unsure);
+        // Grid and exact check agree where the grid answers
+        assert!(map.is_line_blocked(eye(-5.0, 0.0), eye(5.0, 0.0)));
+        assert!(!map.is_line_blocked(eye(-5.0, 8.0), eye(5.0, 8.0)));
+    }
+}
@@ -1,5 +1,7 @@
 pub mod renderer;
 pub mod map2d;
+pub mod pvs;
 
 pub use renderer::Raycaster;
-pub use map2d::Map2D;
+pub use map2d::{Map2D, Sight, MAP_GRID_CELL_SIZE};
+pub use pvs::{Pvs, PVS_ZONE_SIZE};
@@ -0,0 +1,150 @@
+use raylib::prelude::*;
+use crate::map::{WORLD_HALF_SIZE, WORLD_SIZE};
+use super::map2d::{Map2D, Sight, SIGHT_HEIGHT};
+
+/// Side of one visibility zone (world units)
+pub const PVS_ZONE_SIZE: f32 = 5.0;
+
+/// Sample points per zone side when testing whether two zones see each other
+const ZONE_SAMPLES: usize = 3;
+
+/// Height the sample points are taken at (any height inside the grid's sight band works)
+const SAMPLE_HEIGHT: f32 = 1.0;
+
+/// Potentially-visible set: which zones of the map can see which, precomputed at load
+///
+/// The map is cut into square zones and sample points in every pair of zones
+/// are tested against the rasterized grid; a pair counts as visible unless the
+/// grid says every line between them is `Blocked`. The result is then grown by
+/// one zone on each side, so a pair of corners the samples missed still shows.
+/// Only walls block, and walls only reach `SIGHT_HEIGHT`, so anything above the
+/// band is always potentially visible.
+pub struct Pvs {
+    /// Zones along each side of the world
+    zones_per_side: usize,
+    zone_size: f32,
+    /// `visible[a * zones + b]`: something in zone `b` may be seen from zone `a`
+    visible: Vec<bool>,
+}
+
+impl Pvs {
+    /// Precompute zone-to-zone visibility from a rasterized map
+    pub fn build(grid: &Map2D, zone_size: f32) -> Self {
+        let zones_per_side = (WORLD_SIZE / zone_size).ceil() as usize;
+        let count = zones_per_side * zones_per_side;
+        let samples: Vec<Vec<Vector3>> = (0..count)
+            .map(|zone| {
+                let (min_x, min_z) = zone_origin(zone, zones_per_side, zone_size);
+                (0..ZONE_SAMPLES * ZONE_SAMPLES)
+                    .map(|i| {
+                        let step = zone_size / ZONE_SAMPLES as f32;
+                        let x = min_x + step * ((i % ZONE_SAMPLES) as f32 + 0.5);
+                        let z = min_z + step * ((i / ZONE_SAMPLES) as f32 + 0.5);
+                        Vector3::new(x, SAMPLE_HEIGHT, z)
+                    })
+                    // Points inside walls see nothing; drop them so they don't cost queries
+                    .filter(|point| {
+                        let (x, y) = grid.world_to_grid(*point);
+                        !grid.is_solid(x, y)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut direct = vec![false; count * count];
+        for a in 0..count {
+            direct[a * count + a] = true;
+            for b in a + 1..count {
+                let seen = samples[a]
+                    .iter()
+                    .any(|from| samples[b].iter().any(|to| grid.line_of_sight(*from, *to) != Sight::Blocked));
+                direct[a * count + b] = seen;
+                direct[b * count + a] = seen;
+            }
+        }
+
+        let neighbours = |zone: usize| {
+            let (col, row) = ((zone % zones_per_side) as i32, (zone / zones_per_side) as i32);
+            (-1..=1)
+                .flat_map(move |dz| (-1..=1).map(move |dx| (col + dx, row + dz)))
+                .filter(|&(c, r)| c >= 0 && r >= 0 && (c as usize) < zones_per_side && (r as usize) < zones_per_side)
+                .map(|(c, r)| r as usize * zones_per_side + c as usize)
+        };
+        let mut visible = vec![false; count * count];
+        for a in 0..count {
+            for b in 0..count {
+                visible[a * count + b] = neighbours(b).any(|n| direct[a * count + n]) || neighbours(a).any(|n| direct[n * count + b]);
+            }
+        }
+
+        Self { zones_per_side, zone_size, visible }
+    }
+
+    /// Zone containing a world position (positions outside the world use the nearest edge zone)
+    fn zone_of(&self, position: Vector3) -> usize {
+        let cell = |value: f32| (((value + WORLD_HALF_SIZE) / self.zone_size).max(0.0) as usize).min(self.zones_per_side - 1);
+        cell(position.z) * self.zones_per_side + cell(position.x)
+    }
+
+    fn zones_see(&self, a: usize, b: usize) -> bool {
+        self.visible[a * self.zones_per_side * self.zones_per_side + b]
+    }
+
+    /// Whether a point may be visible from `from` (false only when walls hide its whole zone)
+    pub fn can_see(&self, from: Vector3, to: Vector3) -> bool {
+        let band = 0.0..=SIGHT_HEIGHT;
+        if !band.contains(&from.y) || !band.contains(&to.y) {
+            return true;
+        }
+        self.zones_see(self.zone_of(from), self.zone_of(to))
+    }
+
+    /// Whether any part of a box may be visible from `from` (it may span several zones;
+    /// anything reaching above the walls' guaranteed height always is)
+    pub fn can_see_box(&self, from: Vector3, bounds: &BoundingBox) -> bool {
+        let band = 0.0..=SIGHT_HEIGHT;
+        if !band.contains(&from.y) || bounds.max.y > SIGHT_HEIGHT {
+            return true;
+        }
+        let origin = self.zone_of(from);
+        let (first, last) = (self.zone_of(bounds.min), self.zone_of(bounds.max));
+        let (first_col, first_row) = (first % self.zones_per_side, first / self.zones_per_side);
+        let (last_col, last_row) = (last % self.zones_per_side, last / self.zones_per_side);
+        (first_row..=last_row)
+            .any(|row| (first_col..=last_col).any(|col| self.zones_see(origin, row * self.zones_per_side + col)))
+    }
+}
+
+/// World (x, z) of a zone's minimum corner
+fn zone_origin(zone: usize, zones_per_side: usize, zone_size: f32) -> (f32, f32) {
+    let (col, row) = (zone % zones_per_side, zone / zones_per_side);
+    (col as f32 * zone_size - WORLD_HALF_SIZE, row as f32 * zone_size - WORLD_HALF_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::{Map, MapObject, ModelType};
+
+    #[test]
+    fn test_wall_splitting_the_map_hides_the_far_side() {
+        let mut map = Map::new("Test".to_string());
+        let mut wall = MapObject::new(ModelType::Cube);
+        wall.set_scale(Vector3::new(1.0, 4.0, WORLD_SIZE));
+        wall.set_position(Vector3::new(0.0, 2.0, 0.0));
+        map.objects = vec![wall];
+
+        let pvs = Pvs::build(&Map2D::from_map(&map, 0.5), PVS_ZONE_SIZE);
+        let eye = |x: f32, z: f32| Vector3::new(x, 1.7, z);
+        assert!(!pvs.can_see(eye(-20.0, 0.0), eye(20.0, 0.0)));
+        assert!(pvs.can_see(eye(-20.0, -20.0), eye(-10.0, 20.0)));
+        // Right next to the wall the grown set still shows the other side
+        assert!(pvs.can_see(eye(-2.0, 0.0), eye(2.0, 0.0)));
+        // Above the wall nothing is culled
+        assert!(pvs.can_see(eye(-20.0, 0.0), Vector3::new(20.0, 6.0, 0.0)));
+        let low_box = BoundingBox::new(Vector3::new(18.0, 0.0, -1.0), Vector3::new(19.0, 1.0, 1.0));
+        assert!(!pvs.can_see_box(eye(-20.0, 0.0), &low_box));
+        let tower = BoundingBox::new(Vector3::new(18.0, 0.0, -1.0), Vector3::new(19.0, 8.0, 1.0));
+        assert!(pvs.can_see_box(eye(-20.0, 0.0), &tower));
+    }
+}
@@ -334,6 +334,75 @@ impl Raycaster {
         }
     }
 
+    /// Render a stylized top-down view of the grid into a `width` x `height` panel at (left, top)
+    /// pos_x, pos_y: viewer position in the 2D map
+    /// dir_x, dir_y: viewer direction (normalized); a fan of rays shows what the viewer can see
+    /// markers: other positions to show (x, y, color)
+    pub fn render_top_down(
+        &self,
+        d: &mut RaylibDrawHandle,
+        map: &Map2D,
+        left: i32,
+        top: i32,
+        pos_x: f32,
+        pos_y: f32,
+        dir_x: f32,
+        dir_y: f32,
+        markers: &[(f32, f32, Color)],
+    ) {
+        // Cell size in pixels, with the grid centered in the panel
+        let cell = (self.width as f32 / map.width as f32).min(self.height as f32 / map.height as f32);
+        let origin_x = left as f32 + (self.width as f32 - cell * map.width as f32) / 2.0;
+        let origin_y = top as f32 + (self.height as f32 - cell * map.height as f32) / 2.0;
+        let to_screen = |x: f32, y: f32| Vector2::new(origin_x + x * cell, origin_y + y * cell);
+
+        d.draw_rectangle(left, top, self.width, self.height, Color::new(8, 10, 16, 220));
+
+        // Walls, dimmed so the sight fan and markers stand out (low cover fainter still)
+        for x in 0..map.width {
+            for y in 0..map.height {
+                let wall_type = map.grid[x][y];
+                if wall_type == 0 {
+                    if map.partial[x][y] {
+                        d.draw_rectangle_v(to_screen(x as f32, y as f32), Vector2::new(cell.ceil(), cell.ceil()), Color::new(60, 60, 70, 255));
+                    }
+                    continue;
+                }
+                let color = map.get_wall_color(wall_type);
+                let corner = to_screen(x as f32, y as f32);
+                d.draw_rectangle_v(
+                    corner,
+                    Vector2::new(cell.ceil(), cell.ceil()),
+                    Color::new(color.r / 2 + 20, color.g / 2 + 20, color.b / 2 + 20, 255),
+                );
+            }
+        }
+
+        // Sight fan: one ray per degree across the FOV, stopped by walls
+        let fov = 90.0_f32.to_radians();
+        let range = map.width.max(map.height) as f32;
+        let heading = dir_y.atan2(dir_x);
+        let eye = to_screen(pos_x, pos_y);
+        for i in 0..=90 {
+            let angle = heading - fov / 2.0 + fov * i as f32 / 90.0;
+            let (ray_x, ray_y) = (angle.cos(), angle.sin());
+            let distance = map.cast_ray(pos_x, pos_y, ray_x, ray_y, range);
+            let hit = to_screen(pos_x + ray_x * distance, pos_y + ray_y * distance);
+            d.draw_line_v(eye, hit, Color::new(255, 240, 160, 40));
+            d.draw_circle_v(hit, 1.5, Color::new(255, 240, 160, 200));
+        }
+
+        for &(x, y, color) in markers {
+            let position = to_screen(x, y);
+            d.draw_circle_v(position, (cell * 0.6).max(3.0), color);
+            d.draw_circle_lines(position.x as i32, position.y as i32, (cell * 0.6).max(3.0), Color::BLACK);
+        }
+
+        // The viewer: a dot with a heading tick
+        d.draw_circle_v(eye, (cell * 0.7).max(4.0), Color::WHITE);
+        d.draw_line_ex(eye, to_screen(pos_x + dir_x * 2.0, pos_y + dir_y * 2.0), 2.0, Color::WHITE);
+    }
+
     /// Render bullet trails as glowing lines in screen space
     /// Trails are drawn as bright lines that appear to go from gun muzzle to hit point
     pub fn render_bullet_trails(
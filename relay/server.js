@@ -0,0 +1,329 @@
+/**
+ * Game relay - topic-based WebSocket fan-out for the traffic that isn't on chain
+ *
+ * Clients (app/src/utils/relay.js) join topics such as `game-events:<gameId>`
+ * or `party:<code>` and every message published to a topic is forwarded to
+ * the other clients in it. The relay keeps no state beyond topic membership
+ * and never interprets the payloads. No dependencies: the WebSocket handshake
+ * and framing (RFC 6455, text frames only) are handled here.
+ *
+ * Every connection starts by proving which key it speaks for: the relay sends
+ * a random nonce, the client signs it with its ed25519 key (the player's
+ * ephemeral wallet, the same key the game uses as the player's authority) and
+ * nothing but pings is accepted until the signature checks out. The relay then
+ * stamps that key on everything the connection publishes as `from`, so
+ * receivers never have to trust sender fields inside the payload.
+ *
+ * The relay pings every connection and drops the ones that stay silent, so
+ * a client that vanished without a close frame doesn't linger in its topics.
+ *
+ *   node relay/server.js            # listens on RELAY_PORT (default 7900)
+ *
+ * Messages are JSON text frames:
+ *   relay -> client  { op: "challenge", nonce }        (on connect)
+ *   client -> relay  { op: "auth", key, signature }    (base58 key, base64 signature of AUTH_PREFIX + nonce)
+ *   relay -> client  { op: "ready", key }
+ *   client -> relay  { op: "join" | "leave", topic }
+ *                    { op: "publish", topic, data }
+ *                    { op: "ping", id }
+ *   relay -> client  { topic, from, data }             (published by another client, `from` is its key)
+ *                    { op: "pong", id }
+ */
+
+const http = require("http");
+const crypto = require("crypto");
+
+const PORT = Number(process.env.RELAY_PORT) || 7900;
+const WEBSOCKET_GUID = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/** Largest message accepted (editor sessions send whole maps to joiners) */
+const MAX_MESSAGE_BYTES = 4 * 1024 * 1024;
+
+/** Topics one connection may be in at once */
+const MAX_TOPICS_PER_CLIENT = 32;
+
+/** Signed together with the nonce, so a relay signature can't pass for anything else */
+const AUTH_PREFIX = "fps.so relay auth:";
+
+/** Connections that haven't signed the challenge by then are closed */
+const AUTH_TIMEOUT_MS = 10000;
+
+/** How often every connection is pinged and checked for silence */
+const HEARTBEAT_INTERVAL_MS = 10000;
+
+/** Connections nothing has arrived from for this long are dropped (browsers answer pings on their own) */
+const IDLE_TIMEOUT_MS = 30000;
+
+/** DER header that turns a raw 32-byte ed25519 key into SPKI for crypto.createPublicKey */
+const ED25519_SPKI_PREFIX = Buffer.from("302a300506032b6570032100", "hex");
+
+const BASE58_ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+const OPCODE = { continuation: 0x0, text: 0x1, binary: 0x2, close: 0x8, ping: 0x9, pong: 0xa };
+
+/** topic -> Set of clients */
+const topics = new Map();
+
+/** Every open connection, for the heartbeat */
+const clients = new Set();
+
+function decodeBase58(text) {
+  let value = 0n;
+  for (const char of text) {
+    const digit = BASE58_ALPHABET.indexOf(char);
+    if (digit < 0) return null;
+    value = value * 58n + BigInt(digit);
+  }
+  const bytes = [];
+  for (; value > 0n; value >>= 8n) bytes.unshift(Number(value & 0xffn));
+  for (let i = 0; i < text.length && text[i] === "1"; i++) bytes.unshift(0);
+  return Buffer.from(bytes);
+}
+
+/** Whether `signature` (base64) is `key`'s (base58 ed25519) signature of the challenge `nonce` */
+function verifyAuth(key, nonce, signature) {
+  const raw = typeof key === "string" && key.length <= 44 ? decodeBase58(key) : null;
+  if (!raw || raw.length !== 32 || typeof signature !== "string") return false;
+  try {
+    const publicKey = crypto.createPublicKey({
+      key: Buffer.concat([ED25519_SPKI_PREFIX, raw]),
+      format: "der",
+      type: "spki",
+    });
+    return crypto.verify(null, Buffer.from(AUTH_PREFIX + nonce), publicKey, Buffer.from(signature, "base64"));
+  } catch (_) {
+    return false;
+  }
+}
+
+function encodeFrame(opcode, payload) {
+  const length = payload.length;
+  let header;
+  if (length < 126) {
+    header = Buffer.from([0x80 | opcode, length]);
+  } else if (length < 0x10000) {
+    header = Buffer.alloc(4);
+    header[0] = 0x80 | opcode;
+    header[1] = 126;
+    header.writeUInt16BE(length, 2);
+  } else {
+    header = Buffer.alloc(10);
+    header[0] = 0x80 | opcode;
+    header[1] = 127;
+    header.writeBigUInt64BE(BigInt(length), 2);
+  }
+  return Buffer.concat([header, payload]);
+}
+
+class Client {
+  constructor(socket) {
+    this.socket = socket;
+    this.buffer = Buffer.alloc(0);
+    this.fragments = [];
+    this.topics = new Set();
+    this.closed = false;
+    this.nonce = crypto.randomBytes(32).toString("base64");
+    this.identity = null; // Key the connection signed for, set once the challenge checks out
+    this.connectedAt = Date.now();
+    this.lastSeen = this.connectedAt;
+    clients.add(this);
+    this.send({ op: "challenge", nonce: this.nonce });
+  }
+
+  send(message) {
+    if (this.closed) return;
+    this.socket.write(encodeFrame(OPCODE.text, Buffer.from(JSON.stringify(message))));
+  }
+
+  close(code = 1000) {
+    if (this.closed) return;
+    const payload = Buffer.alloc(2);
+    payload.writeUInt16BE(code, 0);
+    this.socket.end(encodeFrame(OPCODE.close, payload));
+    this.dispose();
+  }
+
+  /** Drop a connection that stopped answering (no close handshake, the peer is gone) */
+  terminate() {
+    this.dispose();
+    this.socket.destroy();
+  }
+
+  dispose() {
+    if (this.closed) return;
+    this.closed = true;
+    clients.delete(this);
+    this.topics.forEach((topic) => leave(this, topic));
+  }
+
+  heartbeat(now) {
+    if (!this.identity && now - this.connectedAt > AUTH_TIMEOUT_MS) {
+      this.close(1008);
+    } else if (now - this.lastSeen > IDLE_TIMEOUT_MS) {
+      this.terminate();
+    } else {
+      this.socket.write(encodeFrame(OPCODE.ping, Buffer.alloc(0)));
+    }
+  }
+
+  /** Parse as many complete frames as the buffer holds */
+  receive(chunk) {
+    this.lastSeen = Date.now();
+    this.buffer = Buffer.concat([this.buffer, chunk]);
+    while (this.buffer.length >= 2) {
+      const first = this.buffer[0];
+      const second = this.buffer[1];
+      const masked = (second & 0x80) !== 0;
+      let length = second & 0x7f;
+      let offset = 2;
+      if (length === 126) {
+        if (this.buffer.length < 4) return;
+        length = this.buffer.readUInt16BE(2);
+        offset = 4;
+      } else if (length === 127) {
+        if (this.buffer.length < 10) return;
+        length = Number(this.buffer.readBigUInt64BE(2));
+        offset = 10;
+      }
+      // Browsers always mask what they send
+      if (!masked || length > MAX_MESSAGE_BYTES) {
+        this.close(!masked ? 1002 : 1009);
+        return;
+      }
+      if (this.buffer.length < offset + 4 + length) return;
+
+      const mask = this.buffer.subarray(offset, offset + 4);
+      const payload = Buffer.from(this.buffer.subarray(offset + 4, offset + 4 + length));
+      for (let i = 0; i < payload.length; i++) payload[i] ^= mask[i % 4];
+      this.buffer = this.buffer.subarray(offset + 4 + length);
+      this.frame(first & 0x80, first & 0x0f, payload);
+      if (this.closed) return;
+    }
+  }
+
+  frame(fin, opcode, payload) {
+    switch (opcode) {
+      case OPCODE.text:
+      case OPCODE.continuation:
+        this.fragments.push(payload);
+        if (this.fragments.reduce((total, part) => total + part.length, 0) > MAX_MESSAGE_BYTES) {
+          this.close(1009);
+          return;
+        }
+        if (fin) {
+          const text = Buffer.concat(this.fragments).toString("utf8");
+          this.fragments = [];
+          this.message(text);
+        }
+        break;
+      case OPCODE.ping:
+        this.socket.write(encodeFrame(OPCODE.pong, payload));
+        break;
+      case OPCODE.close:
+        this.close();
+        break;
+      case OPCODE.pong:
+        break;
+      default:
+        this.close(1003); // Binary frames aren't part of the protocol
+    }
+  }
+
+  message(text) {
+    let message;
+    try {
+      message = JSON.parse(text);
+    } catch (_) {
+      return;
+    }
+    if (!message || typeof message !== "object") return;
+    const topic = typeof message.topic === "string" ? message.topic.slice(0, 128) : null;
+
+    if (message.op === "ping") {
+      this.send({ op: "pong", id: message.id });
+      return;
+    }
+    if (!this.identity) {
+      if (message.op !== "auth") return;
+      if (!verifyAuth(message.key, this.nonce, message.signature)) {
+        this.close(1008);
+        return;
+      }
+      this.identity = message.key;
+      this.send({ op: "ready", key: this.identity });
+      return;
+    }
+
+    switch (message.op) {
+      case "join":
+        if (topic && this.topics.size < MAX_TOPICS_PER_CLIENT) join(this, topic);
+        break;
+      case "leave":
+        if (topic) leave(this, topic);
+        break;
+      case "publish":
+        // Only members of a topic may publish to it
+        if (topic && this.topics.has(topic)) publish(this, topic, message.data);
+        break;
+      default:
+        break;
+    }
+  }
+}
+
+function join(client, topic) {
+  if (!topics.has(topic)) topics.set(topic, new Set());
+  topics.get(topic).add(client);
+  client.topics.add(topic);
+}
+
+function leave(client, topic) {
+  const members = topics.get(topic);
+  if (members) {
+    members.delete(client);
+    if (members.size === 0) topics.delete(topic);
+  }
+  client.topics.delete(topic);
+}
+
+function publish(sender, topic, data) {
+  topics.get(topic)?.forEach((member) => {
+    if (member !== sender) member.send({ topic, from: sender.identity, data });
+  });
+}
+
+const server = http.createServer((request, response) => {
+  // Health check for load balancers and the README's quick test
+  response.writeHead(request.url === "/health" ? 200 : 426, { "Content-Type": "text/plain" });
+  response.end(request.url === "/health" ? "ok" : "WebSocket only");
+});
+
+server.on("upgrade", (request, socket) => {
+  const key = request.headers["sec-websocket-key"];
+  if (!key || String(request.headers.upgrade).toLowerCase() !== "websocket") {
+    socket.end("HTTP/1.1 400 Bad Request\r\n\r\n");
+    return;
+  }
+  const accept = crypto.createHash("sha1").update(key + WEBSOCKET_GUID).digest("base64");
+  socket.write(
+    "HTTP/1.1 101 Switching Protocols\r\n" +
+      "Upgrade: websocket\r\n" +
+      "Connection: Upgrade\r\n" +
+      `Sec-WebSocket-Accept: ${accept}\r\n\r\n`
+  );
+  socket.setNoDelay(true);
+
+  const client = new Client(socket);
+  socket.on("data", (chunk) => client.receive(chunk));
+  socket.on("close", () => client.dispose());
+  socket.on("error", () => client.dispose());
+});
+
+setInterval(() => {
+  const now = Date.now();
+  clients.forEach((client) => client.heartbeat(now));
+}, HEARTBEAT_INTERVAL_MS);
+
+server.listen(PORT, () => {
+  console.log(`🛰️  Game relay listening on ws://0.0.0.0:${PORT}`);
+});
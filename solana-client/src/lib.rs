@@ -0,0 +1,165 @@
+//! Map registry client, compiled with wasm-bindgen for the React app
+//!
+//! Map encoding, PDA derivation, account decoding and transaction building happen
+//! here; JavaScript only signs the returned transactions with the wallet and sends
+//! them (see `app/src/utils/solana-client.js`).
+
+use std::fmt;
+use js_sys::{Array, Object, Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+
+pub mod map_chunks;
+pub mod map_registry;
+pub mod map_tables;
+pub mod maps;
+pub mod pubkey;
+pub mod rpc;
+pub mod transaction;
+
+use map_chunks::{chunk_map_id, is_chunk_map_id, read_map_manifest, tables_map_id};
+use map_registry::{Account, MapData, MapMetadata, UserMapIndex};
+use map_tables::MapTables;
+use maps::MapContent;
+use pubkey::Pubkey;
+use rpc::RpcClient;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientError {
+    InvalidPubkey(String),
+    /// Not a Borsh-encoded game map
+    InvalidMapData(String),
+    /// Account data that doesn't decode as the expected account
+    InvalidAccount(String),
+    AccountNotFound(String),
+    /// Updating a map created by another wallet
+    NotCreator(String),
+    Rpc(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::InvalidPubkey(key) => write!(f, "invalid public key: {}", key),
+            ClientError::InvalidMapData(reason) => write!(f, "invalid map data: {}", reason),
+            ClientError::InvalidAccount(reason) => write!(f, "invalid account data: {}", reason),
+            ClientError::AccountNotFound(what) => write!(f, "{} not found", what),
+            ClientError::NotCreator(map_id) => write!(f, "map \"{}\" belongs to another wallet", map_id),
+            ClientError::Rpc(reason) => write!(f, "RPC request failed: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+async fn fetch_account<T: Account>(rpc: &RpcClient, address: &Pubkey, what: &str) -> Result<T, ClientError> {
+    let data = rpc
+        .get_account_data(address)
+        .await?
+        .ok_or_else(|| ClientError::AccountNotFound(what.to_string()))?;
+    T::decode(&data)
+}
+
+/// Each plan step as an unsigned transaction, all on the same recent blockhash
+async fn unsigned_transactions(rpc: &RpcClient, payer: &Pubkey, plan: &[Vec<transaction::Instruction>]) -> Result<Array, ClientError> {
+    let blockhash = rpc.get_latest_blockhash().await?;
+    Ok(plan
+        .iter()
+        .map(|instructions| Uint8Array::from(&transaction::unsigned_transaction(payer, instructions, &blockhash)[..]))
+        .collect())
+}
+
+/// Transactions creating a map (the head, then one per chunk and side-table account)
+/// `map_bytes` is a Borsh-encoded game `Map`
+#[wasm_bindgen(js_name = createMap)]
+pub async fn create_map(
+    rpc_url: &str,
+    payer: &str,
+    map_id: &str,
+    name: &str,
+    description: &str,
+    is_default: bool,
+    map_bytes: &[u8],
+) -> Result<Array, JsError> {
+    let rpc = RpcClient::new(rpc_url);
+    let payer = Pubkey::from_base58(payer)?;
+    let (_, map) = maps::read_map_bytes(map_bytes)?;
+    let registry_exists = rpc.get_account_data(&map_registry::registry_address()).await?.is_some();
+    let plan = maps::create_map_plan(&payer, registry_exists, map_id, name, description, is_default, &map);
+    Ok(unsigned_transactions(&rpc, &payer, &plan).await?)
+}
+
+/// Transactions replacing the objects and side tables of a map `payer` created
+#[wasm_bindgen(js_name = updateMap)]
+pub async fn update_map(rpc_url: &str, payer: &str, map_id: &str, map_bytes: &[u8]) -> Result<Array, JsError> {
+    let rpc = RpcClient::new(rpc_url);
+    let payer = Pubkey::from_base58(payer)?;
+    let (_, map) = maps::read_map_bytes(map_bytes)?;
+    let metadata: MapMetadata = fetch_account(&rpc, &map_registry::metadata_address(map_id), "map metadata").await?;
+    let plan = maps::update_map_plan(&payer, &metadata, &map)?;
+    Ok(unsigned_transactions(&rpc, &payer, &plan).await?)
+}
+
+/// Objects of the first `chunks` chunks of a map and its side tables, in one request
+async fn fetch_map_content(rpc: &RpcClient, map_id: &str, metadata: &MapMetadata, chunks: usize) -> Result<MapContent, ClientError> {
+    let tables = read_map_manifest(&metadata.description).tables;
+    let ids: Vec<String> = (0..chunks)
+        .map(|i| chunk_map_id(map_id, i))
+        .chain((1..=tables).map(|i| tables_map_id(map_id, i)))
+        .collect();
+    let addresses: Vec<Pubkey> = ids.iter().map(|id| map_registry::data_address(id)).collect();
+
+    let mut accounts = Vec::new();
+    for (id, data) in ids.iter().zip(rpc.get_multiple_account_data(&addresses).await?) {
+        let data = data.ok_or_else(|| ClientError::AccountNotFound(format!("map data of {}", id)))?;
+        accounts.push(MapData::decode(&data)?.objects);
+    }
+    let records = accounts.split_off(chunks);
+    Ok(MapContent { objects: accounts.concat(), tables: MapTables::from_records(&records.concat())? })
+}
+
+/// A map's objects from every chunk and its side tables, as a Borsh-encoded game `Map` named after the head
+#[wasm_bindgen(js_name = getMapData)]
+pub async fn get_map_data(rpc_url: &str, map_id: &str) -> Result<Uint8Array, JsError> {
+    let rpc = RpcClient::new(rpc_url);
+    let metadata: MapMetadata = fetch_account(&rpc, &map_registry::metadata_address(map_id), "map metadata").await?;
+    let chunks = read_map_manifest(&metadata.description).chunks;
+    let map = fetch_map_content(&rpc, map_id, &metadata, chunks).await?;
+    Ok(Uint8Array::from(&maps::map_bytes(&metadata.name, &map)[..]))
+}
+
+/// `{bytes, name, chunks}`: the head chunk and every side table as a Borsh-encoded game `Map`,
+/// to start a match before the other chunks stream in, and the map's chunk count
+#[wasm_bindgen(js_name = getMapHead)]
+pub async fn get_map_head(rpc_url: &str, map_id: &str) -> Result<Object, JsError> {
+    let rpc = RpcClient::new(rpc_url);
+    let metadata: MapMetadata = fetch_account(&rpc, &map_registry::metadata_address(map_id), "map metadata").await?;
+    let chunks = read_map_manifest(&metadata.description).chunks;
+    let map = fetch_map_content(&rpc, map_id, &metadata, 1).await?;
+    let bytes = Uint8Array::from(&maps::map_bytes(&metadata.name, &map)[..]);
+
+    let result = Object::new();
+    Reflect::set(&result, &"bytes".into(), &bytes).map_err(|_| JsError::new("getMapHead: result"))?;
+    Reflect::set(&result, &"name".into(), &JsValue::from_str(&metadata.name)).map_err(|_| JsError::new("getMapHead: result"))?;
+    Reflect::set(&result, &"chunks".into(), &(chunks as u32).into()).map_err(|_| JsError::new("getMapHead: result"))?;
+    Ok(result)
+}
+
+/// `{owner, mapCount, mapIds}` of a wallet's map index, without the chunk accounts of large maps
+#[wasm_bindgen(js_name = getUserMaps)]
+pub async fn get_user_maps(rpc_url: &str, owner: &str) -> Result<Object, JsError> {
+    let rpc = RpcClient::new(rpc_url);
+    let owner = Pubkey::from_base58(owner)?;
+    let index: UserMapIndex = fetch_account(&rpc, &map_registry::user_index_address(&owner), "user map index").await?;
+    let map_ids: Array = index
+        .map_ids
+        .iter()
+        .filter(|map_id| !is_chunk_map_id(map_id))
+        .map(|map_id| JsValue::from_str(map_id))
+        .collect();
+
+    let result = Object::new();
+    Reflect::set(&result, &"owner".into(), &index.owner.to_base58().into()).map_err(|_| JsError::new("getUserMaps: result"))?;
+    Reflect::set(&result, &"mapCount".into(), &index.map_count.into()).map_err(|_| JsError::new("getUserMaps: result"))?;
+    Reflect::set(&result, &"mapIds".into(), &map_ids).map_err(|_| JsError::new("getUserMaps: result"))?;
+    Ok(result)
+}
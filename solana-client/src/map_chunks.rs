@@ -0,0 +1,180 @@
+//! Chunked storage for maps too large for one map account
+//!
+//! Same layout as `app/src/utils/map-chunks.js`, which the JS bridge still uses to
+//! stream chunks into a running match: the head keeps the map's own id and the
+//! first chunk, chunk i (from 1) is stored under `{map_id}~{i}`, and the head's
+//! description ends with a manifest such as `[chunks 3 objects 1320]`. Maps with side
+//! tables (see `map_tables.rs`) also count their table accounts, stored under
+//! `{map_id}~t{i}`: `[chunks 1 objects 40 tables 1]`.
+
+use crate::map_registry::MapObject;
+
+/// Objects stored per map account (19 Borsh bytes each, inside the 10 KB account budget)
+pub const MAP_CHUNK_OBJECTS: usize = 500;
+
+const CHUNK_SEPARATOR: char = '~';
+
+/// Map id under which chunk `index` of `map_id` is stored (the head is chunk 0)
+pub fn chunk_map_id(map_id: &str, index: usize) -> String {
+    if index == 0 {
+        map_id.to_string()
+    } else {
+        format!("{}{}{}", map_id, CHUNK_SEPARATOR, index)
+    }
+}
+
+/// Map id under which side-table account `index` (from 1) of `map_id` is stored
+pub fn tables_map_id(map_id: &str, index: usize) -> String {
+    format!("{}{}t{}", map_id, CHUNK_SEPARATOR, index)
+}
+
+/// Whether a map id names a chunk or side-table account rather than a map players pick
+pub fn is_chunk_map_id(map_id: &str) -> bool {
+    match map_id.rsplit_once(CHUNK_SEPARATOR) {
+        Some((_, index)) => {
+            let index = index.strip_prefix('t').unwrap_or(index);
+            !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+fn distance_from_center(object: &MapObject) -> f64 {
+    (object.pos_x as f64).hypot(object.pos_z as f64)
+}
+
+/// Order objects are stored in, as indices into `objects`
+///
+/// Spawn points go first, then objects by distance from the center, so the head chunk
+/// is the playable core of the map. Maps that fit one account keep their object order.
+pub fn chunk_order(objects: &[MapObject]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..objects.len()).collect();
+    if objects.len() > MAP_CHUNK_OBJECTS {
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&objects[a], &objects[b]);
+            b.is_spawn_point()
+                .cmp(&a.is_spawn_point())
+                .then(distance_from_center(a).total_cmp(&distance_from_center(b)))
+        });
+    }
+    order
+}
+
+/// Split objects into account-sized chunks, in `chunk_order`
+pub fn split_map_objects(objects: &[MapObject]) -> Vec<Vec<MapObject>> {
+    if objects.len() <= MAP_CHUNK_OBJECTS {
+        return vec![objects.to_vec()];
+    }
+    let ordered: Vec<MapObject> = chunk_order(objects).into_iter().map(|i| objects[i]).collect();
+    ordered.chunks(MAP_CHUNK_OBJECTS).map(<[MapObject]>::to_vec).collect()
+}
+
+/// Account counts a head description's manifest records
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapManifest {
+    pub chunks: usize,
+    /// Total object count (None when there's no manifest)
+    pub objects: Option<usize>,
+    /// Side-table accounts
+    pub tables: usize,
+}
+
+/// Byte offset where the manifest (with its leading whitespace) starts, and what it records
+fn find_manifest(description: &str) -> Option<(usize, MapManifest)> {
+    let body = description.strip_suffix(']')?;
+    let open = body.rfind("[chunks ")?;
+    let (chunks, rest) = body[open + "[chunks ".len()..].split_once(" objects ")?;
+    let (objects, tables) = rest.split_once(" tables ").unwrap_or((rest, "0"));
+    let is_count = |text: &str| !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit());
+    if !is_count(chunks) || !is_count(objects) || !is_count(tables) {
+        return None;
+    }
+    let start = description[..open].trim_end().len();
+    let manifest = MapManifest { chunks: chunks.parse().ok()?, objects: Some(objects.parse().ok()?), tables: tables.parse().ok()? };
+    Some((start, manifest))
+}
+
+/// What a head description's manifest records; one chunk and no tables when there's no manifest
+pub fn read_map_manifest(description: &str) -> MapManifest {
+    match find_manifest(description) {
+        Some((_, manifest)) => manifest,
+        None => MapManifest { chunks: 1, objects: None, tables: 0 },
+    }
+}
+
+/// Description without its manifest, as shown to players
+pub fn strip_map_manifest(description: &str) -> &str {
+    match find_manifest(description) {
+        Some((start, _)) => &description[..start],
+        None => description,
+    }
+}
+
+/// Description carrying the manifest for a map split into `chunks` chunks with `tables` side-table accounts
+pub fn with_map_manifest(description: &str, chunks: usize, objects: usize, tables: usize) -> String {
+    let text = strip_map_manifest(description);
+    match (chunks, tables) {
+        (0..=1, 0) => text.to_string(),
+        (_, 0) => format!("{} [chunks {} objects {}]", text, chunks, objects),
+        _ => format!("{} [chunks {} objects {} tables {}]", text, chunks, objects, tables),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map_registry::ModelType;
+
+    fn object(model_type: ModelType, pos_x: i16, pos_z: i16) -> MapObject {
+        MapObject {
+            model_type,
+            pos_x, pos_y: 0, pos_z,
+            rot_x: 0, rot_y: 0, rot_z: 0,
+            scale_x: 1, scale_y: 1, scale_z: 1,
+            color_r: 0, color_g: 0, color_b: 0,
+        }
+    }
+
+    #[test]
+    fn test_chunk_ids() {
+        assert_eq!(chunk_map_id("arena", 0), "arena");
+        assert_eq!(chunk_map_id("arena", 2), "arena~2");
+        assert_eq!(tables_map_id("arena", 1), "arena~t1");
+        assert!(is_chunk_map_id("arena~2"));
+        assert!(is_chunk_map_id("arena~t1"));
+        assert!(!is_chunk_map_id("arena~t"));
+        assert!(!is_chunk_map_id("arena"));
+        assert!(!is_chunk_map_id("arena~"));
+        assert!(!is_chunk_map_id("arena~b"));
+    }
+
+    #[test]
+    fn test_split_puts_spawns_and_center_first() {
+        let small: Vec<MapObject> = (0..3).map(|i| object(ModelType::Cube, 100 - i, 0)).collect();
+        assert_eq!(split_map_objects(&small), vec![small.clone()]);
+
+        let mut large: Vec<MapObject> = (0..MAP_CHUNK_OBJECTS as i16 + 10).map(|i| object(ModelType::Cube, i * 10, 0)).collect();
+        large.push(object(ModelType::SpawnPointRed, 30000, 30000));
+        let chunks = split_map_objects(&large);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].len(), MAP_CHUNK_OBJECTS);
+        assert_eq!(chunks[1].len(), 11);
+        assert!(chunks[0][0].is_spawn_point());
+        assert_eq!(chunks[0][1].pos_x, 0);
+        assert_eq!(chunks[1].last().unwrap().pos_x, (MAP_CHUNK_OBJECTS as i16 + 9) * 10);
+    }
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let manifest = |chunks, objects, tables| MapManifest { chunks, objects, tables };
+        assert_eq!(read_map_manifest("Big arena [chunks 3 objects 1320]"), manifest(3, Some(1320), 0));
+        assert_eq!(read_map_manifest("Lit arena [chunks 1 objects 40 tables 1]"), manifest(1, Some(40), 1));
+        assert_eq!(read_map_manifest("Small arena"), manifest(1, None, 0));
+        assert_eq!(read_map_manifest("[chunks x objects 2]"), manifest(1, None, 0));
+        assert_eq!(strip_map_manifest("Big arena  [chunks 3 objects 1320]"), "Big arena");
+        assert_eq!(strip_map_manifest("Lit arena [chunks 1 objects 40 tables 1]"), "Lit arena");
+        assert_eq!(with_map_manifest("Big arena [chunks 3 objects 1320]", 2, 900, 0), "Big arena [chunks 2 objects 900]");
+        assert_eq!(with_map_manifest("Big arena [chunks 3 objects 1320]", 1, 400, 0), "Big arena");
+        assert_eq!(with_map_manifest("Lit arena", 1, 40, 1), "Lit arena [chunks 1 objects 40 tables 1]");
+    }
+}
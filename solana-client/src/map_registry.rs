@@ -0,0 +1,263 @@
+//! map_registry program: accounts, PDAs and instructions (see idls/map_registry.json)
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use crate::pubkey::{Pubkey, SYSTEM_PROGRAM_ID};
+use crate::transaction::{AccountMeta, Instruction};
+use crate::ClientError;
+
+/// `6XPHneawKSf2BWTtfZurtMdVvBiKsriTnGLKjoWdK791`
+pub const PROGRAM_ID: Pubkey = Pubkey([
+    82, 19, 15, 199, 242, 157, 253, 71, 233, 47, 67, 163, 20, 160, 1, 19,
+    50, 188, 123, 200, 185, 87, 103, 64, 161, 234, 116, 134, 136, 208, 173, 88,
+]);
+
+const REGISTRY_SEED: &[u8] = b"fps.so map-registry";
+const METADATA_SEED: &[u8] = b"fps.so map-metadata";
+const DATA_SEED: &[u8] = b"fps.so map-data";
+const USER_INDEX_SEED: &[u8] = b"fps.so user-map-index";
+
+// Anchor discriminators: first 8 bytes of sha256("global:<instruction>") / sha256("account:<Account>")
+const INITIALIZE: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+const CREATE_MAP: [u8; 8] = [119, 8, 165, 241, 187, 193, 182, 112];
+const UPDATE_MAP_DATA: [u8; 8] = [17, 72, 140, 250, 90, 174, 19, 55];
+const UPDATE_MAP_METADATA: [u8; 8] = [159, 12, 58, 77, 27, 72, 54, 190];
+const DELETE_MAP: [u8; 8] = [67, 93, 211, 211, 155, 204, 208, 34];
+
+/// Shape of a map object, same variant order as the game's `ModelType`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum ModelType {
+    Cube,
+    Rectangle,
+    Triangle,
+    Sphere,
+    Cylinder,
+    Plane,
+    SpawnPointBlue,
+    SpawnPointRed,
+}
+
+/// One object as stored on chain (19 Borsh bytes, the same layout as the game's `MapObject`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct MapObject {
+    pub model_type: ModelType,
+    pub pos_x: i16,
+    pub pos_y: i16,
+    pub pos_z: i16,
+    pub rot_x: u16,
+    pub rot_y: u16,
+    pub rot_z: u16,
+    pub scale_x: u8,
+    pub scale_y: u8,
+    pub scale_z: u8,
+    pub color_r: u8,
+    pub color_g: u8,
+    pub color_b: u8,
+}
+
+impl MapObject {
+    pub fn is_spawn_point(&self) -> bool {
+        matches!(self.model_type, ModelType::SpawnPointBlue | ModelType::SpawnPointRed)
+    }
+}
+
+/// An Anchor account: 8-byte discriminator followed by the Borsh fields
+pub trait Account: BorshDeserialize {
+    const DISCRIMINATOR: [u8; 8];
+
+    /// Decode raw account data; trailing bytes (unused account space) are ignored
+    fn decode(data: &[u8]) -> Result<Self, ClientError> {
+        let body = data
+            .strip_prefix(&Self::DISCRIMINATOR)
+            .ok_or_else(|| ClientError::InvalidAccount("wrong account discriminator".to_string()))?;
+        Self::deserialize(&mut &body[..]).map_err(|e| ClientError::InvalidAccount(e.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct MapMetadata {
+    pub map_id: String,
+    pub name: String,
+    pub description: String,
+    pub creator: Pubkey,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub is_default: bool,
+}
+
+impl Account for MapMetadata {
+    const DISCRIMINATOR: [u8; 8] = [248, 190, 206, 246, 83, 170, 160, 254];
+}
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct MapData {
+    pub objects: Vec<MapObject>,
+}
+
+impl Account for MapData {
+    const DISCRIMINATOR: [u8; 8] = [117, 157, 116, 167, 252, 79, 29, 37];
+}
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct UserMapIndex {
+    pub owner: Pubkey,
+    pub map_count: u32,
+    pub map_ids: Vec<String>,
+}
+
+impl Account for UserMapIndex {
+    const DISCRIMINATOR: [u8; 8] = [60, 99, 152, 100, 155, 78, 200, 200];
+}
+
+pub fn registry_address() -> Pubkey {
+    Pubkey::find_program_address(&[REGISTRY_SEED], &PROGRAM_ID).0
+}
+
+pub fn metadata_address(map_id: &str) -> Pubkey {
+    Pubkey::find_program_address(&[METADATA_SEED, map_id.as_bytes()], &PROGRAM_ID).0
+}
+
+pub fn data_address(map_id: &str) -> Pubkey {
+    Pubkey::find_program_address(&[DATA_SEED, map_id.as_bytes()], &PROGRAM_ID).0
+}
+
+pub fn user_index_address(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[USER_INDEX_SEED, user.as_bytes()], &PROGRAM_ID).0
+}
+
+/// Discriminator followed by the Borsh-encoded arguments
+fn instruction_data(discriminator: [u8; 8], args: impl BorshSerialize) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    args.serialize(&mut data).expect("writing to a Vec can't fail");
+    data
+}
+
+/// Create the global registry account (once per deployment)
+pub fn initialize(user: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::writable(registry_address()),
+            AccountMeta::signer(*user),
+            AccountMeta::readonly(SYSTEM_PROGRAM_ID),
+        ],
+        data: INITIALIZE.to_vec(),
+    }
+}
+
+/// Create the metadata and data accounts of `map_id`
+pub fn create_map(user: &Pubkey, map_id: &str, name: &str, description: &str, is_default: bool, objects: &[MapObject]) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::writable(metadata_address(map_id)),
+            AccountMeta::writable(data_address(map_id)),
+            AccountMeta::writable(registry_address()),
+            AccountMeta::writable(user_index_address(user)),
+            AccountMeta::signer(*user),
+            AccountMeta::readonly(SYSTEM_PROGRAM_ID),
+        ],
+        data: instruction_data(CREATE_MAP, (map_id, name, description, is_default, objects)),
+    }
+}
+
+/// Replace the objects in the data account of `map_id`
+pub fn update_map_data(user: &Pubkey, map_id: &str, objects: &[MapObject]) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::readonly(metadata_address(map_id)),
+            AccountMeta::writable(data_address(map_id)),
+            AccountMeta::signer(*user),
+            AccountMeta::readonly(SYSTEM_PROGRAM_ID),
+        ],
+        data: instruction_data(UPDATE_MAP_DATA, objects),
+    }
+}
+
+/// Change the name and/or description of `map_id`
+pub fn update_map_metadata(user: &Pubkey, map_id: &str, name: Option<&str>, description: Option<&str>) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::writable(metadata_address(map_id)),
+            AccountMeta::signer(*user),
+        ],
+        data: instruction_data(UPDATE_MAP_METADATA, (name, description)),
+    }
+}
+
+/// Close both accounts of `map_id` and drop it from the creator's index
+pub fn delete_map(user: &Pubkey, creator: &Pubkey, map_id: &str) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::writable(metadata_address(map_id)),
+            AccountMeta::writable(data_address(map_id)),
+            AccountMeta::writable(user_index_address(user)),
+            AccountMeta::signer(*user),
+            AccountMeta::readonly(*creator),
+            AccountMeta::readonly(SYSTEM_PROGRAM_ID),
+        ],
+        data: DELETE_MAP.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    fn discriminator(preimage: &str) -> [u8; 8] {
+        Sha256::digest(preimage.as_bytes())[..8].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_program_id_and_discriminators_match_the_idl() {
+        assert_eq!(PROGRAM_ID.to_base58(), "6XPHneawKSf2BWTtfZurtMdVvBiKsriTnGLKjoWdK791");
+        assert_eq!(INITIALIZE, discriminator("global:initialize"));
+        assert_eq!(CREATE_MAP, discriminator("global:create_map"));
+        assert_eq!(UPDATE_MAP_DATA, discriminator("global:update_map_data"));
+        assert_eq!(UPDATE_MAP_METADATA, discriminator("global:update_map_metadata"));
+        assert_eq!(DELETE_MAP, discriminator("global:delete_map"));
+        assert_eq!(MapMetadata::DISCRIMINATOR, discriminator("account:MapMetadata"));
+        assert_eq!(MapData::DISCRIMINATOR, discriminator("account:MapData"));
+        assert_eq!(UserMapIndex::DISCRIMINATOR, discriminator("account:UserMapIndex"));
+    }
+
+    #[test]
+    fn test_decode_accounts() {
+        let index = UserMapIndex { owner: PROGRAM_ID, map_count: 2, map_ids: vec!["arena".into(), "arena~1".into()] };
+        let mut data = UserMapIndex::DISCRIMINATOR.to_vec();
+        index.serialize(&mut data).unwrap();
+        // Account space past the fields is zero-filled
+        data.extend([0; 16]);
+        assert_eq!(UserMapIndex::decode(&data).unwrap(), index);
+        assert!(MapData::decode(&data).is_err());
+    }
+
+    #[test]
+    fn test_create_map_instruction_layout() {
+        let user = Pubkey([7; 32]);
+        let object = MapObject {
+            model_type: ModelType::Sphere,
+            pos_x: -1, pos_y: 2, pos_z: 3,
+            rot_x: 0, rot_y: 90, rot_z: 0,
+            scale_x: 10, scale_y: 10, scale_z: 10,
+            color_r: 255, color_g: 0, color_b: 0,
+        };
+        let ix = create_map(&user, "arena", "Arena", "", false, &[object]);
+        assert_eq!(ix.accounts.len(), 6);
+        assert!(ix.accounts[4].is_signer && ix.accounts[4].pubkey == user);
+
+        let mut expected = CREATE_MAP.to_vec();
+        for text in ["arena", "Arena", ""] {
+            expected.extend((text.len() as u32).to_le_bytes());
+            expected.extend(text.as_bytes());
+        }
+        expected.push(0); // is_default
+        expected.extend(1u32.to_le_bytes());
+        assert_eq!(&ix.data[..expected.len()], &expected[..]);
+        assert_eq!(ix.data.len(), expected.len() + 19);
+        assert_eq!(ix.data[expected.len()], 3); // Sphere
+    }
+}
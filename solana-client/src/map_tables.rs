@@ -0,0 +1,377 @@
+//! On-chain storage for a game map's side tables
+//!
+//! Map accounts only hold objects, so everything the game's `Map` keeps after the spawn
+//! point (labels, ambiance, glows, collision flags, triggers, the script and ambient
+//! sounds) is stored in extra accounts of the same program. The tables are Borsh-encoded
+//! exactly as the game writes them, prefixed with their length and packed 18 bytes at a
+//! time into `MapObject` records (every field after the model type). Table account i (from
+//! 1) is stored under `{map_id}~t{i}` (see `map_chunks.rs`).
+//!
+//! Entries keyed by object index are renumbered when a large map's objects are reordered
+//! into chunks, so they keep pointing at their objects in the stored order.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use crate::map_chunks::MAP_CHUNK_OBJECTS;
+use crate::map_registry::{MapObject, ModelType};
+use crate::ClientError;
+
+/// Table bytes carried per record: everything in a `MapObject` after the model type
+const RECORD_BYTES: usize = 18;
+
+/// The game's `Ambiance::default()` (the Night preset), which maps without tables load with
+const DEFAULT_AMBIANCE: [u8; 8] = [13, 13, 17, 13, 13, 17, 0, 100];
+
+/// Same layout as the game's `ObjectLabel`
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+struct ObjectLabel {
+    object: u16,
+    name: String,
+    tags: String,
+}
+
+/// Same layout as the game's `ObjectGlow`
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+struct ObjectGlow {
+    object: u16,
+    color: [u8; 3],
+    intensity: u8,
+}
+
+/// Same layout as the game's `ObjectCollision` (the `Collision` variant as its Borsh tag)
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+struct ObjectCollision {
+    object: u16,
+    collision: u8,
+}
+
+/// Same variant order as the game's `TriggerAction` (the `TriggerSound` as its Borsh tag)
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+enum TriggerAction {
+    TeleportToTag(String),
+    OpenMover(String),
+    GrantPickup(String),
+    PlaySound(u8),
+    StartObjective(String),
+    Script,
+}
+
+/// Same layout as the game's `ObjectTrigger`
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+struct ObjectTrigger {
+    object: u16,
+    action: TriggerAction,
+    once: bool,
+}
+
+/// Entries of a side table that point at an object by index
+trait ObjectEntry {
+    fn object(&self) -> u16;
+    fn set_object(&mut self, object: u16);
+}
+
+macro_rules! object_entry {
+    ($($entry:ty),*) => {$(
+        impl ObjectEntry for $entry {
+            fn object(&self) -> u16 {
+                self.object
+            }
+
+            fn set_object(&mut self, object: u16) {
+                self.object = object;
+            }
+        }
+    )*};
+}
+
+object_entry!(ObjectLabel, ObjectGlow, ObjectCollision, ObjectTrigger);
+
+/// Point `entries` at the new position of their objects (`position[old] = new`), dropping
+/// any whose object doesn't exist, and keep them sorted by object index like the game does
+fn renumber<T: ObjectEntry>(entries: &mut Vec<T>, position: &[usize]) {
+    entries.retain_mut(|entry| match position.get(entry.object() as usize) {
+        Some(&new) => {
+            entry.set_object(new as u16);
+            true
+        }
+        None => false,
+    });
+    entries.sort_by_key(ObjectEntry::object);
+}
+
+/// The sections after the spawn point of a game map
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapTables {
+    /// How many sections the map had, in the game's order (older map versions end early)
+    sections: usize,
+    labels: Vec<ObjectLabel>,
+    ambiance: [u8; 8],
+    glows: Vec<ObjectGlow>,
+    collisions: Vec<ObjectCollision>,
+    triggers: Vec<ObjectTrigger>,
+    script: String,
+    /// Ambient sound emitters (nine bytes each, no object index)
+    ambient_sounds: Vec<[u8; 9]>,
+}
+
+impl Default for MapTables {
+    fn default() -> Self {
+        Self {
+            sections: 0,
+            labels: Vec::new(),
+            ambiance: DEFAULT_AMBIANCE,
+            glows: Vec::new(),
+            collisions: Vec::new(),
+            triggers: Vec::new(),
+            script: String::new(),
+            ambient_sounds: Vec::new(),
+        }
+    }
+}
+
+/// Read one section into `field` unless the map ended before it
+fn read_section<T: BorshDeserialize>(buf: &mut &[u8], sections: &mut usize, field: &mut T) -> std::io::Result<()> {
+    if !buf.is_empty() {
+        *field = T::deserialize(buf)?;
+        *sections += 1;
+    }
+    Ok(())
+}
+
+impl MapTables {
+    /// Read the sections after the spawn point, as the game's `Map::read_borsh_tail` does
+    pub fn read(buf: &mut &[u8]) -> Result<Self, ClientError> {
+        let mut tables = Self::default();
+        let invalid = |e: std::io::Error| ClientError::InvalidMapData(e.to_string());
+        read_section(buf, &mut tables.sections, &mut tables.labels).map_err(invalid)?;
+        read_section(buf, &mut tables.sections, &mut tables.ambiance).map_err(invalid)?;
+        read_section(buf, &mut tables.sections, &mut tables.glows).map_err(invalid)?;
+        read_section(buf, &mut tables.sections, &mut tables.collisions).map_err(invalid)?;
+        read_section(buf, &mut tables.sections, &mut tables.triggers).map_err(invalid)?;
+        read_section(buf, &mut tables.sections, &mut tables.script).map_err(invalid)?;
+        read_section(buf, &mut tables.sections, &mut tables.ambient_sounds).map_err(invalid)?;
+        if !buf.is_empty() {
+            return Err(ClientError::InvalidMapData("unexpected bytes after the side tables".to_string()));
+        }
+        Ok(tables)
+    }
+
+    /// Borsh bytes of the sections the map had, to follow the spawn point
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let sections = [
+            borsh::to_vec(&self.labels),
+            borsh::to_vec(&self.ambiance),
+            borsh::to_vec(&self.glows),
+            borsh::to_vec(&self.collisions),
+            borsh::to_vec(&self.triggers),
+            borsh::to_vec(&self.script),
+            borsh::to_vec(&self.ambient_sounds),
+        ];
+        sections
+            .into_iter()
+            .take(self.sections)
+            .flat_map(|section| section.expect("writing to a Vec can't fail"))
+            .collect()
+    }
+
+    /// Game map format version matching the sections (1 has none, each later version added one)
+    pub fn map_version(&self) -> u8 {
+        1 + self.sections as u8
+    }
+
+    /// Whether the tables hold nothing a map without them wouldn't load with
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+            && self.ambiance == DEFAULT_AMBIANCE
+            && self.glows.is_empty()
+            && self.collisions.is_empty()
+            && self.triggers.is_empty()
+            && self.script.trim().is_empty()
+            && self.ambient_sounds.is_empty()
+    }
+
+    /// Renumber object entries for objects stored in `order` (`order[new] = old`)
+    pub fn reorder(&mut self, order: &[usize]) {
+        let mut position = vec![0; order.len()];
+        for (new, &old) in order.iter().enumerate() {
+            position[old] = new;
+        }
+        renumber(&mut self.labels, &position);
+        renumber(&mut self.glows, &position);
+        renumber(&mut self.collisions, &position);
+        renumber(&mut self.triggers, &position);
+    }
+
+    /// Records for the table accounts, at most `MAP_CHUNK_OBJECTS` per account (none when empty)
+    pub fn to_records(&self) -> Vec<Vec<MapObject>> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let tables = self.to_bytes();
+        let mut bytes = (tables.len() as u32).to_le_bytes().to_vec();
+        bytes.extend(tables);
+        bytes.resize(bytes.len().div_ceil(RECORD_BYTES) * RECORD_BYTES, 0);
+
+        let records: Vec<MapObject> = bytes
+            .chunks(RECORD_BYTES)
+            .map(|payload| {
+                let mut record = vec![ModelType::Cube as u8];
+                record.extend(payload);
+                MapObject::try_from_slice(&record).expect("any 18 bytes are a valid record payload")
+            })
+            .collect();
+        records.chunks(MAP_CHUNK_OBJECTS).map(<[MapObject]>::to_vec).collect()
+    }
+
+    /// Tables packed by `to_records`, from every table account in order (the default tables
+    /// when the map has no table accounts)
+    pub fn from_records(records: &[MapObject]) -> Result<Self, ClientError> {
+        if records.is_empty() {
+            return Ok(Self::default());
+        }
+        let bytes: Vec<u8> = records
+            .iter()
+            .flat_map(|record| borsh::to_vec(record).expect("writing to a Vec can't fail").split_off(1))
+            .collect();
+        let invalid = || ClientError::InvalidMapData("truncated side tables".to_string());
+        let length = u32::from_le_bytes(bytes.get(..4).ok_or_else(invalid)?.try_into().expect("four bytes")) as usize;
+        let tables = bytes.get(4..4 + length).ok_or_else(invalid)?;
+        Self::read(&mut &tables[..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(object: u16, name: &str) -> ObjectLabel {
+        ObjectLabel { object, name: name.to_string(), tags: String::new() }
+    }
+
+    /// Tables as they come back from the table accounts `tables` is published to
+    fn published(tables: &MapTables) -> MapTables {
+        MapTables::from_records(&tables.to_records().concat()).unwrap()
+    }
+
+    #[test]
+    fn test_labels_and_ambiance_survive_the_table_accounts() {
+        let tables = MapTables {
+            sections: 2,
+            labels: vec![label(0, "mid box"), label(3, "a-site")],
+            ambiance: [120, 160, 210, 170, 195, 220, 8, 115],
+            ..MapTables::default()
+        };
+        let records = tables.to_records();
+        assert_eq!(records.len(), 1);
+        assert!(records[0].iter().all(|record| record.model_type == ModelType::Cube));
+        assert_eq!(published(&tables), tables);
+
+        // Large tables spill into a second account
+        let tables = MapTables {
+            sections: 1,
+            labels: (0..600).map(|i| label(i, "crate")).collect(),
+            ..MapTables::default()
+        };
+        assert_eq!(tables.to_records().len(), 2);
+        assert_eq!(published(&tables), tables);
+    }
+
+    #[test]
+    fn test_glows_survive_chunking_and_the_table_accounts() {
+        let glow = |object, intensity| ObjectGlow { object, color: [255, 0, 128], intensity };
+        let mut tables = MapTables { sections: 3, glows: vec![glow(0, 40), glow(2, 90)], ..MapTables::default() };
+        assert!(!tables.is_empty());
+
+        // Stored order: old object 1, then 2, then 0
+        tables.reorder(&[1, 2, 0]);
+        assert_eq!(tables.glows, vec![glow(1, 90), glow(2, 40)]);
+        assert_eq!(published(&tables), tables);
+    }
+
+    #[test]
+    fn test_triggers_survive_chunking_and_the_table_accounts() {
+        let trigger = |object, action, once| ObjectTrigger { object, action, once };
+        let mut tables = MapTables {
+            sections: 5,
+            triggers: vec![
+                trigger(0, TriggerAction::TeleportToTag("b-site".to_string()), false),
+                trigger(1, TriggerAction::OpenMover("gate".to_string()), true),
+                trigger(2, TriggerAction::GrantPickup("shotgun".to_string()), true),
+                trigger(3, TriggerAction::PlaySound(2), false),
+                trigger(4, TriggerAction::StartObjective("Plant the charge".to_string()), true),
+                trigger(5, TriggerAction::Script, false),
+            ],
+            ..MapTables::default()
+        };
+        assert_eq!(published(&tables), tables);
+
+        // Stored order reversed: the script trigger's object comes first
+        tables.reorder(&[5, 4, 3, 2, 1, 0]);
+        assert_eq!(tables.triggers[0], trigger(0, TriggerAction::Script, false));
+        assert_eq!(tables.triggers[5].object, 5);
+        assert_eq!(published(&tables), tables);
+    }
+
+    #[test]
+    fn test_script_alone_gets_table_accounts() {
+        let script = "on gate: open door\nevery 30s: grant shotgun\n";
+        let tables = MapTables { sections: 6, script: script.to_string(), ..MapTables::default() };
+        assert!(!tables.is_empty());
+        assert_eq!(published(&tables), tables);
+
+        // A script longer than one account spills into the next
+        let tables = MapTables { script: script.repeat(250), ..tables };
+        assert_eq!(tables.to_records().len(), 2);
+        assert_eq!(published(&tables).script, script.repeat(250));
+    }
+
+    #[test]
+    fn test_ambient_sounds_survive_chunking_and_the_table_accounts() {
+        // Wind at (1.5, 0, -2) heard within 12 units at 60%
+        let wind = [0, 150, 0, 0, 0, 56, 255, 12, 60];
+        let mut tables = MapTables { sections: 7, ambient_sounds: vec![wind; 3], ..MapTables::default() };
+        assert!(!tables.is_empty());
+
+        // Emitters aren't objects, so reordering leaves them alone
+        tables.reorder(&[1, 0]);
+        assert_eq!(tables.ambient_sounds, vec![wind; 3]);
+        assert_eq!(published(&tables), tables);
+    }
+
+    #[test]
+    fn test_reorder_follows_objects_into_chunks() {
+        let mut tables = MapTables {
+            sections: 1,
+            labels: vec![label(0, "first"), label(2, "last"), label(9, "gone")],
+            ..MapTables::default()
+        };
+        // Stored order: old object 2, then 0, then 1
+        tables.reorder(&[2, 0, 1]);
+        assert_eq!(tables.labels, vec![label(0, "last"), label(1, "first")]);
+    }
+
+    #[test]
+    fn test_read_keeps_older_map_versions() {
+        let mut tail = borsh::to_vec(&vec![label(1, "door")]).unwrap();
+        let tables = MapTables::read(&mut &tail[..]).unwrap();
+        assert_eq!(tables.map_version(), 2);
+        assert_eq!(tables.ambiance, DEFAULT_AMBIANCE);
+        assert_eq!(tables.to_bytes(), tail);
+
+        assert_eq!(MapTables::read(&mut &[][..]).unwrap(), MapTables::default());
+        assert_eq!(MapTables::from_records(&[]).unwrap(), MapTables::default());
+        tail.push(0);
+        assert!(MapTables::read(&mut &tail[..]).is_err());
+    }
+
+    #[test]
+    fn test_default_tables_need_no_accounts() {
+        let mut tables = MapTables { sections: 7, ..MapTables::default() };
+        assert!(tables.is_empty());
+        assert!(tables.to_records().is_empty());
+
+        tables.script = "  \n".to_string();
+        assert!(tables.is_empty());
+        tables.ambiance[7] = 80;
+        assert!(!tables.is_empty());
+    }
+}
@@ -0,0 +1,289 @@
+//! Map create/update plans and the game's Borsh map format
+//!
+//! A plan is the list of transactions (each a list of instructions) the wallet has
+//! to sign, in order. Fetching the accounts a plan depends on is left to the caller
+//! (see `lib.rs`), so everything here is plain data in, data out.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use crate::map_chunks::{chunk_map_id, chunk_order, read_map_manifest, split_map_objects, tables_map_id, with_map_manifest};
+use crate::map_registry::{self, MapMetadata, MapObject};
+use crate::map_tables::MapTables;
+use crate::pubkey::Pubkey;
+use crate::transaction::Instruction;
+use crate::ClientError;
+
+/// What the map accounts store of a game `Map`: its objects and side tables
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapContent {
+    pub objects: Vec<MapObject>,
+    pub tables: MapTables,
+}
+
+/// Name and content of a Borsh-encoded game `Map` (the spawn point isn't kept)
+pub fn read_map_bytes(bytes: &[u8]) -> Result<(String, MapContent), ClientError> {
+    let reader = &mut &bytes[..];
+    let invalid = |e: std::io::Error| ClientError::InvalidMapData(e.to_string());
+    let name = String::deserialize(reader).map_err(invalid)?;
+    let _version = u8::deserialize(reader).map_err(invalid)?;
+    let objects = Vec::<MapObject>::deserialize(reader).map_err(invalid)?;
+    let _spawn = <[i16; 3]>::deserialize(reader).map_err(invalid)?;
+    let tables = MapTables::read(reader)?;
+    Ok((name, MapContent { objects, tables }))
+}
+
+/// Borsh-encoded game `Map` holding `map`, with the spawn at the origin
+pub fn map_bytes(name: &str, map: &MapContent) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    (name, map.tables.map_version(), &map.objects, [0i16; 3])
+        .serialize(&mut bytes)
+        .expect("writing to a Vec can't fail");
+    bytes.extend(map.tables.to_bytes());
+    bytes
+}
+
+fn chunk_name(name: &str, index: usize, chunks: usize) -> String {
+    format!("{} ({}/{})", name, index + 1, chunks)
+}
+
+fn tables_name(name: &str, index: usize, tables: usize) -> String {
+    format!("{} (tables {}/{})", name, index, tables)
+}
+
+/// Table account records for `map`, pointing at its objects in the order they're stored in
+fn table_records(map: &MapContent) -> Vec<Vec<MapObject>> {
+    let mut tables = map.tables.clone();
+    tables.reorder(&chunk_order(&map.objects));
+    tables.to_records()
+}
+
+/// Transactions creating a map, one per account pair: the head, then each chunk and
+/// each side-table account
+///
+/// The registry is initialized in the head's transaction when it doesn't exist yet.
+pub fn create_map_plan(
+    payer: &Pubkey,
+    registry_exists: bool,
+    map_id: &str,
+    name: &str,
+    description: &str,
+    is_default: bool,
+    map: &MapContent,
+) -> Vec<Vec<Instruction>> {
+    let chunks = split_map_objects(&map.objects);
+    let tables = table_records(map);
+    let description = with_map_manifest(description, chunks.len(), map.objects.len(), tables.len());
+
+    let mut head = Vec::new();
+    if !registry_exists {
+        head.push(map_registry::initialize(payer));
+    }
+    head.push(map_registry::create_map(payer, map_id, name, &description, is_default, &chunks[0]));
+
+    let mut plan = vec![head];
+    for (i, chunk) in chunks.iter().enumerate().skip(1) {
+        let chunk_id = chunk_map_id(map_id, i);
+        plan.push(vec![map_registry::create_map(payer, &chunk_id, &chunk_name(name, i, chunks.len()), "", false, chunk)]);
+    }
+    for (i, records) in (1..).zip(&tables) {
+        let tables_id = tables_map_id(map_id, i);
+        plan.push(vec![map_registry::create_map(payer, &tables_id, &tables_name(name, i, tables.len()), "", false, records)]);
+    }
+    plan
+}
+
+/// Transactions replacing the objects and side tables of an existing map
+///
+/// Chunks and table accounts the map already had are rewritten, new ones created and the
+/// ones it no longer needs deleted; the head's manifest is updated last if a count changed.
+pub fn update_map_plan(
+    payer: &Pubkey,
+    metadata: &MapMetadata,
+    map: &MapContent,
+) -> Result<Vec<Vec<Instruction>>, ClientError> {
+    // Fail early with a clear message instead of a constraint error from the program
+    if metadata.creator != *payer {
+        return Err(ClientError::NotCreator(metadata.map_id.clone()));
+    }
+    let map_id = metadata.map_id.as_str();
+    let previous = read_map_manifest(&metadata.description);
+    let chunks = split_map_objects(&map.objects);
+    let tables = table_records(map);
+
+    let mut plan = vec![vec![map_registry::update_map_data(payer, map_id, &chunks[0])]];
+    for (i, chunk) in chunks.iter().enumerate().skip(1) {
+        let chunk_id = chunk_map_id(map_id, i);
+        let instruction = if i < previous.chunks {
+            map_registry::update_map_data(payer, &chunk_id, chunk)
+        } else {
+            map_registry::create_map(payer, &chunk_id, &chunk_name(&metadata.name, i, chunks.len()), "", false, chunk)
+        };
+        plan.push(vec![instruction]);
+    }
+    for i in chunks.len()..previous.chunks {
+        plan.push(vec![map_registry::delete_map(payer, &metadata.creator, &chunk_map_id(map_id, i))]);
+    }
+    for (i, records) in (1..).zip(&tables) {
+        let tables_id = tables_map_id(map_id, i);
+        let instruction = if i <= previous.tables {
+            map_registry::update_map_data(payer, &tables_id, records)
+        } else {
+            map_registry::create_map(payer, &tables_id, &tables_name(&metadata.name, i, tables.len()), "", false, records)
+        };
+        plan.push(vec![instruction]);
+    }
+    for i in tables.len() + 1..=previous.tables {
+        plan.push(vec![map_registry::delete_map(payer, &metadata.creator, &tables_map_id(map_id, i))]);
+    }
+    let description = with_map_manifest(&metadata.description, chunks.len(), map.objects.len(), tables.len());
+    if description != metadata.description {
+        plan.push(vec![map_registry::update_map_metadata(payer, map_id, None, Some(&description))]);
+    }
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map_chunks::MAP_CHUNK_OBJECTS;
+    use crate::map_registry::ModelType;
+
+    fn cubes(count: usize) -> Vec<MapObject> {
+        (0..count)
+            .map(|i| MapObject {
+                model_type: ModelType::Cube,
+                pos_x: i as i16, pos_y: 0, pos_z: 0,
+                rot_x: 0, rot_y: 0, rot_z: 0,
+                scale_x: 1, scale_y: 1, scale_z: 1,
+                color_r: 0, color_g: 0, color_b: 0,
+            })
+            .collect()
+    }
+
+    fn metadata(creator: Pubkey, description: &str) -> MapMetadata {
+        MapMetadata {
+            map_id: "arena".to_string(),
+            name: "Arena".to_string(),
+            description: description.to_string(),
+            creator,
+            created_at: 0,
+            updated_at: 0,
+            is_default: false,
+        }
+    }
+
+    fn content(objects: Vec<MapObject>) -> MapContent {
+        MapContent { objects, tables: MapTables::default() }
+    }
+
+    /// Objects with one labeled object, as the game's map format writes them after the spawn point
+    fn labeled(objects: Vec<MapObject>, object: u16) -> MapContent {
+        let tail = borsh::to_vec(&vec![(object, "mid box", "")]).unwrap();
+        MapContent { objects, tables: MapTables::read(&mut &tail[..]).unwrap() }
+    }
+
+    #[test]
+    fn test_map_bytes_round_trip() {
+        let map = content(cubes(3));
+        let bytes = map_bytes("Arena", &map);
+        assert_eq!(bytes.len(), 4 + 5 + 1 + 4 + 19 * 3 + 6);
+        assert_eq!(read_map_bytes(&bytes).unwrap(), ("Arena".to_string(), map));
+
+        // Side tables after the spawn point come back, and set the version
+        let map = labeled(cubes(3), 1);
+        let bytes = map_bytes("Arena", &map);
+        assert_eq!(bytes[9], 2);
+        assert_eq!(read_map_bytes(&bytes).unwrap().1, map);
+
+        let mut with_junk = bytes.clone();
+        with_junk.push(0);
+        assert!(read_map_bytes(&with_junk).is_err());
+        assert!(read_map_bytes(&bytes[..12]).is_err());
+    }
+
+    #[test]
+    fn test_create_plan_initializes_registry_and_chunks() {
+        let payer = Pubkey([1; 32]);
+        let plan = create_map_plan(&payer, false, "arena", "Arena", "Big", false, &content(cubes(MAP_CHUNK_OBJECTS + 1)));
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0].len(), 2);
+        assert_eq!(plan[0][1], map_registry::create_map(&payer, "arena", "Arena", "Big [chunks 2 objects 501]", false, &cubes(MAP_CHUNK_OBJECTS)));
+        assert_eq!(plan[1][0].accounts[0].pubkey, map_registry::metadata_address("arena~1"));
+
+        let plan = create_map_plan(&payer, true, "arena", "Arena", "Small", false, &content(cubes(2)));
+        assert_eq!(plan, vec![vec![map_registry::create_map(&payer, "arena", "Arena", "Small", false, &cubes(2))]]);
+    }
+
+    #[test]
+    fn test_create_plan_stores_side_tables() {
+        let payer = Pubkey([1; 32]);
+        let map = labeled(cubes(2), 1);
+        let plan = create_map_plan(&payer, true, "arena", "Arena", "Lit", false, &map);
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0], vec![map_registry::create_map(&payer, "arena", "Arena", "Lit [chunks 1 objects 2 tables 1]", false, &cubes(2))]);
+        let records = table_records(&map);
+        assert_eq!(plan[1], vec![map_registry::create_map(&payer, "arena~t1", "Arena (tables 1/1)", "", false, &records[0])]);
+        assert_eq!(MapTables::from_records(&records[0]).unwrap(), map.tables);
+    }
+
+    #[test]
+    fn test_table_records_follow_chunked_objects() {
+        // The last object is a spawn point, so chunking stores it first
+        let mut objects = cubes(MAP_CHUNK_OBJECTS + 1);
+        objects[MAP_CHUNK_OBJECTS].model_type = ModelType::SpawnPointBlue;
+        let map = labeled(objects, MAP_CHUNK_OBJECTS as u16);
+
+        let tables = MapTables::from_records(&table_records(&map).concat()).unwrap();
+        assert_eq!(tables, labeled(Vec::new(), 0).tables);
+    }
+
+    #[test]
+    fn test_collision_flags_load_back_from_chain() {
+        // A version 5 game map: no labels or glows, Day ambiance, object 1 is a player clip
+        let objects = cubes(3);
+        let mut bytes = borsh::to_vec(&("Arena", 5u8, &objects, [0i16; 3])).unwrap();
+        bytes.extend(borsh::to_vec(&(Vec::<u8>::new(), [120u8, 160, 210, 170, 195, 220, 8, 115], Vec::<u8>::new())).unwrap());
+        bytes.extend(borsh::to_vec(&vec![(1u16, 3u8)]).unwrap());
+
+        let (name, map) = read_map_bytes(&bytes).unwrap();
+        let records = table_records(&map);
+        let loaded = MapContent { objects: map.objects.clone(), tables: MapTables::from_records(&records.concat()).unwrap() };
+        assert_eq!(map_bytes(&name, &loaded), bytes);
+    }
+
+    #[test]
+    fn test_update_plan_drops_extra_chunks() {
+        let payer = Pubkey([1; 32]);
+        assert!(update_map_plan(&Pubkey([2; 32]), &metadata(payer, ""), &content(cubes(1))).is_err());
+
+        let plan = update_map_plan(&payer, &metadata(payer, "Big [chunks 3 objects 1200]"), &content(cubes(2))).unwrap();
+        assert_eq!(plan.len(), 4);
+        assert_eq!(plan[0], vec![map_registry::update_map_data(&payer, "arena", &cubes(2))]);
+        assert_eq!(plan[1], vec![map_registry::delete_map(&payer, &payer, "arena~1")]);
+        assert_eq!(plan[2], vec![map_registry::delete_map(&payer, &payer, "arena~2")]);
+        assert_eq!(plan[3], vec![map_registry::update_map_metadata(&payer, "arena", None, Some("Big"))]);
+
+        let plan = update_map_plan(&payer, &metadata(payer, "Small"), &content(cubes(2))).unwrap();
+        assert_eq!(plan.len(), 1);
+    }
+
+    #[test]
+    fn test_update_plan_rewrites_table_accounts() {
+        let payer = Pubkey([1; 32]);
+        let map = labeled(cubes(2), 0);
+        let records = table_records(&map);
+
+        let plan = update_map_plan(&payer, &metadata(payer, "Lit [chunks 1 objects 2 tables 2]"), &map).unwrap();
+        assert_eq!(plan.len(), 4);
+        assert_eq!(plan[1], vec![map_registry::update_map_data(&payer, "arena~t1", &records[0])]);
+        assert_eq!(plan[2], vec![map_registry::delete_map(&payer, &payer, "arena~t2")]);
+        assert_eq!(plan[3], vec![map_registry::update_map_metadata(&payer, "arena", None, Some("Lit [chunks 1 objects 2 tables 1]"))]);
+
+        let plan = update_map_plan(&payer, &metadata(payer, "Plain"), &map).unwrap();
+        assert_eq!(plan.len(), 3);
+        assert_eq!(plan[1], vec![map_registry::create_map(&payer, "arena~t1", "Arena (tables 1/1)", "", false, &records[0])]);
+
+        let plan = update_map_plan(&payer, &metadata(payer, "Lit [chunks 1 objects 2 tables 1]"), &content(cubes(2))).unwrap();
+        assert_eq!(plan[1], vec![map_registry::delete_map(&payer, &payer, "arena~t1")]);
+        assert_eq!(plan[2], vec![map_registry::update_map_metadata(&payer, "arena", None, Some("Lit"))]);
+    }
+}
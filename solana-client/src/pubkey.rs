@@ -0,0 +1,105 @@
+//! Public keys and program derived addresses
+
+use std::fmt;
+use borsh::{BorshDeserialize, BorshSerialize};
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use sha2::{Digest, Sha256};
+use crate::ClientError;
+
+/// Seeds longer than this are rejected by the runtime
+pub const MAX_SEED_LEN: usize = 32;
+
+/// Appended to every PDA hash so derived addresses can't collide with anything else
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+/// A 32-byte ed25519 public key (account address)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, BorshSerialize, BorshDeserialize)]
+pub struct Pubkey(pub [u8; 32]);
+
+/// The system program (all zeroes, `11111111111111111111111111111111`)
+pub const SYSTEM_PROGRAM_ID: Pubkey = Pubkey([0; 32]);
+
+impl Pubkey {
+    pub fn from_base58(address: &str) -> Result<Self, ClientError> {
+        let bytes = bs58::decode(address)
+            .into_vec()
+            .map_err(|_| ClientError::InvalidPubkey(address.to_string()))?;
+        let bytes: [u8; 32] = bytes.try_into().map_err(|_| ClientError::InvalidPubkey(address.to_string()))?;
+        Ok(Pubkey(bytes))
+    }
+
+    pub fn to_base58(&self) -> String {
+        bs58::encode(self.0).into_string()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Whether the key is a point on the ed25519 curve (PDAs must not be)
+    pub fn is_on_curve(&self) -> bool {
+        CompressedEdwardsY(self.0).decompress().is_some()
+    }
+
+    /// Address derived from `seeds` plus a bump seed, or None if it lands on the curve
+    pub fn create_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<Pubkey> {
+        if seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+            return None;
+        }
+        let mut hasher = Sha256::new();
+        for seed in seeds {
+            hasher.update(seed);
+        }
+        hasher.update(program_id.0);
+        hasher.update(PDA_MARKER);
+        let address = Pubkey(hasher.finalize().into());
+        (!address.is_on_curve()).then_some(address)
+    }
+
+    /// First off-curve address walking the bump seed down from 255, like `findProgramAddressSync`
+    pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+        for bump in (0..=u8::MAX).rev() {
+            let bump_seed = [bump];
+            let mut with_bump = seeds.to_vec();
+            with_bump.push(&bump_seed);
+            if let Some(address) = Self::create_program_address(&with_bump, program_id) {
+                return (address, bump);
+            }
+        }
+        // Every bump landing on the curve has probability 2^-256
+        panic!("no program address found for these seeds");
+    }
+}
+
+impl fmt::Display for Pubkey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_base58())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base58_round_trip() {
+        assert_eq!(SYSTEM_PROGRAM_ID.to_base58(), "11111111111111111111111111111111");
+        let key = Pubkey::from_base58("6XPHneawKSf2BWTtfZurtMdVvBiKsriTnGLKjoWdK791").unwrap();
+        assert_eq!(key.to_string(), "6XPHneawKSf2BWTtfZurtMdVvBiKsriTnGLKjoWdK791");
+        assert!(Pubkey::from_base58("not a key").is_err());
+        assert!(Pubkey::from_base58("1111").is_err());
+    }
+
+    #[test]
+    fn test_program_addresses_are_off_curve() {
+        let program_id = Pubkey::from_base58("6XPHneawKSf2BWTtfZurtMdVvBiKsriTnGLKjoWdK791").unwrap();
+        let (address, bump) = Pubkey::find_program_address(&[b"fps.so map-registry"], &program_id);
+        assert!(!address.is_on_curve());
+        assert_eq!(Pubkey::create_program_address(&[b"fps.so map-registry", &[bump]], &program_id), Some(address));
+
+        // Wallet keys are on the curve
+        let wallet = Pubkey::from_base58("DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh").unwrap();
+        assert!(wallet.is_on_curve());
+        assert!(Pubkey::create_program_address(&[&[0; 33]], &program_id).is_none());
+    }
+}
@@ -0,0 +1,97 @@
+//! The few JSON-RPC calls the map client needs, over the browser's `fetch`
+
+use base64::Engine;
+use serde_json::{json, Value};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Request, RequestInit, Response};
+use crate::pubkey::Pubkey;
+use crate::ClientError;
+
+/// Most keys one `getMultipleAccounts` call accepts
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+fn js_error(error: JsValue) -> ClientError {
+    ClientError::Rpc(error.as_string().unwrap_or_else(|| format!("{:?}", error)))
+}
+
+pub struct RpcClient {
+    url: String,
+}
+
+impl RpcClient {
+    pub fn new(url: &str) -> Self {
+        RpcClient { url: url.to_string() }
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value, ClientError> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let init = RequestInit::new();
+        init.set_method("POST");
+        init.set_body(&JsValue::from_str(&body.to_string()));
+        let request = Request::new_with_str_and_init(&self.url, &init).map_err(js_error)?;
+        request.headers().set("Content-Type", "application/json").map_err(js_error)?;
+
+        let window = web_sys::window().ok_or_else(|| ClientError::Rpc("no window to fetch from".to_string()))?;
+        let response: Response = JsFuture::from(window.fetch_with_request(&request))
+            .await
+            .map_err(js_error)?
+            .dyn_into()
+            .map_err(js_error)?;
+        let text = JsFuture::from(response.text().map_err(js_error)?).await.map_err(js_error)?;
+        let mut reply: Value = serde_json::from_str(&text.as_string().unwrap_or_default())
+            .map_err(|e| ClientError::Rpc(format!("{}: {}", method, e)))?;
+
+        if let Some(error) = reply.get("error") {
+            let message = error.get("message").and_then(Value::as_str).unwrap_or("unknown error");
+            return Err(ClientError::Rpc(format!("{}: {}", method, message)));
+        }
+        Ok(reply["result"].take())
+    }
+
+    /// Data of an account in a `getAccountInfo`/`getMultipleAccounts` value, None if it doesn't exist
+    fn account_data(account: &Value) -> Result<Option<Vec<u8>>, ClientError> {
+        if account.is_null() {
+            return Ok(None);
+        }
+        let encoded = account["data"][0]
+            .as_str()
+            .ok_or_else(|| ClientError::Rpc("account data isn't base64".to_string()))?;
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map(Some)
+            .map_err(|e| ClientError::Rpc(e.to_string()))
+    }
+
+    pub async fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+        let result = self
+            .call("getAccountInfo", json!([address.to_base58(), { "encoding": "base64", "commitment": "confirmed" }]))
+            .await?;
+        Self::account_data(&result["value"])
+    }
+
+    pub async fn get_multiple_account_data(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>, ClientError> {
+        let mut data = Vec::with_capacity(addresses.len());
+        for batch in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let keys: Vec<String> = batch.iter().map(Pubkey::to_base58).collect();
+            let result = self
+                .call("getMultipleAccounts", json!([keys, { "encoding": "base64", "commitment": "confirmed" }]))
+                .await?;
+            let accounts = result["value"]
+                .as_array()
+                .ok_or_else(|| ClientError::Rpc("getMultipleAccounts: no value".to_string()))?;
+            for account in accounts {
+                data.push(Self::account_data(account)?);
+            }
+        }
+        Ok(data)
+    }
+
+    pub async fn get_latest_blockhash(&self) -> Result<[u8; 32], ClientError> {
+        let result = self.call("getLatestBlockhash", json!([{ "commitment": "confirmed" }])).await?;
+        let blockhash = result["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| ClientError::Rpc("getLatestBlockhash: no blockhash".to_string()))?;
+        Pubkey::from_base58(blockhash).map(|hash| hash.0)
+    }
+}
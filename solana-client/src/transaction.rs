@@ -0,0 +1,146 @@
+//! Legacy transaction wire format, built unsigned for the wallet to sign
+
+use crate::pubkey::Pubkey;
+
+/// An account an instruction reads or writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl AccountMeta {
+    pub fn readonly(pubkey: Pubkey) -> Self {
+        AccountMeta { pubkey, is_signer: false, is_writable: false }
+    }
+
+    pub fn writable(pubkey: Pubkey) -> Self {
+        AccountMeta { pubkey, is_signer: false, is_writable: true }
+    }
+
+    /// Writable signer (the wallet paying for the accounts it creates)
+    pub fn signer(pubkey: Pubkey) -> Self {
+        AccountMeta { pubkey, is_signer: true, is_writable: true }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+/// Solana's compact-u16 length prefix (7 bits per byte, high bit = more)
+fn write_shortvec_len(out: &mut Vec<u8>, len: usize) {
+    let mut rest = len as u16;
+    loop {
+        let byte = (rest & 0x7f) as u8;
+        rest >>= 7;
+        if rest == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Every account of the message, payer first, then writable signers, readonly signers,
+/// writable non-signers and readonly non-signers (the order the runtime requires)
+fn ordered_accounts(payer: &Pubkey, instructions: &[Instruction]) -> Vec<AccountMeta> {
+    let mut accounts: Vec<AccountMeta> = vec![AccountMeta::signer(*payer)];
+    let metas = instructions.iter().flat_map(|ix| {
+        ix.accounts.iter().copied().chain(std::iter::once(AccountMeta::readonly(ix.program_id)))
+    });
+    for meta in metas {
+        match accounts.iter_mut().find(|known| known.pubkey == meta.pubkey) {
+            Some(known) => {
+                known.is_signer |= meta.is_signer;
+                known.is_writable |= meta.is_writable;
+            }
+            None => accounts.push(meta),
+        }
+    }
+    // Stable sort keeps the payer first and instruction order within each group
+    accounts.sort_by_key(|meta| (!meta.is_signer, !meta.is_writable));
+    accounts
+}
+
+/// Unsigned legacy transaction: zeroed signature slots followed by the compiled message
+///
+/// `web3.Transaction.from` reads the zeroed slots as missing signatures, so the wallet
+/// fills them in with `signTransaction`.
+pub fn unsigned_transaction(payer: &Pubkey, instructions: &[Instruction], recent_blockhash: &[u8; 32]) -> Vec<u8> {
+    let accounts = ordered_accounts(payer, instructions);
+    let index_of = |pubkey: &Pubkey| accounts.iter().position(|meta| meta.pubkey == *pubkey).unwrap() as u8;
+    let signers = accounts.iter().filter(|meta| meta.is_signer).count();
+    let readonly_signers = accounts.iter().filter(|meta| meta.is_signer && !meta.is_writable).count();
+    let readonly_unsigned = accounts.iter().filter(|meta| !meta.is_signer && !meta.is_writable).count();
+
+    let mut tx = Vec::new();
+    write_shortvec_len(&mut tx, signers);
+    tx.resize(tx.len() + 64 * signers, 0);
+
+    tx.extend([signers as u8, readonly_signers as u8, readonly_unsigned as u8]);
+    write_shortvec_len(&mut tx, accounts.len());
+    for meta in &accounts {
+        tx.extend(meta.pubkey.as_bytes());
+    }
+    tx.extend(recent_blockhash);
+    write_shortvec_len(&mut tx, instructions.len());
+    for ix in instructions {
+        tx.push(index_of(&ix.program_id));
+        write_shortvec_len(&mut tx, ix.accounts.len());
+        tx.extend(ix.accounts.iter().map(|meta| index_of(&meta.pubkey)));
+        write_shortvec_len(&mut tx, ix.data.len());
+        tx.extend(&ix.data);
+    }
+    tx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortvec_lengths() {
+        for (len, expected) in [(0, vec![0]), (127, vec![0x7f]), (128, vec![0x80, 0x01]), (9500, vec![0x9c, 0x4a])] {
+            let mut out = Vec::new();
+            write_shortvec_len(&mut out, len);
+            assert_eq!(out, expected, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_unsigned_transaction_layout() {
+        let payer = Pubkey([1; 32]);
+        let program = Pubkey([9; 32]);
+        let writable = Pubkey([2; 32]);
+        let readonly = Pubkey([3; 32]);
+        let ix = Instruction {
+            program_id: program,
+            accounts: vec![
+                AccountMeta::readonly(readonly),
+                AccountMeta::writable(writable),
+                AccountMeta::signer(payer),
+            ],
+            data: vec![42, 43],
+        };
+        let tx = unsigned_transaction(&payer, &[ix], &[5; 32]);
+
+        // One zeroed signature
+        assert_eq!(tx[0], 1);
+        assert!(tx[1..65].iter().all(|&b| b == 0));
+        // Header: 1 signer, 0 readonly signers, 2 readonly non-signers (account + program)
+        assert_eq!(&tx[65..68], &[1, 0, 2]);
+        // payer, writable, readonly, program
+        assert_eq!(tx[68], 4);
+        let keys: Vec<u8> = (0..4).map(|i| tx[69 + 32 * i]).collect();
+        assert_eq!(keys, vec![1, 2, 3, 9]);
+        let rest = &tx[69 + 128..];
+        assert_eq!(&rest[..32], &[5; 32]);
+        // One instruction: program index, account indices, data
+        assert_eq!(&rest[32..], &[1, 3, 3, 2, 1, 0, 2, 42, 43]);
+    }
+}